@@ -11,6 +11,8 @@
 //!                      [--compression 10] [--sample-rate 16000] [--channels 1]
 //!                      [--application voip]
 //!   trispr-opus concat --list concat.txt --output session.opus [--cwd DIR]
+//!   trispr-opus decode --input X.opus --output Y.wav [--sample-rate 16000]
+//!                      [--channels 1]
 //!   trispr-opus probe
 //!
 //! Exit code 0 = success, non-zero = failure. On success a JSON object is
@@ -45,6 +47,7 @@ fn run(args: &[String]) -> Result<String, String> {
     match subcommand {
         "encode" => cmd_encode(&opts),
         "concat" => cmd_concat(&opts),
+        "decode" => cmd_decode(&opts),
         "probe" => cmd_probe(),
         "-h" | "--help" | "help" => Ok(usage()),
         other => Err(format!("Unknown subcommand '{other}'.\n{}", usage())),
@@ -52,7 +55,7 @@ fn run(args: &[String]) -> Result<String, String> {
 }
 
 fn usage() -> String {
-    "trispr-opus <encode|concat|probe> [--key value ...]".to_string()
+    "trispr-opus <encode|concat|decode|probe> [--key value ...]".to_string()
 }
 
 /// Parse `--key value` pairs into a map. Flags without a following value are
@@ -255,6 +258,47 @@ fn cmd_concat(opts: &HashMap<String, String>) -> Result<String, String> {
     ))
 }
 
+/// Decode an OPUS recording back to 16-bit PCM WAV (core reads it with `hound`).
+fn cmd_decode(opts: &HashMap<String, String>) -> Result<String, String> {
+    let input = require(opts, "input")?;
+    let output = require(opts, "output")?;
+    if !Path::new(&input).exists() {
+        return Err(format!("Input file does not exist: {input}"));
+    }
+    let sample_rate = opt_u32(opts, "sample-rate", 16000);
+    let channels = opt_u32(opts, "channels", 1);
+
+    let ffmpeg = find_ffmpeg()?;
+    let mut cmd = Command::new(&ffmpeg);
+    no_window(&mut cmd);
+    cmd.arg("-i")
+        .arg(&input)
+        .arg("-y")
+        .arg("-c:a")
+        .arg("pcm_s16le")
+        .arg("-ar")
+        .arg(sample_rate.to_string())
+        .arg("-ac")
+        .arg(channels.to_string())
+        .arg(&output)
+        .arg("-loglevel")
+        .arg("error")
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped());
+
+    let out = cmd
+        .output()
+        .map_err(|e| format!("Failed to execute FFmpeg: {e}"))?;
+    if !out.status.success() {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        return Err(format!("FFmpeg decoding failed: {stderr}"));
+    }
+    Ok(format!(
+        "{{\"output_path\":\"{}\"}}",
+        json_escape(&output)
+    ))
+}
+
 fn cmd_probe() -> Result<String, String> {
     let ffmpeg = match find_ffmpeg() {
         Ok(path) => path,
@@ -0,0 +1,116 @@
+// Live caption window. Rust owns the window (live_captions.rs); this page
+// keeps the text of the last `captions_window_secs` seconds on screen.
+// Sources:
+//   - "transcribe:history-updated": system audio (loopback) history snapshots
+//   - "transcription:result": microphone dictations, if captions_include_mic
+
+const container = document.getElementById("container");
+const captionsEl = document.getElementById("captions");
+
+const TICK_MS = 500;
+
+let windowMs = 8000;
+let includeMic = false;
+
+// Captions currently on screen, oldest first: { id, text, at }
+let captions = [];
+// Last text seen per system-audio history entry id. Seeded from the current
+// history on load so existing entries are not replayed as captions.
+let seenHistory = null;
+
+function applySettings(payload) {
+  if (!payload) return;
+  const secs = Number(payload.captions_window_secs);
+  if (Number.isFinite(secs)) windowMs = Math.max(2, Math.min(60, secs)) * 1000;
+  includeMic = Boolean(payload.captions_include_mic);
+  container.dataset.position = payload.captions_position === "top" ? "top" : "bottom";
+  const style = document.body.style;
+  if (typeof payload.captions_font_family === "string" && payload.captions_font_family.trim()) {
+    style.setProperty("--caption-font-family", `"${payload.captions_font_family.trim()}", sans-serif`);
+  }
+  const size = Number(payload.captions_font_size);
+  if (Number.isFinite(size)) style.setProperty("--caption-font-size", `${size}px`);
+  const opacity = Number(payload.captions_background_opacity);
+  if (Number.isFinite(opacity)) {
+    style.setProperty("--caption-bg-opacity", String(Math.max(0, Math.min(1, opacity))));
+  }
+  render();
+}
+
+function upsertCaption(id, text) {
+  const trimmed = typeof text === "string" ? text.trim() : "";
+  if (!trimmed) return;
+  const at = Date.now();
+  const existing = captions.find((caption) => caption.id === id);
+  if (existing) {
+    existing.text = trimmed;
+    existing.at = at;
+  } else {
+    captions.push({ id, text: trimmed, at });
+  }
+}
+
+function handleHistorySnapshot(entries) {
+  if (!Array.isArray(entries)) return;
+  const next = new Map();
+  for (const entry of entries) {
+    if (entry && typeof entry.id === "string") next.set(entry.id, entry.text);
+  }
+  if (seenHistory) {
+    for (const [id, text] of next) {
+      if (seenHistory.get(id) !== text) upsertCaption(`sys:${id}`, text);
+    }
+    // Chunks merged into a larger entry disappear from the snapshot; drop
+    // them so the merged text is not shown twice.
+    captions = captions.filter(
+      (caption) => !caption.id.startsWith("sys:") || next.has(caption.id.slice(4))
+    );
+  }
+  seenHistory = next;
+  render();
+}
+
+function render() {
+  const cutoff = Date.now() - windowMs;
+  captions = captions.filter((caption) => caption.at >= cutoff);
+  captionsEl.replaceChildren(
+    ...captions.map((caption) => {
+      const line = document.createElement("div");
+      line.className = "caption-line";
+      line.textContent = caption.text;
+      return line;
+    })
+  );
+}
+
+setInterval(render, TICK_MS);
+
+const invoke = window.__TAURI__?.core?.invoke;
+if (invoke) {
+  invoke("get_settings")
+    .then(applySettings)
+    .catch(() => {});
+  invoke("get_transcribe_history")
+    .then((entries) => {
+      if (!seenHistory) handleHistorySnapshot(entries);
+    })
+    .catch(() => {
+      if (!seenHistory) seenHistory = new Map();
+    });
+}
+
+const listen = window.__TAURI__?.event?.listen;
+if (listen) {
+  listen("settings-changed", (event) => applySettings(event?.payload)).catch(() => {});
+
+  listen("transcribe:history-updated", (event) => handleHistorySnapshot(event?.payload)).catch(
+    () => {}
+  );
+
+  listen("transcription:result", (event) => {
+    const payload = event?.payload;
+    if (!includeMic || !payload) return;
+    upsertCaption(`mic:${payload.entry_id || payload.job_id || Date.now()}`, payload.text);
+    render();
+  }).catch(() => {});
+}
@@ -7,6 +7,8 @@
 const container = document.getElementById("container");
 const dot = document.getElementById("dot");
 const kitt = document.getElementById("kitt");
+const waveform = document.getElementById("waveform");
+const waveformCtx = waveform ? waveform.getContext("2d") : null;
 const refineIndicator = document.getElementById("refine-indicator");
 const ttsStopLayer = document.getElementById("tts-stop-layer");
 const ttsStopButton = document.getElementById("tts-stop-button");
@@ -17,6 +19,11 @@ let currentState = "idle";
 let opacityActive = 1.0;
 let opacityInactive = 0.25;
 let baseColor = "#ff3d2e";
+// Privacy mute, errors and cloud transcription use fixed colors so they never
+// blend with the user's preset.
+const MUTED_COLOR = "#a259ff";
+const ERROR_COLOR = "#ff1f3d";
+const CLOUD_COLOR = "#3d9bff";
 let currentStyle = "dot";
 let refiningActive = false;
 let refiningEnabled = true;
@@ -29,6 +36,7 @@ let ttsStopEnabled = true;
 let ttsStopShape = "compact";
 let ttsStopColor = "#4be0d4";
 let lastHeartbeatSentAt = 0;
+let moveMode = false;
 
 // KITT settings
 let kittMinWidth = 20;
@@ -39,12 +47,22 @@ let kittHeight = 20;
 let dotMinRadius = 8;
 let dotMaxRadius = 24;
 
+// Waveform: recent levels (0-255, oldest first), pushed by Rust over a binary channel
+const WAVEFORM_HISTORY_LEN = 64;
+let waveformLevels = new Uint8Array(0);
+
+function isBarStyle(style) {
+  return style === "kitt" || style === "waveform";
+}
+
 function updateOpacity() {
-  const kittArmed = currentStyle === "kitt" && currentState === "armed";
+  const kittArmed = isBarStyle(currentStyle) && currentState === "armed";
   const kittArmedOpacity = Math.max(opacityInactive, Math.min(opacityActive, 0.35));
-  const opacity = isActive ? opacityActive : (kittArmed ? kittArmedOpacity : opacityInactive);
+  const emphasized = isActive || currentState === "muted" || currentState === "error" || moveMode;
+  const opacity = emphasized ? opacityActive : (kittArmed ? kittArmedOpacity : opacityInactive);
   dot.style.opacity = opacity;
   kitt.style.opacity = opacity;
+  if (waveform) waveform.style.opacity = opacity;
 }
 
 function updateRefiningIndicator() {
@@ -104,14 +122,21 @@ function hexToRgb(hex) {
   return { r, g, b };
 }
 
+function effectiveColor() {
+  if (currentState === "muted") return MUTED_COLOR;
+  if (currentState === "error") return ERROR_COLOR;
+  if (currentState === "cloud-transcribing") return CLOUD_COLOR;
+  return baseColor;
+}
+
 function updateDotGradient() {
-  const rgb = hexToRgb(baseColor);
+  const rgb = hexToRgb(effectiveColor());
   if (!rgb) return;
   dot.style.background = `radial-gradient(circle, rgba(${rgb.r}, ${rgb.g}, ${rgb.b}, 1) 0%, rgba(${rgb.r}, ${rgb.g}, ${rgb.b}, 0.2) 60%, rgba(${rgb.r}, ${rgb.g}, ${rgb.b}, 0) 100%)`;
 }
 
 function updateKittGradient() {
-  const rgb = hexToRgb(baseColor);
+  const rgb = hexToRgb(effectiveColor());
   if (!rgb) return;
   const active = Math.max(0.05, Math.min(1, opacityActive));
   const inactive = Math.max(0.05, Math.min(1, opacityInactive));
@@ -128,6 +153,25 @@ function updateKittGradient() {
   kitt.style.boxShadow = `0 0 10px rgba(${rgb.r}, ${rgb.g}, ${rgb.b}, ${Math.max(0.18, active * 0.38)})`;
 }
 
+function drawWaveform() {
+  if (!waveform || !waveformCtx) return;
+  const { width, height } = waveform;
+  waveformCtx.clearRect(0, 0, width, height);
+  const rgb = hexToRgb(effectiveColor());
+  if (!rgb) return;
+  const slot = width / WAVEFORM_HISTORY_LEN;
+  const barWidth = Math.max(1, slot * 0.6);
+  const minBar = Math.min(2, height);
+  waveformCtx.fillStyle = `rgba(${rgb.r}, ${rgb.g}, ${rgb.b}, ${Math.max(0.05, Math.min(1, opacityActive))})`;
+  // Newest level on the right edge; history scrolls left.
+  const offset = WAVEFORM_HISTORY_LEN - waveformLevels.length;
+  for (let i = 0; i < waveformLevels.length; i += 1) {
+    const barHeight = Math.max(minBar, (waveformLevels[i] / 255) * height);
+    const x = (offset + i) * slot + (slot - barWidth) / 2;
+    waveformCtx.fillRect(x, (height - barHeight) / 2, barWidth, barHeight);
+  }
+}
+
 function updateRefiningAppearance() {
   if (!refineIndicator) return;
   const rgb = hexToRgb(refiningColor);
@@ -181,18 +225,23 @@ function resetOverlayGeometryToMinimum() {
   dot.style.width = `${dotMinRadius * 2}px`;
   dot.style.height = `${dotMinRadius * 2}px`;
   kitt.style.width = `${kittMinWidth}px`;
+  waveformLevels = new Uint8Array(0);
+  drawWaveform();
 }
 
 // --- Public API called from Rust via window.eval() ---
 
 window.setOverlayState = function(state) {
   currentState = state;
-  isActive = (state === "recording" || state === "transcribing");
+  isActive = (state === "recording" || state === "transcribing" || state === "cloud-transcribing");
   container.dataset.state = state;
   if (state !== "recording") {
     resetOverlayGeometryToMinimum();
   }
   updateOpacity();
+  updateDotGradient();
+  updateKittGradient();
+  drawWaveform();
   updateRefiningIndicator();
 };
 
@@ -200,6 +249,7 @@ window.setOverlayColor = function(color) {
   baseColor = color;
   updateDotGradient();
   updateKittGradient();
+  drawWaveform();
 };
 
 window.setOverlayOpacity = function(active, inactive) {
@@ -208,6 +258,7 @@ window.setOverlayOpacity = function(active, inactive) {
   updateOpacity();
   updateDotGradient();
   updateKittGradient();
+  drawWaveform();
 };
 
 window.setOverlayStyle = function(style) {
@@ -279,6 +330,13 @@ window.setKittDimensions = function(minWidth, maxWidth, height) {
   kitt.style.minWidth = kittMinWidth + "px";
   kitt.style.maxWidth = kittMaxWidth + "px";
   kitt.style.width = kittMinWidth + "px";
+  if (waveform) {
+    waveform.width = Math.round(kittMaxWidth);
+    waveform.height = Math.round(kittHeight);
+    waveform.style.width = kittMaxWidth + "px";
+    waveform.style.height = kittHeight + "px";
+    drawWaveform();
+  }
 };
 
 window.setDotDimensions = function(minRadius, maxRadius) {
@@ -297,13 +355,15 @@ window.setDotDimensions = function(minRadius, maxRadius) {
   dot.style.height = size + "px";
 };
 
-window.setOverlayLevel = function(level) {
+function applyOverlayLevel(level) {
   if (container.dataset.state !== "recording") {
     resetOverlayGeometryToMinimum();
     return;
   }
   const clamped = Math.max(0, Math.min(1, level));
-  if (currentStyle === "kitt") {
+  if (currentStyle === "waveform") {
+    drawWaveform();
+  } else if (currentStyle === "kitt") {
     const widthRaw = kittMinWidth + (kittMaxWidth - kittMinWidth) * clamped;
     const width = Math.max(kittMinWidth, Math.min(kittMaxWidth, widthRaw));
     kitt.style.width = width + "px";
@@ -313,8 +373,28 @@ window.setOverlayLevel = function(level) {
     dot.style.width = size + "px";
     dot.style.height = size + "px";
   }
+}
+
+// Fallback path (eval from Rust) used until the level channel is subscribed.
+window.setOverlayLevel = function(level) {
+  if (currentStyle === "waveform" && container.dataset.state === "recording") {
+    const clamped = Math.max(0, Math.min(1, Number(level) || 0));
+    const next = new Uint8Array(Math.min(WAVEFORM_HISTORY_LEN, waveformLevels.length + 1));
+    next.set(waveformLevels.subarray(waveformLevels.length - (next.length - 1)));
+    next[next.length - 1] = Math.round(clamped * 255);
+    waveformLevels = next;
+  }
+  applyOverlayLevel(level);
 };
 
+// Primary path: each frame is the level history, last byte = current level.
+function handleLevelFrame(data) {
+  const frame = data instanceof Uint8Array ? data : new Uint8Array(data);
+  waveformLevels = frame.slice(-WAVEFORM_HISTORY_LEN);
+  const current = frame.length > 0 ? frame[frame.length - 1] / 255 : 0;
+  applyOverlayLevel(current);
+}
+
 // --- Initialization ---
 
 updateOpacity();
@@ -343,8 +423,8 @@ if (window.__TAURI__?.event?.emit) {
 // Apply full settings payload from app settings object
 function applySettingsPayload(payload) {
   if (!payload) return;
-  const style = payload.overlay_style === "kitt" ? "kitt" : "dot";
-  const isKitt = style === "kitt";
+  const style = isBarStyle(payload.overlay_style) ? payload.overlay_style : "dot";
+  const isKitt = isBarStyle(style);
   const color = isKitt ? (payload.overlay_kitt_color || payload.overlay_color) : payload.overlay_color;
   const activeOpacity = isKitt
     ? (payload.overlay_kitt_opacity_active ?? payload.overlay_opacity_active)
@@ -389,12 +469,40 @@ function applySettingsPayload(payload) {
 
 // Load initial settings from backend
 const invoke = window.__TAURI__?.core?.invoke;
+
+// Move mode: Rust stops ignoring pointer input; dragging is driven from Rust
+// (overlay_drag_start/overlay_drag_end) so it can snap and save the position.
+window.setOverlayMoveMode = function(enabled) {
+  moveMode = Boolean(enabled);
+  container.dataset.move = moveMode ? "on" : "off";
+  updateOpacity();
+};
+
+if (invoke) {
+  container.addEventListener("pointerdown", (event) => {
+    if (!moveMode || event.button !== 0) return;
+    if (ttsStopButton && ttsStopButton.contains(event.target)) return;
+    event.preventDefault();
+    invoke("overlay_drag_start").catch(() => {});
+  });
+  window.addEventListener("pointerup", () => {
+    if (!moveMode) return;
+    invoke("overlay_drag_end").catch(() => {});
+  });
+}
 if (invoke) {
   invoke("get_settings")
     .then(applySettingsPayload)
     .catch(() => {});
 }
 
+const Channel = window.__TAURI__?.core?.Channel;
+if (invoke && Channel) {
+  const levelChannel = new Channel();
+  levelChannel.onmessage = handleLevelFrame;
+  invoke("overlay_subscribe_levels", { onLevels: levelChannel }).catch(() => {});
+}
+
 if (ttsStopButton && invoke) {
   ttsStopButton.addEventListener("click", () => {
     invoke("stop_tts").catch(() => {});
@@ -421,7 +529,7 @@ if (listen) {
   listen("overlay:settings", (event) => {
     const payload = event?.payload;
     if (!payload) return;
-    if (payload.style === "kitt" || payload.style === "dot") {
+    if (isBarStyle(payload.style) || payload.style === "dot") {
       applySettingsPayload({
         overlay_style: payload.style,
         overlay_color: payload.color,
@@ -0,0 +1,242 @@
+//! Activation words: with `activation_words_enabled`, a transcript is only
+//! kept when it contains one of `activation_words` ("computer", "hey
+//! assistant"). Entries match as whole words or whole phrases, ignoring case
+//! and punctuation. With `activation_words_strip` the matched phrase is cut
+//! from the text that gets pasted and stored.
+
+use std::borrow::Cow;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
+use crate::hallucination_filter::normalize_phrase;
+use crate::state::{AppState, Settings};
+
+/// Punctuation left dangling where the activation phrase was cut out.
+const STRIP_SEPARATORS: &[char] = &[',', ';', ':', '-', '–', '—', '.', '!', '?'];
+
+#[derive(Debug, Clone, PartialEq)]
+struct ActivationMatch {
+    phrase: String,
+    /// Byte range of the matched words in the original text.
+    start: usize,
+    end: usize,
+}
+
+/// First activation entry found in `text` as a run of whole words.
+fn find_activation(text: &str, activation_words: &[String]) -> Option<ActivationMatch> {
+    let tokens: Vec<(usize, usize, String)> = text
+        .split_whitespace()
+        .map(|word| {
+            let start = word.as_ptr() as usize - text.as_ptr() as usize;
+            (start, start + word.len(), normalize_phrase(word))
+        })
+        .filter(|(_, _, normalized)| !normalized.is_empty())
+        .collect();
+    activation_words.iter().find_map(|phrase| {
+        let wanted: Vec<String> = normalize_phrase(phrase)
+            .split_whitespace()
+            .map(String::from)
+            .collect();
+        if wanted.is_empty() || wanted.len() > tokens.len() {
+            return None;
+        }
+        tokens.windows(wanted.len()).find_map(|window| {
+            window
+                .iter()
+                .zip(&wanted)
+                .all(|((_, _, token), wanted)| token == wanted)
+                .then(|| ActivationMatch {
+                    phrase: phrase.trim().to_string(),
+                    start: window[0].0,
+                    end: window[window.len() - 1].1,
+                })
+        })
+    })
+}
+
+/// `text` without the matched phrase and the punctuation that joined it.
+fn strip_activation(text: &str, found: &ActivationMatch) -> String {
+    let before = text[..found.start].trim_end();
+    let after = text[found.end..]
+        .trim_start_matches(|c: char| c.is_whitespace() || STRIP_SEPARATORS.contains(&c));
+    if before.is_empty() {
+        let mut chars = after.chars();
+        let capitalize = text.trim_start().starts_with(char::is_uppercase);
+        return match chars.next() {
+            Some(first) if capitalize => first.to_uppercase().chain(chars).collect(),
+            _ => after.to_string(),
+        };
+    }
+    let before = before.trim_end_matches(|c: char| STRIP_SEPARATORS.contains(&c) && c != '.');
+    if after.is_empty() {
+        before.to_string()
+    } else {
+        format!("{} {}", before, after)
+    }
+}
+
+/// The transcript to keep under the activation-word settings, or `None`
+/// when it has to be dropped.
+pub(crate) fn gate<'a>(text: &'a str, settings: &Settings) -> Option<Cow<'a, str>> {
+    if !settings.activation_words_enabled || settings.activation_words.is_empty() {
+        return Some(Cow::Borrowed(text));
+    }
+    let found = find_activation(text, &settings.activation_words)?;
+    if settings.activation_words_strip {
+        Some(Cow::Owned(strip_activation(text, &found)))
+    } else {
+        Some(Cow::Borrowed(text))
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ActivationWords {
+    pub(crate) enabled: bool,
+    pub(crate) strip: bool,
+    pub(crate) words: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ActivationTest {
+    /// Whether the feature is on; the test runs as if it were.
+    pub(crate) enabled: bool,
+    pub(crate) matched: Option<String>,
+    /// What would be pasted; `None` when the transcript would be dropped.
+    pub(crate) output: Option<String>,
+}
+
+fn current_settings(app: &AppHandle) -> Settings {
+    app.state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+fn activation_words(settings: &Settings) -> ActivationWords {
+    ActivationWords {
+        enabled: settings.activation_words_enabled,
+        strip: settings.activation_words_strip,
+        words: settings.activation_words.clone(),
+    }
+}
+
+/// Apply `edit` to a copy of the settings and save it.
+async fn edit_words(
+    app: AppHandle,
+    edit: impl FnOnce(&mut Vec<String>) -> CommandResult<()> + Send + 'static,
+) -> CommandResult<ActivationWords> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut settings = current_settings(&app);
+        edit(&mut settings.activation_words)?;
+        crate::save_settings_inner(&app, &mut settings).with_code(ErrorCode::Storage)?;
+        Ok(activation_words(&settings))
+    })
+    .await
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("activation word task failed: {}", e),
+        )
+    })?
+}
+
+#[tauri::command]
+pub(crate) fn list_activation_words(app: AppHandle) -> ActivationWords {
+    activation_words(&current_settings(&app))
+}
+
+#[tauri::command]
+pub(crate) async fn add_activation_word(
+    app: AppHandle,
+    phrase: String,
+) -> CommandResult<ActivationWords> {
+    edit_words(app, move |words| {
+        let key = normalize_phrase(&phrase);
+        if key.is_empty() || words.iter().any(|word| normalize_phrase(word) == key) {
+            return Err(CommandError::new(
+                ErrorCode::InvalidInput,
+                format!("'{}' is empty or already listed", phrase.trim()),
+            ));
+        }
+        words.push(phrase.trim().to_string());
+        Ok(())
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn remove_activation_word(
+    app: AppHandle,
+    phrase: String,
+) -> CommandResult<ActivationWords> {
+    edit_words(app, move |words| {
+        let key = normalize_phrase(&phrase);
+        let before = words.len();
+        words.retain(|word| normalize_phrase(word) != key);
+        if words.len() == before {
+            return Err(CommandError::new(
+                ErrorCode::NotFound,
+                format!("'{}' is not listed", phrase.trim()),
+            ));
+        }
+        Ok(())
+    })
+    .await
+}
+
+/// Runs `text` through the activation-word check with the saved words and
+/// strip option.
+#[tauri::command]
+pub(crate) fn test_activation(app: AppHandle, text: String) -> ActivationTest {
+    let settings = current_settings(&app);
+    let found = find_activation(&text, &settings.activation_words);
+    let output = found.as_ref().map(|found| {
+        if settings.activation_words_strip {
+            strip_activation(&text, found)
+        } else {
+            text.clone()
+        }
+    });
+    ActivationTest {
+        enabled: settings.activation_words_enabled,
+        matched: found.map(|found| found.phrase),
+        output,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_phrases_and_strips_them() {
+        let words = vec!["computer".to_string(), "hey assistant".to_string()];
+        assert_eq!(find_activation("my computers are slow", &words), None);
+
+        let text = "Hey, Assistant: open the report.";
+        let found = find_activation(text, &words).unwrap();
+        assert_eq!(found.phrase, "hey assistant");
+        assert_eq!(strip_activation(text, &found), "Open the report.");
+
+        let text = "Okay computer, play music";
+        let found = find_activation(text, &words).unwrap();
+        assert_eq!(strip_activation(text, &found), "Okay play music");
+
+        let text = "Send it now. Computer.";
+        let found = find_activation(text, &words).unwrap();
+        assert_eq!(strip_activation(text, &found), "Send it now.");
+
+        let mut settings = Settings {
+            activation_words_enabled: true,
+            activation_words: words,
+            ..Settings::default()
+        };
+        assert!(gate("just talking", &settings).is_none());
+        assert_eq!(gate("computer stop", &settings).unwrap(), "computer stop");
+        settings.activation_words_strip = true;
+        assert_eq!(gate("computer stop", &settings).unwrap(), "stop");
+    }
+}
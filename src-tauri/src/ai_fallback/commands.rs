@@ -6,6 +6,7 @@ use super::provider::{
     ProviderFactory,
 };
 use super::{check_strict_local_mode, prepare_refinement, update_and_persist_settings};
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
 use crate::state::{normalize_ai_fallback_fields, AppState};
 use crate::{
     now_iso, terminate_managed_child_slot, update_runtime_diagnostics, update_startup_status,
@@ -24,7 +25,7 @@ pub(crate) async fn fetch_available_models(
     app: AppHandle,
     state: State<'_, AppState>,
     provider: String,
-) -> Result<Vec<String>, String> {
+) -> CommandResult<Vec<String>> {
     let provider_id = provider.trim().to_lowercase();
     if provider_id == "ollama" {
         let endpoint = {
@@ -32,14 +33,19 @@ pub(crate) async fn fetch_available_models(
                 .settings
                 .read()
                 .unwrap_or_else(|poisoned| poisoned.into_inner());
-            check_strict_local_mode(&settings)?;
+            check_strict_local_mode(&settings).with_code(ErrorCode::InvalidInput)?;
             settings.providers.ollama.endpoint.clone()
         };
         return tauri::async_runtime::spawn_blocking(move || {
             fetch_available_models_ollama_impl(endpoint)
         })
         .await
-        .map_err(|e| format!("Fetch available models task failed: {}", e))?;
+        .map_err(|e| {
+            CommandError::new(
+                ErrorCode::Internal,
+                format!("Fetch available models task failed: {}", e),
+            )
+        })?;
     }
 
     if provider_id == "lm_studio" || provider_id == "oobabooga" {
@@ -63,33 +69,43 @@ pub(crate) async fn fetch_available_models(
         return tauri::async_runtime::spawn_blocking(move || {
             let models = super::provider::list_openai_compat_models(&endpoint, &api_key);
             if models.is_empty() {
-                Err(format!(
-                    "No models found at {}. Is the server running?",
-                    endpoint
+                Err(CommandError::new(
+                    ErrorCode::Network,
+                    format!("No models found at {}. Is the server running?", endpoint),
                 ))
             } else {
                 Ok(models)
             }
         })
         .await
-        .map_err(|e| format!("Fetch available models task failed: {}", e))?;
+        .map_err(|e| {
+            CommandError::new(
+                ErrorCode::Internal,
+                format!("Fetch available models task failed: {}", e),
+            )
+        })?;
     }
 
     let app_handle = app.clone();
     tauri::async_runtime::spawn_blocking(move || fetch_available_models_impl(&app_handle, provider))
         .await
-        .map_err(|e| format!("Fetch available models task failed: {}", e))?
+        .map_err(|e| {
+            CommandError::new(
+                ErrorCode::Internal,
+                format!("Fetch available models task failed: {}", e),
+            )
+        })?
 }
 
-fn fetch_available_models_ollama_impl(endpoint: String) -> Result<Vec<String>, String> {
+fn fetch_available_models_ollama_impl(endpoint: String) -> CommandResult<Vec<String>> {
     let models = list_ollama_models(&endpoint);
     if models.is_empty() {
-        ping_ollama_quick(&endpoint).map_err(|e| e.to_string())?;
+        ping_ollama_quick(&endpoint)?;
     }
     Ok(models)
 }
 
-fn fetch_available_models_impl(app: &AppHandle, provider: String) -> Result<Vec<String>, String> {
+fn fetch_available_models_impl(app: &AppHandle, provider: String) -> CommandResult<Vec<String>> {
     let provider_id = provider.trim().to_lowercase();
 
     let from_settings = {
@@ -111,7 +127,10 @@ fn fetch_available_models_impl(app: &AppHandle, provider: String) -> Result<Vec<
 
     let defaults = default_models_for_provider(&provider_id);
     if defaults.is_empty() {
-        return Err(format!("Unknown AI provider: {}", provider));
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            format!("Unknown AI provider: {}", provider),
+        ));
     }
     Ok(defaults)
 }
@@ -119,24 +138,29 @@ fn fetch_available_models_impl(app: &AppHandle, provider: String) -> Result<Vec<
 #[tauri::command]
 pub(crate) async fn fetch_ollama_models_with_size(
     state: State<'_, AppState>,
-) -> Result<Vec<serde_json::Value>, String> {
+) -> CommandResult<Vec<serde_json::Value>> {
     let endpoint = {
         let settings = state
             .settings
             .read()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
-        check_strict_local_mode(&settings)?;
+        check_strict_local_mode(&settings).with_code(ErrorCode::InvalidInput)?;
         settings.providers.ollama.endpoint.clone()
     };
     tauri::async_runtime::spawn_blocking(move || fetch_ollama_models_with_size_impl(endpoint))
         .await
-        .map_err(|e| format!("Fetch Ollama models task failed: {}", e))?
+        .map_err(|e| {
+            CommandError::new(
+                ErrorCode::Internal,
+                format!("Fetch Ollama models task failed: {}", e),
+            )
+        })?
 }
 
-fn fetch_ollama_models_with_size_impl(endpoint: String) -> Result<Vec<serde_json::Value>, String> {
+fn fetch_ollama_models_with_size_impl(endpoint: String) -> CommandResult<Vec<serde_json::Value>> {
     let models = list_ollama_models_with_size(&endpoint);
     if models.is_empty() {
-        ping_ollama_quick(&endpoint).map_err(|e| e.to_string())?;
+        ping_ollama_quick(&endpoint)?;
     }
     Ok(models
         .into_iter()
@@ -149,7 +173,7 @@ pub(crate) async fn test_provider_connection(
     state: State<'_, AppState>,
     provider: String,
     api_key: String,
-) -> Result<serde_json::Value, String> {
+) -> CommandResult<serde_json::Value> {
     let provider_id = provider.trim().to_lowercase();
 
     if provider_id == "ollama" {
@@ -158,14 +182,19 @@ pub(crate) async fn test_provider_connection(
                 .settings
                 .read()
                 .unwrap_or_else(|poisoned| poisoned.into_inner());
-            check_strict_local_mode(&settings)?;
+            check_strict_local_mode(&settings).with_code(ErrorCode::InvalidInput)?;
             settings.providers.ollama.endpoint.clone()
         };
         return tauri::async_runtime::spawn_blocking(move || {
             test_provider_connection_ollama_impl(endpoint)
         })
         .await
-        .map_err(|e| format!("Test provider connection task failed: {}", e))?;
+        .map_err(|e| {
+            CommandError::new(
+                ErrorCode::Internal,
+                format!("Test provider connection task failed: {}", e),
+            )
+        })?;
     }
 
     if provider_id == "lm_studio" || provider_id == "oobabooga" {
@@ -196,9 +225,9 @@ pub(crate) async fn test_provider_connection(
         return tauri::async_runtime::spawn_blocking(move || {
             let models = super::provider::list_openai_compat_models(&endpoint, &effective_key);
             if models.is_empty() {
-                Err(format!(
-                    "{} not reachable at {}. Is the server running?",
-                    label, endpoint
+                Err(CommandError::new(
+                    ErrorCode::Network,
+                    format!("{} not reachable at {}. Is the server running?", label, endpoint),
                 ))
             } else {
                 Ok(serde_json::json!({
@@ -210,18 +239,28 @@ pub(crate) async fn test_provider_connection(
             }
         })
         .await
-        .map_err(|e| format!("Test provider connection task failed: {}", e))?;
+        .map_err(|e| {
+            CommandError::new(
+                ErrorCode::Internal,
+                format!("Test provider connection task failed: {}", e),
+            )
+        })?;
     }
 
     tauri::async_runtime::spawn_blocking(move || {
         test_provider_connection_impl(provider_id, api_key)
     })
     .await
-    .map_err(|e| format!("Test provider connection task failed: {}", e))?
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("Test provider connection task failed: {}", e),
+        )
+    })?
 }
 
-fn test_provider_connection_ollama_impl(endpoint: String) -> Result<serde_json::Value, String> {
-    ping_ollama(&endpoint).map_err(|e| e.to_string())?;
+fn test_provider_connection_ollama_impl(endpoint: String) -> CommandResult<serde_json::Value> {
+    ping_ollama(&endpoint)?;
     let models = list_ollama_models(&endpoint);
     Ok(serde_json::json!({
         "ok": true,
@@ -234,11 +273,9 @@ fn test_provider_connection_ollama_impl(endpoint: String) -> Result<serde_json::
 fn test_provider_connection_impl(
     provider_id: String,
     api_key: String,
-) -> Result<serde_json::Value, String> {
-    let provider_client = ProviderFactory::create(&provider_id).map_err(|e| e.to_string())?;
-    provider_client
-        .validate_api_key(api_key.trim())
-        .map_err(|e| e.to_string())?;
+) -> CommandResult<serde_json::Value> {
+    let provider_client = ProviderFactory::create(&provider_id)?;
+    provider_client.validate_api_key(api_key.trim())?;
 
     Ok(serde_json::json!({
       "ok": true,
@@ -253,19 +290,19 @@ pub(crate) fn save_provider_api_key(
     state: State<'_, AppState>,
     provider: String,
     api_key: String,
-) -> Result<serde_json::Value, String> {
+) -> CommandResult<serde_json::Value> {
     let provider_id = provider.trim().to_lowercase();
-    let provider_client = ProviderFactory::create(&provider_id).map_err(|e| e.to_string())?;
-    provider_client
-        .validate_api_key(api_key.trim())
-        .map_err(|e| e.to_string())?;
-    ai_fallback_keyring::store_api_key(&app, &provider_id, api_key.trim())?;
+    let provider_client = ProviderFactory::create(&provider_id)?;
+    provider_client.validate_api_key(api_key.trim())?;
+    ai_fallback_keyring::store_api_key(&app, &provider_id, api_key.trim())
+        .with_code(ErrorCode::Credentials)?;
 
     update_and_persist_settings(&app, state.inner(), |settings| {
         settings.providers.set_api_key_stored(&provider_id, true)?;
         normalize_ai_fallback_fields(settings);
         Ok(())
-    })?;
+    })
+    .with_code(ErrorCode::Storage)?;
 
     Ok(serde_json::json!({
       "status": "success",
@@ -280,15 +317,16 @@ pub(crate) fn clear_provider_api_key(
     app: AppHandle,
     state: State<'_, AppState>,
     provider: String,
-) -> Result<serde_json::Value, String> {
+) -> CommandResult<serde_json::Value> {
     let provider_id = provider.trim().to_lowercase();
-    ai_fallback_keyring::clear_api_key(&app, &provider_id)?;
+    ai_fallback_keyring::clear_api_key(&app, &provider_id).with_code(ErrorCode::Credentials)?;
 
     update_and_persist_settings(&app, state.inner(), |settings| {
         settings.providers.set_api_key_stored(&provider_id, false)?;
         normalize_ai_fallback_fields(settings);
         Ok(())
-    })?;
+    })
+    .with_code(ErrorCode::Storage)?;
 
     Ok(serde_json::json!({
       "status": "success",
@@ -304,20 +342,26 @@ pub(crate) fn verify_provider_auth(
     state: State<'_, AppState>,
     provider: String,
     method: Option<String>,
-) -> Result<serde_json::Value, String> {
+) -> CommandResult<serde_json::Value> {
     let provider_id = provider.trim().to_lowercase();
     let method_id = method.as_deref().unwrap_or("api_key").trim().to_lowercase();
 
     if provider_id == "ollama" {
-        return Err("Ollama does not require cloud credential verification.".to_string());
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "Ollama does not require cloud credential verification.",
+        ));
     }
     if !matches!(provider_id.as_str(), "claude" | "openai" | "gemini") {
-        return Err(format!("Unknown AI provider: {}", provider));
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            format!("Unknown AI provider: {}", provider),
+        ));
     }
     if method_id != "api_key" && method_id != "oauth" {
-        return Err(format!(
-            "Unsupported auth verification method '{}'.",
-            method_id
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            format!("Unsupported auth verification method '{}'.", method_id),
         ));
     }
 
@@ -326,33 +370,38 @@ pub(crate) fn verify_provider_auth(
             settings.providers.lock_auth(&provider_id)?;
             normalize_ai_fallback_fields(settings);
             Ok(())
-        })?;
-        return Err(
-            "OAuth verification is not supported yet. Use API key verification.".to_string(),
-        );
+        })
+        .with_code(ErrorCode::Storage)?;
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "OAuth verification is not supported yet. Use API key verification.",
+        ));
     }
 
-    let stored_key = ai_fallback_keyring::read_api_key(&app, &provider_id)?;
+    let stored_key =
+        ai_fallback_keyring::read_api_key(&app, &provider_id).with_code(ErrorCode::Credentials)?;
     let Some(api_key) = stored_key else {
         update_and_persist_settings(&app, state.inner(), |settings| {
             settings.providers.lock_auth(&provider_id)?;
             normalize_ai_fallback_fields(settings);
             Ok(())
-        })?;
-        return Err(format!(
-            "No stored API key found for provider '{}'.",
-            provider_id
+        })
+        .with_code(ErrorCode::Storage)?;
+        return Err(CommandError::new(
+            ErrorCode::Credentials,
+            format!("No stored API key found for provider '{}'.", provider_id),
         ));
     };
 
-    let provider_client = ProviderFactory::create(&provider_id).map_err(|e| e.to_string())?;
+    let provider_client = ProviderFactory::create(&provider_id)?;
     if let Err(error) = provider_client.validate_api_key(api_key.trim()) {
         update_and_persist_settings(&app, state.inner(), |settings| {
             settings.providers.lock_auth(&provider_id)?;
             normalize_ai_fallback_fields(settings);
             Ok(())
-        })?;
-        return Err(error.to_string());
+        })
+        .with_code(ErrorCode::Storage)?;
+        return Err(error.into());
     }
 
     let verified_at = now_iso();
@@ -364,7 +413,8 @@ pub(crate) fn verify_provider_auth(
         )?;
         normalize_ai_fallback_fields(settings);
         Ok(())
-    })?;
+    })
+    .with_code(ErrorCode::Storage)?;
 
     Ok(serde_json::json!({
       "ok": true,
@@ -380,13 +430,19 @@ pub(crate) fn save_ollama_endpoint(
     app: AppHandle,
     state: State<'_, AppState>,
     endpoint: String,
-) -> Result<serde_json::Value, String> {
+) -> CommandResult<serde_json::Value> {
     let trimmed = endpoint.trim().to_string();
     if trimmed.is_empty() {
-        return Err("Endpoint cannot be empty.".to_string());
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "Endpoint cannot be empty.",
+        ));
     }
     if is_ssrf_target(&trimmed) {
-        return Err("This endpoint address is not allowed (SSRF protection).".to_string());
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "This endpoint address is not allowed (SSRF protection).",
+        ));
     }
     {
         let settings = state
@@ -394,16 +450,17 @@ pub(crate) fn save_ollama_endpoint(
             .read()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
         if settings.ai_fallback.strict_local_mode && !is_local_ollama_endpoint(&trimmed) {
-            return Err(
-                "Strict local mode is enabled. Only localhost/127.0.0.1:11434 is allowed."
-                    .to_string(),
-            );
+            return Err(CommandError::new(
+                ErrorCode::InvalidInput,
+                "Strict local mode is enabled. Only localhost/127.0.0.1:11434 is allowed.",
+            ));
         }
     }
     update_and_persist_settings(&app, state.inner(), |settings| {
         settings.providers.ollama.endpoint = trimmed.clone();
         Ok(())
-    })?;
+    })
+    .with_code(ErrorCode::Storage)?;
     Ok(serde_json::json!({
         "status": "success",
         "endpoint": trimmed,
@@ -415,14 +472,14 @@ pub(crate) async fn refine_transcript(
     app: AppHandle,
     state: State<'_, AppState>,
     transcript: String,
-) -> Result<serde_json::Value, String> {
+) -> CommandResult<serde_json::Value> {
     let settings_snapshot = state
         .settings
         .read()
         .unwrap_or_else(|poisoned| poisoned.into_inner())
         .clone();
 
-    let setup = prepare_refinement(&app, &settings_snapshot)?;
+    let setup = prepare_refinement(&app, &settings_snapshot).with_code(ErrorCode::InvalidInput)?;
 
     if setup.repaired {
         let model = setup.model.clone();
@@ -432,7 +489,8 @@ pub(crate) async fn refine_transcript(
             settings.postproc_llm_model = model;
             normalize_ai_fallback_fields(settings);
             Ok(())
-        })?;
+        })
+        .with_code(ErrorCode::Storage)?;
     }
 
     let app_clone = app.clone();
@@ -442,21 +500,26 @@ pub(crate) async fn refine_transcript(
             .refine_transcript(&transcript, &setup.model, &setup.options, &setup.api_key)
     })
     .await
-    .map_err(|e| format!("refine_transcript task failed: {}", e))?;
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("refine_transcript task failed: {}", e),
+        )
+    })?;
 
     if let Err(AIError::Timeout | AIError::OllamaNotRunning) = &result {
         let _ = app_clone.emit("ai_fallback:health_degraded", ());
     }
 
-    let result = result.map_err(|e| e.to_string())?;
-    serde_json::to_value(&result).map_err(|e| e.to_string())
+    let result = result?;
+    serde_json::to_value(&result).with_code(ErrorCode::Internal)
 }
 
 #[tauri::command]
 pub(crate) async fn ping_refinement_model(
     app: AppHandle,
     state: State<'_, AppState>,
-) -> Result<bool, String> {
+) -> CommandResult<bool> {
     let settings_snapshot = state
         .settings
         .read()
@@ -485,7 +548,12 @@ pub(crate) async fn ping_refinement_model(
             .refine_transcript(ping_text, &setup.model, &setup.options, &setup.api_key)
     })
     .await
-    .map_err(|e| format!("ping_refinement_model task failed: {}", e))?;
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("ping_refinement_model task failed: {}", e),
+        )
+    })?;
 
     Ok(result.is_ok())
 }
@@ -495,12 +563,12 @@ pub(crate) fn pull_ollama_model(
     app: AppHandle,
     state: State<'_, AppState>,
     model: String,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     use super::provider::{
         precheck_ollama_registry_model_tag, pull_ollama_model_inner, validate_ollama_model_name,
     };
 
-    validate_ollama_model_name(&model)?;
+    validate_ollama_model_name(&model).with_code(ErrorCode::InvalidInput)?;
 
     {
         let mut pulls = state
@@ -508,7 +576,10 @@ pub(crate) fn pull_ollama_model(
             .lock()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
         if pulls.contains(&model) {
-            return Err(format!("Pull already in progress for '{}'", model));
+            return Err(CommandError::new(
+                ErrorCode::InvalidInput,
+                format!("Pull already in progress for '{}'", model),
+            ));
         }
         pulls.insert(model.clone());
     }
@@ -524,7 +595,7 @@ pub(crate) fn pull_ollama_model(
                 .lock()
                 .unwrap_or_else(|poisoned| poisoned.into_inner());
             pulls.remove(&model);
-            return Err(error);
+            return Err(CommandError::new(ErrorCode::InvalidInput, error));
         }
         settings.providers.ollama.endpoint.clone()
     };
@@ -535,7 +606,7 @@ pub(crate) fn pull_ollama_model(
             .lock()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
         pulls.remove(&model);
-        return Err(error);
+        return Err(CommandError::new(ErrorCode::Network, error));
     }
 
     struct PullGuard {
@@ -567,25 +638,30 @@ pub(crate) fn pull_ollama_model(
 pub(crate) async fn delete_ollama_model(
     state: State<'_, AppState>,
     model: String,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     use super::provider::validate_ollama_model_name;
 
-    validate_ollama_model_name(&model)?;
+    validate_ollama_model_name(&model).with_code(ErrorCode::InvalidInput)?;
     let endpoint = {
         let settings = state
             .settings
             .read()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
-        check_strict_local_mode(&settings)?;
+        check_strict_local_mode(&settings).with_code(ErrorCode::InvalidInput)?;
         settings.providers.ollama.endpoint.clone()
     };
 
     tauri::async_runtime::spawn_blocking(move || delete_ollama_model_impl(endpoint, model))
         .await
-        .map_err(|e| format!("Delete Ollama model task failed: {}", e))?
+        .map_err(|e| {
+            CommandError::new(
+                ErrorCode::Internal,
+                format!("Delete Ollama model task failed: {}", e),
+            )
+        })?
 }
 
-fn delete_ollama_model_impl(endpoint: String, model: String) -> Result<(), String> {
+fn delete_ollama_model_impl(endpoint: String, model: String) -> CommandResult<()> {
     use super::provider::ollama_endpoint_candidates;
 
     let body = serde_json::json!({ "model": model.clone() });
@@ -605,19 +681,30 @@ fn delete_ollama_model_impl(endpoint: String, model: String) -> Result<(), Strin
         match request.send_json(body.clone()) {
             Ok(_) => return Ok(()),
             Err(ureq::Error::Status(404, _)) => {
-                return Err(format!("Model '{}' not found in Ollama", model));
+                return Err(CommandError::new(
+                    ErrorCode::NotFound,
+                    format!("Model '{}' not found in Ollama", model),
+                ));
             }
             Err(ureq::Error::Transport(transport)) => {
                 last_transport_error = Some(transport.to_string());
                 continue;
             }
-            Err(error) => return Err(format!("Failed to delete model: {}", error)),
+            Err(error) => {
+                return Err(CommandError::new(
+                    ErrorCode::Network,
+                    format!("Failed to delete model: {}", error),
+                ))
+            }
         }
     }
 
-    Err(format!(
-        "Failed to delete model: {}",
-        last_transport_error.unwrap_or_else(|| "unable to reach Ollama endpoint".to_string())
+    Err(CommandError::new(
+        ErrorCode::Network,
+        format!(
+            "Failed to delete model: {}",
+            last_transport_error.unwrap_or_else(|| "unable to reach Ollama endpoint".to_string())
+        ),
     ))
 }
 
@@ -625,28 +712,30 @@ fn delete_ollama_model_impl(endpoint: String, model: String) -> Result<(), Strin
 pub(crate) async fn get_ollama_model_info(
     state: State<'_, AppState>,
     model: String,
-) -> Result<serde_json::Value, String> {
+) -> CommandResult<serde_json::Value> {
     use super::provider::validate_ollama_model_name;
 
-    validate_ollama_model_name(&model)?;
+    validate_ollama_model_name(&model).with_code(ErrorCode::InvalidInput)?;
     let endpoint = {
         let settings = state
             .settings
             .read()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
-        check_strict_local_mode(&settings)?;
+        check_strict_local_mode(&settings).with_code(ErrorCode::InvalidInput)?;
         settings.providers.ollama.endpoint.clone()
     };
 
     tauri::async_runtime::spawn_blocking(move || get_ollama_model_info_impl(endpoint, model))
         .await
-        .map_err(|e| format!("Get Ollama model info task failed: {}", e))?
+        .map_err(|e| {
+            CommandError::new(
+                ErrorCode::Internal,
+                format!("Get Ollama model info task failed: {}", e),
+            )
+        })?
 }
 
-fn get_ollama_model_info_impl(
-    endpoint: String,
-    model: String,
-) -> Result<serde_json::Value, String> {
+fn get_ollama_model_info_impl(endpoint: String, model: String) -> CommandResult<serde_json::Value> {
     use super::provider::ollama_endpoint_candidates;
 
     let body = serde_json::json!({ "model": model });
@@ -669,36 +758,53 @@ fn get_ollama_model_info_impl(
                 last_transport_error = Some(transport.to_string());
                 continue;
             }
-            Err(error) => return Err(format!("Failed to get model info: {}", error)),
+            Err(error) => {
+                return Err(CommandError::new(
+                    ErrorCode::Network,
+                    format!("Failed to get model info: {}", error),
+                ))
+            }
         };
 
-        return response
-            .into_json::<serde_json::Value>()
-            .map_err(|e| format!("Failed to parse response: {}", e));
+        return response.into_json::<serde_json::Value>().map_err(|e| {
+            CommandError::new(
+                ErrorCode::Network,
+                format!("Failed to parse response: {}", e),
+            )
+        });
     }
 
-    Err(format!(
-        "Failed to get model info: {}",
-        last_transport_error.unwrap_or_else(|| "unable to reach Ollama endpoint".to_string())
+    Err(CommandError::new(
+        ErrorCode::Network,
+        format!(
+            "Failed to get model info: {}",
+            last_transport_error.unwrap_or_else(|| "unable to reach Ollama endpoint".to_string())
+        ),
     ))
 }
 
 #[tauri::command]
-pub(crate) async fn unload_ollama_model(app: AppHandle, model: String) -> Result<(), String> {
+pub(crate) async fn unload_ollama_model(app: AppHandle, model: String) -> CommandResult<()> {
     tauri::async_runtime::spawn_blocking(move || unload_configured_ollama_model(&app, &model))
         .await
-        .map_err(|e| format!("Unload Ollama model task failed: {}", e))?
+        .map_err(|e| {
+            CommandError::new(
+                ErrorCode::Internal,
+                format!("Unload Ollama model task failed: {}", e),
+            )
+        })?
 }
 
-fn unload_configured_ollama_model(app: &AppHandle, model: &str) -> Result<(), String> {
+fn unload_configured_ollama_model(app: &AppHandle, model: &str) -> CommandResult<()> {
     let state = app.state::<AppState>();
     let settings = state
         .settings
         .read()
         .unwrap_or_else(|poisoned| poisoned.into_inner())
         .clone();
-    check_strict_local_mode(&settings)?;
+    check_strict_local_mode(&settings).with_code(ErrorCode::InvalidInput)?;
     unload_ollama_model_impl(&settings.providers.ollama.endpoint, model)
+        .with_code(ErrorCode::InvalidInput)
 }
 
 pub(crate) fn unload_ollama_model_impl(endpoint: &str, model: &str) -> Result<(), String> {
@@ -780,7 +886,7 @@ pub(crate) fn warmup_ollama_model_impl(endpoint: &str, model: &str) -> Result<()
 }
 
 #[tauri::command]
-pub(crate) fn purge_gpu_memory(state: State<'_, AppState>) -> Result<(), String> {
+pub(crate) fn purge_gpu_memory(state: State<'_, AppState>) -> CommandResult<()> {
     let settings = state
         .settings
         .read()
@@ -806,7 +912,7 @@ pub(crate) fn purge_gpu_memory(state: State<'_, AppState>) -> Result<(), String>
 }
 
 #[tauri::command]
-pub(crate) async fn stop_ollama_runtime(app: AppHandle) -> Result<(), String> {
+pub(crate) async fn stop_ollama_runtime(app: AppHandle) -> CommandResult<()> {
     tauri::async_runtime::spawn_blocking(move || {
         let state = app.state::<AppState>();
         terminate_managed_child_slot("managed Ollama runtime", &state.managed_ollama_child);
@@ -845,7 +951,7 @@ pub(crate) async fn stop_ollama_runtime(app: AppHandle) -> Result<(), String> {
 }
 
 #[tauri::command]
-pub(crate) fn install_lm_studio() -> Result<(), String> {
+pub(crate) fn install_lm_studio() -> CommandResult<()> {
     #[cfg(target_os = "windows")]
     {
         use std::os::windows::process::CommandExt;
@@ -864,11 +970,19 @@ pub(crate) fn install_lm_studio() -> Result<(), String> {
             ])
             .creation_flags(CREATE_NO_WINDOW)
             .spawn()
-            .map_err(|e| format!("Failed to launch LM Studio installer: {e}"))?;
+            .map_err(|e| {
+                CommandError::new(
+                    ErrorCode::Internal,
+                    format!("Failed to launch LM Studio installer: {e}"),
+                )
+            })?;
         Ok(())
     }
     #[cfg(not(target_os = "windows"))]
     {
-        Err("LM Studio installer helper is only supported on Windows.".to_string())
+        Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "LM Studio installer helper is only supported on Windows.",
+        ))
     }
 }
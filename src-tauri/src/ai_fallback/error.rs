@@ -1,3 +1,4 @@
+use crate::errors::{CommandError, ErrorCode};
 use std::fmt;
 
 #[derive(Debug, Clone)]
@@ -37,3 +38,21 @@ impl fmt::Display for AIError {
 }
 
 impl std::error::Error for AIError {}
+
+impl AIError {
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            AIError::UnknownProvider(_) => ErrorCode::InvalidInput,
+            AIError::MissingApiKey(_) | AIError::InvalidApiKey(_) => ErrorCode::Credentials,
+            AIError::OllamaNotRunning | AIError::NetworkError(_) | AIError::Timeout => {
+                ErrorCode::Network
+            }
+        }
+    }
+}
+
+impl From<AIError> for CommandError {
+    fn from(error: AIError) -> Self {
+        CommandError::new(error.code(), error.to_string())
+    }
+}
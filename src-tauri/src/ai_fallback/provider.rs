@@ -1124,7 +1124,7 @@ fn suspicious_refinement_shape(original: &str, refined: &str) -> bool {
 /// Strip `<think>…</think>` blocks that reasoning models (Qwen3, DeepSeek-R1,
 /// etc.) inject before the actual answer.  Works for both single-line and
 /// multi-line think blocks.  Returns input unchanged if no `<think>` tag found.
-fn strip_thinking_tags(text: &str) -> String {
+pub fn strip_thinking_tags(text: &str) -> String {
     if !text.contains("<think>") {
         return text.to_string();
     }
@@ -1148,7 +1148,10 @@ fn sanitize_ollama_refinement_output(
     } else {
         collapse_excessive_blank_lines(normalized.trim())
     };
-    if suspicious_refinement_shape(original, &collapsed) {
+    // Summaries are meant to shrink and reword; only check the shape of
+    // transcript-preserving profiles.
+    let shape_checked = !matches!(profile, "summary" | "action_items");
+    if shape_checked && suspicious_refinement_shape(original, &collapsed) {
         warn!(
             "Ignoring suspicious Ollama refinement output and keeping original transcript (orig_len={}, refined_len={})",
             original.len(),
@@ -0,0 +1,234 @@
+//! Per-application settings overrides keyed by the foreground process name.
+//!
+//! When a dictation finishes, the foreground process (e.g. `code.exe`,
+//! `outlook.exe`) is looked up in `Settings::app_overrides` and the matching
+//! entry is laid over a copy of the settings used for that transcription only.
+
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+use crate::state::Settings;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct AppOverride {
+    /// Executable name, matched case-insensitively with or without ".exe".
+    pub(crate) process_name: String,
+    /// ASR language; "auto" unpins the language for this app.
+    pub(crate) language_mode: Option<String>,
+    pub(crate) postproc_enabled: Option<bool>,
+    pub(crate) auto_paste_enabled: Option<bool>,
+    /// "paste" | "clipboard" | "type" | "inject"
+    pub(crate) output_mode: Option<String>,
+}
+
+impl AppOverride {
+    fn matches(&self, process_name: &str) -> bool {
+        process_name_matches(process_name, &self.process_name)
+    }
+
+    fn apply(&self, settings: &mut Settings) {
+        if let Some(language) = &self.language_mode {
+            settings.language_mode = language.clone();
+            settings.language_pinned = language != "auto";
+            settings.postproc_language = crate::state::derive_postproc_language_from_asr(
+                &settings.language_mode,
+                settings.language_pinned,
+            );
+        }
+        if let Some(enabled) = self.postproc_enabled {
+            settings.postproc_enabled = enabled;
+        }
+        if let Some(enabled) = self.auto_paste_enabled {
+            settings.auto_paste_enabled = enabled;
+        }
+        if let Some(mode) = &self.output_mode {
+            settings.output_mode = mode.clone();
+        }
+    }
+}
+
+pub(crate) fn normalize_output_mode(mode: &str) -> String {
+    match mode.trim().to_ascii_lowercase().as_str() {
+        "clipboard" => "clipboard".to_string(),
+        "type" => "type".to_string(),
+        // Accessibility injection exists on Windows and macOS only.
+        "inject" if cfg!(any(target_os = "windows", target_os = "macos")) => "inject".to_string(),
+        _ => "paste".to_string(),
+    }
+}
+
+/// Lowercases process names, drops nameless entries and invalid values.
+pub(crate) fn normalize_app_overrides(overrides: &mut Vec<AppOverride>) {
+    const LANGUAGES: [&str; 16] = [
+        "auto", "en", "de", "fr", "es", "it", "pt", "nl", "pl", "ru", "ja", "ko", "zh", "ar", "tr",
+        "hi",
+    ];
+    for entry in overrides.iter_mut() {
+        entry.process_name = entry.process_name.trim().to_lowercase();
+        entry.language_mode = entry
+            .language_mode
+            .as_deref()
+            .map(|language| language.trim().to_lowercase())
+            .filter(|language| LANGUAGES.contains(&language.as_str()));
+        entry.output_mode = entry.output_mode.as_deref().map(normalize_output_mode);
+    }
+    overrides.retain(|entry| !entry.process_name.is_empty());
+}
+
+fn resolve<'a>(settings: &'a Settings, process_name: Option<&str>) -> Cow<'a, Settings> {
+    let found = process_name.and_then(|name| {
+        settings
+            .app_overrides
+            .iter()
+            .find(|entry| entry.matches(name))
+    });
+    match found {
+        Some(entry) => {
+            let mut overridden = settings.clone();
+            entry.apply(&mut overridden);
+            Cow::Owned(overridden)
+        }
+        None => Cow::Borrowed(settings),
+    }
+}
+
+/// The foreground app, looked up once per dictation so transcription and
+/// output use the same override; `None` without configured overrides.
+pub(crate) fn foreground_app(settings: &Settings) -> Option<String> {
+    if settings.app_overrides.is_empty() {
+        return None;
+    }
+    foreground_process_name()
+}
+
+/// `settings` with the override for `app` (from `foreground_app`) applied.
+pub(crate) fn for_app<'a>(settings: &'a Settings, app: Option<&str>) -> Cow<'a, Settings> {
+    resolve(settings, app)
+}
+
+/// Lowercased executable name of the foreground window's process, or `None`
+/// when it cannot be determined or is Trispr Flow itself.
+#[cfg(target_os = "windows")]
+pub(crate) fn foreground_process_name() -> Option<String> {
+    use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
+
+    // SAFETY: plain Win32 queries on the foreground window handle.
+    let pid = unsafe {
+        let hwnd = GetForegroundWindow();
+        if hwnd.0.is_null() {
+            return None;
+        }
+        let mut pid: u32 = 0;
+        let _tid = GetWindowThreadProcessId(hwnd, Some(&mut pid));
+        pid
+    };
+    if pid == 0 || pid == std::process::id() {
+        return None;
+    }
+    process_name(pid)
+}
+
+#[cfg(not(target_os = "windows"))]
+pub(crate) fn foreground_process_name() -> Option<String> {
+    None
+}
+
+/// Lowercased executable name of process `pid`.
+#[cfg(target_os = "windows")]
+pub(crate) fn process_name(pid: u32) -> Option<String> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+        PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    // SAFETY: the process handle is checked before use and closed afterwards;
+    // the image name buffer length is passed in and updated by the call.
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if handle.is_null() {
+            return None;
+        }
+        let mut buffer = [0u16; 1024];
+        let mut len = buffer.len() as u32;
+        let ok =
+            QueryFullProcessImageNameW(handle, PROCESS_NAME_WIN32, buffer.as_mut_ptr(), &mut len);
+        CloseHandle(handle);
+        if ok == 0 {
+            return None;
+        }
+        let path = String::from_utf16_lossy(&buffer[..len as usize]);
+        std::path::Path::new(&path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_lowercase())
+    }
+}
+
+/// Case-insensitive executable match, with or without ".exe" on either side.
+pub(crate) fn process_name_matches(process_name: &str, wanted: &str) -> bool {
+    !wanted.is_empty()
+        && (process_name == wanted
+            || process_name.strip_suffix(".exe") == Some(wanted)
+            || wanted.strip_suffix(".exe") == Some(process_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings_with(overrides: Vec<AppOverride>) -> Settings {
+        let mut settings = Settings {
+            app_overrides: overrides,
+            ..Settings::default()
+        };
+        normalize_app_overrides(&mut settings.app_overrides);
+        settings
+    }
+
+    #[test]
+    fn override_matches_process_name_with_or_without_exe() {
+        let settings = settings_with(vec![AppOverride {
+            process_name: " Code.EXE ".to_string(),
+            language_mode: Some("EN".to_string()),
+            postproc_enabled: Some(false),
+            output_mode: Some("type".to_string()),
+            ..AppOverride::default()
+        }]);
+        let resolved = resolve(&settings, Some("code.exe"));
+        assert_eq!(resolved.language_mode, "en");
+        assert!(resolved.language_pinned);
+        assert!(!resolved.postproc_enabled);
+        assert_eq!(resolved.output_mode, "type");
+        assert_eq!(resolved.auto_paste_enabled, settings.auto_paste_enabled);
+
+        assert!(matches!(resolve(&settings, Some("code")), Cow::Owned(_)));
+        assert!(matches!(
+            resolve(&settings, Some("codex.exe")),
+            Cow::Borrowed(_)
+        ));
+        assert!(matches!(resolve(&settings, None), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn normalization_drops_invalid_values() {
+        let settings = settings_with(vec![
+            AppOverride {
+                process_name: "  ".to_string(),
+                ..AppOverride::default()
+            },
+            AppOverride {
+                process_name: "outlook.exe".to_string(),
+                language_mode: Some("klingon".to_string()),
+                output_mode: Some("fax".to_string()),
+                ..AppOverride::default()
+            },
+        ]);
+        assert_eq!(settings.app_overrides.len(), 1);
+        assert_eq!(settings.app_overrides[0].language_mode, None);
+        assert_eq!(
+            settings.app_overrides[0].output_mode.as_deref(),
+            Some("paste")
+        );
+    }
+}
@@ -1,11 +1,15 @@
 use crate::ai_fallback::error::AIError;
-use crate::constants::{TARGET_SAMPLE_RATE, VAD_MIN_CONSECUTIVE_CHUNKS, VAD_MIN_VOICE_MS};
+use crate::audio_dsp::{MicDspChain, MicDspControls};
+use crate::constants::{
+    PRE_ROLL_MS_MAX, TARGET_SAMPLE_RATE, VAD_MIN_CONSECUTIVE_CHUNKS, VAD_MIN_VOICE_MS,
+};
 use crate::continuous_dump::{AdaptiveSegmenter, AdaptiveSegmenterConfig, SegmentFlushReason};
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
 use crate::overlay::{
     emit_capture_idle_overlay, sync_overlay_level, update_overlay_refining_indicator,
     update_overlay_state, OverlayState,
 };
-use crate::postprocessing::process_transcript;
+use crate::postprocessing::{process_for_output, Rendition};
 use crate::refinement_adaptation::{record_refinement_observation, RefinementObservation};
 use crate::state::{
     mark_entry_refinement_failed, mark_entry_refinement_started, mark_entry_refinement_success,
@@ -15,9 +19,10 @@ use crate::state::{
 use crate::transcription::{
     rms_i16, should_drop_transcript, transcribe_audio, RefinementGateDecision, TranscriptionResult,
 };
+use crate::transcription_jobs::{is_cancellation, run_job, JobSource};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{SampleFormat, StreamConfig};
-use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
@@ -25,8 +30,6 @@ use tauri::{AppHandle, Emitter, Manager, State};
 use tracing::{error, info, warn};
 
 const MIC_MIN_AUDIO_MS: u64 = 120;
-const VAD_PRE_ROLL_MS_MIN: u64 = 250;
-const VAD_PRE_ROLL_MS_MAX: u64 = 350;
 const VAD_PRE_ROLL_MIN_MS: u64 = 60;
 const VAD_PRE_ROLL_ENERGY_FACTOR: f32 = 0.45;
 const REFINEMENT_WATCHDOG_TIMEOUT_MS: u64 = 30_000; // must not fire during cold model load (~20s)
@@ -36,7 +39,25 @@ const REFINEMENT_COLD_PASTE_TIMEOUT_MS: u64 = 30_000;
 const REFINEMENT_COLD_PASTE_MAX_AGE_MS: u64 = 12 * 60_000;
 const OVERLAY_EMIT_INTERVAL_MS: u64 = 33; // ~30 FPS for smoother overlay motion
 const PTT_VAD_TAIL_MS: u64 = 150;
+const MAX_DURATION_POLL_MS: u64 = 500;
+pub(crate) const MAX_DURATION_EVENT: &str = "capture:max-duration";
 static TRANSCRIPTION_JOB_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+static MAX_DURATION_SESSION: AtomicU64 = AtomicU64::new(0);
+/// Held while a mic segment transcribes, so a forced max-duration segment and
+/// the final chunk of the same recording are pasted in spoken order.
+static MIC_SEGMENT_ORDER: Mutex<()> = Mutex::new(());
+/// Device id last reported as matched by name, so the 2 s device poll warns
+/// once per id instead of on every tick.
+static NAME_MATCH_WARNED: Mutex<Option<String>> = Mutex::new(None);
+
+/// Payload of `capture:max-duration`: a recording reached `max_recording_ms`
+/// and was cut into a segment while capture continues.
+#[derive(Debug, Clone, serde::Serialize)]
+pub(crate) struct MaxDurationWarning {
+    /// "ptt" | "vad"
+    pub(crate) source: &'static str,
+    pub(crate) segment_ms: u64,
+}
 
 #[derive(Debug, Clone, serde::Serialize)]
 pub(crate) struct AudioDevice {
@@ -98,6 +119,33 @@ impl CaptureBuffer {
     }
 }
 
+/// Decodes a WAV file (any sample rate or channel count, integer or float
+/// PCM) into 16 kHz mono samples ready for transcription.
+pub(crate) fn decode_wav_to_mono_16k(bytes: &[u8]) -> Result<Vec<i16>, String> {
+    let mut reader = hound::WavReader::new(std::io::Cursor::new(bytes))
+        .map_err(|e| format!("Invalid WAV: {}", e))?;
+    let spec = reader.spec();
+    let channels = spec.channels.max(1) as usize;
+    let interleaved: Vec<f32> = match spec.sample_format {
+        hound::SampleFormat::Float => reader.samples::<f32>().collect::<Result<_, _>>(),
+        hound::SampleFormat::Int => {
+            let scale = (1i64 << spec.bits_per_sample.clamp(1, 32).saturating_sub(1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|sample| sample.map(|value| value as f32 / scale))
+                .collect()
+        }
+    }
+    .map_err(|e| format!("Failed to read WAV samples: {}", e))?;
+    let mono: Vec<f32> = interleaved
+        .chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+        .collect();
+    let mut buffer = CaptureBuffer::default();
+    buffer.push_samples(&mono, spec.sample_rate);
+    Ok(buffer.take_all_samples())
+}
+
 fn float_to_i16(sample: f32) -> i16 {
     let clamped = sample.clamp(-1.0, 1.0);
     (clamped * i16::MAX as f32) as i16
@@ -117,6 +165,25 @@ pub(crate) struct ContinuousDumpEvent {
 }
 
 fn mic_segmenter_config(settings: &Settings) -> AdaptiveSegmenterConfig {
+    if !settings.continuous_dump_enabled {
+        // Periodic flush: one chunk per interval, cut at the first pause after
+        // it rather than at every breath.
+        let mut periodic = AdaptiveSegmenterConfig::balanced_default();
+        periodic.soft_flush_ms = settings.mic_flush_interval_ms;
+        periodic.silence_flush_ms = 5_000;
+        periodic.hard_cut_ms = 120_000;
+        periodic.min_chunk_ms = settings.continuous_min_chunk_ms;
+        periodic.pre_roll_ms = 0;
+        periodic.post_roll_ms = 0;
+        periodic.idle_keepalive_ms = 120_000;
+        periodic.threshold_start = settings.vad_threshold_start.max(0.001);
+        periodic.threshold_sustain = settings
+            .vad_threshold_sustain
+            .clamp(0.001, periodic.threshold_start);
+        periodic.clamp();
+        return periodic;
+    }
+
     let mut cfg = AdaptiveSegmenterConfig::from_profile(&settings.continuous_dump_profile);
     cfg.soft_flush_ms = if settings.continuous_mic_override_enabled {
         settings.continuous_mic_soft_flush_ms
@@ -157,11 +224,35 @@ pub(crate) struct Recorder {
     vad_tx: Option<std::sync::mpsc::Sender<VadEvent>>,
     vad_runtime: Option<Arc<VadRuntime>>,
     pub(crate) input_gain_db: Arc<AtomicI64>,
+    input_channel: Arc<AtomicUsize>,
+    mic_dsp: Arc<MicDspControls>,
+    stream_lost: Arc<AtomicBool>,
     ptt_hot_stop_tx: Option<std::sync::mpsc::Sender<()>>,
     ptt_hot_join_handle: Option<thread::JoinHandle<()>>,
     ptt_hot_recording: Arc<AtomicBool>,
     ptt_hot_device_id: Option<String>,
     ptt_hot_keepalive_generation: AtomicU64,
+    /// Rolling pre-roll filled by the PTT standby stream while idle. Cold
+    /// starts (PTT+VAD) take it so their first syllable is kept too.
+    idle_pre_roll: Arc<Mutex<CaptureBuffer>>,
+}
+
+/// Live-tunable controls shared between the settings path and every mic
+/// stream callback.
+#[derive(Clone)]
+struct MicInputControls {
+    gain_db: Arc<AtomicI64>,
+    /// 1-based input channel to capture; 0 averages all channels.
+    channel: Arc<AtomicUsize>,
+    dsp: Arc<MicDspControls>,
+    /// Set by the stream error callback when the device went away.
+    stream_lost: Arc<AtomicBool>,
+}
+
+/// Maps the 1-based `input_channel` setting onto a frame index. `None` means
+/// down-mix every channel (setting 0, or a channel the device does not have).
+fn selected_channel_index(channel: usize, channels: usize) -> Option<usize> {
+    (channel >= 1 && channel <= channels).then(|| channel - 1)
 }
 
 impl Recorder {
@@ -178,11 +269,58 @@ impl Recorder {
             vad_tx: None,
             vad_runtime: None,
             input_gain_db: Arc::new(AtomicI64::new(0)),
+            input_channel: Arc::new(AtomicUsize::new(0)),
+            mic_dsp: Arc::new(MicDspControls::default()),
+            stream_lost: Arc::new(AtomicBool::new(false)),
             ptt_hot_stop_tx: None,
             ptt_hot_join_handle: None,
             ptt_hot_recording: Arc::new(AtomicBool::new(false)),
             ptt_hot_device_id: None,
             ptt_hot_keepalive_generation: AtomicU64::new(0),
+            idle_pre_roll: Arc::new(Mutex::new(CaptureBuffer::default())),
+        }
+    }
+
+    fn input_controls(&self) -> MicInputControls {
+        MicInputControls {
+            gain_db: self.input_gain_db.clone(),
+            channel: self.input_channel.clone(),
+            dsp: self.mic_dsp.clone(),
+            stream_lost: self.stream_lost.clone(),
+        }
+    }
+
+    /// Push the live-tunable input settings (gain, DSP chain) to running streams.
+    /// While AGC stays enabled the gain it converged to is kept; the fixed
+    /// `mic_input_gain_db` is only its starting point.
+    pub(crate) fn apply_input_settings(&self, settings: &Settings) {
+        if !(settings.mic_agc_enabled && self.mic_dsp.agc_enabled()) {
+            self.input_gain_db.store(
+                (settings.mic_input_gain_db * 1000.0) as i64,
+                Ordering::Relaxed,
+            );
+        }
+        self.input_channel
+            .store(settings.input_channel as usize, Ordering::Relaxed);
+        self.mic_dsp.apply_settings(settings);
+    }
+
+    /// Audio the standby stream kept from just before this call; empty when
+    /// no standby is running.
+    fn take_idle_pre_roll(&self) -> Vec<i16> {
+        self.idle_pre_roll
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take_all_samples()
+    }
+
+    /// The user is mid-utterance: speaking into a PTT/toggle recording, or
+    /// VAD has detected speech. An always-on VAD stream that is only
+    /// listening does not count.
+    pub(crate) fn utterance_in_flight(&self) -> bool {
+        match self.vad_runtime.as_ref() {
+            Some(runtime) => runtime.recording.load(Ordering::Relaxed),
+            None => self.active,
         }
     }
 
@@ -315,7 +453,8 @@ impl OverlayLevelEmitter {
         }
 
         if let Ok(state) = self.app.state::<AppState>().settings.read() {
-            let (rise_ms, fall_ms) = if state.overlay_style == "kitt" {
+            let (rise_ms, fall_ms) = if crate::overlay::overlay_style_uses_bar(&state.overlay_style)
+            {
                 (state.overlay_kitt_rise_ms, state.overlay_kitt_fall_ms)
             } else {
                 (state.overlay_rise_ms, state.overlay_fall_ms)
@@ -364,6 +503,7 @@ struct VadRuntime {
     silence_ms: AtomicU64,
     hold_tail_ms: AtomicU64,
     consecutive_above: AtomicU64,
+    max_recording_samples: Option<usize>,
 }
 
 impl VadRuntime {
@@ -392,9 +532,15 @@ impl VadRuntime {
             silence_ms: AtomicU64::new(silence_ms.max(100)),
             hold_tail_ms: AtomicU64::new(hold_tail_ms.max(1)),
             consecutive_above: AtomicU64::new(0),
+            max_recording_samples: None,
         }
     }
 
+    fn with_max_recording_ms(mut self, max_recording_ms: u64) -> Self {
+        self.max_recording_samples = max_recording_samples(max_recording_ms);
+        self
+    }
+
     fn threshold_start(&self) -> f32 {
         self.threshold_start_scaled.load(Ordering::Relaxed) as f32 / 1_000_000.0
     }
@@ -439,6 +585,8 @@ struct VadHandle {
     pre_roll_buffer: Arc<Mutex<CaptureBuffer>>,
     pre_roll_samples: usize,
     pre_roll_min_samples: usize,
+    /// Set in wake-word mode: speech only starts a recording once armed.
+    wake: Option<Arc<crate::wake_word::WakeWordGate>>,
 }
 
 #[tauri::command]
@@ -517,7 +665,39 @@ pub(crate) async fn list_output_devices() -> Vec<AudioDevice> {
     .unwrap_or_else(|_| vec![])
 }
 
+/// Channel count of an input device's default config, for the channel picker.
+#[tauri::command]
+pub(crate) async fn get_input_device_channels(device_id: String) -> CommandResult<u16> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let device = resolve_input_device(&device_id).ok_or_else(|| {
+            CommandError::new(ErrorCode::AudioDevice, "No input device available")
+        })?;
+        device
+            .default_input_config()
+            .map(|config| config.channels())
+            .with_code(ErrorCode::AudioDevice)
+    })
+    .await
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("get_input_device_channels task failed: {}", e),
+        )
+    })?
+}
+
+/// Whether any input device, configured or system default, can be opened.
+pub(crate) fn input_device_available(device_id: &str) -> bool {
+    resolve_input_device(device_id).is_some()
+}
+
 fn resolve_input_device(device_id: &str) -> Option<cpal::Device> {
+    find_input_device(device_id).or_else(|| cpal::default_host().default_input_device())
+}
+
+/// Looks up the configured input device without falling back to the system
+/// default, so callers can tell a missing device from a present one.
+fn find_input_device(device_id: &str) -> Option<cpal::Device> {
     let host = cpal::default_host();
     if device_id == "default" {
         return host.default_input_device();
@@ -549,13 +729,67 @@ fn resolve_input_device(device_id: &str) -> Option<cpal::Device> {
     }
 
     if name_match.is_some() {
-        tracing::warn!(
-            "Input device '{}' not found by exact ID; matched by name instead.",
-            device_id
-        );
+        let mut warned = NAME_MATCH_WARNED
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if warned.as_deref() != Some(device_id) {
+            tracing::warn!(
+                "Input device '{}' not found by exact ID; matched by name instead.",
+                device_id
+            );
+            *warned = Some(device_id.to_string());
+        }
     }
 
-    name_match.or_else(|| host.default_input_device())
+    name_match
+}
+
+pub(crate) fn input_device_present(device_id: &str) -> bool {
+    find_input_device(device_id).is_some()
+}
+
+/// True while a mic stream is open (VAD monitor, toggle/PTT recording or the
+/// PTT hot standby).
+pub(crate) fn input_capture_running(state: &AppState) -> bool {
+    let recorder = state
+        .recorder
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    recorder.active || recorder.ptt_hot_join_handle.is_some()
+}
+
+/// Returns and clears the "stream reported DeviceNotAvailable" flag.
+pub(crate) fn take_input_stream_lost(state: &AppState) -> bool {
+    let recorder = state
+        .recorder
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    recorder.stream_lost.swap(false, Ordering::Relaxed)
+}
+
+/// Re-open the always-on mic streams (VAD monitor, PTT hot standby) so they
+/// pick up the device again — or the system default when it is gone.
+/// A toggle/PTT recording in flight is left alone; it ends with the key/toggle.
+pub(crate) fn restart_input_capture(
+    app: &AppHandle,
+    state: &State<'_, AppState>,
+    settings: &Settings,
+) {
+    if crate::state::mode_uses_vad_monitor(&settings.mode) && settings.capture_enabled {
+        stop_vad_monitor(app, state);
+        if let Err(err) = start_vad_monitor(app, state, settings) {
+            warn!("Failed to restart VAD monitor after device change: {}", err);
+        }
+    }
+    let recording = state
+        .recorder
+        .lock()
+        .map(|recorder| recorder.ptt_hot_recording.load(Ordering::Relaxed))
+        .unwrap_or(false);
+    if !recording {
+        stop_ptt_hot_standby(state);
+        sync_ptt_hot_standby(app, state, settings);
+    }
 }
 
 fn push_mono_samples(buffer: &Arc<Mutex<CaptureBuffer>>, mono: &[f32], sample_rate: u32) {
@@ -575,6 +809,21 @@ fn handle_vad_audio(
     let now = crate::util::now_ms();
     let is_recording = runtime.recording.load(Ordering::Relaxed);
 
+    if let Some(wake) = vad_handle.wake.as_ref() {
+        if !is_recording && !wake.is_armed(now) {
+            wake.feed(&mono, sample_rate);
+            runtime.consecutive_above.store(0, Ordering::Relaxed);
+            // Audio from before the wake phrase must not be prepended to the
+            // recording it arms.
+            vad_handle
+                .pre_roll_buffer
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .reset();
+            return;
+        }
+    }
+
     let threshold = if is_recording {
         runtime.threshold_sustain()
     } else {
@@ -589,6 +838,9 @@ fn handle_vad_audio(
             runtime.recording.store(true, Ordering::Relaxed);
             runtime.start_ms.store(now, Ordering::Relaxed);
             runtime.pending_flush.store(false, Ordering::Relaxed);
+            if let Some(wake) = vad_handle.wake.as_ref() {
+                wake.disarm();
+            }
             let warmup = {
                 let mut pre = vad_handle
                     .pre_roll_buffer
@@ -609,7 +861,7 @@ fn handle_vad_audio(
             let _ = vad_handle.app.emit("capture:state", "recording");
             let _ = update_overlay_state(&vad_handle.app, OverlayState::Recording);
             if runtime.audio_cues {
-                let _ = vad_handle.app.emit("audio:cue", "start");
+                crate::audio_cues::play_cue(&vad_handle.app, "start");
             }
         }
     } else if !is_recording {
@@ -626,6 +878,20 @@ fn handle_vad_audio(
             push_mono_samples(buffer, &mono, sample_rate);
         }
 
+        if let Some(max_samples) = runtime.max_recording_samples {
+            let segment = {
+                let mut buf = buffer
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+                (buf.samples.len() >= max_samples).then(|| buf.drain())
+            };
+            if let Some(samples) = segment {
+                emit_max_duration_warning(&vad_handle.app, "vad", samples.len());
+                runtime.start_ms.store(now, Ordering::Relaxed);
+                let _ = vad_handle.tx.send(VadEvent::Finalize(samples));
+            }
+        }
+
         if runtime.flush_on_silence
             && now.saturating_sub(last) > silence_ms
             && now.saturating_sub(start) > VAD_MIN_VOICE_MS
@@ -661,7 +927,8 @@ fn handle_vad_audio(
 /// Macro that generates a `build_input_stream_*` function for a specific sample
 /// type.  The only thing that varies across f32 / i16 / u16 is how one raw
 /// sample is normalised to `f32` in the range `[-1, 1]`.  Everything else
-/// (gain, mono down-mix, RMS / level calculation, VAD dispatch) is identical.
+/// (channel pick / mono down-mix, gain, DSP chain, RMS / level calculation,
+/// VAD dispatch) is identical.
 macro_rules! build_input_stream_typed {
     ($fn_name:ident, $sample_ty:ty, $to_f32:expr) => {
         fn $fn_name(
@@ -670,14 +937,21 @@ macro_rules! build_input_stream_typed {
             buffer: Arc<Mutex<CaptureBuffer>>,
             overlay: Option<Arc<OverlayLevelEmitter>>,
             vad: Option<VadHandle>,
-            gain_db: Arc<AtomicI64>,
+            input: MicInputControls,
         ) -> Result<cpal::Stream, String> {
             let channels = config.channels as usize;
             let sample_rate = config.sample_rate.0;
-            let err_fn = |err| tracing::error!("audio stream error: {}", err);
+            let stream_lost = input.stream_lost.clone();
+            let err_fn = move |err: cpal::StreamError| {
+                tracing::error!("audio stream error: {}", err);
+                if matches!(err, cpal::StreamError::DeviceNotAvailable) {
+                    stream_lost.store(true, Ordering::Relaxed);
+                }
+            };
 
             // Converter closure produced by the caller expression.
             let convert: fn(&$sample_ty) -> f32 = $to_f32;
+            let mut dsp = MicDspChain::new(sample_rate);
 
             device
                 .build_input_stream(
@@ -685,18 +959,30 @@ macro_rules! build_input_stream_typed {
                     move |data: &[$sample_ty], _| {
                         let ch = channels.max(1);
                         let mut mono = Vec::with_capacity(data.len() / ch);
-                        let mut sum_squared = 0.0f32;
-                        let gain_db_val = gain_db.load(Ordering::Relaxed) as f32 / 1000.0;
+                        let gain_db_val = input.gain_db.load(Ordering::Relaxed) as f32 / 1000.0;
                         let gain = (10.0f32).powf(gain_db_val / 20.0);
+                        let selected =
+                            selected_channel_index(input.channel.load(Ordering::Relaxed), ch);
                         for frame in data.chunks(ch) {
-                            let mut sum = 0.0f32;
-                            for sample in frame {
-                                sum += convert(sample);
-                            }
-                            let sample = (sum / ch as f32 * gain).clamp(-1.0, 1.0);
-                            mono.push(sample);
-                            sum_squared += sample * sample;
+                            let sample = match selected.and_then(|index| frame.get(index)) {
+                                Some(sample) => convert(sample),
+                                None => {
+                                    let mut sum = 0.0f32;
+                                    for sample in frame {
+                                        sum += convert(sample);
+                                    }
+                                    sum / ch as f32
+                                }
+                            };
+                            mono.push((sample * gain).clamp(-1.0, 1.0));
                         }
+                        dsp.process(&input.dsp, &mut mono);
+                        // Without VAD the stream only runs while recording.
+                        let speech = vad.as_ref().is_none_or(|vad_handle| {
+                            vad_handle.runtime.recording.load(Ordering::Relaxed)
+                        });
+                        dsp.track_gain(&input.dsp, &input.gain_db, &mono, speech);
+                        let sum_squared: f32 = mono.iter().map(|sample| sample * sample).sum();
                         let level = if mono.is_empty() {
                             0.0
                         } else {
@@ -728,6 +1014,92 @@ build_input_stream_typed!(build_input_stream_u16, u16, |s: &u16| {
     (*s as f32 - 32768.0) / 32768.0
 });
 
+/// Records `duration` of mic audio with the configured device, gain and DSP
+/// chain on a stream of its own, so it works whether or not capture is
+/// running. Used by the setup wizard's microphone test.
+/// A standalone mic stream on the selected input device (channel, gain and
+/// DSP applied) feeding `buffer` at 16 kHz. cpal streams are not `Send`, so
+/// keep it on the thread that opened it; dropping it stops capture.
+pub(crate) struct MicCapture {
+    _stream: cpal::Stream,
+    input: MicInputControls,
+    pub(crate) buffer: Arc<Mutex<CaptureBuffer>>,
+}
+
+impl MicCapture {
+    pub(crate) fn stream_lost(&self) -> bool {
+        self.input.stream_lost.load(Ordering::Relaxed)
+    }
+}
+
+pub(crate) fn open_mic_capture(settings: &Settings) -> Result<MicCapture, String> {
+    let recorder = Recorder::new();
+    recorder.apply_input_settings(settings);
+    let input = recorder.input_controls();
+    let buffer = recorder.buffer.clone();
+
+    let device = resolve_input_device(&settings.input_device)
+        .ok_or_else(|| "No input device available".to_string())?;
+    let config = device.default_input_config().map_err(|e| e.to_string())?;
+    let stream_config: StreamConfig = config.clone().into();
+    let stream = match config.sample_format() {
+        SampleFormat::F32 => build_input_stream_f32(
+            &device,
+            &stream_config,
+            buffer.clone(),
+            None,
+            None,
+            input.clone(),
+        )?,
+        SampleFormat::I16 => build_input_stream_i16(
+            &device,
+            &stream_config,
+            buffer.clone(),
+            None,
+            None,
+            input.clone(),
+        )?,
+        SampleFormat::U16 => build_input_stream_u16(
+            &device,
+            &stream_config,
+            buffer.clone(),
+            None,
+            None,
+            input.clone(),
+        )?,
+        _ => return Err("Unsupported sample format".to_string()),
+    };
+    stream.play().map_err(|e| e.to_string())?;
+    Ok(MicCapture {
+        _stream: stream,
+        input,
+        buffer,
+    })
+}
+
+pub(crate) fn record_mic_sample(
+    settings: &Settings,
+    duration: Duration,
+) -> Result<Vec<i16>, String> {
+    if crate::privacy_mute::is_muted() {
+        return Err("Privacy mute is on; release it to test the microphone".to_string());
+    }
+    let capture = open_mic_capture(settings)?;
+    thread::sleep(duration);
+    let lost = capture.stream_lost();
+    let buffer = capture.buffer.clone();
+    drop(capture);
+
+    if lost {
+        return Err("Input device was disconnected during the test".to_string());
+    }
+    let samples = buffer
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .drain();
+    Ok(samples)
+}
+
 macro_rules! build_ptt_hot_stream_typed {
     ($fn_name:ident, $sample_ty:ty, $to_f32:expr) => {
         fn $fn_name(
@@ -735,17 +1107,24 @@ macro_rules! build_ptt_hot_stream_typed {
             config: &StreamConfig,
             buffer: Arc<Mutex<CaptureBuffer>>,
             overlay: Option<Arc<OverlayLevelEmitter>>,
-            gain_db: Arc<AtomicI64>,
+            input: MicInputControls,
             recording_flag: Arc<AtomicBool>,
+            pre_roll: Arc<Mutex<CaptureBuffer>>,
             pre_roll_samples: usize,
         ) -> Result<cpal::Stream, String> {
             let channels = config.channels as usize;
             let sample_rate = config.sample_rate.0;
-            let err_fn = |err| tracing::error!("audio stream error: {}", err);
+            let stream_lost = input.stream_lost.clone();
+            let err_fn = move |err: cpal::StreamError| {
+                tracing::error!("audio stream error: {}", err);
+                if matches!(err, cpal::StreamError::DeviceNotAvailable) {
+                    stream_lost.store(true, Ordering::Relaxed);
+                }
+            };
 
             let convert: fn(&$sample_ty) -> f32 = $to_f32;
+            let mut dsp = MicDspChain::new(sample_rate);
 
-            let mut pre_roll = CaptureBuffer::default();
             let mut was_recording = false;
 
             device
@@ -754,18 +1133,33 @@ macro_rules! build_ptt_hot_stream_typed {
                     move |data: &[$sample_ty], _| {
                         let ch = channels.max(1);
                         let mut mono = Vec::with_capacity(data.len() / ch);
-                        let mut sum_squared = 0.0f32;
-                        let gain_db_val = gain_db.load(Ordering::Relaxed) as f32 / 1000.0;
+                        let gain_db_val = input.gain_db.load(Ordering::Relaxed) as f32 / 1000.0;
                         let gain = (10.0f32).powf(gain_db_val / 20.0);
+                        let selected = selected_channel_index(
+                            input.channel.load(Ordering::Relaxed),
+                            ch,
+                        );
                         for frame in data.chunks(ch) {
-                            let mut sum = 0.0f32;
-                            for sample in frame {
-                                sum += convert(sample);
-                            }
-                            let sample = (sum / ch as f32 * gain).clamp(-1.0, 1.0);
-                            mono.push(sample);
-                            sum_squared += sample * sample;
+                            let sample = match selected.and_then(|index| frame.get(index)) {
+                                Some(sample) => convert(sample),
+                                None => {
+                                    let mut sum = 0.0f32;
+                                    for sample in frame {
+                                        sum += convert(sample);
+                                    }
+                                    sum / ch as f32
+                                }
+                            };
+                            mono.push((sample * gain).clamp(-1.0, 1.0));
                         }
+                        dsp.process(&input.dsp, &mut mono);
+                        dsp.track_gain(
+                            &input.dsp,
+                            &input.gain_db,
+                            &mono,
+                            recording_flag.load(Ordering::Relaxed),
+                        );
+                        let sum_squared: f32 = mono.iter().map(|sample| sample * sample).sum();
 
                         let level = if mono.is_empty() {
                             0.0
@@ -781,7 +1175,10 @@ macro_rules! build_ptt_hot_stream_typed {
 
                         if recording_now {
                             if !was_recording {
-                                let warmup = pre_roll.take_all_samples();
+                                let warmup = pre_roll
+                                    .lock()
+                                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                                    .take_all_samples();
                                 let warmup_ms = (warmup.len() as u64 * 1000) / TARGET_SAMPLE_RATE as u64;
                                 let expected_ms =
                                     (pre_roll_samples as u64 * 1000) / TARGET_SAMPLE_RATE as u64;
@@ -810,14 +1207,16 @@ macro_rules! build_ptt_hot_stream_typed {
                                 }
                             }
                             push_mono_samples(&buffer, &mono, sample_rate);
-                        } else if pre_roll_samples > 0 {
-                            pre_roll.push_samples(&mono, sample_rate);
-                            if pre_roll.samples.len() > pre_roll_samples {
-                                let overflow = pre_roll.samples.len() - pre_roll_samples;
-                                pre_roll.samples.drain(0..overflow);
+                        } else if let Ok(mut pre_roll) = pre_roll.lock() {
+                            if pre_roll_samples > 0 {
+                                pre_roll.push_samples(&mono, sample_rate);
+                                if pre_roll.samples.len() > pre_roll_samples {
+                                    let overflow = pre_roll.samples.len() - pre_roll_samples;
+                                    pre_roll.samples.drain(0..overflow);
+                                }
+                            } else {
+                                pre_roll.reset();
                             }
-                        } else {
-                            pre_roll.reset();
                         }
 
                         was_recording = recording_now;
@@ -849,6 +1248,7 @@ fn stop_ptt_hot_standby(state: &State<'_, AppState>) {
             .fetch_add(1, Ordering::Relaxed);
         recorder.ptt_hot_recording.store(false, Ordering::Relaxed);
         recorder.ptt_hot_device_id = None;
+        let _ = recorder.take_idle_pre_roll();
         (
             recorder.ptt_hot_stop_tx.take(),
             recorder.ptt_hot_join_handle.take(),
@@ -870,15 +1270,12 @@ fn start_ptt_hot_standby(
     let diagnostics_enabled = crate::state::diagnostic_logging_enabled();
     let device_id = settings.input_device.clone();
 
-    let (existing_stop_tx, existing_join_handle, buffer, gain_db, recording_flag) = {
+    let (existing_stop_tx, existing_join_handle, buffer, input, recording_flag, pre_roll) = {
         let mut recorder = state
             .recorder
             .lock()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
-        recorder.input_gain_db.store(
-            (settings.mic_input_gain_db * 1000.0) as i64,
-            Ordering::Relaxed,
-        );
+        recorder.apply_input_settings(settings);
 
         let same_device = recorder.ptt_hot_device_id.as_deref() == Some(device_id.as_str());
         if recorder.ptt_hot_join_handle.is_some() && same_device {
@@ -893,13 +1290,15 @@ fn start_ptt_hot_standby(
 
         recorder.ptt_hot_recording.store(false, Ordering::Relaxed);
         recorder.ptt_hot_device_id = None;
+        let _ = recorder.take_idle_pre_roll();
 
         (
             recorder.ptt_hot_stop_tx.take(),
             recorder.ptt_hot_join_handle.take(),
             recorder.buffer.clone(),
-            recorder.input_gain_db.clone(),
+            recorder.input_controls(),
             recorder.ptt_hot_recording.clone(),
+            recorder.idle_pre_roll.clone(),
         )
     };
 
@@ -915,7 +1314,7 @@ fn start_ptt_hot_standby(
         settings.vad_threshold_sustain,
         settings.vad_threshold_start,
     ));
-    let pre_roll_ms = settings.continuous_pre_roll_ms.min(1_500);
+    let pre_roll_ms = settings.pre_roll_ms.min(PRE_ROLL_MS_MAX);
     let pre_roll_samples = ((TARGET_SAMPLE_RATE as u64 * pre_roll_ms) / 1000) as usize;
     let (stop_tx, stop_rx) = std::sync::mpsc::channel::<()>();
     let (ready_tx, ready_rx) = std::sync::mpsc::channel::<Result<(), String>>();
@@ -941,8 +1340,9 @@ fn start_ptt_hot_standby(
                     &stream_config,
                     buffer,
                     overlay.clone(),
-                    gain_db.clone(),
+                    input.clone(),
                     recording_flag.clone(),
+                    pre_roll,
                     pre_roll_samples,
                 )?,
                 SampleFormat::I16 => build_ptt_hot_stream_i16(
@@ -950,8 +1350,9 @@ fn start_ptt_hot_standby(
                     &stream_config,
                     buffer,
                     overlay.clone(),
-                    gain_db.clone(),
+                    input.clone(),
                     recording_flag.clone(),
+                    pre_roll,
                     pre_roll_samples,
                 )?,
                 SampleFormat::U16 => build_ptt_hot_stream_u16(
@@ -959,8 +1360,9 @@ fn start_ptt_hot_standby(
                     &stream_config,
                     buffer,
                     overlay.clone(),
-                    gain_db.clone(),
+                    input.clone(),
                     recording_flag.clone(),
+                    pre_roll,
                     pre_roll_samples,
                 )?,
                 _ => return Err("Unsupported sample format".to_string()),
@@ -1006,7 +1408,12 @@ pub(crate) fn sync_ptt_hot_standby(
     settings: &Settings,
 ) {
     let diagnostics_enabled = crate::state::diagnostic_logging_enabled();
-    let should_run = settings.capture_enabled && settings.mode == "ptt" && !settings.ptt_use_vad;
+    // PTT+VAD opens its own stream on press; the standby then only keeps
+    // the idle pre-roll for it.
+    let should_run = settings.capture_enabled
+        && settings.mode == "ptt"
+        && (!settings.ptt_use_vad || settings.pre_roll_ms > 0)
+        && !crate::privacy_mute::is_muted();
     let running_state = {
         let recorder = state
             .recorder
@@ -1099,10 +1506,7 @@ fn start_ptt_hot_recording(
         buf.reset();
     }
 
-    recorder.input_gain_db.store(
-        (settings.mic_input_gain_db * 1000.0) as i64,
-        Ordering::Relaxed,
-    );
+    recorder.apply_input_settings(settings);
     recorder.ptt_hot_recording.store(true, Ordering::Relaxed);
     recorder.active = true;
     recorder.continuous_toggle_mode = false;
@@ -1115,6 +1519,8 @@ fn start_ptt_hot_recording(
             keepalive_generation, settings.ptt_hot_keepalive_ms
         );
     }
+    drop(recorder);
+    spawn_max_duration_guard(app, settings);
     let _ = app.emit("capture:state", "recording");
     let _ = update_overlay_state(app, OverlayState::Recording);
     // Audio cue already emitted at the top of handle_ptt_press for immediate
@@ -1149,15 +1555,14 @@ pub(crate) fn start_recording_with_settings(
         return Ok(());
     }
 
+    let warmup = recorder.take_idle_pre_roll();
     if let Ok(mut buf) = recorder.buffer.lock() {
         buf.reset();
+        buf.samples.extend_from_slice(&warmup);
     }
 
-    recorder.input_gain_db.store(
-        (settings.mic_input_gain_db * 1000.0) as i64,
-        Ordering::Relaxed,
-    );
-    let gain_db = recorder.input_gain_db.clone();
+    recorder.apply_input_settings(settings);
+    let input = recorder.input_controls();
     let buffer = recorder.buffer.clone();
     let overlay_emitter = Arc::new(OverlayLevelEmitter::new(
         app.clone(),
@@ -1184,7 +1589,7 @@ pub(crate) fn start_recording_with_settings(
                     buffer,
                     overlay.clone(),
                     vad.clone(),
-                    gain_db.clone(),
+                    input.clone(),
                 )?,
                 SampleFormat::I16 => build_input_stream_i16(
                     &device,
@@ -1192,7 +1597,7 @@ pub(crate) fn start_recording_with_settings(
                     buffer,
                     overlay.clone(),
                     vad.clone(),
-                    gain_db.clone(),
+                    input.clone(),
                 )?,
                 SampleFormat::U16 => build_input_stream_u16(
                     &device,
@@ -1200,7 +1605,7 @@ pub(crate) fn start_recording_with_settings(
                     buffer,
                     overlay.clone(),
                     vad.clone(),
-                    gain_db.clone(),
+                    input.clone(),
                 )?,
                 _ => return Err("Unsupported sample format".to_string()),
             };
@@ -1238,6 +1643,9 @@ pub(crate) fn start_recording_with_settings(
     recorder.continuous_processor_stop_tx = None;
     recorder.continuous_processor_join_handle = None;
 
+    drop(recorder);
+    spawn_max_duration_guard(app, settings);
+
     if diagnostics_enabled {
         info!("Recording started successfully, updating overlay");
     }
@@ -1245,15 +1653,91 @@ pub(crate) fn start_recording_with_settings(
     let _ = update_overlay_state(app, OverlayState::Recording);
 
     if settings.audio_cues {
-        let _ = app.emit("audio:cue", "start");
+        crate::audio_cues::play_cue(app, "start");
     }
 
     Ok(())
 }
 
+/// Audio behind a transcript, linked to the history entry it produces.
+struct TranscriptAudio<'a> {
+    samples: &'a [i16],
+    /// When the capture ended, the start of the latency breakdown.
+    captured_at: Instant,
+    /// Recording already saved for this capture (long PTT dictations).
+    saved_path: Option<String>,
+}
+
+/// Mic transcription with the override (language) of `foreground`, the app
+/// being dictated into, applied.
+fn transcribe_mic_audio(
+    app_handle: &AppHandle,
+    settings: &Settings,
+    foreground: Option<&str>,
+    samples: &[i16],
+) -> Result<(String, String), String> {
+    let result = transcribe_audio(
+        app_handle,
+        &crate::app_overrides::for_app(settings, foreground),
+        samples,
+    );
+    if let Err(err) = &result {
+        crate::failed_segments::keep(app_handle, "mic", samples, err);
+    }
+    result
+}
+
+/// Transcribes audio kept from a failed mic segment and delivers it like a
+/// fresh dictation.
+pub(crate) fn transcribe_retained_mic_audio(
+    app_handle: &AppHandle,
+    samples: Vec<i16>,
+) -> Result<(), String> {
+    // The original capture is long gone; measure from the retry.
+    let captured_at = Instant::now();
+    let _segment_order = MIC_SEGMENT_ORDER
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let settings = app_handle
+        .state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    let duration_ms = samples.len() as u64 * 1000 / TARGET_SAMPLE_RATE as u64;
+    let foreground = crate::app_overrides::foreground_app(&settings);
+    let result = run_job(app_handle, JobSource::Mic, duration_ms, || {
+        transcribe_mic_audio(app_handle, &settings, foreground.as_deref(), &samples)
+    });
+    match result {
+        Ok((text, source)) => {
+            handle_transcription_ok(
+                app_handle,
+                &text,
+                &source,
+                &crate::app_overrides::for_app(&settings, foreground.as_deref()),
+                rms_i16(&samples),
+                duration_ms,
+                TranscriptAudio {
+                    samples: &samples,
+                    captured_at,
+                    saved_path: None,
+                },
+            );
+            Ok(())
+        }
+        Err(err) => {
+            emit_transcription_error(app_handle, err.clone());
+            Err(err)
+        }
+    }
+}
+
 /// Common transcription-result handling: post-process, push to history, emit
 /// events, and optionally spawn AI refinement. Returns `Some(processed_text_len)`
 /// when a result was emitted, `None` when the transcript was filtered/dropped.
+/// `settings` already carry the override of the app being dictated into, so
+/// post-processing and paste behaviour follow it.
 fn handle_transcription_ok(
     app_handle: &AppHandle,
     text: &str,
@@ -1261,7 +1745,10 @@ fn handle_transcription_ok(
     settings: &Settings,
     level: f32,
     duration_ms: u64,
+    audio: TranscriptAudio<'_>,
 ) -> Option<usize> {
+    let job_ms = crate::transcription_jobs::take_last_job_elapsed_ms();
+    let detected_language = crate::detected_language::take_detected_language();
     let _ = app_handle.emit(
         "transcription:raw-result",
         crate::workflow_agent::RawTranscriptionEvent {
@@ -1271,13 +1758,21 @@ fn handle_transcription_ok(
         },
     );
 
+    let activated = crate::activation_words::gate(text, settings);
     if text.trim().is_empty()
-        || should_drop_transcript(text, level, duration_ms, false)
-        || crate::transcription::should_drop_by_activation_words(
+        || should_drop_transcript(
             text,
-            &settings.activation_words,
-            settings.activation_words_enabled,
+            level,
+            duration_ms,
+            false,
+            &crate::hallucination_filter::HallucinationFilter::for_settings(
+                settings,
+                detected_language.as_deref(),
+            ),
         )
+        || activated
+            .as_deref()
+            .is_none_or(|activated| activated.trim().is_empty())
     {
         let _ = app_handle.emit(
             "transcription:dropped",
@@ -1289,23 +1784,105 @@ fn handle_transcription_ok(
         );
         return None;
     }
+    let text = activated.as_deref().unwrap_or(text);
+    let language_verdict =
+        crate::detected_language::check_language(detected_language.as_deref(), settings);
+    if language_verdict == crate::detected_language::UnexpectedLanguageAction::Reject {
+        let _ = app_handle.emit(
+            "transcription:dropped",
+            serde_json::json!({
+                "source": source,
+                "text": text,
+                "reason": "unexpected_language",
+                "language": detected_language,
+            }),
+        );
+        return None;
+    }
 
-    let processed_text = if settings.postproc_enabled {
-        match process_transcript(text, settings, app_handle) {
-            Ok(processed) => processed,
-            Err(err) => {
-                error!("Post-processing failed: {}", err);
-                text.to_string()
+    if let Some(entry) = crate::dictation_macros::match_for_settings(text, settings) {
+        crate::dictation_macros::execute(app_handle, entry);
+        return None;
+    }
+
+    // Spoken editing commands run before post-processing so punctuation and
+    // capitalization rules see the edited text, not the command words.
+    let postprocess_started = std::time::Instant::now();
+    let voice_command = crate::voice_commands::interpret_for_settings(text, settings);
+    let text = match &voice_command {
+        Some(interpreted) => {
+            crate::voice_commands::send_keystrokes(&interpreted.keystrokes);
+            let _ = app_handle.emit(
+                "voice-command:applied",
+                serde_json::json!({
+                    "source": source,
+                    "commands": interpreted.commands_applied,
+                    "keystrokes": interpreted.keystrokes.len(),
+                    "text": interpreted.text,
+                }),
+            );
+            if interpreted.text.trim().is_empty() {
+                return None;
             }
+            interpreted.text.as_str()
         }
+        None => text,
+    };
+
+    let processed = process_for_output(text, settings, app_handle);
+    if processed.dropped {
+        // Only the category; the filtered text must not leave the backend.
+        let _ = app_handle.emit(
+            "transcription:dropped",
+            serde_json::json!({
+                "source": source,
+                "reason": "content_filter",
+                "tags": processed.tags,
+            }),
+        );
+        return None;
+    }
+    let mut entry_tags = processed.tags.clone();
+    if language_verdict == crate::detected_language::UnexpectedLanguageAction::Flag {
+        entry_tags.push(crate::detected_language::UNEXPECTED_LANGUAGE_TAG.to_string());
+    }
+    // Snippets expand after post-processing so their text is pasted verbatim.
+    // Paste and history may each take a different post-processing rendition.
+    let processed_text = crate::snippets::expand_for_settings(
+        processed.rendition(Rendition::from_setting(&settings.history_rendition)),
+        settings,
+    );
+    let paste_text = crate::snippets::expand_for_settings(
+        processed.rendition(Rendition::from_setting(&settings.paste_rendition)),
+        settings,
+    );
+    // Only a translation that replaces the paste is waited for; otherwise it
+    // follows into history after the original went out.
+    let translation = if settings.translation.paste_translation {
+        crate::translation::translate_transcript(
+            app_handle,
+            &paste_text,
+            &settings.translation,
+            detected_language.as_deref(),
+        )
     } else {
-        text.to_string()
+        None
     };
+    let paste_translation = translation
+        .as_ref()
+        .is_some_and(|translation| translation.pasted);
+    let latency = crate::latency_stats::LatencyBreakdown::for_job(
+        source,
+        audio.captured_at,
+        job_ms.unwrap_or(0),
+        postprocess_started.elapsed().as_millis() as u64,
+    );
 
     let job_id = next_transcription_job_id(source);
     let state = app_handle.state::<AppState>();
     let (paste_timeout_ms, paste_timeout_cold) = refinement_paste_timeout_ms(app_handle, settings);
     let mut entry_id: Option<String> = None;
+    let mut output_text = paste_text.clone();
     if let Ok(updated) = push_history_entry_inner(
         app_handle,
         &state.history,
@@ -1313,7 +1890,76 @@ fn handle_transcription_ok(
         source.to_string(),
     ) {
         entry_id = updated.first().map(|entry| entry.id.clone());
+        if let Some(formatted) = updated
+            .first()
+            .and_then(|entry| entry.formatted_text.clone())
+        {
+            output_text = formatted;
+        }
+        // The entry's formatted text renders the stored rendition; render
+        // the pasted one when it differs.
+        let pasted_body = match &translation {
+            Some(translation) if paste_translation => Some(translation.text.as_str()),
+            _ if paste_text != processed_text => Some(paste_text.as_str()),
+            _ => None,
+        };
+        if let (Some(entry), Some(body)) = (updated.first(), pasted_body) {
+            output_text = crate::postprocessing::apply_output_template(
+                settings,
+                source,
+                entry.speaker_name.as_deref().unwrap_or_default(),
+                body,
+                entry.timestamp_ms,
+            )
+            .unwrap_or_else(|| body.to_string());
+        }
+        let updated = match &entry_id {
+            Some(id) if detected_language.is_some() || !entry_tags.is_empty() => {
+                crate::history_partition::annotate_entry(
+                    &state.history,
+                    id,
+                    detected_language.as_deref(),
+                    &entry_tags,
+                )
+                .unwrap_or(updated)
+            }
+            _ => updated,
+        };
+        let updated = match (&entry_id, translation) {
+            (Some(id), Some(translation)) => {
+                crate::history_partition::attach_translation(&state.history, id, translation)
+                    .unwrap_or(updated)
+            }
+            _ => updated,
+        };
+        let updated = match (&entry_id, audio.saved_path) {
+            (Some(id), Some(path)) => {
+                crate::history_partition::attach_entry_audio(&state.history, id, path)
+                    .unwrap_or(updated)
+            }
+            (Some(id), None) if settings.entry_audio_enabled => {
+                crate::entry_audio::keep_entry_audio(
+                    app_handle,
+                    id.clone(),
+                    audio.samples.to_vec(),
+                );
+                updated
+            }
+            _ => updated,
+        };
         let _ = app_handle.emit("history:updated", updated);
+        if let Some(id) = entry_id
+            .clone()
+            .filter(|_| !settings.translation.paste_translation)
+        {
+            crate::translation::attach_translation_later(
+                app_handle,
+                id,
+                paste_text.clone(),
+                settings,
+                detected_language.clone(),
+            );
+        }
     }
     let word_count = processed_text.split_whitespace().count() as u32;
     info!(
@@ -1327,6 +1973,22 @@ fn handle_transcription_ok(
             "source": source,
         })
     );
+    {
+        let app = app_handle.clone();
+        let sample = crate::usage_stats::UsageSample {
+            model: if source.starts_with("local") {
+                settings.model.clone()
+            } else {
+                source.to_string()
+            },
+            audio_ms: duration_ms,
+            words: word_count as u64,
+            latency_ms: job_ms,
+        };
+        crate::util::spawn_guarded("usage_stats_record", move || {
+            crate::usage_stats::record_dictation(&app, sample);
+        });
+    }
     // Just-in-time refinement gate. Rather than trust cached warm/warmup flags
     // (which drift whenever OLLAMA loads or unloads the model on its own), ask
     // OLLAMA right now whether the refinement model is loaded. /api/ps is the
@@ -1362,8 +2024,11 @@ fn handle_transcription_ok(
     // refinement warm the model in the background (history-only result).
     // Waiting out a cold model maximises user-visible latency exactly when
     // the system is least able to honour it.
+    // A pasted translation is never held back: the refinement result is of
+    // the original text and only updates history.
     let paste_deferred = should_refine
         && !paste_timeout_cold
+        && !paste_translation
         && should_defer_paste_for_refinement(&app_handle, settings);
     let skipped_reason = if should_refine {
         None
@@ -1389,8 +2054,10 @@ fn handle_transcription_ok(
                 None
             },
             entry_id: entry_id.clone(),
+            detected_language,
             audio_duration_ms: duration_ms,
             word_count,
+            latency: latency.clone(),
             refinement_gate: RefinementGateDecision {
                 enabled: refinement_enabled,
                 provider: settings.ai_fallback.provider.clone(),
@@ -1426,31 +2093,47 @@ fn handle_transcription_ok(
     // Rust-side deadline (immune to WebView timer throttling); everything
     // else pastes raw right away. A refinement that finishes after the
     // deadline settles as a no-op and only updates history.
-    state
-        .paste_arbiter
-        .register(&job_id, processed_text.clone());
+    state.paste_arbiter.register(
+        &job_id,
+        output_text,
+        crate::paste_arbiter::PasteDelivery::from_settings(settings),
+    );
     if paste_deferred {
         crate::paste_arbiter::schedule_deadline(
             app_handle.clone(),
             job_id.clone(),
             paste_timeout_ms,
         );
+        crate::latency_stats::record(latency);
     } else {
+        let paste_started = std::time::Instant::now();
         state.paste_arbiter.settle(
             app_handle,
             &job_id,
             crate::paste_arbiter::PasteOutcome::Raw,
             None,
         );
+        crate::latency_stats::record(
+            latency.with_paste(paste_started.elapsed().as_millis() as u64),
+        );
     }
     // Only spawn refinement when the model is loaded. On bypass we
     // skip it entirely: the user already has the raw paste, and spawning now
     // would fire the refining pulse and a cold-load GPU spike for a result no
     // one is waiting on. The eager-warmup keeps loading the model for next time.
     if should_refine {
+        // The refinement runs on the pasted rendition; note which one so
+        // history does not present it as a refinement of the stored text.
+        if let Some(id) = &entry_id {
+            let _ = crate::state::mark_entry_refinement_rendition(
+                app_handle,
+                id,
+                Rendition::from_setting(&settings.paste_rendition).as_str(),
+            );
+        }
         maybe_spawn_ai_refinement(
             app_handle.clone(),
-            processed_text.clone(),
+            paste_text,
             source.to_string(),
             "transcription_postprocess",
             job_id,
@@ -1900,6 +2583,32 @@ pub(crate) fn maybe_spawn_ai_refinement(
                             "model": result.model,
                         })
                     );
+                    // The refined text goes out through the same output template,
+                    // stamped with its history entry's time like the raw paste.
+                    let speaker =
+                        crate::state::speaker_name_for_source(&settings_snapshot, &source);
+                    let entry_timestamp_ms = entry_id
+                        .as_deref()
+                        .and_then(|id| {
+                            let state = app_handle.state::<AppState>();
+                            [&state.history, &state.history_transcribe]
+                                .into_iter()
+                                .find_map(|history| {
+                                    history
+                                        .lock()
+                                        .unwrap_or_else(|poisoned| poisoned.into_inner())
+                                        .find_entry(id)
+                                })
+                                .map(|entry| entry.timestamp_ms)
+                        })
+                        .unwrap_or_else(crate::util::now_ms);
+                    let output_text = crate::postprocessing::apply_output_template(
+                        &settings_snapshot,
+                        &source,
+                        &speaker,
+                        &result.text,
+                        entry_timestamp_ms,
+                    );
                     // Claim the paste before announcing the result. `pasted:
                     // false` means the deadline already pasted raw (or the job
                     // was never deferred) — the refined text is history-only.
@@ -1907,7 +2616,7 @@ pub(crate) fn maybe_spawn_ai_refinement(
                         &app_handle,
                         &job_id,
                         crate::paste_arbiter::PasteOutcome::Refined,
-                        Some(&result.text),
+                        Some(output_text.as_deref().unwrap_or(&result.text)),
                     );
                     let _ = app_handle.emit(
                         "transcription:refined",
@@ -2274,6 +2983,110 @@ pub(crate) fn end_refinement_activity(app_handle: &AppHandle, provider: &str, mo
     }
 }
 
+/// Sample count at which a recording is cut into a segment; `None` = no limit.
+fn max_recording_samples(max_recording_ms: u64) -> Option<usize> {
+    if max_recording_ms == 0 {
+        return None;
+    }
+    Some((TARGET_SAMPLE_RATE as u64 * max_recording_ms / 1000) as usize)
+}
+
+fn emit_max_duration_warning(app_handle: &AppHandle, source: &'static str, samples: usize) {
+    let segment_ms = samples as u64 * 1000 / TARGET_SAMPLE_RATE as u64;
+    warn!(
+        "{} recording reached the maximum duration; transcribing {} ms as a segment",
+        source, segment_ms
+    );
+    let _ = app_handle.emit(
+        MAX_DURATION_EVENT,
+        MaxDurationWarning { source, segment_ms },
+    );
+}
+
+/// Watches a PTT or toggle recording and cuts its buffer into a segment each
+/// time it reaches `max_recording_ms`, so a stuck key or forgotten toggle is
+/// transcribed in pieces instead of as one huge blob. Ends with the recording.
+fn spawn_max_duration_guard(app: &AppHandle, settings: &Settings) {
+    let session = MAX_DURATION_SESSION.fetch_add(1, Ordering::Relaxed) + 1;
+    let Some(max_samples) = max_recording_samples(settings.max_recording_ms) else {
+        return;
+    };
+    let app_handle = app.clone();
+    crate::util::spawn_guarded("max_duration_guard", move || loop {
+        thread::sleep(Duration::from_millis(MAX_DURATION_POLL_MS));
+        if MAX_DURATION_SESSION.load(Ordering::Relaxed) != session {
+            return;
+        }
+        let state = app_handle.state::<AppState>();
+        let buffer = {
+            let recorder = state
+                .recorder
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            // Continuous toggle mode has its own segmenter.
+            if !recorder.active || recorder.continuous_toggle_mode {
+                return;
+            }
+            recorder.buffer.clone()
+        };
+
+        let _segment_order = MIC_SEGMENT_ORDER
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let samples = {
+            let mut buf = buffer
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            if buf.samples.len() < max_samples {
+                continue;
+            }
+            buf.drain()
+        };
+        let captured_at = Instant::now();
+        emit_max_duration_warning(&app_handle, "ptt", samples.len());
+        if crate::privacy_mute::is_muted() {
+            continue;
+        }
+
+        let settings = state
+            .settings
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone();
+        let duration_ms = samples.len() as u64 * 1000 / TARGET_SAMPLE_RATE as u64;
+        let foreground = crate::app_overrides::foreground_app(&settings);
+        let result = run_job(&app_handle, JobSource::Mic, duration_ms, || {
+            transcribe_mic_audio(&app_handle, &settings, foreground.as_deref(), &samples)
+        });
+        match result {
+            Ok((text, source)) => {
+                handle_transcription_ok(
+                    &app_handle,
+                    &text,
+                    &source,
+                    &crate::app_overrides::for_app(&settings, foreground.as_deref()),
+                    rms_i16(&samples),
+                    duration_ms,
+                    TranscriptAudio {
+                        samples: &samples,
+                        captured_at,
+                        saved_path: None,
+                    },
+                );
+            }
+            Err(err) => emit_transcription_error(&app_handle, err),
+        }
+    });
+}
+
+/// Reports a failed transcription; cancelled jobs end silently.
+fn emit_transcription_error(app_handle: &AppHandle, err: String) {
+    if !is_cancellation(&err) {
+        crate::overlay::flash_overlay_error(app_handle);
+        let _ = app_handle.emit("transcription:error", err);
+    }
+}
+
 fn flush_mic_audio_to_session(buffer: &mut Vec<i16>) {
     if buffer.is_empty() {
         return;
@@ -2330,7 +3143,15 @@ fn process_toggle_segment(
     }
 
     let t_before_transcribe = std::time::Instant::now();
-    let result = transcribe_audio(app_handle, &effective_settings, &chunk);
+    let foreground = crate::app_overrides::foreground_app(&effective_settings);
+    let result = run_job(app_handle, JobSource::Mic, duration_ms, || {
+        transcribe_mic_audio(
+            app_handle,
+            &effective_settings,
+            foreground.as_deref(),
+            &chunk,
+        )
+    });
     if diagnostics_enabled {
         info!(
             "[TIMING] transcribe_audio done: {:.2}s (total since segment_start: {:.2}s)",
@@ -2350,9 +3171,14 @@ fn process_toggle_segment(
                 app_handle,
                 &text,
                 &source,
-                &effective_settings,
+                &crate::app_overrides::for_app(&effective_settings, foreground.as_deref()),
                 segment_rms,
                 duration_ms,
+                TranscriptAudio {
+                    samples: &chunk,
+                    captured_at: t_segment_start,
+                    saved_path: None,
+                },
             ) {
                 if diagnostics_enabled {
                     info!(
@@ -2373,9 +3199,7 @@ fn process_toggle_segment(
                 );
             }
         }
-        Err(err) => {
-            let _ = app_handle.emit("transcription:error", err);
-        }
+        Err(err) => emit_transcription_error(app_handle, err),
     }
 
     if diagnostics_enabled {
@@ -2416,14 +3240,13 @@ fn run_toggle_processor(
     let mut last_settings_check = Instant::now();
     let mut runtime_settings = initial_settings;
 
-    let auto_save = runtime_settings.auto_save_mic_audio && runtime_settings.opus_enabled;
+    let auto_save = runtime_settings.auto_save_mic_audio
+        && crate::session_manager::recording_available(&runtime_settings);
     let mut save_buffer: Vec<i16> = Vec::new();
     let flush_threshold = TARGET_SAMPLE_RATE as usize * 60;
 
     if auto_save {
-        let recordings_dir = crate::paths::resolve_recordings_dir(&app_handle);
-        let modules_dir = crate::paths::resolve_modules_dir(&app_handle);
-        crate::session_manager::init(recordings_dir, modules_dir);
+        crate::session_manager::init_from_settings(&app_handle, &runtime_settings);
     }
 
     loop {
@@ -2620,7 +3443,7 @@ pub(crate) fn stop_toggle_recording_async(app: AppHandle, state: &State<'_, AppS
 
         let _ = emit_capture_idle_overlay(&app_handle, &settings);
         if settings.audio_cues {
-            let _ = app_handle.emit("audio:cue", "stop");
+            crate::audio_cues::play_cue(&app_handle, "stop");
         }
     });
 }
@@ -2634,7 +3457,11 @@ pub(crate) fn start_vad_monitor(
     if diagnostics_enabled {
         info!("start_vad_monitor called");
     }
-    if !settings.capture_enabled {
+    if !settings.capture_enabled
+        || crate::privacy_mute::is_muted()
+        || crate::capture_policy::is_paused()
+        || crate::capture_schedule::mic_blocked()
+    {
         return Ok(());
     }
     let mut recorder = state
@@ -2652,11 +3479,8 @@ pub(crate) fn start_vad_monitor(
         buf.reset();
     }
 
-    recorder.input_gain_db.store(
-        (settings.mic_input_gain_db * 1000.0) as i64,
-        Ordering::Relaxed,
-    );
-    let gain_db = recorder.input_gain_db.clone();
+    recorder.apply_input_settings(settings);
+    let input = recorder.input_controls();
     let buffer = recorder.buffer.clone();
     let overlay_emitter = Arc::new(OverlayLevelEmitter::new(
         app.clone(),
@@ -2667,15 +3491,28 @@ pub(crate) fn start_vad_monitor(
     let (stop_tx, stop_rx) = std::sync::mpsc::channel::<()>();
     let (ready_tx, ready_rx) = std::sync::mpsc::channel::<Result<(), String>>();
     let (vad_tx, vad_rx) = std::sync::mpsc::channel::<VadEvent>();
-    let pre_roll_ms = settings
-        .continuous_pre_roll_ms
-        .clamp(VAD_PRE_ROLL_MS_MIN, VAD_PRE_ROLL_MS_MAX);
+    let pre_roll_ms = settings.pre_roll_ms.min(PRE_ROLL_MS_MAX);
     let pre_roll_samples = ((TARGET_SAMPLE_RATE as u64 * pre_roll_ms) / 1000) as usize;
     let pre_roll_min_samples = ((TARGET_SAMPLE_RATE as u64 * VAD_PRE_ROLL_MIN_MS) / 1000) as usize;
-    let pre_roll_buffer = Arc::new(Mutex::new(CaptureBuffer::default()));
+    // A PTT+VAD press starts from the standby's idle pre-roll instead of an
+    // empty ring.
+    let pre_roll_buffer = Arc::new(Mutex::new(CaptureBuffer {
+        samples: if settings.mode == "ptt" {
+            recorder.take_idle_pre_roll()
+        } else {
+            Vec::new()
+        },
+        ..CaptureBuffer::default()
+    }));
+    let wake = if settings.mode == "wakeword" {
+        Some(crate::wake_word::WakeWordGate::start(app, settings)?)
+    } else {
+        None
+    };
 
     let ptt_threshold_gate = settings.mode == "ptt" && settings.ptt_use_vad;
-    let flush_on_silence = settings.mode == "vad" && !ptt_threshold_gate;
+    let flush_on_silence =
+        crate::state::mode_uses_vad_monitor(&settings.mode) && !ptt_threshold_gate;
     let silence_ms = if ptt_threshold_gate {
         // In PTT+VAD we only gate capture start by threshold while key is held.
         // No silence-based auto-finalize should happen before key release.
@@ -2683,15 +3520,18 @@ pub(crate) fn start_vad_monitor(
     } else {
         settings.vad_silence_ms
     };
-    let vad_runtime = Arc::new(VadRuntime::new(
-        settings.audio_cues,
-        settings.vad_threshold_start,
-        settings.vad_threshold_sustain,
-        silence_ms,
-        flush_on_silence,
-        ptt_threshold_gate,
-        PTT_VAD_TAIL_MS,
-    ));
+    let vad_runtime = Arc::new(
+        VadRuntime::new(
+            settings.audio_cues,
+            settings.vad_threshold_start,
+            settings.vad_threshold_sustain,
+            silence_ms,
+            flush_on_silence,
+            ptt_threshold_gate,
+            PTT_VAD_TAIL_MS,
+        )
+        .with_max_recording_ms(settings.max_recording_ms),
+    );
     let vad_handle = VadHandle {
         runtime: vad_runtime.clone(),
         tx: vad_tx.clone(),
@@ -2699,6 +3539,7 @@ pub(crate) fn start_vad_monitor(
         pre_roll_buffer,
         pre_roll_samples,
         pre_roll_min_samples,
+        wake,
     };
 
     let app_handle = app.clone();
@@ -2728,7 +3569,7 @@ pub(crate) fn start_vad_monitor(
 
             let overlay = Some(overlay_emitter);
             let vad = Some(vad_handle);
-            let gain_db = gain_db.clone();
+            let input = input.clone();
             let stream = match config.sample_format() {
                 SampleFormat::F32 => build_input_stream_f32(
                     &device,
@@ -2736,7 +3577,7 @@ pub(crate) fn start_vad_monitor(
                     buffer,
                     overlay.clone(),
                     vad.clone(),
-                    gain_db.clone(),
+                    input.clone(),
                 )?,
                 SampleFormat::I16 => build_input_stream_i16(
                     &device,
@@ -2744,7 +3585,7 @@ pub(crate) fn start_vad_monitor(
                     buffer,
                     overlay.clone(),
                     vad.clone(),
-                    gain_db.clone(),
+                    input.clone(),
                 )?,
                 SampleFormat::U16 => build_input_stream_u16(
                     &device,
@@ -2752,7 +3593,7 @@ pub(crate) fn start_vad_monitor(
                     buffer,
                     overlay.clone(),
                     vad.clone(),
-                    gain_db.clone(),
+                    input.clone(),
                 )?,
                 _ => return Err("Unsupported sample format".to_string()),
             };
@@ -2872,6 +3713,7 @@ fn process_vad_segment(
     samples: Vec<i16>,
     runtime: Arc<VadRuntime>,
 ) {
+    let captured_at = Instant::now();
     let state = app_handle.state::<AppState>();
     if samples.is_empty() {
         runtime.pending_flush.store(false, Ordering::Relaxed);
@@ -2910,9 +3752,15 @@ fn process_vad_segment(
     let _ = app_handle.emit("capture:state", "transcribing");
     let _ = update_overlay_state(&app_handle, OverlayState::Transcribing);
 
-    let result = transcribe_audio(&app_handle, &settings, &samples);
-    let level = rms_i16(&samples);
+    let _segment_order = MIC_SEGMENT_ORDER
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
     let duration_ms = samples.len() as u64 * 1000 / TARGET_SAMPLE_RATE as u64;
+    let foreground = crate::app_overrides::foreground_app(&settings);
+    let result = run_job(&app_handle, JobSource::Mic, duration_ms, || {
+        transcribe_mic_audio(&app_handle, &settings, foreground.as_deref(), &samples)
+    });
+    let level = rms_i16(&samples);
 
     if let Ok(mut recorder) = state.recorder.lock() {
         recorder.transcribing = false;
@@ -2934,7 +3782,7 @@ fn process_vad_segment(
     }
 
     if settings.audio_cues {
-        let _ = app_handle.emit("audio:cue", "stop");
+        crate::audio_cues::play_cue(&app_handle, "stop");
     }
 
     match result {
@@ -2944,12 +3792,46 @@ fn process_vad_segment(
                 .read()
                 .unwrap_or_else(|poisoned| poisoned.into_inner())
                 .clone();
-            handle_transcription_ok(&app_handle, &text, &source, &settings, level, duration_ms);
-        }
-        Err(err) => {
-            let _ = app_handle.emit("transcription:error", err);
+            handle_transcription_ok(
+                &app_handle,
+                &text,
+                &source,
+                &crate::app_overrides::for_app(&settings, foreground.as_deref()),
+                level,
+                duration_ms,
+                TranscriptAudio {
+                    samples: &samples,
+                    captured_at,
+                    saved_path: None,
+                },
+            );
         }
+        Err(err) => emit_transcription_error(&app_handle, err),
+    }
+}
+
+/// With privacy mute on, a finished recording is dropped instead of
+/// transcribed, whichever capture path took it. Returns true when it was.
+fn discard_muted_recording(
+    app: &AppHandle,
+    state: &AppState,
+    settings: &Settings,
+    samples: &[i16],
+) -> bool {
+    if !crate::privacy_mute::is_muted() {
+        return false;
     }
+    info!(
+        "Privacy mute engaged: discarding {} ms of mic audio",
+        samples.len() as u64 * 1000 / TARGET_SAMPLE_RATE as u64
+    );
+    let _ = emit_capture_idle_overlay(app, settings);
+    state
+        .recorder
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .transcribing = false;
+    true
 }
 
 pub(crate) fn stop_recording_async(app: AppHandle, state: &State<'_, AppState>) {
@@ -2999,6 +3881,11 @@ pub(crate) fn stop_recording_async(app: AppHandle, state: &State<'_, AppState>)
                     .unwrap_or_else(|poisoned| poisoned.into_inner());
                 buf.drain()
             };
+            let captured_at = Instant::now();
+
+            if discard_muted_recording(&app_handle, &state, &settings, &samples) {
+                return;
+            }
 
             let min_samples = mic_min_samples();
             if samples.len() < min_samples {
@@ -3024,9 +3911,15 @@ pub(crate) fn stop_recording_async(app: AppHandle, state: &State<'_, AppState>)
             let _ = app_handle.emit("capture:state", "transcribing");
             let _ = update_overlay_state(&app_handle, OverlayState::Transcribing);
 
-            let result = transcribe_audio(&app_handle, &settings, &samples);
-            let level = rms_i16(&samples);
+            let _segment_order = MIC_SEGMENT_ORDER
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
             let duration_ms = samples.len() as u64 * 1000 / TARGET_SAMPLE_RATE as u64;
+            let foreground = crate::app_overrides::foreground_app(&settings);
+            let result = run_job(&app_handle, JobSource::Mic, duration_ms, || {
+                transcribe_mic_audio(&app_handle, &settings, foreground.as_deref(), &samples)
+            });
+            let level = rms_i16(&samples);
 
             {
                 let mut recorder = state
@@ -3039,7 +3932,7 @@ pub(crate) fn stop_recording_async(app: AppHandle, state: &State<'_, AppState>)
             let _ = emit_capture_idle_overlay(&app_handle, &settings);
 
             if settings.audio_cues {
-                let _ = app_handle.emit("audio:cue", "stop");
+                crate::audio_cues::play_cue(&app_handle, "stop");
             }
 
             match result {
@@ -3053,14 +3946,17 @@ pub(crate) fn stop_recording_async(app: AppHandle, state: &State<'_, AppState>)
                         &app_handle,
                         &text,
                         &source,
-                        &settings,
+                        &crate::app_overrides::for_app(&settings, foreground.as_deref()),
                         level,
                         duration_ms,
+                        TranscriptAudio {
+                            samples: &samples,
+                            captured_at,
+                            saved_path: None,
+                        },
                     );
                 }
-                Err(err) => {
-                    let _ = app_handle.emit("transcription:error", err);
-                }
+                Err(err) => emit_transcription_error(&app_handle, err),
             }
 
             // PTT standby stays warm indefinitely (no shutdown on release).
@@ -3116,7 +4012,7 @@ pub(crate) fn stop_recording_async(app: AppHandle, state: &State<'_, AppState>)
             let _ = join_handle.join();
             let _ = emit_capture_idle_overlay(&app_handle, &settings);
             if settings.audio_cues {
-                let _ = app_handle.emit("audio:cue", "stop");
+                crate::audio_cues::play_cue(&app_handle, "stop");
             }
             return;
         }
@@ -3127,6 +4023,11 @@ pub(crate) fn stop_recording_async(app: AppHandle, state: &State<'_, AppState>)
                 .unwrap_or_else(|poisoned| poisoned.into_inner());
             buf.drain()
         };
+        let captured_at = Instant::now();
+
+        if discard_muted_recording(&app_handle, &state, &settings, &samples) {
+            return;
+        }
 
         let min_samples = mic_min_samples();
         if samples.len() < min_samples {
@@ -3149,12 +4050,19 @@ pub(crate) fn stop_recording_async(app: AppHandle, state: &State<'_, AppState>)
         let _ = app_handle.emit("capture:state", "transcribing");
         let _ = update_overlay_state(&app_handle, OverlayState::Transcribing);
 
-        let result = transcribe_audio(&app_handle, &settings, &samples);
-        let level = rms_i16(&samples);
+        let _segment_order = MIC_SEGMENT_ORDER
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
         let duration_ms = samples.len() as u64 * 1000 / TARGET_SAMPLE_RATE as u64;
+        let foreground = crate::app_overrides::foreground_app(&settings);
+        let result = run_job(&app_handle, JobSource::Mic, duration_ms, || {
+            transcribe_mic_audio(&app_handle, &settings, foreground.as_deref(), &samples)
+        });
+        let level = rms_i16(&samples);
 
         // Save recording as OPUS for optional later processing/export.
         // Only save if duration > 10 seconds (avoid short dictations)
+        let mut audio_path = None;
         if duration_ms >= 10_000 {
             if let Ok(Some(opus_path)) =
                 crate::save_recording_opus(&app_handle, &samples, "mic", None)
@@ -3163,7 +4071,8 @@ pub(crate) fn stop_recording_async(app: AppHandle, state: &State<'_, AppState>)
                 *state_ref
                     .last_mic_recording_path
                     .lock()
-                    .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(opus_path);
+                    .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(opus_path.clone());
+                audio_path = Some(opus_path);
             }
         }
 
@@ -3177,7 +4086,7 @@ pub(crate) fn stop_recording_async(app: AppHandle, state: &State<'_, AppState>)
         let _ = emit_capture_idle_overlay(&app_handle, &settings);
 
         if settings.audio_cues {
-            let _ = app_handle.emit("audio:cue", "stop");
+            crate::audio_cues::play_cue(&app_handle, "stop");
         }
 
         match result {
@@ -3187,11 +4096,21 @@ pub(crate) fn stop_recording_async(app: AppHandle, state: &State<'_, AppState>)
                     .read()
                     .unwrap_or_else(|poisoned| poisoned.into_inner())
                     .clone();
-                handle_transcription_ok(&app_handle, &text, &source, &settings, level, duration_ms);
-            }
-            Err(err) => {
-                let _ = app_handle.emit("transcription:error", err);
+                handle_transcription_ok(
+                    &app_handle,
+                    &text,
+                    &source,
+                    &crate::app_overrides::for_app(&settings, foreground.as_deref()),
+                    level,
+                    duration_ms,
+                    TranscriptAudio {
+                        samples: &samples,
+                        captured_at,
+                        saved_path: audio_path,
+                    },
+                );
             }
+            Err(err) => emit_transcription_error(&app_handle, err),
         }
     });
 }
@@ -3200,7 +4119,7 @@ pub(crate) fn stop_recording_async(app: AppHandle, state: &State<'_, AppState>)
 pub(crate) fn get_last_recording_path(
     source: String,
     state: State<'_, AppState>,
-) -> Result<Option<String>, String> {
+) -> CommandResult<Option<String>> {
     let path = if source == "output" || source == "system" {
         state
             .last_system_recording_path
@@ -3218,18 +4137,27 @@ pub(crate) fn get_last_recording_path(
 }
 
 #[tauri::command]
-pub(crate) fn get_recordings_directory(app: AppHandle) -> Result<String, String> {
-    let data_dir = crate::paths::resolve_base_dir(&app);
-    let recordings_dir = data_dir.join("recordings");
+pub(crate) fn get_recordings_directory(app: AppHandle) -> CommandResult<String> {
+    let settings = app
+        .state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    let recordings_dir = crate::session_manager::recordings_dir_for(&app, &settings);
 
-    std::fs::create_dir_all(&recordings_dir)
-        .map_err(|e| format!("Failed to create recordings dir: {}", e))?;
+    std::fs::create_dir_all(&recordings_dir).map_err(|e| {
+        CommandError::new(
+            ErrorCode::Storage,
+            format!("Failed to create recordings dir: {}", e),
+        )
+    })?;
 
     Ok(recordings_dir.to_string_lossy().to_string())
 }
 
 #[tauri::command]
-pub(crate) fn open_recordings_directory(app: AppHandle) -> Result<(), String> {
+pub(crate) fn open_recordings_directory(app: AppHandle) -> CommandResult<()> {
     let recordings_dir = get_recordings_directory(app.clone())?;
 
     #[cfg(target_os = "windows")]
@@ -3237,7 +4165,12 @@ pub(crate) fn open_recordings_directory(app: AppHandle) -> Result<(), String> {
         std::process::Command::new("explorer")
             .arg(&recordings_dir)
             .spawn()
-            .map_err(|e| format!("Failed to open directory: {}", e))?;
+            .map_err(|e| {
+                CommandError::new(
+                    ErrorCode::Window,
+                    format!("Failed to open directory: {}", e),
+                )
+            })?;
     }
 
     #[cfg(target_os = "macos")]
@@ -3245,7 +4178,12 @@ pub(crate) fn open_recordings_directory(app: AppHandle) -> Result<(), String> {
         std::process::Command::new("open")
             .arg(&recordings_dir)
             .spawn()
-            .map_err(|e| format!("Failed to open directory: {}", e))?;
+            .map_err(|e| {
+                CommandError::new(
+                    ErrorCode::Window,
+                    format!("Failed to open directory: {}", e),
+                )
+            })?;
     }
 
     #[cfg(target_os = "linux")]
@@ -3253,7 +4191,12 @@ pub(crate) fn open_recordings_directory(app: AppHandle) -> Result<(), String> {
         std::process::Command::new("xdg-open")
             .arg(&recordings_dir)
             .spawn()
-            .map_err(|e| format!("Failed to open directory: {}", e))?;
+            .map_err(|e| {
+                CommandError::new(
+                    ErrorCode::Window,
+                    format!("Failed to open directory: {}", e),
+                )
+            })?;
     }
 
     Ok(())
@@ -3276,6 +4219,10 @@ pub(crate) fn handle_ptt_press(app: &AppHandle) -> Result<(), String> {
         }
         return Ok(());
     }
+    if crate::privacy_mute::is_muted() {
+        info!("handle_ptt_press: privacy mute engaged -> no-op");
+        return Ok(());
+    }
     if settings.mode != "ptt" {
         if diagnostics_enabled {
             info!("handle_ptt_press: mode is '{}' -> no-op", settings.mode);
@@ -3283,11 +4230,15 @@ pub(crate) fn handle_ptt_press(app: &AppHandle) -> Result<(), String> {
         return Ok(());
     }
 
+    // A new dictation supersedes the previous one: stop its whisper run so
+    // the older text cannot paste after the new one.
+    crate::transcription_jobs::cancel_source(app, JobSource::Mic);
+
     // Emit the audio cue immediately so the user gets instant feedback that
     // the button press was registered — before anything that could block
     // (standby cold-start, whisper warmup, OLLAMA warmup).
     if settings.audio_cues {
-        let _ = app.emit("audio:cue", "start");
+        crate::audio_cues::play_cue(app, "start");
     }
 
     // Show the overlay in Recording state immediately, before anything that can
@@ -3413,7 +4364,7 @@ pub(crate) fn handle_toggle_async(app: AppHandle) {
         .read()
         .unwrap_or_else(|poisoned| poisoned.into_inner())
         .clone();
-    if !settings.capture_enabled {
+    if !settings.capture_enabled || crate::privacy_mute::is_muted() {
         return;
     }
     if settings.mode != "ptt" {
@@ -3434,7 +4385,7 @@ pub(crate) fn handle_toggle_async(app: AppHandle) {
             stop_recording_async(app, &state);
         }
     } else {
-        if settings.continuous_dump_enabled {
+        if settings.continuous_dump_enabled || settings.mic_flush_interval_ms > 0 {
             let _ = start_toggle_recording_with_settings(&app, &state, &settings);
         } else {
             let _ = start_recording_with_settings(&app, &state, &settings);
@@ -3443,21 +4394,103 @@ pub(crate) fn handle_toggle_async(app: AppHandle) {
 }
 
 #[tauri::command]
-pub(crate) fn start_recording(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+pub(crate) fn start_recording(app: AppHandle, state: State<'_, AppState>) -> CommandResult<()> {
     let settings = state
         .settings
         .read()
         .unwrap_or_else(|poisoned| poisoned.into_inner())
         .clone();
-    start_recording_with_settings(&app, &state, &settings)
+    start_recording_with_settings(&app, &state, &settings).with_code(ErrorCode::AudioDevice)
 }
 
 #[tauri::command]
-pub(crate) fn stop_recording(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+pub(crate) fn stop_recording(app: AppHandle, state: State<'_, AppState>) -> CommandResult<()> {
     stop_recording_async(app, &state);
     Ok(())
 }
 
+#[cfg(test)]
+mod wav_decode_tests {
+    use super::decode_wav_to_mono_16k;
+
+    #[test]
+    fn stereo_48k_wav_is_downmixed_and_resampled() {
+        let spec = hound::WavSpec {
+            channels: 2,
+            sample_rate: 48_000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        {
+            let mut writer = hound::WavWriter::new(&mut bytes, spec).unwrap();
+            for _ in 0..48_000 {
+                writer.write_sample(8_000i16).unwrap();
+                writer.write_sample(-8_000i16).unwrap();
+            }
+            writer.finalize().unwrap();
+        }
+
+        let samples = decode_wav_to_mono_16k(bytes.get_ref()).unwrap();
+        assert!((15_990..=16_000).contains(&samples.len()));
+        assert!(samples.iter().all(|sample| sample.abs() <= 1));
+        assert!(decode_wav_to_mono_16k(b"not a wav").is_err());
+    }
+}
+
+#[cfg(test)]
+mod input_channel_tests {
+    use super::selected_channel_index;
+
+    #[test]
+    fn channel_zero_or_out_of_range_downmixes() {
+        assert_eq!(selected_channel_index(0, 2), None);
+        assert_eq!(selected_channel_index(3, 2), None);
+        assert_eq!(selected_channel_index(1, 2), Some(0));
+        assert_eq!(selected_channel_index(2, 2), Some(1));
+    }
+}
+
+#[cfg(test)]
+mod max_recording_tests {
+    use super::max_recording_samples;
+
+    #[test]
+    fn max_recording_limit_converts_to_samples() {
+        assert_eq!(max_recording_samples(0), None);
+        assert_eq!(max_recording_samples(1_000), Some(16_000));
+        assert_eq!(max_recording_samples(300_000), Some(4_800_000));
+    }
+}
+
+#[cfg(test)]
+mod mic_flush_tests {
+    use super::mic_segmenter_config;
+    use crate::continuous_dump::{AdaptiveSegmenter, SegmentFlushReason};
+    use crate::state::Settings;
+
+    #[test]
+    fn periodic_flush_waits_for_a_pause_after_the_interval() {
+        let mut settings = Settings::default();
+        settings.continuous_dump_enabled = false;
+        settings.mic_flush_interval_ms = 10_000;
+        let mut segmenter = AdaptiveSegmenter::new(mic_segmenter_config(&settings));
+        let speech = vec![1000i16; 16_000];
+
+        // A short breath before the interval is not a cut point.
+        for _ in 0..4 {
+            assert!(segmenter.push_samples(&speech, 0.08).is_empty());
+        }
+        assert!(segmenter.push_samples(&[0; 4_800], 0.0).is_empty());
+        for _ in 0..8 {
+            assert!(segmenter.push_samples(&speech, 0.08).is_empty());
+        }
+        let out = segmenter.push_samples(&[0; 4_800], 0.0);
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].reason, SegmentFlushReason::SoftInterval);
+    }
+}
+
 #[cfg(test)]
 mod refinement_defer_policy_tests {
     use super::{is_ollama_model_not_found_message, should_defer_paste_for_refinement_inner};
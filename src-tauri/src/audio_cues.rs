@@ -0,0 +1,248 @@
+//! Audible cues for recording start/stop, errors and pastes.
+//!
+//! Cues used to be beeped by the webview on `audio:cue`, which Chromium
+//! delays or drops while the window is hidden or suspended — exactly when
+//! the user is dictating into another app. They are now played here on the
+//! default output device. `audio:cue` is still emitted for UI feedback.
+//!
+//! A sound pack is a folder under `<data>/sound_packs` holding
+//! `start`/`stop`/`error`/`paste` files (wav, ogg, mp3 or flac). Cues the
+//! pack does not provide fall back to the built-in tones.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::warn;
+
+use crate::constants::TARGET_SAMPLE_RATE;
+use crate::errors::{CommandError, CommandResult, ErrorCode};
+use crate::state::{AppState, Settings};
+
+pub(crate) const CUE_EVENT: &str = "audio:cue";
+pub(crate) const DEFAULT_SOUND_PACK: &str = "default";
+const CUE_NAMES: [&str; 4] = ["start", "stop", "error", "paste"];
+const PACK_EXTENSIONS: [&str; 4] = ["wav", "ogg", "mp3", "flac"];
+const TONE_SAMPLE_RATE: u32 = 48_000;
+const TONE_FADE_IN_MS: f32 = 10.0;
+/// Errors this soon after the last error cue stay silent.
+const ERROR_CUE_DEBOUNCE_MS: u64 = 10_000;
+
+static LAST_ERROR_CUE_MS: AtomicU64 = AtomicU64::new(0);
+
+/// Pack names are plain folder names, never paths.
+pub(crate) fn is_valid_pack_name(name: &str) -> bool {
+    !name.trim().is_empty() && name != "." && name != ".." && !name.contains(['/', '\\', ':'])
+}
+
+fn sound_packs_dir(app: &AppHandle) -> PathBuf {
+    let dir = crate::paths::resolve_base_dir(app).join("sound_packs");
+    let _ = std::fs::create_dir_all(&dir);
+    dir
+}
+
+/// Built-in cue: a short sweep from/to these frequencies (Hz) over `ms`.
+fn tone_shape(cue: &str) -> Option<(f32, f32, f32)> {
+    match cue {
+        "start" => Some((600.0, 800.0, 100.0)),
+        "stop" => Some((800.0, 600.0, 100.0)),
+        "error" => Some((440.0, 330.0, 180.0)),
+        "paste" => Some((1_000.0, 1_000.0, 40.0)),
+        _ => None,
+    }
+}
+
+/// Renders the built-in tone with a quick fade in and a linear fade out.
+fn tone(cue: &str) -> Option<Vec<f32>> {
+    let (from_hz, to_hz, ms) = tone_shape(cue)?;
+    let rate = TONE_SAMPLE_RATE as f32;
+    let len = (rate * ms / 1000.0) as usize;
+    let fade_in = (rate * TONE_FADE_IN_MS / 1000.0) as usize;
+    let mut phase = 0.0f32;
+    Some(
+        (0..len)
+            .map(|i| {
+                let progress = i as f32 / len as f32;
+                let freq = from_hz + (to_hz - from_hz) * progress;
+                phase = (phase + std::f32::consts::TAU * freq / rate) % std::f32::consts::TAU;
+                let envelope = if i < fade_in {
+                    i as f32 / fade_in as f32
+                } else {
+                    (len - i) as f32 / (len - fade_in) as f32
+                };
+                phase.sin() * envelope
+            })
+            .collect(),
+    )
+}
+
+/// The pack's file for `cue`, decoded to mono.
+fn pack_sound(app: &AppHandle, pack: &str, cue: &str) -> Option<Vec<f32>> {
+    let dir = sound_packs_dir(app).join(pack);
+    let path = PACK_EXTENSIONS
+        .iter()
+        .map(|ext| dir.join(format!("{}.{}", cue, ext)))
+        .find(|path| path.is_file())?;
+    match crate::file_transcription::decode_with_symphonia(&path) {
+        Ok(samples) => Some(
+            samples
+                .into_iter()
+                .map(|sample| sample as f32 / i16::MAX as f32)
+                .collect(),
+        ),
+        Err(err) => {
+            warn!("Sound pack '{}' cue '{}' unusable: {}", pack, cue, err);
+            None
+        }
+    }
+}
+
+fn play(app: &AppHandle, cue: &'static str, pack: String, volume: f32) {
+    let app = app.clone();
+    crate::util::spawn_guarded("audio_cue", move || {
+        let custom = if pack == DEFAULT_SOUND_PACK {
+            None
+        } else {
+            pack_sound(&app, &pack, cue)
+        };
+        let (samples, sample_rate) = match custom {
+            Some(samples) => (samples, TARGET_SAMPLE_RATE),
+            None => match tone(cue) {
+                Some(samples) => (samples, TONE_SAMPLE_RATE),
+                None => return,
+            },
+        };
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate,
+            bits_per_sample: 32,
+            sample_format: hound::SampleFormat::Float,
+        };
+        if let Err(err) =
+            crate::multimodal_io::play_samples_blocking(&samples, spec, volume, "default", None)
+        {
+            warn!("Audio cue '{}' failed to play: {}", cue, err);
+        }
+    });
+}
+
+fn cue_name(cue: &str) -> Option<&'static str> {
+    CUE_NAMES.iter().copied().find(|name| *name == cue)
+}
+
+fn cue_volume(settings: &Settings, cue: &str) -> f32 {
+    settings.audio_cues_volume * settings.audio_cue_volumes.get(cue).unwrap_or(1.0)
+}
+
+/// Emits `audio:cue` and plays the cue when cues are enabled.
+pub(crate) fn play_cue(app: &AppHandle, cue: &str) {
+    let _ = app.emit(CUE_EVENT, cue);
+    let Some(cue) = cue_name(cue) else {
+        return;
+    };
+    let (enabled, pack, volume) = {
+        let settings = app
+            .state::<AppState>()
+            .settings
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        (
+            settings.audio_cues,
+            settings.audio_cue_pack.clone(),
+            cue_volume(&settings, cue),
+        )
+    };
+    if enabled && volume > 0.0 {
+        play(app, cue, pack, volume);
+    }
+}
+
+/// The error cue, at most once per `ERROR_CUE_DEBOUNCE_MS`, so a run of
+/// failing loopback chunks beeps once instead of once per chunk.
+pub(crate) fn play_error_cue(app: &AppHandle) {
+    let now = crate::util::now_ms();
+    let last = LAST_ERROR_CUE_MS.load(Ordering::Relaxed);
+    if now.saturating_sub(last) < ERROR_CUE_DEBOUNCE_MS {
+        return;
+    }
+    if LAST_ERROR_CUE_MS
+        .compare_exchange(last, now, Ordering::Relaxed, Ordering::Relaxed)
+        .is_ok()
+    {
+        play_cue(app, "error");
+    }
+}
+
+/// Plays `cue` regardless of `audio_cues`, optionally from another pack or
+/// at another volume, so the settings page can audition changes unsaved.
+#[tauri::command]
+pub(crate) fn preview_cue(
+    app: AppHandle,
+    cue: String,
+    pack: Option<String>,
+    volume: Option<f32>,
+) -> CommandResult<()> {
+    let cue = cue_name(&cue).ok_or_else(|| {
+        CommandError::new(ErrorCode::InvalidInput, format!("Unknown cue '{}'", cue))
+    })?;
+    let settings = app
+        .state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    let pack = pack.unwrap_or_else(|| settings.audio_cue_pack.clone());
+    if !is_valid_pack_name(&pack) {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            format!("Invalid sound pack '{}'", pack),
+        ));
+    }
+    let volume = volume
+        .unwrap_or_else(|| cue_volume(&settings, cue))
+        .clamp(0.0, 1.0);
+    play(&app, cue, pack, volume);
+    Ok(())
+}
+
+/// "default" followed by the installed pack folders.
+#[tauri::command]
+pub(crate) fn list_sound_packs(app: AppHandle) -> Vec<String> {
+    let mut packs: Vec<String> = std::fs::read_dir(sound_packs_dir(&app))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| name != DEFAULT_SOUND_PACK && is_valid_pack_name(name))
+        .collect();
+    packs.sort();
+    packs.insert(0, DEFAULT_SOUND_PACK.to_string());
+    packs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_tones_fade_in_and_out() {
+        for cue in CUE_NAMES {
+            let samples = tone(cue).unwrap();
+            let (_, _, ms) = tone_shape(cue).unwrap();
+            assert_eq!(
+                samples.len(),
+                (TONE_SAMPLE_RATE as f32 * ms / 1000.0) as usize
+            );
+            assert!(samples[0].abs() < 0.01);
+            assert!(samples[samples.len() - 1].abs() < 0.01);
+            assert!(samples.iter().all(|sample| sample.abs() <= 1.0));
+        }
+        assert!(tone("chime").is_none());
+
+        assert!(is_valid_pack_name("retro"));
+        assert!(!is_valid_pack_name("../retro"));
+        assert!(!is_valid_pack_name(".."));
+        assert!(!is_valid_pack_name(" "));
+    }
+}
@@ -0,0 +1,886 @@
+// Mic pre-processing chain applied inside the capture callback, before the
+// level meter, VAD and whisper see the samples.
+//
+// Stages (each optional, in order):
+//   0. Echo cancellation — WebRTC AEC removes the speaker output (cues,
+//      system audio) from `echo_reference` as it leaks into the mic, so VAD
+//      does not trigger on playback. Its delay-agnostic mode estimates the
+//      loopback-to-mic delay itself. Runs first, while the mic signal is
+//      still a linear function of the echo, on 10 ms frames at 48 kHz (one
+//      frame of latency while enabled).
+//   1. High-pass biquad (~80 Hz) — removes fan/HVAC rumble and mains hum
+//      energy that otherwise keeps the VAD above its start threshold.
+//   2. Noise suppression — RNNoise (nnnoiseless), a small recurrent network
+//      trained to keep speech and drop café chatter, keyboard and
+//      ventilation noise. Same 48 kHz framing and 10 ms of latency as the
+//      echo canceller.
+//   3. Spectral noise gate — an STFT that learns a noise floor per frequency
+//      bin and passes a bin only while it rises clearly above it, so a steady
+//      fan is gated even under speech. Blocks whose RMS stays below the dBFS
+//      threshold close every bin; hold/release smoothing keeps word tails.
+//      One FFT frame (~21-32 ms) of latency while enabled.
+//
+// After the chain, optional automatic gain control watches the speech level
+// of the processed blocks and steers the shared input gain atomic toward a
+// target, so quiet mics stop producing transcripts the hallucination filter
+// drops. It only listens while someone is speaking (VAD utterance or a held
+// push-to-talk) so room noise between utterances is never amplified, and it
+// boosts by at most `AGC_MAX_BOOST_DB`.
+//
+// `MicDspControls` is shared with the settings path (atomics, like
+// `Recorder::input_gain_db`) so changes apply to a running stream; each
+// stream owns its own `MicDspChain` with the filter state.
+
+use crate::state::Settings;
+use rustfft::num_complex::Complex;
+use rustfft::{Fft, FftPlanner};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::Arc;
+
+pub(crate) const INPUT_GAIN_MIN_DB: f32 = -30.0;
+pub(crate) const INPUT_GAIN_MAX_DB: f32 = 30.0;
+pub(crate) const AGC_TARGET_MIN_DB: f32 = -40.0;
+pub(crate) const AGC_TARGET_MAX_DB: f32 = -6.0;
+
+pub(crate) const HIGHPASS_CUTOFF_MIN_HZ: f32 = 20.0;
+pub(crate) const HIGHPASS_CUTOFF_MAX_HZ: f32 = 400.0;
+pub(crate) const NOISE_GATE_THRESHOLD_MIN_DB: f32 = -90.0;
+pub(crate) const NOISE_GATE_THRESHOLD_MAX_DB: f32 = -10.0;
+
+const NOISE_GATE_FLOOR_DB: f32 = -30.0;
+const NOISE_GATE_HOLD_MS: f32 = 200.0;
+const NOISE_GATE_ATTACK_MS: f32 = 2.0;
+const NOISE_GATE_RELEASE_MS: f32 = 60.0;
+/// How far above its noise floor a bin must be to pass.
+const NOISE_GATE_MARGIN_DB: f32 = 10.0;
+/// How fast a bin's noise floor follows a louder steady signal upward.
+const NOISE_GATE_FLOOR_RISE_DB_PER_S: f32 = 6.0;
+/// Per-frame smoothing of bin levels, so random noise peaks stay under the margin.
+const NOISE_GATE_LEVEL_SMOOTHING: f32 = 0.3;
+
+const AGC_WINDOW_MS: f32 = 1_500.0;
+const AGC_SPEECH_FLOOR_DB: f32 = -55.0;
+const AGC_MIN_SPEECH_MS: f32 = 300.0;
+const AGC_MAX_RAISE_DB_PER_S: f32 = 6.0;
+const AGC_MAX_CUT_DB_PER_S: f32 = 18.0;
+/// Highest gain AGC raises to; a manual gain above it is only ever lowered.
+const AGC_MAX_BOOST_DB: f32 = 12.0;
+
+/// Rate and frame size both the WebRTC processor and RNNoise work at.
+const FRAME_RATE: u32 = 48_000;
+const FRAME_LEN: usize = webrtc_audio_processing::NUM_SAMPLES_PER_FRAME as usize;
+const I16_SCALE: f32 = 32_768.0;
+
+pub(crate) struct MicDspControls {
+    echo_cancellation_enabled: AtomicBool,
+    highpass_enabled: AtomicBool,
+    highpass_cutoff_milli_hz: AtomicI64,
+    noise_suppression_enabled: AtomicBool,
+    noise_gate_enabled: AtomicBool,
+    noise_gate_threshold_milli_db: AtomicI64,
+    agc_enabled: AtomicBool,
+    agc_target_milli_db: AtomicI64,
+}
+
+impl Default for MicDspControls {
+    fn default() -> Self {
+        Self {
+            echo_cancellation_enabled: AtomicBool::new(false),
+            highpass_enabled: AtomicBool::new(false),
+            highpass_cutoff_milli_hz: AtomicI64::new(80_000),
+            noise_suppression_enabled: AtomicBool::new(false),
+            noise_gate_enabled: AtomicBool::new(false),
+            noise_gate_threshold_milli_db: AtomicI64::new(-50_000),
+            agc_enabled: AtomicBool::new(false),
+            agc_target_milli_db: AtomicI64::new(-20_000),
+        }
+    }
+}
+
+impl MicDspControls {
+    pub(crate) fn apply_settings(&self, settings: &Settings) {
+        self.echo_cancellation_enabled
+            .store(settings.mic_echo_cancellation, Ordering::Relaxed);
+        self.highpass_enabled
+            .store(settings.mic_highpass_enabled, Ordering::Relaxed);
+        self.highpass_cutoff_milli_hz.store(
+            (clamp_highpass_cutoff_hz(settings.mic_highpass_cutoff_hz) * 1000.0) as i64,
+            Ordering::Relaxed,
+        );
+        self.noise_suppression_enabled
+            .store(settings.noise_suppression, Ordering::Relaxed);
+        self.noise_gate_enabled
+            .store(settings.mic_noise_gate_enabled, Ordering::Relaxed);
+        self.noise_gate_threshold_milli_db.store(
+            (clamp_noise_gate_threshold_db(settings.mic_noise_gate_threshold_db) * 1000.0) as i64,
+            Ordering::Relaxed,
+        );
+        self.agc_enabled
+            .store(settings.mic_agc_enabled, Ordering::Relaxed);
+        self.agc_target_milli_db.store(
+            (clamp_agc_target_db(settings.mic_agc_target_db) * 1000.0) as i64,
+            Ordering::Relaxed,
+        );
+    }
+
+    pub(crate) fn agc_enabled(&self) -> bool {
+        self.agc_enabled.load(Ordering::Relaxed)
+    }
+
+    fn agc_target_db(&self) -> Option<f32> {
+        self.agc_enabled()
+            .then(|| self.agc_target_milli_db.load(Ordering::Relaxed) as f32 / 1000.0)
+    }
+
+    fn highpass_cutoff_hz(&self) -> Option<f32> {
+        self.highpass_enabled
+            .load(Ordering::Relaxed)
+            .then(|| self.highpass_cutoff_milli_hz.load(Ordering::Relaxed) as f32 / 1000.0)
+    }
+
+    fn noise_gate_threshold_db(&self) -> Option<f32> {
+        self.noise_gate_enabled
+            .load(Ordering::Relaxed)
+            .then(|| self.noise_gate_threshold_milli_db.load(Ordering::Relaxed) as f32 / 1000.0)
+    }
+}
+
+pub(crate) fn clamp_highpass_cutoff_hz(value: f32) -> f32 {
+    if value.is_finite() {
+        value.clamp(HIGHPASS_CUTOFF_MIN_HZ, HIGHPASS_CUTOFF_MAX_HZ)
+    } else {
+        80.0
+    }
+}
+
+pub(crate) fn clamp_noise_gate_threshold_db(value: f32) -> f32 {
+    if value.is_finite() {
+        value.clamp(NOISE_GATE_THRESHOLD_MIN_DB, NOISE_GATE_THRESHOLD_MAX_DB)
+    } else {
+        -50.0
+    }
+}
+
+pub(crate) fn clamp_agc_target_db(value: f32) -> f32 {
+    if value.is_finite() {
+        value.clamp(AGC_TARGET_MIN_DB, AGC_TARGET_MAX_DB)
+    } else {
+        -20.0
+    }
+}
+
+/// Streaming linear-interpolation resampler. Keeps the last input sample and
+/// the fractional read position so consecutive blocks join without clicks.
+struct LinearResampler {
+    step: f64,
+    pos: f64,
+    last: f32,
+}
+
+impl LinearResampler {
+    fn new(from_rate: u32, to_rate: u32) -> Self {
+        Self {
+            step: from_rate.max(1) as f64 / to_rate.max(1) as f64,
+            // Start on the first real sample, not the silent `last`.
+            pos: 1.0,
+            last: 0.0,
+        }
+    }
+
+    fn process(&mut self, input: &[f32], output: &mut Vec<f32>) {
+        let Some(&newest) = input.last() else {
+            return;
+        };
+        // Positions index [last, input..]; position 0 is `last`.
+        let at = |index: usize| {
+            if index == 0 {
+                self.last
+            } else {
+                input[index - 1]
+            }
+        };
+        while self.pos < input.len() as f64 {
+            let index = self.pos as usize;
+            let frac = (self.pos - index as f64) as f32;
+            let (a, b) = (at(index), at(index + 1));
+            output.push(a + (b - a) * frac);
+            self.pos += self.step;
+        }
+        self.pos -= input.len() as f64;
+        self.last = newest;
+    }
+}
+
+/// Runs a stage that needs fixed 10 ms frames at 48 kHz inside a stream of
+/// arbitrary blocks at the mic rate: resamples in, cuts frames, resamples the
+/// processed frames back. One frame of priming silence keeps the output
+/// queue from underrunning, so the stage adds a fixed 10 ms of latency.
+struct FrameAdapter {
+    to_frames: Option<LinearResampler>,
+    from_frames: Option<LinearResampler>,
+    pending: Vec<f32>,
+    resampled: Vec<f32>,
+    output: VecDeque<f32>,
+}
+
+impl FrameAdapter {
+    fn new(sample_rate: u32) -> Self {
+        let resample = sample_rate != FRAME_RATE;
+        let priming = (sample_rate as usize * FRAME_LEN).div_ceil(FRAME_RATE as usize);
+        Self {
+            to_frames: resample.then(|| LinearResampler::new(sample_rate, FRAME_RATE)),
+            from_frames: resample.then(|| LinearResampler::new(FRAME_RATE, sample_rate)),
+            pending: Vec::with_capacity(FRAME_LEN * 2),
+            resampled: Vec::new(),
+            output: std::iter::repeat_n(0.0, priming).collect(),
+        }
+    }
+
+    fn process(&mut self, samples: &mut [f32], mut stage: impl FnMut(&mut [f32])) {
+        match self.to_frames.as_mut() {
+            Some(resampler) => resampler.process(samples, &mut self.pending),
+            None => self.pending.extend_from_slice(samples),
+        }
+        let mut start = 0;
+        while self.pending.len() - start >= FRAME_LEN {
+            let frame = &mut self.pending[start..start + FRAME_LEN];
+            stage(frame);
+            match self.from_frames.as_mut() {
+                Some(resampler) => {
+                    self.resampled.clear();
+                    resampler.process(frame, &mut self.resampled);
+                    self.output.extend(self.resampled.iter().copied());
+                }
+                None => self.output.extend(frame.iter().copied()),
+            }
+            start += FRAME_LEN;
+        }
+        self.pending.drain(..start);
+        for sample in samples.iter_mut() {
+            *sample = self.output.pop_front().unwrap_or(0.0);
+        }
+    }
+}
+
+/// WebRTC acoustic echo canceller fed with the loopback reference. A
+/// processor that fails to initialize or process leaves the mic untouched.
+struct EchoCanceller {
+    frames: FrameAdapter,
+    processor: Option<webrtc_audio_processing::Processor>,
+}
+
+impl EchoCanceller {
+    fn new(sample_rate: u32) -> Self {
+        use webrtc_audio_processing::{
+            Config, EchoCancellation, EchoCancellationSuppressionLevel, InitializationConfig,
+            Processor,
+        };
+
+        let processor = Processor::new(&InitializationConfig {
+            num_capture_channels: 1,
+            num_render_channels: 1,
+            ..InitializationConfig::default()
+        })
+        .map_err(|err| tracing::warn!("Echo canceller unavailable: {:?}", err))
+        .ok()
+        .map(|mut processor| {
+            processor.set_config(Config {
+                echo_cancellation: Some(EchoCancellation {
+                    suppression_level: EchoCancellationSuppressionLevel::High,
+                    // Loopback and mic run on separate clocks and buffers;
+                    // let the AEC estimate and track the delay between them.
+                    enable_delay_agnostic: true,
+                    enable_extended_filter: true,
+                    stream_delay_ms: None,
+                }),
+                ..Config::default()
+            });
+            processor
+        });
+        Self {
+            frames: FrameAdapter::new(sample_rate),
+            processor,
+        }
+    }
+
+    fn process(&mut self, samples: &mut [f32]) {
+        let Some(processor) = self.processor.as_mut() else {
+            return;
+        };
+        self.frames.process(samples, |frame| {
+            let mut reference = crate::echo_reference::take(FRAME_LEN, FRAME_RATE);
+            // Render first: the AEC needs the far end before the capture
+            // frame it leaks into.
+            if processor.process_render_frame(&mut reference).is_ok() {
+                let original = frame.to_vec();
+                if processor.process_capture_frame(frame).is_err() {
+                    frame.copy_from_slice(&original);
+                }
+            }
+        });
+    }
+}
+
+/// Second-order Butterworth high-pass (RBJ cookbook, transposed direct form II).
+struct HighPassFilter {
+    cutoff_hz: f32,
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+    z1: f32,
+    z2: f32,
+}
+
+impl HighPassFilter {
+    fn new(cutoff_hz: f32, sample_rate: u32) -> Self {
+        let w0 = 2.0 * std::f32::consts::PI * cutoff_hz / sample_rate.max(1) as f32;
+        let (sin_w0, cos_w0) = w0.sin_cos();
+        let alpha = sin_w0 / (2.0 * std::f32::consts::FRAC_1_SQRT_2);
+        let a0 = 1.0 + alpha;
+        Self {
+            cutoff_hz,
+            b0: (1.0 + cos_w0) / 2.0 / a0,
+            b1: -(1.0 + cos_w0) / a0,
+            b2: (1.0 + cos_w0) / 2.0 / a0,
+            a1: -2.0 * cos_w0 / a0,
+            a2: (1.0 - alpha) / a0,
+            z1: 0.0,
+            z2: 0.0,
+        }
+    }
+
+    fn process(&mut self, samples: &mut [f32]) {
+        for sample in samples {
+            let input = *sample;
+            let output = self.b0 * input + self.z1;
+            self.z1 = self.b1 * input - self.a1 * output + self.z2;
+            self.z2 = self.b2 * input - self.a2 * output;
+            *sample = output;
+        }
+    }
+}
+
+/// RNNoise denoiser. It expects samples on the 16-bit scale, so frames are
+/// scaled up going in and back down coming out.
+struct NoiseSuppressor {
+    frames: FrameAdapter,
+    state: Box<nnnoiseless::DenoiseState<'static>>,
+    denoised: Vec<f32>,
+}
+
+impl NoiseSuppressor {
+    fn new(sample_rate: u32) -> Self {
+        Self {
+            frames: FrameAdapter::new(sample_rate),
+            state: nnnoiseless::DenoiseState::new(),
+            denoised: vec![0.0; FRAME_LEN],
+        }
+    }
+
+    fn process(&mut self, samples: &mut [f32]) {
+        let state = &mut self.state;
+        let denoised = &mut self.denoised;
+        self.frames.process(samples, |frame| {
+            for sample in frame.iter_mut() {
+                *sample *= I16_SCALE;
+            }
+            state.process_frame(denoised, frame);
+            for (sample, &clean) in frame.iter_mut().zip(denoised.iter()) {
+                *sample = clean / I16_SCALE;
+            }
+        });
+    }
+}
+
+/// Spectral noise gate on an STFT at the mic rate (sqrt-Hann windows, 50 %
+/// overlap, so unity gains reconstruct the input exactly). Each bin tracks
+/// its noise floor as the minimum of its smoothed level, rising slowly while
+/// the bin stays louder. The output queue is primed with one FFT frame of
+/// silence, which is the stage's fixed latency.
+struct SpectralGate {
+    fft_len: usize,
+    hop: usize,
+    sample_rate: f32,
+    forward: Arc<dyn Fft<f32>>,
+    inverse: Arc<dyn Fft<f32>>,
+    window: Vec<f32>,
+    spectrum: Vec<Complex<f32>>,
+    scratch: Vec<Complex<f32>>,
+    input: Vec<f32>,
+    overlap: Vec<f32>,
+    output: VecDeque<f32>,
+    /// Smoothed magnitude, noise floor and applied gain of bins `0..=fft_len / 2`.
+    levels: Vec<f32>,
+    noise_floor: Vec<f32>,
+    gains: Vec<f32>,
+    learned: bool,
+    hold_samples_left: usize,
+}
+
+impl SpectralGate {
+    fn new(sample_rate: u32) -> Self {
+        // ~21-32 ms frames: fine enough for speech harmonics, short enough
+        // for the latency budget.
+        let fft_len = if sample_rate > 24_000 { 1024 } else { 512 };
+        let mut planner = FftPlanner::<f32>::new();
+        let forward = planner.plan_fft_forward(fft_len);
+        let inverse = planner.plan_fft_inverse(fft_len);
+        let scratch_len = forward
+            .get_inplace_scratch_len()
+            .max(inverse.get_inplace_scratch_len());
+        let window = (0..fft_len)
+            .map(|i| {
+                let phase = 2.0 * std::f32::consts::PI * i as f32 / fft_len as f32;
+                (0.5 - 0.5 * phase.cos()).sqrt()
+            })
+            .collect();
+        let bins = fft_len / 2 + 1;
+        Self {
+            fft_len,
+            hop: fft_len / 2,
+            sample_rate: sample_rate.max(1) as f32,
+            forward,
+            inverse,
+            window,
+            spectrum: vec![Complex::default(); fft_len],
+            scratch: vec![Complex::default(); scratch_len],
+            input: Vec::with_capacity(fft_len * 2),
+            overlap: vec![0.0; fft_len / 2],
+            output: std::iter::repeat_n(0.0, fft_len).collect(),
+            levels: vec![0.0; bins],
+            noise_floor: vec![0.0; bins],
+            gains: vec![1.0; bins],
+            learned: false,
+            hold_samples_left: 0,
+        }
+    }
+
+    fn process(&mut self, samples: &mut [f32], threshold_db: f32) {
+        self.input.extend_from_slice(samples);
+        while self.input.len() >= self.fft_len {
+            self.process_frame(threshold_db);
+            self.input.drain(..self.hop);
+        }
+        for sample in samples.iter_mut() {
+            *sample = self.output.pop_front().unwrap_or(0.0);
+        }
+    }
+
+    fn process_frame(&mut self, threshold_db: f32) {
+        let (fft_len, hop) = (self.fft_len, self.hop);
+        let newest = &self.input[hop..fft_len];
+        let rms = (newest.iter().map(|s| s * s).sum::<f32>() / hop as f32).sqrt();
+        if 20.0 * rms.max(1e-9).log10() >= threshold_db {
+            self.hold_samples_left = (NOISE_GATE_HOLD_MS / 1000.0 * self.sample_rate) as usize;
+        } else {
+            self.hold_samples_left = self.hold_samples_left.saturating_sub(hop);
+        }
+        let open = self.hold_samples_left > 0;
+
+        for ((bin, &sample), &weight) in self
+            .spectrum
+            .iter_mut()
+            .zip(&self.input[..fft_len])
+            .zip(&self.window)
+        {
+            *bin = Complex::new(sample * weight, 0.0);
+        }
+        self.forward
+            .process_with_scratch(&mut self.spectrum, &mut self.scratch);
+
+        let hop_s = hop as f32 / self.sample_rate;
+        let floor_rise = 10f32.powf(NOISE_GATE_FLOOR_RISE_DB_PER_S * hop_s / 20.0);
+        let margin = 10f32.powf(NOISE_GATE_MARGIN_DB / 20.0);
+        let closed_gain = 10f32.powf(NOISE_GATE_FLOOR_DB / 20.0);
+        let attack = 1.0 - (-hop_s * 1000.0 / NOISE_GATE_ATTACK_MS).exp();
+        let release = 1.0 - (-hop_s * 1000.0 / NOISE_GATE_RELEASE_MS).exp();
+        for bin in 0..self.levels.len() {
+            let magnitude = self.spectrum[bin].norm();
+            let level = &mut self.levels[bin];
+            let noise_floor = &mut self.noise_floor[bin];
+            if !self.learned {
+                *level = magnitude;
+                *noise_floor = magnitude;
+            } else {
+                *level += (magnitude - *level) * NOISE_GATE_LEVEL_SMOOTHING;
+                if *level < *noise_floor {
+                    *noise_floor = *level;
+                } else {
+                    *noise_floor *= floor_rise;
+                }
+            }
+            let target = if open && *level > *noise_floor * margin {
+                1.0
+            } else {
+                closed_gain
+            };
+            let gain = &mut self.gains[bin];
+            *gain += (target - *gain) * if target > *gain { attack } else { release };
+            self.spectrum[bin] *= *gain;
+            if bin > 0 && bin < fft_len - bin {
+                self.spectrum[fft_len - bin] *= *gain;
+            }
+        }
+        self.learned = true;
+
+        self.inverse
+            .process_with_scratch(&mut self.spectrum, &mut self.scratch);
+        // rustfft leaves the inverse unnormalized.
+        let scale = 1.0 / fft_len as f32;
+        for (i, (bin, &weight)) in self.spectrum.iter().zip(&self.window).enumerate() {
+            let sample = bin.re * weight * scale;
+            if i < hop {
+                self.output.push_back(self.overlap[i] + sample);
+            } else {
+                self.overlap[i - hop] = sample;
+            }
+        }
+    }
+}
+
+/// Tracks the speech level (exponential average over ~1.5 s of blocks above
+/// the speech floor) and nudges the input gain toward the target, rate-limited
+/// so a cough or a door slam does not pump the level.
+struct AutoGainControl {
+    speech_level_db: f32,
+    speech_ms: f32,
+}
+
+impl AutoGainControl {
+    fn new() -> Self {
+        Self {
+            speech_level_db: AGC_SPEECH_FLOOR_DB,
+            speech_ms: 0.0,
+        }
+    }
+
+    /// Returns the new gain when it should change.
+    fn update(
+        &mut self,
+        target_db: f32,
+        gain_db: f32,
+        block_rms: f32,
+        block_ms: f32,
+    ) -> Option<f32> {
+        let level_db = 20.0 * block_rms.max(1e-9).log10();
+        if level_db < AGC_SPEECH_FLOOR_DB || block_ms <= 0.0 {
+            return None;
+        }
+        let alpha = (block_ms / AGC_WINDOW_MS).min(1.0);
+        if self.speech_ms == 0.0 {
+            self.speech_level_db = level_db;
+        } else {
+            self.speech_level_db += (level_db - self.speech_level_db) * alpha;
+        }
+        self.speech_ms += block_ms;
+        if self.speech_ms < AGC_MIN_SPEECH_MS {
+            return None;
+        }
+
+        let seconds = block_ms / 1000.0;
+        let step = (target_db - self.speech_level_db).clamp(
+            -AGC_MAX_CUT_DB_PER_S * seconds,
+            AGC_MAX_RAISE_DB_PER_S * seconds,
+        );
+        let ceiling = AGC_MAX_BOOST_DB.max(gain_db).min(INPUT_GAIN_MAX_DB);
+        let next = (gain_db + step).clamp(INPUT_GAIN_MIN_DB, ceiling);
+        let applied = next - gain_db;
+        if applied.abs() < f32::EPSILON {
+            return None;
+        }
+        // The tracked level was measured at the old gain; shift it so the
+        // next blocks are not over-corrected while the average catches up.
+        self.speech_level_db += applied;
+        Some(next)
+    }
+}
+
+/// Per-stream DSP state. Create one per capture stream and call
+/// [`MicDspChain::process`] on every mono block.
+pub(crate) struct MicDspChain {
+    sample_rate: u32,
+    echo: Option<Box<EchoCanceller>>,
+    highpass: Option<HighPassFilter>,
+    denoiser: Option<Box<NoiseSuppressor>>,
+    gate: Option<Box<SpectralGate>>,
+    agc: AutoGainControl,
+}
+
+impl MicDspChain {
+    pub(crate) fn new(sample_rate: u32) -> Self {
+        Self {
+            sample_rate,
+            echo: None,
+            highpass: None,
+            denoiser: None,
+            gate: None,
+            agc: AutoGainControl::new(),
+        }
+    }
+
+    pub(crate) fn process(&mut self, controls: &MicDspControls, samples: &mut [f32]) {
+        if controls.echo_cancellation_enabled.load(Ordering::Relaxed) {
+            self.echo
+                .get_or_insert_with(|| Box::new(EchoCanceller::new(self.sample_rate)))
+                .process(samples);
+        } else {
+            self.echo = None;
+        }
+
+        match controls.highpass_cutoff_hz() {
+            Some(cutoff_hz) => {
+                let stale = self
+                    .highpass
+                    .as_ref()
+                    .map(|filter| (filter.cutoff_hz - cutoff_hz).abs() > f32::EPSILON)
+                    .unwrap_or(true);
+                if stale {
+                    self.highpass = Some(HighPassFilter::new(cutoff_hz, self.sample_rate));
+                }
+                if let Some(filter) = self.highpass.as_mut() {
+                    filter.process(samples);
+                }
+            }
+            None => self.highpass = None,
+        }
+
+        if controls.noise_suppression_enabled.load(Ordering::Relaxed) {
+            self.denoiser
+                .get_or_insert_with(|| Box::new(NoiseSuppressor::new(self.sample_rate)))
+                .process(samples);
+        } else {
+            self.denoiser = None;
+        }
+
+        match controls.noise_gate_threshold_db() {
+            Some(threshold_db) => self
+                .gate
+                .get_or_insert_with(|| Box::new(SpectralGate::new(self.sample_rate)))
+                .process(samples, threshold_db),
+            None => self.gate = None,
+        }
+    }
+
+    /// Feed a processed block to the AGC; updates `gain_db` (milli-dB, the
+    /// same atomic the capture callback reads) when AGC is enabled. `speech`
+    /// says whether the block belongs to an utterance; the gain is frozen
+    /// otherwise.
+    pub(crate) fn track_gain(
+        &mut self,
+        controls: &MicDspControls,
+        gain_db: &AtomicI64,
+        samples: &[f32],
+        speech: bool,
+    ) {
+        let Some(target_db) = controls.agc_target_db() else {
+            self.agc = AutoGainControl::new();
+            return;
+        };
+        if samples.is_empty() || !speech {
+            return;
+        }
+        let rms = (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt();
+        let block_ms = samples.len() as f32 * 1000.0 / self.sample_rate.max(1) as f32;
+        let current = gain_db.load(Ordering::Relaxed) as f32 / 1000.0;
+        if let Some(next) = self.agc.update(target_db, current, rms, block_ms) {
+            gain_db.store((next * 1000.0) as i64, Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FrameAdapter, MicDspChain, MicDspControls, AGC_MAX_BOOST_DB};
+    use crate::state::Settings;
+    use std::sync::atomic::{AtomicI64, Ordering};
+
+    fn sine(freq_hz: f32, sample_rate: u32, len: usize, amplitude: f32) -> Vec<f32> {
+        (0..len)
+            .map(|i| {
+                amplitude
+                    * (2.0 * std::f32::consts::PI * freq_hz * i as f32 / sample_rate as f32).sin()
+            })
+            .collect()
+    }
+
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    fn controls(configure: impl FnOnce(&mut Settings)) -> MicDspControls {
+        let mut settings = Settings::default();
+        configure(&mut settings);
+        let controls = MicDspControls::default();
+        controls.apply_settings(&settings);
+        controls
+    }
+
+    #[test]
+    fn highpass_removes_hum_and_keeps_speech_band() {
+        let controls = controls(|s| s.mic_highpass_enabled = true);
+        let rate = 48_000;
+
+        let mut hum = sine(30.0, rate, rate as usize, 0.5);
+        MicDspChain::new(rate).process(&controls, &mut hum);
+        assert!(rms(&hum[rate as usize / 2..]) < 0.5 * 0.707 * 0.2);
+
+        let mut voice = sine(1_000.0, rate, rate as usize, 0.5);
+        MicDspChain::new(rate).process(&controls, &mut voice);
+        assert!(rms(&voice[rate as usize / 2..]) > 0.5 * 0.707 * 0.95);
+    }
+
+    #[test]
+    fn noise_gate_attenuates_quiet_blocks_only() {
+        let controls = controls(|s| {
+            s.mic_noise_gate_enabled = true;
+            s.mic_noise_gate_threshold_db = -40.0;
+        });
+        let rate = 16_000;
+        let mut chain = MicDspChain::new(rate);
+
+        // ~-52 dBFS floor noise for half a second, processed in 10 ms blocks.
+        let mut quiet = sine(440.0, rate, rate as usize / 2, 0.0035);
+        for block in quiet.chunks_mut(160) {
+            chain.process(&controls, block);
+        }
+        assert!(rms(&quiet[quiet.len() - 160..]) < 0.0035 * 0.707 * 0.1);
+
+        let mut loud = sine(440.0, rate, 1_600, 0.3);
+        for block in loud.chunks_mut(160) {
+            chain.process(&controls, block);
+        }
+        assert!(rms(&loud[800..]) > 0.3 * 0.707 * 0.95);
+    }
+
+    #[test]
+    fn noise_gate_learns_steady_noise_and_keeps_speech_over_it() {
+        let controls = controls(|s| {
+            s.mic_noise_gate_enabled = true;
+            s.mic_noise_gate_threshold_db = -40.0;
+        });
+        let rate = 16_000;
+        let len = rate as usize;
+        let mut chain = MicDspChain::new(rate);
+
+        // ~-31 dBFS fan noise: above the threshold, so only the spectral
+        // floor can gate it.
+        let mut noise = white_noise(len, 0.05);
+        for block in noise.chunks_mut(160) {
+            chain.process(&controls, block);
+        }
+        assert!(rms(&noise[len / 2..]) < rms(&white_noise(len, 0.05)) * 0.3);
+
+        let tone = sine(1_000.0, rate, len / 2, 0.3);
+        let mut mixed: Vec<f32> = tone
+            .iter()
+            .zip(white_noise(len / 2, 0.05))
+            .map(|(t, n)| t + n)
+            .collect();
+        for block in mixed.chunks_mut(160) {
+            chain.process(&controls, block);
+        }
+        assert!(rms(&mixed[len / 4..]) > rms(&tone) * 0.95);
+    }
+
+    fn white_noise(len: usize, amplitude: f32) -> Vec<f32> {
+        let mut state = 0x1234_5678u32;
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                amplitude * ((state >> 8) as f32 / (1u32 << 24) as f32 * 2.0 - 1.0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn noise_suppression_reduces_stationary_noise() {
+        let controls = controls(|s| s.noise_suppression = true);
+        let rate = 48_000;
+        let len = rate as usize;
+        let mut chain = MicDspChain::new(rate);
+
+        let mut noise = white_noise(len, 0.05);
+        for block in noise.chunks_mut(480) {
+            chain.process(&controls, block);
+        }
+        assert!(rms(&noise[len / 2..]) < rms(&white_noise(len, 0.05)) * 0.5);
+    }
+
+    #[test]
+    fn frame_adapter_round_trips_other_rates_with_fixed_latency() {
+        let rate = 16_000;
+        let mut adapter = FrameAdapter::new(rate);
+        let original = sine(300.0, rate, rate as usize, 0.5);
+        let mut signal = original.clone();
+        for block in signal.chunks_mut(137) {
+            adapter.process(block, |_| {});
+        }
+        // One 48 kHz frame of priming = 160 samples at 16 kHz.
+        let latency = 160;
+        let error: Vec<f32> = signal[latency..]
+            .iter()
+            .zip(&original)
+            .map(|(a, b)| a - b)
+            .collect();
+        assert!(rms(&error[rate as usize / 2..]) < 0.01);
+
+        let mut native = FrameAdapter::new(48_000);
+        let mut block = vec![0.25; 480];
+        native.process(&mut block, |frame| frame.fill(1.0));
+        assert_eq!(block, vec![0.0; 480]);
+        native.process(&mut block, |_| {});
+        assert_eq!(block, vec![1.0; 480]);
+    }
+
+    #[test]
+    fn agc_raises_quiet_speech_up_to_the_cap_and_ignores_non_speech() {
+        let controls = controls(|s| {
+            s.mic_agc_enabled = true;
+            s.mic_agc_target_db = -20.0;
+        });
+        let rate = 16_000;
+        let mut chain = MicDspChain::new(rate);
+        let gain_db = AtomicI64::new(0);
+
+        // -30 dBFS speech at the mic; the callback applies the current gain.
+        let raw = sine(300.0, rate, 160, 0.0316 * std::f32::consts::SQRT_2);
+        for _ in 0..1_000 {
+            let gain = 10f32.powf(gain_db.load(Ordering::Relaxed) as f32 / 1000.0 / 20.0);
+            let block: Vec<f32> = raw.iter().map(|s| s * gain).collect();
+            chain.track_gain(&controls, &gain_db, &block, true);
+        }
+        let settled = gain_db.load(Ordering::Relaxed) as f32 / 1000.0;
+        assert!((settled - 10.0).abs() < 1.5, "settled at {settled} dB");
+
+        // Room noise between utterances neither raises nor lowers the gain.
+        let noise = sine(300.0, rate, 160, 0.05);
+        for _ in 0..500 {
+            chain.track_gain(&controls, &gain_db, &noise, false);
+        }
+        assert_eq!(gain_db.load(Ordering::Relaxed) as f32 / 1000.0, settled);
+
+        // -50 dBFS speech would need +30 dB; AGC stops at the cap.
+        let faint = sine(300.0, rate, 160, 0.00316 * std::f32::consts::SQRT_2);
+        for _ in 0..2_000 {
+            let gain = 10f32.powf(gain_db.load(Ordering::Relaxed) as f32 / 1000.0 / 20.0);
+            let block: Vec<f32> = faint.iter().map(|s| s * gain).collect();
+            chain.track_gain(&controls, &gain_db, &block, true);
+        }
+        assert_eq!(
+            gain_db.load(Ordering::Relaxed) as f32 / 1000.0,
+            AGC_MAX_BOOST_DB
+        );
+    }
+
+    #[test]
+    fn disabled_chain_is_passthrough() {
+        let controls = controls(|_| {});
+        let original = sine(50.0, 16_000, 1_600, 0.001);
+        let mut samples = original.clone();
+        MicDspChain::new(16_000).process(&controls, &mut samples);
+        assert_eq!(samples, original);
+    }
+}
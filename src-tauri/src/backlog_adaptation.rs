@@ -0,0 +1,215 @@
+//! Sustained-overload handling for the system-audio queue.
+//!
+//! `AdaptiveSegmenter::set_backpressure_percent` only stretches chunks while
+//! the queue is nearly full right now, and "expand capacity" only makes room
+//! for more backlog. When the backlog stays high the adapter steps up a load
+//! level instead: longer chunks first (fewer whisper calls for the same
+//! audio), then fast decoding. Once the queue has stayed drained for a while
+//! it steps back down. Every step emits `transcribe:adaptation`.
+//!
+//! Fast decoding is a per-request setting of the loopback worker: the
+//! encoder window (`audio_ctx`) is cut to the chunk length and context
+//! priming is skipped. The shared whisper-server keeps its model, so mic
+//! dictation is unaffected and stepping levels never blocks the capture
+//! thread.
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tracing::info;
+
+use crate::state::Settings;
+
+pub(crate) const ADAPTATION_EVENT: &str = "transcribe:adaptation";
+const STEP_UP_PERCENT: u8 = 70;
+const STEP_DOWN_PERCENT: u8 = 20;
+const STEP_UP_AFTER_MS: u64 = 5_000;
+const STEP_DOWN_AFTER_MS: u64 = 30_000;
+/// Chunk-length multiplier per level; the last level also decodes fast.
+const INTERVAL_SCALES: [f32; 4] = [1.0, 1.5, 2.0, 2.0];
+const FAST_DECODE_LEVEL: u8 = 3;
+/// whisper's encoder covers 30 s in 1500 frames.
+const AUDIO_CTX_FULL: u32 = 1500;
+const AUDIO_CTX_MS_PER_FRAME: u64 = 20;
+/// Headroom past the chunk end, and a floor below which accuracy collapses.
+const AUDIO_CTX_MARGIN: u32 = 64;
+const AUDIO_CTX_MIN: u32 = 512;
+
+static FAST_DECODE: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static AUDIO_CTX: Cell<Option<u32>> = const { Cell::new(None) };
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct BacklogAdaptation {
+    /// 0 = configured settings; higher = more aggressive.
+    pub(crate) level: u8,
+    pub(crate) interval_scale: f32,
+    pub(crate) fast_decode: bool,
+    pub(crate) percent_used: u8,
+    /// "overload" | "drained" | "disabled" | "stopped"
+    pub(crate) reason: &'static str,
+}
+
+/// Whether loopback chunks should be decoded fast right now.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub(crate) fn fast_decode_active() -> bool {
+    FAST_DECODE.load(Ordering::Relaxed)
+}
+
+/// Encoder window covering `duration_ms` of audio.
+fn audio_ctx_for(duration_ms: u64) -> u32 {
+    let frames = duration_ms.div_ceil(AUDIO_CTX_MS_PER_FRAME) as u32;
+    (frames + AUDIO_CTX_MARGIN).clamp(AUDIO_CTX_MIN, AUDIO_CTX_FULL)
+}
+
+/// Runs `decode` on this thread with the encoder window cut to a chunk of
+/// `duration_ms` (`None` keeps the full window).
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub(crate) fn with_fast_decode<T>(duration_ms: Option<u64>, decode: impl FnOnce() -> T) -> T {
+    AUDIO_CTX.with(|ctx| ctx.set(duration_ms.map(audio_ctx_for)));
+    let result = decode();
+    AUDIO_CTX.with(|ctx| ctx.set(None));
+    result
+}
+
+/// `audio_ctx` for the decode running on this thread, if fast decoding.
+pub(crate) fn current_audio_ctx() -> Option<u32> {
+    AUDIO_CTX
+        .with(Cell::get)
+        .filter(|ctx| *ctx < AUDIO_CTX_FULL)
+}
+
+#[derive(Debug, Default)]
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub(crate) struct BacklogAdapter {
+    level: u8,
+    pressure_since_ms: Option<u64>,
+    drained_since_ms: Option<u64>,
+}
+
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+impl BacklogAdapter {
+    pub(crate) fn interval_scale(&self) -> f32 {
+        INTERVAL_SCALES[self.level as usize]
+    }
+
+    /// New level once the backlog has stayed high or drained long enough.
+    fn observe(&mut self, percent_used: u8, now_ms: u64) -> Option<u8> {
+        if percent_used >= STEP_UP_PERCENT {
+            self.drained_since_ms = None;
+            let since = *self.pressure_since_ms.get_or_insert(now_ms);
+            if self.level < FAST_DECODE_LEVEL && now_ms.saturating_sub(since) >= STEP_UP_AFTER_MS {
+                self.level += 1;
+                self.pressure_since_ms = Some(now_ms);
+                return Some(self.level);
+            }
+        } else if percent_used <= STEP_DOWN_PERCENT {
+            self.pressure_since_ms = None;
+            let since = *self.drained_since_ms.get_or_insert(now_ms);
+            if self.level > 0 && now_ms.saturating_sub(since) >= STEP_DOWN_AFTER_MS {
+                self.level -= 1;
+                self.drained_since_ms = Some(now_ms);
+                return Some(self.level);
+            }
+        } else {
+            self.pressure_since_ms = None;
+            self.drained_since_ms = None;
+        }
+        None
+    }
+
+    /// Feeds one backlog sample. Returns true when the chunk length changed
+    /// and the segmenter config needs to be rebuilt.
+    pub(crate) fn update(
+        &mut self,
+        app: &AppHandle,
+        settings: &Settings,
+        percent_used: u8,
+    ) -> bool {
+        if !settings.transcribe_adaptive_chunking {
+            return self.reset(app, "disabled");
+        }
+        let previous = self.level;
+        let Some(level) = self.observe(percent_used, crate::util::now_ms()) else {
+            return false;
+        };
+        let reason = if level > previous {
+            "overload"
+        } else {
+            "drained"
+        };
+        self.apply(app, reason, percent_used);
+        true
+    }
+
+    /// Back to the configured settings, e.g. when the monitor stops.
+    pub(crate) fn reset(&mut self, app: &AppHandle, reason: &'static str) -> bool {
+        self.pressure_since_ms = None;
+        self.drained_since_ms = None;
+        if self.level == 0 {
+            return false;
+        }
+        self.level = 0;
+        self.apply(app, reason, 0);
+        true
+    }
+
+    fn apply(&self, app: &AppHandle, reason: &'static str, percent_used: u8) {
+        let fast_decode = self.level >= FAST_DECODE_LEVEL;
+        FAST_DECODE.store(fast_decode, Ordering::Relaxed);
+
+        let adaptation = BacklogAdaptation {
+            level: self.level,
+            interval_scale: self.interval_scale(),
+            fast_decode,
+            percent_used,
+            reason,
+        };
+        info!(
+            "Backlog adaptation level {} ({}): chunk x{}, fast decode {}",
+            adaptation.level, reason, adaptation.interval_scale, adaptation.fast_decode
+        );
+        let _ = app.emit(ADAPTATION_EVENT, &adaptation);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_up_under_sustained_pressure_and_down_after_draining() {
+        let mut adapter = BacklogAdapter::default();
+        assert_eq!(adapter.observe(90, 0), None);
+        assert_eq!(adapter.observe(50, 3_000), None);
+        assert_eq!(adapter.observe(90, 4_000), None);
+        assert_eq!(adapter.observe(90, 9_000), Some(1));
+        assert_eq!(adapter.observe(95, 14_000), Some(2));
+        assert_eq!(adapter.observe(95, 19_000), Some(FAST_DECODE_LEVEL));
+        assert_eq!(adapter.observe(95, 24_000), None);
+        assert_eq!(adapter.interval_scale(), 2.0);
+
+        assert_eq!(adapter.observe(10, 25_000), None);
+        assert_eq!(adapter.observe(10, 55_000), Some(2));
+        assert_eq!(adapter.observe(10, 85_000), Some(1));
+        assert_eq!(adapter.observe(10, 115_000), Some(0));
+        assert_eq!(adapter.observe(0, 200_000), None);
+    }
+
+    #[test]
+    fn fast_decode_window_covers_the_chunk() {
+        assert_eq!(audio_ctx_for(4_000), AUDIO_CTX_MIN);
+        assert_eq!(audio_ctx_for(20_000), 1_000 + AUDIO_CTX_MARGIN);
+        assert_eq!(audio_ctx_for(60_000), AUDIO_CTX_FULL);
+        assert_eq!(
+            with_fast_decode(Some(20_000), current_audio_ctx),
+            Some(1_064)
+        );
+        assert_eq!(with_fast_decode(Some(60_000), current_audio_ctx), None);
+        assert_eq!(current_audio_ctx(), None);
+    }
+}
@@ -0,0 +1,260 @@
+//! Scheduler for batch transcription (imported files, watch folders).
+//!
+//! Jobs run on up to `batch_max_parallel` worker threads. Live dictation has
+//! priority: no batch job starts while the user is recording or a mic
+//! transcription is in flight, and running file jobs wait between chunks
+//! (see `wait_for_turn`). `pause_queue` holds new and running batch work at
+//! the same points; `resume_queue` releases it.
+
+use serde::Serialize;
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::sync_channel;
+use std::sync::{Condvar, Mutex, OnceLock};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::info;
+
+use crate::errors::{CommandError, CommandResult, ErrorCode};
+use crate::state::AppState;
+use crate::transcription_jobs::JobSource;
+
+pub(crate) const DEFAULT_BATCH_MAX_PARALLEL: u32 = 1;
+pub(crate) const MAX_BATCH_PARALLEL: u32 = 4;
+/// Re-check interval while waiting for live dictation to finish.
+const YIELD_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+type Task = Box<dyn FnOnce() + Send + 'static>;
+
+struct QueuedJob {
+    entry: BatchJobEntry,
+    task: Task,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct BatchJobEntry {
+    pub(crate) id: u64,
+    pub(crate) label: String,
+    pub(crate) created_ms: u64,
+    pub(crate) started_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct BatchQueueStatus {
+    pub(crate) paused: bool,
+    pub(crate) max_parallel: u32,
+    pub(crate) running: Vec<BatchJobEntry>,
+    pub(crate) queued: Vec<BatchJobEntry>,
+}
+
+struct Scheduler {
+    next_id: u64,
+    queue: VecDeque<QueuedJob>,
+    running: Vec<BatchJobEntry>,
+}
+
+static SCHEDULER: Mutex<Scheduler> = Mutex::new(Scheduler {
+    next_id: 0,
+    queue: VecDeque::new(),
+    running: Vec::new(),
+});
+static WAKE: Condvar = Condvar::new();
+static PAUSED: AtomicBool = AtomicBool::new(false);
+static DISPATCHER: OnceLock<()> = OnceLock::new();
+
+thread_local! {
+    static IN_BATCH_WORKER: Cell<bool> = const { Cell::new(false) };
+}
+
+fn max_parallel(app: &AppHandle) -> u32 {
+    app.state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .batch_max_parallel
+        .clamp(1, MAX_BATCH_PARALLEL)
+}
+
+/// The user is dictating: an utterance is being captured, or a mic
+/// transcription is in flight. An idle always-on VAD stream does not hold
+/// the queue back.
+fn interactive_busy(app: &AppHandle) -> bool {
+    let (speaking, transcribing) = {
+        let recorder = app
+            .state::<AppState>()
+            .recorder
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        (recorder.utterance_in_flight(), recorder.transcribing)
+    };
+    speaking || transcribing || crate::transcription_jobs::has_unfinished(JobSource::Mic)
+}
+
+fn status_of(scheduler: &Scheduler, max_parallel: u32) -> BatchQueueStatus {
+    BatchQueueStatus {
+        paused: PAUSED.load(Ordering::Acquire),
+        max_parallel,
+        running: scheduler.running.clone(),
+        queued: scheduler
+            .queue
+            .iter()
+            .map(|job| job.entry.clone())
+            .collect(),
+    }
+}
+
+fn publish(app: &AppHandle, scheduler: &Scheduler) {
+    let _ = app.emit(
+        "batch-queue:changed",
+        status_of(scheduler, max_parallel(app)),
+    );
+}
+
+fn run_worker(app: AppHandle, id: u64, task: Task) {
+    crate::util::spawn_guarded("batch_queue_worker", move || {
+        IN_BATCH_WORKER.with(|flag| flag.set(true));
+        task();
+        let mut scheduler = SCHEDULER
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        scheduler.running.retain(|entry| entry.id != id);
+        publish(&app, &scheduler);
+        WAKE.notify_all();
+    });
+}
+
+fn dispatch_loop(app: AppHandle) {
+    let mut scheduler = SCHEDULER
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    loop {
+        let can_start = !scheduler.queue.is_empty()
+            && !PAUSED.load(Ordering::Acquire)
+            && (scheduler.running.len() as u32) < max_parallel(&app)
+            && !interactive_busy(&app);
+        if !can_start {
+            // Time out so dictation ending (which does not notify) is noticed.
+            scheduler = WAKE
+                .wait_timeout(scheduler, YIELD_POLL_INTERVAL)
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .0;
+            continue;
+        }
+        let Some(QueuedJob { mut entry, task }) = scheduler.queue.pop_front() else {
+            continue;
+        };
+        entry.started_ms = Some(crate::util::now_ms());
+        info!("Batch queue: starting '{}'", entry.label);
+        let id = entry.id;
+        scheduler.running.push(entry);
+        publish(&app, &scheduler);
+        run_worker(app.clone(), id, task);
+    }
+}
+
+/// Queues `task` behind earlier batch work and returns its id.
+pub(crate) fn submit(app: &AppHandle, label: String, task: impl FnOnce() + Send + 'static) -> u64 {
+    DISPATCHER.get_or_init(|| {
+        let app = app.clone();
+        crate::util::spawn_guarded("batch_queue_dispatcher", move || dispatch_loop(app));
+    });
+    let mut scheduler = SCHEDULER
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    scheduler.next_id += 1;
+    let id = scheduler.next_id;
+    scheduler.queue.push_back(QueuedJob {
+        entry: BatchJobEntry {
+            id,
+            label,
+            created_ms: crate::util::now_ms(),
+            started_ms: None,
+        },
+        task: Box::new(task),
+    });
+    publish(app, &scheduler);
+    WAKE.notify_all();
+    id
+}
+
+/// Runs `work` as a batch job and blocks until it has finished.
+pub(crate) fn run_blocking<T: Send + 'static>(
+    app: &AppHandle,
+    label: String,
+    work: impl FnOnce() -> Result<T, String> + Send + 'static,
+) -> Result<T, String> {
+    let (tx, rx) = sync_channel(1);
+    submit(app, label, move || {
+        let _ = tx.send(work());
+    });
+    // A job removed from the queue drops its sender without a result.
+    rx.recv()
+        .map_err(|_| crate::transcription_jobs::JOB_CANCELLED_ERROR.to_string())?
+}
+
+/// Called by batch work between chunks: waits while the queue is paused or
+/// the user is dictating. No-op outside batch workers.
+pub(crate) fn wait_for_turn(app: &AppHandle) {
+    if !IN_BATCH_WORKER.with(|flag| flag.get()) {
+        return;
+    }
+    while (PAUSED.load(Ordering::Acquire) || interactive_busy(app))
+        && !crate::transcription_jobs::current_job_cancelled()
+    {
+        std::thread::sleep(YIELD_POLL_INTERVAL);
+    }
+}
+
+#[tauri::command]
+pub(crate) fn get_batch_queue(app: AppHandle) -> BatchQueueStatus {
+    let scheduler = SCHEDULER
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    status_of(&scheduler, max_parallel(&app))
+}
+
+#[tauri::command]
+pub(crate) fn pause_queue(app: AppHandle) -> BatchQueueStatus {
+    PAUSED.store(true, Ordering::Release);
+    info!("Batch queue paused");
+    let scheduler = SCHEDULER
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    publish(&app, &scheduler);
+    status_of(&scheduler, max_parallel(&app))
+}
+
+#[tauri::command]
+pub(crate) fn resume_queue(app: AppHandle) -> BatchQueueStatus {
+    PAUSED.store(false, Ordering::Release);
+    info!("Batch queue resumed");
+    let scheduler = SCHEDULER
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    publish(&app, &scheduler);
+    WAKE.notify_all();
+    status_of(&scheduler, max_parallel(&app))
+}
+
+/// Removes a job that has not started yet; running jobs are cancelled via
+/// `cancel_job` like any other transcription.
+#[tauri::command]
+pub(crate) fn remove_batch_job(app: AppHandle, job_id: u64) -> CommandResult<BatchQueueStatus> {
+    let mut scheduler = SCHEDULER
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let index = scheduler
+        .queue
+        .iter()
+        .position(|job| job.entry.id == job_id)
+        .ok_or_else(|| {
+            CommandError::new(
+                ErrorCode::NotFound,
+                format!("Batch job {} is not queued", job_id),
+            )
+        })?;
+    scheduler.queue.remove(index);
+    publish(&app, &scheduler);
+    Ok(status_of(&scheduler, max_parallel(&app)))
+}
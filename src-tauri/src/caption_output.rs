@@ -0,0 +1,241 @@
+//! OBS-friendly caption output: the latest caption text is mirrored to a
+//! plain text file (for a "Text (GDI+) → Read from file" source) and to a
+//! Server-Sent Events stream at `GET /v1/captions` on the HTTP API (for a
+//! browser source, no NDI needed).
+//!
+//! Fed from the history push paths, so it sees every final system-audio
+//! segment and, with `caption_file_include_mic`, dictation as well. The text
+//! is a rolling tail capped at `caption_file_max_chars` and cleared after
+//! `caption_file_clear_secs` of silence.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{sync_channel, RecvTimeoutError, SyncSender, TrySendError};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tiny_http::Request;
+use tracing::{info, warn};
+
+use crate::state::{AppState, Settings};
+
+pub(crate) const DEFAULT_CAPTION_FILE_NAME: &str = "obs-captions.txt";
+const CLEAR_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const SSE_KEEPALIVE: Duration = Duration::from_secs(15);
+const SSE_CLIENT_QUEUE: usize = 64;
+
+struct CaptionState {
+    text: String,
+    updated: Option<Instant>,
+}
+
+static CAPTION: Mutex<CaptionState> = Mutex::new(CaptionState {
+    text: String::new(),
+    updated: None,
+});
+static SSE_CLIENTS: Mutex<Vec<SyncSender<String>>> = Mutex::new(Vec::new());
+
+/// Appends `text` and keeps at most `max_chars` characters, cutting at a
+/// word boundary so the visible line never starts mid-word.
+fn append_caption(current: &str, text: &str, max_chars: usize) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let combined = if current.is_empty() {
+        text
+    } else {
+        format!("{} {}", current, text)
+    };
+    let total = combined.chars().count();
+    if total <= max_chars {
+        return combined;
+    }
+    let tail: String = combined.chars().skip(total - max_chars).collect();
+    match tail.split_once(' ') {
+        Some((_, rest)) if !rest.is_empty() => rest.to_string(),
+        _ => tail,
+    }
+}
+
+fn caption_path(app: &AppHandle, settings: &Settings) -> PathBuf {
+    let configured = settings.caption_file_path.trim();
+    if configured.is_empty() {
+        crate::paths::resolve_data_path(app, DEFAULT_CAPTION_FILE_NAME)
+    } else {
+        PathBuf::from(configured)
+    }
+}
+
+/// Write-then-rename so OBS never reads a half-written file.
+fn write_caption_file(path: &Path, text: &str) -> Result<(), String> {
+    let tmp = path.with_extension("txt.tmp");
+    std::fs::write(&tmp, text.as_bytes())
+        .map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+    std::fs::rename(&tmp, path).map_err(|e| format!("Failed to replace {}: {}", path.display(), e))
+}
+
+fn sse_frame(text: &str) -> String {
+    format!("data: {}\n\n", serde_json::json!({ "text": text }))
+}
+
+fn publish(app: &AppHandle, settings: &Settings, text: &str) {
+    if let Err(err) = write_caption_file(&caption_path(app, settings), text) {
+        warn!("Caption output: {}", err);
+    }
+    let frame = sse_frame(text);
+    SSE_CLIENTS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .retain(|tx| {
+            !matches!(
+                tx.try_send(frame.clone()),
+                Err(TrySendError::Disconnected(_))
+            )
+        });
+}
+
+fn current_settings(app: &AppHandle) -> Settings {
+    app.state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+/// Adds a final transcript to the caption output. `source` is the history
+/// source ("output" for system audio).
+pub(crate) fn push_caption(app: &AppHandle, text: &str, source: &str) {
+    let settings = current_settings(app);
+    if !settings.caption_file_enabled || text.trim().is_empty() {
+        return;
+    }
+    if source != "output" && !settings.caption_file_include_mic {
+        return;
+    }
+    let updated = {
+        let mut caption = CAPTION
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        caption.text = append_caption(
+            &caption.text,
+            text,
+            settings.caption_file_max_chars as usize,
+        );
+        caption.updated = Some(Instant::now());
+        caption.text.clone()
+    };
+    publish(app, &settings, &updated);
+}
+
+fn clear_if_idle(app: &AppHandle) {
+    let settings = current_settings(app);
+    if !settings.caption_file_enabled || settings.caption_file_clear_secs == 0 {
+        return;
+    }
+    let expired = {
+        let mut caption = CAPTION
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let idle = Duration::from_secs(settings.caption_file_clear_secs as u64);
+        let expired = caption
+            .updated
+            .is_some_and(|updated| updated.elapsed() >= idle);
+        if expired {
+            caption.text.clear();
+            caption.updated = None;
+        }
+        expired
+    };
+    if expired {
+        publish(app, &settings, "");
+    }
+}
+
+/// Spawn the idle-clear task. Call once from app setup.
+pub(crate) fn start(app: AppHandle) {
+    crate::util::spawn_guarded("caption_output", move || loop {
+        std::thread::sleep(CLEAR_POLL_INTERVAL);
+        clear_if_idle(&app);
+    });
+}
+
+/// Serves an authorized `GET /v1/captions` request as an SSE stream until
+/// the client goes away. Runs on the request's own thread.
+pub(crate) fn serve_sse(request: Request) {
+    let (tx, rx) = sync_channel::<String>(SSE_CLIENT_QUEUE);
+    let current = CAPTION
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .text
+        .clone();
+    // tiny_http buffers chunked bodies, so write the stream by hand.
+    let mut writer = request.into_writer();
+    let head = "HTTP/1.1 200 OK\r\n\
+                Content-Type: text/event-stream\r\n\
+                Cache-Control: no-cache\r\n\
+                Access-Control-Allow-Origin: *\r\n\
+                Connection: close\r\n\r\n";
+    if writer
+        .write_all(head.as_bytes())
+        .and_then(|_| writer.write_all(sse_frame(&current).as_bytes()))
+        .and_then(|_| writer.flush())
+        .is_err()
+    {
+        return;
+    }
+    SSE_CLIENTS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .push(tx);
+    info!("Caption SSE client connected");
+    loop {
+        let chunk = match rx.recv_timeout(SSE_KEEPALIVE) {
+            Ok(frame) => frame,
+            Err(RecvTimeoutError::Timeout) => ": keepalive\n\n".to_string(),
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+        if writer
+            .write_all(chunk.as_bytes())
+            .and_then(|_| writer.flush())
+            .is_err()
+        {
+            break;
+        }
+    }
+    // Dropping `rx` makes the next publish remove this client.
+    info!("Caption SSE client disconnected");
+}
+
+/// Closes every SSE client, e.g. when the HTTP API is turned off.
+pub(crate) fn disconnect_all() {
+    SSE_CLIENTS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clear();
+}
+
+/// Resolved caption file path, for display in the settings.
+#[tauri::command]
+pub(crate) fn get_caption_file_path(app: AppHandle) -> String {
+    caption_path(&app, &current_settings(&app))
+        .to_string_lossy()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn caption_tail_is_capped_at_word_boundary() {
+        assert_eq!(append_caption("", "  hello   world ", 50), "hello world");
+        assert_eq!(
+            append_caption("hello world", "and goodbye", 50),
+            "hello world and goodbye"
+        );
+        assert_eq!(
+            append_caption("hello world", "and goodbye", 14),
+            "and goodbye"
+        );
+        assert_eq!(append_caption("", "abcdefghij", 4), "ghij");
+        assert_eq!(sse_frame("hi"), "data: {\"text\":\"hi\"}\n\n");
+    }
+}
@@ -0,0 +1,274 @@
+//! Do-not-disturb policy: pauses always-on capture (VAD monitor and system
+//! audio) while a fullscreen app or a call is active, and resumes it after.
+//!
+//! Push-to-talk stays available; it is an explicit user action. The pause is
+//! session-only like the privacy mute, but driven by a poller instead of the
+//! user, and every change is announced via `capture:paused-by-policy`.
+
+use serde::Serialize;
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{info, warn};
+
+use crate::state::{AppState, Settings};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Consecutive clear polls before resuming, so alt-tabbing out of a game or
+/// a brief mic release in a call does not restart capture.
+const RESUME_AFTER_CLEAR_POLLS: u32 = 3;
+
+pub(crate) const DEFAULT_CALL_APPS: [&str; 8] = [
+    "ms-teams.exe",
+    "teams.exe",
+    "zoom.exe",
+    "discord.exe",
+    "slack.exe",
+    "skype.exe",
+    "webex.exe",
+    "signal.exe",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum PauseReason {
+    Fullscreen,
+    Call,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct CapturePolicyState {
+    pub(crate) paused: bool,
+    pub(crate) reason: Option<PauseReason>,
+    /// Call app holding the microphone, when paused for a call.
+    pub(crate) app: Option<String>,
+}
+
+/// Active pause: reason plus the call app, if any.
+static PAUSED: Mutex<Option<(PauseReason, Option<String>)>> = Mutex::new(None);
+
+fn paused_guard() -> MutexGuard<'static, Option<(PauseReason, Option<String>)>> {
+    PAUSED
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+pub(crate) fn is_paused() -> bool {
+    paused_guard().is_some()
+}
+
+fn snapshot() -> CapturePolicyState {
+    let paused = paused_guard().clone();
+    CapturePolicyState {
+        paused: paused.is_some(),
+        reason: paused.as_ref().map(|(reason, _)| *reason),
+        app: paused.and_then(|(_, app)| app),
+    }
+}
+
+/// Fullscreen game, presentation or "busy" fullscreen app per the shell.
+#[cfg(target_os = "windows")]
+fn fullscreen_app_active() -> bool {
+    use windows::Win32::UI::Shell::{
+        SHQueryUserNotificationState, QUNS_BUSY, QUNS_PRESENTATION_MODE,
+        QUNS_RUNNING_D3D_FULL_SCREEN,
+    };
+
+    // SAFETY: no arguments; returns the state by value.
+    match unsafe { SHQueryUserNotificationState() } {
+        Ok(state) => {
+            state == QUNS_BUSY
+                || state == QUNS_RUNNING_D3D_FULL_SCREEN
+                || state == QUNS_PRESENTATION_MODE
+        }
+        Err(_) => false,
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn fullscreen_app_active() -> bool {
+    false
+}
+
+/// Process ids with an active session on any capture endpoint, i.e. apps
+/// currently recording from a microphone.
+#[cfg(target_os = "windows")]
+fn active_capture_pids() -> Vec<u32> {
+    use windows::core::Interface;
+    use windows::Win32::Media::Audio::{
+        eCapture, AudioSessionStateActive, IAudioSessionControl2, IAudioSessionManager2,
+        IMMDeviceEnumerator, MMDeviceEnumerator, DEVICE_STATE_ACTIVE,
+    };
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CLSCTX_ALL, COINIT_MULTITHREADED,
+    };
+
+    // SAFETY: COM is initialized for this (poller) thread before use; all
+    // interfaces are reference-counted wrappers released on drop.
+    unsafe {
+        let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+        let collect = || -> windows::core::Result<Vec<u32>> {
+            let enumerator: IMMDeviceEnumerator =
+                CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL)?;
+            let devices = enumerator.EnumAudioEndpoints(eCapture, DEVICE_STATE_ACTIVE)?;
+            let mut pids = Vec::new();
+            for device_index in 0..devices.GetCount()? {
+                let device = devices.Item(device_index)?;
+                let manager: IAudioSessionManager2 = device.Activate(CLSCTX_ALL, None)?;
+                let sessions = manager.GetSessionEnumerator()?;
+                for session_index in 0..sessions.GetCount()? {
+                    let control = sessions.GetSession(session_index)?;
+                    if control.GetState()? != AudioSessionStateActive {
+                        continue;
+                    }
+                    let control: IAudioSessionControl2 = control.cast()?;
+                    if let Ok(pid) = control.GetProcessId() {
+                        pids.push(pid);
+                    }
+                }
+            }
+            Ok(pids)
+        };
+        collect().unwrap_or_else(|err| {
+            warn!("Failed to enumerate microphone sessions: {}", err);
+            Vec::new()
+        })
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn active_capture_pids() -> Vec<u32> {
+    Vec::new()
+}
+
+fn matching_call_app(process_name: &str, call_apps: &[String]) -> bool {
+    call_apps
+        .iter()
+        .any(|wanted| crate::app_overrides::process_name_matches(process_name, wanted))
+}
+
+/// First configured call app that is currently recording from a microphone.
+fn active_call_app(call_apps: &[String]) -> Option<String> {
+    let own_pid = std::process::id();
+    active_capture_pids()
+        .into_iter()
+        .filter(|pid| *pid != own_pid)
+        .filter_map(crate::app_overrides::process_name)
+        .find(|name| matching_call_app(name, call_apps))
+}
+
+fn evaluate(settings: &Settings) -> (Option<PauseReason>, Option<String>) {
+    if settings.auto_pause_calls {
+        if let Some(app) = active_call_app(&settings.auto_pause_call_apps) {
+            return (Some(PauseReason::Call), Some(app));
+        }
+    }
+    if settings.auto_pause_fullscreen && fullscreen_app_active() {
+        return (Some(PauseReason::Fullscreen), None);
+    }
+    (None, None)
+}
+
+fn pause(app: &AppHandle, reason: PauseReason, call_app: Option<String>) {
+    let was_paused = paused_guard().replace((reason, call_app.clone())).is_some();
+    if !was_paused {
+        info!(
+            "Do-not-disturb: pausing capture ({:?}{})",
+            reason,
+            call_app
+                .as_deref()
+                .map(|name| format!(", {}", name))
+                .unwrap_or_default()
+        );
+        let state = app.state::<AppState>();
+        crate::audio::stop_vad_monitor(app, &state);
+        crate::transcription::stop_transcribe_monitor(app, state.inner());
+    }
+    let _ = app.emit("capture:paused-by-policy", snapshot());
+}
+
+fn resume(app: &AppHandle) {
+    if paused_guard().take().is_none() {
+        return;
+    }
+    info!("Do-not-disturb: resuming capture");
+    let state = app.state::<AppState>();
+    let settings = state
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    if crate::state::mode_uses_vad_monitor(&settings.mode) && settings.capture_enabled {
+        if let Err(err) = crate::audio::start_vad_monitor(app, &state, &settings) {
+            warn!("Failed to restart VAD monitor after pause: {}", err);
+        }
+    }
+    if settings.transcribe_enabled && !crate::privacy_mute::is_muted() {
+        if let Err(err) =
+            crate::transcription::start_transcribe_monitor(app, state.inner(), &settings)
+        {
+            warn!(
+                "Failed to restart system audio capture after pause: {}",
+                err
+            );
+        }
+    }
+    let _ = app.emit("capture:paused-by-policy", snapshot());
+}
+
+/// Spawn the polling thread. Call once from app setup.
+pub(crate) fn start(app: AppHandle) {
+    crate::util::spawn_guarded("capture_policy_monitor", move || {
+        let mut clear_polls = 0u32;
+        let mut last: Option<(PauseReason, Option<String>)> = None;
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+            let settings = app
+                .state::<AppState>()
+                .settings
+                .read()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .clone();
+            if !settings.auto_pause_calls && !settings.auto_pause_fullscreen && !is_paused() {
+                continue;
+            }
+            match evaluate(&settings) {
+                (Some(reason), call_app) => {
+                    clear_polls = 0;
+                    let next = Some((reason, call_app.clone()));
+                    if last != next {
+                        pause(&app, reason, call_app);
+                        last = next;
+                    }
+                }
+                (None, _) => {
+                    clear_polls += 1;
+                    // Settings turned off: resume right away.
+                    let disabled = !settings.auto_pause_calls && !settings.auto_pause_fullscreen;
+                    if is_paused() && (disabled || clear_polls >= RESUME_AFTER_CLEAR_POLLS) {
+                        resume(&app);
+                        last = None;
+                    }
+                }
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub(crate) fn get_capture_policy_state() -> CapturePolicyState {
+    snapshot()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn call_apps_match_with_or_without_exe() {
+        let apps = vec!["zoom".to_string(), "ms-teams.exe".to_string()];
+        assert!(matching_call_app("zoom.exe", &apps));
+        assert!(matching_call_app("ms-teams.exe", &apps));
+        assert!(!matching_call_app("obs64.exe", &apps));
+    }
+}
@@ -0,0 +1,253 @@
+//! Weekly capture schedule: mic monitoring and system-audio transcription only
+//! run inside the configured windows (e.g. work hours).
+//!
+//! A background task re-evaluates the timetable and calls the regular
+//! start/stop monitor functions on every transition; the start paths check
+//! the blocked flags so a settings save outside a window does not restart
+//! capture. Push-to-talk is not affected.
+
+use chrono::{Datelike, Local, Timelike};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{info, warn};
+
+use crate::state::AppState;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(15);
+const MINUTES_PER_DAY: u32 = 24 * 60;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct ScheduleWindow {
+    /// 0 = Monday … 6 = Sunday; the day the window starts on.
+    pub(crate) days: Vec<u8>,
+    /// "HH:MM", local time.
+    pub(crate) start: String,
+    /// "HH:MM"; at or before `start` means the window ends the next day.
+    pub(crate) end: String,
+    pub(crate) mic: bool,
+    pub(crate) system_audio: bool,
+}
+
+impl Default for ScheduleWindow {
+    fn default() -> Self {
+        Self {
+            days: vec![0, 1, 2, 3, 4],
+            start: "09:00".to_string(),
+            end: "17:00".to_string(),
+            mic: true,
+            system_audio: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct CaptureSchedule {
+    pub(crate) enabled: bool,
+    pub(crate) windows: Vec<ScheduleWindow>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub(crate) struct ScheduleStatus {
+    pub(crate) enabled: bool,
+    pub(crate) mic_allowed: bool,
+    pub(crate) system_audio_allowed: bool,
+}
+
+static MIC_BLOCKED: AtomicBool = AtomicBool::new(false);
+static SYSTEM_AUDIO_BLOCKED: AtomicBool = AtomicBool::new(false);
+
+pub(crate) fn mic_blocked() -> bool {
+    MIC_BLOCKED.load(Ordering::Acquire)
+}
+
+pub(crate) fn system_audio_blocked() -> bool {
+    SYSTEM_AUDIO_BLOCKED.load(Ordering::Acquire)
+}
+
+fn parse_hhmm(value: &str) -> Option<u32> {
+    let (hours, minutes) = value.trim().split_once(':')?;
+    let hours: u32 = hours.parse().ok()?;
+    let minutes: u32 = minutes.parse().ok()?;
+    (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+}
+
+pub(crate) fn normalize_capture_schedule(schedule: &mut CaptureSchedule) {
+    for window in schedule.windows.iter_mut() {
+        window.days.retain(|day| *day < 7);
+        window.days.sort_unstable();
+        window.days.dedup();
+        for time in [&mut window.start, &mut window.end] {
+            *time = parse_hhmm(time)
+                .map(|minutes| format!("{:02}:{:02}", minutes / 60, minutes % 60))
+                .unwrap_or_default();
+        }
+    }
+    schedule.windows.retain(|window| {
+        !window.days.is_empty() && !window.start.is_empty() && !window.end.is_empty()
+    });
+}
+
+impl ScheduleWindow {
+    /// Whether the window covers `minute` of `weekday` (0 = Monday).
+    fn covers(&self, weekday: u8, minute: u32) -> bool {
+        let (Some(start), Some(end)) = (parse_hhmm(&self.start), parse_hhmm(&self.end)) else {
+            return false;
+        };
+        if start < end {
+            return self.days.contains(&weekday) && (start..end).contains(&minute);
+        }
+        // Overnight (or 24h when start == end): today's part plus the tail
+        // of a window that started yesterday.
+        let yesterday = (weekday + 6) % 7;
+        (self.days.contains(&weekday) && minute >= start)
+            || (self.days.contains(&yesterday) && minute < end)
+    }
+}
+
+fn status_at(schedule: &CaptureSchedule, weekday: u8, minute: u32) -> ScheduleStatus {
+    if !schedule.enabled || schedule.windows.is_empty() {
+        return ScheduleStatus {
+            enabled: schedule.enabled,
+            mic_allowed: true,
+            system_audio_allowed: true,
+        };
+    }
+    let active: Vec<&ScheduleWindow> = schedule
+        .windows
+        .iter()
+        .filter(|window| window.covers(weekday, minute))
+        .collect();
+    ScheduleStatus {
+        enabled: true,
+        mic_allowed: active.iter().any(|window| window.mic),
+        system_audio_allowed: active.iter().any(|window| window.system_audio),
+    }
+}
+
+fn current_status(schedule: &CaptureSchedule) -> ScheduleStatus {
+    let now = Local::now();
+    let minute = (now.hour() * 60 + now.minute()) % MINUTES_PER_DAY;
+    status_at(schedule, now.weekday().num_days_from_monday() as u8, minute)
+}
+
+/// Re-evaluates the schedule and starts/stops monitors on transitions.
+pub(crate) fn enforce(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let settings = state
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    let status = current_status(&settings.capture_schedule);
+    let mic_was_blocked = MIC_BLOCKED.swap(!status.mic_allowed, Ordering::AcqRel);
+    let system_was_blocked =
+        SYSTEM_AUDIO_BLOCKED.swap(!status.system_audio_allowed, Ordering::AcqRel);
+    let mic_changed = mic_was_blocked == status.mic_allowed;
+    let system_changed = system_was_blocked == status.system_audio_allowed;
+    if !mic_changed && !system_changed {
+        return;
+    }
+
+    if mic_changed && crate::state::mode_uses_vad_monitor(&settings.mode) {
+        if status.mic_allowed {
+            info!("Capture schedule: mic window opened");
+            if settings.capture_enabled {
+                if let Err(err) = crate::audio::start_vad_monitor(app, &state, &settings) {
+                    warn!("Failed to start VAD monitor for schedule: {}", err);
+                }
+            }
+        } else {
+            info!("Capture schedule: mic window closed");
+            crate::audio::stop_vad_monitor(app, &state);
+        }
+    }
+    if system_changed {
+        if status.system_audio_allowed {
+            info!("Capture schedule: system audio window opened");
+            if settings.transcribe_enabled {
+                if let Err(err) =
+                    crate::transcription::start_transcribe_monitor(app, state.inner(), &settings)
+                {
+                    warn!("Failed to start system audio capture for schedule: {}", err);
+                }
+            }
+        } else {
+            info!("Capture schedule: system audio window closed");
+            crate::transcription::stop_transcribe_monitor(app, state.inner());
+        }
+    }
+    let _ = app.emit("schedule:changed", status);
+}
+
+/// Spawn the schedule task. Call once from app setup, before the monitors
+/// are started, so capture outside a window never starts.
+pub(crate) fn start(app: AppHandle) {
+    enforce(&app);
+    crate::util::spawn_guarded("capture_schedule", move || loop {
+        std::thread::sleep(POLL_INTERVAL);
+        enforce(&app);
+    });
+}
+
+#[tauri::command]
+pub(crate) fn get_schedule_status(app: AppHandle) -> ScheduleStatus {
+    let state = app.state::<AppState>();
+    let settings = state
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    current_status(&settings.capture_schedule)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(days: &[u8], start: &str, end: &str) -> ScheduleWindow {
+        ScheduleWindow {
+            days: days.to_vec(),
+            start: start.to_string(),
+            end: end.to_string(),
+            mic: true,
+            system_audio: false,
+        }
+    }
+
+    #[test]
+    fn windows_cover_work_hours_and_overnight_spans() {
+        let schedule = CaptureSchedule {
+            enabled: true,
+            windows: vec![
+                window(&[0, 1, 2, 3, 4], "09:00", "17:00"),
+                window(&[4], "22:00", "02:00"),
+            ],
+        };
+        // Monday 10:00 inside, 17:00 outside.
+        assert!(status_at(&schedule, 0, 600).mic_allowed);
+        assert!(!status_at(&schedule, 0, 17 * 60).mic_allowed);
+        assert!(!status_at(&schedule, 0, 600).system_audio_allowed);
+        // Friday 23:00 and Saturday 01:00 are covered by the overnight window.
+        assert!(status_at(&schedule, 4, 23 * 60).mic_allowed);
+        assert!(status_at(&schedule, 5, 60).mic_allowed);
+        assert!(!status_at(&schedule, 5, 3 * 60).mic_allowed);
+    }
+
+    #[test]
+    fn normalization_drops_invalid_windows() {
+        let mut schedule = CaptureSchedule {
+            enabled: true,
+            windows: vec![
+                window(&[9, 2, 2], "9:5", "18:00"),
+                window(&[1], "25:00", "18:00"),
+            ],
+        };
+        normalize_capture_schedule(&mut schedule);
+        assert_eq!(schedule.windows.len(), 1);
+        assert_eq!(schedule.windows[0].days, vec![2]);
+        assert_eq!(schedule.windows[0].start, "09:05");
+    }
+}
@@ -0,0 +1,117 @@
+//! Repeated phrases at loopback chunk boundaries.
+//!
+//! System-audio chunks start with a little of the previous chunk's audio
+//! (pre-roll) so words cut at the boundary are not lost, but whisper then
+//! transcribes those words twice. Before a chunk is stored, the transcribe
+//! worker aligns the end of the previously emitted text with the start of
+//! the new one and drops the repeated words. Only as many words as fit in
+//! the pre-roll are considered, so a longer repeat is taken as real speech.
+
+/// Longest boundary repeat searched for, in words.
+const MAX_OVERLAP_WORDS: usize = 16;
+/// Shorter matches are too often said twice for real ("no, no", "thank
+/// you. Thank you") or a coincidence ("and the").
+const MIN_OVERLAP_WORDS: usize = 3;
+/// Fast speech; bounds how many words the pre-roll can hold.
+const MAX_WORDS_PER_SECOND: u64 = 5;
+/// Leading words of the new chunk that may be skipped before the repeat,
+/// e.g. the tail of a word the pre-roll cut in half.
+const MAX_LEADING_SKIP: usize = 1;
+
+/// Lowercased word without surrounding punctuation, for comparing.
+fn normalize(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
+
+/// Words of `text` with their byte offsets.
+fn words(text: &str) -> Vec<(usize, &str)> {
+    text.split_whitespace()
+        .map(|word| (word.as_ptr() as usize - text.as_ptr() as usize, word))
+        .collect()
+}
+
+/// `next` without the words it repeats from the end of `previous`, given
+/// `pre_roll_ms` of audio shared by the two chunks. Returns `next` unchanged
+/// when no boundary repeat is found, and an empty string when the whole
+/// chunk is a repeat.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub(crate) fn trim_repeated_prefix<'a>(previous: &str, next: &'a str, pre_roll_ms: u64) -> &'a str {
+    let max_words =
+        ((pre_roll_ms * MAX_WORDS_PER_SECOND).div_ceil(1000) as usize).min(MAX_OVERLAP_WORDS);
+    let previous_words: Vec<String> = previous.split_whitespace().map(normalize).collect();
+    let tail = &previous_words[previous_words.len().saturating_sub(MAX_OVERLAP_WORDS)..];
+    let head = words(next);
+    let normalized: Vec<String> = head.iter().map(|(_, word)| normalize(word)).collect();
+
+    for skip in 0..=MAX_LEADING_SKIP {
+        let max_len = tail
+            .len()
+            .min(normalized.len().saturating_sub(skip))
+            .min(max_words);
+        for len in (MIN_OVERLAP_WORDS..=max_len).rev() {
+            let candidate = &normalized[skip..skip + len];
+            if candidate.iter().any(String::is_empty) || candidate != &tail[tail.len() - len..] {
+                continue;
+            }
+            return match head.get(skip + len) {
+                Some((offset, _)) => &next[*offset..],
+                None => "",
+            };
+        }
+    }
+    next
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trims_words_repeated_across_the_boundary() {
+        assert_eq!(
+            trim_repeated_prefix(
+                "We should ship the release on Friday.",
+                "the release on Friday. Then we can start planning.",
+                1_000
+            ),
+            "Then we can start planning."
+        );
+        // Partial first word from the pre-roll.
+        assert_eq!(
+            trim_repeated_prefix(
+                "the new build is green today",
+                "ew build is green today. Ship it",
+                1_000
+            ),
+            "Ship it"
+        );
+        assert_eq!(
+            trim_repeated_prefix("the build is green", "build is green", 1_000),
+            ""
+        );
+        assert_eq!(
+            trim_repeated_prefix("", "hello there", 1_000),
+            "hello there"
+        );
+    }
+
+    #[test]
+    fn keeps_short_and_longer_than_pre_roll_repeats() {
+        // Said twice for real.
+        assert_eq!(
+            trim_repeated_prefix("Thank you.", "Thank you. Next item", 1_000),
+            "Thank you. Next item"
+        );
+        // Six words cannot fit in half a second of pre-roll.
+        let next = "meet at the office on Monday again";
+        assert_eq!(
+            trim_repeated_prefix("we will meet at the office on Monday", next, 500),
+            next
+        );
+        assert_eq!(
+            trim_repeated_prefix("we will meet at the office on Monday", next, 1_500),
+            "again"
+        );
+    }
+}
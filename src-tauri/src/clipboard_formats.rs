@@ -0,0 +1,156 @@
+//! Full-fidelity clipboard snapshots on Windows.
+//!
+//! Every clipboard format backed by global memory is copied byte for byte
+//! before a dictation paste and written back afterwards, so images, HTML,
+//! RTF and application-specific content survive. Formats held as GDI
+//! handles are skipped; Windows synthesizes the common ones (`CF_BITMAP`
+//! from `CF_DIB`). Other platforms use the text / HTML / image snapshot in
+//! `paste_text`.
+
+/// Larger clipboards (huge bitmaps) fall back to the basic snapshot.
+#[cfg(target_os = "windows")]
+const MAX_SNAPSHOT_BYTES: usize = 64 * 1024 * 1024;
+#[cfg(any(test, target_os = "windows"))]
+const CF_UNICODETEXT: u32 = 13;
+
+/// Clipboard formats with their raw data, in enumeration order.
+#[cfg(any(test, target_os = "windows"))]
+pub(crate) struct FormatSnapshot {
+    formats: Vec<(u32, Vec<u8>)>,
+}
+
+#[cfg(any(test, target_os = "windows"))]
+impl FormatSnapshot {
+    /// The snapshot's plain text (`CF_UNICODETEXT`), if it has any.
+    pub(crate) fn text(&self) -> Option<String> {
+        let (_, data) = self
+            .formats
+            .iter()
+            .find(|(format, _)| *format == CF_UNICODETEXT)?;
+        let units: Vec<u16> = data
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .take_while(|unit| *unit != 0)
+            .collect();
+        String::from_utf16(&units).ok()
+    }
+}
+
+/// Whether `format`'s data is global memory that can be copied verbatim.
+/// Excluded: bitmaps, metafiles, palettes, owner-display formats and the
+/// private / GDI-object ranges, whose handles are not plain memory.
+#[cfg(any(test, target_os = "windows"))]
+fn is_copyable_format(format: u32) -> bool {
+    !matches!(format, 2 | 3 | 9 | 14 | 0x80 | 0x82 | 0x83 | 0x8E)
+        && !(0x0200..=0x03FF).contains(&format)
+}
+
+/// Copies all copyable formats. `Err` when the clipboard is busy (retry),
+/// `Ok(None)` when it is empty or too large to keep.
+#[cfg(target_os = "windows")]
+pub(crate) fn capture() -> Result<Option<FormatSnapshot>, String> {
+    use windows::Win32::Foundation::HGLOBAL;
+    use windows::Win32::System::DataExchange::{
+        CloseClipboard, EnumClipboardFormats, GetClipboardData, OpenClipboard,
+    };
+    use windows::Win32::System::Memory::{GlobalLock, GlobalSize, GlobalUnlock};
+
+    let mut formats = Vec::new();
+    let mut total = 0usize;
+    // SAFETY: the clipboard is opened by this thread and closed before
+    // returning; locked memory is copied out before it is unlocked.
+    unsafe {
+        OpenClipboard(None).map_err(|e| format!("OpenClipboard failed: {}", e))?;
+        let mut format = EnumClipboardFormats(0);
+        while format != 0 && total <= MAX_SNAPSHOT_BYTES {
+            let handle = is_copyable_format(format)
+                .then(|| GetClipboardData(format).ok())
+                .flatten()
+                .filter(|handle| !handle.is_invalid());
+            if let Some(handle) = handle {
+                let memory = HGLOBAL(handle.0);
+                let size = GlobalSize(memory);
+                let data = GlobalLock(memory) as *const u8;
+                if !data.is_null() {
+                    if size > 0 {
+                        formats.push((format, std::slice::from_raw_parts(data, size).to_vec()));
+                        total += size;
+                    }
+                    let _ = GlobalUnlock(memory);
+                }
+            }
+            format = EnumClipboardFormats(format);
+        }
+        let _ = CloseClipboard();
+    }
+    if formats.is_empty() || total > MAX_SNAPSHOT_BYTES {
+        return Ok(None);
+    }
+    Ok(Some(FormatSnapshot { formats }))
+}
+
+/// Replaces the clipboard content with `snapshot`.
+#[cfg(target_os = "windows")]
+pub(crate) fn restore(snapshot: &FormatSnapshot) -> Result<(), String> {
+    use windows::Win32::Foundation::HANDLE;
+    use windows::Win32::System::DataExchange::{
+        CloseClipboard, EmptyClipboard, OpenClipboard, SetClipboardData,
+    };
+    use windows::Win32::System::Memory::{
+        GlobalAlloc, GlobalFree, GlobalLock, GlobalUnlock, GMEM_MOVEABLE,
+    };
+
+    // SAFETY: as in `capture`; each buffer is sized to the copied data and
+    // owned by the clipboard once `SetClipboardData` succeeds.
+    unsafe {
+        OpenClipboard(None).map_err(|e| format!("OpenClipboard failed: {}", e))?;
+        if let Err(e) = EmptyClipboard() {
+            let _ = CloseClipboard();
+            return Err(format!("EmptyClipboard failed: {}", e));
+        }
+        for (format, data) in &snapshot.formats {
+            let Ok(memory) = GlobalAlloc(GMEM_MOVEABLE, data.len()) else {
+                continue;
+            };
+            let target = GlobalLock(memory) as *mut u8;
+            if target.is_null() {
+                let _ = GlobalFree(Some(memory));
+                continue;
+            }
+            std::ptr::copy_nonoverlapping(data.as_ptr(), target, data.len());
+            let _ = GlobalUnlock(memory);
+            if SetClipboardData(*format, Some(HANDLE(memory.0))).is_err() {
+                let _ = GlobalFree(Some(memory));
+            }
+        }
+        CloseClipboard().map_err(|e| format!("CloseClipboard failed: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_memory_formats_and_reads_unicode_text() {
+        assert!(is_copyable_format(CF_UNICODETEXT));
+        assert!(is_copyable_format(8)); // CF_DIB
+        assert!(is_copyable_format(0xC0F1)); // registered, e.g. "HTML Format"
+        assert!(!is_copyable_format(2)); // CF_BITMAP
+        assert!(!is_copyable_format(14)); // CF_ENHMETAFILE
+        assert!(!is_copyable_format(0x0250)); // private range
+
+        let text: Vec<u8> = "Grüße\0garbage"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        let snapshot = FormatSnapshot {
+            formats: vec![(1, b"Gr??e\0".to_vec()), (CF_UNICODETEXT, text)],
+        };
+        assert_eq!(snapshot.text().as_deref(), Some("Grüße"));
+        let empty = FormatSnapshot {
+            formats: Vec::new(),
+        };
+        assert_eq!(empty.text(), None);
+    }
+}
@@ -0,0 +1,43 @@
+//! Cloud transcription API keys live only in the OS keychain. Unlike the AI
+//! refinement key store there is deliberately no plaintext file fallback:
+//! if the keychain is unavailable, storing a key fails.
+
+const KEYRING_SERVICE: &str = "com.trispr.flow.cloud-transcription";
+
+fn normalize_provider(provider: &str) -> Result<&'static str, String> {
+    super::normalize_cloud_provider_id(provider)
+        .ok_or_else(|| format!("Unknown cloud transcription provider: {}", provider))
+}
+
+fn keyring_entry(provider: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYRING_SERVICE, provider)
+        .map_err(|e| format!("Failed to create keyring entry: {}", e))
+}
+
+pub fn store_api_key(provider: &str, api_key: &str) -> Result<(), String> {
+    let provider = normalize_provider(provider)?;
+    let key = api_key.trim();
+    if key.is_empty() {
+        return Err("API key cannot be empty".to_string());
+    }
+    keyring_entry(provider)?
+        .set_password(key)
+        .map_err(|e| format!("Failed to store key in system keyring: {}", e))
+}
+
+pub fn read_api_key(provider: &str) -> Result<Option<String>, String> {
+    let provider = normalize_provider(provider)?;
+    match keyring_entry(provider)?.get_password() {
+        Ok(key) if !key.trim().is_empty() => Ok(Some(key.trim().to_string())),
+        Ok(_) | Err(keyring::Error::NoEntry) => Ok(None),
+        Err(err) => Err(format!("Failed to read key from system keyring: {}", err)),
+    }
+}
+
+pub fn clear_api_key(provider: &str) -> Result<(), String> {
+    let provider = normalize_provider(provider)?;
+    match keyring_entry(provider)?.delete_password() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(err) => Err(format!("Failed to delete key from system keyring: {}", err)),
+    }
+}
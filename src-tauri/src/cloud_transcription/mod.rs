@@ -0,0 +1,481 @@
+//! Cloud speech-to-text providers.
+//!
+//! A `CloudProvider` turns a 16 kHz mono WAV into text. OpenAI and Groq share
+//! the `audio/transcriptions` multipart API; Deepgram takes the raw WAV body;
+//! `custom` keeps the original bespoke endpoint (`TRISPR_CLOUD_ENDPOINT`,
+//! JSON `{ "text": ... }` response) working.
+//!
+//! API keys come from the OS keychain (`set_cloud_credentials`); the
+//! per-provider environment variable is only a development fallback.
+
+pub(crate) mod keyring;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Write;
+use std::time::Duration;
+
+use crate::errors::{CommandResult, ErrorCode, WithErrorCode};
+use crate::whisper_server::{write_multipart_field_file, write_multipart_field_text};
+use tracing::warn;
+
+pub(crate) const CLOUD_PROVIDER_IDS: &[&str] = &["openai", "groq", "deepgram", "custom"];
+pub(crate) const TRANSCRIPTION_ENGINE_IDS: &[&str] = &["local", "cloud"];
+const MULTIPART_BOUNDARY: &str = "trispr_cloud_boundary_5d1c7e";
+const CLOUD_CONNECT_TIMEOUT_SECS: u64 = 5;
+const CLOUD_READ_TIMEOUT_SECS: u64 = 120;
+
+/// Mirrors `src/types.ts::CloudTranscriptionSettings`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct CloudTranscriptionSettings {
+    pub(crate) enabled: bool,
+    /// "openai" | "groq" | "deepgram" | "custom"
+    pub(crate) provider: String,
+    /// Provider model id; empty uses the provider default.
+    pub(crate) model: String,
+    /// Base URL override (required for `custom`, optional for the others).
+    pub(crate) endpoint: String,
+    /// Engines tried in order until one succeeds: "local" | "cloud".
+    /// "cloud" is skipped while `enabled` is false.
+    pub(crate) fallback_order: Vec<String>,
+}
+
+fn default_fallback_order() -> Vec<String> {
+    vec!["local".to_string(), "cloud".to_string()]
+}
+
+impl Default for CloudTranscriptionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: "openai".to_string(),
+            model: String::new(),
+            endpoint: String::new(),
+            fallback_order: default_fallback_order(),
+        }
+    }
+}
+
+pub(crate) fn normalize_cloud_provider_id(provider: &str) -> Option<&'static str> {
+    let normalized = provider.trim().to_lowercase();
+    CLOUD_PROVIDER_IDS
+        .iter()
+        .copied()
+        .find(|id| *id == normalized)
+}
+
+pub(crate) fn normalize_cloud_transcription_settings(settings: &mut CloudTranscriptionSettings) {
+    settings.provider = normalize_cloud_provider_id(&settings.provider)
+        .unwrap_or("openai")
+        .to_string();
+    settings.model = settings.model.trim().to_string();
+    settings.endpoint = settings.endpoint.trim().trim_end_matches('/').to_string();
+
+    let mut order: Vec<String> = Vec::new();
+    for engine in &settings.fallback_order {
+        let engine = engine.trim().to_lowercase();
+        if TRANSCRIPTION_ENGINE_IDS.contains(&engine.as_str()) && !order.contains(&engine) {
+            order.push(engine);
+        }
+    }
+    settings.fallback_order = if order.is_empty() {
+        default_fallback_order()
+    } else {
+        order
+    };
+}
+
+pub(crate) struct CloudTranscriptionRequest<'a> {
+    pub(crate) wav_bytes: &'a [u8],
+    pub(crate) model: &'a str,
+    /// ISO-639-1 code, or "auto" to let the provider detect it.
+    pub(crate) language: &'a str,
+    pub(crate) endpoint: &'a str,
+}
+
+pub(crate) trait CloudProvider: Send + Sync {
+    fn id(&self) -> &'static str;
+    fn default_model(&self) -> &'static str;
+    /// Environment variable consulted when no keychain entry exists.
+    fn api_key_env(&self) -> &'static str;
+    fn requires_api_key(&self) -> bool {
+        true
+    }
+    fn transcribe(
+        &self,
+        request: &CloudTranscriptionRequest<'_>,
+        api_key: &str,
+    ) -> Result<String, String>;
+}
+
+/// OpenAI `audio/transcriptions` and API-compatible services (Groq).
+struct OpenAICompatibleProvider {
+    id: &'static str,
+    base_url: &'static str,
+    default_model: &'static str,
+    api_key_env: &'static str,
+}
+
+struct DeepgramProvider;
+
+/// Bespoke endpoint: raw WAV POST, `{ "text": ... }` response.
+struct CustomEndpointProvider;
+
+pub(crate) fn create_cloud_provider(provider: &str) -> Result<Box<dyn CloudProvider>, String> {
+    match normalize_cloud_provider_id(provider) {
+        Some("openai") => Ok(Box::new(OpenAICompatibleProvider {
+            id: "openai",
+            base_url: "https://api.openai.com/v1",
+            default_model: "whisper-1",
+            api_key_env: "OPENAI_API_KEY",
+        })),
+        Some("groq") => Ok(Box::new(OpenAICompatibleProvider {
+            id: "groq",
+            base_url: "https://api.groq.com/openai/v1",
+            default_model: "whisper-large-v3-turbo",
+            api_key_env: "GROQ_API_KEY",
+        })),
+        Some("deepgram") => Ok(Box::new(DeepgramProvider)),
+        Some("custom") => Ok(Box::new(CustomEndpointProvider)),
+        _ => Err(format!(
+            "Unknown cloud transcription provider: {}",
+            provider
+        )),
+    }
+}
+
+fn cloud_agent() -> ureq::Agent {
+    ureq::builder()
+        .timeout_connect(Duration::from_secs(CLOUD_CONNECT_TIMEOUT_SECS))
+        .timeout_read(Duration::from_secs(CLOUD_READ_TIMEOUT_SECS))
+        .build()
+}
+
+fn map_ureq_error(provider: &str, err: ureq::Error) -> String {
+    match err {
+        ureq::Error::Status(code, response) => {
+            crate::format_ureq_status_error(&format!("{} transcription", provider), code, response)
+        }
+        ureq::Error::Transport(transport) => {
+            format!(
+                "{} transcription connection failed: {}",
+                provider, transport
+            )
+        }
+    }
+}
+
+fn language_param(language: &str) -> Option<&str> {
+    let trimmed = language.trim();
+    if trimmed.is_empty() || trimmed.eq_ignore_ascii_case("auto") {
+        None
+    } else {
+        Some(trimmed)
+    }
+}
+
+fn parse_text_field(json: &serde_json::Value) -> Option<String> {
+    json.get("text")
+        .and_then(|v| v.as_str())
+        .map(|text| text.trim().to_string())
+}
+
+fn parse_deepgram_transcript(json: &serde_json::Value) -> Option<String> {
+    json.pointer("/results/channels/0/alternatives/0/transcript")
+        .and_then(|v| v.as_str())
+        .map(|text| text.trim().to_string())
+}
+
+impl CloudProvider for OpenAICompatibleProvider {
+    fn id(&self) -> &'static str {
+        self.id
+    }
+
+    fn default_model(&self) -> &'static str {
+        self.default_model
+    }
+
+    fn api_key_env(&self) -> &'static str {
+        self.api_key_env
+    }
+
+    fn transcribe(
+        &self,
+        request: &CloudTranscriptionRequest<'_>,
+        api_key: &str,
+    ) -> Result<String, String> {
+        let encode_err = |e: std::io::Error| format!("Failed to encode multipart: {}", e);
+        let mut body: Vec<u8> = Vec::new();
+        write_multipart_field_file(
+            &mut body,
+            MULTIPART_BOUNDARY,
+            "file",
+            "audio.wav",
+            "audio/wav",
+            request.wav_bytes,
+        )
+        .map_err(encode_err)?;
+        write_multipart_field_text(&mut body, MULTIPART_BOUNDARY, "model", request.model)
+            .map_err(encode_err)?;
+        write_multipart_field_text(&mut body, MULTIPART_BOUNDARY, "response_format", "json")
+            .map_err(encode_err)?;
+        if let Some(language) = language_param(request.language) {
+            write_multipart_field_text(&mut body, MULTIPART_BOUNDARY, "language", language)
+                .map_err(encode_err)?;
+        }
+        write!(body, "--{}--\r\n", MULTIPART_BOUNDARY).map_err(encode_err)?;
+
+        let base_url = if request.endpoint.is_empty() {
+            self.base_url
+        } else {
+            request.endpoint
+        };
+        let json: serde_json::Value = cloud_agent()
+            .post(&format!("{}/audio/transcriptions", base_url))
+            .set("Authorization", &format!("Bearer {}", api_key))
+            .set(
+                "Content-Type",
+                &format!("multipart/form-data; boundary={}", MULTIPART_BOUNDARY),
+            )
+            .send_bytes(&body)
+            .map_err(|err| map_ureq_error(self.id, err))?
+            .into_json()
+            .map_err(|e| format!("Failed to parse {} response: {}", self.id, e))?;
+
+        parse_text_field(&json)
+            .ok_or_else(|| format!("No transcript in {} response: {}", self.id, json))
+    }
+}
+
+impl CloudProvider for DeepgramProvider {
+    fn id(&self) -> &'static str {
+        "deepgram"
+    }
+
+    fn default_model(&self) -> &'static str {
+        "nova-2"
+    }
+
+    fn api_key_env(&self) -> &'static str {
+        "DEEPGRAM_API_KEY"
+    }
+
+    fn transcribe(
+        &self,
+        request: &CloudTranscriptionRequest<'_>,
+        api_key: &str,
+    ) -> Result<String, String> {
+        let base_url = if request.endpoint.is_empty() {
+            "https://api.deepgram.com/v1"
+        } else {
+            request.endpoint
+        };
+        let mut req = cloud_agent()
+            .post(&format!("{}/listen", base_url))
+            .query("model", request.model)
+            .query("smart_format", "true")
+            .set("Authorization", &format!("Token {}", api_key))
+            .set("Content-Type", "audio/wav");
+        req = match language_param(request.language) {
+            Some(language) => req.query("language", language),
+            None => req.query("detect_language", "true"),
+        };
+        let json: serde_json::Value = req
+            .send_bytes(request.wav_bytes)
+            .map_err(|err| map_ureq_error("deepgram", err))?
+            .into_json()
+            .map_err(|e| format!("Failed to parse deepgram response: {}", e))?;
+
+        parse_deepgram_transcript(&json)
+            .ok_or_else(|| format!("No transcript in deepgram response: {}", json))
+    }
+}
+
+impl CloudProvider for CustomEndpointProvider {
+    fn id(&self) -> &'static str {
+        "custom"
+    }
+
+    fn default_model(&self) -> &'static str {
+        ""
+    }
+
+    fn api_key_env(&self) -> &'static str {
+        "TRISPR_CLOUD_TOKEN"
+    }
+
+    fn requires_api_key(&self) -> bool {
+        false
+    }
+
+    fn transcribe(
+        &self,
+        request: &CloudTranscriptionRequest<'_>,
+        api_key: &str,
+    ) -> Result<String, String> {
+        let endpoint = if request.endpoint.is_empty() {
+            std::env::var("TRISPR_CLOUD_ENDPOINT").unwrap_or_default()
+        } else {
+            request.endpoint.to_string()
+        };
+        if endpoint.trim().is_empty() {
+            return Err("Custom cloud transcription endpoint is not configured".to_string());
+        }
+
+        let mut req = cloud_agent()
+            .post(endpoint.trim())
+            .set("Content-Type", "audio/wav");
+        if !api_key.trim().is_empty() {
+            req = req.set("Authorization", &format!("Bearer {}", api_key.trim()));
+        }
+        let json: serde_json::Value = req
+            .send_bytes(request.wav_bytes)
+            .map_err(|err| map_ureq_error("custom", err))?
+            .into_json()
+            .map_err(|e| format!("Failed to parse custom endpoint response: {}", e))?;
+
+        parse_text_field(&json)
+            .ok_or_else(|| format!("No transcript in custom endpoint response: {}", json))
+    }
+}
+
+fn resolve_api_key(provider: &dyn CloudProvider) -> Option<String> {
+    match keyring::read_api_key(provider.id()) {
+        Ok(Some(key)) => return Some(key),
+        Ok(None) => {}
+        Err(err) => warn!(
+            "Cloud transcription key lookup failed for '{}': {}",
+            provider.id(),
+            err
+        ),
+    }
+    std::env::var(provider.api_key_env())
+        .ok()
+        .map(|key| key.trim().to_string())
+        .filter(|key| !key.is_empty())
+}
+
+/// Transcribe a WAV with the given provider settings. Returns the text and the
+/// provider id that produced it.
+pub(crate) fn transcribe_with_settings(
+    cloud: &CloudTranscriptionSettings,
+    language: &str,
+    wav_bytes: &[u8],
+) -> Result<(String, &'static str), String> {
+    let provider = create_cloud_provider(&cloud.provider)?;
+    let api_key = resolve_api_key(provider.as_ref()).unwrap_or_default();
+    if api_key.is_empty() && provider.requires_api_key() {
+        return Err(format!(
+            "No API key configured for cloud provider '{}'. Store one with set_cloud_credentials (or set {} for development).",
+            provider.id(),
+            provider.api_key_env()
+        ));
+    }
+    let model = if cloud.model.is_empty() {
+        provider.default_model()
+    } else {
+        cloud.model.as_str()
+    };
+    let request = CloudTranscriptionRequest {
+        wav_bytes,
+        model,
+        language,
+        endpoint: cloud.endpoint.as_str(),
+    };
+    let text = provider.transcribe(&request, &api_key)?;
+    Ok((text, provider.id()))
+}
+
+#[tauri::command]
+pub(crate) fn set_cloud_credentials(provider: String, api_key: String) -> CommandResult<()> {
+    keyring::store_api_key(&provider, &api_key).with_code(ErrorCode::Credentials)
+}
+
+#[tauri::command]
+pub(crate) fn clear_cloud_credentials(provider: String) -> CommandResult<()> {
+    keyring::clear_api_key(&provider).with_code(ErrorCode::Credentials)
+}
+
+/// Which providers have a key in the keychain. Never returns the keys.
+#[tauri::command]
+pub(crate) fn get_cloud_credentials_status() -> HashMap<String, bool> {
+    CLOUD_PROVIDER_IDS
+        .iter()
+        .map(|id| {
+            let stored = matches!(keyring::read_api_key(id), Ok(Some(_)));
+            (id.to_string(), stored)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_settings_falls_back_to_openai() {
+        let mut settings = CloudTranscriptionSettings {
+            provider: " Groq ".to_string(),
+            endpoint: "https://example.test/v1/".to_string(),
+            ..CloudTranscriptionSettings::default()
+        };
+        normalize_cloud_transcription_settings(&mut settings);
+        assert_eq!(settings.provider, "groq");
+        assert_eq!(settings.endpoint, "https://example.test/v1");
+
+        settings.provider = "azure".to_string();
+        normalize_cloud_transcription_settings(&mut settings);
+        assert_eq!(settings.provider, "openai");
+    }
+
+    #[test]
+    fn normalize_fallback_order_dedupes_and_defaults() {
+        let mut settings = CloudTranscriptionSettings {
+            fallback_order: vec![
+                "Cloud".to_string(),
+                "bogus".to_string(),
+                "cloud".to_string(),
+                "local".to_string(),
+            ],
+            ..CloudTranscriptionSettings::default()
+        };
+        normalize_cloud_transcription_settings(&mut settings);
+        assert_eq!(settings.fallback_order, vec!["cloud", "local"]);
+
+        settings.fallback_order = vec!["nope".to_string()];
+        normalize_cloud_transcription_settings(&mut settings);
+        assert_eq!(settings.fallback_order, vec!["local", "cloud"]);
+    }
+
+    #[test]
+    fn factory_creates_every_known_provider() {
+        for id in CLOUD_PROVIDER_IDS {
+            let provider = create_cloud_provider(id).expect("known provider");
+            assert_eq!(provider.id(), *id);
+        }
+        assert!(create_cloud_provider("unknown").is_err());
+    }
+
+    #[test]
+    fn parses_provider_responses() {
+        let openai = serde_json::json!({ "text": " hello world " });
+        assert_eq!(parse_text_field(&openai).as_deref(), Some("hello world"));
+
+        let deepgram = serde_json::json!({
+            "results": { "channels": [ { "alternatives": [ { "transcript": "hallo welt" } ] } ] }
+        });
+        assert_eq!(
+            parse_deepgram_transcript(&deepgram).as_deref(),
+            Some("hallo welt")
+        );
+        assert!(parse_deepgram_transcript(&openai).is_none());
+    }
+
+    #[test]
+    fn auto_language_is_not_sent() {
+        assert_eq!(language_param("auto"), None);
+        assert_eq!(language_param(""), None);
+        assert_eq!(language_param("de"), Some("de"));
+    }
+}
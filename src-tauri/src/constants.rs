@@ -8,6 +8,15 @@ pub const VAD_SILENCE_MS_DEFAULT: u64 = 700;
 pub const VAD_MIN_VOICE_MS: u64 = 120;
 pub const VAD_MIN_CONSECUTIVE_CHUNKS: u64 = 3;
 
+pub const HOTKEY_GESTURE_HOLD_MS_DEFAULT: u64 = 300;
+pub const HOTKEY_GESTURE_DOUBLE_TAP_MS_DEFAULT: u64 = 250;
+pub const PTT_LOCK_TAP_MS_DEFAULT: u64 = 250;
+pub const PRE_ROLL_MS_DEFAULT: u64 = 500;
+pub const PRE_ROLL_MS_MAX: u64 = 1_500;
+pub const MAX_RECORDING_MS_DEFAULT: u64 = 5 * 60 * 1000;
+pub const MAX_RECORDING_MS_MIN: u64 = 30_000;
+pub const MAX_RECORDING_MS_MAX: u64 = 60 * 60 * 1000;
+
 pub const HALLUCINATION_RMS_THRESHOLD: f32 = 0.012; // ~ -38 dB
 pub const HALLUCINATION_MAX_WORDS: usize = 2;
 pub const HALLUCINATION_MAX_CHARS: usize = 12;
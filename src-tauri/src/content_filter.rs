@@ -0,0 +1,225 @@
+//! Profanity and PII filter, applied after post-processing by
+//! `process_for_output` (also when post-processing is off).
+//!
+//! Detects profanity (built-in English/German list plus user words) and PII
+//! patterns — e-mail addresses, card numbers (Luhn-checked) and phone
+//! numbers. Depending on `content_filter_action` a hit is masked, drops the
+//! whole transcript, or is only noted; in every kept case the history entry
+//! is tagged "profanity" / "pii".
+
+use std::sync::LazyLock;
+
+use regex::{Captures, Regex};
+
+use crate::state::Settings;
+
+pub(crate) const PROFANITY_TAG: &str = "profanity";
+pub(crate) const PII_TAG: &str = "pii";
+
+static PROFANITY: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\b(fuck\w*|motherfuck\w*|shit\w*|bullshit|bitch\w*|asshole\w*|bastard\w*|cunt\w*|dickhead\w*|scheiß\w*|scheiss\w*|arschloch\w*|fotze\w*|wichser\w*|hurensohn\w*)\b",
+    )
+    .expect("valid regex")
+});
+static EMAIL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b[a-z0-9._%+-]+@[a-z0-9.-]+\.[a-z]{2,}\b").expect("valid regex")
+});
+static CARD: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(?:\d[ -]?){12,18}\d\b").expect("valid regex"));
+static PHONE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:\+\d{1,3}[ .-]?)?(?:\(\d{1,5}\)[ .-]?)?\d{2,5}(?:[ .-]\d{2,8}){1,4}")
+        .expect("valid regex")
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ContentFilterAction {
+    /// Replace hits with `f***` / `[email]` style placeholders.
+    Mask,
+    /// Discard the whole transcript.
+    Drop,
+    /// Keep the text as is and only tag the entry.
+    Tag,
+}
+
+impl ContentFilterAction {
+    pub(crate) fn from_setting(value: &str) -> Self {
+        match value.trim() {
+            "drop" => Self::Drop,
+            "tag" => Self::Tag,
+            _ => Self::Mask,
+        }
+    }
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Mask => "mask",
+            Self::Drop => "drop",
+            Self::Tag => "tag",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ContentFilterResult {
+    pub(crate) text: String,
+    /// History tags for what was found.
+    pub(crate) tags: Vec<String>,
+    /// The transcript must not be kept (action "drop" with a hit).
+    pub(crate) dropped: bool,
+}
+
+fn luhn_valid(digits: &[u32]) -> bool {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(index, &digit)| {
+            if index % 2 == 1 {
+                let doubled = digit * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                digit
+            }
+        })
+        .sum();
+    sum % 10 == 0
+}
+
+fn is_card_number(candidate: &str) -> bool {
+    let digits: Vec<u32> = candidate.chars().filter_map(|c| c.to_digit(10)).collect();
+    (13..=19).contains(&digits.len()) && luhn_valid(&digits)
+}
+
+/// Needs 7–15 digits and a leading `+` or grouping, so plain amounts and
+/// years are not taken for phone numbers.
+fn is_phone_number(candidate: &str) -> bool {
+    let digits = candidate.chars().filter(char::is_ascii_digit).count();
+    let grouped = candidate.starts_with('+') || candidate.contains('(') || {
+        candidate
+            .split([' ', '.', '-'])
+            .filter(|group| !group.is_empty())
+            .count()
+            >= 3
+    };
+    (7..=15).contains(&digits) && grouped
+}
+
+fn mask_word(word: &str) -> String {
+    let mut chars = word.chars();
+    let first = chars.next().map(String::from).unwrap_or_default();
+    format!("{}{}", first, "*".repeat(chars.count()))
+}
+
+/// Replace every accepted match of `pattern`; returns whether any matched.
+fn replace_matches(
+    text: &mut String,
+    pattern: &Regex,
+    accept: impl Fn(&str) -> bool,
+    mask: impl Fn(&str) -> String,
+) -> bool {
+    let mut found = false;
+    let replaced = pattern.replace_all(text.as_str(), |caps: &Captures| {
+        let matched = &caps[0];
+        if accept(matched) {
+            found = true;
+            mask(matched)
+        } else {
+            matched.to_string()
+        }
+    });
+    if found {
+        *text = replaced.into_owned();
+    }
+    found
+}
+
+fn custom_word_pattern(words: &[String]) -> Option<Regex> {
+    let alternatives: Vec<String> = words
+        .iter()
+        .map(|word| word.trim())
+        .filter(|word| !word.is_empty())
+        .map(regex::escape)
+        .collect();
+    if alternatives.is_empty() {
+        return None;
+    }
+    Regex::new(&format!(r"(?i)\b({})\b", alternatives.join("|"))).ok()
+}
+
+/// Scan (and, for "mask", rewrite) `text` according to the settings.
+pub(crate) fn apply_content_filter(text: &str, settings: &Settings) -> ContentFilterResult {
+    let mut masked = text.to_string();
+    let mut tags = Vec::new();
+
+    if settings.content_filter_profanity {
+        let mut found = replace_matches(&mut masked, &PROFANITY, |_| true, mask_word);
+        if let Some(custom) = custom_word_pattern(&settings.content_filter_words) {
+            found |= replace_matches(&mut masked, &custom, |_| true, mask_word);
+        }
+        if found {
+            tags.push(PROFANITY_TAG.to_string());
+        }
+    }
+    if settings.content_filter_pii {
+        // E-mails first so their digits are not read as phone numbers.
+        let mut found = replace_matches(&mut masked, &EMAIL, |_| true, |_| "[email]".into());
+        found |= replace_matches(&mut masked, &CARD, is_card_number, |_| "[card]".into());
+        found |= replace_matches(&mut masked, &PHONE, is_phone_number, |_| "[phone]".into());
+        if found {
+            tags.push(PII_TAG.to_string());
+        }
+    }
+
+    let action = ContentFilterAction::from_setting(&settings.content_filter_action);
+    ContentFilterResult {
+        text: if action == ContentFilterAction::Mask {
+            masked
+        } else {
+            text.to_string()
+        },
+        dropped: action == ContentFilterAction::Drop && !tags.is_empty(),
+        tags,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masks_profanity_and_pii_and_honors_action() {
+        let mut settings = Settings {
+            content_filter_enabled: true,
+            content_filter_words: vec!["Projekt Falke".to_string()],
+            ..Settings::default()
+        };
+        let text = "Shit, mail me at jo.doe@example.com or call +49 170 1234567 \
+                    about Projekt Falke, card 4111 1111 1111 1111 in 2024 for 250 euros";
+        let result = apply_content_filter(text, &settings);
+        assert_eq!(
+            result.text,
+            "S***, mail me at [email] or call [phone] about P************, \
+             card [card] in 2024 for 250 euros"
+        );
+        assert_eq!(result.tags, vec![PROFANITY_TAG, PII_TAG]);
+        assert!(!result.dropped);
+
+        // A 16-digit number failing the Luhn check is not a card.
+        let clean = apply_content_filter("order 1234 5678 9012 3456 shipped", &settings);
+        assert!(clean.tags.is_empty());
+
+        settings.content_filter_action = "tag".to_string();
+        let tagged = apply_content_filter(text, &settings);
+        assert_eq!(tagged.text, text);
+        assert_eq!(tagged.tags.len(), 2);
+
+        settings.content_filter_action = "drop".to_string();
+        assert!(apply_content_filter(text, &settings).dropped);
+        assert!(!apply_content_filter("all good", &settings).dropped);
+    }
+}
@@ -0,0 +1,107 @@
+//! Rolling transcript context for system-audio chunks.
+//!
+//! Each loopback chunk is decoded on its own, so a sentence split across two
+//! chunks loses its casing, the spelling of names and sometimes the language.
+//! With `transcribe_context_priming` on, the worker keeps the tail of the last
+//! stored chunk and hands it to whisper as the prompt (after the vocabulary
+//! terms) for the next one. It runs with a single decode worker so each chunk
+//! sees the text published just before it; the prompt is set per decode
+//! thread, so mic dictation never sees it.
+
+use std::cell::RefCell;
+use std::sync::Mutex;
+
+/// Context budget; the vocabulary prompt keeps the rest of whisper's window.
+const MAX_CONTEXT_CHARS: usize = 200;
+
+thread_local! {
+    static CONTEXT_PROMPT: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Runs `decode` with `context` as this thread's prompt context.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub(crate) fn with_context<T>(context: Option<String>, decode: impl FnOnce() -> T) -> T {
+    CONTEXT_PROMPT.with(|prompt| *prompt.borrow_mut() = context);
+    let result = decode();
+    CONTEXT_PROMPT.with(|prompt| *prompt.borrow_mut() = None);
+    result
+}
+
+/// Prompt context for the decode running on this thread, if any.
+pub(crate) fn current_context() -> Option<String> {
+    CONTEXT_PROMPT.with(|prompt| prompt.borrow().clone())
+}
+
+/// The last `MAX_CONTEXT_CHARS` of `text`, starting at a word boundary.
+fn context_tail(text: &str) -> &str {
+    let text = text.trim();
+    if text.len() <= MAX_CONTEXT_CHARS {
+        return text;
+    }
+    let mut start = text.len() - MAX_CONTEXT_CHARS;
+    while !text.is_char_boundary(start) {
+        start += 1;
+    }
+    let tail = &text[start..];
+    match tail.find(char::is_whitespace) {
+        Some(space) => tail[space..].trim_start(),
+        None => tail,
+    }
+}
+
+/// Tail of the last stored chunk, shared by the decode workers.
+#[derive(Default)]
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub(crate) struct RollingContext {
+    tail: Mutex<Option<String>>,
+}
+
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+impl RollingContext {
+    pub(crate) fn remember(&self, text: &str) {
+        let tail = context_tail(text);
+        *self
+            .tail
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) =
+            (!tail.is_empty()).then(|| tail.to_string());
+    }
+
+    pub(crate) fn get(&self) -> Option<String> {
+        self.tail
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn context_keeps_a_word_aligned_tail() {
+        let context = RollingContext::default();
+        context.remember("  Meeting with Anja about Trispr.  ");
+        assert_eq!(
+            context.get().as_deref(),
+            Some("Meeting with Anja about Trispr.")
+        );
+
+        let long = format!("{} the end of the sentence", "wörter ".repeat(60));
+        context.remember(&long);
+        let tail = context.get().unwrap();
+        assert!(tail.len() <= MAX_CONTEXT_CHARS);
+        assert!(tail.starts_with("wörter "));
+        assert!(tail.ends_with("the end of the sentence"));
+
+        context.remember("   ");
+        assert_eq!(context.get(), None);
+
+        assert_eq!(
+            with_context(Some("prior".to_string()), current_context).as_deref(),
+            Some("prior")
+        );
+        assert_eq!(current_context(), None);
+    }
+}
@@ -0,0 +1,213 @@
+//! Conversation view: mic and system-audio history merged into one
+//! chronological transcript, with the local speaker as "Me" and the other
+//! side as "Them". Imported files sit in the mic history but were not spoken
+//! by the user, so they get a neutral "File" label.
+//!
+//! Scoped to a transcript session's time window when a session id is given
+//! (archived months included), otherwise to the active history partitions.
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::errors::CommandResult;
+use crate::state::{AppState, HistoryEntry};
+
+const ME_LABEL: &str = "Me";
+const THEM_LABEL: &str = "Them";
+const FILE_LABEL: &str = "File";
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ConversationTurn {
+    pub(crate) entry_id: String,
+    /// "me" (mic), "them" (system audio) or "file" (imported file).
+    pub(crate) role: &'static str,
+    /// Diarized speaker name when known, else "Me" / "Them" / "File".
+    pub(crate) speaker: String,
+    pub(crate) text: String,
+    pub(crate) timestamp_ms: u64,
+    pub(crate) source: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct Conversation {
+    pub(crate) session_id: Option<String>,
+    pub(crate) name: Option<String>,
+    pub(crate) started_ms: Option<u64>,
+    pub(crate) ended_ms: Option<u64>,
+    pub(crate) turns: Vec<ConversationTurn>,
+}
+
+fn preferred_text(entry: &HistoryEntry) -> String {
+    entry
+        .refinement
+        .as_ref()
+        .filter(|refinement| refinement.status == "refined" && !refinement.refined.is_empty())
+        .map(|refinement| refinement.refined.clone())
+        .unwrap_or_else(|| entry.text.clone())
+}
+
+fn turn(entry: &HistoryEntry, me: bool) -> ConversationTurn {
+    let (role, fallback) = if entry.source == crate::file_transcription::FILE_HISTORY_SOURCE {
+        ("file", FILE_LABEL)
+    } else if me {
+        ("me", ME_LABEL)
+    } else {
+        ("them", THEM_LABEL)
+    };
+    ConversationTurn {
+        entry_id: entry.id.clone(),
+        role,
+        speaker: entry
+            .speaker_name
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .unwrap_or(fallback)
+            .to_string(),
+        text: preferred_text(entry).trim().to_string(),
+        timestamp_ms: entry.timestamp_ms,
+        source: entry.source.clone(),
+    }
+}
+
+/// Interleaves both histories oldest-first, keeping entries inside
+/// `[from_ms, to_ms]` when a window is given. Empty entries are dropped.
+fn interleave(
+    mic: &[HistoryEntry],
+    system: &[HistoryEntry],
+    window: Option<(u64, u64)>,
+) -> Vec<ConversationTurn> {
+    let in_window = |entry: &&HistoryEntry| {
+        window.is_none_or(|(from, to)| entry.timestamp_ms >= from && entry.timestamp_ms <= to)
+    };
+    let mut turns: Vec<ConversationTurn> = mic
+        .iter()
+        .filter(in_window)
+        .map(|entry| turn(entry, true))
+        .chain(
+            system
+                .iter()
+                .filter(in_window)
+                .map(|entry| turn(entry, false)),
+        )
+        .filter(|turn| !turn.text.is_empty())
+        .collect();
+    // Stable sort: a mic and system entry with the same timestamp keep mic first.
+    turns.sort_by_key(|turn| turn.timestamp_ms);
+    turns
+}
+
+#[tauri::command]
+pub(crate) fn get_conversation(
+    app: AppHandle,
+    session_id: Option<String>,
+) -> CommandResult<Conversation> {
+    let state = app.state::<AppState>();
+    let session_id = session_id.filter(|id| !id.trim().is_empty());
+    let Some(session_id) = session_id else {
+        let mic: Vec<HistoryEntry> = state
+            .history
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .active
+            .iter()
+            .cloned()
+            .collect();
+        let system: Vec<HistoryEntry> = state
+            .history_transcribe
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .active
+            .iter()
+            .cloned()
+            .collect();
+        return Ok(Conversation {
+            session_id: None,
+            name: None,
+            started_ms: None,
+            ended_ms: None,
+            turns: interleave(&mic, &system, None),
+        });
+    };
+
+    let session = crate::session_manager::get_session_transcript(app.clone(), session_id)?;
+    let window = (
+        session.started_ms,
+        session.ended_ms.unwrap_or_else(crate::util::now_ms),
+    );
+    let mic = state
+        .history
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .all_entries();
+    let system = state
+        .history_transcribe
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .all_entries();
+    Ok(Conversation {
+        session_id: Some(session.id),
+        name: Some(session.name),
+        started_ms: Some(session.started_ms),
+        ended_ms: session.ended_ms,
+        turns: interleave(&mic, &system, Some(window)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, text: &str, timestamp_ms: u64, source: &str) -> HistoryEntry {
+        HistoryEntry {
+            id: id.to_string(),
+            text: text.to_string(),
+            timestamp_ms,
+            source: source.to_string(),
+            speaker_name: None,
+            refinement: None,
+            audio_path: None,
+            revisions: Vec::new(),
+            pinned: false,
+            tags: Vec::new(),
+            note: None,
+            formatted_text: None,
+            language: None,
+            translation: None,
+        }
+    }
+
+    #[test]
+    fn interleaves_by_time_with_me_and_them_labels() {
+        let mic = vec![
+            entry("h_3", "Sounds good", 3_000, "mic"),
+            entry("h_1", "Hi there", 1_000, "mic"),
+            entry("h_9", "too late", 9_000, "mic"),
+            entry("f_6", "From a recording", 6_000, "file"),
+        ];
+        let mut remote = entry("o_2", " Hello! ", 2_000, "output");
+        remote.speaker_name = Some("Alex".to_string());
+        let system = vec![
+            remote,
+            entry("o_4", "Bye", 4_000, "output"),
+            entry("o_5", "   ", 5_000, "output"),
+        ];
+
+        let turns = interleave(&mic, &system, Some((0, 8_000)));
+        let summary: Vec<(&str, &str, &str)> = turns
+            .iter()
+            .map(|turn| (turn.role, turn.speaker.as_str(), turn.text.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("me", "Me", "Hi there"),
+                ("them", "Alex", "Hello!"),
+                ("me", "Me", "Sounds good"),
+                ("them", "Them", "Bye"),
+                ("file", "File", "From a recording"),
+            ]
+        );
+        assert_eq!(interleave(&mic, &system, None).len(), 6);
+    }
+}
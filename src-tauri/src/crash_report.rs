@@ -0,0 +1,211 @@
+//! Crash reports: the panic hook writes a report file next to the logs, and
+//! the next start announces it to the frontend via `app:crash-report-available`.
+//!
+//! Panics in background threads used to disappear into a single log line (or
+//! nothing, when the process died before the non-blocking writer flushed).
+//! Reports are written synchronously from the hook and include the tail of
+//! the main log so the lead-up is visible without digging through log files.
+
+use serde::Serialize;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tracing::{error, info, warn};
+
+const CRASH_DIR: &str = "crashes";
+const CRASH_FILE_PREFIX: &str = "crash-";
+/// Marks a report that has not been shown to the user yet; holds its file name.
+const PENDING_MARKER: &str = "pending";
+const MAX_CRASH_REPORTS: usize = 10;
+const LOG_TAIL_LINES: usize = 200;
+/// Only the end of the log is read; 200 lines comfortably fit in this.
+const LOG_TAIL_BYTES: u64 = 64 * 1024;
+/// Give the main window time to register its listeners before announcing.
+const ANNOUNCE_DELAY: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct CrashReport {
+    pub(crate) path: String,
+    pub(crate) created_ms: u64,
+    pub(crate) contents: String,
+}
+
+fn crash_dir() -> PathBuf {
+    crate::logging::resolve_log_dir().join(CRASH_DIR)
+}
+
+/// Last `max_lines` lines of `path`, reading at most `max_bytes` from the end.
+fn tail_lines(path: &Path, max_bytes: u64, max_lines: usize) -> Option<String> {
+    let mut file = std::fs::File::open(path).ok()?;
+    let len = file.metadata().ok()?.len();
+    let start = len.saturating_sub(max_bytes);
+    file.seek(SeekFrom::Start(start)).ok()?;
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).ok()?;
+    let text = String::from_utf8_lossy(&buf);
+    let mut lines: Vec<&str> = text.lines().collect();
+    // A mid-file start lands inside a line; drop the fragment.
+    if start > 0 && !lines.is_empty() {
+        lines.remove(0);
+    }
+    let skip = lines.len().saturating_sub(max_lines);
+    Some(lines[skip..].join("\n"))
+}
+
+fn format_report(
+    thread: &str,
+    location: &str,
+    payload: &str,
+    backtrace: &str,
+    log_tail: Option<&str>,
+) -> String {
+    format!(
+        "Trispr Flow {} crash report\nTime: {}\nThread: {}\nLocation: {}\nMessage: {}\n\nBacktrace:\n{}\n\nRecent log lines:\n{}\n",
+        env!("CARGO_PKG_VERSION"),
+        chrono::Local::now().to_rfc3339(),
+        thread,
+        location,
+        payload,
+        backtrace,
+        log_tail.unwrap_or("(log unavailable)")
+    )
+}
+
+fn crash_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(CRASH_FILE_PREFIX))
+        })
+        .collect();
+    // Names embed the timestamp, so lexical order is chronological.
+    files.sort();
+    files
+}
+
+/// Writes a report and marks it pending. Best effort: the hook must not panic.
+fn write_report(contents: &str) -> Option<PathBuf> {
+    let dir = crash_dir();
+    std::fs::create_dir_all(&dir).ok()?;
+    let file_name = format!("{}{:013}.txt", CRASH_FILE_PREFIX, crate::util::now_ms());
+    let path = dir.join(&file_name);
+    std::fs::write(&path, contents).ok()?;
+    let _ = std::fs::write(dir.join(PENDING_MARKER), &file_name);
+
+    let files = crash_files(&dir);
+    for old in files
+        .iter()
+        .take(files.len().saturating_sub(MAX_CRASH_REPORTS))
+    {
+        let _ = std::fs::remove_file(old);
+    }
+    Some(path)
+}
+
+/// Global panic hook: log every panic (including from spawned threads) and
+/// write a crash report, then defer to the default hook.
+pub(crate) fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let location = info
+            .location()
+            .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()))
+            .unwrap_or_else(|| "unknown".to_string());
+        let payload = info
+            .payload()
+            .downcast_ref::<String>()
+            .cloned()
+            .or_else(|| info.payload().downcast_ref::<&str>().map(|s| s.to_string()))
+            .unwrap_or_else(|| "non-string panic".to_string());
+        let backtrace = std::backtrace::Backtrace::force_capture().to_string();
+        error!(
+            "PANIC at {}: {}\nBacktrace:\n{}",
+            location, payload, backtrace
+        );
+
+        let thread = std::thread::current()
+            .name()
+            .unwrap_or("<unnamed>")
+            .to_string();
+        let log_tail = crate::logging::current_log_file()
+            .and_then(|path| tail_lines(&path, LOG_TAIL_BYTES, LOG_TAIL_LINES));
+        let report = format_report(
+            &thread,
+            &location,
+            &payload,
+            &backtrace,
+            log_tail.as_deref(),
+        );
+        if let Some(path) = write_report(&report) {
+            error!("Crash report written to {}", path.display());
+        }
+        default_hook(info);
+    }));
+}
+
+fn read_report(path: &Path) -> Option<CrashReport> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    let created_ms = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .and_then(|stem| stem.strip_prefix(CRASH_FILE_PREFIX))
+        .and_then(|ms| ms.parse().ok())
+        .unwrap_or(0);
+    Some(CrashReport {
+        path: path.to_string_lossy().to_string(),
+        created_ms,
+        contents,
+    })
+}
+
+/// Announces a report left by the previous run, once.
+pub(crate) fn announce_pending_crash_report(app: &AppHandle) {
+    let dir = crash_dir();
+    let marker = dir.join(PENDING_MARKER);
+    let Ok(file_name) = std::fs::read_to_string(&marker) else {
+        return;
+    };
+    let _ = std::fs::remove_file(&marker);
+    let Some(report) = read_report(&dir.join(file_name.trim())) else {
+        warn!("Pending crash report '{}' is missing", file_name.trim());
+        return;
+    };
+    info!("Previous run crashed; report at {}", report.path);
+
+    let app = app.clone();
+    crate::util::spawn_guarded("crash_report_announce", move || {
+        std::thread::sleep(ANNOUNCE_DELAY);
+        let _ = app.emit("app:crash-report-available", &report);
+    });
+}
+
+#[tauri::command]
+pub(crate) fn get_last_crash_report() -> Option<CrashReport> {
+    crash_files(&crash_dir())
+        .last()
+        .and_then(|path| read_report(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tail_lines_drops_partial_first_line() {
+        let path =
+            std::env::temp_dir().join(format!("trispr-crash-tail-{}.txt", std::process::id()));
+        std::fs::write(&path, "first line\nsecond\nthird\nfourth\n").unwrap();
+
+        assert_eq!(tail_lines(&path, 1024, 2).as_deref(), Some("third\nfourth"));
+        // 16 bytes from the end start inside "second"; the fragment is dropped.
+        assert_eq!(tail_lines(&path, 16, 10).as_deref(), Some("third\nfourth"));
+        let _ = std::fs::remove_file(&path);
+    }
+}
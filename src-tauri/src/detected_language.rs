@@ -0,0 +1,262 @@
+//! Language whisper detected for a transcript, and the guard that flags or
+//! rejects results in languages the user does not speak.
+//!
+//! Whisper hallucinating on noise often shows up as a confident transcript
+//! in a random language ("Untertitel der Amara.org-Community" in an English
+//! meeting). The transcription path records the detected language per
+//! thread; result handlers take it, store it on the history entry and check
+//! it against `expected_languages`.
+
+use std::cell::RefCell;
+
+use crate::state::Settings;
+
+pub(crate) const UNEXPECTED_LANGUAGE_TAG: &str = "unexpected-language";
+
+/// whisper.cpp's language table; the server reports full names.
+const WHISPER_LANGUAGES: &[(&str, &str)] = &[
+    ("en", "english"),
+    ("zh", "chinese"),
+    ("de", "german"),
+    ("es", "spanish"),
+    ("ru", "russian"),
+    ("ko", "korean"),
+    ("fr", "french"),
+    ("ja", "japanese"),
+    ("pt", "portuguese"),
+    ("tr", "turkish"),
+    ("pl", "polish"),
+    ("ca", "catalan"),
+    ("nl", "dutch"),
+    ("ar", "arabic"),
+    ("sv", "swedish"),
+    ("it", "italian"),
+    ("id", "indonesian"),
+    ("hi", "hindi"),
+    ("fi", "finnish"),
+    ("vi", "vietnamese"),
+    ("he", "hebrew"),
+    ("uk", "ukrainian"),
+    ("el", "greek"),
+    ("ms", "malay"),
+    ("cs", "czech"),
+    ("ro", "romanian"),
+    ("da", "danish"),
+    ("hu", "hungarian"),
+    ("ta", "tamil"),
+    ("no", "norwegian"),
+    ("th", "thai"),
+    ("ur", "urdu"),
+    ("hr", "croatian"),
+    ("bg", "bulgarian"),
+    ("lt", "lithuanian"),
+    ("la", "latin"),
+    ("mi", "maori"),
+    ("ml", "malayalam"),
+    ("cy", "welsh"),
+    ("sk", "slovak"),
+    ("te", "telugu"),
+    ("fa", "persian"),
+    ("lv", "latvian"),
+    ("bn", "bengali"),
+    ("sr", "serbian"),
+    ("az", "azerbaijani"),
+    ("sl", "slovenian"),
+    ("kn", "kannada"),
+    ("et", "estonian"),
+    ("mk", "macedonian"),
+    ("br", "breton"),
+    ("eu", "basque"),
+    ("is", "icelandic"),
+    ("hy", "armenian"),
+    ("ne", "nepali"),
+    ("mn", "mongolian"),
+    ("bs", "bosnian"),
+    ("kk", "kazakh"),
+    ("sq", "albanian"),
+    ("sw", "swahili"),
+    ("gl", "galician"),
+    ("mr", "marathi"),
+    ("pa", "punjabi"),
+    ("si", "sinhala"),
+    ("km", "khmer"),
+    ("sn", "shona"),
+    ("yo", "yoruba"),
+    ("so", "somali"),
+    ("af", "afrikaans"),
+    ("oc", "occitan"),
+    ("ka", "georgian"),
+    ("be", "belarusian"),
+    ("tg", "tajik"),
+    ("sd", "sindhi"),
+    ("gu", "gujarati"),
+    ("am", "amharic"),
+    ("yi", "yiddish"),
+    ("lo", "lao"),
+    ("uz", "uzbek"),
+    ("fo", "faroese"),
+    ("ht", "haitian creole"),
+    ("ps", "pashto"),
+    ("tk", "turkmen"),
+    ("nn", "nynorsk"),
+    ("mt", "maltese"),
+    ("sa", "sanskrit"),
+    ("lb", "luxembourgish"),
+    ("my", "myanmar"),
+    ("bo", "tibetan"),
+    ("tl", "tagalog"),
+    ("mg", "malagasy"),
+    ("as", "assamese"),
+    ("tt", "tatar"),
+    ("haw", "hawaiian"),
+    ("ln", "lingala"),
+    ("ha", "hausa"),
+    ("ba", "bashkir"),
+    ("jw", "javanese"),
+    ("su", "sundanese"),
+    ("yue", "cantonese"),
+];
+
+thread_local! {
+    /// Language of the last transcription run on this thread.
+    static LAST_DETECTED_LANGUAGE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Whisper language code for a code or full name ("English", "de-DE");
+/// `None` for "auto", empty or unknown values.
+pub(crate) fn normalize_language_code(value: &str) -> Option<String> {
+    let value = value.trim().to_lowercase();
+    let primary = value.split(['-', '_']).next().unwrap_or_default();
+    WHISPER_LANGUAGES
+        .iter()
+        .find(|(code, name)| *code == primary || *name == value)
+        .map(|(code, _)| code.to_string())
+}
+
+/// Language from whisper-cli stderr ("auto-detected language: en (p = 0.97)").
+pub(crate) fn parse_cli_detected_language(stderr: &str) -> Option<String> {
+    stderr.lines().find_map(|line| {
+        let (_, rest) = line.split_once("auto-detected language:")?;
+        normalize_language_code(rest.split_whitespace().next()?)
+    })
+}
+
+pub(crate) fn record_detected_language(language: Option<String>) {
+    LAST_DETECTED_LANGUAGE.with(|last| *last.borrow_mut() = language);
+}
+
+/// Language of the last transcription on this thread; consumed by the read.
+pub(crate) fn take_detected_language() -> Option<String> {
+    LAST_DETECTED_LANGUAGE.with(|last| last.borrow_mut().take())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum UnexpectedLanguageAction {
+    Off,
+    /// Keep the result and tag the entry.
+    Flag,
+    /// Drop the result like a filtered transcript.
+    Reject,
+}
+
+impl UnexpectedLanguageAction {
+    pub(crate) fn from_setting(value: &str) -> Self {
+        match value.trim() {
+            "flag" => Self::Flag,
+            "reject" => Self::Reject,
+            _ => Self::Off,
+        }
+    }
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Flag => "flag",
+            Self::Reject => "reject",
+        }
+    }
+}
+
+fn expected_languages(settings: &Settings) -> Vec<String> {
+    if !settings.expected_languages.is_empty() {
+        return settings.expected_languages.clone();
+    }
+    if settings.language_pinned {
+        return normalize_language_code(&settings.language_mode)
+            .into_iter()
+            .collect();
+    }
+    Vec::new()
+}
+
+/// Action to take for a result in `language`. Results without a detected
+/// language, translated results and an empty expectation always pass.
+pub(crate) fn check_language(
+    language: Option<&str>,
+    settings: &Settings,
+) -> UnexpectedLanguageAction {
+    let action = UnexpectedLanguageAction::from_setting(&settings.unexpected_language_action);
+    let Some(language) = language else {
+        return UnexpectedLanguageAction::Off;
+    };
+    let expected = expected_languages(settings);
+    if action == UnexpectedLanguageAction::Off
+        || settings.translate_to_english
+        || expected.is_empty()
+        || expected.iter().any(|code| code == language)
+    {
+        return UnexpectedLanguageAction::Off;
+    }
+    action
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_languages_and_guards_unexpected_ones() {
+        assert_eq!(normalize_language_code("English").as_deref(), Some("en"));
+        assert_eq!(normalize_language_code("de-DE").as_deref(), Some("de"));
+        assert_eq!(normalize_language_code("auto"), None);
+        assert_eq!(
+            parse_cli_detected_language(
+                "whisper_init: loaded\nwhisper_full_with_state: auto-detected language: ja (p = 0.41)\n"
+            )
+            .as_deref(),
+            Some("ja")
+        );
+        assert_eq!(parse_cli_detected_language("no detection here"), None);
+
+        let mut settings = Settings {
+            unexpected_language_action: "reject".to_string(),
+            expected_languages: vec!["en".to_string(), "de".to_string()],
+            ..Settings::default()
+        };
+        assert_eq!(
+            check_language(Some("de"), &settings),
+            UnexpectedLanguageAction::Off
+        );
+        assert_eq!(
+            check_language(Some("cy"), &settings),
+            UnexpectedLanguageAction::Reject
+        );
+        assert_eq!(
+            check_language(None, &settings),
+            UnexpectedLanguageAction::Off
+        );
+
+        settings.expected_languages.clear();
+        assert_eq!(
+            check_language(Some("cy"), &settings),
+            UnexpectedLanguageAction::Off
+        );
+        settings.language_pinned = true;
+        settings.language_mode = "en".to_string();
+        settings.unexpected_language_action = "flag".to_string();
+        assert_eq!(
+            check_language(Some("cy"), &settings),
+            UnexpectedLanguageAction::Flag
+        );
+    }
+}
@@ -0,0 +1,197 @@
+// Device Monitor — hot-unplug detection and automatic stream recovery
+//
+// cpal streams do not come back on their own when a USB mic is unplugged or a
+// Bluetooth headset goes to sleep; they just stop delivering callbacks. This
+// module polls the configured input/output devices (plus the stream error
+// flag set on `DeviceNotAvailable`) and, on loss, emits `audio:device-lost`
+// and reopens the capture on the system default. When the configured device
+// shows up again the streams are reopened on it and `audio:device-restored`
+// is emitted.
+
+use crate::state::AppState;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{info, warn};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DeviceAction {
+    None,
+    Lost,
+    Restored,
+}
+
+/// Tracks one direction (input or output). `lost_device` holds the id of the
+/// configured device while the stream is running on the fallback.
+#[derive(Default)]
+struct DeviceWatch {
+    lost_device: Option<String>,
+}
+
+impl DeviceWatch {
+    fn next_action(
+        &mut self,
+        device_id: &str,
+        running: bool,
+        present: bool,
+        stream_failed: bool,
+    ) -> DeviceAction {
+        match self.lost_device.as_deref() {
+            // The user picked another device meanwhile; nothing to restore.
+            Some(lost) if lost != device_id => {
+                self.lost_device = None;
+                DeviceAction::None
+            }
+            Some(_) if present => {
+                self.lost_device = None;
+                if running {
+                    DeviceAction::Restored
+                } else {
+                    DeviceAction::None
+                }
+            }
+            Some(_) => DeviceAction::None,
+            None if running && (!present || stream_failed) => {
+                if !present {
+                    self.lost_device = Some(device_id.to_string());
+                }
+                DeviceAction::Lost
+            }
+            None => DeviceAction::None,
+        }
+    }
+}
+
+fn emit_device_event(app: &AppHandle, action: DeviceAction, direction: &str, device_id: &str) {
+    let event = match action {
+        DeviceAction::Lost => "audio:device-lost",
+        DeviceAction::Restored => "audio:device-restored",
+        DeviceAction::None => return,
+    };
+    let payload = serde_json::json!({
+        "direction": direction,
+        "device_id": device_id,
+        "fallback": if action == DeviceAction::Lost { Some("default") } else { None },
+    });
+    let _ = app.emit(event, payload);
+}
+
+fn check_devices(app: &AppHandle, input: &mut DeviceWatch, output: &mut DeviceWatch) {
+    let state = app.state::<AppState>();
+    let settings = state
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+
+    let input_action = input.next_action(
+        &settings.input_device,
+        crate::audio::input_capture_running(state.inner()),
+        crate::audio::input_device_present(&settings.input_device),
+        crate::audio::take_input_stream_lost(state.inner()),
+    );
+    if input_action != DeviceAction::None {
+        warn!(
+            "Input device '{}' {:?}; reopening capture",
+            settings.input_device, input_action
+        );
+        emit_device_event(app, input_action, "input", &settings.input_device);
+        crate::audio::restart_input_capture(app, &state, &settings);
+    }
+
+    let output_action = output.next_action(
+        &settings.transcribe_output_device,
+        settings.transcribe_enabled
+            && state
+                .transcribe_active
+                .load(std::sync::atomic::Ordering::Relaxed),
+        crate::transcription::output_device_present(&settings.transcribe_output_device),
+        false,
+    );
+    if output_action != DeviceAction::None {
+        warn!(
+            "Output device '{}' {:?}; reopening system audio capture",
+            settings.transcribe_output_device, output_action
+        );
+        emit_device_event(
+            app,
+            output_action,
+            "output",
+            &settings.transcribe_output_device,
+        );
+        crate::transcription::stop_transcribe_monitor(app, state.inner());
+        if let Err(err) =
+            crate::transcription::start_transcribe_monitor(app, state.inner(), &settings)
+        {
+            warn!("Failed to restart system audio capture: {}", err);
+        }
+    }
+}
+
+/// Spawn the polling thread. Call once from app setup.
+pub(crate) fn start(app: AppHandle) {
+    info!("Audio device monitor started");
+    crate::util::spawn_guarded("audio_device_monitor", move || {
+        let mut input = DeviceWatch::default();
+        let mut output = DeviceWatch::default();
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+            check_devices(&app, &mut input, &mut output);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DeviceAction, DeviceWatch};
+
+    #[test]
+    fn unplug_then_replug_emits_lost_then_restored() {
+        let mut watch = DeviceWatch::default();
+        let id = "input-1-USB Mic";
+        assert_eq!(watch.next_action(id, true, true, false), DeviceAction::None);
+        assert_eq!(
+            watch.next_action(id, true, false, false),
+            DeviceAction::Lost
+        );
+        // Running on the default now; stays quiet until the device returns.
+        assert_eq!(
+            watch.next_action(id, true, false, false),
+            DeviceAction::None
+        );
+        assert_eq!(
+            watch.next_action(id, true, true, false),
+            DeviceAction::Restored
+        );
+        assert_eq!(watch.next_action(id, true, true, false), DeviceAction::None);
+    }
+
+    #[test]
+    fn stream_error_on_present_device_restarts_without_tracking() {
+        let mut watch = DeviceWatch::default();
+        assert_eq!(
+            watch.next_action("default", true, true, true),
+            DeviceAction::Lost
+        );
+        assert!(watch.lost_device.is_none());
+        assert_eq!(
+            watch.next_action("default", true, true, false),
+            DeviceAction::None
+        );
+    }
+
+    #[test]
+    fn switching_devices_clears_pending_restore() {
+        let mut watch = DeviceWatch::default();
+        assert_eq!(
+            watch.next_action("input-0-Headset", true, false, false),
+            DeviceAction::Lost
+        );
+        assert_eq!(
+            watch.next_action("input-2-Desk Mic", true, true, false),
+            DeviceAction::None
+        );
+        assert!(watch.lost_device.is_none());
+    }
+}
@@ -0,0 +1,221 @@
+//! Dictation macros: spoken phrases that run an app action instead of being
+//! pasted ("open settings", "stop transcribing", "switch to German").
+//!
+//! A macro only fires when the whole mic utterance is its phrase, ignoring
+//! case and punctuation, so dictating a sentence that merely mentions
+//! "open settings" still pastes it. Runs before the spoken editing commands;
+//! system-audio transcripts never trigger macros.
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::info;
+
+use crate::hallucination_filter::normalize_phrase;
+use crate::state::{AppState, Settings};
+
+pub(crate) const MACRO_EVENT: &str = "macro:executed";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum MacroAction {
+    OpenSettings,
+    StartTranscribing,
+    StopTranscribing,
+    ToggleTranscribing,
+    TogglePrivacyMute,
+    /// Pin `argument` (a language code) as the dictation language.
+    SwitchLanguage,
+    /// Activate the settings profile named `argument`.
+    ActivateProfile,
+    /// A missing action or one this build does not know (renamed, or from a
+    /// newer version); such macros are dropped by `normalize_macros` instead
+    /// of failing the whole settings file.
+    #[default]
+    #[serde(other)]
+    Unknown,
+}
+
+/// Mirrors `src/types.ts::DictationMacro`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct DictationMacro {
+    #[serde(default)]
+    pub(crate) phrase: String,
+    #[serde(default)]
+    pub(crate) action: MacroAction,
+    /// Language code or profile name, for actions that take one.
+    #[serde(default)]
+    pub(crate) argument: String,
+}
+
+impl DictationMacro {
+    fn new(phrase: &str, action: MacroAction, argument: &str) -> Self {
+        Self {
+            phrase: phrase.to_string(),
+            action,
+            argument: argument.to_string(),
+        }
+    }
+}
+
+pub(crate) fn default_dictation_macros() -> Vec<DictationMacro> {
+    vec![
+        DictationMacro::new("open settings", MacroAction::OpenSettings, ""),
+        DictationMacro::new("start transcribing", MacroAction::StartTranscribing, ""),
+        DictationMacro::new("stop transcribing", MacroAction::StopTranscribing, ""),
+        DictationMacro::new("switch to english", MacroAction::SwitchLanguage, "en"),
+        DictationMacro::new("switch to german", MacroAction::SwitchLanguage, "de"),
+    ]
+}
+
+/// Trims phrases and arguments; drops macros without a phrase.
+pub(crate) fn normalize_macros(macros: &mut Vec<DictationMacro>) {
+    for entry in macros.iter_mut() {
+        entry.phrase = entry.phrase.trim().to_string();
+        entry.argument = entry.argument.trim().to_string();
+    }
+    macros.retain(|entry| {
+        entry.action != MacroAction::Unknown && !normalize_phrase(&entry.phrase).is_empty()
+    });
+}
+
+/// The macro whose phrase is the whole utterance, if any.
+fn find_macro<'a>(text: &str, macros: &'a [DictationMacro]) -> Option<&'a DictationMacro> {
+    let spoken = normalize_phrase(text);
+    if spoken.is_empty() {
+        return None;
+    }
+    macros
+        .iter()
+        .find(|entry| normalize_phrase(&entry.phrase) == spoken)
+}
+
+/// Macro for a mic transcript when macros are enabled.
+pub(crate) fn match_for_settings(text: &str, settings: &Settings) -> Option<DictationMacro> {
+    if !settings.dictation_macros_enabled {
+        return None;
+    }
+    find_macro(text, &settings.dictation_macros).cloned()
+}
+
+fn save_language(app: &AppHandle, code: &str) -> Result<(), String> {
+    let code = crate::detected_language::normalize_language_code(code)
+        .ok_or_else(|| format!("Unknown language '{}'", code.trim()))?;
+    let mut settings = app
+        .state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    settings.language_mode = code;
+    settings.language_pinned = true;
+    crate::save_settings_inner(app, &mut settings)
+}
+
+fn run(app: &AppHandle, entry: &DictationMacro) -> Result<(), String> {
+    match entry.action {
+        MacroAction::OpenSettings => {
+            crate::show_main_window(app);
+            let _ = app.emit(
+                "assistant:open-module",
+                serde_json::json!({ "target": "settings", "reason": "dictation_macro" }),
+            );
+        }
+        MacroAction::StartTranscribing => {
+            crate::set_transcribe_enabled(app, true)?;
+        }
+        MacroAction::StopTranscribing => {
+            crate::set_transcribe_enabled(app, false)?;
+        }
+        MacroAction::ToggleTranscribing => crate::toggle_transcribe_from_hotkey(app),
+        MacroAction::TogglePrivacyMute => {
+            crate::privacy_mute::toggle(app);
+        }
+        MacroAction::SwitchLanguage => save_language(app, &entry.argument)?,
+        MacroAction::ActivateProfile => {
+            let name = entry.argument.trim();
+            if name.is_empty() {
+                return Err("Profile macro has no profile name".to_string());
+            }
+            crate::settings_profiles::activate_profile_async(app.clone(), Some(name.to_string()));
+        }
+        MacroAction::Unknown => return Err("Unknown macro action".to_string()),
+    }
+    Ok(())
+}
+
+/// Runs `entry` off the transcription thread and reports it on
+/// `macro:executed`; failures surface as app errors.
+pub(crate) fn execute(app: &AppHandle, entry: DictationMacro) {
+    let app = app.clone();
+    crate::util::spawn_guarded("dictation_macro", move || {
+        info!("Dictation macro '{}': {:?}", entry.phrase, entry.action);
+        let result = run(&app, &entry);
+        let _ = app.emit(
+            MACRO_EVENT,
+            serde_json::json!({
+                "phrase": entry.phrase,
+                "action": entry.action,
+                "argument": entry.argument,
+                "error": result.as_ref().err(),
+            }),
+        );
+        if let Err(err) = result {
+            crate::emit_error(
+                &app,
+                crate::errors::AppError::Other(err),
+                Some("Dictation macro"),
+            );
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn macros_fire_only_on_the_whole_utterance() {
+        let macros = default_dictation_macros();
+        assert_eq!(
+            find_macro("Switch to German.", &macros).map(|entry| entry.argument.as_str()),
+            Some("de")
+        );
+        assert_eq!(
+            find_macro(" Open settings! ", &macros).map(|entry| entry.action),
+            Some(MacroAction::OpenSettings)
+        );
+        assert!(find_macro("Please open settings for the router", &macros).is_none());
+        assert!(find_macro("...", &macros).is_none());
+
+        let mut padded = vec![DictationMacro::new(" ?! ", MacroAction::OpenSettings, "")];
+        padded.extend(macros);
+        normalize_macros(&mut padded);
+        assert_eq!(padded, default_dictation_macros());
+        let settings = Settings {
+            dictation_macros: padded,
+            ..Settings::default()
+        };
+        assert!(match_for_settings("stop transcribing", &settings).is_none());
+    }
+
+    #[test]
+    fn unknown_actions_are_dropped_instead_of_failing_settings() {
+        let raw = r#"[
+            {"phrase": "open settings", "action": "open_settings"},
+            {"phrase": "summon dragons", "action": "summon_dragons", "argument": "3"},
+            {"action": "stop_transcribing"},
+            {"phrase": "stop transcribing"}
+        ]"#;
+        let mut macros: Vec<DictationMacro> = serde_json::from_str(raw).unwrap();
+        assert_eq!(macros[1].action, MacroAction::Unknown);
+        normalize_macros(&mut macros);
+        assert_eq!(
+            macros,
+            vec![DictationMacro::new(
+                "open settings",
+                MacroAction::OpenSettings,
+                ""
+            )]
+        );
+    }
+}
@@ -0,0 +1,148 @@
+//! Far-end reference for the mic echo canceller.
+//!
+//! With `mic_echo_cancellation` on, a loopback stream on the default output
+//! device records what the speakers play, start/stop cues included (the
+//! webview plays them there), and pushes it into a short shared FIFO. The
+//! system-audio transcription device is not used: it may be a different
+//! endpoint than the one the cues leak from.
+//! The mic DSP chain pulls 10 ms of it per processed frame and the WebRTC
+//! echo canceller removes whatever of it leaks back into the microphone.
+//! The FIFO is trimmed to a few milliseconds of lead over the mic; the
+//! canceller's delay estimation covers the rest (acoustic path and the
+//! buffering difference between the two streams).
+
+use std::collections::VecDeque;
+use std::sync::mpsc::Sender;
+use std::sync::Mutex;
+
+use tracing::info;
+
+use crate::state::Settings;
+
+/// Reference audio kept ahead of the mic; older samples are dropped.
+const REFERENCE_LEAD_MS: u64 = 8;
+/// Hard cap while no mic stream is pulling.
+const REFERENCE_MAX_MS: u64 = 250;
+
+struct FarEnd {
+    samples: VecDeque<f32>,
+    sample_rate: u32,
+}
+
+static FAR_END: Mutex<FarEnd> = Mutex::new(FarEnd {
+    samples: VecDeque::new(),
+    sample_rate: 0,
+});
+static REFERENCE_CAPTURE: Mutex<Option<(String, Sender<()>)>> = Mutex::new(None);
+
+/// Appends loopback audio (mono, -1..1) at `sample_rate`.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub(crate) fn push(samples: &[f32], sample_rate: u32) {
+    let mut far_end = FAR_END
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if far_end.sample_rate != sample_rate {
+        far_end.samples.clear();
+        far_end.sample_rate = sample_rate;
+    }
+    far_end.samples.extend(samples);
+    let max = (sample_rate as u64 * REFERENCE_MAX_MS / 1000) as usize;
+    let excess = far_end.samples.len().saturating_sub(max);
+    far_end.samples.drain(..excess);
+}
+
+/// Takes `len` reference samples at the mic's `sample_rate`; silence where
+/// the loopback has not delivered anything.
+pub(crate) fn take(len: usize, sample_rate: u32) -> Vec<f32> {
+    let mut far_end = FAR_END
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let far_rate = far_end.sample_rate;
+    if far_rate == 0 || sample_rate == 0 || far_end.samples.is_empty() {
+        return vec![0.0; len];
+    }
+    let needed = (len as u64 * far_rate as u64).div_ceil(sample_rate as u64) as usize;
+    let lead = (far_rate as u64 * REFERENCE_LEAD_MS / 1000) as usize;
+    let stale = far_end.samples.len().saturating_sub(needed + lead);
+    far_end.samples.drain(..stale);
+
+    let available = needed.min(far_end.samples.len());
+    let chunk: Vec<f32> = far_end.samples.drain(..available).collect();
+    (0..len)
+        .map(|i| {
+            let index = (i as u64 * far_rate as u64 / sample_rate as u64) as usize;
+            chunk.get(index).copied().unwrap_or(0.0)
+        })
+        .collect()
+}
+
+fn clear() {
+    FAR_END
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .samples
+        .clear();
+}
+
+/// Starts, restarts or stops the reference loopback to match `settings`.
+/// Runs from setup, every settings save and privacy mute changes.
+pub(crate) fn sync_echo_reference(settings: &Settings) {
+    let wanted = (settings.mic_echo_cancellation && !crate::privacy_mute::is_muted())
+        .then(|| "default".to_string());
+    let mut running = REFERENCE_CAPTURE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if running.as_ref().map(|(device, _)| device) == wanted.as_ref() {
+        return;
+    }
+    if let Some((device, stop_tx)) = running.take() {
+        let _ = stop_tx.send(());
+        info!("Echo reference capture on '{}' stopped", device);
+    }
+    clear();
+    if let Some(device) = wanted {
+        *running = start_reference_capture(device);
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn start_reference_capture(device: String) -> Option<(String, Sender<()>)> {
+    let (stop_tx, stop_rx) = std::sync::mpsc::channel::<()>();
+    let device_id = device.clone();
+    crate::util::spawn_guarded("echo_reference", move || {
+        if let Err(err) = crate::transcription::capture_loopback(&device_id, stop_rx, push) {
+            tracing::warn!("Echo reference capture on '{}' failed: {}", device_id, err);
+        }
+        clear();
+    });
+    info!("Echo reference capture on '{}' started", device);
+    Some((device, stop_tx))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn start_reference_capture(device: String) -> Option<(String, Sender<()>)> {
+    tracing::warn!(
+        "Echo cancellation needs WASAPI loopback; no reference for '{}' on this platform",
+        device
+    );
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn take_resamples_and_keeps_only_a_short_lead() {
+        clear();
+        push(&[0.5; 4_800], 48_000);
+        // 100 ms queued, but only 10 ms (+ lead) is wanted at 16 kHz.
+        let block = take(160, 16_000);
+        assert_eq!(block, vec![0.5; 160]);
+        let remaining = FAR_END.lock().unwrap().samples.len();
+        assert_eq!(remaining, 48_000 * REFERENCE_LEAD_MS as usize / 1000);
+
+        clear();
+        assert_eq!(take(4, 16_000), vec![0.0; 4]);
+    }
+}
@@ -0,0 +1,177 @@
+//! Detects insertion targets Windows will not let us type into.
+//!
+//! User Interface Privilege Isolation drops synthetic input and UI
+//! Automation writes aimed at a process with a higher integrity level, e.g.
+//! an app running as administrator while Trispr Flow is not. The paste then
+//! fails silently. Before inserting we compare integrity levels; a blocked
+//! target gets the text on the clipboard instead and the frontend a
+//! `transcription:paste-blocked` event explaining why.
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tracing::warn;
+
+pub(crate) const PASTE_BLOCKED_EVENT: &str = "transcription:paste-blocked";
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct PasteBlocked {
+    pub(crate) process_name: Option<String>,
+    /// "high" | "system" — the target's integrity level.
+    pub(crate) integrity: &'static str,
+    pub(crate) message: String,
+}
+
+/// Name of a Windows mandatory integrity level RID.
+#[cfg(any(test, target_os = "windows"))]
+fn integrity_label(rid: u32) -> &'static str {
+    match rid {
+        0..=0x0FFF => "untrusted",
+        0x1000..=0x1FFF => "low",
+        0x2000..=0x2FFF => "medium",
+        0x3000..=0x3FFF => "high",
+        _ => "system",
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use std::ffi::c_void;
+
+    use windows_sys::Win32::Foundation::{CloseHandle, HANDLE};
+    use windows_sys::Win32::Security::{
+        GetSidSubAuthority, GetSidSubAuthorityCount, GetTokenInformation, TokenIntegrityLevel,
+        TOKEN_MANDATORY_LABEL, TOKEN_QUERY,
+    };
+    use windows_sys::Win32::System::Threading::{
+        GetCurrentProcess, OpenProcess, OpenProcessToken, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    /// Mandatory integrity RID of `process`'s token.
+    ///
+    /// # Safety
+    /// `process` must be a valid handle with query access.
+    unsafe fn process_integrity(process: HANDLE) -> Option<u32> {
+        let mut token: HANDLE = std::ptr::null_mut();
+        if OpenProcessToken(process, TOKEN_QUERY, &mut token) == 0 {
+            return None;
+        }
+        let mut len = 0u32;
+        GetTokenInformation(
+            token,
+            TokenIntegrityLevel,
+            std::ptr::null_mut(),
+            0,
+            &mut len,
+        );
+        // u64 storage keeps the label struct aligned.
+        let mut buffer = vec![0u64; (len as usize).div_ceil(8).max(1)];
+        let ok = GetTokenInformation(
+            token,
+            TokenIntegrityLevel,
+            buffer.as_mut_ptr() as *mut c_void,
+            len,
+            &mut len,
+        );
+        CloseHandle(token);
+        if ok == 0 {
+            return None;
+        }
+        let label = &*(buffer.as_ptr() as *const TOKEN_MANDATORY_LABEL);
+        let count = *GetSidSubAuthorityCount(label.Label.Sid);
+        if count == 0 {
+            return None;
+        }
+        Some(*GetSidSubAuthority(label.Label.Sid, u32::from(count) - 1))
+    }
+
+    pub(super) fn own_integrity() -> Option<u32> {
+        // SAFETY: the pseudo handle of the current process is always valid.
+        unsafe { process_integrity(GetCurrentProcess()) }
+    }
+
+    /// Process id and integrity RID of the foreground window's process.
+    pub(super) fn foreground_integrity() -> Option<(u32, u32)> {
+        use windows::Win32::UI::WindowsAndMessaging::{
+            GetForegroundWindow, GetWindowThreadProcessId,
+        };
+
+        // SAFETY: the process handle is checked before use and closed
+        // afterwards.
+        unsafe {
+            let hwnd = GetForegroundWindow();
+            if hwnd.0.is_null() {
+                return None;
+            }
+            let mut pid = 0u32;
+            let _tid = GetWindowThreadProcessId(hwnd, Some(&mut pid));
+            if pid == 0 {
+                return None;
+            }
+            let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+            if process.is_null() {
+                return None;
+            }
+            let integrity = process_integrity(process);
+            CloseHandle(process);
+            integrity.map(|rid| (pid, rid))
+        }
+    }
+}
+
+/// The foreground app when it runs at a higher integrity level than we do.
+/// Unknown levels count as not blocked, so a failed query never stops a
+/// paste.
+#[cfg(target_os = "windows")]
+pub(crate) fn blocked_target() -> Option<PasteBlocked> {
+    let own = platform::own_integrity()?;
+    let (pid, target) = platform::foreground_integrity()?;
+    if target <= own {
+        return None;
+    }
+    let process_name = crate::app_overrides::process_name(pid);
+    let app = process_name.as_deref().unwrap_or("The focused app");
+    Some(PasteBlocked {
+        message: format!(
+            "{} runs as administrator, so Windows blocks Trispr Flow from typing into it. \
+             The text is on the clipboard — press Ctrl+V, or start Trispr Flow as administrator.",
+            app
+        ),
+        process_name,
+        integrity: integrity_label(target),
+    })
+}
+
+#[cfg(not(target_os = "windows"))]
+pub(crate) fn blocked_target() -> Option<PasteBlocked> {
+    None
+}
+
+/// Copies `text` instead of inserting it when the target is elevated.
+/// Returns `Ok(true)` when the insertion was diverted to the clipboard.
+pub(crate) fn divert_if_blocked(app_handle: &AppHandle, text: &str) -> Result<bool, String> {
+    let Some(blocked) = blocked_target() else {
+        return Ok(false);
+    };
+    warn!(
+        "Paste target {:?} is elevated ({}); leaving text on the clipboard",
+        blocked.process_name, blocked.integrity
+    );
+    crate::set_clipboard_text_with_retry(text)?;
+    let _ = app_handle.emit(PASTE_BLOCKED_EVENT, &blocked);
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_integrity_levels() {
+        assert_eq!(integrity_label(0x0000), "untrusted");
+        assert_eq!(integrity_label(0x1000), "low");
+        assert_eq!(integrity_label(0x2000), "medium");
+        assert_eq!(integrity_label(0x2100), "medium");
+        assert_eq!(integrity_label(0x3000), "high");
+        assert_eq!(integrity_label(0x4000), "system");
+    }
+}
@@ -0,0 +1,290 @@
+//! Per-dictation audio kept for playback, keyed by history entry ID.
+//!
+//! Files live in `recordings/entries/<entry_id>.opus` (or `.wav` when the opus
+//! module is not installed) and are pruned by age and total size after every
+//! save, following `entry_audio_max_days` / `entry_audio_max_mb`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::warn;
+
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
+use crate::history_partition::{attach_entry_audio, history_containing};
+use crate::state::{AppState, HistoryEntry};
+
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+const ENTRY_AUDIO_EXTENSIONS: [&str; 2] = ["opus", "wav"];
+
+pub(crate) fn entry_audio_dir(app: &AppHandle) -> PathBuf {
+    crate::paths::resolve_base_dir(app)
+        .join("recordings")
+        .join("entries")
+}
+
+/// Entry IDs end up in file names; only accept the generated `h_123` shape.
+fn valid_entry_id(entry_id: &str) -> bool {
+    !entry_id.is_empty()
+        && entry_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn find_entry_audio(dir: &Path, entry_id: &str) -> Option<PathBuf> {
+    ENTRY_AUDIO_EXTENSIONS
+        .iter()
+        .map(|ext| dir.join(format!("{}.{}", entry_id, ext)))
+        .find(|path| path.exists())
+}
+
+fn save_entry_audio(app: &AppHandle, entry_id: &str, samples: &[i16]) -> Result<PathBuf, String> {
+    if !valid_entry_id(entry_id) {
+        return Err(format!("Invalid history entry id: {}", entry_id));
+    }
+    let dir = entry_audio_dir(app);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create entry audio dir: {}", e))?;
+
+    let wav_path = dir.join(format!("{}.wav", entry_id));
+    crate::session_manager::write_wav_i16(&wav_path, samples)?;
+
+    let (opus_enabled, bitrate_kbps) = {
+        let state = app.state::<AppState>();
+        let settings = state
+            .settings
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        (settings.opus_enabled, settings.opus_bitrate_kbps)
+    };
+    let sidecar = crate::opus::resolve_sidecar(app).filter(|_| opus_enabled);
+    let Some(sidecar) = sidecar else {
+        crate::history_crypto::seal_recording(&wav_path);
+        return Ok(wav_path);
+    };
+    let opus_path = dir.join(format!("{}.opus", entry_id));
+    let config = crate::opus::OpusEncoderConfig {
+        bitrate_kbps,
+        ..Default::default()
+    };
+    match crate::opus::encode_with_sidecar(&sidecar, &wav_path, &opus_path, &config) {
+        Ok(_) => {
+            let _ = fs::remove_file(&wav_path);
+            crate::history_crypto::seal_recording(&opus_path);
+            Ok(opus_path)
+        }
+        Err(err) => {
+            // The WAV is still playable; keep it rather than losing the audio.
+            warn!("Entry audio opus encode failed, keeping WAV: {}", err);
+            crate::history_crypto::seal_recording(&wav_path);
+            Ok(wav_path)
+        }
+    }
+}
+
+/// Saves a dictation's audio for `entry_id` in the background, links it to the
+/// mic history entry and applies the retention limits.
+pub(crate) fn keep_entry_audio(app: &AppHandle, entry_id: String, samples: Vec<i16>) {
+    let app = app.clone();
+    crate::util::spawn_guarded("entry_audio_save", move || {
+        let path = match save_entry_audio(&app, &entry_id, &samples) {
+            Ok(path) => path,
+            Err(err) => {
+                warn!("Failed to keep audio for {}: {}", entry_id, err);
+                return;
+            }
+        };
+        let state = app.state::<AppState>();
+        if let Some(updated) = attach_entry_audio(
+            &state.history,
+            &entry_id,
+            path.to_string_lossy().to_string(),
+        ) {
+            let _ = app.emit("history:updated", updated);
+        }
+        prune_entry_audio(&app);
+    });
+}
+
+/// Files to delete so that nothing is older than `max_days` and the total stays
+/// under `max_bytes`, removing the oldest first. Zero disables a limit.
+/// `files` holds `(path, modified_ms, size_bytes)`.
+fn select_expired(
+    mut files: Vec<(PathBuf, u64, u64)>,
+    max_days: u32,
+    max_bytes: u64,
+    now_ms: u64,
+) -> Vec<PathBuf> {
+    files.sort_by_key(|(_, modified_ms, _)| *modified_ms);
+    let mut total: u64 = files.iter().map(|(_, _, size)| size).sum();
+    let cutoff_ms = now_ms.saturating_sub(max_days as u64 * DAY_MS);
+    let mut expired = Vec::new();
+    for (path, modified_ms, size) in files {
+        let too_old = max_days > 0 && modified_ms < cutoff_ms;
+        let over_budget = max_bytes > 0 && total > max_bytes;
+        if !too_old && !over_budget {
+            break;
+        }
+        total = total.saturating_sub(size);
+        expired.push(path);
+    }
+    expired
+}
+
+fn prune_entry_audio(app: &AppHandle) {
+    let (max_days, max_mb) = {
+        let state = app.state::<AppState>();
+        let settings = state
+            .settings
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        (settings.entry_audio_max_days, settings.entry_audio_max_mb)
+    };
+    let Ok(entries) = fs::read_dir(entry_audio_dir(app)) else {
+        return;
+    };
+    let files = entries
+        .flatten()
+        .filter_map(|entry| {
+            let meta = entry.metadata().ok()?;
+            let modified_ms = meta
+                .modified()
+                .ok()?
+                .duration_since(std::time::UNIX_EPOCH)
+                .ok()?
+                .as_millis() as u64;
+            meta.is_file()
+                .then(|| (entry.path(), modified_ms, meta.len()))
+        })
+        .collect();
+    for path in select_expired(files, max_days, max_mb * 1024 * 1024, crate::util::now_ms()) {
+        if let Err(err) = fs::remove_file(&path) {
+            warn!("Failed to prune entry audio {}: {}", path.display(), err);
+        }
+    }
+}
+
+fn stored_entry_audio(app: &AppHandle, entry_id: &str) -> Option<PathBuf> {
+    if !valid_entry_id(entry_id) {
+        return None;
+    }
+    let state = app.state::<AppState>();
+    let linked = history_containing(&state, entry_id).and_then(|(history, _)| {
+        history
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .active
+            .iter()
+            .find(|entry| entry.id == entry_id)
+            .and_then(|entry| entry.audio_path.clone())
+    });
+    linked
+        .map(PathBuf::from)
+        .filter(|path| path.exists())
+        .or_else(|| find_entry_audio(&entry_audio_dir(app), entry_id))
+}
+
+/// Deletes the audio kept for an entry that was removed from history.
+pub(crate) fn discard_entry_audio(app: &AppHandle, entry: &HistoryEntry) {
+    let path = entry
+        .audio_path
+        .as_deref()
+        .map(PathBuf::from)
+        .filter(|path| path.exists())
+        .or_else(|| {
+            if valid_entry_id(&entry.id) {
+                find_entry_audio(&entry_audio_dir(app), &entry.id)
+            } else {
+                None
+            }
+        });
+    if let Some(path) = path {
+        if let Err(err) = fs::remove_file(&path) {
+            warn!(
+                "Failed to delete audio of removed entry {}: {}",
+                entry.id, err
+            );
+        }
+    }
+}
+
+/// Playable path of the audio kept for `entry_id`, if it still exists on disk.
+/// Encrypted recordings are decrypted to a temporary copy, replaced by the
+/// next call.
+#[tauri::command]
+pub(crate) fn get_entry_audio_path(
+    app: AppHandle,
+    entry_id: String,
+) -> CommandResult<Option<String>> {
+    stored_entry_audio(&app, &entry_id)
+        .map(|path| crate::history_crypto::plaintext_recording_for_player(&path))
+        .transpose()
+        .map(|path| path.map(|path| path.to_string_lossy().to_string()))
+        .with_code(ErrorCode::Storage)
+}
+
+/// Deletes the audio kept for `entry_id` and unlinks it from the entry.
+/// Returns false when there was nothing to delete.
+#[tauri::command]
+pub(crate) fn delete_entry_audio(app: AppHandle, entry_id: String) -> CommandResult<bool> {
+    let Some(path) = stored_entry_audio(&app, &entry_id) else {
+        return Ok(false);
+    };
+    fs::remove_file(&path).map_err(|e| {
+        CommandError::new(
+            ErrorCode::Storage,
+            format!("Failed to delete entry audio: {}", e),
+        )
+    })?;
+
+    let state = app.state::<AppState>();
+    if let Some((history, event)) = history_containing(&state, &entry_id) {
+        let mut ph = history
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        ph.update_active_entry(&entry_id, |entry| entry.audio_path = None);
+        ph.flush_to_disk().with_code(ErrorCode::Storage)?;
+        let updated: Vec<_> = ph.active.iter().cloned().collect();
+        drop(ph);
+        let _ = app.emit(event, updated);
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(name: &str, age_days: u64, size_mb: u64) -> (PathBuf, u64, u64) {
+        let now_ms = 100 * DAY_MS;
+        (
+            PathBuf::from(name),
+            now_ms - age_days * DAY_MS,
+            size_mb * 1024 * 1024,
+        )
+    }
+
+    #[test]
+    fn prunes_by_age_then_size_oldest_first() {
+        let files = vec![
+            file("new.opus", 1, 4),
+            file("old.opus", 40, 1),
+            file("mid.opus", 10, 4),
+        ];
+        let expired = select_expired(files.clone(), 30, 0, 100 * DAY_MS);
+        assert_eq!(expired, vec![PathBuf::from("old.opus")]);
+
+        let expired = select_expired(files.clone(), 0, 5 * 1024 * 1024, 100 * DAY_MS);
+        assert_eq!(
+            expired,
+            vec![PathBuf::from("old.opus"), PathBuf::from("mid.opus")]
+        );
+
+        assert!(select_expired(files, 0, 0, 100 * DAY_MS).is_empty());
+    }
+
+    #[test]
+    fn rejects_entry_ids_that_are_not_file_safe() {
+        assert!(valid_entry_id("h_1772101100000"));
+        assert!(!valid_entry_id("../settings"));
+        assert!(!valid_entry_id(""));
+    }
+}
@@ -87,16 +87,180 @@ impl AppError {
     /// Returns a suggested action for the user
     #[allow(dead_code)]
     pub fn suggested_action(&self) -> Option<&str> {
+        self.code().hint()
+    }
+
+    /// Stable code for this error; `Other` is classified from its message.
+    pub fn code(&self) -> ErrorCode {
         match self {
-            AppError::AudioDevice(_) => Some("Check your microphone connection and try again"),
-            AppError::Transcription(_) => {
+            AppError::AudioDevice(_) => ErrorCode::AudioDevice,
+            AppError::Transcription(msg) => match ErrorCode::classify(msg) {
+                code @ (ErrorCode::ModelNotFound
+                | ErrorCode::RuntimeMissing
+                | ErrorCode::Cancelled) => code,
+                _ => ErrorCode::Transcription,
+            },
+            AppError::Hotkey(_) => ErrorCode::Hotkey,
+            AppError::Storage(_) => ErrorCode::Storage,
+            AppError::Network(_) => ErrorCode::Network,
+            AppError::Window(_) => ErrorCode::Window,
+            AppError::Other(msg) => ErrorCode::classify(msg),
+        }
+    }
+}
+
+/// Stable, machine-readable error codes (`src/types.ts::ErrorCode`). The
+/// frontend keys handling and translations off these, so never rename one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    AudioDevice,
+    Transcription,
+    ModelNotFound,
+    RuntimeMissing,
+    Hotkey,
+    Storage,
+    Network,
+    Credentials,
+    InvalidInput,
+    NotFound,
+    Cancelled,
+    Window,
+    Internal,
+}
+
+impl ErrorCode {
+    /// Remediation shown next to the message, if there is a useful one.
+    pub fn hint(self) -> Option<&'static str> {
+        match self {
+            ErrorCode::AudioDevice => Some("Check your microphone connection and try again"),
+            ErrorCode::Transcription => {
                 Some("Try recording again or check your model installation")
             }
-            AppError::Hotkey(_) => Some("Choose a different hotkey combination"),
-            AppError::Storage(_) => Some("Check disk space and permissions"),
-            AppError::Network(_) => Some("Check your internet connection"),
-            AppError::Window(_) => Some("Try restarting the application"),
-            AppError::Other(_) => None,
+            ErrorCode::ModelNotFound => Some("Download or select a model in the Models settings"),
+            ErrorCode::RuntimeMissing => Some("Reinstall the whisper runtime from the setup page"),
+            ErrorCode::Hotkey => Some("Choose a different hotkey combination"),
+            ErrorCode::Storage => Some("Check disk space and permissions"),
+            ErrorCode::Network => Some("Check your internet connection"),
+            ErrorCode::Credentials => Some("Check the API key stored for this provider"),
+            ErrorCode::Window => Some("Try restarting the application"),
+            ErrorCode::InvalidInput
+            | ErrorCode::NotFound
+            | ErrorCode::Cancelled
+            | ErrorCode::Internal => None,
+        }
+    }
+
+    /// Best-effort code for a plain `String` error, from its wording.
+    pub fn classify(message: &str) -> Self {
+        let message = message.to_lowercase();
+        let has = |markers: &[&str]| markers.iter().any(|marker| message.contains(marker));
+        if has(&["cancelled"]) {
+            ErrorCode::Cancelled
+        } else if has(&["model file not found", "model not found"]) {
+            ErrorCode::ModelNotFound
+        } else if has(&["could not be located", "not installed"]) {
+            ErrorCode::RuntimeMissing
+        } else if has(&["api key", "credential", "http 401", "http 403"]) {
+            ErrorCode::Credentials
+        } else if has(&["connection failed", "timed out", "http 5", "network"]) {
+            ErrorCode::Network
+        } else if has(&["not found", "unknown "]) {
+            ErrorCode::NotFound
+        } else if has(&["must be", "invalid", "unsupported"]) {
+            ErrorCode::InvalidInput
+        } else if has(&[
+            "failed to write",
+            "failed to read",
+            "failed to open",
+            "failed to create",
+            "permission denied",
+        ]) {
+            ErrorCode::Storage
+        } else {
+            ErrorCode::Internal
+        }
+    }
+}
+
+/// Error returned by Tauri commands, serialized as `{ code, message, hint }`
+/// (`src/types.ts::CommandError`). Commands set the code where they fail,
+/// with `CommandError::new` or `.with_code()` on a helper's `Result`. The
+/// `From<String>` conversion classifies the code from the text and is only a
+/// fallback for code that has not been converted yet.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommandError {
+    pub code: ErrorCode,
+    pub message: String,
+    pub hint: Option<String>,
+}
+
+pub type CommandResult<T> = Result<T, CommandError>;
+
+impl CommandError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            hint: code.hint().map(str::to_string),
+        }
+    }
+
+    pub fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+/// Attaches an explicit `ErrorCode` to a helper's error at the failure site.
+pub trait WithErrorCode<T> {
+    fn with_code(self, code: ErrorCode) -> CommandResult<T>;
+}
+
+impl<T, E: fmt::Display> WithErrorCode<T> for Result<T, E> {
+    fn with_code(self, code: ErrorCode) -> CommandResult<T> {
+        self.map_err(|error| CommandError::new(code, error.to_string()))
+    }
+}
+
+/// Lets `String`-based callers of a command use `?` on its result.
+impl From<CommandError> for String {
+    fn from(error: CommandError) -> Self {
+        error.message
+    }
+}
+
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        Self::new(ErrorCode::classify(&message), message)
+    }
+}
+
+impl From<&str> for CommandError {
+    fn from(message: &str) -> Self {
+        message.to_string().into()
+    }
+}
+
+impl From<AppError> for CommandError {
+    fn from(error: AppError) -> Self {
+        let code = error.code();
+        match error {
+            AppError::AudioDevice(message)
+            | AppError::Transcription(message)
+            | AppError::Hotkey(message)
+            | AppError::Storage(message)
+            | AppError::Network(message)
+            | AppError::Window(message)
+            | AppError::Other(message) => Self::new(code, message),
         }
     }
 }
@@ -119,14 +283,19 @@ impl From<&str> for AppError {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ErrorEvent {
     pub error: AppError,
+    pub code: ErrorCode,
+    pub hint: Option<String>,
     pub timestamp: u64,
     pub context: Option<String>,
 }
 
 impl ErrorEvent {
     pub fn new(error: AppError) -> Self {
+        let code = error.code();
         Self {
             error,
+            code,
+            hint: code.hint().map(str::to_string),
             timestamp: std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap_or_default()
@@ -176,5 +345,36 @@ mod tests {
 
         assert!(event.context.is_some());
         assert_eq!(event.context.unwrap(), "Downloading model");
+        assert_eq!(event.code, ErrorCode::Network);
+    }
+
+    #[test]
+    fn test_command_error_codes() {
+        let err: CommandError = "Model file not found. Set TRISPR_WHISPER_MODEL_DIR.".into();
+        assert_eq!(err.code, ErrorCode::ModelNotFound);
+        assert!(err.hint.is_some());
+        let err: CommandError = "No API key configured for translation provider 'deepl'"
+            .to_string()
+            .into();
+        assert_eq!(err.code, ErrorCode::Credentials);
+        let err: CommandError = "Failed segment 'mic_1' not found".into();
+        assert_eq!(err.code, ErrorCode::NotFound);
+        assert_eq!(err.to_string(), "Failed segment 'mic_1' not found");
+        assert_eq!(
+            CommandError::from(AppError::Transcription("whisper-cli failed".into())).code,
+            ErrorCode::Transcription
+        );
+
+        let json = serde_json::to_value(CommandError::new(ErrorCode::InvalidInput, "bad")).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "code": "invalid_input", "message": "bad", "hint": null })
+        );
+
+        // An explicit code wins over what the wording would classify as.
+        let failed: Result<(), String> = Err("Model not found on the server".to_string());
+        let err = failed.with_code(ErrorCode::Network).unwrap_err();
+        assert_eq!(err.code, ErrorCode::Network);
+        assert_eq!(String::from(err), "Model not found on the server");
     }
 }
@@ -0,0 +1,202 @@
+//! WebSocket event stream for external consumers (OBS overlays, note apps).
+//!
+//! Served by the HTTP API at `GET /v1/events` and sharing its token and
+//! enable switch. Each client gets `{"event": name, "payload": …}` text
+//! frames for the app events in `STREAM_EVENTS`; `?events=a,b` narrows the
+//! set (level events are chatty). Browsers cannot set headers on WebSocket
+//! requests, so this endpoint also accepts `?token=`.
+
+use std::sync::mpsc::{sync_channel, Receiver, RecvTimeoutError, SyncSender, TrySendError};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Listener};
+use tiny_http::{Header, Request, Response, StatusCode};
+use tracing::{info, warn};
+use tungstenite::protocol::Role;
+use tungstenite::{Message, WebSocket};
+
+pub(crate) const STREAM_EVENTS: [&str; 10] = [
+    "transcription:result",
+    "transcription:error",
+    "transcription:jobs",
+    "transcribe:state",
+    "transcribe:history-updated",
+    "transcribe:level",
+    "transcribe:db",
+    "audio:level",
+    "capture:state",
+    "capture:paused-by-policy",
+];
+
+/// Frames queued per client; a client that falls further behind loses frames.
+const CLIENT_QUEUE: usize = 256;
+/// Also how quickly a client that went away is noticed (see `await_pong`).
+const PING_INTERVAL: Duration = Duration::from_secs(10);
+
+struct Client {
+    events: Vec<String>,
+    tx: SyncSender<String>,
+}
+
+static CLIENTS: Mutex<Vec<Client>> = Mutex::new(Vec::new());
+
+fn frame(event: &str, payload: &str) -> String {
+    let payload = if payload.trim().is_empty() {
+        "null"
+    } else {
+        payload
+    };
+    format!(r#"{{"event":"{}","payload":{}}}"#, event, payload)
+}
+
+fn broadcast(event: &str, payload: &str) {
+    let mut clients = CLIENTS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if clients.is_empty() {
+        return;
+    }
+    let message = frame(event, payload);
+    clients.retain(|client| {
+        if !client.events.iter().any(|wanted| wanted == event) {
+            return true;
+        }
+        !matches!(
+            client.tx.try_send(message.clone()),
+            Err(TrySendError::Disconnected(_))
+        )
+    });
+}
+
+/// Forwards the stream events to connected clients. Call once from setup.
+pub(crate) fn install(app: &AppHandle) {
+    for event in STREAM_EVENTS {
+        app.listen(event, move |message| broadcast(event, message.payload()));
+    }
+}
+
+/// Closes every client, e.g. when the HTTP API is turned off.
+pub(crate) fn disconnect_all() {
+    CLIENTS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clear();
+}
+
+/// Events requested via `?events=`; unknown names are ignored, none = all.
+fn requested_events(filter: Option<&str>) -> Vec<String> {
+    let requested: Vec<String> = filter
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|name| STREAM_EVENTS.contains(name))
+        .map(str::to_string)
+        .collect();
+    if requested.is_empty() {
+        STREAM_EVENTS.iter().map(|name| name.to_string()).collect()
+    } else {
+        requested
+    }
+}
+
+fn header_value<'a>(request: &'a Request, name: &'static str) -> Option<&'a str> {
+    request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv(name))
+        .map(|header| header.value.as_str())
+}
+
+/// Completes the WebSocket handshake for an authorized request and serves
+/// the client on its own thread.
+pub(crate) fn accept(request: Request, filter: Option<&str>) {
+    let Some(key) = header_value(&request, "Sec-WebSocket-Key") else {
+        let _ = request
+            .respond(Response::from_string("Expected a WebSocket upgrade").with_status_code(400));
+        return;
+    };
+    let accept_key = tungstenite::handshake::derive_accept_key(key.trim().as_bytes());
+    let headers = [
+        ("Upgrade", "websocket".to_string()),
+        ("Connection", "Upgrade".to_string()),
+        ("Sec-WebSocket-Accept", accept_key),
+    ];
+    let mut response = Response::empty(StatusCode(101));
+    for (name, value) in headers {
+        if let Ok(header) = Header::from_bytes(name, value) {
+            response.add_header(header);
+        }
+    }
+    let events = requested_events(filter);
+    let (tx, rx) = sync_channel::<String>(CLIENT_QUEUE);
+    let stream = request.upgrade("websocket", response);
+    let socket = WebSocket::from_raw_socket(stream, Role::Server, None);
+
+    CLIENTS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .push(Client { events, tx });
+    info!("Event stream client connected");
+    crate::util::spawn_guarded("event_stream_client", move || serve_client(socket, rx));
+}
+
+fn serve_client<S: std::io::Read + std::io::Write>(mut socket: WebSocket<S>, rx: Receiver<String>) {
+    loop {
+        let (message, ping) = match rx.recv_timeout(PING_INTERVAL) {
+            Ok(text) => (Message::text(text), false),
+            Err(RecvTimeoutError::Timeout) => (Message::Ping(Vec::new()), true),
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+        let mut result = socket.send(message);
+        if ping && result.is_ok() {
+            result = await_pong(&mut socket);
+        }
+        if let Err(err) = result {
+            if !matches!(
+                err,
+                tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed
+            ) {
+                warn!("Event stream client dropped: {}", err);
+            }
+            break;
+        }
+    }
+    // Dropping `rx` makes the next broadcast remove this client.
+    info!("Event stream client disconnected");
+}
+
+/// Reads and drops what the client sent up to its answer to our ping. The
+/// upgraded stream can't be read on another thread, so reading waits for a
+/// ping, which a live client answers right away; a closed one shows up here
+/// as an error instead of lingering until a write fails.
+fn await_pong<S: std::io::Read + std::io::Write>(
+    socket: &mut WebSocket<S>,
+) -> tungstenite::Result<()> {
+    loop {
+        if let Message::Pong(_) = socket.read()? {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_wrap_payload_and_filter_defaults_to_all() {
+        assert_eq!(
+            frame("capture:state", "\"recording\""),
+            r#"{"event":"capture:state","payload":"recording"}"#
+        );
+        assert_eq!(
+            frame("audio:level", ""),
+            r#"{"event":"audio:level","payload":null}"#
+        );
+        assert_eq!(
+            requested_events(Some("audio:level, bogus")),
+            vec!["audio:level".to_string()]
+        );
+        assert_eq!(requested_events(None).len(), STREAM_EVENTS.len());
+    }
+}
@@ -0,0 +1,271 @@
+//! Audio of segments whose transcription failed, kept on disk so no speech
+//! is lost.
+//!
+//! Each failure is written to `<base>/failed/` as `<id>.wav` plus `<id>.json`
+//! metadata. Retrying transcribes the audio as a new job and delivers the
+//! result like a fresh segment; the files are removed once that succeeds (a
+//! retry that fails again is kept under a new id with the new error).
+//! Cancelled jobs are not kept. Only the newest `MAX_FAILED_SEGMENTS` are
+//! kept. With history encryption on, the audio is sealed like recordings.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+use tracing::{info, warn};
+
+use crate::constants::TARGET_SAMPLE_RATE;
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
+use crate::transcription_jobs::{current_job_cancelled, current_job_id, is_cancellation};
+
+const MAX_FAILED_SEGMENTS: usize = 200;
+const FAILED_SEGMENTS_EVENT: &str = "failed-segments:changed";
+
+/// Mirrors `src/types.ts::FailedSegment`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct FailedSegment {
+    pub(crate) id: String,
+    /// "mic" or "output" (system audio).
+    pub(crate) source: String,
+    /// Id of the transcription job that failed; unique across app runs.
+    #[serde(default)]
+    pub(crate) job_id: Option<String>,
+    pub(crate) error: String,
+    pub(crate) failed_ms: u64,
+    pub(crate) duration_ms: u64,
+}
+
+fn is_valid_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn wav_path(dir: &Path, id: &str) -> PathBuf {
+    dir.join(format!("{}.wav", id))
+}
+
+fn meta_path(dir: &Path, id: &str) -> PathBuf {
+    dir.join(format!("{}.json", id))
+}
+
+/// The WAV is encoded in memory so it only reaches the disk sealed.
+fn write_wav(path: &Path, samples: &[i16]) -> Result<(), String> {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: TARGET_SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut wav = std::io::Cursor::new(Vec::new());
+    {
+        let mut writer = hound::WavWriter::new(&mut wav, spec)
+            .map_err(|e| format!("Failed to create failed-segment WAV: {}", e))?;
+        for &sample in samples {
+            writer
+                .write_sample(sample)
+                .map_err(|e| format!("Failed to write failed-segment WAV: {}", e))?;
+        }
+        writer
+            .finalize()
+            .map_err(|e| format!("Failed to finalize failed-segment WAV: {}", e))?;
+    }
+    let data = crate::history_crypto::seal(&wav.into_inner())?;
+    fs::write(path, data).map_err(|e| format!("Failed to write failed-segment WAV: {}", e))
+}
+
+fn read_wav(path: &Path) -> Result<Vec<i16>, String> {
+    let data = crate::history_crypto::read_file(path)?;
+    let mut reader = hound::WavReader::new(std::io::Cursor::new(data))
+        .map_err(|e| format!("Failed to open failed-segment audio: {}", e))?;
+    reader
+        .samples::<i16>()
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read failed-segment audio: {}", e))
+}
+
+/// Segments in `dir`, newest first. Metadata without audio is skipped.
+fn list_in(dir: &Path) -> Vec<FailedSegment> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut segments: Vec<FailedSegment> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| fs::read_to_string(&path).ok())
+        .filter_map(|raw| serde_json::from_str::<FailedSegment>(&raw).ok())
+        .filter(|segment| is_valid_id(&segment.id) && wav_path(dir, &segment.id).exists())
+        .collect();
+    segments.sort_by(|a, b| b.failed_ms.cmp(&a.failed_ms).then(b.id.cmp(&a.id)));
+    segments
+}
+
+fn remove_in(dir: &Path, id: &str) -> Result<(), String> {
+    if !is_valid_id(id) {
+        return Err(format!("Invalid failed segment id '{}'", id));
+    }
+    let wav = wav_path(dir, id);
+    if !wav.exists() && !meta_path(dir, id).exists() {
+        return Err(format!("Failed segment '{}' not found", id));
+    }
+    let _ = fs::remove_file(meta_path(dir, id));
+    fs::remove_file(&wav)
+        .or_else(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                Ok(())
+            } else {
+                Err(e)
+            }
+        })
+        .map_err(|e| format!("Failed to remove failed segment '{}': {}", id, e))
+}
+
+/// Writes `samples` as a new segment in `dir` and prunes the oldest beyond
+/// `MAX_FAILED_SEGMENTS`.
+fn store_in(
+    dir: &Path,
+    source: &str,
+    job_id: Option<String>,
+    samples: &[i16],
+    error: &str,
+    failed_ms: u64,
+) -> Result<FailedSegment, String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let mut id = format!("{}_{}", source, failed_ms);
+    let mut suffix = 1;
+    while wav_path(dir, &id).exists() || meta_path(dir, &id).exists() {
+        suffix += 1;
+        id = format!("{}_{}_{}", source, failed_ms, suffix);
+    }
+    let segment = FailedSegment {
+        id,
+        source: source.to_string(),
+        job_id,
+        error: error.to_string(),
+        failed_ms,
+        duration_ms: samples.len() as u64 * 1000 / TARGET_SAMPLE_RATE as u64,
+    };
+    write_wav(&wav_path(dir, &segment.id), samples)?;
+    let json = serde_json::to_string_pretty(&segment).map_err(|e| e.to_string())?;
+    if let Err(e) = fs::write(meta_path(dir, &segment.id), json) {
+        let _ = fs::remove_file(wav_path(dir, &segment.id));
+        return Err(format!("Failed to write failed-segment metadata: {}", e));
+    }
+    for stale in list_in(dir).iter().skip(MAX_FAILED_SEGMENTS) {
+        warn!("Dropping oldest failed segment '{}'", stale.id);
+        let _ = remove_in(dir, &stale.id);
+    }
+    Ok(segment)
+}
+
+fn failed_dir(app: &AppHandle) -> PathBuf {
+    crate::paths::resolve_failed_segments_dir(app)
+}
+
+/// Keeps the audio of the transcription job running on this thread after it
+/// failed for good. `source` is "mic" or "output".
+pub(crate) fn keep(app: &AppHandle, source: &str, samples: &[i16], error: &str) {
+    if samples.is_empty() || is_cancellation(error) || current_job_cancelled() {
+        return;
+    }
+    match store_in(
+        &failed_dir(app),
+        source,
+        current_job_id(),
+        samples,
+        error,
+        crate::util::now_ms(),
+    ) {
+        Ok(segment) => {
+            info!("Kept failed {} segment as '{}'", source, segment.id);
+            let _ = app.emit(FAILED_SEGMENTS_EVENT, &segment);
+        }
+        Err(err) => warn!("Could not keep failed segment audio: {}", err),
+    }
+}
+
+#[tauri::command]
+pub(crate) fn list_failed_segments(app: AppHandle) -> Vec<FailedSegment> {
+    list_in(&failed_dir(&app))
+}
+
+/// Id of the newest segment kept for transcription job `job_id`.
+pub(crate) fn find_by_job_id(app: &AppHandle, job_id: &str) -> Option<String> {
+    list_in(&failed_dir(app))
+        .into_iter()
+        .find(|segment| segment.job_id.as_deref() == Some(job_id))
+        .map(|segment| segment.id)
+}
+
+/// Transcribes a failed segment again in the background; the result is
+/// delivered like a fresh segment of its source.
+#[tauri::command]
+pub(crate) fn retry_failed_segment(app: AppHandle, id: String) -> CommandResult<()> {
+    let dir = failed_dir(&app);
+    let segment = list_in(&dir)
+        .into_iter()
+        .find(|segment| segment.id == id)
+        .ok_or_else(|| {
+            CommandError::new(
+                ErrorCode::NotFound,
+                format!("Failed segment '{}' not found", id),
+            )
+        })?;
+    let samples = read_wav(&wav_path(&dir, &segment.id))?;
+    crate::util::spawn_guarded("retry_failed_segment", move || {
+        let result = match segment.source.as_str() {
+            "output" => crate::transcription::transcribe_retained_loopback_audio(&app, samples),
+            _ => crate::audio::transcribe_retained_mic_audio(&app, samples),
+        };
+        // A retry that failed again was kept under a new id; a cancelled one
+        // keeps the original.
+        if matches!(&result, Err(err) if is_cancellation(err)) {
+            return;
+        }
+        if let Err(err) = remove_in(&dir, &segment.id) {
+            warn!("{}", err);
+        }
+        let _ = app.emit(FAILED_SEGMENTS_EVENT, serde_json::Value::Null);
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) fn discard_failed_segment(app: AppHandle, id: String) -> CommandResult<()> {
+    remove_in(&failed_dir(&app), &id).with_code(ErrorCode::NotFound)?;
+    let _ = app.emit(FAILED_SEGMENTS_EVENT, serde_json::Value::Null);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn segments_round_trip_through_the_failed_folder() {
+        let dir = std::env::temp_dir().join(format!(
+            "trispr_failed_segments_test_{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        let samples = vec![0i16, 120, -120, 4000];
+
+        let first = store_in(&dir, "mic", Some("job_3".into()), &samples, "boom", 1_000).unwrap();
+        let second = store_in(&dir, "mic", None, &samples, "again", 1_000).unwrap();
+        assert_eq!(first.id, "mic_1000");
+        assert_eq!(second.id, "mic_1000_2");
+        assert_eq!(read_wav(&wav_path(&dir, &first.id)).unwrap(), samples);
+
+        let listed = list_in(&dir);
+        assert_eq!(listed, vec![second.clone(), first.clone()]);
+        assert!(remove_in(&dir, "../settings").is_err());
+        remove_in(&dir, &first.id).unwrap();
+        assert_eq!(list_in(&dir), vec![second]);
+        assert!(remove_in(&dir, &first.id).is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
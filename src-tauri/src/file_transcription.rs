@@ -0,0 +1,345 @@
+//! Transcribe an existing audio or video file (meeting recordings dropped on
+//! the window, the MCP `transcribe_file` tool).
+//!
+//! Files are decoded with symphonia (WAV, MP3, AAC/M4A/MP4, FLAC, Ogg Vorbis,
+//! MKV/WebM audio, …) and, for anything symphonia cannot read (Opus, most
+//! video codecs), through the FFmpeg sidecar of the `opus` module. Audio is
+//! downmixed and resampled with `CaptureBuffer`, then transcribed in chunks
+//! split at quiet points so progress can be reported. The result lands in
+//! the mic history with source `"file"`.
+
+use serde::Serialize;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{info, warn};
+
+use crate::audio::CaptureBuffer;
+use crate::constants::TARGET_SAMPLE_RATE;
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
+use crate::state::{AppState, Settings};
+use crate::transcription_jobs::JobSource;
+
+/// History source for file transcripts.
+pub(crate) const FILE_HISTORY_SOURCE: &str = "file";
+/// Target chunk length; whisper works on 30 s windows.
+const CHUNK_SECS: usize = 30;
+/// How far back from a chunk boundary to look for a quiet split point.
+const SPLIT_SEARCH_SECS: usize = 3;
+const SPLIT_FRAME_SAMPLES: usize = TARGET_SAMPLE_RATE as usize / 50; // 20 ms
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FileTranscriptionOptions {
+    /// Model id overriding `settings.model`.
+    pub(crate) model: Option<String>,
+    /// Language code (or "auto") overriding `settings.language_mode`.
+    pub(crate) language: Option<String>,
+    /// Add the transcript to the history (off for watch-folder style callers
+    /// that write their own output).
+    pub(crate) add_to_history: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct FileTranscriptSegment {
+    pub(crate) start_ms: u64,
+    pub(crate) end_ms: u64,
+    pub(crate) text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct FileTranscription {
+    pub(crate) path: String,
+    pub(crate) text: String,
+    pub(crate) duration_ms: u64,
+    pub(crate) segments: Vec<FileTranscriptSegment>,
+    pub(crate) entry_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct FileTranscriptionProgress<'a> {
+    path: &'a str,
+    processed_ms: u64,
+    total_ms: u64,
+    done: bool,
+}
+
+fn samples_to_ms(samples: usize) -> u64 {
+    samples as u64 * 1000 / TARGET_SAMPLE_RATE as u64
+}
+
+fn symphonia_error(path: &Path, err: SymphoniaError) -> String {
+    format!("Failed to decode {}: {}", path.display(), err)
+}
+
+/// Decodes the first audio track of `path` to 16 kHz mono.
+pub(crate) fn decode_with_symphonia(path: &Path) -> Result<Vec<i16>, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let stream = MediaSourceStream::new(Box::new(file), Default::default());
+    let mut hint = Hint::new();
+    if let Some(ext) = path.extension().and_then(|ext| ext.to_str()) {
+        hint.with_extension(ext);
+    }
+    let probed = symphonia::default::get_probe()
+        .format(
+            &hint,
+            stream,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .map_err(|e| symphonia_error(path, e))?;
+    let mut format = probed.format;
+    let track = format
+        .tracks()
+        .iter()
+        .find(|track| track.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| format!("{} has no audio track", path.display()))?;
+    let track_id = track.id;
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|e| symphonia_error(path, e))?;
+
+    let mut buffer = CaptureBuffer::default();
+    let mut mono = Vec::new();
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(err))
+                if err.kind() == std::io::ErrorKind::UnexpectedEof =>
+            {
+                break
+            }
+            Err(SymphoniaError::ResetRequired) => break,
+            Err(err) => return Err(symphonia_error(path, err)),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            // A corrupt packet costs a few milliseconds, not the whole file.
+            Err(SymphoniaError::DecodeError(err)) => {
+                warn!("Skipping undecodable packet in {}: {}", path.display(), err);
+                continue;
+            }
+            Err(err) => return Err(symphonia_error(path, err)),
+        };
+        let spec = *decoded.spec();
+        let channels = spec.channels.count().max(1);
+        let mut samples = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+        samples.copy_interleaved_ref(decoded);
+        mono.clear();
+        mono.extend(
+            samples
+                .samples()
+                .chunks(channels)
+                .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32),
+        );
+        buffer.push_samples(&mono, spec.rate);
+    }
+    Ok(buffer.take_all_samples())
+}
+
+/// Decodes through the FFmpeg sidecar into a temporary WAV.
+fn decode_with_sidecar(app: &AppHandle, path: &Path) -> Result<Vec<i16>, String> {
+    let sidecar = crate::opus::resolve_sidecar(app)
+        .ok_or_else(|| "Install the opus module to import this format.".to_string())?;
+    let wav_path = crate::scratch::scratch_path("import", "wav");
+    let result = crate::opus::decode_with_sidecar(&sidecar, path, &wav_path).and_then(|_| {
+        let bytes = std::fs::read(&wav_path).map_err(|e| e.to_string())?;
+        crate::audio::decode_wav_to_mono_16k(&bytes)
+    });
+    let _ = std::fs::remove_file(&wav_path);
+    result
+}
+
+/// Loads any supported audio/video file as 16 kHz mono samples.
+pub(crate) fn decode_file(app: &AppHandle, path: &Path) -> Result<Vec<i16>, String> {
+    match decode_with_symphonia(path) {
+        Ok(samples) => Ok(samples),
+        Err(symphonia_err) => {
+            info!("{}; trying the FFmpeg sidecar", symphonia_err);
+            decode_with_sidecar(app, path)
+                .map_err(|sidecar_err| format!("{} ({})", symphonia_err, sidecar_err))
+        }
+    }
+}
+
+/// Chunk end offsets: every `CHUNK_SECS`, moved back to the quietest 20 ms
+/// frame within the preceding `SPLIT_SEARCH_SECS` so words are not cut.
+fn chunk_boundaries(samples: &[i16]) -> Vec<usize> {
+    let chunk = CHUNK_SECS * TARGET_SAMPLE_RATE as usize;
+    let search = SPLIT_SEARCH_SECS * TARGET_SAMPLE_RATE as usize;
+    let mut boundaries = Vec::new();
+    let mut start = 0;
+    while samples.len() - start > chunk {
+        let target = start + chunk;
+        let quietest = (target - search..target)
+            .step_by(SPLIT_FRAME_SAMPLES)
+            .min_by_key(|&frame| {
+                samples[frame..frame + SPLIT_FRAME_SAMPLES]
+                    .iter()
+                    .map(|sample| (*sample as i64).abs())
+                    .sum::<i64>()
+            })
+            .unwrap_or(target);
+        boundaries.push(quietest);
+        start = quietest;
+    }
+    boundaries.push(samples.len());
+    boundaries
+}
+
+fn effective_settings(app: &AppHandle, options: &FileTranscriptionOptions) -> Settings {
+    let mut settings = app
+        .state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    if let Some(model) = options.model.as_deref().filter(|model| !model.is_empty()) {
+        settings.model = model.to_string();
+    }
+    if let Some(language) = options.language.as_deref().map(str::trim) {
+        if language.eq_ignore_ascii_case("auto") {
+            settings.language_pinned = false;
+        } else if !language.is_empty() {
+            settings.language_mode = language.to_lowercase();
+            settings.language_pinned = true;
+        }
+    }
+    settings
+}
+
+/// Decodes and transcribes `path`, emitting `file-transcription:progress`.
+/// Blocking; runs as a cancellable `File` job. Callers go through
+/// `batch_queue` so file work yields to live dictation.
+pub(crate) fn transcribe_file_blocking(
+    app: &AppHandle,
+    path: &Path,
+    options: &FileTranscriptionOptions,
+) -> Result<FileTranscription, String> {
+    let display_path = path.to_string_lossy().to_string();
+    let samples = decode_file(app, path)?;
+    if samples.is_empty() {
+        return Err(format!("{} contains no audio", display_path));
+    }
+    let settings = effective_settings(app, options);
+    let total_ms = samples_to_ms(samples.len());
+    info!(
+        "Transcribing file {} ({} ms, model {})",
+        display_path, total_ms, settings.model
+    );
+
+    let segments = crate::transcription_jobs::run_job(app, JobSource::File, total_ms, || {
+        let mut segments = Vec::new();
+        let mut start = 0;
+        for end in chunk_boundaries(&samples) {
+            crate::batch_queue::wait_for_turn(app);
+            if crate::transcription_jobs::current_job_cancelled() {
+                return Err(crate::transcription_jobs::JOB_CANCELLED_ERROR.to_string());
+            }
+            let (text, _source) =
+                crate::transcription::transcribe_audio(app, &settings, &samples[start..end])?;
+            let text = text.trim();
+            if !text.is_empty() {
+                segments.push(FileTranscriptSegment {
+                    start_ms: samples_to_ms(start),
+                    end_ms: samples_to_ms(end),
+                    text: text.to_string(),
+                });
+            }
+            let _ = app.emit(
+                "file-transcription:progress",
+                FileTranscriptionProgress {
+                    path: &display_path,
+                    processed_ms: samples_to_ms(end),
+                    total_ms,
+                    done: end == samples.len(),
+                },
+            );
+            start = end;
+        }
+        Ok(segments)
+    })?;
+
+    let text = segments
+        .iter()
+        .map(|segment| segment.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut entry_id = None;
+    if options.add_to_history && !text.is_empty() {
+        let state = app.state::<AppState>();
+        let updated = crate::state::push_history_entry_inner(
+            app,
+            &state.history,
+            text.clone(),
+            FILE_HISTORY_SOURCE.to_string(),
+        )?;
+        entry_id = updated.first().map(|entry| entry.id.clone());
+        let _ = app.emit("history:updated", updated);
+    }
+    Ok(FileTranscription {
+        path: display_path,
+        text,
+        duration_ms: total_ms,
+        segments,
+        entry_id,
+    })
+}
+
+#[tauri::command]
+pub(crate) async fn transcribe_file(
+    app: AppHandle,
+    path: String,
+    model: Option<String>,
+    language: Option<String>,
+) -> CommandResult<FileTranscription> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let options = FileTranscriptionOptions {
+            model,
+            language,
+            add_to_history: true,
+        };
+        let path = PathBuf::from(path.trim());
+        let label = path.to_string_lossy().to_string();
+        let worker_app = app.clone();
+        crate::batch_queue::run_blocking(&app, label, move || {
+            transcribe_file_blocking(&worker_app, &path, &options)
+        })
+        .with_code(ErrorCode::Transcription)
+    })
+    .await
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("transcribe_file task failed: {}", e),
+        )
+    })?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks_split_at_the_quietest_frame_before_the_boundary() {
+        let rate = TARGET_SAMPLE_RATE as usize;
+        let mut samples = vec![1000i16; 70 * rate];
+        // Silence 1.5 s before the 30 s mark.
+        let quiet = 28 * rate + rate / 2;
+        samples[quiet..quiet + SPLIT_FRAME_SAMPLES].fill(0);
+        let boundaries = chunk_boundaries(&samples);
+        assert_eq!(boundaries[0], quiet);
+        assert_eq!(*boundaries.last().unwrap(), samples.len());
+        assert!(boundaries.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(chunk_boundaries(&samples[..rate]), vec![rate]);
+    }
+}
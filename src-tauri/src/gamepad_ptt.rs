@@ -0,0 +1,283 @@
+//! Push-to-talk from a game controller button or a USB foot pedal.
+//!
+//! Controllers and pedals that enumerate as HID joysticks are read through
+//! gilrs on a dedicated thread. A press/release of the configured button goes
+//! through the same `on_ptt_hotkey` path as the keyboard PTT hotkey. The thread
+//! only runs while a binding is active or a button is being learned.
+
+use gilrs::{Button, EventType, Gilrs};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Mutex};
+use std::time::Duration;
+use tauri::AppHandle;
+use tracing::{info, warn};
+
+use crate::errors::{CommandError, CommandResult, ErrorCode};
+use crate::state::Settings;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+const LEARN_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct GamepadPttConfig {
+    /// Controller name; empty matches any controller.
+    device: String,
+    button: String,
+}
+
+impl GamepadPttConfig {
+    fn from_settings(settings: &Settings) -> Option<Self> {
+        let button = settings.ptt_gamepad_button.trim();
+        if !settings.ptt_gamepad_enabled
+            || crate::state::mode_uses_vad_monitor(&settings.mode)
+            || button.is_empty()
+        {
+            return None;
+        }
+        Some(Self {
+            device: settings.ptt_gamepad_device.trim().to_string(),
+            button: button.to_string(),
+        })
+    }
+
+    fn matches(&self, device: &str, button: &str) -> bool {
+        (self.device.is_empty() || self.device.eq_ignore_ascii_case(device))
+            && self.button.eq_ignore_ascii_case(button)
+    }
+}
+
+/// A button press reported while learning a binding.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GamepadButtonCapture {
+    pub(crate) device: String,
+    pub(crate) button: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GamepadInfo {
+    pub(crate) name: String,
+    pub(crate) connected: bool,
+}
+
+struct WorkerState {
+    config: Option<GamepadPttConfig>,
+    learn: Option<mpsc::Sender<GamepadButtonCapture>>,
+}
+
+impl WorkerState {
+    fn idle(&self) -> bool {
+        self.config.is_none() && self.learn.is_none()
+    }
+}
+
+static STATE: Mutex<WorkerState> = Mutex::new(WorkerState {
+    config: None,
+    learn: None,
+});
+static WORKER_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Stable label for a button. Pedals usually report buttons gilrs has no
+/// mapping for, so those fall back to the raw event code.
+fn button_label(button: Button, code: u32) -> String {
+    match button {
+        Button::Unknown => format!("Code{}", code),
+        known => format!("{:?}", known),
+    }
+}
+
+/// Applies the gamepad PTT settings, starting or stopping the reader thread.
+pub(crate) fn apply_settings(app: &AppHandle, settings: &Settings) {
+    let config = GamepadPttConfig::from_settings(settings);
+    let enabled = config.is_some();
+    STATE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .config = config;
+    if enabled {
+        ensure_worker(app);
+    }
+}
+
+fn ensure_worker(app: &AppHandle) {
+    if WORKER_RUNNING.swap(true, Ordering::AcqRel) {
+        return;
+    }
+    let app = app.clone();
+    crate::util::spawn_guarded("gamepad-ptt", move || run_worker(&app));
+}
+
+fn run_worker(app: &AppHandle) {
+    let mut gilrs = match Gilrs::new() {
+        Ok(gilrs) => gilrs,
+        Err(err) => {
+            WORKER_RUNNING.store(false, Ordering::Release);
+            crate::emit_error(
+                app,
+                crate::errors::AppError::Hotkey(format!(
+                    "Game controller input unavailable: {}",
+                    err
+                )),
+                Some("Gamepad PTT"),
+            );
+            return;
+        }
+    };
+    info!("Gamepad PTT reader started");
+    // Device whose button currently holds PTT down, so a disconnect mid-press
+    // does not leave recording running.
+    let mut held_by: Option<gilrs::GamepadId> = None;
+
+    loop {
+        {
+            let state = STATE
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            if state.idle() {
+                WORKER_RUNNING.store(false, Ordering::Release);
+                break;
+            }
+        }
+        let Some(event) = gilrs.next_event_blocking(Some(POLL_INTERVAL)) else {
+            continue;
+        };
+        let (button, code, pressed) = match event.event {
+            EventType::ButtonPressed(button, code) => (button, code, true),
+            EventType::ButtonReleased(button, code) => (button, code, false),
+            EventType::Disconnected => {
+                if held_by == Some(event.id) {
+                    warn!("Gamepad disconnected while PTT was held; releasing");
+                    held_by = None;
+                    crate::on_ptt_hotkey(app, false);
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        let device = gilrs.gamepad(event.id).name().to_string();
+        let label = button_label(button, code.into_u32());
+
+        let mut state = STATE
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(learn) = state.learn.take() {
+            if pressed {
+                let _ = learn.send(GamepadButtonCapture {
+                    device,
+                    button: label,
+                });
+            } else {
+                state.learn = Some(learn);
+            }
+            continue;
+        }
+        let matched = state
+            .config
+            .as_ref()
+            .is_some_and(|config| config.matches(&device, &label));
+        drop(state);
+        if !matched {
+            continue;
+        }
+        if pressed {
+            held_by = Some(event.id);
+        } else if held_by == Some(event.id) {
+            held_by = None;
+        } else {
+            continue;
+        }
+        crate::on_ptt_hotkey(app, pressed);
+    }
+    if held_by.is_some() {
+        crate::on_ptt_hotkey(app, false);
+    }
+    info!("Gamepad PTT reader stopped");
+}
+
+/// Controllers and pedals currently known to the input backend.
+#[tauri::command]
+pub(crate) async fn list_gamepads() -> CommandResult<Vec<GamepadInfo>> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let gilrs = Gilrs::new().map_err(|e| {
+            CommandError::new(
+                ErrorCode::Hotkey,
+                format!("Game controller input unavailable: {}", e),
+            )
+        })?;
+        Ok(gilrs
+            .gamepads()
+            .map(|(_, gamepad)| GamepadInfo {
+                name: gamepad.name().to_string(),
+                connected: gamepad.is_connected(),
+            })
+            .collect())
+    })
+    .await
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("list_gamepads task failed: {}", e),
+        )
+    })?
+}
+
+/// Waits for the next controller/pedal button press and returns it so the
+/// settings UI can store it as the PTT binding.
+#[tauri::command]
+pub(crate) async fn capture_gamepad_button(app: AppHandle) -> CommandResult<GamepadButtonCapture> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let (tx, rx) = mpsc::channel();
+        STATE
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .learn = Some(tx);
+        ensure_worker(&app);
+        let result = rx.recv_timeout(LEARN_TIMEOUT);
+        STATE
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .learn = None;
+        result.map_err(|_| CommandError::new(ErrorCode::Cancelled, "No controller button pressed"))
+    })
+    .await
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("capture_gamepad_button task failed: {}", e),
+        )
+    })?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_requires_enabled_button_outside_vad() {
+        let mut settings = Settings {
+            ptt_gamepad_enabled: true,
+            ptt_gamepad_button: " Code288 ".to_string(),
+            mode: "ptt".to_string(),
+            ..Settings::default()
+        };
+        let config = GamepadPttConfig::from_settings(&settings).unwrap();
+        assert!(config.matches("VEC USB Footpedal", "code288"));
+        assert!(!config.matches("VEC USB Footpedal", "Code289"));
+
+        settings.ptt_gamepad_device = "Xbox Controller".to_string();
+        let config = GamepadPttConfig::from_settings(&settings).unwrap();
+        assert!(!config.matches("VEC USB Footpedal", "Code288"));
+
+        settings.mode = "vad".to_string();
+        assert!(GamepadPttConfig::from_settings(&settings).is_none());
+        settings.mode = "ptt".to_string();
+        settings.ptt_gamepad_enabled = false;
+        assert!(GamepadPttConfig::from_settings(&settings).is_none());
+    }
+
+    #[test]
+    fn unknown_buttons_are_labelled_by_code() {
+        assert_eq!(button_label(Button::South, 304), "South");
+        assert_eq!(button_label(Button::Unknown, 288), "Code288");
+    }
+}
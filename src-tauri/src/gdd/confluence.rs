@@ -6,6 +6,7 @@ use std::time::Duration;
 use tauri::{AppHandle, Emitter, State};
 
 use crate::confluence::keyring as confluence_keyring;
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
 use crate::gdd::require_gdd_module_active;
 use crate::modules::{normalize_confluence_settings, ConfluenceSettings};
 use crate::state::{save_settings_file, AppState};
@@ -184,6 +185,15 @@ fn http_status_from_error(error: &str) -> Option<u16> {
     digits.parse::<u16>().ok()
 }
 
+/// A failed Confluence call: rejected credentials or an unreachable site.
+fn request_error(error: String) -> CommandError {
+    let code = match http_status_from_error(&error) {
+        Some(401 | 403) => ErrorCode::Credentials,
+        _ => ErrorCode::Network,
+    };
+    CommandError::new(code, error)
+}
+
 fn is_publish_update_conflict(error: &str) -> bool {
     if matches!(http_status_from_error(error), Some(409)) {
         return true;
@@ -987,14 +997,14 @@ pub fn oauth_exchange(
 pub(crate) fn test_confluence_connection(
     app: AppHandle,
     state: State<'_, AppState>,
-) -> Result<ConfluenceConnectionResult, String> {
+) -> CommandResult<ConfluenceConnectionResult> {
     crate::guarded_command!("test_confluence_connection", {
         let settings = state
             .settings
             .read()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
         require_gdd_module_active(&app, &settings)?;
-        test_connection(&app, &settings.confluence_settings)
+        test_connection(&app, &settings.confluence_settings).map_err(request_error)
     })
 }
 
@@ -1002,14 +1012,14 @@ pub(crate) fn test_confluence_connection(
 pub(crate) fn confluence_oauth_start(
     app: AppHandle,
     state: State<'_, AppState>,
-) -> Result<ConfluenceOauthStartResult, String> {
+) -> CommandResult<ConfluenceOauthStartResult> {
     crate::guarded_command!("confluence_oauth_start", {
         let settings = state
             .settings
             .read()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
         require_gdd_module_active(&app, &settings)?;
-        oauth_start()
+        oauth_start().with_code(ErrorCode::InvalidInput)
     })
 }
 
@@ -1018,7 +1028,7 @@ pub(crate) fn confluence_oauth_exchange(
     app: AppHandle,
     state: State<'_, AppState>,
     code: String,
-) -> Result<serde_json::Value, String> {
+) -> CommandResult<serde_json::Value> {
     crate::guarded_command!("confluence_oauth_exchange", {
         let exchange_result = {
             let settings = state
@@ -1026,7 +1036,7 @@ pub(crate) fn confluence_oauth_exchange(
                 .read()
                 .unwrap_or_else(|poisoned| poisoned.into_inner());
             require_gdd_module_active(&app, &settings)?;
-            oauth_exchange(&app, &settings.confluence_settings, &code)?
+            oauth_exchange(&app, &settings.confluence_settings, &code).map_err(request_error)?
         };
 
         let snapshot = {
@@ -1042,10 +1052,10 @@ pub(crate) fn confluence_oauth_exchange(
             settings.clone()
         };
 
-        save_settings_file(&app, &snapshot)?;
+        save_settings_file(&app, &snapshot).with_code(ErrorCode::Storage)?;
         let _ = app.emit("settings-changed", snapshot);
 
-        serde_json::to_value(exchange_result).map_err(|error| error.to_string())
+        serde_json::to_value(exchange_result).with_code(ErrorCode::Internal)
     })
 }
 
@@ -1053,14 +1063,14 @@ pub(crate) fn confluence_oauth_exchange(
 pub(crate) fn confluence_list_spaces(
     app: AppHandle,
     state: State<'_, AppState>,
-) -> Result<Vec<ConfluenceSpace>, String> {
+) -> CommandResult<Vec<ConfluenceSpace>> {
     crate::guarded_command!("confluence_list_spaces", {
         let settings = state
             .settings
             .read()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
         require_gdd_module_active(&app, &settings)?;
-        list_spaces(&app, &settings.confluence_settings)
+        list_spaces(&app, &settings.confluence_settings).map_err(request_error)
     })
 }
 
@@ -1069,14 +1079,14 @@ pub(crate) fn load_gdd_template_from_file(
     app: AppHandle,
     state: State<'_, AppState>,
     file_path: String,
-) -> Result<crate::gdd::GddTemplateSourceResult, String> {
+) -> CommandResult<crate::gdd::GddTemplateSourceResult> {
     crate::guarded_command!("load_gdd_template_from_file", {
         let settings = state
             .settings
             .read()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
         require_gdd_module_active(&app, &settings)?;
-        crate::gdd::load_template_from_file(&file_path)
+        crate::gdd::load_template_from_file(&file_path).with_code(ErrorCode::Storage)
     })
 }
 
@@ -1085,14 +1095,15 @@ pub(crate) fn load_gdd_template_from_confluence(
     app: AppHandle,
     state: State<'_, AppState>,
     source_url: String,
-) -> Result<crate::gdd::GddTemplateSourceResult, String> {
+) -> CommandResult<crate::gdd::GddTemplateSourceResult> {
     crate::guarded_command!("load_gdd_template_from_confluence", {
         let settings = state
             .settings
             .read()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
         require_gdd_module_active(&app, &settings)?;
-        let page = load_page_template_from_url(&app, &settings.confluence_settings, &source_url)?;
+        let page = load_page_template_from_url(&app, &settings.confluence_settings, &source_url)
+            .map_err(request_error)?;
         Ok(crate::gdd::template_sources::from_confluence_page(
             page.source_url,
             page.page_title,
@@ -1106,14 +1117,14 @@ pub(crate) fn suggest_confluence_target(
     app: AppHandle,
     state: State<'_, AppState>,
     request: ConfluenceTargetSuggestionRequest,
-) -> Result<ConfluenceTargetSuggestion, String> {
+) -> CommandResult<ConfluenceTargetSuggestion> {
     crate::guarded_command!("suggest_confluence_target", {
         let settings = state
             .settings
             .read()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
         require_gdd_module_active(&app, &settings)?;
-        suggest_target(&app, &settings.confluence_settings, &request)
+        suggest_target(&app, &settings.confluence_settings, &request).map_err(request_error)
     })
 }
 
@@ -1122,7 +1133,7 @@ pub(crate) fn publish_gdd_to_confluence(
     app: AppHandle,
     state: State<'_, AppState>,
     request: ConfluencePublishRequest,
-) -> Result<ConfluencePublishResult, String> {
+) -> CommandResult<ConfluencePublishResult> {
     crate::guarded_command!("publish_gdd_to_confluence", {
         let settings_snapshot = {
             let settings = state
@@ -1163,7 +1174,7 @@ pub(crate) fn publish_gdd_to_confluence(
                     "gdd:publish-failed",
                     serde_json::json!({ "title": request.title, "error": error }),
                 );
-                Err(error)
+                Err(request_error(error))
             }
         }
     })
@@ -1174,7 +1185,7 @@ pub(crate) fn publish_or_queue_gdd_to_confluence(
     app: AppHandle,
     state: State<'_, AppState>,
     request: crate::gdd::publish_queue::GddPublishOrQueueRequest,
-) -> Result<crate::gdd::publish_queue::GddPublishAttemptResult, String> {
+) -> CommandResult<crate::gdd::publish_queue::GddPublishAttemptResult> {
     crate::guarded_command!("publish_or_queue_gdd_to_confluence", {
         let settings_snapshot = {
             let settings = state
@@ -1226,7 +1237,8 @@ pub(crate) fn publish_or_queue_gdd_to_confluence(
             Err(error) => {
                 if crate::gdd::publish_queue::is_queueable_publish_error(&error) {
                     let queued_job =
-                        crate::gdd::publish_queue::queue_publish_request(&app, &request, &error)?;
+                        crate::gdd::publish_queue::queue_publish_request(&app, &request, &error)
+                            .with_code(ErrorCode::Storage)?;
                     let _ = app.emit(
                         "gdd:publish-queued",
                         serde_json::json!({
@@ -1265,14 +1277,14 @@ pub(crate) fn publish_or_queue_gdd_to_confluence(
 pub(crate) fn list_pending_gdd_publishes(
     app: AppHandle,
     state: State<'_, AppState>,
-) -> Result<Vec<crate::gdd::publish_queue::GddPendingPublishJob>, String> {
+) -> CommandResult<Vec<crate::gdd::publish_queue::GddPendingPublishJob>> {
     crate::guarded_command!("list_pending_gdd_publishes", {
         let settings = state
             .settings
             .read()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
         require_gdd_module_active(&app, &settings)?;
-        crate::gdd::publish_queue::list_pending_jobs(&app)
+        crate::gdd::publish_queue::list_pending_jobs(&app).with_code(ErrorCode::Storage)
     })
 }
 
@@ -1281,7 +1293,7 @@ pub(crate) fn retry_pending_gdd_publish(
     app: AppHandle,
     state: State<'_, AppState>,
     job_id: String,
-) -> Result<crate::gdd::publish_queue::GddPublishAttemptResult, String> {
+) -> CommandResult<crate::gdd::publish_queue::GddPublishAttemptResult> {
     crate::guarded_command!("retry_pending_gdd_publish", {
         {
             let settings = state
@@ -1293,11 +1305,21 @@ pub(crate) fn retry_pending_gdd_publish(
 
         let job_id = job_id.trim().to_string();
         if job_id.is_empty() {
-            return Err("job_id is required.".to_string());
+            return Err(CommandError::new(
+                ErrorCode::InvalidInput,
+                "job_id is required.",
+            ));
         }
-        let mut job = crate::gdd::publish_queue::load_pending_job(&app, &job_id)?
-            .ok_or_else(|| format!("Pending publish job '{}' not found.", job_id))?;
-        let publish_request = crate::gdd::publish_queue::load_publish_request_for_job(&job)?;
+        let mut job = crate::gdd::publish_queue::load_pending_job(&app, &job_id)
+            .with_code(ErrorCode::Storage)?
+            .ok_or_else(|| {
+                CommandError::new(
+                    ErrorCode::NotFound,
+                    format!("Pending publish job '{}' not found.", job_id),
+                )
+            })?;
+        let publish_request = crate::gdd::publish_queue::load_publish_request_for_job(&job)
+            .with_code(ErrorCode::Storage)?;
 
         let _ = app.emit(
             "gdd:publish-started",
@@ -1317,7 +1339,8 @@ pub(crate) fn retry_pending_gdd_publish(
 
         match publish_result {
             Ok(publish) => {
-                let _ = crate::gdd::publish_queue::consume_pending_job(&app, &job.job_id)?;
+                let _ = crate::gdd::publish_queue::consume_pending_job(&app, &job.job_id)
+                    .with_code(ErrorCode::Storage)?;
                 {
                     let mut settings = state
                         .settings
@@ -1343,7 +1366,8 @@ pub(crate) fn retry_pending_gdd_publish(
             }
             Err(error) => {
                 crate::gdd::publish_queue::mark_retry_failure(&mut job, &error);
-                crate::gdd::publish_queue::persist_pending_job(&app, &job)?;
+                crate::gdd::publish_queue::persist_pending_job(&app, &job)
+                    .with_code(ErrorCode::Storage)?;
                 let _ = app.emit(
                     "gdd:publish-failed",
                     serde_json::json!({
@@ -1372,7 +1396,7 @@ pub(crate) fn delete_pending_gdd_publish(
     app: AppHandle,
     state: State<'_, AppState>,
     job_id: String,
-) -> Result<bool, String> {
+) -> CommandResult<bool> {
     crate::guarded_command!("delete_pending_gdd_publish", {
         let settings = state
             .settings
@@ -1382,9 +1406,12 @@ pub(crate) fn delete_pending_gdd_publish(
 
         let job_id = job_id.trim();
         if job_id.is_empty() {
-            return Err("job_id is required.".to_string());
+            return Err(CommandError::new(
+                ErrorCode::InvalidInput,
+                "job_id is required.",
+            ));
         }
-        crate::gdd::publish_queue::delete_pending_job(&app, job_id)
+        crate::gdd::publish_queue::delete_pending_job(&app, job_id).with_code(ErrorCode::Storage)
     })
 }
 
@@ -1394,7 +1421,7 @@ pub(crate) fn save_confluence_secret(
     state: State<'_, AppState>,
     secret_id: String,
     secret_value: String,
-) -> Result<serde_json::Value, String> {
+) -> CommandResult<serde_json::Value> {
     crate::guarded_command!("save_confluence_secret", {
         {
             let settings = state
@@ -1405,7 +1432,8 @@ pub(crate) fn save_confluence_secret(
         }
 
         let secret_id = secret_id.trim().to_lowercase();
-        confluence_keyring::store_secret(&app, &secret_id, &secret_value)?;
+        confluence_keyring::store_secret(&app, &secret_id, &secret_value)
+            .with_code(ErrorCode::Credentials)?;
 
         let snapshot = {
             let mut settings = state
@@ -1415,7 +1443,7 @@ pub(crate) fn save_confluence_secret(
             settings.confluence_settings.enabled = true;
             settings.clone()
         };
-        save_settings_file(&app, &snapshot)?;
+        save_settings_file(&app, &snapshot).with_code(ErrorCode::Storage)?;
         let _ = app.emit("settings-changed", snapshot);
 
         Ok(serde_json::json!({
@@ -1430,7 +1458,7 @@ pub(crate) fn clear_confluence_secret(
     app: AppHandle,
     state: State<'_, AppState>,
     secret_id: String,
-) -> Result<serde_json::Value, String> {
+) -> CommandResult<serde_json::Value> {
     crate::guarded_command!("clear_confluence_secret", {
         let settings = state
             .settings
@@ -1439,7 +1467,7 @@ pub(crate) fn clear_confluence_secret(
         require_gdd_module_active(&app, &settings)?;
 
         let secret_id = secret_id.trim().to_lowercase();
-        confluence_keyring::clear_secret(&app, &secret_id)?;
+        confluence_keyring::clear_secret(&app, &secret_id).with_code(ErrorCode::Credentials)?;
         Ok(serde_json::json!({
             "status": "success",
             "secret_id": secret_id
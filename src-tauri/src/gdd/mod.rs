@@ -12,6 +12,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use tauri::{AppHandle, Emitter, State};
 
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
 use crate::modules::{
     canonicalize_module_id, normalize_gdd_module_settings, package as module_package,
     registry as module_registry, GDD_MODULE_ID,
@@ -127,24 +128,22 @@ pub(crate) fn require_gdd_module_active_from_ids(
     Ok(())
 }
 
-pub(crate) fn require_gdd_module_active(
-    app: &AppHandle,
-    settings: &Settings,
-) -> Result<(), String> {
+pub(crate) fn require_gdd_module_active(app: &AppHandle, settings: &Settings) -> CommandResult<()> {
     let modules_dir = crate::paths::resolve_modules_dir(app);
     let installed_package_ids = if modules_dir.is_dir() {
-        module_package::scan_installed_module_ids(&modules_dir)?
+        module_package::scan_installed_module_ids(&modules_dir).with_code(ErrorCode::Storage)?
     } else {
         HashSet::new()
     };
     require_gdd_module_active_from_ids(settings, &installed_package_ids)
+        .with_code(ErrorCode::InvalidInput)
 }
 
 #[tauri::command]
 pub(crate) fn list_gdd_presets(
     app: AppHandle,
     state: State<'_, AppState>,
-) -> Result<Vec<GddPreset>, String> {
+) -> CommandResult<Vec<GddPreset>> {
     let settings = state
         .settings
         .read()
@@ -158,7 +157,7 @@ pub(crate) fn save_gdd_preset_clone(
     app: AppHandle,
     state: State<'_, AppState>,
     mut preset: GddPresetClone,
-) -> Result<Vec<GddPreset>, String> {
+) -> CommandResult<Vec<GddPreset>> {
     crate::guarded_command!("save_gdd_preset_clone", {
         {
             let settings = state
@@ -170,13 +169,22 @@ pub(crate) fn save_gdd_preset_clone(
 
         preset.id = preset.id.trim().to_lowercase();
         if preset.id.is_empty() {
-            return Err("Preset clone id cannot be empty.".to_string());
+            return Err(CommandError::new(
+                ErrorCode::InvalidInput,
+                "Preset clone id cannot be empty.",
+            ));
         }
         if preset.section_order.is_empty() {
-            return Err("Preset clone requires at least one section.".to_string());
+            return Err(CommandError::new(
+                ErrorCode::InvalidInput,
+                "Preset clone requires at least one section.",
+            ));
         }
         if preset.name.trim().is_empty() {
-            return Err("Preset clone name cannot be empty.".to_string());
+            return Err(CommandError::new(
+                ErrorCode::InvalidInput,
+                "Preset clone name cannot be empty.",
+            ));
         }
 
         let snapshot = {
@@ -198,7 +206,7 @@ pub(crate) fn save_gdd_preset_clone(
             settings.clone()
         };
 
-        save_settings_file(&app, &snapshot)?;
+        save_settings_file(&app, &snapshot).with_code(ErrorCode::Storage)?;
         let _ = app.emit("settings-changed", snapshot.clone());
 
         Ok(list_presets(&snapshot.gdd_module_settings.preset_clones))
@@ -210,7 +218,7 @@ pub(crate) fn detect_gdd_preset(
     app: AppHandle,
     state: State<'_, AppState>,
     request: DetectGddPresetRequest,
-) -> Result<GddRecognitionResult, String> {
+) -> CommandResult<GddRecognitionResult> {
     let settings = state
         .settings
         .read()
@@ -225,7 +233,7 @@ pub(crate) fn generate_gdd_draft(
     app: AppHandle,
     state: State<'_, AppState>,
     request: GenerateGddDraftRequest,
-) -> Result<GddDraft, String> {
+) -> CommandResult<GddDraft> {
     crate::guarded_command!("generate_gdd_draft", {
         {
             let settings = state
@@ -267,7 +275,7 @@ pub(crate) fn validate_gdd_draft(
     app: AppHandle,
     state: State<'_, AppState>,
     draft: GddDraft,
-) -> Result<ValidateGddDraftResult, String> {
+) -> CommandResult<ValidateGddDraftResult> {
     let settings = state
         .settings
         .read()
@@ -281,7 +289,7 @@ pub(crate) fn render_gdd_for_confluence(
     app: AppHandle,
     state: State<'_, AppState>,
     draft: GddDraft,
-) -> Result<String, String> {
+) -> CommandResult<String> {
     let settings = state
         .settings
         .read()
@@ -295,7 +303,7 @@ pub(crate) fn render_gdd_markdown(
     app: AppHandle,
     state: State<'_, AppState>,
     draft: GddDraft,
-) -> Result<String, String> {
+) -> CommandResult<String> {
     let settings = state
         .settings
         .read()
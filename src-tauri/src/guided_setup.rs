@@ -0,0 +1,296 @@
+//! First-run guided setup: the checks behind the frontend setup wizard.
+//!
+//! Fresh installs used to fail silently when whisper-cli or the selected
+//! model was missing. The wizard walks through `setup_status` (what is still
+//! missing), `run_mic_test` (a short recording with level and playback) and
+//! `verify_whisper_runtime` (the binary actually starts and the model file is
+//! where transcription will look for it).
+
+use serde::Serialize;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_global_shortcut::GlobalShortcutExt;
+use tracing::{info, warn};
+
+use crate::constants::TARGET_SAMPLE_RATE;
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
+use crate::state::{AppState, Settings};
+
+const MIC_TEST_DURATION: Duration = Duration::from_secs(3);
+const MIC_TEST_FILE: &str = "mic-test.wav";
+/// Recording RMS (0..1) below which the test counts as silence.
+const MIC_TEST_SILENCE_RMS: f32 = 0.003;
+/// `whisper-cli --help` returns instantly; anything slower is a hang.
+const WHISPER_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct MicTestResult {
+    pub(crate) rms: f32,
+    pub(crate) peak: f32,
+    pub(crate) duration_ms: u64,
+    /// True when the recording is too quiet to transcribe.
+    pub(crate) silent: bool,
+    /// WAV of the test recording for playback in the wizard.
+    pub(crate) playback_path: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct WhisperRuntimeCheck {
+    pub(crate) cli_path: Option<String>,
+    /// whisper-cli started and exited cleanly (libraries and GPU runtime load).
+    pub(crate) cli_runs: bool,
+    pub(crate) model: String,
+    pub(crate) model_path: Option<String>,
+    pub(crate) error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct SetupStatus {
+    pub(crate) microphone_ready: bool,
+    pub(crate) whisper_cli_ready: bool,
+    pub(crate) model_ready: bool,
+    pub(crate) model: String,
+    pub(crate) hotkey_ready: bool,
+    /// The hotkey the current capture mode needs; empty in VAD mode.
+    pub(crate) hotkey: String,
+    pub(crate) complete: bool,
+    /// What is still missing, in wizard order.
+    pub(crate) issues: Vec<String>,
+}
+
+fn settings_snapshot(app: &AppHandle) -> Settings {
+    app.state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+fn peak_i16(samples: &[i16]) -> f32 {
+    samples
+        .iter()
+        .map(|sample| sample.unsigned_abs() as f32 / i16::MAX as f32)
+        .fold(0.0, f32::max)
+        .min(1.0)
+}
+
+fn write_mic_test_wav(path: &Path, samples: &[i16]) -> Result<(), String> {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: TARGET_SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::create(path, spec)
+        .map_err(|e| format!("Failed to create mic test WAV: {}", e))?;
+    for &sample in samples {
+        writer
+            .write_sample(sample)
+            .map_err(|e| format!("Failed to write mic test WAV: {}", e))?;
+    }
+    writer
+        .finalize()
+        .map_err(|e| format!("Failed to finalize mic test WAV: {}", e))
+}
+
+/// Records three seconds from the configured microphone and reports its level.
+#[tauri::command]
+pub(crate) async fn run_mic_test(app: AppHandle) -> CommandResult<MicTestResult> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let settings = settings_snapshot(&app);
+        let samples = crate::audio::record_mic_sample(&settings, MIC_TEST_DURATION)
+            .with_code(ErrorCode::AudioDevice)?;
+        if samples.is_empty() {
+            return Err(CommandError::new(
+                ErrorCode::AudioDevice,
+                "The microphone delivered no audio",
+            ));
+        }
+        let path = crate::paths::resolve_base_dir(&app).join(MIC_TEST_FILE);
+        write_mic_test_wav(&path, &samples).with_code(ErrorCode::Storage)?;
+
+        let rms = crate::transcription::rms_i16(&samples);
+        let result = MicTestResult {
+            rms,
+            peak: peak_i16(&samples),
+            duration_ms: samples.len() as u64 * 1000 / TARGET_SAMPLE_RATE as u64,
+            silent: rms < MIC_TEST_SILENCE_RMS,
+            playback_path: path.to_string_lossy().to_string(),
+        };
+        info!(
+            "[setup] mic test: rms={:.4}, peak={:.3}, silent={}",
+            result.rms, result.peak, result.silent
+        );
+        Ok(result)
+    })
+    .await
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("run_mic_test task failed: {}", e),
+        )
+    })?
+}
+
+/// Runs `whisper-cli --help`, which fails fast when DLLs or the GPU runtime
+/// are missing even though the binary itself exists.
+fn probe_whisper_cli(path: &Path) -> Result<(), String> {
+    let mut cmd = Command::new(path);
+    cmd.arg("--help")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    crate::apply_hidden_creation_flags(&mut cmd);
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| format!("Failed to start whisper-cli: {}", e))?;
+    let started = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() => return Ok(()),
+            Ok(Some(status)) => return Err(format!("whisper-cli exited with {}", status)),
+            Ok(None) if started.elapsed() >= WHISPER_PROBE_TIMEOUT => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err("whisper-cli did not respond".to_string());
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+            Err(e) => return Err(format!("Failed while whisper-cli was running: {}", e)),
+        }
+    }
+}
+
+/// Checks that local transcription can actually run with the current settings.
+#[tauri::command]
+pub(crate) async fn verify_whisper_runtime(app: AppHandle) -> WhisperRuntimeCheck {
+    let fallback_model = settings_snapshot(&app).model;
+    tauri::async_runtime::spawn_blocking(move || {
+        let settings = settings_snapshot(&app);
+        let cli_path = crate::paths::resolve_whisper_cli_path_for_backend(Some(
+            settings.local_backend_preference.as_str(),
+        ));
+        let model_path = crate::models::resolve_model_path(&app, &settings.model);
+
+        let mut error = None;
+        let cli_runs = match cli_path.as_deref() {
+            Some(path) => match probe_whisper_cli(path) {
+                Ok(()) => true,
+                Err(err) => {
+                    warn!("[setup] whisper runtime check failed: {}", err);
+                    error = Some(err);
+                    false
+                }
+            },
+            None => {
+                error = Some("whisper-cli was not found next to the app".to_string());
+                false
+            }
+        };
+        if error.is_none() && model_path.is_none() {
+            error = Some(format!("Model '{}' is not downloaded yet", settings.model));
+        }
+
+        WhisperRuntimeCheck {
+            cli_path: cli_path.map(|path| path.to_string_lossy().to_string()),
+            cli_runs,
+            model: settings.model,
+            model_path: model_path.map(|path| path.to_string_lossy().to_string()),
+            error,
+        }
+    })
+    .await
+    .unwrap_or_else(|e| WhisperRuntimeCheck {
+        cli_path: None,
+        cli_runs: false,
+        model: fallback_model,
+        model_path: None,
+        error: Some(format!("verify_whisper_runtime task failed: {}", e)),
+    })
+}
+
+/// The hotkey a capture mode cannot work without (none for VAD or wakeword).
+fn required_hotkey(settings: &Settings) -> &str {
+    match settings.mode.as_str() {
+        "ptt" => settings.hotkey_ptt.trim(),
+        _ => "",
+    }
+}
+
+fn hotkey_ready(app: &AppHandle, hotkey: &str) -> bool {
+    if hotkey.is_empty() || crate::mouse_hotkeys::is_mouse_binding(hotkey) {
+        return true;
+    }
+    crate::hotkeys::validate_hotkey_format(hotkey).valid
+        && app.global_shortcut().is_registered(hotkey)
+}
+
+/// What the setup wizard still has to fix. Cheap enough to poll.
+#[tauri::command]
+pub(crate) async fn setup_status(app: AppHandle) -> CommandResult<SetupStatus> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let settings = settings_snapshot(&app);
+        let microphone_ready = crate::audio::input_device_available(&settings.input_device);
+        let whisper_cli_ready = crate::paths::resolve_whisper_cli_path_for_backend(Some(
+            settings.local_backend_preference.as_str(),
+        ))
+        .is_some();
+        let model_ready = crate::models::resolve_model_path(&app, &settings.model).is_some();
+        let hotkey = required_hotkey(&settings).to_string();
+        let hotkey_ready = hotkey_ready(&app, &hotkey);
+
+        let mut issues = Vec::new();
+        if !microphone_ready {
+            issues.push("No microphone found.".to_string());
+        }
+        if !whisper_cli_ready {
+            issues.push("Local transcription runtime (whisper-cli) is missing.".to_string());
+        }
+        if !model_ready {
+            issues.push(format!("Model '{}' is not downloaded yet.", settings.model));
+        }
+        if !hotkey_ready {
+            issues.push(format!("Hotkey '{}' could not be registered.", hotkey));
+        }
+
+        Ok(SetupStatus {
+            microphone_ready,
+            whisper_cli_ready,
+            model_ready,
+            model: settings.model,
+            hotkey_ready,
+            hotkey,
+            complete: issues.is_empty(),
+            issues,
+        })
+    })
+    .await
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("setup_status task failed: {}", e),
+        )
+    })?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn required_hotkey_follows_capture_mode() {
+        let mut settings = Settings::default();
+        settings.mode = "ptt".to_string();
+        assert_eq!(required_hotkey(&settings), settings.hotkey_ptt);
+        settings.mode = "vad".to_string();
+        assert_eq!(required_hotkey(&settings), "");
+    }
+
+    #[test]
+    fn peak_is_normalised_and_handles_i16_min() {
+        assert_eq!(peak_i16(&[]), 0.0);
+        assert_eq!(peak_i16(&[0, i16::MIN, 100]), 1.0);
+        assert!((peak_i16(&[i16::MAX / 2]) - 0.5).abs() < 0.001);
+    }
+}
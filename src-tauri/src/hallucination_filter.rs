@@ -0,0 +1,407 @@
+//! Hallucination filter: phrases Whisper produces from silence, noise or
+//! background audio, kept per language in settings, plus an "always allow"
+//! list of phrases that are never dropped.
+//!
+//! The list is picked by the transcript's language — whisper's detection,
+//! else the pinned language (English when translating) — so short German
+//! dictations are not judged by English reactions and vice versa. A language
+//! without a list of its own falls back to the English one, since Whisper's
+//! stock hallucinations ("Thank you.", "you") are English whatever was said.
+//! With no known language every list applies.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
+use crate::state::{AppState, Settings};
+
+/// Language whose list also covers languages that have none.
+const FALLBACK_LANGUAGE: &str = "en";
+
+/// Shipped English list; short reactions background audio is full of.
+const DEFAULT_PHRASES_EN: &[&str] = &[
+    // Filler sounds / acknowledgements
+    "uh",
+    "um",
+    "hmm",
+    "huh",
+    "ah",
+    "oh",
+    "uh huh",
+    // Single-word reactions
+    "yes",
+    "no",
+    "okay",
+    "ok",
+    "yeah",
+    "right",
+    "sure",
+    "fine",
+    "good",
+    "great",
+    "nice",
+    "wow",
+    "cool",
+    "really",
+    "exactly",
+    "absolutely",
+    "definitely",
+    "correct",
+    "true",
+    "hey",
+    "hi",
+    "hello",
+    "bye",
+    "goodbye",
+    "welcome",
+    "please",
+    "wait",
+    "sorry",
+    // Gratitude / social phrases
+    "you",
+    "thank you",
+    "thanks",
+    // Two-word phrases common in background audio
+    "all right",
+    "alright",
+    "oh no",
+    "oh yeah",
+    "oh well",
+    "oh wow",
+    "oh my",
+    "come on",
+    "go on",
+    "hold on",
+    "i see",
+    "me too",
+    "of course",
+    "no no",
+    "yes yes",
+    "good job",
+    "well done",
+    "no problem",
+    "no worries",
+    "for sure",
+    "see ya",
+    "take care",
+    "good luck",
+    "good night",
+    "good morning",
+    "thats right",
+    "youre right",
+    "youre welcome",
+    "not bad",
+];
+
+/// Shipped German list: fillers and the credits Whisper learned from
+/// subtitled broadcasts. Everyday answers ("ja", "nein") are left out.
+const DEFAULT_PHRASES_DE: &[&str] = &[
+    "äh",
+    "ähm",
+    "hm",
+    "hmm",
+    "mhm",
+    "tschüss",
+    "danke fürs zuschauen",
+    "vielen dank fürs zuschauen",
+    "bis zum nächsten mal",
+    "untertitel der amara.org-community",
+    "untertitel im auftrag des zdf",
+    "untertitel im auftrag des zdf für funk 2017",
+    "untertitelung des zdf 2020",
+    "copyright wdr 2021",
+];
+
+pub(crate) fn default_hallucination_phrases() -> HashMap<String, Vec<String>> {
+    [("en", DEFAULT_PHRASES_EN), ("de", DEFAULT_PHRASES_DE)]
+        .into_iter()
+        .map(|(language, phrases)| {
+            (
+                language.to_string(),
+                phrases.iter().map(|phrase| phrase.to_string()).collect(),
+            )
+        })
+        .collect()
+}
+
+/// Lower-cased, punctuation-free form both phrases and transcripts are
+/// compared in ("Thank you." == "thank you").
+pub(crate) fn normalize_phrase(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn push_unique(list: &mut Vec<String>, phrase: &str) -> bool {
+    let phrase = phrase.trim();
+    let key = normalize_phrase(phrase);
+    if key.is_empty()
+        || list
+            .iter()
+            .any(|existing| normalize_phrase(existing) == key)
+    {
+        return false;
+    }
+    list.push(phrase.to_string());
+    true
+}
+
+fn normalize_list(list: &[String]) -> Vec<String> {
+    let mut cleaned = Vec::new();
+    for phrase in list {
+        push_unique(&mut cleaned, phrase);
+    }
+    cleaned
+}
+
+/// Settings sanitation: language keys become whisper codes (unknown keys
+/// are dropped), phrases are trimmed and de-duplicated.
+pub(crate) fn normalize_hallucination_fields(settings: &mut Settings) {
+    let mut phrases: HashMap<String, Vec<String>> = HashMap::new();
+    for (language, list) in &settings.hallucination_phrases {
+        if let Some(code) = crate::detected_language::normalize_language_code(language) {
+            let merged = phrases.entry(code).or_default();
+            for phrase in list {
+                push_unique(merged, phrase);
+            }
+        }
+    }
+    settings.hallucination_phrases = phrases;
+    settings.hallucination_allowed_phrases =
+        normalize_list(&settings.hallucination_allowed_phrases);
+}
+
+/// Phrase lists resolved for one transcript.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct HallucinationFilter {
+    phrases: Vec<String>,
+    allowed: Vec<String>,
+}
+
+impl HallucinationFilter {
+    /// Lists for a transcript in `language` (whisper code, when detected).
+    pub(crate) fn for_settings(settings: &Settings, language: Option<&str>) -> Self {
+        let language = if settings.translate_to_english {
+            Some("en".to_string())
+        } else {
+            language.map(str::to_string).or_else(|| {
+                settings
+                    .language_pinned
+                    .then(|| {
+                        crate::detected_language::normalize_language_code(&settings.language_mode)
+                    })
+                    .flatten()
+            })
+        };
+        let phrases: Vec<&String> = if !settings.hallucination_filter_enabled {
+            Vec::new()
+        } else {
+            match &language {
+                Some(code) => settings
+                    .hallucination_phrases
+                    .get(code)
+                    .or_else(|| settings.hallucination_phrases.get(FALLBACK_LANGUAGE))
+                    .map(|list| list.iter().collect())
+                    .unwrap_or_default(),
+                None => settings.hallucination_phrases.values().flatten().collect(),
+            }
+        };
+        Self {
+            phrases: phrases
+                .into_iter()
+                .map(|phrase| normalize_phrase(phrase))
+                .collect(),
+            allowed: settings
+                .hallucination_allowed_phrases
+                .iter()
+                .map(|phrase| normalize_phrase(phrase))
+                .collect(),
+        }
+    }
+
+    /// `normalized` is a known hallucination phrase.
+    pub(crate) fn matches(&self, normalized: &str) -> bool {
+        self.phrases.iter().any(|phrase| phrase == normalized)
+    }
+
+    /// `normalized` is on the always-allow list.
+    pub(crate) fn allows(&self, normalized: &str) -> bool {
+        self.allowed.iter().any(|phrase| phrase == normalized)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct HallucinationPhraseLists {
+    /// Phrases per whisper language code.
+    pub(crate) phrases: HashMap<String, Vec<String>>,
+    pub(crate) allowed: Vec<String>,
+}
+
+fn current_settings(app: &AppHandle) -> Settings {
+    app.state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+fn phrase_lists(settings: &Settings) -> HallucinationPhraseLists {
+    HallucinationPhraseLists {
+        phrases: settings.hallucination_phrases.clone(),
+        allowed: settings.hallucination_allowed_phrases.clone(),
+    }
+}
+
+/// Apply `edit` to a copy of the settings and save it.
+async fn edit_lists(
+    app: AppHandle,
+    edit: impl FnOnce(&mut Settings) -> CommandResult<()> + Send + 'static,
+) -> CommandResult<HallucinationPhraseLists> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut settings = current_settings(&app);
+        edit(&mut settings)?;
+        crate::save_settings_inner(&app, &mut settings).with_code(ErrorCode::Storage)?;
+        Ok(phrase_lists(&settings))
+    })
+    .await
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("hallucination phrase task failed: {}", e),
+        )
+    })?
+}
+
+fn language_code(language: &str) -> Result<String, String> {
+    crate::detected_language::normalize_language_code(language)
+        .ok_or_else(|| format!("Unknown language '{}'", language.trim()))
+}
+
+#[tauri::command]
+pub(crate) fn list_hallucination_phrases(app: AppHandle) -> HallucinationPhraseLists {
+    phrase_lists(&current_settings(&app))
+}
+
+#[tauri::command]
+pub(crate) async fn add_hallucination_phrase(
+    app: AppHandle,
+    language: String,
+    phrase: String,
+) -> CommandResult<HallucinationPhraseLists> {
+    let code = language_code(&language).with_code(ErrorCode::InvalidInput)?;
+    edit_lists(app, move |settings| {
+        let list = settings.hallucination_phrases.entry(code).or_default();
+        if !push_unique(list, &phrase) {
+            return Err(CommandError::new(
+                ErrorCode::InvalidInput,
+                format!("'{}' is empty or already listed", phrase.trim()),
+            ));
+        }
+        Ok(())
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn remove_hallucination_phrase(
+    app: AppHandle,
+    language: String,
+    phrase: String,
+) -> CommandResult<HallucinationPhraseLists> {
+    let code = language_code(&language).with_code(ErrorCode::InvalidInput)?;
+    edit_lists(app, move |settings| {
+        let key = normalize_phrase(&phrase);
+        let list = settings.hallucination_phrases.entry(code).or_default();
+        let before = list.len();
+        list.retain(|existing| normalize_phrase(existing) != key);
+        if list.len() == before {
+            return Err(CommandError::new(
+                ErrorCode::NotFound,
+                format!("'{}' is not listed", phrase.trim()),
+            ));
+        }
+        Ok(())
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn add_allowed_phrase(
+    app: AppHandle,
+    phrase: String,
+) -> CommandResult<HallucinationPhraseLists> {
+    edit_lists(app, move |settings| {
+        if !push_unique(&mut settings.hallucination_allowed_phrases, &phrase) {
+            return Err(CommandError::new(
+                ErrorCode::InvalidInput,
+                format!("'{}' is empty or already allowed", phrase.trim()),
+            ));
+        }
+        Ok(())
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn remove_allowed_phrase(
+    app: AppHandle,
+    phrase: String,
+) -> CommandResult<HallucinationPhraseLists> {
+    edit_lists(app, move |settings| {
+        let key = normalize_phrase(&phrase);
+        let before = settings.hallucination_allowed_phrases.len();
+        settings
+            .hallucination_allowed_phrases
+            .retain(|existing| normalize_phrase(existing) != key);
+        if settings.hallucination_allowed_phrases.len() == before {
+            return Err(CommandError::new(
+                ErrorCode::NotFound,
+                format!("'{}' is not on the allow list", phrase.trim()),
+            ));
+        }
+        Ok(())
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_list_by_language_and_honors_allow_list() {
+        let mut settings = Settings::default();
+        let german = HallucinationFilter::for_settings(&settings, Some("de"));
+        assert!(german.matches(&normalize_phrase("Untertitel der Amara.org-Community")));
+        assert!(!german.matches("okay"));
+        assert!(HallucinationFilter::for_settings(&settings, Some("en")).matches("thank you"));
+        // No French list: the English one stands in.
+        assert!(HallucinationFilter::for_settings(&settings, Some("fr")).matches("thank you"));
+        assert!(!HallucinationFilter::for_settings(&settings, Some("fr")).matches("ähm"));
+        // Unknown language: every list applies.
+        assert!(HallucinationFilter::for_settings(&settings, None).matches("ähm"));
+
+        settings.translate_to_english = true;
+        assert!(HallucinationFilter::for_settings(&settings, Some("de")).matches("okay"));
+
+        settings.hallucination_phrases = HashMap::from([(
+            "German".to_string(),
+            vec![" Tschüss ".to_string(), "tschüss!".to_string()],
+        )]);
+        settings.hallucination_allowed_phrases =
+            vec!["Ja genau".to_string(), "ja, genau".to_string()];
+        normalize_hallucination_fields(&mut settings);
+        assert_eq!(settings.hallucination_phrases["de"], vec!["Tschüss"]);
+        assert_eq!(settings.hallucination_allowed_phrases, vec!["Ja genau"]);
+        assert!(HallucinationFilter::for_settings(&settings, Some("de")).allows("ja genau"));
+
+        settings.hallucination_filter_enabled = false;
+        assert!(!HallucinationFilter::for_settings(&settings, None).matches("tschüss"));
+    }
+}
@@ -0,0 +1,297 @@
+//! Hardware probe (CPU features, GPU, VRAM) and the model recommendation
+//! used on first run.
+
+use crate::state::Settings;
+use serde::Serialize;
+use std::process::Command;
+use std::sync::OnceLock;
+use tracing::info;
+
+/// Layer count the settings default treats as "offload everything".
+const FULL_GPU_LAYERS: usize = 35;
+const LARGE_V3_MIN_VRAM_MB: u64 = 6 * 1024;
+const TURBO_FULL_OFFLOAD_MIN_VRAM_MB: u64 = 3 * 1024;
+const TURBO_PARTIAL_OFFLOAD_MIN_VRAM_MB: u64 = 2 * 1024;
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct HardwareProfile {
+    pub(crate) cpu_cores: usize,
+    pub(crate) avx: bool,
+    pub(crate) avx2: bool,
+    pub(crate) avx512: bool,
+    pub(crate) gpu_name: Option<String>,
+    /// "nvidia" | "amd" | "intel" | "other"
+    pub(crate) gpu_vendor: Option<String>,
+    pub(crate) vram_mb: Option<u64>,
+    pub(crate) cuda_available: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ModelRecommendation {
+    pub(crate) model_id: String,
+    pub(crate) gpu_layers: Option<usize>,
+    /// Value for `local_backend_preference`: "cuda" | "vulkan" | "auto".
+    pub(crate) backend: String,
+    pub(crate) reason: String,
+    pub(crate) profile: HardwareProfile,
+}
+
+fn gpu_vendor(name: &str) -> &'static str {
+    let lower = name.to_ascii_lowercase();
+    if lower.contains("nvidia") || lower.contains("geforce") || lower.contains("quadro") {
+        "nvidia"
+    } else if lower.contains("amd") || lower.contains("radeon") {
+        "amd"
+    } else if lower.contains("intel") {
+        "intel"
+    } else {
+        "other"
+    }
+}
+
+fn hidden_command(program: &str) -> Command {
+    #[allow(unused_mut)]
+    let mut cmd = Command::new(program);
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+    cmd
+}
+
+/// Name and total VRAM of the first NVIDIA GPU, via nvidia-smi.
+fn query_nvidia_smi() -> Option<(String, u64)> {
+    let output = hidden_command("nvidia-smi")
+        .args([
+            "--query-gpu=name,memory.total",
+            "--format=csv,noheader,nounits",
+        ])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_nvidia_smi_line(String::from_utf8_lossy(&output.stdout).lines().next()?)
+}
+
+fn parse_nvidia_smi_line(line: &str) -> Option<(String, u64)> {
+    let (name, total) = line.rsplit_once(',')?;
+    let total_mb = total.trim().parse::<f64>().ok()?;
+    Some((name.trim().to_string(), total_mb as u64))
+}
+
+fn probe_cpu(profile: &mut HardwareProfile) {
+    profile.cpu_cores = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        profile.avx = std::arch::is_x86_feature_detected!("avx");
+        profile.avx2 = std::arch::is_x86_feature_detected!("avx2");
+        profile.avx512 = std::arch::is_x86_feature_detected!("avx512f");
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn probe_gpu_platform(profile: &mut HardwareProfile) {
+    use windows::Win32::Graphics::Dxgi::{CreateDXGIFactory1, IDXGIFactory1};
+
+    // Pick the adapter with the most dedicated memory; that is the one whisper uses.
+    if let Ok(factory) = unsafe { CreateDXGIFactory1::<IDXGIFactory1>() } {
+        let mut index = 0;
+        while let Ok(adapter) = unsafe { factory.EnumAdapters1(index) } {
+            index += 1;
+            let Ok(desc) = (unsafe { adapter.GetDesc1() }) else {
+                continue;
+            };
+            let name = String::from_utf16_lossy(&desc.Description)
+                .trim_matches(char::from(0))
+                .trim()
+                .to_string();
+            let vram_mb = (desc.DedicatedVideoMemory / (1024 * 1024)) as u64;
+            if vram_mb > profile.vram_mb.unwrap_or(0) {
+                profile.gpu_vendor = Some(gpu_vendor(&name).to_string());
+                profile.gpu_name = Some(name);
+                profile.vram_mb = Some(vram_mb);
+            }
+        }
+    }
+    profile.cuda_available = std::path::Path::new("C:\\Windows\\System32\\nvcuda.dll").exists();
+}
+
+#[cfg(target_os = "linux")]
+fn probe_gpu_platform(profile: &mut HardwareProfile) {
+    // AMD exposes VRAM size through amdgpu sysfs nodes.
+    let Ok(entries) = std::fs::read_dir("/sys/class/drm") else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let device = entry.path().join("device");
+        let vendor = std::fs::read_to_string(device.join("vendor")).unwrap_or_default();
+        if vendor.trim() != "0x1002" {
+            continue;
+        }
+        let Some(vram_mb) = std::fs::read_to_string(device.join("mem_info_vram_total"))
+            .ok()
+            .and_then(|raw| raw.trim().parse::<u64>().ok())
+            .map(|bytes| bytes / (1024 * 1024))
+        else {
+            continue;
+        };
+        if vram_mb > profile.vram_mb.unwrap_or(0) {
+            profile.gpu_name = Some("AMD Radeon".to_string());
+            profile.gpu_vendor = Some("amd".to_string());
+            profile.vram_mb = Some(vram_mb);
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+fn probe_gpu_platform(_profile: &mut HardwareProfile) {}
+
+fn probe_uncached() -> HardwareProfile {
+    let mut profile = HardwareProfile::default();
+    probe_cpu(&mut profile);
+    probe_gpu_platform(&mut profile);
+    // nvidia-smi reports VRAM more reliably than DXGI for cards above 4 GB.
+    if let Some((name, vram_mb)) = query_nvidia_smi() {
+        profile.gpu_vendor = Some(gpu_vendor(&name).to_string());
+        profile.gpu_name = Some(name);
+        profile.vram_mb = Some(vram_mb);
+        profile.cuda_available = true;
+    }
+    info!("Hardware probe: {:?}", profile);
+    profile
+}
+
+/// Probes once per process; hardware does not change while the app runs.
+pub(crate) fn probe_hardware() -> HardwareProfile {
+    static PROFILE: OnceLock<HardwareProfile> = OnceLock::new();
+    PROFILE.get_or_init(probe_uncached).clone()
+}
+
+pub(crate) fn recommend_for(profile: &HardwareProfile) -> ModelRecommendation {
+    let usable_gpu = match profile.gpu_vendor.as_deref() {
+        Some("nvidia") if profile.cuda_available => Some("cuda"),
+        Some("nvidia") | Some("amd") | Some("intel") => Some("vulkan"),
+        _ => None,
+    };
+    let vram_mb = profile.vram_mb.unwrap_or(0);
+
+    let (model_id, gpu_layers, backend, reason) = match usable_gpu {
+        Some(backend) if vram_mb >= LARGE_V3_MIN_VRAM_MB => (
+            "whisper-large-v3",
+            Some(FULL_GPU_LAYERS),
+            backend,
+            format!("{} MB VRAM fits large-v3 fully on the GPU", vram_mb),
+        ),
+        Some(backend) if vram_mb >= TURBO_FULL_OFFLOAD_MIN_VRAM_MB => (
+            "whisper-large-v3-turbo",
+            Some(FULL_GPU_LAYERS),
+            backend,
+            format!("{} MB VRAM fits large-v3-turbo fully on the GPU", vram_mb),
+        ),
+        Some(backend) if vram_mb >= TURBO_PARTIAL_OFFLOAD_MIN_VRAM_MB => (
+            "whisper-large-v3-turbo",
+            Some(FULL_GPU_LAYERS / 2),
+            backend,
+            format!("{} MB VRAM: offloading half of large-v3-turbo", vram_mb),
+        ),
+        _ => {
+            let mut reason = format!(
+                "No GPU with enough VRAM; large-v3-turbo on {} CPU threads",
+                profile.cpu_cores
+            );
+            if !profile.avx2 {
+                reason.push_str(" (no AVX2, expect slow transcription)");
+            }
+            ("whisper-large-v3-turbo", Some(0), "auto", reason)
+        }
+    };
+
+    ModelRecommendation {
+        model_id: model_id.to_string(),
+        gpu_layers,
+        backend: backend.to_string(),
+        reason,
+        profile: profile.clone(),
+    }
+}
+
+/// Applies the recommendation to fresh settings (no settings file yet).
+pub(crate) fn apply_first_run_recommendation(settings: &mut Settings) {
+    let recommendation = recommend_for(&probe_hardware());
+    info!(
+        "First run: recommending {} ({})",
+        recommendation.model_id, recommendation.reason
+    );
+    settings.model = recommendation.model_id;
+    settings.whisper_gpu_layers = recommendation.gpu_layers;
+    settings.local_backend_preference = recommendation.backend;
+}
+
+#[tauri::command]
+pub(crate) fn get_hardware_profile() -> HardwareProfile {
+    probe_hardware()
+}
+
+#[tauri::command]
+pub(crate) fn recommend_model() -> ModelRecommendation {
+    recommend_for(&probe_hardware())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gpu(vendor: &str, vram_mb: u64, cuda: bool) -> HardwareProfile {
+        HardwareProfile {
+            cpu_cores: 8,
+            avx: true,
+            avx2: true,
+            gpu_name: Some("Test GPU".to_string()),
+            gpu_vendor: Some(vendor.to_string()),
+            vram_mb: Some(vram_mb),
+            cuda_available: cuda,
+            ..HardwareProfile::default()
+        }
+    }
+
+    #[test]
+    fn recommends_by_vram_and_backend() {
+        let big = recommend_for(&gpu("nvidia", 12 * 1024, true));
+        assert_eq!(big.model_id, "whisper-large-v3");
+        assert_eq!(big.backend, "cuda");
+        assert_eq!(big.gpu_layers, Some(FULL_GPU_LAYERS));
+
+        let mid = recommend_for(&gpu("amd", 4 * 1024, false));
+        assert_eq!(mid.model_id, "whisper-large-v3-turbo");
+        assert_eq!(mid.backend, "vulkan");
+
+        let small = recommend_for(&gpu("intel", 2048, false));
+        assert_eq!(small.gpu_layers, Some(FULL_GPU_LAYERS / 2));
+    }
+
+    #[test]
+    fn cpu_only_gets_turbo_without_offload() {
+        let rec = recommend_for(&HardwareProfile {
+            cpu_cores: 4,
+            ..HardwareProfile::default()
+        });
+        assert_eq!(rec.model_id, "whisper-large-v3-turbo");
+        assert_eq!(rec.gpu_layers, Some(0));
+        assert_eq!(rec.backend, "auto");
+        assert!(rec.reason.contains("no AVX2"));
+    }
+
+    #[test]
+    fn parses_nvidia_smi_output() {
+        assert_eq!(
+            parse_nvidia_smi_line("NVIDIA GeForce RTX 4070, 12282"),
+            Some(("NVIDIA GeForce RTX 4070".to_string(), 12282))
+        );
+        assert_eq!(parse_nvidia_smi_line("garbage"), None);
+    }
+}
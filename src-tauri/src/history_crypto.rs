@@ -0,0 +1,668 @@
+//! Opt-in encryption at rest for history partitions, transcript sessions,
+//! saved recordings and kept failed-segment audio.
+//!
+//! Sealed files are `MAGIC || nonce || AES-256-GCM ciphertext`. Readers accept
+//! plaintext as well, so switching modes never strands existing data. The key
+//! lives in the OS keychain ("keychain" mode) or is derived from a passphrase
+//! with PBKDF2-SHA256 ("passphrase" mode) and only held in memory after
+//! `unlock_history`. While passphrase mode is locked, sealed files cannot be
+//! read and writes fail instead of overwriting them with plaintext.
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tracing::{info, warn};
+
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
+use crate::state::AppState;
+
+const MAGIC: &[u8] = b"TRISPR-ENC1\n";
+const NONCE_LEN: usize = 12;
+const KEYRING_SERVICE: &str = "com.trispr.flow.history";
+const KEYRING_USER: &str = "history-key";
+const PASSPHRASE_FILE: &str = "history_crypto.json";
+const MIGRATION_FILE: &str = "history_crypto_migration.json";
+/// Appended to a protected file's name for its re-sealed copy during a switch.
+const STAGED_SUFFIX: &str = "reseal";
+const PBKDF2_ROUNDS: u32 = if cfg!(test) { 1_000 } else { 600_000 };
+const MIN_PASSPHRASE_LEN: usize = 8;
+/// Sealed with the derived key so a wrong passphrase is rejected up front.
+const KEY_CHECK_PLAINTEXT: &[u8] = b"trispr-history-key-check";
+const LOCKED_ERROR: &str = "History is locked; unlock it with your passphrase";
+const RECORDING_EXTENSIONS: [&str; 2] = ["opus", "wav"];
+
+type HistoryKey = [u8; 32];
+
+#[derive(Default)]
+struct CryptoState {
+    enabled: bool,
+    key: Option<HistoryKey>,
+}
+
+fn crypto_state() -> MutexGuard<'static, CryptoState> {
+    static STATE: OnceLock<Mutex<CryptoState>> = OnceLock::new();
+    STATE
+        .get_or_init(|| Mutex::new(CryptoState::default()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PassphraseParams {
+    salt: String,
+    check: String,
+}
+
+/// A mode switch whose re-sealed copies are all staged. Once this is on disk
+/// the switch is committed; `apply_migration` finishes it, at the next start
+/// if the running app could not.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PendingMigration {
+    mode: String,
+    previous: String,
+    params: Option<PassphraseParams>,
+    committed_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct HistoryEncryptionStatus {
+    /// "off" | "keychain" | "passphrase"
+    pub(crate) mode: String,
+    pub(crate) locked: bool,
+}
+
+fn is_sealed(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+fn seal_with(key: &HistoryKey, plaintext: &[u8]) -> Result<Vec<u8>, String> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|_| "Failed to encrypt history data".to_string())?;
+    let mut out = Vec::with_capacity(MAGIC.len() + NONCE_LEN + ciphertext.len());
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+fn open_with(key: &HistoryKey, data: &[u8]) -> Result<Vec<u8>, String> {
+    let body = data
+        .strip_prefix(MAGIC)
+        .filter(|body| body.len() > NONCE_LEN)
+        .ok_or_else(|| "Not an encrypted history file".to_string())?;
+    let (nonce, ciphertext) = body.split_at(NONCE_LEN);
+    Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key))
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| "Failed to decrypt history data (wrong key?)".to_string())
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> HistoryKey {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, PBKDF2_ROUNDS, &mut key);
+    key
+}
+
+/// Contents of `path`, decrypted when sealed.
+pub(crate) fn read_file(path: &Path) -> Result<Vec<u8>, String> {
+    let data = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    if !is_sealed(&data) {
+        return Ok(data);
+    }
+    let key = crypto_state().key.ok_or(LOCKED_ERROR)?;
+    open_with(&key, &data)
+}
+
+pub(crate) fn read_to_string(path: &Path) -> Result<String, String> {
+    String::from_utf8(read_file(path)?).map_err(|e| e.to_string())
+}
+
+/// Bytes to store for `plaintext`: sealed when encryption is on.
+pub(crate) fn seal(plaintext: &[u8]) -> Result<Vec<u8>, String> {
+    let state = crypto_state();
+    if !state.enabled {
+        return Ok(plaintext.to_vec());
+    }
+    let key = state.key.ok_or(LOCKED_ERROR)?;
+    seal_with(&key, plaintext)
+}
+
+fn write_atomic(path: &Path, data: &[u8]) -> Result<(), String> {
+    let tmp_path = path.with_extension("enc.tmp");
+    fs::write(&tmp_path, data).map_err(|e| e.to_string())?;
+    fs::rename(&tmp_path, path).map_err(|e| e.to_string())
+}
+
+/// Seal a freshly written recording when encryption is on.
+pub(crate) fn seal_recording(path: &Path) {
+    if !crypto_state().enabled {
+        return;
+    }
+    let result = fs::read(path).map_err(|e| e.to_string()).and_then(|data| {
+        if is_sealed(&data) {
+            return Ok(());
+        }
+        write_atomic(path, &seal(&data)?)
+    });
+    if let Err(err) = result {
+        warn!("Failed to encrypt recording {}: {}", path.display(), err);
+    }
+}
+
+/// Decrypted copies live in the scratch dir, which startup empties.
+fn decrypted_dir() -> PathBuf {
+    crate::scratch::scratch_dir().join("decrypted")
+}
+
+/// A readable recording: the file itself, or a decrypted temporary copy of a
+/// sealed one that is deleted on drop.
+pub(crate) struct PlaintextRecording {
+    path: PathBuf,
+    temporary: bool,
+}
+
+impl PlaintextRecording {
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for PlaintextRecording {
+    fn drop(&mut self) {
+        if self.temporary {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// A readable copy of a possibly sealed recording, for the audio player and
+/// the opus sidecar. Plain files are returned unchanged.
+pub(crate) fn plaintext_recording(path: &Path) -> Result<PlaintextRecording, String> {
+    static NEXT_COPY_ID: AtomicU64 = AtomicU64::new(0);
+    let mut head = vec![0u8; MAGIC.len()];
+    let sealed = fs::File::open(path)
+        .and_then(|mut file| file.read_exact(&mut head))
+        .map(|_| is_sealed(&head))
+        .unwrap_or(false);
+    if !sealed {
+        return Ok(PlaintextRecording {
+            path: path.to_path_buf(),
+            temporary: false,
+        });
+    }
+    let dir = decrypted_dir();
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| format!("Invalid recording path: {}", path.display()))?;
+    let copy_id = NEXT_COPY_ID.fetch_add(1, Ordering::Relaxed);
+    let out = PlaintextRecording {
+        path: dir.join(format!("{}_{}", copy_id, file_name.to_string_lossy())),
+        temporary: true,
+    };
+    fs::write(&out.path, read_file(path)?)
+        .map_err(|e| format!("Failed to decrypt recording: {}", e))?;
+    Ok(out)
+}
+
+/// Like `plaintext_recording`, but the copy outlives the call for the audio
+/// player; only the most recent one is kept.
+pub(crate) fn plaintext_recording_for_player(path: &Path) -> Result<PathBuf, String> {
+    static PLAYER_COPY: Mutex<Option<PlaintextRecording>> = Mutex::new(None);
+    let recording = plaintext_recording(path)?;
+    let playable = recording.path().to_path_buf();
+    *PLAYER_COPY
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(recording);
+    Ok(playable)
+}
+
+fn keychain_entry() -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+        .map_err(|e| format!("Failed to create keyring entry: {}", e))
+}
+
+fn keychain_key(create: bool) -> Result<Option<HistoryKey>, String> {
+    let entry = keychain_entry()?;
+    match entry.get_password() {
+        Ok(encoded) => {
+            let bytes = BASE64
+                .decode(encoded.trim())
+                .map_err(|e| format!("Invalid history key in keychain: {}", e))?;
+            let key: HistoryKey = bytes
+                .try_into()
+                .map_err(|_| "Invalid history key length in keychain".to_string())?;
+            Ok(Some(key))
+        }
+        Err(keyring::Error::NoEntry) if create => {
+            let key: HistoryKey = Aes256Gcm::generate_key(&mut OsRng).into();
+            entry
+                .set_password(&BASE64.encode(key))
+                .map_err(|e| format!("Failed to store history key in keychain: {}", e))?;
+            Ok(Some(key))
+        }
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(err) => Err(format!("Failed to read history key from keychain: {}", err)),
+    }
+}
+
+fn passphrase_params_path(app: &AppHandle) -> PathBuf {
+    crate::paths::resolve_config_path(app, PASSPHRASE_FILE)
+}
+
+fn load_passphrase_params(app: &AppHandle) -> Result<PassphraseParams, String> {
+    let raw = fs::read_to_string(passphrase_params_path(app))
+        .map_err(|e| format!("Missing history passphrase parameters: {}", e))?;
+    serde_json::from_str(&raw).map_err(|e| format!("Invalid history passphrase parameters: {}", e))
+}
+
+/// Key for `passphrase` checked against the stored parameters.
+fn unlock_key(params: &PassphraseParams, passphrase: &str) -> Result<HistoryKey, String> {
+    let salt = BASE64.decode(&params.salt).map_err(|e| e.to_string())?;
+    let check = BASE64.decode(&params.check).map_err(|e| e.to_string())?;
+    let key = derive_key(passphrase, &salt);
+    match open_with(&key, &check) {
+        Ok(plain) if plain == KEY_CHECK_PLAINTEXT => Ok(key),
+        _ => Err("Wrong history passphrase".to_string()),
+    }
+}
+
+fn new_passphrase_key(passphrase: &str) -> Result<(HistoryKey, PassphraseParams), String> {
+    if passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+        return Err(format!(
+            "Passphrase must be at least {} characters",
+            MIN_PASSPHRASE_LEN
+        ));
+    }
+    let mut salt = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+    let key = derive_key(passphrase, &salt);
+    let params = PassphraseParams {
+        salt: BASE64.encode(salt),
+        check: BASE64.encode(seal_with(&key, KEY_CHECK_PLAINTEXT)?),
+    };
+    Ok((key, params))
+}
+
+/// Call at startup, before history is loaded.
+pub(crate) fn init(mode: &str) {
+    let mut state = crypto_state();
+    *state = CryptoState::default();
+    match mode {
+        "keychain" => {
+            state.enabled = true;
+            match keychain_key(false) {
+                Ok(Some(key)) => state.key = Some(key),
+                Ok(None) => warn!("History encryption is on but no key is in the keychain"),
+                Err(err) => warn!("{}", err),
+            }
+        }
+        "passphrase" => {
+            state.enabled = true;
+            info!("History is encrypted with a passphrase; locked until unlocked");
+        }
+        _ => {}
+    }
+}
+
+fn status(mode: &str) -> HistoryEncryptionStatus {
+    let state = crypto_state();
+    HistoryEncryptionStatus {
+        mode: mode.to_string(),
+        locked: state.enabled && state.key.is_none(),
+    }
+}
+
+fn current_mode(state: &AppState) -> String {
+    state
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .history_encryption_mode
+        .clone()
+}
+
+fn collect_files(dir: &Path, extensions: &[&str], out: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            collect_files(&path, extensions, out);
+        } else if path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| extensions.contains(&ext))
+        {
+            out.push(path);
+        }
+    }
+}
+
+/// Every file the encryption mode covers, or with `staged` their re-sealed
+/// copies from an unfinished switch.
+fn protected_files(app: &AppHandle, staged: bool) -> Vec<PathBuf> {
+    let base_dir = crate::paths::resolve_base_dir(app);
+    let mut files = Vec::new();
+    for (dir, extensions) in [
+        (base_dir.join("history"), &["json"][..]),
+        (base_dir.join("transcript_sessions"), &["json"][..]),
+        (base_dir.join("recordings"), &RECORDING_EXTENSIONS[..]),
+        (base_dir.join("failed"), &["wav"][..]),
+    ] {
+        let extensions = if staged {
+            &[STAGED_SUFFIX][..]
+        } else {
+            extensions
+        };
+        collect_files(&dir, extensions, &mut files);
+    }
+    files
+}
+
+fn staged_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(STAGED_SUFFIX);
+    path.with_file_name(name)
+}
+
+/// Write the copy of `path` for the new key (`None` = plaintext) next to it.
+fn stage_reseal(path: &Path, new_key: Option<&HistoryKey>) -> Result<(), String> {
+    let plaintext = read_file(path)?;
+    let data = match new_key {
+        Some(key) => seal_with(key, &plaintext)?,
+        None => plaintext,
+    };
+    fs::write(staged_path(path), data)
+        .map_err(|e| format!("Failed to stage {}: {}", path.display(), e))
+}
+
+fn discard_staged(app: &AppHandle) {
+    for path in protected_files(app, true) {
+        let _ = fs::remove_file(path);
+    }
+}
+
+fn migration_path(app: &AppHandle) -> PathBuf {
+    crate::paths::resolve_config_path(app, MIGRATION_FILE)
+}
+
+/// Moves the staged copies over the originals and stores the new parameters.
+/// Originals written after the commit are newer than their copy and stay.
+/// Safe to run again after an interruption.
+fn apply_migration(app: &AppHandle, migration: &PendingMigration) -> Result<(), String> {
+    let params_path = passphrase_params_path(app);
+    match &migration.params {
+        Some(params) => {
+            let raw = serde_json::to_string_pretty(params).map_err(|e| e.to_string())?;
+            fs::write(&params_path, raw)
+                .map_err(|e| format!("Failed to save passphrase parameters: {}", e))?;
+        }
+        None => {
+            let _ = fs::remove_file(&params_path);
+        }
+    }
+    for staged in protected_files(app, true) {
+        let original = staged.with_extension("");
+        let written_after_commit = fs::metadata(&original)
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+            .is_some_and(|modified| modified.as_millis() as u64 > migration.committed_ms);
+        if written_after_commit {
+            let _ = fs::remove_file(&staged);
+            continue;
+        }
+        fs::rename(&staged, &original)
+            .map_err(|e| format!("Failed to replace {}: {}", original.display(), e))?;
+    }
+    if migration.mode != "keychain" && migration.previous == "keychain" {
+        if let Err(e) = keychain_entry().and_then(|entry| {
+            entry
+                .delete_password()
+                .map_err(|e| format!("Failed to remove keychain key: {}", e))
+        }) {
+            warn!("{}", e);
+        }
+    }
+    Ok(())
+}
+
+/// Finishes an encryption switch interrupted by a crash or I/O error, or
+/// drops the copies of one that never committed. Call at startup before
+/// `init`; updates and saves `settings` when a switch completes.
+pub(crate) fn resume_migration(app: &AppHandle, settings: &mut crate::state::Settings) {
+    let path = migration_path(app);
+    let Some(migration) = fs::read_to_string(&path)
+        .ok()
+        .and_then(|raw| serde_json::from_str::<PendingMigration>(&raw).ok())
+    else {
+        discard_staged(app);
+        return;
+    };
+    if let Err(err) = apply_migration(app, &migration) {
+        warn!("History encryption switch still unfinished: {}", err);
+        return;
+    }
+    settings.history_encryption_mode = migration.mode.clone();
+    if let Err(err) = crate::save_settings_file(app, settings) {
+        warn!("Failed to save history encryption mode: {}", err);
+        return;
+    }
+    let _ = fs::remove_file(&path);
+    info!(
+        "Finished switching history encryption to {}",
+        migration.mode
+    );
+}
+
+/// Reload both histories after unlocking and tell the frontend.
+fn reload_histories(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    for (history, event) in [
+        (&state.history, "history:updated"),
+        (&state.history_transcribe, "transcribe:history-updated"),
+    ] {
+        let mut ph = history
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        ph.reload_active();
+        let updated: Vec<_> = ph.active.iter().cloned().collect();
+        drop(ph);
+        let _ = app.emit(event, updated);
+    }
+    crate::session_manager::reload_transcript_sessions(app);
+}
+
+#[tauri::command]
+pub(crate) fn get_history_encryption_status(state: State<'_, AppState>) -> HistoryEncryptionStatus {
+    status(&current_mode(&state))
+}
+
+/// Unlock passphrase-encrypted history for this run.
+#[tauri::command]
+pub(crate) async fn unlock_history(
+    app: AppHandle,
+    passphrase: String,
+) -> CommandResult<HistoryEncryptionStatus> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let mode = current_mode(&app.state::<AppState>());
+        if mode != "passphrase" {
+            return Err(CommandError::new(
+                ErrorCode::InvalidInput,
+                "History is not passphrase-encrypted",
+            ));
+        }
+        let params = load_passphrase_params(&app).with_code(ErrorCode::Storage)?;
+        let key = unlock_key(&params, &passphrase).with_code(ErrorCode::Credentials)?;
+        crypto_state().key = Some(key);
+        reload_histories(&app);
+        Ok(status(&mode))
+    })
+    .await
+    .map_err(|e| CommandError::new(ErrorCode::Internal, format!("Unlock task failed: {}", e)))?
+}
+
+/// Switch the encryption mode ("off" | "keychain" | "passphrase") and re-seal
+/// all history, transcript sessions and recordings for it. The re-sealed
+/// copies are staged and committed together (see `PendingMigration`).
+#[tauri::command]
+pub(crate) async fn set_history_encryption(
+    app: AppHandle,
+    mode: String,
+    passphrase: Option<String>,
+) -> CommandResult<HistoryEncryptionStatus> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app.state::<AppState>();
+        let current = current_mode(&state);
+        if status(&current).locked {
+            return Err(CommandError::new(ErrorCode::Credentials, LOCKED_ERROR));
+        }
+        let (new_key, params) = match mode.as_str() {
+            "off" => (None, None),
+            "keychain" => (keychain_key(true).with_code(ErrorCode::Credentials)?, None),
+            "passphrase" => {
+                let (key, params) = new_passphrase_key(passphrase.as_deref().unwrap_or(""))
+                    .with_code(ErrorCode::InvalidInput)?;
+                (Some(key), Some(params))
+            }
+            other => {
+                return Err(CommandError::new(
+                    ErrorCode::InvalidInput,
+                    format!("Unknown encryption mode: {}", other),
+                ))
+            }
+        };
+
+        // Hold both histories so no write lands between re-sealing and the switch.
+        let mic = state
+            .history
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let system = state
+            .history_transcribe
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        // Stage every re-sealed copy first; until the migration file is
+        // written nothing has changed and a failure just drops the copies.
+        discard_staged(&app);
+        let files = protected_files(&app, false);
+        let committed = files
+            .iter()
+            .try_for_each(|path| stage_reseal(path, new_key.as_ref()))
+            .and_then(|_| {
+                let migration = PendingMigration {
+                    mode: mode.clone(),
+                    previous: current.clone(),
+                    params,
+                    committed_ms: crate::util::now_ms(),
+                };
+                let raw = serde_json::to_string_pretty(&migration).map_err(|e| e.to_string())?;
+                write_atomic(&migration_path(&app), raw.as_bytes())?;
+                Ok(migration)
+            });
+        let migration = match committed {
+            Ok(migration) => migration,
+            Err(err) => {
+                discard_staged(&app);
+                return Err(CommandError::new(ErrorCode::Storage, err));
+            }
+        };
+        *crypto_state() = CryptoState {
+            enabled: new_key.is_some(),
+            key: new_key,
+        };
+        apply_migration(&app, &migration).map_err(|err| {
+            CommandError::new(
+                ErrorCode::Storage,
+                format!("{} (the encryption switch finishes on the next start)", err),
+            )
+        })?;
+        // The in-memory months may be ahead of their debounced files.
+        mic.flush_to_disk().with_code(ErrorCode::Storage)?;
+        system.flush_to_disk().with_code(ErrorCode::Storage)?;
+        drop(system);
+        drop(mic);
+
+        let mut settings = state
+            .settings
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        settings.history_encryption_mode = mode.clone();
+        crate::save_settings_file(&app, &settings).with_code(ErrorCode::Storage)?;
+        drop(settings);
+        let _ = fs::remove_file(migration_path(&app));
+        info!(
+            "History encryption set to {} ({} files re-sealed)",
+            mode,
+            files.len()
+        );
+        Ok(status(&mode))
+    })
+    .await
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("Encryption task failed: {}", e),
+        )
+    })?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seal_roundtrip_and_tamper_detection() {
+        let key = [7u8; 32];
+        let sealed = seal_with(&key, b"meeting notes").unwrap();
+        assert!(is_sealed(&sealed));
+        assert_eq!(open_with(&key, &sealed).unwrap(), b"meeting notes");
+        assert!(open_with(&[8u8; 32], &sealed).is_err());
+
+        let mut tampered = sealed.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(open_with(&key, &tampered).is_err());
+        assert!(!is_sealed(b"[{\"id\":\"h_1\"}]"));
+    }
+
+    #[test]
+    fn staged_copies_map_back_to_their_originals() {
+        let original = Path::new("recordings").join("rec_1.opus");
+        let staged = staged_path(&original);
+        assert_eq!(staged, Path::new("recordings").join("rec_1.opus.reseal"));
+        assert_eq!(staged.with_extension(""), original);
+    }
+
+    #[test]
+    fn decrypted_recording_copies_are_removed_on_drop() {
+        let copy = std::env::temp_dir().join(format!("trispr_plain_copy_{}", std::process::id()));
+        fs::write(&copy, b"audio").unwrap();
+        drop(PlaintextRecording {
+            path: copy.clone(),
+            temporary: true,
+        });
+        assert!(!copy.exists());
+    }
+
+    #[test]
+    fn passphrase_check_rejects_wrong_passphrase() {
+        let (key, params) = new_passphrase_key("correct horse").unwrap();
+        assert_eq!(unlock_key(&params, "correct horse").unwrap(), key);
+        assert!(unlock_key(&params, "wrong horse!").is_err());
+        assert!(new_passphrase_key("short").is_err());
+    }
+}
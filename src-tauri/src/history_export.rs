@@ -0,0 +1,257 @@
+//! Export of mic or system history to Markdown, CSV or JSON.
+
+use chrono::Local;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use tauri::State;
+
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
+use crate::state::{AppState, HistoryEntry};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExportFormat {
+    Markdown,
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    fn parse(format: &str) -> Result<Self, String> {
+        match format.trim().to_ascii_lowercase().as_str() {
+            "md" | "markdown" => Ok(Self::Markdown),
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            other => Err(format!("Unknown export format: {}", other)),
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Csv => "csv",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// Which entries to export. Empty fields select everything.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct HistoryExportFilter {
+    /// "mic" (default) | "system"
+    pub(crate) kind: String,
+    /// Inclusive lower bound on `timestamp_ms`.
+    pub(crate) from_ms: Option<u64>,
+    /// Exclusive upper bound on `timestamp_ms`.
+    pub(crate) to_ms: Option<u64>,
+    pub(crate) tag: Option<String>,
+    pub(crate) pinned_only: bool,
+}
+
+impl HistoryExportFilter {
+    fn matches(&self, entry: &HistoryEntry) -> bool {
+        self.from_ms.is_none_or(|from| entry.timestamp_ms >= from)
+            && self.to_ms.is_none_or(|to| entry.timestamp_ms < to)
+            && (!self.pinned_only || entry.pinned)
+            && self.tag.as_deref().is_none_or(|tag| {
+                let tag = tag.trim().to_lowercase();
+                tag.is_empty() || entry.tags.contains(&tag)
+            })
+    }
+}
+
+fn local_time(ms: u64, fmt: &str) -> String {
+    chrono::DateTime::from_timestamp_millis(ms as i64)
+        .map(|utc| utc.with_timezone(&Local).format(fmt).to_string())
+        .unwrap_or_default()
+}
+
+fn speaker(entry: &HistoryEntry) -> &str {
+    entry
+        .speaker_name
+        .as_deref()
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or(entry.source.as_str())
+}
+
+/// One section per local day, oldest first.
+fn render_markdown(entries: &[HistoryEntry]) -> String {
+    let mut days: BTreeMap<String, Vec<&HistoryEntry>> = BTreeMap::new();
+    for entry in entries {
+        days.entry(local_time(entry.timestamp_ms, "%Y-%m-%d"))
+            .or_default()
+            .push(entry);
+    }
+    let mut out = String::from("# Trispr Flow history\n");
+    for (day, entries) in days {
+        out.push_str(&format!("\n## {}\n\n", day));
+        for entry in entries {
+            let pin = if entry.pinned { " 📌" } else { "" };
+            out.push_str(&format!(
+                "- **{}** {}{}: {}\n",
+                local_time(entry.timestamp_ms, "%H:%M:%S"),
+                speaker(entry),
+                pin,
+                entry.text.trim()
+            ));
+            if !entry.tags.is_empty() {
+                let tags: Vec<String> = entry.tags.iter().map(|tag| format!("#{}", tag)).collect();
+                out.push_str(&format!("  - Tags: {}\n", tags.join(" ")));
+            }
+            if let Some(note) = &entry.note {
+                out.push_str(&format!("  - Note: {}\n", note));
+            }
+        }
+    }
+    out
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn render_csv(entries: &[HistoryEntry]) -> String {
+    let mut out = String::from("id,timestamp,source,speaker,text,tags,note,pinned\n");
+    for entry in entries {
+        let row = [
+            entry.id.clone(),
+            local_time(entry.timestamp_ms, "%Y-%m-%dT%H:%M:%S%:z"),
+            entry.source.clone(),
+            speaker(entry).to_string(),
+            entry.text.trim().to_string(),
+            entry.tags.join(";"),
+            entry.note.clone().unwrap_or_default(),
+            entry.pinned.to_string(),
+        ];
+        let fields: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+fn render(format: ExportFormat, entries: &[HistoryEntry]) -> Result<String, String> {
+    match format {
+        ExportFormat::Markdown => Ok(render_markdown(entries)),
+        ExportFormat::Csv => Ok(render_csv(entries)),
+        ExportFormat::Json => serde_json::to_string_pretty(entries).map_err(|e| e.to_string()),
+    }
+}
+
+/// Write the filtered mic or system history (all months) to a file picked by
+/// the user. Returns the written path.
+#[tauri::command]
+pub(crate) fn export_history(
+    state: State<'_, AppState>,
+    format: String,
+    filter: Option<HistoryExportFilter>,
+) -> CommandResult<String> {
+    let format = ExportFormat::parse(&format).with_code(ErrorCode::InvalidInput)?;
+    let filter = filter.unwrap_or_default();
+    let history = match filter.kind.as_str() {
+        "" | "mic" => &state.history,
+        "system" => &state.history_transcribe,
+        other => {
+            return Err(CommandError::new(
+                ErrorCode::InvalidInput,
+                format!("Unknown history kind: {}", other),
+            ))
+        }
+    };
+    let mut entries: Vec<HistoryEntry> = history
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .all_entries()
+        .into_iter()
+        .filter(|entry| filter.matches(entry))
+        .collect();
+    if entries.is_empty() {
+        return Err(CommandError::new(
+            ErrorCode::NotFound,
+            "No history entries match the export filter",
+        ));
+    }
+    entries.sort_by_key(|entry| entry.timestamp_ms);
+    let content = render(format, &entries).with_code(ErrorCode::Internal)?;
+
+    let file_path = rfd::FileDialog::new()
+        .set_file_name(format!(
+            "trispr-history-{}.{}",
+            local_time(crate::util::now_ms(), "%Y-%m-%d"),
+            format.extension()
+        ))
+        .add_filter(format.extension().to_uppercase(), &[format.extension()])
+        .save_file()
+        .ok_or_else(|| CommandError::new(ErrorCode::Cancelled, "File save cancelled"))?;
+    std::fs::write(&file_path, content).map_err(|e| {
+        CommandError::new(ErrorCode::Storage, format!("Failed to write file: {}", e))
+    })?;
+    Ok(file_path.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, timestamp_ms: u64, text: &str, tags: &[&str]) -> HistoryEntry {
+        HistoryEntry {
+            id: id.to_string(),
+            text: text.to_string(),
+            timestamp_ms,
+            source: "local".to_string(),
+            speaker_name: Some("Me".to_string()),
+            refinement: None,
+            audio_path: None,
+            revisions: Vec::new(),
+            pinned: false,
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            note: None,
+            formatted_text: None,
+            language: None,
+            translation: None,
+        }
+    }
+
+    #[test]
+    fn filter_applies_range_tag_and_pin() {
+        let filter = HistoryExportFilter {
+            from_ms: Some(100),
+            to_ms: Some(200),
+            tag: Some("Work".to_string()),
+            ..HistoryExportFilter::default()
+        };
+        assert!(filter.matches(&entry("a", 150, "x", &["work"])));
+        assert!(!filter.matches(&entry("b", 200, "x", &["work"])));
+        assert!(!filter.matches(&entry("c", 150, "x", &[])));
+        let pinned_only = HistoryExportFilter {
+            pinned_only: true,
+            ..HistoryExportFilter::default()
+        };
+        assert!(!pinned_only.matches(&entry("d", 150, "x", &[])));
+    }
+
+    #[test]
+    fn csv_quotes_fields_that_need_it() {
+        let csv = render_csv(&[entry("h_1", 0, "Hello, \"world\"", &["a", "b"])]);
+        let row = csv.lines().nth(1).unwrap();
+        assert!(row.starts_with("h_1,"));
+        assert!(row.contains(",Me,\"Hello, \"\"world\"\"\",a;b,,false"));
+    }
+
+    #[test]
+    fn markdown_groups_entries_by_day() {
+        let day_ms = 24 * 60 * 60 * 1000;
+        let markdown = render_markdown(&[
+            entry("1", 1_700_000_000_000, "first", &["idea"]),
+            entry("2", 1_700_000_000_000 + 2 * day_ms, "second", &[]),
+        ]);
+        assert_eq!(markdown.matches("\n## ").count(), 2);
+        assert!(markdown.contains("Me: first\n  - Tags: #idea\n"));
+        assert_eq!(ExportFormat::parse("MD"), Ok(ExportFormat::Markdown));
+        assert!(ExportFormat::parse("xml").is_err());
+    }
+}
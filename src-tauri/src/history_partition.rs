@@ -3,10 +3,17 @@ use serde::Serialize;
 use std::collections::{BTreeMap, VecDeque};
 use std::fs;
 use std::path::{Path, PathBuf};
-use tauri::{AppHandle, Emitter, Manager, State};
-use tracing::warn;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::menu::{MenuItem, Submenu};
+use tauri::{AppHandle, Emitter, Manager, State, Wry};
+use tracing::{info, warn};
 
-use crate::state::{push_history_entry_inner, push_transcribe_entry_inner, AppState, HistoryEntry};
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
+use crate::state::{
+    push_history_entry_inner, push_transcribe_entry_inner, AppState, HistoryEntry, HistoryRevision,
+    HistoryTranslation, Settings,
+};
 
 // ---------------------------------------------------------------------------
 // PartitionKey
@@ -100,6 +107,80 @@ pub(crate) struct PartitionInfo {
     pub(crate) is_active: bool,
 }
 
+// ---------------------------------------------------------------------------
+// Retention
+// ---------------------------------------------------------------------------
+
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+const HISTORY_PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+/// Let startup settle before the first background prune.
+const HISTORY_PRUNE_INITIAL_DELAY: Duration = Duration::from_secs(60);
+
+/// History limits from settings; zero disables a limit.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct HistoryRetention {
+    pub(crate) max_entries: usize,
+    pub(crate) max_age_days: u32,
+    pub(crate) max_disk_mb: u64,
+}
+
+impl HistoryRetention {
+    pub(crate) fn from_settings(settings: &Settings) -> Self {
+        Self {
+            max_entries: settings.history_max_entries as usize,
+            max_age_days: settings.history_max_age_days,
+            max_disk_mb: settings.history_max_disk_mb,
+        }
+    }
+
+    /// True when every limit is zero, i.e. history is kept forever.
+    pub(crate) fn is_disabled(&self) -> bool {
+        self.max_entries == 0 && self.max_age_days == 0 && self.max_disk_mb == 0
+    }
+
+    fn cutoff_ms(&self, now_ms: u64) -> Option<u64> {
+        (self.max_age_days > 0).then(|| now_ms.saturating_sub(self.max_age_days as u64 * DAY_MS))
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct HistoryPruneReport {
+    pub(crate) entries_removed: usize,
+    pub(crate) partitions_removed: usize,
+    pub(crate) bytes_freed: u64,
+}
+
+impl HistoryPruneReport {
+    fn merge(&mut self, other: HistoryPruneReport) {
+        self.entries_removed += other.entries_removed;
+        self.partitions_removed += other.partitions_removed;
+        self.bytes_freed += other.bytes_freed;
+    }
+}
+
+/// Retention filter over entries visited newest first: pinned entries always
+/// stay; others must be inside the age cutoff and the remaining count budget.
+fn within_retention(
+    entry: &HistoryEntry,
+    cutoff_ms: Option<u64>,
+    budget: &mut Option<usize>,
+) -> bool {
+    if entry.pinned {
+        return true;
+    }
+    if cutoff_ms.is_some_and(|cutoff_ms| entry.timestamp_ms < cutoff_ms) {
+        return false;
+    }
+    match budget {
+        Some(0) => false,
+        Some(remaining) => {
+            *remaining -= 1;
+            true
+        }
+        None => true,
+    }
+}
+
 // ---------------------------------------------------------------------------
 // PartitionedHistory
 // ---------------------------------------------------------------------------
@@ -171,7 +252,7 @@ impl PartitionedHistory {
         // Load the current month partition
         let active_key = PartitionKey::current();
         let active_path = base_dir.join(active_key.filename());
-        let active = match fs::read_to_string(&active_path) {
+        let active = match crate::history_crypto::read_to_string(&active_path) {
             Ok(raw) => {
                 let entries: Vec<HistoryEntry> = serde_json::from_str(&raw).unwrap_or_default();
                 VecDeque::from(entries)
@@ -199,7 +280,7 @@ impl PartitionedHistory {
             self.active_key = entry_key;
             // Load existing data for the new month (there might already be entries)
             let path = self.base_dir.join(self.active_key.filename());
-            self.active = match fs::read_to_string(&path) {
+            self.active = match crate::history_crypto::read_to_string(&path) {
                 Ok(raw) => {
                     let entries: Vec<HistoryEntry> = serde_json::from_str(&raw).unwrap_or_default();
                     VecDeque::from(entries)
@@ -210,13 +291,33 @@ impl PartitionedHistory {
         self.active.push_front(entry);
     }
 
+    /// Re-read the active month from disk (after encrypted history is
+    /// unlocked), keeping entries pushed while it could not be read.
+    pub(crate) fn reload_active(&mut self) {
+        let path = self.base_dir.join(self.active_key.filename());
+        let Ok(raw) = crate::history_crypto::read_to_string(&path) else {
+            return;
+        };
+        let stored: Vec<HistoryEntry> = serde_json::from_str(&raw).unwrap_or_default();
+        let pending: Vec<HistoryEntry> = self
+            .active
+            .drain(..)
+            .filter(|entry| !stored.iter().any(|stored| stored.id == entry.id))
+            .collect();
+        self.active = pending.into_iter().chain(stored).collect();
+        if let Err(e) = self.flush_to_disk() {
+            warn!("Failed to persist reloaded history: {}", e);
+        }
+    }
+
     /// Persist the active partition to disk atomically (.tmp + rename).
     pub(crate) fn flush_to_disk(&self) -> Result<(), String> {
         let path = self.base_dir.join(self.active_key.filename());
         let entries: Vec<&HistoryEntry> = self.active.iter().collect();
         let raw = serde_json::to_string_pretty(&entries).map_err(|e| e.to_string())?;
         let tmp_path = path.with_extension("json.tmp");
-        fs::write(&tmp_path, &raw).map_err(|e| e.to_string())?;
+        fs::write(&tmp_path, crate::history_crypto::seal(raw.as_bytes())?)
+            .map_err(|e| e.to_string())?;
         fs::rename(&tmp_path, &path).map_err(|e| e.to_string())?;
         Ok(())
     }
@@ -257,7 +358,7 @@ impl PartitionedHistory {
             } else {
                 let size = path.metadata().map(|m| m.len()).unwrap_or(0);
                 // For archived partitions, estimate entry count from file or read
-                let count = match fs::read_to_string(&path) {
+                let count = match crate::history_crypto::read_to_string(&path) {
                     Ok(raw) => {
                         let parsed: Vec<HistoryEntry> =
                             serde_json::from_str(&raw).unwrap_or_default();
@@ -289,12 +390,210 @@ impl PartitionedHistory {
             return self.active.iter().cloned().collect();
         }
         let path = self.base_dir.join(key.filename());
-        match fs::read_to_string(&path) {
+        match crate::history_crypto::read_to_string(&path) {
             Ok(raw) => serde_json::from_str(&raw).unwrap_or_default(),
             Err(_) => Vec::new(),
         }
     }
 
+    /// Apply `f` to the active-partition entry with `id`; returns false when
+    /// the entry is not in the active month.
+    pub(crate) fn update_active_entry<F: FnOnce(&mut HistoryEntry)>(
+        &mut self,
+        id: &str,
+        f: F,
+    ) -> bool {
+        match self.active.iter_mut().find(|entry| entry.id == id) {
+            Some(entry) => {
+                f(entry);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Drop active-month entries past the age or count limit. Cheap enough to
+    /// run on every push; returns the number of entries removed.
+    pub(crate) fn trim_active(&mut self, retention: &HistoryRetention, now_ms: u64) -> usize {
+        let before = self.active.len();
+        let cutoff_ms = retention.cutoff_ms(now_ms);
+        let mut budget = (retention.max_entries > 0).then_some(retention.max_entries);
+        // Newest entries are at the front.
+        self.active
+            .retain(|entry| within_retention(entry, cutoff_ms, &mut budget));
+        before - self.active.len()
+    }
+
+    /// Apply `f` to the entry with `id`, looking in the active month first and
+    /// then in archived partitions (rewritten in place). Returns the updated
+    /// entry, or `None` when no partition holds it.
+    pub(crate) fn update_entry<F: FnOnce(&mut HistoryEntry)>(
+        &mut self,
+        id: &str,
+        f: F,
+    ) -> Result<Option<HistoryEntry>, String> {
+        if let Some(entry) = self.active.iter_mut().find(|entry| entry.id == id) {
+            f(entry);
+            let updated = entry.clone();
+            self.flush_to_disk()?;
+            return Ok(Some(updated));
+        }
+        for (key, path) in self.archived_partitions() {
+            let mut entries = self.load_partition(&key);
+            if let Some(entry) = entries.iter_mut().find(|entry| entry.id == id) {
+                f(entry);
+                let updated = entry.clone();
+                save_entries_to_path(&path, &entries)?;
+                return Ok(Some(updated));
+            }
+        }
+        Ok(None)
+    }
+
+    /// The entry with `id` from the active month or an archived partition.
+    pub(crate) fn find_entry(&self, id: &str) -> Option<HistoryEntry> {
+        if let Some(entry) = self.active.iter().find(|entry| entry.id == id) {
+            return Some(entry.clone());
+        }
+        self.archived_partitions().into_iter().find_map(|(key, _)| {
+            self.load_partition(&key)
+                .into_iter()
+                .find(|entry| entry.id == id)
+        })
+    }
+
+    /// Every entry in every partition, active month first.
+    pub(crate) fn all_entries(&self) -> Vec<HistoryEntry> {
+        let mut entries: Vec<HistoryEntry> = self.active.iter().cloned().collect();
+        for (key, _) in self.archived_partitions() {
+            entries.extend(self.load_partition(&key));
+        }
+        entries
+    }
+
+    /// Archived partition files, newest month first.
+    fn archived_partitions(&self) -> Vec<(PartitionKey, PathBuf)> {
+        let mut partitions: Vec<(PartitionKey, PathBuf)> = fs::read_dir(&self.base_dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter_map(|entry| {
+                        let path = entry.path();
+                        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                            return None;
+                        }
+                        let key = PartitionKey::parse(path.file_stem()?.to_str()?).ok()?;
+                        (key != self.active_key).then_some((key, path))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        partitions.sort_by_key(|(key, _)| std::cmp::Reverse(*key));
+        partitions
+    }
+
+    /// Full retention pass: trims the active month, then rewrites or deletes
+    /// archived partitions for age and entry count, then deletes the oldest
+    /// archived partitions until the disk budget fits. The active partition is
+    /// never deleted for the disk budget.
+    pub(crate) fn prune(
+        &mut self,
+        retention: &HistoryRetention,
+        now_ms: u64,
+    ) -> HistoryPruneReport {
+        if retention.is_disabled() {
+            return HistoryPruneReport::default();
+        }
+        let mut report = HistoryPruneReport {
+            entries_removed: self.trim_active(retention, now_ms),
+            ..HistoryPruneReport::default()
+        };
+        if report.entries_removed > 0 {
+            if let Err(e) = self.flush_to_disk() {
+                warn!("Failed to persist trimmed history: {}", e);
+            }
+        }
+
+        let cutoff_ms = retention.cutoff_ms(now_ms);
+        let mut remaining = (retention.max_entries > 0).then(|| {
+            let unpinned = self.active.iter().filter(|entry| !entry.pinned).count();
+            retention.max_entries.saturating_sub(unpinned)
+        });
+        // (path, size_bytes, entry_count, pinned entries), newest first
+        let mut kept: Vec<(PathBuf, u64, usize, Vec<HistoryEntry>)> = Vec::new();
+        for (_, path) in self.archived_partitions() {
+            let size_before = path.metadata().map(|m| m.len()).unwrap_or(0);
+            // Never rewrite or delete an archive that could not be read.
+            let mut entries = match read_partition_file(&path) {
+                Ok(entries) => entries,
+                Err(e) => {
+                    warn!("Skipping unreadable partition {}: {}", path.display(), e);
+                    continue;
+                }
+            };
+            let before = entries.len();
+            entries.sort_by_key(|entry| std::cmp::Reverse(entry.timestamp_ms));
+            entries.retain(|entry| within_retention(entry, cutoff_ms, &mut remaining));
+            report.entries_removed += before - entries.len();
+
+            if entries.is_empty() {
+                match fs::remove_file(&path) {
+                    Ok(()) => {
+                        report.partitions_removed += 1;
+                        report.bytes_freed += size_before;
+                    }
+                    Err(e) => warn!("Failed to delete partition {}: {}", path.display(), e),
+                }
+                continue;
+            }
+            let mut size = size_before;
+            if entries.len() < before {
+                if let Err(e) = save_entries_to_path(&path, &entries) {
+                    warn!("Failed to rewrite partition {}: {}", path.display(), e);
+                }
+                size = path.metadata().map(|m| m.len()).unwrap_or(size_before);
+                report.bytes_freed += size_before.saturating_sub(size);
+            }
+            let count = entries.len();
+            entries.retain(|entry| entry.pinned);
+            kept.push((path, size, count, entries));
+        }
+
+        if retention.max_disk_mb > 0 {
+            let budget = retention.max_disk_mb * 1024 * 1024;
+            let active_size = self
+                .base_dir
+                .join(self.active_key.filename())
+                .metadata()
+                .map(|m| m.len())
+                .unwrap_or(0);
+            let mut total = active_size + kept.iter().map(|(_, size, ..)| size).sum::<u64>();
+            while total > budget {
+                let Some((path, size, count, pinned)) = kept.pop() else {
+                    break;
+                };
+                // Partitions holding pinned entries shrink to just those.
+                let result = if pinned.is_empty() {
+                    fs::remove_file(&path)
+                } else {
+                    save_entries_to_path(&path, &pinned).map_err(std::io::Error::other)
+                };
+                if let Err(e) = result {
+                    warn!("Failed to prune partition {}: {}", path.display(), e);
+                    continue;
+                }
+                let new_size = path.metadata().map(|m| m.len()).unwrap_or(0);
+                total = total - size + new_size;
+                report.entries_removed += count - pinned.len();
+                if pinned.is_empty() {
+                    report.partitions_removed += 1;
+                }
+                report.bytes_freed += size.saturating_sub(new_size);
+            }
+        }
+        report
+    }
+
     /// Wrapper around `VecDeque::retain` for the active partition (needed by
     /// cluster-flush logic in `transcription.rs`).
     #[cfg(target_os = "windows")]
@@ -307,21 +606,117 @@ impl PartitionedHistory {
 // Standalone helpers
 // ---------------------------------------------------------------------------
 
+/// Entries of a partition file; unlike `load_partition`, a file that cannot
+/// be decrypted or parsed is an error rather than an empty month.
+fn read_partition_file(path: &Path) -> Result<Vec<HistoryEntry>, String> {
+    let raw = crate::history_crypto::read_to_string(path)?;
+    serde_json::from_str(&raw).map_err(|e| format!("Invalid history partition: {}", e))
+}
+
 /// Write a slice of entries to the given path atomically (.tmp + rename).
 pub(crate) fn save_entries_to_path(path: &Path, entries: &[HistoryEntry]) -> Result<(), String> {
     let raw = serde_json::to_string_pretty(entries).map_err(|e| e.to_string())?;
     let tmp_path = path.with_extension("json.tmp");
-    fs::write(&tmp_path, &raw).map_err(|e| e.to_string())?;
+    fs::write(&tmp_path, crate::history_crypto::seal(raw.as_bytes())?)
+        .map_err(|e| e.to_string())?;
     fs::rename(&tmp_path, path).map_err(|e| e.to_string())?;
     Ok(())
 }
 
+/// The history (mic or system) whose active partition holds `entry_id`, with
+/// the event that announces changes to it.
+pub(crate) fn history_containing<'a>(
+    state: &'a AppState,
+    entry_id: &str,
+) -> Option<(&'a Mutex<PartitionedHistory>, &'static str)> {
+    [
+        (&state.history, "history:updated"),
+        (&state.history_transcribe, "transcribe:history-updated"),
+    ]
+    .into_iter()
+    .find(|(history, _)| {
+        history
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .active
+            .iter()
+            .any(|entry| entry.id == entry_id)
+    })
+}
+
+/// Link a saved recording to an entry so it can be re-transcribed later.
+/// Returns the updated active entries, or `None` if the entry is gone.
+pub(crate) fn attach_entry_audio(
+    history: &Mutex<PartitionedHistory>,
+    entry_id: &str,
+    audio_path: String,
+) -> Option<Vec<HistoryEntry>> {
+    let mut ph = history
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if !ph.update_active_entry(entry_id, |entry| entry.audio_path = Some(audio_path)) {
+        return None;
+    }
+    if let Err(e) = ph.flush_to_disk() {
+        warn!("Failed to persist entry audio link: {}", e);
+    }
+    Some(ph.active.iter().cloned().collect())
+}
+
+/// Record the translation of an active entry. Returns the updated active
+/// entries, or `None` if the entry is gone.
+pub(crate) fn attach_translation(
+    history: &Mutex<PartitionedHistory>,
+    entry_id: &str,
+    translation: HistoryTranslation,
+) -> Option<Vec<HistoryEntry>> {
+    let mut ph = history
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if !ph.update_active_entry(entry_id, |entry| entry.translation = Some(translation)) {
+        return None;
+    }
+    if let Err(e) = ph.flush_to_disk() {
+        warn!("Failed to persist entry translation: {}", e);
+    }
+    Some(ph.active.iter().cloned().collect())
+}
+
+/// Record the detected language and add tags (e.g. content-filter findings)
+/// on an active entry. Returns the updated active entries, or `None` if the
+/// entry is gone.
+pub(crate) fn annotate_entry(
+    history: &Mutex<PartitionedHistory>,
+    entry_id: &str,
+    language: Option<&str>,
+    tags: &[String],
+) -> Option<Vec<HistoryEntry>> {
+    let mut ph = history
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let updated = ph.update_active_entry(entry_id, |entry| {
+        if let Some(language) = language {
+            entry.language = Some(language.to_string());
+        }
+        let mut merged = std::mem::take(&mut entry.tags);
+        merged.extend(tags.iter().cloned());
+        entry.tags = normalize_tags(merged);
+    });
+    if !updated {
+        return None;
+    }
+    if let Err(e) = ph.flush_to_disk() {
+        warn!("Failed to persist entry annotations: {}", e);
+    }
+    Some(ph.active.iter().cloned().collect())
+}
+
 #[tauri::command]
 pub(crate) fn save_transcript(
     filename: String,
     content: String,
     format: String,
-) -> Result<String, String> {
+) -> CommandResult<String> {
     let extension = match format.as_str() {
         "txt" => "txt",
         "md" => "md",
@@ -333,9 +728,11 @@ pub(crate) fn save_transcript(
         .set_file_name(&filename)
         .add_filter(&format.to_uppercase(), &[extension])
         .save_file()
-        .ok_or("File save cancelled")?;
+        .ok_or_else(|| CommandError::new(ErrorCode::Cancelled, "File save cancelled"))?;
 
-    std::fs::write(&file_path, content).map_err(|e| format!("Failed to write file: {}", e))?;
+    std::fs::write(&file_path, content).map_err(|e| {
+        CommandError::new(ErrorCode::Storage, format!("Failed to write file: {}", e))
+    })?;
 
     Ok(file_path.to_string_lossy().to_string())
 }
@@ -368,19 +765,20 @@ pub(crate) fn get_transcribe_history(state: State<'_, AppState>) -> Vec<HistoryE
 pub(crate) fn clear_active_transcript_history(
     app: AppHandle,
     state: State<'_, AppState>,
-) -> Result<u64, String> {
+) -> CommandResult<u64> {
     let mic_deleted = {
         let mut history = state
             .history
             .lock()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
-        let deleted = history.active.len() as u64;
-        history.active.clear();
-        history.flush_to_disk()?;
-        let updated: Vec<_> = history.active.iter().cloned().collect();
+        let removed: Vec<_> = history.active.drain(..).collect();
+        history.flush_to_disk().with_code(ErrorCode::Storage)?;
         drop(history);
-        let _ = app.emit("history:updated", updated);
-        deleted
+        let _ = app.emit("history:updated", Vec::<HistoryEntry>::new());
+        for entry in &removed {
+            crate::entry_audio::discard_entry_audio(&app, entry);
+        }
+        removed.len() as u64
     };
 
     let system_deleted = {
@@ -388,13 +786,14 @@ pub(crate) fn clear_active_transcript_history(
             .history_transcribe
             .lock()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
-        let deleted = history.active.len() as u64;
-        history.active.clear();
-        history.flush_to_disk()?;
-        let updated: Vec<_> = history.active.iter().cloned().collect();
+        let removed: Vec<_> = history.active.drain(..).collect();
+        history.flush_to_disk().with_code(ErrorCode::Storage)?;
         drop(history);
-        let _ = app.emit("transcribe:history-updated", updated);
-        deleted
+        let _ = app.emit("transcribe:history-updated", Vec::<HistoryEntry>::new());
+        for entry in &removed {
+            crate::entry_audio::discard_entry_audio(&app, entry);
+        }
+        removed.len() as u64
     };
 
     Ok(mic_deleted + system_deleted)
@@ -405,7 +804,7 @@ pub(crate) fn delete_active_transcript_entry(
     app: AppHandle,
     state: State<'_, AppState>,
     entry_id: String,
-) -> Result<u64, String> {
+) -> CommandResult<u64> {
     let entry_id = entry_id.trim();
     if entry_id.is_empty() {
         return Ok(0);
@@ -416,16 +815,21 @@ pub(crate) fn delete_active_transcript_entry(
             .history
             .lock()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
-        let before = history.active.len();
-        history.active.retain(|entry| entry.id != entry_id);
-        let deleted = before.saturating_sub(history.active.len()) as u64;
-        if deleted > 0 {
-            history.flush_to_disk()?;
+        let (removed, kept): (Vec<_>, Vec<_>) = history
+            .active
+            .drain(..)
+            .partition(|entry| entry.id == entry_id);
+        history.active.extend(kept);
+        if !removed.is_empty() {
+            history.flush_to_disk().with_code(ErrorCode::Storage)?;
             let updated: Vec<_> = history.active.iter().cloned().collect();
             drop(history);
             let _ = app.emit("history:updated", updated);
+            for entry in &removed {
+                crate::entry_audio::discard_entry_audio(&app, entry);
+            }
         }
-        deleted
+        removed.len() as u64
     };
 
     let system_deleted = {
@@ -433,16 +837,21 @@ pub(crate) fn delete_active_transcript_entry(
             .history_transcribe
             .lock()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
-        let before = history.active.len();
-        history.active.retain(|entry| entry.id != entry_id);
-        let deleted = before.saturating_sub(history.active.len()) as u64;
-        if deleted > 0 {
-            history.flush_to_disk()?;
+        let (removed, kept): (Vec<_>, Vec<_>) = history
+            .active
+            .drain(..)
+            .partition(|entry| entry.id == entry_id);
+        history.active.extend(kept);
+        if !removed.is_empty() {
+            history.flush_to_disk().with_code(ErrorCode::Storage)?;
             let updated: Vec<_> = history.active.iter().cloned().collect();
             drop(history);
             let _ = app.emit("transcribe:history-updated", updated);
+            for entry in &removed {
+                crate::entry_audio::discard_entry_audio(&app, entry);
+            }
         }
-        deleted
+        removed.len() as u64
     };
 
     Ok(mic_deleted + system_deleted)
@@ -452,7 +861,7 @@ pub(crate) fn delete_active_transcript_entry(
 pub(crate) fn list_history_partitions(
     app: AppHandle,
     kind: String,
-) -> Result<Vec<PartitionInfo>, String> {
+) -> CommandResult<Vec<PartitionInfo>> {
     let state = app.state::<AppState>();
     match kind.as_str() {
         "mic" => Ok(state
@@ -465,7 +874,10 @@ pub(crate) fn list_history_partitions(
             .lock()
             .unwrap_or_else(|poisoned| poisoned.into_inner())
             .list_partitions()),
-        _ => Err(format!("Unknown history kind: {}", kind)),
+        _ => Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            format!("Unknown history kind: {}", kind),
+        )),
     }
 }
 
@@ -474,9 +886,9 @@ pub(crate) fn load_history_partition(
     app: AppHandle,
     kind: String,
     key: String,
-) -> Result<Vec<HistoryEntry>, String> {
+) -> CommandResult<Vec<HistoryEntry>> {
     let state = app.state::<AppState>();
-    let pk = PartitionKey::parse(&key)?;
+    let pk = PartitionKey::parse(&key).with_code(ErrorCode::InvalidInput)?;
     match kind.as_str() {
         "mic" => Ok(state
             .history
@@ -488,7 +900,10 @@ pub(crate) fn load_history_partition(
             .lock()
             .unwrap_or_else(|poisoned| poisoned.into_inner())
             .load_partition(&pk)),
-        _ => Err(format!("Unknown history kind: {}", kind)),
+        _ => Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            format!("Unknown history kind: {}", kind),
+        )),
     }
 }
 
@@ -498,9 +913,9 @@ pub(crate) fn add_history_entry(
     state: State<'_, AppState>,
     text: String,
     source: Option<String>,
-) -> Result<Vec<HistoryEntry>, String> {
+) -> CommandResult<Vec<HistoryEntry>> {
     let source = source.unwrap_or_else(|| "local".to_string());
-    push_history_entry_inner(&app, &state.history, text, source)
+    push_history_entry_inner(&app, &state.history, text, source).with_code(ErrorCode::Storage)
 }
 
 #[tauri::command]
@@ -508,6 +923,653 @@ pub(crate) fn add_transcribe_entry(
     app: AppHandle,
     state: State<'_, AppState>,
     text: String,
-) -> Result<Vec<HistoryEntry>, String> {
-    push_transcribe_entry_inner(&app, &state.history_transcribe, text)
+) -> CommandResult<Vec<HistoryEntry>> {
+    push_transcribe_entry_inner(&app, &state.history_transcribe, text).with_code(ErrorCode::Storage)
+}
+
+/// Re-transcribe an entry's saved recording with `model_id` (local engine) and
+/// attach the result as a new revision. The entry text itself is unchanged.
+#[tauri::command]
+pub(crate) async fn retranscribe_entry(
+    app: AppHandle,
+    entry_id: String,
+    model_id: String,
+) -> CommandResult<HistoryEntry> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app.state::<AppState>();
+        // Any month: older entries keep their recordings too.
+        let (history, event, entry) = [
+            (&state.history, "history:updated"),
+            (&state.history_transcribe, "transcribe:history-updated"),
+        ]
+        .into_iter()
+        .find_map(|(history, event)| {
+            history
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .find_entry(&entry_id)
+                .map(|entry| (history, event, entry))
+        })
+        .ok_or_else(|| {
+            CommandError::new(
+                ErrorCode::NotFound,
+                format!("History entry '{}' not found", entry_id),
+            )
+        })?;
+
+        let audio_path = entry.audio_path.ok_or_else(|| {
+            CommandError::new(ErrorCode::NotFound, "No saved recording for this entry")
+        })?;
+        let audio_path = PathBuf::from(audio_path);
+        if !audio_path.exists() {
+            return Err(CommandError::new(
+                ErrorCode::NotFound,
+                format!("Saved recording is missing: {}", audio_path.display()),
+            ));
+        }
+        let samples =
+            crate::opus::load_recording_samples(&app, &audio_path).with_code(ErrorCode::Storage)?;
+
+        let mut settings = state
+            .settings
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone();
+        crate::models::resolve_model_path(&app, &model_id).ok_or_else(|| {
+            CommandError::new(
+                ErrorCode::ModelNotFound,
+                format!("Model '{}' is not installed", model_id),
+            )
+        })?;
+        settings.model = model_id.clone();
+        settings.cloud_transcription.enabled = false;
+        settings.cloud_fallback = false;
+        // Own whisper-cli: the shared server keeps the dictation model.
+        let (text, _) = crate::transcription::with_dedicated_cli(
+            std::sync::Arc::new(std::sync::atomic::AtomicU32::new(0)),
+            || crate::transcription::transcribe_audio(&app, &settings, &samples),
+        )
+        .with_code(ErrorCode::Transcription)?;
+
+        let revision = HistoryRevision {
+            model: model_id,
+            text: text.trim().to_string(),
+            created_ms: crate::util::now_ms(),
+        };
+        let mut ph = history
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let updated_entry = ph
+            .update_entry(&entry_id, |entry| entry.revisions.push(revision))
+            .with_code(ErrorCode::Storage)?
+            .ok_or_else(|| {
+                CommandError::new(
+                    ErrorCode::NotFound,
+                    format!("History entry '{}' was removed", entry_id),
+                )
+            })?;
+        let updated: Vec<_> = ph.active.iter().cloned().collect();
+        drop(ph);
+        let _ = app.emit(event, updated);
+        Ok(updated_entry)
+    })
+    .await
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("Re-transcription task failed: {}", e),
+        )
+    })?
+}
+
+/// Trimmed, lowercased, de-duplicated tags in first-seen order.
+fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if !tag.is_empty() && !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    normalized
+}
+
+/// Set any of `pinned`, `tags` and `note` on a history entry (mic or system,
+/// any month). An empty note clears it.
+#[tauri::command]
+pub(crate) fn update_history_entry(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    entry_id: String,
+    pinned: Option<bool>,
+    tags: Option<Vec<String>>,
+    note: Option<String>,
+) -> CommandResult<HistoryEntry> {
+    let tags = tags.map(normalize_tags);
+    let note = note.map(|note| note.trim().to_string());
+    for (history, event) in [
+        (&state.history, "history:updated"),
+        (&state.history_transcribe, "transcribe:history-updated"),
+    ] {
+        let mut ph = history
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let in_active = ph.active.iter().any(|entry| entry.id == entry_id);
+        let updated = ph
+            .update_entry(&entry_id, |entry| {
+                if let Some(pinned) = pinned {
+                    entry.pinned = pinned;
+                }
+                if let Some(tags) = tags.clone() {
+                    entry.tags = tags;
+                }
+                if let Some(note) = note.clone() {
+                    entry.note = (!note.is_empty()).then_some(note);
+                }
+            })
+            .with_code(ErrorCode::Storage)?;
+        if let Some(updated) = updated {
+            if in_active {
+                let active: Vec<_> = ph.active.iter().cloned().collect();
+                drop(ph);
+                let _ = app.emit(event, active);
+            }
+            return Ok(updated);
+        }
+    }
+    Err(CommandError::new(
+        ErrorCode::NotFound,
+        format!("History entry '{}' not found", entry_id),
+    ))
+}
+
+/// Replace an entry's transcript (any month). A refined version is replaced
+/// as well so the entry shows the correction, and the pasted rendering is
+/// dropped because it no longer matches.
+#[tauri::command]
+pub(crate) fn edit_history_entry(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    id: String,
+    new_text: String,
+) -> CommandResult<HistoryEntry> {
+    let new_text = new_text.trim().to_string();
+    if new_text.is_empty() {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "Entry text cannot be empty; delete the entry instead",
+        ));
+    }
+    for (history, event) in [
+        (&state.history, "history:updated"),
+        (&state.history_transcribe, "transcribe:history-updated"),
+    ] {
+        let mut ph = history
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let updated = ph
+            .update_entry(&id, |entry| {
+                entry.text = new_text.clone();
+                entry.formatted_text = None;
+                // The translation was of the old text.
+                entry.translation = None;
+                if let Some(refinement) = entry.refinement.as_mut() {
+                    if !refinement.refined.is_empty() {
+                        refinement.refined = new_text.clone();
+                    }
+                }
+            })
+            .with_code(ErrorCode::Storage)?;
+        if let Some(updated) = updated {
+            let active: Vec<_> = ph.active.iter().cloned().collect();
+            drop(ph);
+            let _ = app.emit(event, active);
+            return Ok(updated);
+        }
+    }
+    Err(CommandError::new(
+        ErrorCode::NotFound,
+        format!("History entry '{}' not found", id),
+    ))
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub(crate) struct TagCount {
+    pub(crate) tag: String,
+    pub(crate) count: usize,
+}
+
+fn count_tags<'a>(entries: impl IntoIterator<Item = &'a HistoryEntry>) -> Vec<TagCount> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for entry in entries {
+        for tag in &entry.tags {
+            *counts.entry(tag.as_str()).or_default() += 1;
+        }
+    }
+    let mut tags: Vec<TagCount> = counts
+        .into_iter()
+        .map(|(tag, count)| TagCount {
+            tag: tag.to_string(),
+            count,
+        })
+        .collect();
+    // Most used first; BTreeMap order keeps ties alphabetical.
+    tags.sort_by_key(|tag| std::cmp::Reverse(tag.count));
+    tags
+}
+
+/// All tags used across the mic and system history, most used first.
+#[tauri::command]
+pub(crate) async fn list_tags(app: AppHandle) -> CommandResult<Vec<TagCount>> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app.state::<AppState>();
+        let mut entries = Vec::new();
+        for history in [&state.history, &state.history_transcribe] {
+            entries.extend(
+                history
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .all_entries(),
+            );
+        }
+        count_tags(&entries)
+    })
+    .await
+    .map_err(|e| CommandError::new(ErrorCode::Internal, format!("list_tags task failed: {}", e)))
+}
+
+fn prune_history(
+    app: &AppHandle,
+    history: &Mutex<PartitionedHistory>,
+    event: &str,
+    retention: &HistoryRetention,
+) -> HistoryPruneReport {
+    let mut ph = history
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let active_before = ph.active.len();
+    let report = ph.prune(retention, crate::util::now_ms());
+    if ph.active.len() != active_before {
+        let updated: Vec<_> = ph.active.iter().cloned().collect();
+        drop(ph);
+        let _ = app.emit(event, updated);
+    }
+    report
+}
+
+fn prune_all_history(app: &AppHandle) -> HistoryPruneReport {
+    let state = app.state::<AppState>();
+    let retention = HistoryRetention::from_settings(
+        &state
+            .settings
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()),
+    );
+    if retention.is_disabled() {
+        return HistoryPruneReport::default();
+    }
+    let mut report = prune_history(app, &state.history, "history:updated", &retention);
+    report.merge(prune_history(
+        app,
+        &state.history_transcribe,
+        "transcribe:history-updated",
+        &retention,
+    ));
+    if report.entries_removed > 0 {
+        info!(
+            "History pruned: {} entries, {} partitions, {} bytes",
+            report.entries_removed, report.partitions_removed, report.bytes_freed
+        );
+    }
+    report
+}
+
+/// Background task applying the history retention settings once an hour.
+/// Each pass reads the settings and does nothing while every limit is zero.
+pub(crate) fn start_history_retention_task(app: AppHandle) {
+    crate::util::spawn_guarded("history_retention", move || {
+        std::thread::sleep(HISTORY_PRUNE_INITIAL_DELAY);
+        loop {
+            prune_all_history(&app);
+            std::thread::sleep(HISTORY_PRUNE_INTERVAL);
+        }
+    });
+}
+
+// ---------------------------------------------------------------------------
+// Tray "Recent transcripts" submenu
+// ---------------------------------------------------------------------------
+
+pub(crate) const TRAY_HISTORY_PREFIX: &str = "history:";
+const TRAY_HISTORY_ENTRIES: usize = 5;
+const TRAY_HISTORY_LABEL_CHARS: usize = 48;
+
+/// The text shown for an entry: the refined version once refinement finished.
+fn entry_output_text(entry: &HistoryEntry) -> &str {
+    entry
+        .refinement
+        .as_ref()
+        .filter(|refinement| {
+            refinement.status == "refined" && !refinement.refined.trim().is_empty()
+        })
+        .map_or(entry.text.as_str(), |refinement| {
+            refinement.refined.as_str()
+        })
+}
+
+/// One-line, length-capped label for a tray menu item.
+fn tray_history_label(entry: &HistoryEntry) -> String {
+    let flat = entry_output_text(entry)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if flat.chars().count() <= TRAY_HISTORY_LABEL_CHARS {
+        return flat;
+    }
+    let mut label: String = flat.chars().take(TRAY_HISTORY_LABEL_CHARS - 1).collect();
+    label.push('…');
+    label
+}
+
+/// Rebuilds the tray submenu from the newest mic history entries.
+pub(crate) fn populate_tray_history_submenu(
+    app: &AppHandle,
+    submenu: &Submenu<Wry>,
+    entries: &[HistoryEntry],
+) -> tauri::Result<()> {
+    for item in submenu.items()? {
+        submenu.remove(&item)?;
+    }
+    let recent: Vec<_> = entries
+        .iter()
+        .filter(|entry| !entry_output_text(entry).trim().is_empty())
+        .take(TRAY_HISTORY_ENTRIES)
+        .collect();
+    if recent.is_empty() {
+        let placeholder = MenuItem::new(app, "No transcripts yet", false, None::<&str>)?;
+        return submenu.append(&placeholder);
+    }
+    for entry in recent {
+        let item = MenuItem::with_id(
+            app,
+            format!("{}{}", TRAY_HISTORY_PREFIX, entry.id),
+            tray_history_label(entry),
+            true,
+            None::<&str>,
+        )?;
+        submenu.append(&item)?;
+    }
+    Ok(())
+}
+
+/// Pastes a history entry into the focused app again. If the paste keystroke
+/// fails the text is still left on the clipboard.
+#[tauri::command]
+pub(crate) fn paste_history_entry(app: AppHandle, id: String) -> CommandResult<()> {
+    let not_found = || {
+        CommandError::new(
+            ErrorCode::NotFound,
+            format!("History entry {} not found", id),
+        )
+    };
+    let state = app.state::<AppState>();
+    let (history, _) = history_containing(&state, &id).ok_or_else(not_found)?;
+    let text = history
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .active
+        .iter()
+        .find(|entry| entry.id == id)
+        .map(|entry| entry_output_text(entry).to_string())
+        .ok_or_else(not_found)?;
+    if let Err(err) = crate::paste_text(&app, &text) {
+        warn!("Re-paste of history entry failed, copying instead: {}", err);
+        crate::set_clipboard_text_with_retry(&text).with_code(ErrorCode::Internal)?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) async fn prune_history_now(app: AppHandle) -> CommandResult<HistoryPruneReport> {
+    tauri::async_runtime::spawn_blocking(move || prune_all_history(&app))
+        .await
+        .map_err(|e| {
+            CommandError::new(
+                ErrorCode::Internal,
+                format!("History prune task failed: {}", e),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str) -> HistoryEntry {
+        HistoryEntry {
+            id: id.to_string(),
+            text: "hello".to_string(),
+            timestamp_ms: 1_772_101_100_000,
+            source: "local".to_string(),
+            speaker_name: None,
+            refinement: None,
+            audio_path: None,
+            revisions: Vec::new(),
+            pinned: false,
+            tags: Vec::new(),
+            note: None,
+            formatted_text: None,
+            language: None,
+            translation: None,
+        }
+    }
+
+    #[test]
+    fn tray_label_prefers_refined_text_and_stays_on_one_short_line() {
+        let mut refined = entry("h_1");
+        refined.refinement = Some(crate::state::HistoryRefinement {
+            refined: "Hello,\nworld.".to_string(),
+            status: "refined".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(tray_history_label(&refined), "Hello, world.");
+
+        let mut long = entry("h_2");
+        long.text = "word ".repeat(20);
+        let label = tray_history_label(&long);
+        assert_eq!(label.chars().count(), TRAY_HISTORY_LABEL_CHARS);
+        assert!(label.ends_with('…'));
+    }
+
+    #[test]
+    fn attach_entry_audio_updates_only_the_matching_entry() {
+        let base_dir =
+            std::env::temp_dir().join(format!("trispr_history_attach_{}", std::process::id()));
+        let mut ph = PartitionedHistory::load_or_migrate(base_dir.clone(), None);
+        ph.active.clear();
+        ph.push_entry(entry("h_1"));
+        ph.push_entry(entry("h_2"));
+        let history = Mutex::new(ph);
+
+        let updated =
+            attach_entry_audio(&history, "h_1", "rec.opus".to_string()).expect("entry exists");
+        let audio: Vec<_> = updated
+            .iter()
+            .map(|entry| (entry.id.as_str(), entry.audio_path.as_deref()))
+            .collect();
+        assert_eq!(audio, vec![("h_2", None), ("h_1", Some("rec.opus"))]);
+        assert!(attach_entry_audio(&history, "missing", "x.opus".to_string()).is_none());
+        let _ = fs::remove_dir_all(&base_dir);
+    }
+
+    #[test]
+    fn entries_without_revision_fields_still_load() {
+        let raw = r#"[{"id":"h_1","text":"hi","timestamp_ms":1,"source":"local"}]"#;
+        let entries: Vec<HistoryEntry> = serde_json::from_str(raw).unwrap();
+        assert!(entries[0].audio_path.is_none());
+        assert!(entries[0].revisions.is_empty());
+    }
+
+    fn entry_at(id: &str, timestamp_ms: u64) -> HistoryEntry {
+        HistoryEntry {
+            timestamp_ms,
+            ..entry(id)
+        }
+    }
+
+    #[test]
+    fn trim_active_applies_age_and_count() {
+        let base_dir =
+            std::env::temp_dir().join(format!("trispr_history_trim_{}", std::process::id()));
+        let now_ms = crate::util::now_ms();
+        let mut ph = PartitionedHistory::load_or_migrate(base_dir.clone(), None);
+        ph.active.clear();
+        ph.active.push_front(entry_at("old", now_ms - 10 * DAY_MS));
+        ph.active.push_front(entry_at("mid", now_ms - DAY_MS));
+        ph.active.push_front(entry_at("new", now_ms));
+
+        let by_age = HistoryRetention {
+            max_age_days: 5,
+            ..HistoryRetention::default()
+        };
+        assert_eq!(ph.trim_active(&by_age, now_ms), 1);
+        let by_count = HistoryRetention {
+            max_entries: 1,
+            ..HistoryRetention::default()
+        };
+        assert_eq!(ph.trim_active(&by_count, now_ms), 1);
+        assert_eq!(ph.active[0].id, "new");
+        let _ = fs::remove_dir_all(&base_dir);
+    }
+
+    #[test]
+    fn prune_rewrites_and_deletes_archived_partitions() {
+        let base_dir =
+            std::env::temp_dir().join(format!("trispr_history_prune_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base_dir);
+        fs::create_dir_all(&base_dir).unwrap();
+        let jan = PartitionKey {
+            year: 2020,
+            month: 1,
+        };
+        let feb = PartitionKey {
+            year: 2020,
+            month: 2,
+        };
+        let jan_ms = 1_578_000_000_000; // 2020-01-02
+        let feb_ms = 1_581_000_000_000; // 2020-02-06
+        save_entries_to_path(
+            &base_dir.join(jan.filename()),
+            &[entry_at("j1", jan_ms), entry_at("j2", jan_ms + 1)],
+        )
+        .unwrap();
+        save_entries_to_path(
+            &base_dir.join(feb.filename()),
+            &[entry_at("f1", feb_ms), entry_at("f2", feb_ms + 1)],
+        )
+        .unwrap();
+        let mut ph = PartitionedHistory::load_or_migrate(base_dir.clone(), None);
+        ph.active.clear();
+
+        let report = ph.prune(
+            &HistoryRetention {
+                max_entries: 3,
+                ..HistoryRetention::default()
+            },
+            crate::util::now_ms(),
+        );
+        assert_eq!(report.entries_removed, 1);
+        assert_eq!(ph.load_partition(&jan).len(), 1);
+        assert_eq!(ph.load_partition(&feb).len(), 2);
+
+        let report = ph.prune(
+            &HistoryRetention {
+                max_age_days: 1,
+                ..HistoryRetention::default()
+            },
+            crate::util::now_ms(),
+        );
+        assert_eq!(report.entries_removed, 3);
+        assert_eq!(report.partitions_removed, 2);
+        assert!(!base_dir.join(jan.filename()).exists());
+        let _ = fs::remove_dir_all(&base_dir);
+    }
+
+    #[test]
+    fn prune_leaves_unreadable_partitions_alone() {
+        let base_dir =
+            std::env::temp_dir().join(format!("trispr_history_corrupt_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base_dir);
+        fs::create_dir_all(&base_dir).unwrap();
+        let jan = PartitionKey {
+            year: 2020,
+            month: 1,
+        };
+        let path = base_dir.join(jan.filename());
+        fs::write(&path, "[{\"id\": truncated").unwrap();
+        let mut ph = PartitionedHistory::load_or_migrate(base_dir.clone(), None);
+        ph.active.clear();
+
+        let retention = HistoryRetention {
+            max_age_days: 1,
+            ..HistoryRetention::default()
+        };
+        assert!(!retention.is_disabled());
+        let report = ph.prune(&retention, crate::util::now_ms());
+        assert_eq!(report.partitions_removed, 0);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "[{\"id\": truncated");
+        assert!(HistoryRetention::default().is_disabled());
+        let _ = fs::remove_dir_all(&base_dir);
+    }
+
+    #[test]
+    fn retention_keeps_pinned_entries() {
+        let base_dir =
+            std::env::temp_dir().join(format!("trispr_history_pinned_{}", std::process::id()));
+        let now_ms = crate::util::now_ms();
+        let mut ph = PartitionedHistory::load_or_migrate(base_dir.clone(), None);
+        ph.active.clear();
+        ph.active.push_front(HistoryEntry {
+            pinned: true,
+            ..entry_at("pinned", now_ms - 10 * DAY_MS)
+        });
+        ph.active.push_front(entry_at("a", now_ms - 1));
+        ph.active.push_front(entry_at("b", now_ms));
+
+        let retention = HistoryRetention {
+            max_entries: 1,
+            max_age_days: 5,
+            ..HistoryRetention::default()
+        };
+        assert_eq!(ph.trim_active(&retention, now_ms), 1);
+        let ids: Vec<_> = ph.active.iter().map(|entry| entry.id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "pinned"]);
+        let _ = fs::remove_dir_all(&base_dir);
+    }
+
+    #[test]
+    fn tags_are_normalized_and_counted() {
+        assert_eq!(
+            normalize_tags(vec![
+                " Work ".into(),
+                "work".into(),
+                "".into(),
+                "ideas".into()
+            ]),
+            vec!["work".to_string(), "ideas".to_string()]
+        );
+        let tagged = |id: &str, tags: &[&str]| HistoryEntry {
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            ..entry(id)
+        };
+        let entries = [tagged("1", &["work", "ideas"]), tagged("2", &["work"])];
+        let counts: Vec<_> = count_tags(&entries)
+            .into_iter()
+            .map(|tag| (tag.tag, tag.count))
+            .collect();
+        assert_eq!(
+            counts,
+            vec![("work".to_string(), 2), ("ideas".to_string(), 1)]
+        );
+    }
 }
@@ -0,0 +1,288 @@
+//! "Press the keys you want" hotkey learning.
+//!
+//! `capture_next_hotkey` installs a temporary low-level keyboard hook, waits
+//! for the next non-modifier key press and returns it, together with the
+//! modifiers held at that moment, in the global-shortcut accelerator syntax
+//! (e.g. "CommandOrControl+Shift+Space"). The captured key is swallowed so it
+//! neither types into the focused app nor fires an existing shortcut.
+
+use serde::Serialize;
+use std::time::Duration;
+
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
+use crate::hotkeys::{validate_hotkey_format, ValidationResult};
+
+const DEFAULT_CAPTURE_TIMEOUT_MS: u64 = 10_000;
+const MAX_CAPTURE_TIMEOUT_MS: u64 = 60_000;
+
+#[cfg(any(target_os = "windows", test))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct HeldModifiers {
+    ctrl: bool,
+    alt: bool,
+    shift: bool,
+    meta: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct CapturedHotkey {
+    /// Accelerator string as captured, before validation.
+    pub(crate) accelerator: String,
+    pub(crate) validation: ValidationResult,
+}
+
+/// Accelerator key name for a Windows virtual-key code. `None` for modifier
+/// keys and keys the global-shortcut plugin cannot register.
+#[cfg(any(target_os = "windows", test))]
+fn accelerator_key_name(vk: u32) -> Option<String> {
+    let name = match vk {
+        0x30..=0x39 | 0x41..=0x5A => return char::from_u32(vk).map(String::from),
+        0x60..=0x69 => return Some(format!("Numpad{}", vk - 0x60)),
+        0x70..=0x87 => return Some(format!("F{}", vk - 0x6F)),
+        0x08 => "Backspace",
+        0x09 => "Tab",
+        0x0D => "Enter",
+        0x13 => "Pause",
+        0x14 => "CapsLock",
+        0x1B => "Escape",
+        0x20 => "Space",
+        0x21 => "PageUp",
+        0x22 => "PageDown",
+        0x23 => "End",
+        0x24 => "Home",
+        0x25 => "ArrowLeft",
+        0x26 => "ArrowUp",
+        0x27 => "ArrowRight",
+        0x28 => "ArrowDown",
+        0x2C => "PrintScreen",
+        0x2D => "Insert",
+        0x2E => "Delete",
+        0x6A => "NumpadMultiply",
+        0x6B => "NumpadAdd",
+        0x6D => "NumpadSubtract",
+        0x6E => "NumpadDecimal",
+        0x6F => "NumpadDivide",
+        0x90 => "NumLock",
+        0x91 => "ScrollLock",
+        0xAD => "AudioVolumeMute",
+        0xAE => "AudioVolumeDown",
+        0xAF => "AudioVolumeUp",
+        0xB0 => "MediaTrackNext",
+        0xB1 => "MediaTrackPrevious",
+        0xB2 => "MediaStop",
+        0xB3 => "MediaPlayPause",
+        0xBA => "Semicolon",
+        0xBB => "Equal",
+        0xBC => "Comma",
+        0xBD => "Minus",
+        0xBE => "Period",
+        0xBF => "Slash",
+        0xC0 => "Backquote",
+        0xDB => "BracketLeft",
+        0xDC => "Backslash",
+        0xDD => "BracketRight",
+        0xDE => "Quote",
+        _ => return None,
+    };
+    Some(name.to_string())
+}
+
+#[cfg(any(target_os = "windows", test))]
+fn accelerator_string(modifiers: HeldModifiers, key: &str) -> String {
+    let mut parts = Vec::with_capacity(5);
+    if modifiers.ctrl {
+        parts.push("CommandOrControl");
+    }
+    if modifiers.alt {
+        parts.push("Alt");
+    }
+    if modifiers.shift {
+        parts.push("Shift");
+    }
+    if modifiers.meta {
+        parts.push("Super");
+    }
+    parts.push(key);
+    parts.join("+")
+}
+
+/// Waits for the next key combination and returns it validated.
+#[tauri::command]
+pub(crate) async fn capture_next_hotkey(timeout_ms: Option<u64>) -> CommandResult<CapturedHotkey> {
+    let timeout = Duration::from_millis(
+        timeout_ms
+            .unwrap_or(DEFAULT_CAPTURE_TIMEOUT_MS)
+            .clamp(1_000, MAX_CAPTURE_TIMEOUT_MS),
+    );
+    tauri::async_runtime::spawn_blocking(move || {
+        let accelerator = capture_blocking(timeout).with_code(ErrorCode::Hotkey)?;
+        let validation = validate_hotkey_format(&accelerator);
+        Ok(CapturedHotkey {
+            accelerator,
+            validation,
+        })
+    })
+    .await
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("capture_next_hotkey task failed: {}", e),
+        )
+    })?
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::{accelerator_key_name, accelerator_string, HeldModifiers};
+    use std::cell::RefCell;
+    use std::sync::{mpsc, Mutex};
+    use std::time::Duration;
+    use windows::Win32::Foundation::{LPARAM, LRESULT, WPARAM};
+    use windows::Win32::UI::Input::KeyboardAndMouse::{
+        GetAsyncKeyState, VIRTUAL_KEY, VK_CONTROL, VK_ESCAPE, VK_LWIN, VK_MENU, VK_RWIN, VK_SHIFT,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{
+        CallNextHookEx, DispatchMessageW, GetMessageW, PostQuitMessage, PostThreadMessageW,
+        SetWindowsHookExW, TranslateMessage, UnhookWindowsHookEx, KBDLLHOOKSTRUCT, MSG,
+        WH_KEYBOARD_LL, WM_KEYDOWN, WM_QUIT, WM_SYSKEYDOWN,
+    };
+
+    /// `None` = cancelled with a bare Escape.
+    type CaptureResult = Option<String>;
+
+    thread_local! {
+        static CAPTURE_TX: RefCell<Option<mpsc::Sender<CaptureResult>>> = const { RefCell::new(None) };
+    }
+
+    /// Only one capture may run at a time.
+    static CAPTURE_ACTIVE: Mutex<()> = Mutex::new(());
+
+    fn held_modifiers() -> HeldModifiers {
+        // SAFETY: GetAsyncKeyState only reads global key state.
+        let down = |key: VIRTUAL_KEY| unsafe { GetAsyncKeyState(key.0 as i32) } < 0;
+        HeldModifiers {
+            ctrl: down(VK_CONTROL),
+            alt: down(VK_MENU),
+            shift: down(VK_SHIFT),
+            meta: down(VK_LWIN) || down(VK_RWIN),
+        }
+    }
+
+    unsafe extern "system" fn ll_keyboard_proc(
+        ncode: i32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> LRESULT {
+        let message = wparam.0 as u32;
+        if ncode >= 0 && (message == WM_KEYDOWN || message == WM_SYSKEYDOWN) {
+            let kbd = &*(lparam.0 as *const KBDLLHOOKSTRUCT);
+            let modifiers = held_modifiers();
+            let result =
+                if kbd.vkCode == VK_ESCAPE.0 as u32 && modifiers == HeldModifiers::default() {
+                    Some(None)
+                } else {
+                    accelerator_key_name(kbd.vkCode)
+                        .map(|key| Some(accelerator_string(modifiers, &key)))
+                };
+            if let Some(result) = result {
+                CAPTURE_TX.with(|cell| {
+                    if let Some(tx) = cell.borrow_mut().take() {
+                        let _ = tx.send(result);
+                    }
+                });
+                PostQuitMessage(0);
+                return LRESULT(1);
+            }
+        }
+        CallNextHookEx(None, ncode, wparam, lparam)
+    }
+
+    pub(super) fn capture_blocking(timeout: Duration) -> Result<String, String> {
+        let _active = CAPTURE_ACTIVE
+            .try_lock()
+            .map_err(|_| "A hotkey capture is already in progress".to_string())?;
+        let (tx, rx) = mpsc::channel::<CaptureResult>();
+        let (tid_tx, tid_rx) = mpsc::channel::<u32>();
+
+        let hook_thread = crate::util::spawn_guarded("hotkey-capture", move || {
+            CAPTURE_TX.with(|cell| *cell.borrow_mut() = Some(tx));
+            // SAFETY: the hook is installed and removed on this thread,
+            // which pumps messages until the hook posts WM_QUIT or the
+            // caller times out.
+            unsafe {
+                let _ = tid_tx.send(windows_sys::Win32::System::Threading::GetCurrentThreadId());
+                let Ok(hook) = SetWindowsHookExW(WH_KEYBOARD_LL, Some(ll_keyboard_proc), None, 0)
+                else {
+                    CAPTURE_TX.with(|cell| cell.borrow_mut().take());
+                    return;
+                };
+                let mut msg = MSG::default();
+                while GetMessageW(&mut msg, None, 0, 0).as_bool() {
+                    let _ = TranslateMessage(&msg);
+                    DispatchMessageW(&msg);
+                }
+                let _ = UnhookWindowsHookEx(hook);
+            }
+        });
+
+        let tid = tid_rx
+            .recv()
+            .map_err(|_| "Hotkey capture thread failed to start".to_string())?;
+        let result = rx.recv_timeout(timeout);
+        if result.is_err() {
+            // SAFETY: posting WM_QUIT to a thread id we own ends its loop.
+            unsafe {
+                let _ = PostThreadMessageW(tid, WM_QUIT, WPARAM(0), LPARAM(0));
+            }
+        }
+        let _ = hook_thread.join();
+        match result {
+            Ok(Some(accelerator)) => Ok(accelerator),
+            Ok(None) => Err("Hotkey capture cancelled".to_string()),
+            Err(mpsc::RecvTimeoutError::Timeout) => Err("No key pressed".to_string()),
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                Err("Failed to install the keyboard hook".to_string())
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+use platform::capture_blocking;
+
+#[cfg(not(target_os = "windows"))]
+fn capture_blocking(_timeout: Duration) -> Result<String, String> {
+    Err("Hotkey capture is only supported on Windows; type the shortcut instead".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn virtual_keys_map_to_accelerator_names() {
+        assert_eq!(accelerator_key_name(0x41).as_deref(), Some("A"));
+        assert_eq!(accelerator_key_name(0x37).as_deref(), Some("7"));
+        assert_eq!(accelerator_key_name(0x78).as_deref(), Some("F9"));
+        assert_eq!(accelerator_key_name(0x87).as_deref(), Some("F24"));
+        assert_eq!(accelerator_key_name(0x63).as_deref(), Some("Numpad3"));
+        assert_eq!(accelerator_key_name(0x20).as_deref(), Some("Space"));
+        // Shift, Ctrl, LWin are modifiers, not keys.
+        assert_eq!(accelerator_key_name(0x10), None);
+        assert_eq!(accelerator_key_name(0x11), None);
+        assert_eq!(accelerator_key_name(0x5B), None);
+    }
+
+    #[test]
+    fn captured_combination_is_valid_accelerator() {
+        let modifiers = HeldModifiers {
+            ctrl: true,
+            shift: true,
+            ..HeldModifiers::default()
+        };
+        let accelerator = accelerator_string(modifiers, "Space");
+        assert_eq!(accelerator, "CommandOrControl+Shift+Space");
+        assert!(validate_hotkey_format(&accelerator).valid);
+        assert!(!validate_hotkey_format(&accelerator_string(HeldModifiers::default(), "K")).valid);
+    }
+}
@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use tauri::{AppHandle, State};
 
+use crate::errors::{CommandResult, ErrorCode, WithErrorCode};
 use crate::state::AppState;
 // tauri_plugin_global_shortcut::GlobalShortcutExt not needed here.
 
@@ -42,6 +43,22 @@ pub fn validate_hotkey_format(key: &str) -> ValidationResult {
         };
     }
 
+    // Mouse buttons and modifier+wheel are served by the mouse hook
+    if let Some(parsed) = crate::mouse_hotkeys::MouseBinding::parse(key) {
+        return match parsed {
+            Ok(binding) => ValidationResult {
+                valid: true,
+                error: None,
+                formatted: Some(binding.to_string()),
+            },
+            Err(error) => ValidationResult {
+                valid: false,
+                error: Some(error),
+                formatted: None,
+            },
+        };
+    }
+
     // Parse modifiers and key
     let parts: Vec<&str> = key.split('+').map(|s| s.trim()).collect();
 
@@ -224,8 +241,8 @@ pub(crate) fn validate_hotkey(key: String) -> ValidationResult {
 }
 
 #[tauri::command]
-pub(crate) fn test_hotkey(app: AppHandle, key: String) -> Result<(), String> {
-    test_hotkey_registration(&app, &key)
+pub(crate) fn test_hotkey(app: AppHandle, key: String) -> CommandResult<()> {
+    test_hotkey_registration(&app, &key).with_code(ErrorCode::Hotkey)
 }
 
 #[tauri::command]
@@ -239,10 +256,200 @@ pub(crate) fn get_hotkey_conflicts(state: State<'_, AppState>) -> Vec<ConflictIn
         settings.hotkey_toggle.clone(),
         settings.transcribe_hotkey.clone(),
         settings.hotkey_product_mode_toggle.clone(),
+        settings.hotkey_gesture.clone(),
+        settings.hotkey_privacy_mute.clone(),
+        settings.hotkey_undo_paste.clone(),
     ];
     detect_conflicts(hotkeys)
 }
 
+/// What a gesture hotkey did, as decided by [`GestureTracker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum GestureAction {
+    /// Pressed and released once, no second press within the double-tap window.
+    Tap,
+    /// Second press within the double-tap window.
+    DoubleTap,
+    /// Still held after the hold threshold.
+    HoldStart,
+    /// Released after `HoldStart`.
+    HoldEnd,
+}
+
+/// A timer the caller must arm; deliver it back via [`GestureTracker::on_timer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct GestureTimer {
+    pub(crate) delay_ms: u64,
+    pub(crate) generation: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GesturePhase {
+    Idle,
+    /// First press, waiting for release or the hold threshold.
+    Pressed,
+    Holding,
+    /// Released once, waiting for a second press or the double-tap window.
+    Released,
+    /// Second press of a double-tap, waiting for its release.
+    SecondPressed,
+}
+
+/// State machine over `ShortcutState` press/release events that lets one key
+/// mean tap, double-tap or hold. Key-repeat presses are ignored. Every phase
+/// change bumps `generation` so timers armed for an earlier phase are dropped.
+#[derive(Debug)]
+pub(crate) struct GestureTracker {
+    phase: GesturePhase,
+    generation: u64,
+    hold_ms: u64,
+    double_tap_ms: u64,
+}
+
+impl GestureTracker {
+    pub(crate) const fn new(hold_ms: u64, double_tap_ms: u64) -> Self {
+        Self {
+            phase: GesturePhase::Idle,
+            generation: 0,
+            hold_ms,
+            double_tap_ms,
+        }
+    }
+
+    /// Applies new timings and drops any gesture in progress.
+    pub(crate) fn configure(&mut self, hold_ms: u64, double_tap_ms: u64) {
+        self.hold_ms = hold_ms;
+        self.double_tap_ms = double_tap_ms;
+        self.enter(GesturePhase::Idle);
+    }
+
+    fn enter(&mut self, phase: GesturePhase) -> u64 {
+        self.phase = phase;
+        self.generation = self.generation.wrapping_add(1);
+        self.generation
+    }
+
+    pub(crate) fn on_press(&mut self) -> (Option<GestureAction>, Option<GestureTimer>) {
+        match self.phase {
+            GesturePhase::Idle => {
+                let generation = self.enter(GesturePhase::Pressed);
+                let timer = GestureTimer {
+                    delay_ms: self.hold_ms,
+                    generation,
+                };
+                (None, Some(timer))
+            }
+            GesturePhase::Released => {
+                self.enter(GesturePhase::SecondPressed);
+                (Some(GestureAction::DoubleTap), None)
+            }
+            GesturePhase::Pressed | GesturePhase::Holding | GesturePhase::SecondPressed => {
+                (None, None)
+            }
+        }
+    }
+
+    pub(crate) fn on_release(&mut self) -> (Option<GestureAction>, Option<GestureTimer>) {
+        match self.phase {
+            GesturePhase::Pressed => {
+                let generation = self.enter(GesturePhase::Released);
+                let timer = GestureTimer {
+                    delay_ms: self.double_tap_ms,
+                    generation,
+                };
+                (None, Some(timer))
+            }
+            GesturePhase::Holding => {
+                self.enter(GesturePhase::Idle);
+                (Some(GestureAction::HoldEnd), None)
+            }
+            GesturePhase::SecondPressed => {
+                self.enter(GesturePhase::Idle);
+                (None, None)
+            }
+            GesturePhase::Idle | GesturePhase::Released => (None, None),
+        }
+    }
+
+    pub(crate) fn on_timer(&mut self, generation: u64) -> Option<GestureAction> {
+        if generation != self.generation {
+            return None;
+        }
+        match self.phase {
+            GesturePhase::Pressed => {
+                self.enter(GesturePhase::Holding);
+                Some(GestureAction::HoldStart)
+            }
+            GesturePhase::Released => {
+                self.enter(GesturePhase::Idle);
+                Some(GestureAction::Tap)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// What a PTT key event means with push-to-lock enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PttLockAction {
+    Start,
+    Stop,
+    /// Released quickly: keep recording until the next press.
+    Lock,
+}
+
+/// Push-to-lock on top of PTT: a tap shorter than the tap threshold locks
+/// recording on and the next press stops it; a longer hold stops on release
+/// like plain PTT. Key-repeat presses are ignored.
+#[derive(Debug)]
+pub(crate) struct PttLockTracker {
+    pressed_at_ms: Option<u64>,
+    locked: bool,
+    /// The current press ended a locked recording; its release does nothing.
+    stopping: bool,
+}
+
+impl PttLockTracker {
+    pub(crate) const fn new() -> Self {
+        Self {
+            pressed_at_ms: None,
+            locked: false,
+            stopping: false,
+        }
+    }
+
+    pub(crate) fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// `recording` is false when a locked recording already ended some other
+    /// way; the press then starts a new one instead of stopping nothing.
+    pub(crate) fn on_press(&mut self, now_ms: u64, recording: bool) -> Option<PttLockAction> {
+        if self.pressed_at_ms.is_some() {
+            return None;
+        }
+        self.pressed_at_ms = Some(now_ms);
+        if std::mem::take(&mut self.locked) && recording {
+            self.stopping = true;
+            return Some(PttLockAction::Stop);
+        }
+        Some(PttLockAction::Start)
+    }
+
+    pub(crate) fn on_release(&mut self, now_ms: u64, tap_ms: u64) -> Option<PttLockAction> {
+        let pressed_at = self.pressed_at_ms.take()?;
+        if std::mem::take(&mut self.stopping) {
+            return None;
+        }
+        if now_ms.saturating_sub(pressed_at) < tap_ms {
+            self.locked = true;
+            Some(PttLockAction::Lock)
+        } else {
+            Some(PttLockAction::Stop)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -286,6 +493,46 @@ mod tests {
         assert!(!conflicts.is_empty());
     }
 
+    #[test]
+    fn gesture_tracker_distinguishes_tap_double_tap_and_hold() {
+        let mut tracker = GestureTracker::new(300, 250);
+
+        let (_, hold_timer) = tracker.on_press();
+        let (_, tap_timer) = tracker.on_release();
+        assert_eq!(tracker.on_timer(hold_timer.unwrap().generation), None);
+        let tap_timer = tap_timer.unwrap();
+        assert_eq!(tap_timer.delay_ms, 250);
+        assert_eq!(
+            tracker.on_timer(tap_timer.generation),
+            Some(GestureAction::Tap)
+        );
+
+        tracker.on_press();
+        let (_, tap_timer) = tracker.on_release();
+        assert_eq!(tracker.on_press().0, Some(GestureAction::DoubleTap));
+        assert_eq!(tracker.on_timer(tap_timer.unwrap().generation), None);
+        assert_eq!(tracker.on_release(), (None, None));
+
+        let (_, hold_timer) = tracker.on_press();
+        let hold_timer = hold_timer.unwrap();
+        assert_eq!(hold_timer.delay_ms, 300);
+        assert_eq!(
+            tracker.on_timer(hold_timer.generation),
+            Some(GestureAction::HoldStart)
+        );
+        assert_eq!(tracker.on_press(), (None, None), "key repeat is ignored");
+        assert_eq!(tracker.on_release().0, Some(GestureAction::HoldEnd));
+    }
+
+    #[test]
+    fn gesture_tracker_configure_cancels_pending_gesture() {
+        let mut tracker = GestureTracker::new(300, 250);
+        let (_, hold_timer) = tracker.on_press();
+        tracker.configure(400, 200);
+        assert_eq!(tracker.on_timer(hold_timer.unwrap().generation), None);
+        assert_eq!(tracker.on_press().1.unwrap().delay_ms, 400);
+    }
+
     #[test]
     fn test_normalize_hotkey() {
         assert_eq!(
@@ -293,4 +540,27 @@ mod tests {
             normalize_hotkey("ctrl+shift+space")
         );
     }
+
+    #[test]
+    fn ptt_lock_taps_lock_and_holds_behave_like_ptt() {
+        let mut tracker = PttLockTracker::new();
+
+        // Hold: stops on release.
+        assert_eq!(tracker.on_press(0, false), Some(PttLockAction::Start));
+        assert_eq!(tracker.on_press(50, true), None);
+        assert_eq!(tracker.on_release(900, 250), Some(PttLockAction::Stop));
+
+        // Tap locks; the next press stops and its release is swallowed.
+        assert_eq!(tracker.on_press(1_000, false), Some(PttLockAction::Start));
+        assert_eq!(tracker.on_release(1_120, 250), Some(PttLockAction::Lock));
+        assert!(tracker.is_locked());
+        assert_eq!(tracker.on_press(5_000, true), Some(PttLockAction::Stop));
+        assert_eq!(tracker.on_release(5_100, 250), None);
+        assert!(!tracker.is_locked());
+
+        // Locked recording ended elsewhere: the press starts a new one.
+        tracker.on_press(6_000, false);
+        tracker.on_release(6_050, 250);
+        assert_eq!(tracker.on_press(9_000, false), Some(PttLockAction::Start));
+    }
 }
@@ -0,0 +1,415 @@
+//! Optional localhost HTTP API so scripts (AutoHotkey, Stream Deck, shell)
+//! can drive Trispr Flow without the GUI.
+//!
+//! The server binds to 127.0.0.1 only and every request must carry
+//! `Authorization: Bearer <token>`. The token lives in the OS keychain and is
+//! shown in the settings via `get_http_api_info`.
+//!
+//! Endpoints (JSON responses):
+//!   GET  /v1/status
+//!   POST /v1/recording/start | /v1/recording/stop | /v1/recording/toggle
+//!   POST /v1/transcribe/toggle          system audio transcription on/off
+//!   GET  /v1/history?limit=N            newest first, default 20
+//!   POST /v1/transcribe                 body = WAV file, returns the text
+//!   GET  /v1/events                     WebSocket event stream (see `event_stream`)
+//!   GET  /v1/captions                   caption SSE stream (see `caption_output`)
+//!   POST /v1/mcp                        MCP JSON-RPC endpoint (see `mcp_server`)
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD as BASE64_URL;
+use base64::Engine;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::io::Read;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager};
+use tiny_http::{Header, Method, Request, Response, Server};
+use tracing::{info, warn};
+
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
+use crate::state::{AppState, Settings};
+
+pub(crate) const DEFAULT_HTTP_API_PORT: u16 = 43117;
+const KEYRING_SERVICE: &str = "com.trispr.flow.http-api";
+const KEYRING_USER: &str = "token";
+const DEFAULT_HISTORY_LIMIT: usize = 20;
+const MAX_HISTORY_LIMIT: usize = 500;
+/// Upper bound for uploaded WAV files (~50 min of 16 kHz stereo 16-bit).
+const MAX_WAV_BYTES: u64 = 200 * 1024 * 1024;
+const MAX_MCP_BYTES: u64 = 1024 * 1024;
+
+struct RunningServer {
+    port: u16,
+    server: Arc<Server>,
+}
+
+static SERVER: Mutex<Option<RunningServer>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct HttpApiInfo {
+    pub(crate) enabled: bool,
+    pub(crate) running: bool,
+    pub(crate) url: String,
+    pub(crate) events_url: String,
+    pub(crate) mcp_url: String,
+    pub(crate) token: String,
+}
+
+fn keychain_entry() -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+        .map_err(|e| format!("Failed to create keyring entry: {}", e))
+}
+
+fn generate_token() -> String {
+    use aes_gcm::aead::rand_core::RngCore;
+    let mut bytes = [0u8; 32];
+    aes_gcm::aead::OsRng.fill_bytes(&mut bytes);
+    BASE64_URL.encode(bytes)
+}
+
+/// The API token, created on first use.
+fn api_token() -> Result<String, String> {
+    let entry = keychain_entry()?;
+    match entry.get_password() {
+        Ok(token) if !token.trim().is_empty() => Ok(token.trim().to_string()),
+        Ok(_) | Err(keyring::Error::NoEntry) => {
+            let token = generate_token();
+            entry
+                .set_password(&token)
+                .map_err(|e| format!("Failed to store HTTP API token in keychain: {}", e))?;
+            Ok(token)
+        }
+        Err(err) => Err(format!(
+            "Failed to read HTTP API token from keychain: {}",
+            err
+        )),
+    }
+}
+
+/// Compares without an early exit so response timing does not leak the token.
+fn token_matches(provided: &str, expected: &str) -> bool {
+    provided.len() == expected.len()
+        && provided
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+fn bearer_token(request: &Request) -> Option<String> {
+    request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv("Authorization"))
+        .and_then(|header| {
+            header
+                .value
+                .as_str()
+                .strip_prefix("Bearer ")
+                .map(|token| token.trim().to_string())
+        })
+}
+
+fn json_response(status: u16, body: Value) -> Response<std::io::Cursor<Vec<u8>>> {
+    let content_type =
+        Header::from_bytes("Content-Type", "application/json").expect("static header is valid");
+    Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(content_type)
+}
+
+fn error_response(status: u16, message: &str) -> Response<std::io::Cursor<Vec<u8>>> {
+    json_response(status, json!({ "error": message }))
+}
+
+fn query_param<'a>(url: &'a str, name: &str) -> Option<&'a str> {
+    let (_, query) = url.split_once('?')?;
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+pub(crate) fn status_body(app: &AppHandle) -> Value {
+    let state = app.state::<AppState>();
+    let settings = state
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    let recording = state
+        .recorder
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .active;
+    json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "mode": settings.mode,
+        "capture_enabled": settings.capture_enabled,
+        "recording": recording,
+        "transcribe_active": state.transcribe_active.load(Ordering::Relaxed),
+        "privacy_muted": crate::privacy_mute::is_muted(),
+    })
+}
+
+fn history_body(app: &AppHandle, url: &str) -> Value {
+    let limit = query_param(url, "limit")
+        .and_then(|value| value.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_HISTORY_LIMIT)
+        .min(MAX_HISTORY_LIMIT);
+    let state = app.state::<AppState>();
+    let history = state
+        .history
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let entries: Vec<Value> = history
+        .active
+        .iter()
+        .take(limit)
+        .map(|entry| {
+            json!({
+                "id": entry.id,
+                "text": entry.text,
+                "timestamp_ms": entry.timestamp_ms,
+                "source": entry.source,
+            })
+        })
+        .collect();
+    json!({ "entries": entries })
+}
+
+fn transcribe_body(app: &AppHandle, request: &mut Request) -> Result<Value, (u16, String)> {
+    let mut bytes = Vec::new();
+    request
+        .as_reader()
+        .take(MAX_WAV_BYTES + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| (400, format!("Failed to read request body: {}", e)))?;
+    if bytes.len() as u64 > MAX_WAV_BYTES {
+        return Err((413, "WAV file is too large".to_string()));
+    }
+    let samples = crate::audio::decode_wav_to_mono_16k(&bytes).map_err(|e| (400, e))?;
+    if samples.is_empty() {
+        return Err((400, "WAV file contains no audio".to_string()));
+    }
+    let settings = app
+        .state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    let (text, source) =
+        crate::transcription::transcribe_audio(app, &settings, &samples).map_err(|e| (500, e))?;
+    Ok(json!({
+        "text": text.trim(),
+        "source": source,
+        "audio_duration_ms": samples.len() as u64 * 1000 / crate::constants::TARGET_SAMPLE_RATE as u64,
+    }))
+}
+
+fn mcp_response(app: &AppHandle, request: &mut Request) -> Response<std::io::Cursor<Vec<u8>>> {
+    let mut bytes = Vec::new();
+    if let Err(err) = request
+        .as_reader()
+        .take(MAX_MCP_BYTES + 1)
+        .read_to_end(&mut bytes)
+    {
+        return error_response(400, &format!("Failed to read request body: {}", err));
+    }
+    if bytes.len() as u64 > MAX_MCP_BYTES {
+        return error_response(413, "MCP message is too large");
+    }
+    match crate::mcp_server::handle_message(app, &bytes) {
+        Some(body) => json_response(200, body),
+        None => Response::from_data(Vec::new()).with_status_code(202),
+    }
+}
+
+fn route(app: &AppHandle, request: &mut Request) -> Response<std::io::Cursor<Vec<u8>>> {
+    let url = request.url().to_string();
+    let path = url.split('?').next().unwrap_or_default();
+    let method = request.method().clone();
+    let ok = || json_response(200, json!({ "ok": true }));
+    match (&method, path) {
+        (Method::Get, "/v1/status") => json_response(200, status_body(app)),
+        (Method::Post, "/v1/recording/start") => {
+            let state = app.state::<AppState>();
+            let settings = state
+                .settings
+                .read()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .clone();
+            match crate::audio::start_recording_with_settings(app, &state, &settings) {
+                Ok(()) => ok(),
+                Err(err) => error_response(409, &err),
+            }
+        }
+        (Method::Post, "/v1/recording/stop") => {
+            crate::audio::stop_recording_async(app.clone(), &app.state::<AppState>());
+            ok()
+        }
+        (Method::Post, "/v1/recording/toggle") => {
+            crate::audio::handle_toggle_async(app.clone());
+            ok()
+        }
+        (Method::Post, "/v1/transcribe/toggle") => {
+            crate::transcription::toggle_transcribe_state(app);
+            json_response(200, status_body(app))
+        }
+        (Method::Get, "/v1/history") => json_response(200, history_body(app, &url)),
+        (Method::Post, "/v1/transcribe") => match transcribe_body(app, request) {
+            Ok(body) => json_response(200, body),
+            Err((status, message)) => error_response(status, &message),
+        },
+        (Method::Post, "/v1/mcp") => mcp_response(app, request),
+        // No server-initiated SSE stream; clients fall back to plain POSTs.
+        (_, "/v1/mcp") => error_response(405, "Method not allowed"),
+        _ => error_response(404, "Not found"),
+    }
+}
+
+fn handle_request(app: &AppHandle, token: &str, mut request: Request) {
+    let url = request.url().to_string();
+    let path = url.split('?').next().unwrap_or_default();
+    if path == "/v1/events" || path == "/v1/captions" {
+        let provided =
+            bearer_token(&request).or_else(|| query_param(&url, "token").map(str::to_string));
+        if !provided.is_some_and(|provided| token_matches(&provided, token)) {
+            let _ = request.respond(error_response(401, "Missing or invalid token"));
+        } else if path == "/v1/events" {
+            crate::event_stream::accept(request, query_param(&url, "events"));
+        } else {
+            crate::caption_output::serve_sse(request);
+        }
+        return;
+    }
+    let response = match bearer_token(&request) {
+        Some(provided) if token_matches(&provided, token) => route(app, &mut request),
+        _ => error_response(401, "Missing or invalid bearer token"),
+    };
+    if let Err(err) = request.respond(response) {
+        warn!("HTTP API: failed to send response: {}", err);
+    }
+}
+
+fn stop_server() {
+    let running = SERVER
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .take();
+    if let Some(running) = running {
+        running.server.unblock();
+        crate::event_stream::disconnect_all();
+        crate::caption_output::disconnect_all();
+        info!("HTTP API stopped");
+    }
+}
+
+fn start_server(app: &AppHandle, port: u16) -> Result<(), String> {
+    let token = api_token()?;
+    let server = Server::http(("127.0.0.1", port))
+        .map(Arc::new)
+        .map_err(|e| format!("Failed to start HTTP API on port {}: {}", port, e))?;
+    info!("HTTP API listening on http://127.0.0.1:{}", port);
+
+    let app = app.clone();
+    let accept_server = server.clone();
+    crate::util::spawn_guarded("http_api_server", move || {
+        // `incoming_requests` ends once `unblock` is called on stop.
+        for request in accept_server.incoming_requests() {
+            let app = app.clone();
+            let token = token.clone();
+            // Transcription can take a while; keep accepting meanwhile.
+            crate::util::spawn_guarded("http_api_request", move || {
+                handle_request(&app, &token, request);
+            });
+        }
+    });
+    *SERVER
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(RunningServer { port, server });
+    Ok(())
+}
+
+/// Starts, restarts or stops the server to match the settings.
+pub(crate) fn sync_http_api(app: &AppHandle, settings: &Settings) {
+    let running_port = SERVER
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .as_ref()
+        .map(|running| running.port);
+    if !settings.http_api_enabled {
+        stop_server();
+        return;
+    }
+    if running_port == Some(settings.http_api_port) {
+        return;
+    }
+    stop_server();
+    if let Err(err) = start_server(app, settings.http_api_port) {
+        warn!("{}", err);
+        crate::emit_error(app, crate::errors::AppError::Network(err), Some("HTTP API"));
+    }
+}
+
+#[tauri::command]
+pub(crate) fn get_http_api_info(app: AppHandle) -> CommandResult<HttpApiInfo> {
+    let settings = app
+        .state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    let running = SERVER
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .is_some();
+    Ok(HttpApiInfo {
+        enabled: settings.http_api_enabled,
+        running,
+        url: format!("http://127.0.0.1:{}/v1", settings.http_api_port),
+        events_url: format!("ws://127.0.0.1:{}/v1/events", settings.http_api_port),
+        mcp_url: format!("http://127.0.0.1:{}/v1/mcp", settings.http_api_port),
+        token: api_token().with_code(ErrorCode::Credentials)?,
+    })
+}
+
+/// Replaces the token and restarts the server so the old one stops working.
+#[tauri::command]
+pub(crate) fn regenerate_http_api_token(app: AppHandle) -> CommandResult<HttpApiInfo> {
+    let token = generate_token();
+    keychain_entry()
+        .with_code(ErrorCode::Credentials)?
+        .set_password(&token)
+        .map_err(|e| {
+            CommandError::new(
+                ErrorCode::Credentials,
+                format!("Failed to store HTTP API token in keychain: {}", e),
+            )
+        })?;
+    stop_server();
+    let settings = app
+        .state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    sync_http_api(&app, &settings);
+    get_http_api_info(app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_and_query_helpers() {
+        assert!(token_matches("abc123", "abc123"));
+        assert!(!token_matches("abc124", "abc123"));
+        assert!(!token_matches("abc", "abc123"));
+        assert_eq!(query_param("/v1/history?x=1&limit=5", "limit"), Some("5"));
+        assert_eq!(query_param("/v1/history", "limit"), None);
+        assert_eq!(generate_token().len(), 43);
+    }
+}
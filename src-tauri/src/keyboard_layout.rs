@@ -0,0 +1,205 @@
+//! Keystrokes that mean the same thing on every keyboard layout.
+//!
+//! `Key::Layout('v')` resolves the letter against the layout of *our* thread,
+//! which differs from the focused app's after a per-window layout switch and
+//! has no 'v' at all on Cyrillic or Greek layouts — the paste chord then hits
+//! a random key. Shortcut letters are resolved against the layout of the
+//! foreground window instead (Windows) or fall back to the physical ANSI key
+//! when the layout cannot type Latin letters (macOS), which is where apps
+//! look for Ctrl/Cmd shortcuts. Typed text sends line breaks and tabs as real
+//! keys, since Unicode key events for control characters are dropped by most
+//! apps.
+
+use enigo::{Enigo, Key, KeyboardControllable};
+
+/// Key for `letter` in a Ctrl/Cmd shortcut on the focused app's layout.
+pub(crate) fn shortcut_key(letter: char) -> Key {
+    platform::shortcut_key(letter.to_ascii_lowercase())
+}
+
+/// Presses `modifier` + `letter`, e.g. the paste chord.
+pub(crate) fn send_shortcut(enigo: &mut Enigo, modifier: Key, letter: char) {
+    let key = shortcut_key(letter);
+    enigo.key_down(modifier);
+    enigo.key_click(key);
+    enigo.key_up(modifier);
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum TypedChunk<'a> {
+    Text(&'a str),
+    Return,
+    Tab,
+}
+
+/// Splits `text` into printable runs and Return / Tab keys; CRLF is one
+/// Return.
+fn typing_chunks(text: &str) -> Vec<TypedChunk<'_>> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut iter = text.char_indices().peekable();
+    while let Some((index, c)) = iter.next() {
+        let key = match c {
+            '\r' | '\n' => TypedChunk::Return,
+            '\t' => TypedChunk::Tab,
+            _ => continue,
+        };
+        if start < index {
+            chunks.push(TypedChunk::Text(&text[start..index]));
+        }
+        if c == '\r' && iter.peek().is_some_and(|(_, next)| *next == '\n') {
+            iter.next();
+        }
+        start = iter.peek().map_or(text.len(), |(next, _)| *next);
+        chunks.push(key);
+    }
+    if start < text.len() {
+        chunks.push(TypedChunk::Text(&text[start..]));
+    }
+    chunks
+}
+
+/// Types `text`; characters go out as Unicode events, which do not depend
+/// on the layout.
+pub(crate) fn type_sequence(enigo: &mut Enigo, text: &str) {
+    for chunk in typing_chunks(text) {
+        match chunk {
+            TypedChunk::Text(run) => enigo.key_sequence(run),
+            TypedChunk::Return => enigo.key_click(Key::Return),
+            TypedChunk::Tab => enigo.key_click(Key::Tab),
+        }
+    }
+}
+
+/// Virtual key from a `VkKeyScanEx` result, if the character needs no
+/// Shift/AltGr on that layout.
+#[cfg(any(test, target_os = "windows"))]
+fn plain_virtual_key(scan: i16) -> Option<u16> {
+    if scan == -1 {
+        return None;
+    }
+    let shift_state = (scan as u16) >> 8;
+    (shift_state == 0).then_some(scan as u16 & 0xFF)
+}
+
+/// macOS virtual key code of a letter key in the ANSI position.
+#[cfg(any(test, target_os = "macos"))]
+fn ansi_key_code(letter: char) -> Option<u16> {
+    const CODES: [u16; 26] = [
+        0x00, 0x0B, 0x08, 0x02, 0x0E, 0x03, 0x05, 0x04, 0x22, 0x26, 0x28, 0x25, 0x2E, 0x2D, 0x1F,
+        0x23, 0x0C, 0x0F, 0x01, 0x11, 0x20, 0x09, 0x0D, 0x07, 0x10, 0x06,
+    ];
+    letter
+        .is_ascii_lowercase()
+        .then(|| CODES[(letter as u8 - b'a') as usize])
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use enigo::Key;
+    use windows::Win32::UI::Input::KeyboardAndMouse::{GetKeyboardLayout, VkKeyScanExW};
+    use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
+
+    pub(super) fn shortcut_key(letter: char) -> Key {
+        // SAFETY: plain Win32 queries; a missing foreground window yields
+        // thread 0, i.e. our own layout.
+        let scan = unsafe {
+            let thread = GetWindowThreadProcessId(GetForegroundWindow(), None);
+            let layout = GetKeyboardLayout(thread);
+            VkKeyScanExW(letter as u16, layout)
+        };
+        // Layouts without the letter: apps match the shortcut on the
+        // letter's virtual key, which equals its uppercase ASCII code.
+        let vk = super::plain_virtual_key(scan).unwrap_or(letter.to_ascii_uppercase() as u16);
+        Key::Raw(vk)
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::ffi::c_void;
+
+    use core_foundation::base::{CFRelease, CFTypeRef, TCFType};
+    use core_foundation::boolean::CFBoolean;
+    use core_foundation::string::CFStringRef;
+    use enigo::Key;
+
+    #[link(name = "Carbon", kind = "framework")]
+    extern "C" {
+        static kTISPropertyInputSourceIsASCIICapable: CFStringRef;
+        fn TISCopyCurrentKeyboardInputSource() -> *const c_void;
+        fn TISGetInputSourceProperty(source: *const c_void, key: CFStringRef) -> CFTypeRef;
+    }
+
+    fn layout_is_ascii_capable() -> bool {
+        // SAFETY: the input source is released after reading a property it
+        // owns (get rule).
+        unsafe {
+            let source = TISCopyCurrentKeyboardInputSource();
+            if source.is_null() {
+                return true;
+            }
+            let value = TISGetInputSourceProperty(source, kTISPropertyInputSourceIsASCIICapable);
+            let capable = value == CFBoolean::true_value().as_CFTypeRef();
+            CFRelease(source as CFTypeRef);
+            capable
+        }
+    }
+
+    pub(super) fn shortcut_key(letter: char) -> Key {
+        if layout_is_ascii_capable() {
+            return Key::Layout(letter);
+        }
+        // Cyrillic, Greek, …: Cmd shortcuts use the ANSI key positions.
+        super::ansi_key_code(letter).map_or(Key::Layout(letter), Key::Raw)
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+mod platform {
+    use enigo::Key;
+
+    /// xdo maps the keysym to whatever keycode produces it on the current
+    /// layout, remapping a spare keycode when the layout lacks it.
+    pub(super) fn shortcut_key(letter: char) -> Key {
+        Key::Layout(letter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_shortcut_keys_for_common_layouts() {
+        // VkKeyScanEx('v') on US, German QWERTZ and French AZERTY.
+        assert_eq!(plain_virtual_key(0x0056), Some(0x56));
+        // 'z' on QWERTZ sits on the key US calls Y, but keeps VK_Z.
+        assert_eq!(plain_virtual_key(0x005A), Some(0x5A));
+        // Russian has no 'v'; AZERTY needs Shift for digits.
+        assert_eq!(plain_virtual_key(-1), None);
+        assert_eq!(plain_virtual_key(0x0131), None);
+
+        assert_eq!(ansi_key_code('v'), Some(0x09));
+        assert_eq!(ansi_key_code('z'), Some(0x06));
+        assert_eq!(ansi_key_code('a'), Some(0x00));
+        assert_eq!(ansi_key_code('é'), None);
+    }
+
+    #[test]
+    fn types_line_breaks_and_tabs_as_keys() {
+        assert_eq!(
+            typing_chunks("Grüße\r\nName:\tSam\n"),
+            vec![
+                TypedChunk::Text("Grüße"),
+                TypedChunk::Return,
+                TypedChunk::Text("Name:"),
+                TypedChunk::Tab,
+                TypedChunk::Text("Sam"),
+                TypedChunk::Return,
+            ]
+        );
+        assert_eq!(typing_chunks("plain"), vec![TypedChunk::Text("plain")]);
+        assert!(typing_chunks("").is_empty());
+    }
+}
@@ -0,0 +1,171 @@
+//! Per-transcription latency breakdown and a rolling window of recent jobs.
+//!
+//! Splits the time from capture end to pasted text into the stages that can
+//! be tuned separately: everything before whisper runs (waiting behind earlier
+//! segments, WAV encoding, temp files, server ping, failed GPU attempts),
+//! whisper itself, post-processing, and the paste.
+
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use std::time::Instant;
+
+/// Jobs kept for `get_latency_stats`.
+const LATENCY_WINDOW: usize = 100;
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct LatencyBreakdown {
+    /// Capture end to whisper start; `None` for cloud transcription.
+    pub(crate) capture_to_whisper_ms: Option<u64>,
+    pub(crate) whisper_ms: u64,
+    pub(crate) postprocess_ms: u64,
+    /// `None` until pasted, and for pastes deferred to refinement.
+    pub(crate) paste_ms: Option<u64>,
+    /// Capture end to pasted text, including time queued behind other jobs.
+    pub(crate) total_ms: u64,
+    /// `server_warm`, `cli_gpu`, `cli_cpu` or the cloud source tag.
+    pub(crate) whisper_path: String,
+}
+
+impl LatencyBreakdown {
+    /// Breaks down the job whose capture ended at `captured_at`, using the
+    /// timing of the local whisper run on this thread if the job ran locally.
+    /// `job_ms` is the whole transcription job, the fallback whisper time.
+    pub(crate) fn for_job(
+        source: &str,
+        captured_at: Instant,
+        job_ms: u64,
+        postprocess_ms: u64,
+    ) -> Self {
+        let whisper_started = crate::transcription::take_whisper_started_at();
+        let (capture_to_whisper_ms, whisper_ms, whisper_path) = if source.starts_with("local") {
+            let timing = crate::transcription::last_transcription_timing_summary();
+            let whisper_ms = timing
+                .warm_server_inference_ms
+                .or(timing.cli_gpu_inference_ms)
+                .or(timing.cli_cpu_fallback_ms)
+                .unwrap_or(job_ms)
+                .min(job_ms);
+            let capture_to_whisper_ms = whisper_started
+                .map(|started| started.saturating_duration_since(captured_at).as_millis() as u64);
+            (capture_to_whisper_ms, whisper_ms, timing.whisper_path)
+        } else {
+            (None, job_ms, source.to_string())
+        };
+        Self {
+            capture_to_whisper_ms,
+            whisper_ms,
+            postprocess_ms,
+            paste_ms: None,
+            total_ms: captured_at.elapsed().as_millis() as u64,
+            whisper_path,
+        }
+    }
+
+    pub(crate) fn with_paste(mut self, paste_ms: u64) -> Self {
+        self.paste_ms = Some(paste_ms);
+        self.total_ms += paste_ms;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct StageStats {
+    pub(crate) samples: usize,
+    pub(crate) avg_ms: u64,
+    pub(crate) p50_ms: u64,
+    pub(crate) p95_ms: u64,
+    pub(crate) max_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct LatencyStats {
+    pub(crate) jobs: usize,
+    pub(crate) capture_to_whisper: Option<StageStats>,
+    pub(crate) whisper: Option<StageStats>,
+    pub(crate) postprocess: Option<StageStats>,
+    pub(crate) paste: Option<StageStats>,
+    pub(crate) total: Option<StageStats>,
+    /// Oldest first.
+    pub(crate) recent: Vec<LatencyBreakdown>,
+}
+
+static RECENT: OnceLock<Mutex<VecDeque<LatencyBreakdown>>> = OnceLock::new();
+
+fn recent() -> MutexGuard<'static, VecDeque<LatencyBreakdown>> {
+    RECENT
+        .get_or_init(|| Mutex::new(VecDeque::with_capacity(LATENCY_WINDOW)))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+pub(crate) fn record(breakdown: LatencyBreakdown) {
+    let mut recent = recent();
+    if recent.len() == LATENCY_WINDOW {
+        recent.pop_front();
+    }
+    recent.push_back(breakdown);
+}
+
+fn stage_stats(mut values: Vec<u64>) -> Option<StageStats> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable();
+    let percentile = |p: usize| values[(values.len() - 1) * p / 100];
+    Some(StageStats {
+        samples: values.len(),
+        avg_ms: values.iter().sum::<u64>() / values.len() as u64,
+        p50_ms: percentile(50),
+        p95_ms: percentile(95),
+        max_ms: values[values.len() - 1],
+    })
+}
+
+fn summarize(entries: &VecDeque<LatencyBreakdown>) -> LatencyStats {
+    let collect = |stage: fn(&LatencyBreakdown) -> Option<u64>| {
+        stage_stats(entries.iter().filter_map(stage).collect())
+    };
+    LatencyStats {
+        jobs: entries.len(),
+        capture_to_whisper: collect(|b| b.capture_to_whisper_ms),
+        whisper: collect(|b| Some(b.whisper_ms)),
+        postprocess: collect(|b| Some(b.postprocess_ms)),
+        paste: collect(|b| b.paste_ms),
+        total: collect(|b| Some(b.total_ms)),
+        recent: entries.iter().cloned().collect(),
+    }
+}
+
+#[tauri::command]
+pub(crate) fn get_latency_stats() -> LatencyStats {
+    summarize(&recent())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarize_skips_missing_stages_and_computes_percentiles() {
+        let entries: VecDeque<LatencyBreakdown> = (1..=20)
+            .map(|i| LatencyBreakdown {
+                capture_to_whisper_ms: (i % 2 == 0).then_some(i * 10),
+                whisper_ms: i * 100,
+                postprocess_ms: 5,
+                paste_ms: None,
+                total_ms: i * 100 + 5,
+                whisper_path: "cli_gpu".to_string(),
+            })
+            .collect();
+
+        let stats = summarize(&entries);
+        assert_eq!(stats.jobs, 20);
+        assert_eq!(stats.capture_to_whisper.unwrap().samples, 10);
+        let whisper = stats.whisper.unwrap();
+        assert_eq!(whisper.p50_ms, 1000);
+        assert_eq!(whisper.p95_ms, 1900);
+        assert_eq!(whisper.max_ms, 2000);
+        assert!(stats.paste.is_none());
+    }
+}
@@ -0,0 +1,167 @@
+//! Live input meter for the device picker.
+//!
+//! `start_level_probe` opens a stream of its own on any input device, with the
+//! configured gain and DSP applied, and emits `probe:level` about twenty times
+//! a second, so the settings page can show whether a mic picks anything up
+//! before it is selected. The recorder, VAD monitor and PTT standby are never
+//! touched. Only one probe runs at a time and it stops by itself after a
+//! while in case the page never calls `stop_level_probe`.
+
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{info, warn};
+
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
+use crate::state::AppState;
+use crate::transcription::rms_i16;
+
+pub(crate) const PROBE_LEVEL_EVENT: &str = "probe:level";
+const PROBE_EMIT_INTERVAL: Duration = Duration::from_millis(50);
+const PROBE_MAX_LIFETIME: Duration = Duration::from_secs(120);
+const PROBE_OPEN_TIMEOUT: Duration = Duration::from_secs(3);
+
+static PROBE_STOP: Mutex<Option<Sender<()>>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ProbeLevel {
+    pub(crate) device_id: String,
+    /// Same scale as the overlay meter (RMS x 2.5, capped at 1).
+    pub(crate) level: f32,
+    pub(crate) peak: f32,
+    /// The device went away; no further events follow.
+    pub(crate) lost: bool,
+}
+
+fn probe_level(samples: &[i16]) -> (f32, f32) {
+    let level = (rms_i16(samples) * 2.5).min(1.0);
+    let peak = samples
+        .iter()
+        .map(|sample| sample.unsigned_abs() as f32 / i16::MAX as f32)
+        .fold(0.0, f32::max)
+        .min(1.0);
+    (level, peak)
+}
+
+/// Stops the running probe, if any.
+pub(crate) fn stop_probe() {
+    let stop_tx = PROBE_STOP
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .take();
+    if let Some(stop_tx) = stop_tx {
+        let _ = stop_tx.send(());
+    }
+}
+
+/// Meters `device_id` until `stop_level_probe`, replacing any running probe.
+#[tauri::command]
+pub(crate) async fn start_level_probe(app: AppHandle, device_id: String) -> CommandResult<()> {
+    if crate::privacy_mute::is_muted() {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "Privacy mute is on; release it to test a microphone",
+        ));
+    }
+    stop_probe();
+
+    let mut settings = app
+        .state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    settings.input_device = device_id.clone();
+    let (stop_tx, stop_rx) = mpsc::channel::<()>();
+    let (ready_tx, ready_rx) = mpsc::channel::<Result<(), String>>();
+    // Registered before the device opens, so a stop that arrives while it is
+    // opening is waiting for the loop below.
+    *PROBE_STOP
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(stop_tx.clone());
+
+    crate::util::spawn_guarded("level_probe", move || {
+        let capture = match crate::audio::open_mic_capture(&settings) {
+            Ok(capture) => {
+                let _ = ready_tx.send(Ok(()));
+                capture
+            }
+            Err(err) => {
+                let _ = ready_tx.send(Err(err));
+                return;
+            }
+        };
+        info!("Level probe started on '{}'", device_id);
+        let started = Instant::now();
+        loop {
+            match stop_rx.recv_timeout(PROBE_EMIT_INTERVAL) {
+                Err(RecvTimeoutError::Timeout) => {}
+                Ok(()) | Err(RecvTimeoutError::Disconnected) => break,
+            }
+            let samples = capture
+                .buffer
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .drain();
+            let (level, peak) = probe_level(&samples);
+            let lost = capture.stream_lost();
+            let _ = app.emit(
+                PROBE_LEVEL_EVENT,
+                ProbeLevel {
+                    device_id: device_id.clone(),
+                    level,
+                    peak,
+                    lost,
+                },
+            );
+            if lost {
+                warn!("Level probe device '{}' disconnected", device_id);
+                break;
+            }
+            if started.elapsed() >= PROBE_MAX_LIFETIME {
+                info!("Level probe on '{}' timed out", device_id);
+                break;
+            }
+        }
+    });
+
+    let opened = tauri::async_runtime::spawn_blocking(move || {
+        ready_rx
+            .recv_timeout(PROBE_OPEN_TIMEOUT)
+            .unwrap_or_else(|_| Err("Failed to open the input device".to_string()))
+    })
+    .await
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("start_level_probe task failed: {}", e),
+        )
+    })?;
+    if opened.is_err() {
+        // A device that opens after the timeout must not keep metering.
+        let _ = stop_tx.send(());
+    }
+    opened.with_code(ErrorCode::AudioDevice)
+}
+
+#[tauri::command]
+pub(crate) fn stop_level_probe() {
+    stop_probe();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn probe_level_matches_meter_scale() {
+        assert_eq!(probe_level(&[]), (0.0, 0.0));
+        let (level, peak) = probe_level(&[i16::MAX / 10, -(i16::MAX / 10)]);
+        assert!((level - 0.25).abs() < 0.001);
+        assert!((peak - 0.1).abs() < 0.001);
+        assert_eq!(probe_level(&[i16::MIN]).1, 1.0);
+    }
+}
@@ -1,43 +1,107 @@
 // Trispr Flow - core app runtime
 #![allow(clippy::needless_return)]
 
+mod activation_words;
 mod ai_fallback;
+mod app_overrides;
 mod assistant_presence;
 mod audio;
+mod audio_cues;
+mod audio_dsp;
+mod backlog_adaptation;
+mod batch_queue;
+mod caption_output;
+mod capture_policy;
+mod capture_schedule;
+mod chunk_dedup;
+mod clipboard_formats;
+mod cloud_transcription;
 mod confluence;
 mod constants;
+mod content_filter;
+mod context_priming;
 mod continuous_dump;
+mod conversation;
+mod crash_report;
 mod data_migration;
+mod detected_language;
+mod device_monitor;
+mod dictation_macros;
+mod echo_reference;
+mod elevation;
+mod entry_audio;
 mod errors;
+mod event_stream;
+mod failed_segments;
+mod file_transcription;
+mod gamepad_ptt;
 mod gdd;
+mod guided_setup;
+mod hallucination_filter;
+mod hardware_probe;
+mod history_crypto;
+mod history_export;
 mod history_partition;
+mod hotkey_capture;
 mod hotkeys;
+mod http_api;
+mod keyboard_layout;
+mod latency_stats;
+mod level_probe;
+mod live_captions;
+mod llm_cleanup;
+mod logging;
+mod mcp_server;
+mod model_metadata;
 mod models;
 mod modules;
+mod mouse_hotkeys;
 mod multimodal_io;
 mod ollama_runtime;
 mod opus;
 mod overlay;
 mod paste_arbiter;
+mod paste_undo;
 mod paths;
 mod postprocessing;
+mod privacy_mute;
 mod refinement_adaptation;
 mod runtime_commands;
+mod runtime_supervisor;
+mod scratch;
 mod session_manager;
+mod session_summary;
+mod settings_profiles;
+mod settings_transfer;
+mod snippets;
 mod state;
+mod text_injection;
+mod transcribe_pool;
 mod transcription;
+mod transcription_jobs;
+mod transcription_retry;
+mod translation;
 mod tts_benchmark;
 mod uiautomation_capture;
+mod usage_stats;
 mod util;
+mod vad_calibration;
 mod video_generation;
 mod video_ingest;
+mod voice_commands;
+mod wake_word;
+mod watch_folders;
 mod weather;
+mod webhooks;
+mod whisper_backends;
+mod whisper_params;
+mod whisper_runtime;
 mod whisper_server;
 mod workflow_agent;
 
 use arboard::{Clipboard, ImageData};
-use enigo::{Enigo, Key, KeyboardControllable};
-use errors::{AppError, ErrorEvent};
+use enigo::{Enigo, Key};
+use errors::{AppError, CommandError, CommandResult, ErrorCode, ErrorEvent, WithErrorCode};
 use overlay::emit_capture_idle_overlay;
 use state::{AppState, RuntimeDiagnostics, Settings, StartupStatus};
 use std::collections::HashSet;
@@ -63,9 +127,28 @@ use tauri::{AppHandle, Emitter, Listener, Manager, State};
 use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
 use tracing::{error, info, warn};
 
+pub(crate) use activation_words::{
+    add_activation_word, list_activation_words, remove_activation_word, test_activation,
+};
 pub(crate) use audio::{
     get_last_recording_path, get_recordings_directory, open_recordings_directory,
 };
+pub(crate) use audio_cues::{list_sound_packs, preview_cue};
+pub(crate) use batch_queue::{get_batch_queue, pause_queue, remove_batch_job, resume_queue};
+pub(crate) use caption_output::get_caption_file_path;
+pub(crate) use capture_policy::get_capture_policy_state;
+pub(crate) use capture_schedule::get_schedule_status;
+pub(crate) use cloud_transcription::{
+    clear_cloud_credentials, get_cloud_credentials_status, set_cloud_credentials,
+};
+pub(crate) use conversation::get_conversation;
+pub(crate) use crash_report::get_last_crash_report;
+pub(crate) use entry_audio::{delete_entry_audio, get_entry_audio_path};
+pub(crate) use failed_segments::{
+    discard_failed_segment, list_failed_segments, retry_failed_segment,
+};
+pub(crate) use file_transcription::transcribe_file;
+pub(crate) use gamepad_ptt::{capture_gamepad_button, list_gamepads};
 #[cfg(feature = "module-confluence")]
 pub(crate) use gdd::confluence::{
     clear_confluence_secret, confluence_list_spaces, confluence_oauth_exchange,
@@ -79,27 +162,75 @@ pub(crate) use gdd::{
     detect_gdd_preset, generate_gdd_draft, list_gdd_presets, render_gdd_for_confluence,
     render_gdd_markdown, save_gdd_preset_clone, validate_gdd_draft,
 };
+pub(crate) use guided_setup::{run_mic_test, setup_status, verify_whisper_runtime};
+pub(crate) use hallucination_filter::{
+    add_allowed_phrase, add_hallucination_phrase, list_hallucination_phrases,
+    remove_allowed_phrase, remove_hallucination_phrase,
+};
+pub(crate) use history_crypto::{
+    get_history_encryption_status, set_history_encryption, unlock_history,
+};
+pub(crate) use history_export::export_history;
 pub(crate) use history_partition::{
     add_history_entry, add_transcribe_entry, clear_active_transcript_history,
-    delete_active_transcript_entry, get_history, get_transcribe_history, list_history_partitions,
-    load_history_partition, save_transcript,
+    delete_active_transcript_entry, edit_history_entry, get_history, get_transcribe_history,
+    list_history_partitions, list_tags, load_history_partition, paste_history_entry,
+    prune_history_now, retranscribe_entry, save_transcript, update_history_entry,
 };
+pub(crate) use hotkey_capture::capture_next_hotkey;
 pub(crate) use hotkeys::{get_hotkey_conflicts, test_hotkey, validate_hotkey};
+pub(crate) use http_api::{get_http_api_info, regenerate_http_api_token};
+pub(crate) use latency_stats::get_latency_stats;
+pub(crate) use level_probe::{start_level_probe, stop_level_probe};
+pub(crate) use llm_cleanup::{
+    clear_postproc_llm_api_key, get_postproc_llm_api_key_status, set_postproc_llm_api_key,
+    test_postproc_llm,
+};
+pub(crate) use logging::{get_log_path, set_log_level};
 pub(crate) use modules::task_capture::{
     get_task_capture_settings, save_task_capture_settings, test_task_capture_endpoint,
 };
 pub(crate) use multimodal_io::{
     capture_vision_snapshot, download_piper_voice_key, get_vision_stream_health,
-    list_piper_voice_catalog, list_screen_sources, list_tts_providers, list_tts_voices, speak_tts,
-    start_vision_stream, stop_tts, stop_vision_stream, test_tts_provider,
+    list_piper_voice_catalog, list_screen_sources, list_tts_providers, list_tts_voices,
+    remove_piper_voice_key, speak_tts, start_vision_stream, stop_tts, stop_vision_stream,
+    test_tts_provider,
 };
 pub(crate) use opus::{check_ffmpeg, encode_to_opus, get_ffmpeg_version_info};
+pub(crate) use overlay::{
+    list_overlay_monitors, overlay_drag_end, overlay_drag_start, overlay_set_move_mode,
+    overlay_subscribe_levels,
+};
+pub(crate) use paste_undo::undo_last_paste;
 pub(crate) use paths::open_log_directory;
-pub(crate) use session_manager::{clear_crash_recovery, save_crash_recovery};
-pub(crate) use tts_benchmark::{run_latency_benchmark, run_tts_benchmark};
+pub(crate) use postprocessing::{get_replacement_rules, save_replacement_rules};
+pub(crate) use privacy_mute::{get_privacy_mute, set_privacy_mute};
+pub(crate) use runtime_supervisor::get_whisper_supervisor_status;
+pub(crate) use session_manager::{
+    add_chapter, clear_crash_recovery, delete_recording, export_session_markdown,
+    export_session_srt, get_call_recording, get_session_transcript, list_chapters, list_recordings,
+    list_sessions, rename_chapter, reveal_recording, save_crash_recovery, start_call_recording,
+    start_transcript_session, stop_call_recording, stop_transcript_session,
+};
+pub(crate) use session_summary::summarize_session;
+pub(crate) use settings_profiles::{activate_profile, delete_profile, list_profiles, save_profile};
+pub(crate) use settings_transfer::{export_settings, import_settings};
+pub(crate) use snippets::{delete_snippet, list_snippets, save_snippet};
+pub(crate) use translation::{
+    clear_translation_credentials, get_translation_credentials_status, set_translation_credentials,
+    translate_text,
+};
+pub(crate) use tts_benchmark::{benchmark_model, run_latency_benchmark, run_tts_benchmark};
+pub(crate) use usage_stats::get_usage_stats;
 pub(crate) use util::{frontend_heartbeat, log_frontend_event};
+pub(crate) use vad_calibration::calibrate_vad;
 pub(crate) use video_generation::{video_generate, video_get_output_dir, video_open_output_dir};
 pub(crate) use video_ingest::{video_ingest_history_entry, video_ingest_sources};
+pub(crate) use wake_word::{download_wake_word_model, list_wake_word_models};
+pub(crate) use webhooks::test_webhook;
+pub(crate) use whisper_backends::list_whisper_backends;
+pub(crate) use whisper_params::{get_whisper_params, set_whisper_params};
+pub(crate) use whisper_runtime::{check_runtime_update, install_runtime};
 pub(crate) use workflow_agent::{
     agent_build_execution_plan, agent_cancel_pending_confirmation, agent_compose_unknown_reply,
     agent_execute_gdd_plan, agent_list_supported_actions, agent_parse_command,
@@ -117,18 +248,27 @@ macro_rules! guarded_command {
             Err(payload) => {
                 let msg = crate::format_panic_payload(&*payload);
                 tracing::error!("Command '{}' panicked: {}", $label, msg);
-                Err(format!("Internal error in {}: {}", $label, msg))
+                Err(crate::errors::CommandError::new(
+                    crate::errors::ErrorCode::Internal,
+                    format!("Internal error in {}: {}", $label, msg),
+                )
+                .into())
             }
         }
     };
 }
 
 use crate::ai_fallback::provider::ping_ollama_quick;
-use crate::audio::{list_audio_devices, list_output_devices, start_recording, stop_recording};
+use crate::audio::{
+    get_input_device_channels, list_audio_devices, list_output_devices, start_recording,
+    stop_recording,
+};
+use crate::hardware_probe::{get_hardware_profile, recommend_model};
 use crate::history_partition::PartitionedHistory;
 use crate::models::{
-    check_model_available, clear_hidden_external_models, download_model, get_models_dir,
-    hide_external_model, list_models, pick_model_dir, quantize_model, remove_model,
+    cancel_download, check_model_available, clear_hidden_external_models, download_model,
+    get_download_queue, get_models_dir, hide_external_model, import_model, list_models,
+    pause_download, pick_model_dir, quantize_model, remove_model, resume_download,
 };
 use crate::modules::{
     canonicalize_module_id, health as module_health, normalize_confluence_settings,
@@ -144,9 +284,11 @@ use crate::state::{
     save_settings_file, sync_model_dir_env, AI_REFINEMENT_MODULE_ID,
 };
 use crate::transcription::{
-    expand_transcribe_backlog as expand_transcribe_backlog_inner, start_transcribe_monitor,
-    stop_transcribe_monitor_and_release_whisper, toggle_transcribe_state,
+    expand_transcribe_backlog as expand_transcribe_backlog_inner, set_translate_session_override,
+    start_transcribe_monitor, stop_transcribe_monitor_and_release_whisper, toggle_transcribe_state,
 };
+use crate::transcription_jobs::{cancel_job, get_jobs};
+use crate::transcription_retry::{list_retained_jobs, retry_job};
 pub(crate) use ai_fallback::commands::{
     clear_provider_api_key, delete_ollama_model, detect_ollama_runtime, download_ollama_runtime,
     fetch_available_models, fetch_ollama_models_with_size, fetch_ollama_online_versions,
@@ -191,6 +333,11 @@ static CLIPBOARD_PASTE_GENERATION: AtomicU64 = AtomicU64::new(0);
 static LAST_GEOMETRY_SAVE_MS: AtomicU64 = AtomicU64::new(0);
 static PTT_KEY_HELD: AtomicBool = AtomicBool::new(false);
 static PTT_PRESS_IN_FLIGHT: AtomicBool = AtomicBool::new(false);
+static PTT_LOCK: Mutex<hotkeys::PttLockTracker> = Mutex::new(hotkeys::PttLockTracker::new());
+static HOTKEY_GESTURE: Mutex<hotkeys::GestureTracker> = Mutex::new(hotkeys::GestureTracker::new(
+    constants::HOTKEY_GESTURE_HOLD_MS_DEFAULT,
+    constants::HOTKEY_GESTURE_DOUBLE_TAP_MS_DEFAULT,
+));
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default)]
 struct FrontendRestartLedger {
@@ -824,6 +971,179 @@ fn is_already_registered_error(err: &str) -> bool {
     lower.contains("already registered") || lower.contains("hotkey already")
 }
 
+/// Push-to-lock filter in front of the PTT handlers. Returns false when the
+/// event was consumed: a tap that locked recording on, the press that ends a
+/// locked recording, or key repeat.
+fn ptt_lock_passes(app: &AppHandle, pressed: bool) -> bool {
+    let (enabled, tap_ms) = {
+        let settings = app
+            .state::<AppState>()
+            .settings
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        (settings.ptt_lock_enabled, settings.ptt_lock_tap_ms)
+    };
+    if !enabled {
+        return true;
+    }
+    let now = crate::util::now_ms();
+    let action = if pressed {
+        let recording = app
+            .state::<AppState>()
+            .recorder
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .active;
+        PTT_LOCK
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .on_press(now, recording)
+    } else {
+        PTT_LOCK
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .on_release(now, tap_ms)
+    };
+    match action {
+        Some(hotkeys::PttLockAction::Start) => true,
+        Some(hotkeys::PttLockAction::Stop) if !pressed => true,
+        Some(hotkeys::PttLockAction::Stop) => {
+            info!("PTT lock ended by press");
+            let _ = app.emit("ptt:locked", false);
+            crate::audio::handle_ptt_release_async(app.clone());
+            false
+        }
+        Some(hotkeys::PttLockAction::Lock) => {
+            info!("PTT locked by tap");
+            let _ = app.emit("ptt:locked", true);
+            false
+        }
+        None => false,
+    }
+}
+
+pub(crate) fn on_ptt_hotkey(app: &AppHandle, pressed: bool) {
+    if !ptt_lock_passes(app, pressed) {
+        return;
+    }
+    let app = app.clone();
+    if pressed {
+        PTT_KEY_HELD.store(true, Ordering::Release);
+        info!("PTT hotkey pressed");
+        if PTT_PRESS_IN_FLIGHT
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+        {
+            crate::util::spawn_guarded("ptt_hotkey_press", move || {
+                struct InFlightReset;
+                impl Drop for InFlightReset {
+                    fn drop(&mut self) {
+                        PTT_PRESS_IN_FLIGHT.store(false, Ordering::Release);
+                    }
+                }
+                let _in_flight_reset = InFlightReset;
+
+                if let Err(err) = crate::audio::handle_ptt_press(&app) {
+                    error!("PTT hotkey press handler failed: {}", err);
+                    emit_error(
+                        &app,
+                        AppError::AudioDevice(format!("PTT startup failed: {}", err.trim())),
+                        Some("PTT"),
+                    );
+                    return;
+                }
+
+                // Release can arrive while press-handling work is still in flight.
+                // If so, complete the pending stop after press initialization.
+                if !PTT_KEY_HELD.load(Ordering::Acquire) {
+                    crate::audio::handle_ptt_release_async(app.clone());
+                }
+            });
+        } else {
+            warn!("PTT press ignored while previous press handling is still active");
+        }
+    } else {
+        PTT_KEY_HELD.store(false, Ordering::Release);
+        info!("PTT hotkey released");
+        crate::audio::handle_ptt_release_async(app);
+    }
+}
+
+fn toggle_transcribe_from_hotkey(app: &AppHandle) {
+    let was_enabled = app
+        .state::<AppState>()
+        .settings
+        .read()
+        .map(|settings| settings.transcribe_enabled)
+        .unwrap_or(false);
+    let target_enabled = !was_enabled;
+    let effective_enabled = match set_transcribe_enabled(app, target_enabled) {
+        Ok(enabled) => enabled,
+        Err(err) => {
+            emit_error(app, AppError::AudioDevice(err), Some("System Audio"));
+            return;
+        }
+    };
+    if effective_enabled != was_enabled {
+        let cue = if effective_enabled { "start" } else { "stop" };
+        audio_cues::play_cue(app, cue);
+    }
+}
+
+/// Mouse-hook events, delivered on the hook's worker thread.
+#[cfg(target_os = "windows")]
+pub(crate) fn on_mouse_hotkey(app: &AppHandle, action: mouse_hotkeys::MouseAction, pressed: bool) {
+    match action {
+        mouse_hotkeys::MouseAction::Ptt => on_ptt_hotkey(app, pressed),
+        mouse_hotkeys::MouseAction::Toggle if pressed => {
+            info!("Toggle mouse binding pressed");
+            crate::audio::handle_toggle_async(app.clone());
+        }
+        mouse_hotkeys::MouseAction::Toggle => {}
+    }
+}
+
+fn dispatch_hotkey_gesture(app: &AppHandle, action: hotkeys::GestureAction) {
+    info!("Gesture hotkey: {:?}", action);
+    match action {
+        hotkeys::GestureAction::Tap => crate::audio::handle_toggle_async(app.clone()),
+        hotkeys::GestureAction::DoubleTap => toggle_transcribe_from_hotkey(app),
+        hotkeys::GestureAction::HoldStart => on_ptt_hotkey(app, true),
+        hotkeys::GestureAction::HoldEnd => on_ptt_hotkey(app, false),
+    }
+}
+
+/// Feeds a press/release of the gesture hotkey into the tracker, arming the
+/// hold / double-tap timer it asks for.
+fn on_gesture_hotkey(app: &AppHandle, pressed: bool) {
+    let (action, timer) = {
+        let mut tracker = HOTKEY_GESTURE
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if pressed {
+            tracker.on_press()
+        } else {
+            tracker.on_release()
+        }
+    };
+    if let Some(action) = action {
+        dispatch_hotkey_gesture(app, action);
+    }
+    if let Some(timer) = timer {
+        let app = app.clone();
+        crate::util::spawn_guarded("hotkey_gesture_timer", move || {
+            thread::sleep(Duration::from_millis(timer.delay_ms));
+            let action = HOTKEY_GESTURE
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .on_timer(timer.generation);
+            if let Some(action) = action {
+                dispatch_hotkey_gesture(&app, action);
+            }
+        });
+    }
+}
+
 fn register_hotkeys(app: &AppHandle, settings: &Settings) -> Result<(), String> {
     let manager = app.global_shortcut();
 
@@ -870,7 +1190,7 @@ fn register_hotkeys(app: &AppHandle, settings: &Settings) -> Result<(), String>
 
     let register_ptt = || -> Result<(), String> {
         let ptt = settings.hotkey_ptt.trim();
-        if ptt.is_empty() {
+        if ptt.is_empty() || mouse_hotkeys::is_mouse_binding(ptt) {
             return Ok(());
         }
         if !try_claim(ptt, "PTT") {
@@ -878,50 +1198,7 @@ fn register_hotkeys(app: &AppHandle, settings: &Settings) -> Result<(), String>
         }
         info!("Registering PTT hotkey (hold): {}", ptt);
         match manager.on_shortcut(ptt, |app, _shortcut, event| {
-            let app = app.clone();
-            if event.state == ShortcutState::Pressed {
-                PTT_KEY_HELD.store(true, Ordering::Release);
-                info!("PTT hotkey pressed");
-                if PTT_PRESS_IN_FLIGHT
-                    .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
-                    .is_ok()
-                {
-                    crate::util::spawn_guarded("ptt_hotkey_press", move || {
-                        struct InFlightReset;
-                        impl Drop for InFlightReset {
-                            fn drop(&mut self) {
-                                PTT_PRESS_IN_FLIGHT.store(false, Ordering::Release);
-                            }
-                        }
-                        let _in_flight_reset = InFlightReset;
-
-                        if let Err(err) = crate::audio::handle_ptt_press(&app) {
-                            error!("PTT hotkey press handler failed: {}", err);
-                            emit_error(
-                                &app,
-                                AppError::AudioDevice(format!(
-                                    "PTT startup failed: {}",
-                                    err.trim()
-                                )),
-                                Some("PTT"),
-                            );
-                            return;
-                        }
-
-                        // Release can arrive while press-handling work is still in flight.
-                        // If so, complete the pending stop after press initialization.
-                        if !PTT_KEY_HELD.load(Ordering::Acquire) {
-                            crate::audio::handle_ptt_release_async(app.clone());
-                        }
-                    });
-                } else {
-                    warn!("PTT press ignored while previous press handling is still active");
-                }
-            } else {
-                PTT_KEY_HELD.store(false, Ordering::Release);
-                info!("PTT hotkey released");
-                crate::audio::handle_ptt_release_async(app);
-            }
+            on_ptt_hotkey(app, event.state == ShortcutState::Pressed);
         }) {
             Ok(_) => {
                 info!("PTT hotkey registered successfully");
@@ -947,7 +1224,7 @@ fn register_hotkeys(app: &AppHandle, settings: &Settings) -> Result<(), String>
 
     let register_toggle = || -> Result<(), String> {
         let toggle = settings.hotkey_toggle.trim();
-        if toggle.is_empty() {
+        if toggle.is_empty() || mouse_hotkeys::is_mouse_binding(toggle) {
             return Ok(());
         }
         if !try_claim(toggle, "Toggle") {
@@ -993,25 +1270,7 @@ fn register_hotkeys(app: &AppHandle, settings: &Settings) -> Result<(), String>
         info!("Registering Transcribe hotkey (toggle): {}", hotkey);
         match manager.on_shortcut(hotkey, |app, _shortcut, event| {
             if event.state == ShortcutState::Pressed {
-                let app = app.clone();
-                let was_enabled = app
-                    .state::<AppState>()
-                    .settings
-                    .read()
-                    .map(|settings| settings.transcribe_enabled)
-                    .unwrap_or(false);
-                let target_enabled = !was_enabled;
-                let effective_enabled = match set_transcribe_enabled(&app, target_enabled) {
-                    Ok(enabled) => enabled,
-                    Err(err) => {
-                        emit_error(&app, AppError::AudioDevice(err), Some("System Audio"));
-                        return;
-                    }
-                };
-                if effective_enabled != was_enabled {
-                    let cue = if effective_enabled { "start" } else { "stop" };
-                    let _ = app.emit("audio:cue", cue);
-                }
+                toggle_transcribe_from_hotkey(app);
             }
         }) {
             Ok(_) => {
@@ -1136,7 +1395,7 @@ fn register_hotkeys(app: &AppHandle, settings: &Settings) -> Result<(), String>
                 errors.push(format!("Toggle: {}", e));
             }
         }
-        "vad" => {}
+        "vad" | "wakeword" => {}
         _ => {
             if let Err(e) = register_ptt() {
                 errors.push(format!("PTT: {}", e));
@@ -1147,6 +1406,93 @@ fn register_hotkeys(app: &AppHandle, settings: &Settings) -> Result<(), String>
         }
     }
 
+    // Mouse buttons / modifier+wheel for PTT and toggle go through the mouse
+    // hook; an empty list releases it.
+    let mut mouse_bindings = Vec::new();
+    if !crate::state::mode_uses_vad_monitor(&settings.mode) {
+        let slots = [
+            (
+                "PTT",
+                settings.hotkey_ptt.trim(),
+                mouse_hotkeys::MouseAction::Ptt,
+            ),
+            (
+                "Toggle",
+                settings.hotkey_toggle.trim(),
+                mouse_hotkeys::MouseAction::Toggle,
+            ),
+        ];
+        for (slot, key, action) in slots {
+            match mouse_hotkeys::MouseBinding::parse(key) {
+                Some(Ok(binding))
+                    if binding.is_wheel() && action == mouse_hotkeys::MouseAction::Ptt =>
+                {
+                    errors.push(format!(
+                        "{}: '{}' has no release; bind the wheel to Toggle instead",
+                        slot, key
+                    ));
+                }
+                Some(Ok(binding)) if try_claim(&binding.to_string(), slot) => {
+                    info!("Registering {} mouse binding: {}", slot, binding);
+                    mouse_bindings.push((binding, action));
+                }
+                Some(Err(e)) => errors.push(format!("{}: {}", slot, e)),
+                Some(Ok(_)) | None => {}
+            }
+        }
+    }
+    if let Err(e) = mouse_hotkeys::set_bindings(app, mouse_bindings) {
+        emit_error(
+            app,
+            AppError::Hotkey(e.clone()),
+            Some("Hotkey Registration"),
+        );
+        errors.push(format!("Mouse: {}", e));
+    }
+
+    gamepad_ptt::apply_settings(app, settings);
+
+    // Gesture key: tap = toggle, double-tap = transcribe, hold = PTT.
+    let hotkey = settings.hotkey_gesture.trim();
+    if !crate::state::mode_uses_vad_monitor(&settings.mode)
+        && !hotkey.is_empty()
+        && try_claim(hotkey, "Gesture")
+    {
+        HOTKEY_GESTURE
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .configure(
+                settings.hotkey_gesture_hold_ms,
+                settings.hotkey_gesture_double_tap_ms,
+            );
+        info!(
+            "Registering Gesture hotkey (tap/double-tap/hold): {}",
+            hotkey
+        );
+        match manager.on_shortcut(hotkey, |app, _shortcut, event| {
+            on_gesture_hotkey(app, event.state == ShortcutState::Pressed);
+        }) {
+            Ok(_) => {
+                info!("Gesture hotkey registered successfully");
+            }
+            Err(e) => {
+                let err_str = e.to_string();
+                if is_already_registered_error(&err_str) {
+                    warn!(
+                        "Gesture hotkey '{}' is already held by another application — shortcut will not fire.",
+                        hotkey
+                    );
+                } else {
+                    error!(
+                        "Failed to register Gesture hotkey '{}': {}",
+                        hotkey, err_str
+                    );
+                    errors.push(format!("Gesture: {}", err_str));
+                }
+            }
+        }
+    }
+
     if let Err(e) = register_transcribe() {
         errors.push(format!("Transcribe: {}", e));
     }
@@ -1194,6 +1540,100 @@ fn register_hotkeys(app: &AppHandle, settings: &Settings) -> Result<(), String>
         }
     }
 
+    // Register Cycle Settings Profile hotkey
+    let hotkey = settings.hotkey_cycle_profile.trim();
+    if !hotkey.is_empty() && try_claim(hotkey, "Cycle Profile") {
+        match manager.on_shortcut(hotkey, |app, _shortcut, event| {
+            if event.state == ShortcutState::Pressed {
+                settings_profiles::activate_profile_async(app.clone(), None);
+            }
+        }) {
+            Ok(_) => {
+                info!("Cycle Profile hotkey registered successfully");
+            }
+            Err(e) => {
+                let err_str = e.to_string();
+                if is_already_registered_error(&err_str) {
+                    warn!(
+                        "Cycle Profile hotkey '{}' is already held by another application — shortcut will not fire.",
+                        hotkey
+                    );
+                } else {
+                    error!(
+                        "Failed to register Cycle Profile hotkey '{}': {}",
+                        hotkey, err_str
+                    );
+                    errors.push(format!("Cycle Profile: {}", err_str));
+                }
+            }
+        }
+    }
+
+    // Register Undo Paste hotkey. Fires on release so the hotkey's own
+    // modifiers do not combine with the deletion keystrokes.
+    let hotkey = settings.hotkey_undo_paste.trim();
+    if !hotkey.is_empty() && try_claim(hotkey, "Undo Paste") {
+        match manager.on_shortcut(hotkey, |app, _shortcut, event| {
+            if event.state == ShortcutState::Released {
+                let app = app.clone();
+                crate::util::spawn_guarded("undo_last_paste", move || {
+                    if let Err(e) = crate::paste_undo::undo_last_paste_inner(&app) {
+                        info!("Undo paste skipped: {}", e);
+                    }
+                });
+            }
+        }) {
+            Ok(_) => {
+                info!("Undo Paste hotkey registered successfully");
+            }
+            Err(e) => {
+                let err_str = e.to_string();
+                if is_already_registered_error(&err_str) {
+                    warn!(
+                        "Undo Paste hotkey '{}' is already held by another application — shortcut will not fire.",
+                        hotkey
+                    );
+                } else {
+                    error!(
+                        "Failed to register Undo Paste hotkey '{}': {}",
+                        hotkey, err_str
+                    );
+                    errors.push(format!("Undo Paste: {}", err_str));
+                }
+            }
+        }
+    }
+
+    // Register Privacy Mute hotkey (all modes: it must always be reachable)
+    let hotkey = settings.hotkey_privacy_mute.trim();
+    if !hotkey.is_empty() && try_claim(hotkey, "Privacy Mute") {
+        match manager.on_shortcut(hotkey, |app, _shortcut, event| {
+            if event.state == ShortcutState::Pressed {
+                privacy_mute::toggle(app);
+            }
+        }) {
+            Ok(_) => {
+                info!("Privacy Mute hotkey registered successfully");
+            }
+            Err(e) => {
+                let err_str = e.to_string();
+                error!(
+                    "Failed to register Privacy Mute hotkey '{}': {}",
+                    hotkey, err_str
+                );
+                errors.push(format!("Privacy Mute: {}", err_str));
+                emit_error(
+                    app,
+                    AppError::Hotkey(format!(
+                        "Could not register Privacy Mute hotkey '{}': {}",
+                        hotkey, err_str
+                    )),
+                    Some("Hotkey Registration"),
+                );
+            }
+        }
+    }
+
     // Emit registration status to frontend so UI can show conflict badges
     {
         let status = serde_json::json!({
@@ -1227,6 +1667,26 @@ fn register_hotkeys(app: &AppHandle, settings: &Settings) -> Result<(), String>
                 "registered": !errors.iter().any(|e| e.starts_with("TTS Stop")),
                 "error": errors.iter().find(|e| e.starts_with("TTS Stop")).cloned(),
             },
+            "gesture": {
+                "key": settings.hotkey_gesture.trim(),
+                "registered": !errors.iter().any(|e| e.starts_with("Gesture")),
+                "error": errors.iter().find(|e| e.starts_with("Gesture")).cloned(),
+            },
+            "cycle_profile": {
+                "key": settings.hotkey_cycle_profile.trim(),
+                "registered": !errors.iter().any(|e| e.starts_with("Cycle Profile")),
+                "error": errors.iter().find(|e| e.starts_with("Cycle Profile")).cloned(),
+            },
+            "undo_paste": {
+                "key": settings.hotkey_undo_paste.trim(),
+                "registered": !errors.iter().any(|e| e.starts_with("Undo Paste")),
+                "error": errors.iter().find(|e| e.starts_with("Undo Paste")).cloned(),
+            },
+            "privacy_mute": {
+                "key": settings.hotkey_privacy_mute.trim(),
+                "registered": !errors.iter().any(|e| e.starts_with("Privacy Mute")),
+                "error": errors.iter().find(|e| e.starts_with("Privacy Mute")).cloned(),
+            },
         });
         let _ = app.emit("hotkey:registration-status", &status);
     }
@@ -1377,6 +1837,8 @@ pub(crate) fn save_settings_inner(app: &AppHandle, settings: &mut Settings) -> R
         prev_local_backend_preference,
         prev_ai_refinement_enabled,
         prev_provider,
+        prev_wake_word,
+        prev_pre_roll_ms,
     ) = {
         let current = state
             .settings
@@ -1391,8 +1853,16 @@ pub(crate) fn save_settings_inner(app: &AppHandle, settings: &mut Settings) -> R
             current.local_backend_preference.clone(),
             current.ai_fallback.enabled,
             current.ai_fallback.provider.clone(),
+            wake_word::WakeWordConfig::from_settings(&current),
+            current.pre_roll_ms,
         )
     };
+    // Thresholds and gain follow the mic: keep what was tuned for the old
+    // device and bring back what was tuned for the new one.
+    crate::state::remember_device_input_profile(settings, &prev_device);
+    if prev_device != settings.input_device {
+        crate::state::apply_device_input_profile(settings);
+    }
     info!("[DIAG] save_settings_inner: normalizing");
     normalize_ai_fallback_fields(settings);
     normalize_continuous_dump_fields(settings);
@@ -1408,6 +1878,8 @@ pub(crate) fn save_settings_inner(app: &AppHandle, settings: &mut Settings) -> R
     normalize_vision_input_settings(&mut settings.vision_input_settings);
     normalize_voice_output_settings(&mut settings.voice_output_settings);
     normalize_task_capture_settings(&mut settings.task_capture_settings);
+    cloud_transcription::normalize_cloud_transcription_settings(&mut settings.cloud_transcription);
+    translation::normalize_translation_settings(&mut settings.translation);
     reconcile_assistant_transcribe_flag(settings);
 
     info!("[DIAG] save_settings_inner: acquiring settings lock (write)");
@@ -1419,6 +1891,10 @@ pub(crate) fn save_settings_inner(app: &AppHandle, settings: &mut Settings) -> R
         *current = settings.clone();
     }
     crate::state::sync_diagnostic_logging_enabled(settings);
+    crate::logging::sync_log_level(settings);
+    crate::capture_schedule::enforce(app);
+    crate::http_api::sync_http_api(app, settings);
+    crate::watch_folders::sync_watch_folders(app, settings);
     info!("[DIAG] save_settings_inner: saving file");
     sync_model_dir_env(settings);
     save_settings_file(app, settings)?;
@@ -1471,24 +1947,28 @@ pub(crate) fn save_settings_inner(app: &AppHandle, settings: &mut Settings) -> R
 
     info!("[DIAG] save_settings_inner: hotkeys spawned, acquiring recorder lock");
     if let Ok(recorder) = state.recorder.lock() {
-        recorder.input_gain_db.store(
-            (settings.mic_input_gain_db * 1000.0) as i64,
-            Ordering::Relaxed,
-        );
+        recorder.apply_input_settings(settings);
     }
+    echo_reference::sync_echo_reference(settings);
     info!("[DIAG] save_settings_inner: recorder lock released, checking mode change");
 
     let mode_changed = prev_mode != settings.mode;
     let device_changed = prev_device != settings.input_device;
+    let uses_vad_monitor = crate::state::mode_uses_vad_monitor(&settings.mode);
+    // The detector is loaded with the monitor, so model changes need a restart.
+    let wake_word_changed = settings.mode == "wakeword"
+        && prev_wake_word != wake_word::WakeWordConfig::from_settings(settings);
 
-    if mode_changed || (device_changed && settings.mode == "vad") {
-        if prev_mode == "vad" || (settings.mode == "vad" && device_changed) {
+    if mode_changed || (uses_vad_monitor && (device_changed || wake_word_changed)) {
+        if crate::state::mode_uses_vad_monitor(&prev_mode) || (uses_vad_monitor && !mode_changed) {
             crate::audio::stop_vad_monitor(app, &state);
         }
-        if settings.mode == "vad" && settings.capture_enabled {
-            let _ = crate::audio::start_vad_monitor(app, &state, settings);
+        if uses_vad_monitor && settings.capture_enabled {
+            if let Err(err) = crate::audio::start_vad_monitor(app, &state, settings) {
+                emit_error(app, AppError::AudioDevice(err), Some("Capture"));
+            }
         }
-    } else if settings.mode == "vad" {
+    } else if uses_vad_monitor {
         if let Ok(recorder) = state.recorder.lock() {
             recorder.update_vad_settings(
                 settings.vad_threshold_start,
@@ -1502,11 +1982,15 @@ pub(crate) fn save_settings_inner(app: &AppHandle, settings: &mut Settings) -> R
     if capture_enabled_changed {
         if !settings.capture_enabled {
             crate::audio::stop_vad_monitor(app, &state);
-        } else if settings.mode == "vad" {
+        } else if uses_vad_monitor {
             let _ = crate::audio::start_vad_monitor(app, &state, settings);
         }
     }
     crate::audio::sync_ptt_hot_standby(app, &state, settings);
+    // Both mic streams size their pre-roll ring when they open.
+    if prev_pre_roll_ms != settings.pre_roll_ms {
+        crate::audio::restart_input_capture(app, &state, settings);
+    }
 
     let transcribe_enabled_changed = prev_transcribe_enabled != settings.transcribe_enabled;
     let transcribe_device_changed =
@@ -1598,6 +2082,7 @@ pub(crate) fn save_settings_inner(app: &AppHandle, settings: &mut Settings) -> R
     info!("[DIAG] save_settings_inner: emitting settings-changed");
     let _ = app.emit("settings-changed", settings.clone());
     assistant_presence::reconcile_assistant_presence_window(app, settings);
+    live_captions::reconcile_captions_window(app, settings);
     let _ = workflow_agent::emit_assistant_baseline_state(
         app,
         state.inner(),
@@ -1611,12 +2096,18 @@ pub(crate) fn save_settings_inner(app: &AppHandle, settings: &mut Settings) -> R
 }
 
 #[tauri::command]
-async fn save_settings(app: AppHandle, mut settings: Settings) -> Result<(), String> {
+async fn save_settings(app: AppHandle, mut settings: Settings) -> CommandResult<()> {
     // Run on a blocking worker thread so the Tauri event-loop thread is never
     // stalled by file I/O, lock contention, or Win32 hotkey-registration calls.
     tauri::async_runtime::spawn_blocking(move || save_settings_inner(&app, &mut settings))
         .await
-        .map_err(|e| format!("save_settings task failed: {}", e))?
+        .map_err(|e| {
+            CommandError::new(
+                ErrorCode::Internal,
+                format!("save_settings task failed: {}", e),
+            )
+        })?
+        .with_code(ErrorCode::Storage)
 }
 
 #[tauri::command]
@@ -1636,9 +2127,9 @@ fn list_modules(
 }
 
 #[tauri::command]
-fn scan_module_packages(app: AppHandle) -> Result<module_package::ModulePackageScanReport, String> {
+fn scan_module_packages(app: AppHandle) -> CommandResult<module_package::ModulePackageScanReport> {
     let modules_dir = crate::paths::resolve_modules_dir(&app);
-    module_package::scan_modules_dir(&modules_dir)
+    module_package::scan_modules_dir(&modules_dir).with_code(ErrorCode::Storage)
 }
 
 fn scan_installed_module_ids_lossy(app: &AppHandle) -> std::collections::HashSet<String> {
@@ -1671,24 +2162,32 @@ fn bundled_module_package_source(app: &AppHandle, module_id: &str) -> Option<Pat
 fn install_bundled_module_package(
     app: AppHandle,
     module_id: String,
-) -> Result<module_package::ModulePackageInstallResult, String> {
+) -> CommandResult<module_package::ModulePackageInstallResult> {
     let module_id = canonicalize_module_id(&module_id).to_string();
-    let manifest = module_registry::find_manifest(&module_id)
-        .ok_or_else(|| format!("Unknown module id '{}'.", module_id))?;
+    let manifest = module_registry::find_manifest(&module_id).ok_or_else(|| {
+        CommandError::new(
+            ErrorCode::NotFound,
+            format!("Unknown module id '{}'.", module_id),
+        )
+    })?;
     if !manifest.bundled {
-        return Err(format!(
-            "Module '{}' is not bundled in this build.",
-            module_id
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            format!("Module '{}' is not bundled in this build.", module_id),
         ));
     }
     let source_dir = bundled_module_package_source(&app, &module_id).ok_or_else(|| {
-        format!(
-            "Bundled module package '{}' was not found in app resources.",
-            module_id
+        CommandError::new(
+            ErrorCode::NotFound,
+            format!(
+                "Bundled module package '{}' was not found in app resources.",
+                module_id
+            ),
         )
     })?;
     let modules_dir = crate::paths::resolve_modules_dir(&app);
     module_package::install_package_from_dir(&source_dir, &modules_dir)
+        .with_code(ErrorCode::Storage)
 }
 
 /// List modules published in the on-demand module index, annotated with local
@@ -1696,10 +2195,16 @@ fn install_bundled_module_package(
 #[tauri::command]
 async fn list_available_modules(
     app: AppHandle,
-) -> Result<Vec<crate::modules::delivery::AvailableModule>, String> {
+) -> CommandResult<Vec<crate::modules::delivery::AvailableModule>> {
     tauri::async_runtime::spawn_blocking(move || crate::modules::delivery::list_available(&app))
         .await
-        .map_err(|error| format!("list_available_modules task failed: {error}"))?
+        .map_err(|error| {
+            CommandError::new(
+                ErrorCode::Internal,
+                format!("list_available_modules task failed: {error}"),
+            )
+        })?
+        .with_code(ErrorCode::Network)
 }
 
 /// Download, verify, unpack and install (or update) a module by id from the
@@ -1708,20 +2213,26 @@ async fn list_available_modules(
 async fn download_module(
     app: AppHandle,
     module_id: String,
-) -> Result<module_package::ModulePackageInstallResult, String> {
+) -> CommandResult<module_package::ModulePackageInstallResult> {
     let module_id = canonicalize_module_id(&module_id).to_string();
     tauri::async_runtime::spawn_blocking(move || {
         crate::modules::delivery::download_and_install(&app, &module_id)
     })
     .await
-    .map_err(|error| format!("download_module task failed: {error}"))?
+    .map_err(|error| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("download_module task failed: {error}"),
+        )
+    })?
+    .with_code(ErrorCode::Network)
 }
 
 /// Remove an installed on-demand module from disk. Idempotent.
 #[tauri::command]
-async fn uninstall_module(app: AppHandle, module_id: String) -> Result<(), String> {
+async fn uninstall_module(app: AppHandle, module_id: String) -> CommandResult<()> {
     let module_id = canonicalize_module_id(&module_id).to_string();
-    crate::modules::delivery::uninstall(&app, &module_id)
+    crate::modules::delivery::uninstall(&app, &module_id).with_code(ErrorCode::Storage)
 }
 
 fn should_autostart_ai_refinement_runtime(settings: &Settings) -> bool {
@@ -1853,7 +2364,7 @@ fn enable_module(
     state: State<'_, AppState>,
     module_id: String,
     grant_permissions: Option<Vec<String>>,
-) -> Result<serde_json::Value, String> {
+) -> CommandResult<serde_json::Value> {
     guarded_command!("enable_module", {
         crate::modules::lifecycle_coordinator::enable_module_actions(
             &app,
@@ -1869,7 +2380,7 @@ fn disable_module(
     app: AppHandle,
     state: State<'_, AppState>,
     module_id: String,
-) -> Result<serde_json::Value, String> {
+) -> CommandResult<serde_json::Value> {
     guarded_command!("disable_module", {
         crate::modules::lifecycle_coordinator::disable_module_actions(
             &app,
@@ -2126,7 +2637,7 @@ fn save_window_state(
     y: i32,
     width: u32,
     height: u32,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     // Validate window state: reject if window is minimized or has invalid dimensions
     // Windows uses ~-32000 for minimized window positions
     const MINIMIZED_THRESHOLD: i32 = -30000;
@@ -2164,7 +2675,12 @@ fn save_window_state(
             current.main_window_height = Some(height);
             current.main_window_monitor = monitor_name;
         }
-        _ => return Err("Unknown window label".to_string()),
+        _ => {
+            return Err(CommandError::new(
+                ErrorCode::InvalidInput,
+                format!("Unknown window label '{}'", window_label),
+            ))
+        }
     }
 
     // Debounce: skip disk write if less than 500ms since last geometry save.
@@ -2188,10 +2704,7 @@ fn save_window_state(
 }
 
 #[tauri::command]
-fn show_assistant_presence_window(
-    app: AppHandle,
-    state: State<'_, AppState>,
-) -> Result<(), String> {
+fn show_assistant_presence_window(app: AppHandle, state: State<'_, AppState>) -> CommandResult<()> {
     let snapshot = {
         let mut settings = state
             .settings
@@ -2203,14 +2716,14 @@ fn show_assistant_presence_window(
         normalize_assistant_presence_binding(&mut settings);
         settings.clone()
     };
-    save_settings_file(&app, &snapshot)?;
+    save_settings_file(&app, &snapshot).with_code(ErrorCode::Storage)?;
     let _ = app.emit("settings-changed", snapshot.clone());
     assistant_presence::reconcile_assistant_presence_window(&app, &snapshot);
     Ok(())
 }
 
 #[tauri::command]
-fn toggle_transcribe(app: AppHandle) -> Result<(), String> {
+fn toggle_transcribe(app: AppHandle) -> CommandResult<()> {
     toggle_transcribe_state(&app);
     Ok(())
 }
@@ -2218,18 +2731,18 @@ fn toggle_transcribe(app: AppHandle) -> Result<(), String> {
 #[tauri::command]
 fn expand_transcribe_backlog(
     app: AppHandle,
-) -> Result<transcription::TranscribeBacklogStatus, String> {
+) -> CommandResult<transcription::TranscribeBacklogStatus> {
     cancel_backlog_auto_expand(&app);
-    expand_transcribe_backlog_inner(&app)
+    expand_transcribe_backlog_inner(&app).with_code(ErrorCode::InvalidInput)
 }
 
 #[tauri::command]
-fn paste_transcript_text(app: AppHandle, text: String) -> Result<(), String> {
-    paste_text(&app, &text)
+fn paste_transcript_text(app: AppHandle, text: String) -> CommandResult<()> {
+    paste_text(&app, &text).with_code(ErrorCode::Internal)
 }
 
 #[tauri::command]
-async fn apply_model(app: AppHandle, model_id: String) -> Result<(), String> {
+async fn apply_model(app: AppHandle, model_id: String) -> CommandResult<()> {
     tauri::async_runtime::spawn_blocking(move || {
         let state = app.state::<AppState>();
         let mut settings = state
@@ -2247,7 +2760,8 @@ async fn apply_model(app: AppHandle, model_id: String) -> Result<(), String> {
                 .settings
                 .read()
                 .unwrap_or_else(|poisoned| poisoned.into_inner()),
-        )?;
+        )
+        .with_code(ErrorCode::Storage)?;
 
         // If transcription is active or Whisper server is running, restart with new model
         // to clear old model from VRAM and load new model
@@ -2274,7 +2788,10 @@ async fn apply_model(app: AppHandle, model_id: String) -> Result<(), String> {
                         .unwrap_or_else(|poisoned| poisoned.into_inner()),
                 );
                 state.transcribe_active.store(false, Ordering::Relaxed);
-                return Err(format!("Failed to apply model: {}", err));
+                return Err(CommandError::new(
+                    ErrorCode::Transcription,
+                    format!("Failed to apply model: {}", err),
+                ));
             }
         } else {
             // Even if transcription is inactive, restart Whisper server if it's running
@@ -2294,7 +2811,12 @@ async fn apply_model(app: AppHandle, model_id: String) -> Result<(), String> {
         Ok(())
     })
     .await
-    .unwrap_or_else(|e| Err(format!("apply_model panicked: {e}")))
+    .unwrap_or_else(|e| {
+        Err(CommandError::new(
+            ErrorCode::Internal,
+            format!("apply_model panicked: {e}"),
+        ))
+    })
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -2308,7 +2830,7 @@ pub struct HardwareInfo {
 }
 
 #[tauri::command]
-fn get_hardware_info() -> Result<HardwareInfo, String> {
+fn get_hardware_info() -> CommandResult<HardwareInfo> {
     #[cfg(target_os = "windows")]
     {
         use windows::Win32::Graphics::Dxgi::{CreateDXGIFactory1, IDXGIFactory1};
@@ -2438,7 +2960,7 @@ fn get_hardware_info() -> Result<HardwareInfo, String> {
 }
 
 #[tauri::command]
-async fn get_gpu_vram_usage() -> Result<String, String> {
+async fn get_gpu_vram_usage() -> CommandResult<String> {
     // Query NVIDIA GPU VRAM usage via nvidia-smi — wrapped in spawn_blocking to
     // avoid blocking the Tokio worker thread during the nvidia-smi process spawn.
     tauri::async_runtime::spawn_blocking(|| {
@@ -2460,7 +2982,7 @@ async fn get_gpu_vram_usage() -> Result<String, String> {
 
         let output = cmd
             .output()
-            .map_err(|_| "nvidia-smi not found".to_string())?;
+            .map_err(|_| CommandError::new(ErrorCode::NotFound, "nvidia-smi not found"))?;
 
         if !output.status.success() {
             return Ok(String::new());
@@ -2741,6 +3263,7 @@ pub(crate) fn save_recording_opus(
     let _ = std::fs::remove_file(&wav_path);
 
     encode_result.map_err(|e| format!("Failed to encode OPUS: {}", e))?;
+    crate::history_crypto::seal_recording(&opus_path);
     Ok(Some(opus_path.to_string_lossy().to_string()))
 }
 
@@ -2858,7 +3381,7 @@ fn lms_load_model(model_identifier: &str) {
 }
 
 fn build_overlay_settings(settings: &Settings) -> overlay::OverlaySettings {
-    let use_kitt = settings.overlay_style == "kitt";
+    let use_kitt = overlay::overlay_style_uses_bar(&settings.overlay_style);
     let (color, rise_ms, fall_ms, opacity_inactive, opacity_active, pos_x, pos_y) = if use_kitt {
         (
             settings.overlay_kitt_color.clone(),
@@ -2890,6 +3413,7 @@ fn build_overlay_settings(settings: &Settings) -> overlay::OverlaySettings {
         opacity_active: opacity_active as f64,
         pos_x,
         pos_y,
+        monitor: settings.overlay_monitor.clone(),
         style: settings.overlay_style.clone(),
         refining_indicator_enabled: settings.overlay_refining_indicator_enabled,
         refining_indicator_preset: settings.overlay_refining_indicator_preset.clone(),
@@ -2905,68 +3429,6 @@ fn build_overlay_settings(settings: &Settings) -> overlay::OverlaySettings {
     }
 }
 
-fn init_logging() {
-    use tracing_appender::rolling::{RollingFileAppender, Rotation};
-    use tracing_subscriber::{
-        filter::LevelFilter, fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer,
-    };
-
-    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
-
-    // Logs go to %LOCALAPPDATA%\Trispr Flow\logs\:
-    //   - trispr-flow.YYYY-MM-DD.txt         (all levels, daily rotation, 30-day retention)
-    //   - trispr-flow-errors.YYYY-MM-DD.txt  (WARN+ERROR only — compact scan surface)
-    let log_dir = std::env::var("LOCALAPPDATA")
-        .map(|d| std::path::PathBuf::from(d).join("Trispr Flow").join("logs"))
-        .unwrap_or_else(|_| std::path::PathBuf::from("logs"));
-    let _ = std::fs::create_dir_all(&log_dir);
-
-    let main_appender = RollingFileAppender::builder()
-        .rotation(Rotation::DAILY)
-        .filename_prefix("trispr-flow")
-        .filename_suffix("txt")
-        .max_log_files(30)
-        .build(&log_dir)
-        .expect("failed to initialize main log appender");
-    let (main_nb, main_guard) = tracing_appender::non_blocking(main_appender);
-    std::mem::forget(main_guard);
-
-    let errors_appender = RollingFileAppender::builder()
-        .rotation(Rotation::DAILY)
-        .filename_prefix("trispr-flow-errors")
-        .filename_suffix("txt")
-        .max_log_files(30)
-        .build(&log_dir)
-        .expect("failed to initialize errors log appender");
-    let (errors_nb, errors_guard) = tracing_appender::non_blocking(errors_appender);
-    std::mem::forget(errors_guard);
-
-    tracing_subscriber::registry()
-        .with(filter)
-        .with(
-            fmt::layer()
-                .with_target(false)
-                .with_thread_ids(false)
-                .with_file(true)
-                .with_line_number(true)
-                .with_writer(main_nb)
-                .with_ansi(false),
-        )
-        .with(
-            fmt::layer()
-                .with_target(false)
-                .with_thread_ids(false)
-                .with_file(true)
-                .with_line_number(true)
-                .with_writer(errors_nb)
-                .with_ansi(false)
-                .with_filter(LevelFilter::WARN),
-        )
-        .init();
-
-    info!("Trispr Flow starting up — log: {}", log_dir.display());
-}
-
 pub(crate) fn emit_error(app: &AppHandle, error: AppError, context: Option<&str>) {
     let event = if let Some(ctx) = context {
         ErrorEvent::new(error.clone()).with_context(ctx)
@@ -2976,6 +3438,14 @@ pub(crate) fn emit_error(app: &AppHandle, error: AppError, context: Option<&str>
 
     error!("{}: {}", error.title(), error.message());
 
+    if matches!(
+        error,
+        AppError::AudioDevice(_) | AppError::Transcription(_) | AppError::Network(_)
+    ) {
+        overlay::flash_overlay_error(app);
+        audio_cues::play_error_cue(app);
+    }
+
     let _ = app.emit("app:error", event);
 }
 
@@ -3032,20 +3502,55 @@ fn env_flag(name: &str) -> bool {
 /// Snapshot of clipboard content before we overwrite it.
 enum ClipboardSnapshot {
     Text(String),
+    /// Rich text (browser, office apps) with its plain-text version.
+    Html {
+        html: String,
+        alt_text: Option<String>,
+    },
     Image {
         width: usize,
         height: usize,
         bytes: Vec<u8>,
     },
+    /// Every clipboard format, byte for byte.
+    #[cfg(target_os = "windows")]
+    Formats(crate::clipboard_formats::FormatSnapshot),
     Empty,
 }
 
+impl ClipboardSnapshot {
+    /// Plain text of the snapshot, if any.
+    fn text(&self) -> Option<String> {
+        match self {
+            Self::Text(text) => Some(text.clone()),
+            Self::Html { alt_text, .. } => alt_text.clone(),
+            #[cfg(target_os = "windows")]
+            Self::Formats(formats) => formats.text(),
+            Self::Image { .. } | Self::Empty => None,
+        }
+    }
+}
+
 fn capture_clipboard_snapshot_with_retry() -> ClipboardSnapshot {
     let deadline = std::time::Instant::now() + Duration::from_millis(CLIPBOARD_CAPTURE_TIMEOUT_MS);
 
     loop {
+        #[cfg(target_os = "windows")]
+        if let Ok(Some(formats)) = crate::clipboard_formats::capture() {
+            return ClipboardSnapshot::Formats(formats);
+        }
+
         match Clipboard::new() {
             Ok(mut clipboard) => {
+                // HTML first: rich copies carry plain text as well, and
+                // keeping only that would drop the formatting.
+                if let Ok(html) = clipboard.get().html() {
+                    return ClipboardSnapshot::Html {
+                        html,
+                        alt_text: clipboard.get_text().ok(),
+                    };
+                }
+
                 if let Ok(text) = clipboard.get_text() {
                     return ClipboardSnapshot::Text(text);
                 }
@@ -3085,7 +3590,7 @@ fn clipboard_text_matches(expected: &str, current: &str) -> bool {
     expected.replace("\r\n", "\n") == current.replace("\r\n", "\n")
 }
 
-fn set_clipboard_text_with_retry(text: &str) -> Result<(), String> {
+pub(crate) fn set_clipboard_text_with_retry(text: &str) -> Result<(), String> {
     let deadline = std::time::Instant::now() + Duration::from_millis(CLIPBOARD_CAPTURE_TIMEOUT_MS);
     let text = text.to_string();
 
@@ -3109,6 +3614,45 @@ fn set_clipboard_text_with_retry(text: &str) -> Result<(), String> {
     }
 }
 
+/// One restore attempt; `Err` carries the reason for the retry log.
+fn restore_snapshot_once(snapshot: &ClipboardSnapshot) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    if let ClipboardSnapshot::Formats(formats) = snapshot {
+        return crate::clipboard_formats::restore(formats);
+    }
+
+    let mut clipboard = Clipboard::new().map_err(|err| err.to_string())?;
+    let write_result = match snapshot {
+        ClipboardSnapshot::Text(text) => clipboard.set_text(text.clone()),
+        ClipboardSnapshot::Html { html, alt_text } => {
+            clipboard.set_html(html.clone(), alt_text.clone())
+        }
+        ClipboardSnapshot::Image {
+            width,
+            height,
+            bytes,
+        } => clipboard.set_image(ImageData {
+            width: *width,
+            height: *height,
+            bytes: std::borrow::Cow::Borrowed(bytes.as_slice()),
+        }),
+        #[cfg(target_os = "windows")]
+        ClipboardSnapshot::Formats(_) => return Ok(()),
+        ClipboardSnapshot::Empty => return Ok(()),
+    };
+    write_result.map_err(|err| err.to_string())?;
+
+    if let ClipboardSnapshot::Text(expected) = snapshot {
+        match clipboard.get_text() {
+            Ok(current) if clipboard_text_matches(expected, &current) => Ok(()),
+            Ok(_) => Err("Clipboard text verification mismatch".to_string()),
+            Err(err) => Err(format!("Clipboard text verification failed: {}", err)),
+        }
+    } else {
+        Ok(())
+    }
+}
+
 fn restore_snapshot_with_retry(snapshot: ClipboardSnapshot) -> Result<(), String> {
     if matches!(snapshot, ClipboardSnapshot::Empty) {
         return Ok(());
@@ -3117,40 +3661,9 @@ fn restore_snapshot_with_retry(snapshot: ClipboardSnapshot) -> Result<(), String
     let deadline = std::time::Instant::now() + Duration::from_millis(CLIPBOARD_RESTORE_TIMEOUT_MS);
 
     loop {
-        let attempt_error = match Clipboard::new() {
-            Ok(mut clipboard) => {
-                let write_result = match &snapshot {
-                    ClipboardSnapshot::Text(text) => clipboard.set_text(text.clone()),
-                    ClipboardSnapshot::Image {
-                        width,
-                        height,
-                        bytes,
-                    } => clipboard.set_image(ImageData {
-                        width: *width,
-                        height: *height,
-                        bytes: std::borrow::Cow::Borrowed(bytes.as_slice()),
-                    }),
-                    ClipboardSnapshot::Empty => return Ok(()),
-                };
-
-                match write_result {
-                    Ok(()) => {
-                        if let ClipboardSnapshot::Text(expected) = &snapshot {
-                            match clipboard.get_text() {
-                                Ok(current) if clipboard_text_matches(expected, &current) => {
-                                    return Ok(());
-                                }
-                                Ok(_) => "Clipboard text verification mismatch".to_string(),
-                                Err(err) => format!("Clipboard text verification failed: {}", err),
-                            }
-                        } else {
-                            return Ok(());
-                        }
-                    }
-                    Err(err) => err.to_string(),
-                }
-            }
-            Err(err) => err.to_string(),
+        let attempt_error = match restore_snapshot_once(&snapshot) {
+            Ok(()) => return Ok(()),
+            Err(err) => err,
         };
 
         if std::time::Instant::now() >= deadline {
@@ -3169,7 +3682,21 @@ fn restore_snapshot_with_retry(snapshot: ClipboardSnapshot) -> Result<(), String
 }
 
 pub(crate) fn paste_text(app_handle: &AppHandle, text: &str) -> Result<(), String> {
-    let snapshot = capture_clipboard_snapshot_with_retry();
+    if elevation::divert_if_blocked(app_handle, text)? {
+        return Ok(());
+    }
+    let restore_enabled = app_handle
+        .state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clipboard_restore_enabled;
+    let snapshot = if restore_enabled {
+        capture_clipboard_snapshot_with_retry()
+    } else {
+        ClipboardSnapshot::Empty
+    };
+    let clipboard_before = snapshot.text();
     set_clipboard_text_with_retry(text)?;
     {
         let ec_state = app_handle.state::<crate::state::AppState>();
@@ -3190,6 +3717,7 @@ pub(crate) fn paste_text(app_handle: &AppHandle, text: &str) -> Result<(), Strin
 
         return Err(format!("Failed to send paste keystroke: {}", paste_error));
     }
+    crate::paste_undo::record_paste(text, clipboard_before);
 
     let operation_generation = CLIPBOARD_PASTE_GENERATION.fetch_add(1, Ordering::AcqRel) + 1;
 
@@ -3209,17 +3737,31 @@ pub(crate) fn paste_text(app_handle: &AppHandle, text: &str) -> Result<(), Strin
     Ok(())
 }
 
+/// Types `text` as keystrokes instead of pasting, for targets that block or
+/// mangle clipboard pastes (terminals, remote sessions).
+pub(crate) fn type_text(app_handle: &AppHandle, text: &str) {
+    match elevation::divert_if_blocked(app_handle, text) {
+        Ok(true) => return,
+        Ok(false) => {}
+        Err(err) => warn!("Clipboard fallback for elevated target failed: {}", err),
+    }
+    {
+        let ec_state = app_handle.state::<crate::state::AppState>();
+        crate::uiautomation_capture::record_paste(&ec_state.enter_capture, text);
+    }
+    let mut enigo = Enigo::new();
+    keyboard_layout::type_sequence(&mut enigo, text);
+    crate::paste_undo::record_paste(text, None);
+}
+
 fn send_paste_keystroke() -> Result<(), String> {
     let mut enigo = Enigo::new();
-    if cfg!(target_os = "macos") {
-        enigo.key_down(Key::Meta);
-        enigo.key_click(Key::Layout('v'));
-        enigo.key_up(Key::Meta);
+    let modifier = if cfg!(target_os = "macos") {
+        Key::Meta
     } else {
-        enigo.key_down(Key::Control);
-        enigo.key_click(Key::Layout('v'));
-        enigo.key_up(Key::Control);
-    }
+        Key::Control
+    };
+    keyboard_layout::send_shortcut(&mut enigo, modifier, 'v');
     Ok(())
 }
 
@@ -3641,7 +4183,7 @@ pub(crate) fn toggle_activation_words_async(app: AppHandle) {
         };
 
         let cue = if new_enabled { "start" } else { "stop" };
-        let _ = app.emit("audio:cue", cue);
+        audio_cues::play_cue(&app, cue);
         let _ = app.emit("settings:updated", {
             let settings = state
                 .settings
@@ -3718,7 +4260,7 @@ pub(crate) fn toggle_product_mode_async(app: AppHandle) {
         } else {
             "stop"
         };
-        let _ = app.emit("audio:cue", cue);
+        audio_cues::play_cue(&app, cue);
         info!("Product mode toggled to: {}", next_mode);
     });
 }
@@ -3759,30 +4301,10 @@ pub(crate) fn format_panic_payload(payload: &(dyn std::any::Any + Send)) -> Stri
 }
 
 pub fn run() {
-    init_logging();
+    crate::logging::init_logging();
     load_local_env();
 
-    // Global panic hook: log every panic (including from spawned threads) so
-    // we have full tracing context for crashes instead of silent thread death.
-    let default_hook = std::panic::take_hook();
-    std::panic::set_hook(Box::new(move |info| {
-        let location = info
-            .location()
-            .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()))
-            .unwrap_or_else(|| "unknown".to_string());
-        let payload = info
-            .payload()
-            .downcast_ref::<String>()
-            .cloned()
-            .or_else(|| info.payload().downcast_ref::<&str>().map(|s| s.to_string()))
-            .unwrap_or_else(|| "non-string panic".to_string());
-        let backtrace = std::backtrace::Backtrace::force_capture();
-        error!(
-            "PANIC at {}: {}\nBacktrace:\n{}",
-            location, payload, backtrace
-        );
-        default_hook(info);
-    }));
+    crate::crash_report::install_panic_hook();
 
     info!("Starting Trispr Flow application");
     let builder = tauri::Builder::default()
@@ -3803,6 +4325,9 @@ pub fn run() {
             // Migrate data from legacy %APPDATA%\com.trispr.flow\ to
             // %LOCALAPPDATA%\Trispr Flow\ before any state is loaded.
             crate::data_migration::migrate_legacy_data(app.handle());
+            crate::paths::init_managed_runtime_dir(app.handle());
+            crate::scratch::init_scratch_dir(app.handle());
+            crate::transcription_jobs::init_jobs(app.handle());
 
             // Kill any Ollama process left over from a previous crash or hard-kill.
             // Moved to a background thread: taskkill on Windows can block for 1–3 s,
@@ -3818,8 +4343,12 @@ pub fn run() {
             let mut settings = load_settings(app.handle());
             reconcile_assistant_transcribe_flag(&mut settings);
             crate::state::sync_diagnostic_logging_enabled(&settings);
+            crate::logging::sync_log_level(&settings);
+            crate::crash_report::announce_pending_crash_report(app.handle());
 
             // Compute partition base directories and legacy paths for migration.
+            crate::history_crypto::resume_migration(app.handle(), &mut settings);
+            crate::history_crypto::init(&settings.history_encryption_mode);
             let app_data_dir = crate::paths::resolve_base_dir(app.handle());
             let mic_history_dir = app_data_dir.join("history").join("mic");
             let system_history_dir = app_data_dir.join("history").join("system");
@@ -3869,6 +4398,7 @@ pub fn run() {
                 managed_whisper_server_child: Mutex::new(None),
                 module_sidecars: crate::modules::runtime::default_sidecar_map(),
                 whisper_server_port: AtomicU16::new(crate::whisper_server::WHISPER_SERVER_PORT),
+                translate_session_override: Mutex::new(None),
                 whisper_server_warmup_started: AtomicBool::new(false),
                 ollama_model_warm: AtomicBool::new(false),
                 ollama_warmup_in_progress: AtomicBool::new(false),
@@ -3959,6 +4489,8 @@ pub fn run() {
                 });
             }
 
+            runtime_supervisor::start_runtime_supervisor(app.handle().clone());
+
             {
                 let handle = app.handle().clone();
                 crate::util::spawn_guarded("dependency_preflight", move || {
@@ -4051,9 +4583,14 @@ pub fn run() {
 
             // Initialise session manager with the recordings directory
             {
-                let recordings_dir = paths::resolve_recordings_dir(app.handle());
-                let modules_dir = paths::resolve_modules_dir(app.handle());
-                session_manager::init(recordings_dir.clone(), modules_dir);
+                let recordings_dir = session_manager::recordings_dir_for(app.handle(), &settings);
+                let config = session_manager::RecordingConfig::from_settings(&settings);
+                session_manager::init(
+                    recordings_dir.clone(),
+                    paths::resolve_modules_dir(app.handle()),
+                    config,
+                );
+                session_manager::cleanup_recordings(&recordings_dir, &config);
 
                 // Surface any incomplete sessions from a previous crash as a warning
                 let incomplete = session_manager::scan_incomplete(&recordings_dir);
@@ -4068,6 +4605,16 @@ pub fn run() {
                 }
             }
 
+            device_monitor::start(app.handle().clone());
+            capture_policy::start(app.handle().clone());
+            capture_schedule::start(app.handle().clone());
+            caption_output::start(app.handle().clone());
+            event_stream::install(app.handle());
+            http_api::sync_http_api(app.handle(), &settings);
+            watch_folders::sync_watch_folders(app.handle(), &settings);
+            crate::models::init_download_queue(app.handle());
+            history_partition::start_history_retention_task(app.handle().clone());
+
             info!("[DIAG] setup: registering hotkeys...");
             if let Err(err) = register_hotkeys(app.handle(), &settings) {
                 warn!("Failed to register hotkeys: {}", err);
@@ -4318,7 +4865,7 @@ pub fn run() {
                 });
             }
 
-            if settings.mode == "vad" && settings.capture_enabled {
+            if crate::state::mode_uses_vad_monitor(&settings.mode) && settings.capture_enabled {
                 // Delay VAD start by 2 seconds to allow models to load on first startup
                 let app_handle = app.handle().clone();
                 let settings_clone = settings.clone();
@@ -4334,6 +4881,7 @@ pub fn run() {
             }
             info!("[DIAG] setup: sync_ptt_hot_standby...");
             crate::audio::sync_ptt_hot_standby(app.handle(), &app.state::<AppState>(), &settings);
+            echo_reference::sync_echo_reference(&settings);
             info!("[DIAG] setup: ptt done, priming overlay state...");
 
             let overlay_app = app.handle().clone();
@@ -4359,6 +4907,7 @@ pub fn run() {
                 info!("[DIAG] setup: overlay state primed + window pre-warmed, building tray...");
             }
             assistant_presence::reconcile_assistant_presence_window(&app.handle(), &settings);
+            live_captions::reconcile_captions_window(&app.handle(), &settings);
 
             let icon = {
                 let paths = [
@@ -4434,6 +4983,46 @@ pub fn run() {
                             }
                         });
                     }
+                    "toggle-privacy-mute" => {
+                        let app_clone = app.clone();
+                        crate::util::spawn_guarded("tray_toggle_privacy_mute", move || {
+                            privacy_mute::toggle(&app_clone);
+                        });
+                    }
+                    id if id.starts_with(settings_profiles::TRAY_PROFILE_PREFIX) => {
+                        let name = id[settings_profiles::TRAY_PROFILE_PREFIX.len()..].to_string();
+                        settings_profiles::activate_profile_async(app.clone(), Some(name));
+                    }
+                    id if id.starts_with(models::TRAY_MODEL_PREFIX) => {
+                        let model_id = id[models::TRAY_MODEL_PREFIX.len()..].to_string();
+                        let app_clone = app.clone();
+                        tauri::async_runtime::spawn(async move {
+                            if let Err(err) = apply_model(app_clone.clone(), model_id).await {
+                                // The click already toggled the item; put the
+                                // mark back on the model that is still active.
+                                let active_model = app_clone
+                                    .state::<AppState>()
+                                    .settings
+                                    .read()
+                                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                                    .model
+                                    .clone();
+                                let _ = app_clone.emit("model:changed", active_model);
+                                emit_error(&app_clone, AppError::Other(err.into()), Some("Tray menu"));
+                            }
+                        });
+                    }
+                    id if id.starts_with(history_partition::TRAY_HISTORY_PREFIX) => {
+                        let entry_id = id[history_partition::TRAY_HISTORY_PREFIX.len()..].to_string();
+                        let app_clone = app.clone();
+                        crate::util::spawn_guarded("tray_paste_history", move || {
+                            // Let the tray menu close so focus is back on the target app.
+                            std::thread::sleep(std::time::Duration::from_millis(150));
+                            if let Err(err) = paste_history_entry(app_clone.clone(), entry_id) {
+                                emit_error(&app_clone, AppError::Other(err.into()), Some("Tray menu"));
+                            }
+                        });
+                    }
                     "cancel-backlog-expand" => {
                         cancel_backlog_auto_expand(app);
                         let _ = cancel_backlog_item_event.set_enabled(false);
@@ -4490,6 +5079,117 @@ pub fn run() {
                         }
                     });
 
+                    let privacy_mute_item = CheckMenuItem::with_id(
+                        app,
+                        "toggle-privacy-mute",
+                        "Privacy mute (all capture off)",
+                        true,
+                        privacy_mute::is_muted(),
+                        None::<&str>,
+                    )?;
+
+                    let privacy_mute_item_clone = privacy_mute_item.clone();
+                    app.listen("menu:update-privacy-mute", move |event| {
+                        if let Ok(checked) = serde_json::from_str::<bool>(event.payload()) {
+                            let _ = privacy_mute_item_clone.set_checked(checked);
+                        }
+                    });
+
+                    let profiles_submenu =
+                        tauri::menu::Submenu::with_id(app, "profiles", "Profiles", true)?;
+                    settings_profiles::populate_tray_submenu(
+                        app.handle(),
+                        &profiles_submenu,
+                        &settings_profiles::load_profiles(app.handle()),
+                    )?;
+                    let profiles_submenu_clone = profiles_submenu.clone();
+                    let profiles_handle = app.handle().clone();
+                    app.listen("profiles:changed", move |event| {
+                        if let Ok(store) = serde_json::from_str(event.payload()) {
+                            let _ = settings_profiles::populate_tray_submenu(
+                                &profiles_handle,
+                                &profiles_submenu_clone,
+                                &store,
+                            );
+                        }
+                    });
+
+                    let model_submenu =
+                        tauri::menu::Submenu::with_id(app, "models", "Model", true)?;
+                    models::populate_tray_model_submenu(
+                        app.handle(),
+                        &model_submenu,
+                        &settings.model,
+                    )?;
+                    // Clicking a check item toggles it natively; every model
+                    // change re-applies the marks from the saved setting.
+                    let model_submenu_clone = model_submenu.clone();
+                    app.listen("model:changed", move |event| {
+                        if let Ok(model_id) = serde_json::from_str::<String>(event.payload()) {
+                            models::check_tray_model(&model_submenu_clone, &model_id);
+                        }
+                    });
+                    let model_submenu_clone = model_submenu.clone();
+                    app.listen("settings-changed", move |event| {
+                        if let Ok(payload) =
+                            serde_json::from_str::<serde_json::Value>(event.payload())
+                        {
+                            if let Some(model_id) = payload["model"].as_str() {
+                                models::check_tray_model(&model_submenu_clone, model_id);
+                            }
+                        }
+                    });
+                    for event_name in ["model:download-complete", "model:removed"] {
+                        let model_submenu_clone = model_submenu.clone();
+                        let models_handle = app.handle().clone();
+                        app.listen(event_name, move |_event| {
+                            let active_model = models_handle
+                                .state::<AppState>()
+                                .settings
+                                .read()
+                                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                                .model
+                                .clone();
+                            let _ = models::populate_tray_model_submenu(
+                                &models_handle,
+                                &model_submenu_clone,
+                                &active_model,
+                            );
+                        });
+                    }
+
+                    let history_submenu = tauri::menu::Submenu::with_id(
+                        app,
+                        "recent-transcripts",
+                        "Recent transcripts",
+                        true,
+                    )?;
+                    let recent_entries: Vec<_> = app
+                        .state::<AppState>()
+                        .history
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner())
+                        .active
+                        .iter()
+                        .cloned()
+                        .collect();
+                    history_partition::populate_tray_history_submenu(
+                        app.handle(),
+                        &history_submenu,
+                        &recent_entries,
+                    )?;
+                    let history_submenu_clone = history_submenu.clone();
+                    let history_handle = app.handle().clone();
+                    app.listen("history:updated", move |event| {
+                        if let Ok(entries) = serde_json::from_str::<Vec<state::HistoryEntry>>(event.payload()) {
+                            let _ = history_partition::populate_tray_history_submenu(
+                                &history_handle,
+                                &history_submenu_clone,
+                                &entries,
+                            );
+                        }
+                    });
+
                     &tauri::menu::Menu::with_items(
                         app,
                         &[
@@ -4500,9 +5200,13 @@ pub fn run() {
                                 true,
                                 None::<&str>,
                             )?,
+                            &history_submenu,
                             &tauri::menu::PredefinedMenuItem::separator(app)?,
                             &mic_item,
                             &transcribe_item,
+                            &privacy_mute_item,
+                            &model_submenu,
+                            &profiles_submenu,
                             &tauri::menu::PredefinedMenuItem::separator(app)?,
                             &cancel_backlog_item_menu,
                             &tauri::menu::PredefinedMenuItem::separator(app)?,
@@ -4635,6 +5339,14 @@ pub fn run() {
             get_startup_status,
             get_runtime_diagnostics,
             save_settings,
+            list_profiles,
+            save_profile,
+            delete_profile,
+            activate_profile,
+            export_settings,
+            import_settings,
+            list_gamepads,
+            capture_gamepad_button,
             save_window_state,
             save_window_visibility_state,
             show_assistant_presence_window,
@@ -4665,6 +5377,7 @@ pub fn run() {
             list_tts_voices,
             list_piper_voice_catalog,
             download_piper_voice_key,
+            remove_piper_voice_key,
             speak_tts,
             stop_tts,
             test_tts_provider,
@@ -4711,10 +5424,61 @@ pub fn run() {
             #[cfg(feature = "module-confluence")]
             clear_confluence_secret,
             save_transcript,
+            get_replacement_rules,
+            save_replacement_rules,
+            list_snippets,
+            save_snippet,
+            delete_snippet,
+            list_hallucination_phrases,
+            add_hallucination_phrase,
+            remove_hallucination_phrase,
+            add_allowed_phrase,
+            remove_allowed_phrase,
+            list_activation_words,
+            add_activation_word,
+            remove_activation_word,
+            test_activation,
+            undo_last_paste,
+            list_whisper_backends,
+            install_runtime,
+            check_runtime_update,
+            get_whisper_supervisor_status,
+            calibrate_vad,
+            start_level_probe,
+            stop_level_probe,
+            preview_cue,
+            list_sound_packs,
+            set_cloud_credentials,
+            clear_cloud_credentials,
+            get_cloud_credentials_status,
+            set_translation_credentials,
+            clear_translation_credentials,
+            get_translation_credentials_status,
+            translate_text,
+            get_whisper_params,
+            set_whisper_params,
+            set_translate_session_override,
+            start_transcript_session,
+            stop_transcript_session,
+            list_sessions,
+            get_session_transcript,
+            get_conversation,
+            export_session_markdown,
+            export_session_srt,
+            add_chapter,
+            list_chapters,
+            rename_chapter,
+            summarize_session,
             list_audio_devices,
+            get_input_device_channels,
             list_output_devices,
             list_models,
             download_model,
+            pause_download,
+            resume_download,
+            cancel_download,
+            get_download_queue,
+            import_model,
             check_model_available,
             remove_model,
             quantize_model,
@@ -4728,6 +5492,21 @@ pub fn run() {
             delete_active_transcript_entry,
             list_history_partitions,
             load_history_partition,
+            retranscribe_entry,
+            prune_history_now,
+            update_history_entry,
+            edit_history_entry,
+            paste_history_entry,
+            run_mic_test,
+            verify_whisper_runtime,
+            setup_status,
+            list_tags,
+            export_history,
+            get_history_encryption_status,
+            set_history_encryption,
+            unlock_history,
+            get_entry_audio_path,
+            delete_entry_audio,
             add_history_entry,
             add_transcribe_entry,
             start_recording,
@@ -4737,6 +5516,14 @@ pub fn run() {
             paste_transcript_text,
             apply_model,
             validate_hotkey,
+            capture_next_hotkey,
+            get_privacy_mute,
+            set_privacy_mute,
+            overlay_subscribe_levels,
+            overlay_set_move_mode,
+            overlay_drag_start,
+            overlay_drag_end,
+            list_overlay_monitors,
             test_hotkey,
             get_hotkey_conflicts,
             save_crash_recovery,
@@ -4748,7 +5535,16 @@ pub fn run() {
             get_last_recording_path,
             get_recordings_directory,
             open_recordings_directory,
+            list_recordings,
+            delete_recording,
+            reveal_recording,
+            start_call_recording,
+            stop_call_recording,
+            get_call_recording,
             open_log_directory,
+            get_log_path,
+            set_log_level,
+            get_last_crash_report,
             fetch_available_models,
             fetch_ollama_models_with_size,
             test_provider_connection,
@@ -4768,6 +5564,35 @@ pub fn run() {
             refine_transcript,
             ping_refinement_model,
             run_latency_benchmark,
+            benchmark_model,
+            get_hardware_profile,
+            recommend_model,
+            get_jobs,
+            cancel_job,
+            list_retained_jobs,
+            retry_job,
+            list_failed_segments,
+            retry_failed_segment,
+            discard_failed_segment,
+            get_usage_stats,
+            get_latency_stats,
+            test_postproc_llm,
+            set_postproc_llm_api_key,
+            clear_postproc_llm_api_key,
+            get_postproc_llm_api_key_status,
+            list_wake_word_models,
+            download_wake_word_model,
+            get_capture_policy_state,
+            get_schedule_status,
+            get_http_api_info,
+            regenerate_http_api_token,
+            test_webhook,
+            get_caption_file_path,
+            transcribe_file,
+            get_batch_queue,
+            pause_queue,
+            resume_queue,
+            remove_batch_job,
             run_tts_benchmark,
             get_runtime_metrics_snapshot,
             record_runtime_metric,
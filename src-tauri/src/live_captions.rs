@@ -0,0 +1,153 @@
+//! Live caption window: floating subtitles for recent transcriptions.
+//!
+//! A transparent, click-through, always-on-top window near the top or bottom
+//! edge of the primary monitor. The page (`captions.html`) listens for
+//! `transcribe:history-updated` (system audio) and, when enabled,
+//! `transcription:result` (microphone), and shows the text of the last
+//! `captions_window_secs` seconds. Rust only owns the window and its geometry.
+
+use crate::state::Settings;
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindow};
+use tracing::warn;
+
+const CAPTIONS_LABEL: &str = "captions";
+/// Caption lines visible at once; older text scrolls out of the top.
+const CAPTIONS_VISIBLE_LINES: f64 = 3.0;
+const CAPTIONS_LINE_HEIGHT: f64 = 1.35;
+const CAPTIONS_VERTICAL_PADDING: f64 = 32.0;
+const CAPTIONS_EDGE_MARGIN_TOP: f64 = 48.0;
+/// Leaves room for the taskbar and the recording overlay below the captions.
+const CAPTIONS_EDGE_MARGIN_BOTTOM: f64 = 120.0;
+const CAPTIONS_WIDTH_FRACTION: f64 = 0.8;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CaptionGeometry {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+}
+
+/// Physical window rectangle for a monitor at `origin` with `size` pixels.
+fn caption_geometry(
+    origin: (i32, i32),
+    size: (u32, u32),
+    scale: f64,
+    font_size: u32,
+    position: &str,
+) -> CaptionGeometry {
+    let width = (size.0 as f64 * CAPTIONS_WIDTH_FRACTION).round();
+    let height = ((font_size as f64 * CAPTIONS_LINE_HEIGHT * CAPTIONS_VISIBLE_LINES
+        + CAPTIONS_VERTICAL_PADDING)
+        * scale)
+        .round()
+        .min(size.1 as f64);
+    let x = origin.0 + ((size.0 as f64 - width) / 2.0).round() as i32;
+    let y = if position == "top" {
+        origin.1 + (CAPTIONS_EDGE_MARGIN_TOP * scale).round() as i32
+    } else {
+        let bottom =
+            origin.1 + size.1 as i32 - (CAPTIONS_EDGE_MARGIN_BOTTOM * scale).round() as i32;
+        (bottom - height as i32).max(origin.1)
+    };
+    CaptionGeometry {
+        x,
+        y,
+        width: width as u32,
+        height: height as u32,
+    }
+}
+
+fn create_captions_window(app: &AppHandle) -> Result<WebviewWindow, String> {
+    if let Some(existing) = app.get_webview_window(CAPTIONS_LABEL) {
+        return Ok(existing);
+    }
+    let window = tauri::WebviewWindowBuilder::new(
+        app,
+        CAPTIONS_LABEL,
+        WebviewUrl::App("captions.html".into()),
+    )
+    .title("Trispr Flow Captions")
+    .inner_size(960.0, 160.0)
+    .resizable(false)
+    .decorations(false)
+    .shadow(false)
+    .transparent(true)
+    .focusable(false)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .visible(false)
+    .build()
+    .map_err(|err| format!("Failed to create caption window: {err}"))?;
+    // Captions must never steal clicks from the app underneath.
+    if let Err(err) = window.set_ignore_cursor_events(true) {
+        warn!("Caption window could not be made click-through: {}", err);
+    }
+    Ok(window)
+}
+
+fn apply_captions_geometry(window: &WebviewWindow, settings: &Settings) {
+    let Ok(Some(monitor)) = window
+        .primary_monitor()
+        .or_else(|_| window.current_monitor())
+    else {
+        return;
+    };
+    let origin = monitor.position();
+    let size = monitor.size();
+    let geometry = caption_geometry(
+        (origin.x, origin.y),
+        (size.width, size.height),
+        monitor.scale_factor(),
+        settings.captions_font_size,
+        &settings.captions_position,
+    );
+    let _ = window.set_size(tauri::PhysicalSize::new(geometry.width, geometry.height));
+    let _ = window.set_position(tauri::PhysicalPosition::new(geometry.x, geometry.y));
+}
+
+pub fn hide_captions_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(CAPTIONS_LABEL) {
+        let _ = window.hide();
+    }
+}
+
+/// Shows, repositions or hides the caption window to match `settings`.
+/// Appearance (font, opacity, time window) is picked up by the page from
+/// `settings-changed`.
+pub fn reconcile_captions_window(app: &AppHandle, settings: &Settings) {
+    if !settings.captions_enabled {
+        hide_captions_window(app);
+        return;
+    }
+    let window = match create_captions_window(app) {
+        Ok(window) => window,
+        Err(err) => {
+            warn!("{}", err);
+            return;
+        }
+    };
+    apply_captions_geometry(&window, settings);
+    if let Err(err) = window.show() {
+        warn!("Failed to show caption window: {}", err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn geometry_is_centred_and_anchored_to_the_chosen_edge() {
+        let bottom = caption_geometry((0, 0), (1920, 1080), 1.0, 28, "bottom");
+        assert_eq!(bottom.width, 1536);
+        assert_eq!(bottom.x, 192);
+        assert_eq!(bottom.height, 145);
+        assert_eq!(bottom.y + bottom.height as i32, 1080 - 120);
+
+        let top = caption_geometry((1920, 0), (2560, 1440), 2.0, 28, "top");
+        assert_eq!(top.x, 1920 + 256);
+        assert_eq!(top.y, 96);
+        assert_eq!(top.height, 291);
+    }
+}
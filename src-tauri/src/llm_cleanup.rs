@@ -0,0 +1,284 @@
+//! Optional LLM cleanup stage of post-processing: punctuation, casing and
+//! filler-word removal by any OpenAI-compatible chat endpoint — a llama.cpp
+//! server, Ollama's `/v1` API, LM Studio or a cloud service.
+//!
+//! Unlike AI refinement this runs inline before paste, so it is bounded by
+//! `postproc_llm_timeout_ms`; any failure or implausible answer keeps the
+//! raw text.
+//!
+//! Only `api.openai.com` gets the OpenAI key of AI refinement; any other
+//! remote endpoint uses its own keychain entry (`set_postproc_llm_api_key`),
+//! and loopback endpoints get no key.
+
+use serde::Serialize;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use url::Url;
+
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
+use crate::state::{AppState, Settings};
+
+pub(crate) const DEFAULT_POSTPROC_LLM_ENDPOINT: &str = "http://127.0.0.1:8080";
+pub(crate) const DEFAULT_POSTPROC_PROMPT: &str = "You clean up dictated text. Fix punctuation and capitalization, remove filler words (um, uh, like, you know, äh, ähm) and false starts. Do not translate, summarize or add anything. Output only the cleaned text.";
+pub(crate) const DEFAULT_POSTPROC_LLM_TIMEOUT_MS: u64 = 4_000;
+const OPENAI_API_HOST: &str = "api.openai.com";
+const KEYRING_SERVICE: &str = "com.trispr.flow.postproc-llm";
+const KEYRING_USER: &str = "endpoint-api-key";
+const TEST_SAMPLE: &str = "um so i think we should uh ship it on friday you know";
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct LlmCleanupTest {
+    pub(crate) input: String,
+    pub(crate) output: String,
+    pub(crate) elapsed_ms: u64,
+}
+
+fn is_loopback_endpoint(endpoint: &str) -> bool {
+    Url::parse(endpoint)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_ascii_lowercase))
+        .is_some_and(|host| matches!(host.as_str(), "localhost" | "127.0.0.1" | "[::1]"))
+}
+
+fn is_openai_endpoint(endpoint: &str) -> bool {
+    Url::parse(endpoint)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_ascii_lowercase))
+        .is_some_and(|host| host == OPENAI_API_HOST)
+}
+
+fn keyring_entry() -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+        .map_err(|e| format!("Failed to create keyring entry: {}", e))
+}
+
+fn read_endpoint_api_key() -> Result<Option<String>, String> {
+    match keyring_entry()?.get_password() {
+        Ok(key) if !key.trim().is_empty() => Ok(Some(key.trim().to_string())),
+        Ok(_) | Err(keyring::Error::NoEntry) => Ok(None),
+        Err(err) => Err(format!("Failed to read key from system keyring: {}", err)),
+    }
+}
+
+/// Bearer token for `url`: the OpenAI key for OpenAI itself, the cleanup
+/// endpoint's own key for other remote hosts, nothing for loopback.
+fn api_key_for(app: &AppHandle, url: &str) -> Result<Option<String>, String> {
+    if is_loopback_endpoint(url) {
+        Ok(None)
+    } else if is_openai_endpoint(url) {
+        crate::ai_fallback::keyring::read_api_key(app, "openai")
+    } else {
+        read_endpoint_api_key()
+    }
+}
+
+fn chat_url(endpoint: &str) -> String {
+    let base = endpoint.trim().trim_end_matches('/');
+    let base = if base.is_empty() {
+        DEFAULT_POSTPROC_LLM_ENDPOINT
+    } else {
+        base
+    };
+    if base.ends_with("/chat/completions") {
+        base.to_string()
+    } else if base.ends_with("/v1") {
+        format!("{}/chat/completions", base)
+    } else {
+        format!("{}/v1/chat/completions", base)
+    }
+}
+
+/// Guards against answers that are chatter rather than the cleaned input:
+/// empty, or far longer/shorter than the original.
+fn plausible_cleanup(input: &str, output: &str) -> bool {
+    let input_words = input.split_whitespace().count();
+    let output_words = output.split_whitespace().count();
+    if output_words == 0 {
+        return false;
+    }
+    output_words <= input_words * 2 + 4 && output_words * 3 + 2 >= input_words
+}
+
+/// Runs `text` through the configured endpoint. Errors leave the caller to
+/// fall back to the raw text.
+pub(crate) fn cleanup_transcript(
+    app: &AppHandle,
+    settings: &Settings,
+    text: &str,
+) -> Result<String, String> {
+    if text.trim().is_empty() {
+        return Ok(text.to_string());
+    }
+    let url = chat_url(&settings.postproc_llm_endpoint);
+    if crate::ai_fallback::provider::is_ssrf_target(&url) {
+        return Err("This endpoint address is not allowed (SSRF protection).".to_string());
+    }
+    if !is_loopback_endpoint(&url) && settings.ai_fallback.strict_local_mode {
+        return Err("Strict local mode is enabled; use a localhost endpoint.".to_string());
+    }
+    let model = if settings.postproc_llm_cleanup_model.trim().is_empty() {
+        settings.ai_fallback.model.trim()
+    } else {
+        settings.postproc_llm_cleanup_model.trim()
+    };
+    let prompt = if settings.postproc_prompt.trim().is_empty() {
+        DEFAULT_POSTPROC_PROMPT
+    } else {
+        settings.postproc_prompt.trim()
+    };
+
+    let body = serde_json::json!({
+        "model": model,
+        "stream": false,
+        "temperature": 0.0,
+        "max_tokens": text.split_whitespace().count() * 4 + 64,
+        "messages": [
+            { "role": "system", "content": prompt },
+            { "role": "user", "content": text }
+        ],
+        "chat_template_kwargs": { "enable_thinking": false }
+    });
+    let agent = ureq::builder()
+        .timeout(Duration::from_millis(settings.postproc_llm_timeout_ms))
+        .build();
+    let mut request = agent.post(&url).set("Content-Type", "application/json");
+    if let Some(key) = api_key_for(app, &url)? {
+        request = request.set("Authorization", &format!("Bearer {}", key));
+    }
+    let response: serde_json::Value = request
+        .send_json(body)
+        .map_err(|e| format!("LLM cleanup request to {} failed: {}", url, e))?
+        .into_json()
+        .map_err(|e| format!("Failed to parse LLM cleanup response: {}", e))?;
+    let content = response
+        .pointer("/choices/0/message/content")
+        .and_then(|value| value.as_str())
+        .ok_or_else(|| "LLM cleanup response has no choices[0].message.content".to_string())?;
+    let cleaned = crate::ai_fallback::provider::strip_thinking_tags(content)
+        .trim()
+        .to_string();
+    if !plausible_cleanup(text, &cleaned) {
+        return Err("LLM cleanup answer does not look like the transcript".to_string());
+    }
+    Ok(cleaned)
+}
+
+/// Sends a sample (or `text`) through the cleanup endpoint with the current
+/// settings, whether or not the stage is enabled.
+#[tauri::command]
+pub(crate) async fn test_postproc_llm(
+    app: AppHandle,
+    text: Option<String>,
+) -> CommandResult<LlmCleanupTest> {
+    let settings = app
+        .state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    let input = text
+        .filter(|text| !text.trim().is_empty())
+        .unwrap_or_else(|| TEST_SAMPLE.to_string());
+    tauri::async_runtime::spawn_blocking(move || {
+        let started = Instant::now();
+        let output = cleanup_transcript(&app, &settings, &input).with_code(ErrorCode::Network)?;
+        Ok(LlmCleanupTest {
+            input,
+            output,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+        })
+    })
+    .await
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("test_postproc_llm task failed: {}", e),
+        )
+    })?
+}
+
+/// Stores the API key sent to a remote cleanup endpoint other than OpenAI.
+#[tauri::command]
+pub(crate) fn set_postproc_llm_api_key(api_key: String) -> CommandResult<()> {
+    let key = api_key.trim();
+    if key.is_empty() {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "API key cannot be empty",
+        ));
+    }
+    keyring_entry()
+        .with_code(ErrorCode::Credentials)?
+        .set_password(key)
+        .map_err(|e| {
+            CommandError::new(
+                ErrorCode::Credentials,
+                format!("Failed to store key in system keyring: {}", e),
+            )
+        })?;
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) fn clear_postproc_llm_api_key() -> CommandResult<()> {
+    match keyring_entry()
+        .with_code(ErrorCode::Credentials)?
+        .delete_password()
+    {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(err) => Err(CommandError::new(
+            ErrorCode::Credentials,
+            format!("Failed to delete key from system keyring: {}", err),
+        )),
+    }
+}
+
+/// Whether the cleanup endpoint has its own key. Never returns the key.
+#[tauri::command]
+pub(crate) fn get_postproc_llm_api_key_status() -> bool {
+    matches!(read_endpoint_api_key(), Ok(Some(_)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_chat_url_and_rejects_implausible_answers() {
+        assert_eq!(chat_url(""), "http://127.0.0.1:8080/v1/chat/completions");
+        assert_eq!(
+            chat_url("http://localhost:11434/v1/"),
+            "http://localhost:11434/v1/chat/completions"
+        );
+        assert_eq!(
+            chat_url("https://api.openai.com"),
+            "https://api.openai.com/v1/chat/completions"
+        );
+        assert!(is_loopback_endpoint(
+            "http://127.0.0.1:8080/v1/chat/completions"
+        ));
+        assert!(!is_loopback_endpoint(
+            "https://api.openai.com/v1/chat/completions"
+        ));
+        assert!(is_openai_endpoint(
+            "https://api.openai.com/v1/chat/completions"
+        ));
+        assert!(!is_openai_endpoint(
+            "https://api.openai.com.example.net/v1/chat/completions"
+        ));
+        assert!(!is_openai_endpoint(
+            "http://127.0.0.1:8080/v1/chat/completions"
+        ));
+
+        let input = "um so i think we should uh ship it on friday";
+        assert!(plausible_cleanup(
+            input,
+            "So I think we should ship it on Friday."
+        ));
+        assert!(!plausible_cleanup(input, ""));
+        assert!(!plausible_cleanup(
+            input,
+            &"Sure! Here is the cleaned up version of your text with notes. ".repeat(3)
+        ));
+    }
+}
@@ -0,0 +1,279 @@
+//! File logging: rolling log files under %LOCALAPPDATA%\Trispr Flow\logs\
+//! plus the commands that let users change the level and find the files.
+//!
+//!   - trispr-flow.YYYY-MM-DD.txt         (all levels, daily rotation)
+//!   - trispr-flow-errors.YYYY-MM-DD.txt  (WARN+ERROR only — compact scan surface)
+//!
+//! Age is bounded by the appender's `max_log_files`; total size is bounded by
+//! pruning the oldest files at startup.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::SystemTime;
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{info, warn};
+use tracing_subscriber::{reload, EnvFilter, Registry};
+
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
+use crate::state::{AppState, Settings};
+
+const MAIN_LOG_PREFIX: &str = "trispr-flow";
+const ERROR_LOG_PREFIX: &str = "trispr-flow-errors";
+const LOG_SUFFIX: &str = "txt";
+/// Daily files kept per log before the appender deletes the oldest.
+const MAX_LOG_FILES: usize = 30;
+/// Upper bound for the whole log directory; a chatty debug day can be large.
+const MAX_LOG_DIR_BYTES: u64 = 200 * 1024 * 1024;
+
+pub(crate) const LOG_LEVELS: [&str; 5] = ["error", "warn", "info", "debug", "trace"];
+pub(crate) const DEFAULT_LOG_LEVEL: &str = "info";
+
+static LOG_FILTER: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct LogPaths {
+    pub(crate) directory: String,
+    /// Most recent main log file, if one has been written yet.
+    pub(crate) current: Option<String>,
+    pub(crate) errors: Option<String>,
+    pub(crate) level: String,
+}
+
+pub(crate) fn resolve_log_dir() -> PathBuf {
+    std::env::var("LOCALAPPDATA")
+        .map(|d| PathBuf::from(d).join("Trispr Flow").join("logs"))
+        .unwrap_or_else(|_| PathBuf::from("logs"))
+}
+
+/// Maps user input onto one of `LOG_LEVELS`, or `None` if it is not a level.
+pub(crate) fn normalize_log_level(level: &str) -> Option<&'static str> {
+    let level = level.trim().to_ascii_lowercase();
+    let level = if level == "warning" { "warn" } else { &level };
+    LOG_LEVELS.iter().copied().find(|known| *known == level)
+}
+
+/// `RUST_LOG` wins over the saved level so developers keep their filters.
+fn env_filter_overridden() -> bool {
+    std::env::var_os("RUST_LOG").is_some()
+}
+
+pub(crate) fn init_logging() {
+    use tracing_appender::rolling::{RollingFileAppender, Rotation};
+    use tracing_subscriber::{
+        filter::LevelFilter, fmt, layer::SubscriberExt, util::SubscriberInitExt, Layer,
+    };
+
+    let filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_LOG_LEVEL));
+    let (filter, filter_handle) = reload::Layer::new(filter);
+    let _ = LOG_FILTER.set(filter_handle);
+
+    let log_dir = resolve_log_dir();
+    let _ = std::fs::create_dir_all(&log_dir);
+    let pruned = prune_log_dir(&log_dir, MAX_LOG_DIR_BYTES);
+
+    let main_appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(MAIN_LOG_PREFIX)
+        .filename_suffix(LOG_SUFFIX)
+        .max_log_files(MAX_LOG_FILES)
+        .build(&log_dir)
+        .expect("failed to initialize main log appender");
+    let (main_nb, main_guard) = tracing_appender::non_blocking(main_appender);
+    std::mem::forget(main_guard);
+
+    let errors_appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(ERROR_LOG_PREFIX)
+        .filename_suffix(LOG_SUFFIX)
+        .max_log_files(MAX_LOG_FILES)
+        .build(&log_dir)
+        .expect("failed to initialize errors log appender");
+    let (errors_nb, errors_guard) = tracing_appender::non_blocking(errors_appender);
+    std::mem::forget(errors_guard);
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(
+            fmt::layer()
+                .with_target(false)
+                .with_thread_ids(false)
+                .with_file(true)
+                .with_line_number(true)
+                .with_writer(main_nb)
+                .with_ansi(false),
+        )
+        .with(
+            fmt::layer()
+                .with_target(false)
+                .with_thread_ids(false)
+                .with_file(true)
+                .with_line_number(true)
+                .with_writer(errors_nb)
+                .with_ansi(false)
+                .with_filter(LevelFilter::WARN),
+        )
+        .init();
+
+    info!("Trispr Flow starting up — log: {}", log_dir.display());
+    if pruned > 0 {
+        info!(
+            "Pruned {} old log file(s) to stay under the size limit",
+            pruned
+        );
+    }
+}
+
+/// Applies the saved log level to the running subscriber.
+pub(crate) fn sync_log_level(settings: &Settings) {
+    if env_filter_overridden() {
+        return;
+    }
+    let Some(handle) = LOG_FILTER.get() else {
+        return;
+    };
+    let level = normalize_log_level(&settings.log_level).unwrap_or(DEFAULT_LOG_LEVEL);
+    if let Err(err) = handle.reload(EnvFilter::new(level)) {
+        warn!("Failed to apply log level '{}': {}", level, err);
+    }
+}
+
+fn log_files(dir: &Path) -> Vec<(PathBuf, SystemTime, u64)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| name.starts_with(MAIN_LOG_PREFIX))
+        })
+        .filter_map(|entry| {
+            let meta = entry.metadata().ok()?;
+            if !meta.is_file() {
+                return None;
+            }
+            let modified = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            Some((entry.path(), modified, meta.len()))
+        })
+        .collect()
+}
+
+/// Deletes the oldest log files until the directory fits in `max_bytes`.
+/// The newest file is always kept. Returns how many files were removed.
+fn prune_log_dir(dir: &Path, max_bytes: u64) -> usize {
+    let mut files = log_files(dir);
+    files.sort_by_key(|(_, modified, _)| *modified);
+    let mut total: u64 = files.iter().map(|(_, _, len)| len).sum();
+    let mut removed = 0;
+    for (path, _, len) in files.iter().take(files.len().saturating_sub(1)) {
+        if total <= max_bytes {
+            break;
+        }
+        if std::fs::remove_file(path).is_ok() {
+            total = total.saturating_sub(*len);
+            removed += 1;
+        }
+    }
+    removed
+}
+
+fn newest_log_file(dir: &Path, prefix: &str) -> Option<PathBuf> {
+    let dated_prefix = format!("{}.", prefix);
+    log_files(dir)
+        .into_iter()
+        .filter(|(path, _, _)| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&dated_prefix))
+        })
+        .max_by_key(|(_, modified, _)| *modified)
+        .map(|(path, _, _)| path)
+}
+
+/// The main log file currently being written, if any.
+pub(crate) fn current_log_file() -> Option<PathBuf> {
+    newest_log_file(&resolve_log_dir(), MAIN_LOG_PREFIX)
+}
+
+#[tauri::command]
+pub(crate) fn get_log_path(app: AppHandle) -> LogPaths {
+    let dir = resolve_log_dir();
+    let level = if env_filter_overridden() {
+        std::env::var("RUST_LOG").unwrap_or_default()
+    } else {
+        app.state::<AppState>()
+            .settings
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .log_level
+            .clone()
+    };
+    LogPaths {
+        current: newest_log_file(&dir, MAIN_LOG_PREFIX).map(|p| p.to_string_lossy().to_string()),
+        errors: newest_log_file(&dir, ERROR_LOG_PREFIX).map(|p| p.to_string_lossy().to_string()),
+        directory: dir.to_string_lossy().to_string(),
+        level,
+    }
+}
+
+#[tauri::command]
+pub(crate) fn set_log_level(app: AppHandle, level: String) -> CommandResult<String> {
+    let level = normalize_log_level(&level).ok_or_else(|| {
+        CommandError::new(
+            ErrorCode::InvalidInput,
+            format!(
+                "Unknown log level '{}' (expected one of: {})",
+                level.trim(),
+                LOG_LEVELS.join(", ")
+            ),
+        )
+    })?;
+    let state = app.state::<AppState>();
+    let snapshot = {
+        let mut settings = state
+            .settings
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        settings.log_level = level.to_string();
+        settings.clone()
+    };
+    sync_log_level(&snapshot);
+    crate::save_settings_file(&app, &snapshot).with_code(ErrorCode::Storage)?;
+    let _ = app.emit("settings-changed", snapshot);
+    info!("Log level set to {}", level);
+    Ok(level.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_log_level_accepts_known_levels_only() {
+        assert_eq!(normalize_log_level(" DEBUG "), Some("debug"));
+        assert_eq!(normalize_log_level("warning"), Some("warn"));
+        assert_eq!(normalize_log_level("verbose"), None);
+    }
+
+    #[test]
+    fn prune_log_dir_removes_oldest_and_keeps_newest() {
+        let dir = std::env::temp_dir().join(format!("trispr-log-prune-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        for day in ["2026-01-01", "2026-01-02", "2026-01-03"] {
+            let path = dir.join(format!("{}.{}.txt", MAIN_LOG_PREFIX, day));
+            std::fs::write(&path, vec![b'x'; 100]).unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        std::fs::write(dir.join("unrelated.txt"), vec![b'x'; 1000]).unwrap();
+
+        assert_eq!(prune_log_dir(&dir, 150), 2);
+        assert!(dir.join("trispr-flow.2026-01-03.txt").exists());
+        assert!(dir.join("unrelated.txt").exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
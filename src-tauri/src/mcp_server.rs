@@ -0,0 +1,267 @@
+//! MCP (Model Context Protocol) adapter so AI assistants can use dictation
+//! and history as tools.
+//!
+//! Served by the HTTP API at `POST /v1/mcp` (Streamable HTTP transport, plain
+//! JSON responses) behind the same bearer token. Each request is one
+//! JSON-RPC 2.0 message; notifications get `202 Accepted` and no body.
+
+use serde_json::{json, Value};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::state::{AppState, HistoryEntry};
+
+const SUPPORTED_PROTOCOL_VERSIONS: [&str; 3] = ["2025-06-18", "2025-03-26", "2024-11-05"];
+const DEFAULT_SEARCH_LIMIT: usize = 20;
+const MAX_SEARCH_LIMIT: usize = 200;
+const DEFAULT_WAIT_SECS: u64 = 30;
+const MAX_WAIT_SECS: u64 = 120;
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+// JSON-RPC error codes.
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+fn tool_definitions() -> Value {
+    json!([
+        {
+            "name": "search_history",
+            "description": "Search Trispr Flow transcripts (dictation and system audio), newest first.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string", "description": "Case-insensitive text to look for; empty returns the latest entries." },
+                    "source": { "type": "string", "enum": ["all", "mic", "system"] },
+                    "limit": { "type": "integer", "minimum": 1, "maximum": MAX_SEARCH_LIMIT }
+                }
+            }
+        },
+        {
+            "name": "transcribe_file",
+            "description": "Transcribe an audio or video file on this machine with the configured speech model.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string", "description": "Absolute path to the file (wav, mp3, m4a, flac, ogg, mp4, …)." },
+                    "language": { "type": "string", "description": "Language code, or \"auto\"." }
+                },
+                "required": ["path"]
+            }
+        },
+        {
+            "name": "wait_for_dictation",
+            "description": "Wait for the next finished dictation and return its text.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "timeout_seconds": { "type": "integer", "minimum": 1, "maximum": MAX_WAIT_SECS }
+                }
+            }
+        },
+        {
+            "name": "get_status",
+            "description": "Current capture mode, recording and system-audio transcription state.",
+            "inputSchema": { "type": "object", "properties": {} }
+        }
+    ])
+}
+
+fn rpc_result(id: &Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+fn rpc_error(id: &Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+fn text_content(text: String, is_error: bool) -> Value {
+    json!({ "content": [{ "type": "text", "text": text }], "isError": is_error })
+}
+
+fn entry_json(entry: &HistoryEntry) -> Value {
+    json!({
+        "id": entry.id,
+        "text": entry.text,
+        "source": entry.source,
+        "speaker": entry.speaker_name,
+        "timestamp_ms": entry.timestamp_ms,
+    })
+}
+
+fn history_snapshot(app: &AppHandle, mic: bool, system: bool) -> Vec<HistoryEntry> {
+    let state = app.state::<AppState>();
+    let mut entries = Vec::new();
+    if mic {
+        let history = state
+            .history
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        entries.extend(history.active.iter().cloned());
+    }
+    if system {
+        let history = state
+            .history_transcribe
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        entries.extend(history.active.iter().cloned());
+    }
+    entries.sort_by(|a, b| b.timestamp_ms.cmp(&a.timestamp_ms));
+    entries
+}
+
+fn search_history(app: &AppHandle, args: &Value) -> Result<String, String> {
+    let query = args["query"]
+        .as_str()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase();
+    let (mic, system) = match args["source"].as_str().unwrap_or("all") {
+        "all" => (true, true),
+        "mic" => (true, false),
+        "system" => (false, true),
+        other => return Err(format!("Unknown source '{}'", other)),
+    };
+    let limit = args["limit"]
+        .as_u64()
+        .map(|limit| limit as usize)
+        .unwrap_or(DEFAULT_SEARCH_LIMIT)
+        .clamp(1, MAX_SEARCH_LIMIT);
+    let matches: Vec<Value> = history_snapshot(app, mic, system)
+        .iter()
+        .filter(|entry| query.is_empty() || entry.text.to_lowercase().contains(&query))
+        .take(limit)
+        .map(entry_json)
+        .collect();
+    serde_json::to_string_pretty(&matches).map_err(|e| e.to_string())
+}
+
+fn transcribe_file(app: &AppHandle, args: &Value) -> Result<String, String> {
+    let path = args["path"]
+        .as_str()
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .ok_or_else(|| "Missing 'path'".to_string())?;
+    let options = crate::file_transcription::FileTranscriptionOptions {
+        language: args["language"].as_str().map(str::to_string),
+        ..Default::default()
+    };
+    let path = std::path::PathBuf::from(path);
+    let worker_app = app.clone();
+    crate::batch_queue::run_blocking(app, path.to_string_lossy().to_string(), move || {
+        crate::file_transcription::transcribe_file_blocking(&worker_app, &path, &options)
+    })
+    .map(|transcript| transcript.text)
+}
+
+/// Long-polls the dictation history for an entry newer than the call.
+fn wait_for_dictation(app: &AppHandle, args: &Value) -> Result<String, String> {
+    let timeout = Duration::from_secs(
+        args["timeout_seconds"]
+            .as_u64()
+            .unwrap_or(DEFAULT_WAIT_SECS)
+            .clamp(1, MAX_WAIT_SECS),
+    );
+    let since = crate::util::now_ms();
+    let started = std::time::Instant::now();
+    while started.elapsed() < timeout {
+        if let Some(entry) = history_snapshot(app, true, false)
+            .into_iter()
+            .find(|entry| entry.timestamp_ms > since)
+        {
+            return serde_json::to_string_pretty(&entry_json(&entry)).map_err(|e| e.to_string());
+        }
+        std::thread::sleep(WAIT_POLL_INTERVAL);
+    }
+    Err(format!(
+        "No dictation finished within {} seconds",
+        timeout.as_secs()
+    ))
+}
+
+fn call_tool(app: &AppHandle, params: &Value) -> Result<Value, (i64, String)> {
+    let name = params["name"]
+        .as_str()
+        .ok_or_else(|| (INVALID_PARAMS, "Missing tool name".to_string()))?;
+    let args = &params["arguments"];
+    let outcome = match name {
+        "search_history" => search_history(app, args),
+        "transcribe_file" => transcribe_file(app, args),
+        "wait_for_dictation" => wait_for_dictation(app, args),
+        "get_status" => Ok(crate::http_api::status_body(app).to_string()),
+        other => return Err((INVALID_PARAMS, format!("Unknown tool '{}'", other))),
+    };
+    // Tool failures are results the model can read, not protocol errors.
+    Ok(match outcome {
+        Ok(text) => text_content(text, false),
+        Err(err) => text_content(err, true),
+    })
+}
+
+fn negotiate_version(params: &Value) -> &'static str {
+    let requested = params["protocolVersion"].as_str().unwrap_or_default();
+    SUPPORTED_PROTOCOL_VERSIONS
+        .iter()
+        .copied()
+        .find(|version| *version == requested)
+        .unwrap_or(SUPPORTED_PROTOCOL_VERSIONS[0])
+}
+
+/// Handles one JSON-RPC message; `None` for notifications.
+pub(crate) fn handle_message(app: &AppHandle, body: &[u8]) -> Option<Value> {
+    let message: Value = match serde_json::from_slice(body) {
+        Ok(message) => message,
+        Err(err) => return Some(rpc_error(&Value::Null, PARSE_ERROR, &err.to_string())),
+    };
+    let Some(method) = message["method"].as_str() else {
+        return Some(rpc_error(
+            &message["id"],
+            INVALID_REQUEST,
+            "Expected a JSON-RPC request",
+        ));
+    };
+    let id = message.get("id")?;
+    let params = &message["params"];
+    Some(match method {
+        "initialize" => rpc_result(
+            id,
+            json!({
+                "protocolVersion": negotiate_version(params),
+                "capabilities": { "tools": { "listChanged": false } },
+                "serverInfo": { "name": "trispr-flow", "version": env!("CARGO_PKG_VERSION") },
+            }),
+        ),
+        "ping" => rpc_result(id, json!({})),
+        "tools/list" => rpc_result(id, json!({ "tools": tool_definitions() })),
+        "tools/call" => match call_tool(app, params) {
+            Ok(result) => rpc_result(id, result),
+            Err((code, message)) => rpc_error(id, code, &message),
+        },
+        other => rpc_error(id, METHOD_NOT_FOUND, &format!("Unknown method '{}'", other)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negotiates_known_versions_and_lists_tools() {
+        assert_eq!(
+            negotiate_version(&json!({ "protocolVersion": "2024-11-05" })),
+            "2024-11-05"
+        );
+        assert_eq!(
+            negotiate_version(&json!({ "protocolVersion": "1999-01-01" })),
+            SUPPORTED_PROTOCOL_VERSIONS[0]
+        );
+        let names: Vec<&str> = tool_definitions()
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|tool| tool["name"].as_str())
+            .collect();
+        assert!(names.contains(&"search_history") && names.contains(&"transcribe_file"));
+    }
+}
@@ -0,0 +1,351 @@
+//! Reads GGML/GGUF model headers so broken or foreign files show up in the
+//! model list instead of failing later inside whisper-cli.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::SystemTime;
+
+/// "ggml" as written by whisper.cpp (little-endian u32).
+const GGML_MAGIC: u32 = 0x6767_6d6c;
+const GGUF_MAGIC: &[u8; 4] = b"GGUF";
+/// whisper.cpp stores `ftype + GGML_QNT_VERSION * 1000` in the header.
+const GGML_QNT_VERSION_FACTOR: i32 = 1000;
+/// Upper bounds that keep a corrupted header from driving huge reads.
+const GGUF_MAX_KV: u64 = 1 << 16;
+const GGUF_MAX_TENSORS: u64 = 1 << 20;
+const GGUF_MAX_STRING: u64 = 1 << 20;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub(crate) struct ModelMetadata {
+    /// "ggml" or "gguf"; `None` when the magic bytes are unknown.
+    pub(crate) format: Option<String>,
+    pub(crate) quantization: Option<String>,
+    pub(crate) parameter_count: Option<u64>,
+    pub(crate) is_whisper: bool,
+    /// Why the file does not look like a usable whisper model, if it does not.
+    pub(crate) header_error: Option<String>,
+}
+
+fn ftype_name(ftype: u32) -> String {
+    match ftype {
+        0 => "f32",
+        1 => "f16",
+        2 => "q4_0",
+        3 => "q4_1",
+        4 => "q4_1_f16",
+        7 => "q8_0",
+        8 => "q5_0",
+        9 => "q5_1",
+        10 => "q2_k",
+        11 => "q3_k",
+        12 => "q4_k",
+        13 => "q5_k",
+        14 => "q6_k",
+        other => return format!("type_{other}"),
+    }
+    .to_string()
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_i32(reader: &mut impl Read) -> io::Result<i32> {
+    read_u32(reader).map(|v| v as i32)
+}
+
+fn read_u64(reader: &mut impl Read) -> io::Result<u64> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+/// Parses a whisper.cpp GGML header (after the magic).
+fn read_ggml_header(reader: &mut impl Read) -> io::Result<ModelMetadata> {
+    let n_vocab = read_i32(reader)?;
+    let n_audio_ctx = read_i32(reader)?;
+    let n_audio_state = read_i32(reader)?;
+    let _n_audio_head = read_i32(reader)?;
+    let n_audio_layer = read_i32(reader)?;
+    let n_text_ctx = read_i32(reader)?;
+    let n_text_state = read_i32(reader)?;
+    let _n_text_head = read_i32(reader)?;
+    let n_text_layer = read_i32(reader)?;
+    let n_mels = read_i32(reader)?;
+    let ftype = read_i32(reader)?;
+    let plausible = (1..=1_000_000).contains(&n_vocab)
+        && (1..=64).contains(&n_audio_layer)
+        && (1..=64).contains(&n_text_layer)
+        && (1..=8192).contains(&n_audio_state)
+        && n_audio_state == n_text_state
+        && (1..=256).contains(&n_mels)
+        && ftype >= 0;
+    if !plausible {
+        return Ok(ModelMetadata {
+            format: Some("ggml".to_string()),
+            header_error: Some("GGML header does not describe a whisper model".to_string()),
+            ..ModelMetadata::default()
+        });
+    }
+
+    // Rough count from the transformer shapes; close enough to tell tiny from large.
+    let d = n_audio_state as u64;
+    let parameter_count = n_audio_layer as u64 * 12 * d * d
+        + n_text_layer as u64 * 16 * d * d
+        + (n_vocab as u64 + n_text_ctx as u64 + n_audio_ctx as u64) * d
+        + 3 * d * (n_mels as u64 + d);
+
+    Ok(ModelMetadata {
+        format: Some("ggml".to_string()),
+        quantization: Some(ftype_name((ftype % GGML_QNT_VERSION_FACTOR) as u32)),
+        parameter_count: Some(parameter_count),
+        is_whisper: true,
+        header_error: None,
+    })
+}
+
+fn read_gguf_string(reader: &mut impl Read) -> io::Result<String> {
+    let len = read_u64(reader)?;
+    if len > GGUF_MAX_STRING {
+        return Err(invalid("GGUF string too long"));
+    }
+    let mut buf = vec![0u8; len as usize];
+    reader.read_exact(&mut buf)?;
+    Ok(String::from_utf8_lossy(&buf).into_owned())
+}
+
+fn gguf_scalar_size(value_type: u32) -> Option<i64> {
+    match value_type {
+        0 | 1 | 7 => Some(1), // u8, i8, bool
+        2 | 3 => Some(2),     // u16, i16
+        4..=6 => Some(4),     // u32, i32, f32
+        10..=12 => Some(8),   // u64, i64, f64
+        _ => None,
+    }
+}
+
+enum GgufValue {
+    Str(String),
+    Int(u64),
+    Other,
+}
+
+fn read_gguf_value<R: Read + Seek>(reader: &mut R, value_type: u32) -> io::Result<GgufValue> {
+    match value_type {
+        8 => read_gguf_string(reader).map(GgufValue::Str),
+        4 => read_u32(reader).map(|v| GgufValue::Int(v as u64)),
+        9 => {
+            let item_type = read_u32(reader)?;
+            let count = read_u64(reader)?;
+            if let Some(size) = gguf_scalar_size(item_type) {
+                let bytes = i64::try_from(count)
+                    .ok()
+                    .and_then(|count| count.checked_mul(size))
+                    .ok_or_else(|| invalid("GGUF array too large"))?;
+                reader.seek(SeekFrom::Current(bytes))?;
+            } else {
+                for _ in 0..count {
+                    read_gguf_value(reader, item_type)?;
+                }
+            }
+            Ok(GgufValue::Other)
+        }
+        other => {
+            let size = gguf_scalar_size(other).ok_or_else(|| invalid("Unknown GGUF value type"))?;
+            reader.seek(SeekFrom::Current(size))?;
+            Ok(GgufValue::Other)
+        }
+    }
+}
+
+/// Parses a GGUF header (after the magic): metadata keys plus tensor shapes.
+fn read_gguf_header<R: Read + Seek>(reader: &mut R) -> io::Result<ModelMetadata> {
+    let version = read_u32(reader)?;
+    if !(2..=3).contains(&version) {
+        return Err(invalid("Unsupported GGUF version"));
+    }
+    let tensor_count = read_u64(reader)?;
+    let kv_count = read_u64(reader)?;
+    if tensor_count > GGUF_MAX_TENSORS || kv_count > GGUF_MAX_KV {
+        return Err(invalid("GGUF header counts are out of range"));
+    }
+
+    let mut architecture = None;
+    let mut file_type = None;
+    for _ in 0..kv_count {
+        let key = read_gguf_string(reader)?;
+        let value_type = read_u32(reader)?;
+        match (key.as_str(), read_gguf_value(reader, value_type)?) {
+            ("general.architecture", GgufValue::Str(value)) => architecture = Some(value),
+            ("general.file_type", GgufValue::Int(value)) => file_type = Some(value as u32),
+            _ => {}
+        }
+    }
+
+    let mut parameter_count = 0u64;
+    for _ in 0..tensor_count {
+        read_gguf_string(reader)?;
+        let n_dims = read_u32(reader)?;
+        if n_dims > 8 {
+            return Err(invalid("GGUF tensor has too many dimensions"));
+        }
+        let mut elements = 1u64;
+        for _ in 0..n_dims {
+            elements = elements.saturating_mul(read_u64(reader)?);
+        }
+        let _tensor_type = read_u32(reader)?;
+        let _offset = read_u64(reader)?;
+        parameter_count = parameter_count.saturating_add(elements);
+    }
+
+    let is_whisper = architecture.as_deref() == Some("whisper");
+    Ok(ModelMetadata {
+        format: Some("gguf".to_string()),
+        quantization: file_type.map(ftype_name),
+        parameter_count: (tensor_count > 0).then_some(parameter_count),
+        is_whisper,
+        header_error: (!is_whisper).then(|| {
+            format!(
+                "GGUF model architecture is {}, not whisper",
+                architecture.as_deref().unwrap_or("unknown")
+            )
+        }),
+    })
+}
+
+fn read_header<R: Read + Seek>(reader: &mut R) -> io::Result<ModelMetadata> {
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic)?;
+    if &magic == GGUF_MAGIC {
+        return read_gguf_header(reader);
+    }
+    if u32::from_le_bytes(magic) == GGML_MAGIC {
+        return read_ggml_header(reader);
+    }
+    Ok(ModelMetadata {
+        header_error: Some("Not a GGML or GGUF model file".to_string()),
+        ..ModelMetadata::default()
+    })
+}
+
+fn inspect_uncached(path: &Path) -> ModelMetadata {
+    let result = fs::File::open(path).and_then(|file| read_header(&mut BufReader::new(file)));
+    result.unwrap_or_else(|err| ModelMetadata {
+        header_error: Some(if err.kind() == io::ErrorKind::UnexpectedEof {
+            "Model file is truncated".to_string()
+        } else {
+            format!("Failed to read model header: {err}")
+        }),
+        ..ModelMetadata::default()
+    })
+}
+
+type CacheKey = (PathBuf, u64, Option<SystemTime>);
+
+static METADATA_CACHE: OnceLock<Mutex<HashMap<CacheKey, ModelMetadata>>> = OnceLock::new();
+
+/// Header metadata for a model file, cached by path, size and modification time.
+pub(crate) fn inspect_model_file(path: &Path) -> ModelMetadata {
+    let Ok(meta) = fs::metadata(path) else {
+        return inspect_uncached(path);
+    };
+    let key = (path.to_path_buf(), meta.len(), meta.modified().ok());
+    let cache = METADATA_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(hit) = cache
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(&key)
+    {
+        return hit.clone();
+    }
+    let metadata = inspect_uncached(path);
+    cache
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(key, metadata.clone());
+    metadata
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn ggml_header(hparams: [i32; 11]) -> Vec<u8> {
+        let mut bytes = GGML_MAGIC.to_le_bytes().to_vec();
+        for value in hparams {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes
+    }
+
+    fn gguf_string(bytes: &mut Vec<u8>, value: &str) {
+        bytes.extend_from_slice(&(value.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(value.as_bytes());
+    }
+
+    #[test]
+    fn reads_whisper_ggml_header() {
+        // ggml-base.bin, q5_0 with GGML_QNT_VERSION 1.
+        let bytes = ggml_header([51865, 1500, 512, 8, 6, 448, 512, 8, 6, 80, 1008]);
+        let meta = read_header(&mut Cursor::new(bytes)).unwrap();
+        assert_eq!(meta.format.as_deref(), Some("ggml"));
+        assert_eq!(meta.quantization.as_deref(), Some("q5_0"));
+        assert!(meta.is_whisper);
+        let params = meta.parameter_count.unwrap();
+        assert!((60_000_000..90_000_000).contains(&params), "{params}");
+    }
+
+    #[test]
+    fn flags_unknown_and_truncated_files() {
+        let meta = read_header(&mut Cursor::new(b"PK\x03\x04rest".to_vec())).unwrap();
+        assert!(!meta.is_whisper);
+        assert!(meta.header_error.is_some());
+
+        let mut truncated = ggml_header([51865, 1500, 512, 8, 6, 448, 512, 8, 6, 80, 1]);
+        truncated.truncate(20);
+        assert!(read_header(&mut Cursor::new(truncated)).is_err());
+    }
+
+    #[test]
+    fn reads_gguf_architecture_and_tensors() {
+        let mut bytes = GGUF_MAGIC.to_vec();
+        bytes.extend_from_slice(&3u32.to_le_bytes());
+        bytes.extend_from_slice(&1u64.to_le_bytes()); // tensors
+        bytes.extend_from_slice(&3u64.to_le_bytes()); // kv pairs
+        gguf_string(&mut bytes, "general.architecture");
+        bytes.extend_from_slice(&8u32.to_le_bytes());
+        gguf_string(&mut bytes, "llama");
+        gguf_string(&mut bytes, "tokenizer.ggml.scores");
+        bytes.extend_from_slice(&9u32.to_le_bytes());
+        bytes.extend_from_slice(&6u32.to_le_bytes());
+        bytes.extend_from_slice(&2u64.to_le_bytes());
+        bytes.extend_from_slice(&[0u8; 8]);
+        gguf_string(&mut bytes, "general.file_type");
+        bytes.extend_from_slice(&4u32.to_le_bytes());
+        bytes.extend_from_slice(&7u32.to_le_bytes());
+        gguf_string(&mut bytes, "token_embd.weight");
+        bytes.extend_from_slice(&2u32.to_le_bytes());
+        bytes.extend_from_slice(&4096u64.to_le_bytes());
+        bytes.extend_from_slice(&32000u64.to_le_bytes());
+        bytes.extend_from_slice(&8u32.to_le_bytes());
+        bytes.extend_from_slice(&0u64.to_le_bytes());
+
+        let meta = read_header(&mut Cursor::new(bytes)).unwrap();
+        assert_eq!(meta.format.as_deref(), Some("gguf"));
+        assert_eq!(meta.quantization.as_deref(), Some("q8_0"));
+        assert_eq!(meta.parameter_count, Some(4096 * 32000));
+        assert!(!meta.is_whisper);
+        assert!(meta.header_error.unwrap().contains("llama"));
+    }
+}
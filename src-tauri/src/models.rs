@@ -1,4 +1,6 @@
-use crate::paths::{resolve_models_dir, resolve_quantize_path};
+use crate::errors::{AppError, CommandError, CommandResult, ErrorCode, WithErrorCode};
+use crate::model_metadata::{inspect_model_file, ModelMetadata};
+use crate::paths::{available_disk_space, resolve_models_dir, resolve_quantize_path};
 use crate::state::{save_settings_file, AppState};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
@@ -9,10 +11,11 @@ use std::net::{IpAddr, ToSocketAddrs};
 use std::path::PathBuf;
 use std::process::Stdio;
 use std::sync::atomic::{AtomicU8, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
 use std::thread;
 use std::time::{Duration, Instant};
-use tauri::{AppHandle, Emitter, Manager, State};
+use tauri::menu::{CheckMenuItem, MenuItem, MenuItemKind, Submenu};
+use tauri::{AppHandle, Emitter, Manager, State, Wry};
 use tracing::{error, info, warn};
 use url::Url;
 
@@ -22,6 +25,7 @@ const DOWNLOAD_TIMEOUT_SECS: u64 = 30; // Timeout for stalled downloads
 const DOWNLOAD_CONNECT_TIMEOUT_SECS: u64 = 10;
 const DOWNLOAD_READ_TIMEOUT_SECS: u64 = 30;
 const DOWNLOAD_REDIRECT_LIMIT: u32 = 5;
+const DOWNLOAD_DISK_HEADROOM_BYTES: u64 = 256 * 1024 * 1024; // Keep free after a download
 
 /// URL validation levels for model downloads
 ///
@@ -183,6 +187,15 @@ fn build_download_agent() -> ureq::Agent {
 }
 
 fn http_get_with_redirects(url: &str) -> Result<ureq::Response, String> {
+    http_get_with_redirects_from(url, None)
+}
+
+/// Like `http_get_with_redirects`, but asks the server to resume at `range_start`.
+/// Servers that ignore the Range header answer with 200 and the full body.
+fn http_get_with_redirects_from(
+    url: &str,
+    range_start: Option<u64>,
+) -> Result<ureq::Response, String> {
     let agent = build_download_agent();
     let mut current = url.to_string();
     let mut is_first = true;
@@ -196,7 +209,11 @@ fn http_get_with_redirects(url: &str) -> Result<ureq::Response, String> {
         };
         let parsed = validate_model_url(&current, safety_mode)?;
         is_first = false;
-        let response = match agent.get(parsed.as_str()).call() {
+        let mut request = agent.get(parsed.as_str());
+        if let Some(start) = range_start.filter(|start| *start > 0) {
+            request = request.set("Range", &format!("bytes={start}-"));
+        }
+        let response = match request.call() {
             Ok(resp) => resp,
             Err(ureq::Error::Status(code, resp)) => {
                 if (300..400).contains(&code) {
@@ -421,6 +438,83 @@ mod tests {
         assert!(q8_name.contains("-q5_0") || q8_name.contains("-q8_0"));
         assert!(!(plain.contains("-q5_0") || plain.contains("-q8_0")));
     }
+
+    #[test]
+    fn import_file_name_rejects_non_model_files() {
+        use std::path::Path;
+        assert_eq!(
+            import_file_name(Path::new("/opt/other-app/ggml-base.bin")).unwrap(),
+            "ggml-base.bin"
+        );
+        assert!(import_file_name(Path::new("/opt/other-app/model.safetensors")).is_err());
+        assert!(import_file_name(Path::new("/opt/other-app/my model.gguf")).is_err());
+    }
+
+    #[test]
+    fn check_disk_space_requires_headroom() {
+        let model = 1024 * 1024 * 1024;
+        assert!(check_disk_space(model, None).is_ok());
+        assert!(check_disk_space(model, Some(model + DOWNLOAD_DISK_HEADROOM_BYTES)).is_ok());
+        let err = check_disk_space(model, Some(model)).unwrap_err();
+        assert!(matches!(err, AppError::Storage(_)));
+        assert!(err.message().contains("Not enough disk space"));
+    }
+
+    #[test]
+    fn download_queue_runs_one_job_at_a_time() {
+        let mut queue = DownloadQueue::default();
+        queue.enqueue("a", "https://x/a.bin", "a.bin").unwrap();
+        queue.enqueue("b", "https://x/b.bin", "b.bin").unwrap();
+        assert!(queue.enqueue("a", "https://x/a.bin", "a.bin").is_err());
+
+        let (first, _) = queue.start_next().expect("first job starts");
+        assert_eq!(first.model_id, "a");
+        assert!(queue.start_next().is_none());
+
+        queue.remove("a");
+        let (second, _) = queue.start_next().expect("second job starts");
+        assert_eq!(second.model_id, "b");
+    }
+
+    #[test]
+    fn download_queue_pause_and_cancel_signal_active_job() {
+        let mut queue = DownloadQueue::default();
+        queue.enqueue("a", "https://x/a.bin", "a.bin").unwrap();
+        queue.enqueue("b", "https://x/b.bin", "b.bin").unwrap();
+        let (_, control) = queue.start_next().unwrap();
+
+        queue.pause("a").unwrap();
+        assert_eq!(control.load(Ordering::Relaxed), DOWNLOAD_CONTROL_PAUSE);
+        assert!(queue.signal_active("a", DOWNLOAD_CONTROL_CANCEL));
+        assert_eq!(control.load(Ordering::Relaxed), DOWNLOAD_CONTROL_CANCEL);
+
+        queue.pause("b").unwrap();
+        assert_eq!(queue.jobs[1].status, DownloadStatus::Paused);
+        queue.resume("b").unwrap();
+        assert_eq!(queue.jobs[1].status, DownloadStatus::Queued);
+        assert!(queue.pause("missing").is_err());
+    }
+
+    #[test]
+    fn download_queue_restore_requeues_interrupted_jobs() {
+        let job = |id: &str, status| DownloadJob {
+            model_id: id.to_string(),
+            url: format!("https://x/{id}.bin"),
+            file_name: format!("{id}.bin"),
+            status,
+            downloaded: 10,
+            total: Some(100),
+        };
+        let queue = DownloadQueue::restore(vec![
+            job("a", DownloadStatus::Downloading),
+            job("b", DownloadStatus::Paused),
+            job("a", DownloadStatus::Queued),
+        ]);
+        assert_eq!(queue.jobs.len(), 2);
+        assert_eq!(queue.jobs[0].status, DownloadStatus::Queued);
+        assert_eq!(queue.jobs[1].status, DownloadStatus::Paused);
+        assert!(queue.active.is_none());
+    }
 }
 
 fn find_model_in_dir(dir: &PathBuf, spec: &ModelSpec) -> Option<PathBuf> {
@@ -855,6 +949,9 @@ pub(crate) struct ModelInfo {
     available: bool,
     download_url: Option<String>,
     removable: bool,
+    /// Parsed from the file header when the model is installed.
+    #[serde(flatten)]
+    header: Option<ModelMetadata>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -876,6 +973,353 @@ pub(crate) struct DownloadError {
     error: String,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct DownloadStatusEvent {
+    id: String,
+}
+
+const DOWNLOAD_CONTROL_RUN: u8 = 0;
+const DOWNLOAD_CONTROL_PAUSE: u8 = 1;
+const DOWNLOAD_CONTROL_CANCEL: u8 = 2;
+const DOWNLOAD_QUEUE_FILE: &str = "download_queue.json";
+
+/// Why a model download stopped before the file was moved into place.
+#[derive(Debug)]
+enum DownloadFailure {
+    /// Stopped on request; the `.part` file is kept with this many bytes.
+    Paused(u64),
+    /// Stopped on request; the `.part` file is removed.
+    Cancelled,
+    /// Failed before writing anything, with a categorized error for the UI.
+    App(AppError),
+    Error(String),
+}
+
+impl From<String> for DownloadFailure {
+    fn from(error: String) -> Self {
+        DownloadFailure::Error(error)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum DownloadStatus {
+    Queued,
+    Downloading,
+    Paused,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct DownloadJob {
+    model_id: String,
+    url: String,
+    file_name: String,
+    status: DownloadStatus,
+    #[serde(default)]
+    downloaded: u64,
+    #[serde(default)]
+    total: Option<u64>,
+}
+
+/// Model downloads run one at a time; everything else waits in order.
+#[derive(Debug, Default)]
+struct DownloadQueue {
+    jobs: Vec<DownloadJob>,
+    /// Control flag of the job currently downloading.
+    active: Option<(String, Arc<AtomicU8>)>,
+}
+
+impl DownloadQueue {
+    fn contains(&self, model_id: &str) -> bool {
+        self.jobs.iter().any(|job| job.model_id == model_id)
+    }
+
+    fn job_mut(&mut self, model_id: &str) -> Option<&mut DownloadJob> {
+        self.jobs.iter_mut().find(|job| job.model_id == model_id)
+    }
+
+    fn enqueue(&mut self, model_id: &str, url: &str, file_name: &str) -> Result<(), String> {
+        if self.contains(model_id) {
+            return Err("Download already in progress".to_string());
+        }
+        self.jobs.push(DownloadJob {
+            model_id: model_id.to_string(),
+            url: url.to_string(),
+            file_name: file_name.to_string(),
+            status: DownloadStatus::Queued,
+            downloaded: 0,
+            total: None,
+        });
+        Ok(())
+    }
+
+    fn remove(&mut self, model_id: &str) -> Option<DownloadJob> {
+        let index = self.jobs.iter().position(|job| job.model_id == model_id)?;
+        if self
+            .active
+            .as_ref()
+            .is_some_and(|(active_id, _)| active_id == model_id)
+        {
+            self.active = None;
+        }
+        Some(self.jobs.remove(index))
+    }
+
+    /// Picks the next queued job and marks it downloading, unless one already runs.
+    fn start_next(&mut self) -> Option<(DownloadJob, Arc<AtomicU8>)> {
+        if self.active.is_some() {
+            return None;
+        }
+        let job = self
+            .jobs
+            .iter_mut()
+            .find(|job| job.status == DownloadStatus::Queued)?;
+        job.status = DownloadStatus::Downloading;
+        let control = Arc::new(AtomicU8::new(DOWNLOAD_CONTROL_RUN));
+        self.active = Some((job.model_id.clone(), control.clone()));
+        Some((job.clone(), control))
+    }
+
+    /// Signals the active job, or returns false when `model_id` is not running.
+    fn signal_active(&self, model_id: &str, signal: u8) -> bool {
+        match self.active.as_ref() {
+            Some((active_id, control)) if active_id == model_id => {
+                control.store(signal, Ordering::Relaxed);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn pause(&mut self, model_id: &str) -> Result<(), String> {
+        if self.signal_active(model_id, DOWNLOAD_CONTROL_PAUSE) {
+            return Ok(());
+        }
+        let job = self
+            .job_mut(model_id)
+            .ok_or_else(|| "No download queued for this model".to_string())?;
+        job.status = DownloadStatus::Paused;
+        Ok(())
+    }
+
+    fn resume(&mut self, model_id: &str) -> Result<(), String> {
+        let job = self
+            .job_mut(model_id)
+            .ok_or_else(|| "No download queued for this model".to_string())?;
+        if job.status == DownloadStatus::Paused {
+            job.status = DownloadStatus::Queued;
+        }
+        Ok(())
+    }
+
+    /// Jobs interrupted by a restart go back into the queue; paused ones stay paused.
+    fn restore(jobs: Vec<DownloadJob>) -> Self {
+        let mut queue = DownloadQueue::default();
+        for mut job in jobs {
+            if queue.contains(&job.model_id) {
+                continue;
+            }
+            if job.status == DownloadStatus::Downloading {
+                job.status = DownloadStatus::Queued;
+            }
+            queue.jobs.push(job);
+        }
+        queue
+    }
+}
+
+static DOWNLOAD_QUEUE: OnceLock<Mutex<DownloadQueue>> = OnceLock::new();
+
+fn download_queue() -> MutexGuard<'static, DownloadQueue> {
+    DOWNLOAD_QUEUE
+        .get_or_init(|| Mutex::new(DownloadQueue::default()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn update_queued_progress(model_id: &str, downloaded: u64, total: Option<u64>) {
+    let mut queue = download_queue();
+    if let Some(job) = queue.job_mut(model_id) {
+        job.downloaded = downloaded;
+        job.total = total;
+    }
+}
+
+/// Persists the queue, mirrors it into `AppState::downloads` and notifies the UI.
+fn publish_download_queue(app: &AppHandle, queue: &DownloadQueue) {
+    let path = crate::paths::resolve_data_path(app, DOWNLOAD_QUEUE_FILE);
+    if queue.jobs.is_empty() {
+        let _ = fs::remove_file(&path);
+    } else {
+        match serde_json::to_string_pretty(&queue.jobs) {
+            Ok(raw) => {
+                if let Err(err) = fs::write(&path, raw) {
+                    warn!("Failed to persist download queue: {}", err);
+                }
+            }
+            Err(err) => warn!("Failed to serialize download queue: {}", err),
+        }
+    }
+
+    let state = app.state::<AppState>();
+    let mut downloads = state
+        .downloads
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    downloads.clear();
+    downloads.extend(queue.jobs.iter().map(|job| job.model_id.clone()));
+    drop(downloads);
+
+    let _ = app.emit("model:download-queue", queue.jobs.clone());
+}
+
+/// Starts the next queued download if nothing is downloading right now.
+fn pump_download_queue(app: &AppHandle) {
+    let mut queue = download_queue();
+    let Some((job, control)) = queue.start_next() else {
+        return;
+    };
+    publish_download_queue(app, &queue);
+    drop(queue);
+
+    let app_handle = app.clone();
+    crate::util::spawn_guarded("model_download", move || {
+        let result = download_model_file(
+            &app_handle,
+            &job.model_id,
+            &job.url,
+            &job.file_name,
+            &control,
+        );
+        let mut queue = download_queue();
+        match result {
+            Ok(path) => {
+                queue.remove(&job.model_id);
+                let _ = app_handle.emit(
+                    "model:download-complete",
+                    DownloadComplete {
+                        id: job.model_id.clone(),
+                        path: path.to_string_lossy().to_string(),
+                    },
+                );
+            }
+            Err(DownloadFailure::Paused(downloaded)) => {
+                queue.active = None;
+                if let Some(entry) = queue.job_mut(&job.model_id) {
+                    entry.status = DownloadStatus::Paused;
+                    entry.downloaded = downloaded;
+                }
+                info!("Paused model download {}", job.model_id);
+                let _ = app_handle.emit(
+                    "model:download-paused",
+                    DownloadStatusEvent {
+                        id: job.model_id.clone(),
+                    },
+                );
+            }
+            Err(DownloadFailure::Cancelled) => {
+                queue.remove(&job.model_id);
+                info!("Cancelled model download {}", job.model_id);
+                let _ = app_handle.emit(
+                    "model:download-cancelled",
+                    DownloadStatusEvent {
+                        id: job.model_id.clone(),
+                    },
+                );
+            }
+            Err(DownloadFailure::App(err)) => {
+                queue.remove(&job.model_id);
+                let _ = app_handle.emit(
+                    "model:download-error",
+                    DownloadError {
+                        id: job.model_id.clone(),
+                        error: err.message().to_string(),
+                    },
+                );
+                crate::emit_error(&app_handle, err, Some("Model download"));
+            }
+            Err(DownloadFailure::Error(error)) => {
+                queue.remove(&job.model_id);
+                let _ = app_handle.emit(
+                    "model:download-error",
+                    DownloadError {
+                        id: job.model_id.clone(),
+                        error,
+                    },
+                );
+            }
+        }
+        publish_download_queue(&app_handle, &queue);
+        drop(queue);
+        pump_download_queue(&app_handle);
+    });
+}
+
+/// Restores the download queue persisted by a previous run and resumes it.
+pub(crate) fn init_download_queue(app: &AppHandle) {
+    let path = crate::paths::resolve_data_path(app, DOWNLOAD_QUEUE_FILE);
+    let jobs: Vec<DownloadJob> = match fs::read_to_string(&path) {
+        Ok(raw) => serde_json::from_str(&raw).unwrap_or_else(|err| {
+            warn!("Ignoring unreadable download queue: {}", err);
+            Vec::new()
+        }),
+        Err(_) => return,
+    };
+    let mut queue = download_queue();
+    *queue = DownloadQueue::restore(jobs);
+    info!("Restored {} model download(s) from queue", queue.jobs.len());
+    publish_download_queue(app, &queue);
+    drop(queue);
+    pump_download_queue(app);
+}
+
+#[tauri::command]
+pub(crate) fn get_download_queue() -> Vec<DownloadJob> {
+    download_queue().jobs.clone()
+}
+
+#[tauri::command]
+pub(crate) fn pause_download(app: AppHandle, model_id: String) -> CommandResult<()> {
+    let mut queue = download_queue();
+    queue.pause(&model_id).with_code(ErrorCode::NotFound)?;
+    publish_download_queue(&app, &queue);
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) fn resume_download(app: AppHandle, model_id: String) -> CommandResult<()> {
+    let mut queue = download_queue();
+    queue.resume(&model_id).with_code(ErrorCode::NotFound)?;
+    publish_download_queue(&app, &queue);
+    drop(queue);
+    pump_download_queue(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) fn cancel_download(app: AppHandle, model_id: String) -> CommandResult<()> {
+    let mut queue = download_queue();
+    // The worker removes the running job and its partial file once it sees the flag.
+    if queue.signal_active(&model_id, DOWNLOAD_CONTROL_CANCEL) {
+        return Ok(());
+    }
+    let job = queue.remove(&model_id).ok_or_else(|| {
+        CommandError::new(ErrorCode::NotFound, "No download queued for this model")
+    })?;
+    if validate_model_file_name(&job.file_name).is_ok() {
+        let part = resolve_models_dir(&app)
+            .join(&job.file_name)
+            .with_extension("part");
+        let _ = fs::remove_file(part);
+    }
+    publish_download_queue(&app, &queue);
+    let _ = app.emit(
+        "model:download-cancelled",
+        DownloadStatusEvent { id: model_id },
+    );
+    Ok(())
+}
+
 fn model_listing_aliases(model_id: &str) -> &'static [&'static str] {
     match model_id {
         // Recognize quantized local variants as "installed" for the base model cards.
@@ -989,6 +1433,7 @@ pub(crate) fn list_models(app: AppHandle, state: State<'_, AppState>) -> Vec<Mod
                 size_mb: model.size_mb,
                 installed: path.is_some(),
                 downloading: downloads.contains(&model.id),
+                header: path.as_deref().map(inspect_model_file),
                 path: path.map(|p| p.to_string_lossy().to_string()),
                 source: model.source.clone(),
                 available: true,
@@ -1040,6 +1485,7 @@ pub(crate) fn list_models(app: AppHandle, state: State<'_, AppState>) -> Vec<Mod
                 size_mb,
                 installed: true,
                 downloading: false,
+                header: Some(inspect_model_file(&path)),
                 path: Some(path.to_string_lossy().to_string()),
                 source: "local".to_string(),
                 available: false,
@@ -1055,111 +1501,288 @@ pub(crate) fn list_models(app: AppHandle, state: State<'_, AppState>) -> Vec<Mod
 #[tauri::command]
 pub(crate) fn download_model(
     app: AppHandle,
-    state: State<'_, AppState>,
     model_id: String,
     download_url: Option<String>,
     file_name: Option<String>,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     let (url, name) = if let Some(url) = download_url.clone() {
         let name = file_name
             .or_else(|| filename_from_url(&url))
-            .ok_or_else(|| "Missing file name for custom download".to_string())?;
-        validate_model_file_name(&name)?;
+            .ok_or_else(|| {
+                CommandError::new(
+                    ErrorCode::InvalidInput,
+                    "Missing file name for custom download",
+                )
+            })?;
+        validate_model_file_name(&name).with_code(ErrorCode::InvalidInput)?;
         // Security: Validate URL before downloading
-        is_url_safe(&url, UrlSafety::Strict)?;
+        is_url_safe(&url, UrlSafety::Strict).with_code(ErrorCode::InvalidInput)?;
         (url, name)
     } else {
-        let spec = model_spec(&model_id).ok_or_else(|| "Unknown model".to_string())?;
+        let spec = model_spec(&model_id)
+            .ok_or_else(|| CommandError::new(ErrorCode::ModelNotFound, "Unknown model"))?;
         let base_url = resolve_model_base_url();
         let name = spec.file_name.to_string();
-        validate_model_file_name(&name)?;
+        validate_model_file_name(&name).with_code(ErrorCode::InvalidInput)?;
         // Add ?download=true for better HuggingFace CDN handling
         let url = format!(
             "{}/{}?download=true",
             base_url.trim_end_matches('/'),
             spec.file_name
         );
-        is_url_safe(&url, UrlSafety::Strict)?;
+        is_url_safe(&url, UrlSafety::Strict).with_code(ErrorCode::InvalidInput)?;
         (url, name)
     };
-    {
-        let mut downloads = state
-            .downloads
-            .lock()
-            .unwrap_or_else(|poisoned| poisoned.into_inner());
-        if downloads.contains(&model_id) {
-            return Err("Download already in progress".to_string());
-        }
-        downloads.insert(model_id.clone());
+    let mut queue = download_queue();
+    queue
+        .enqueue(&model_id, &url, &name)
+        .with_code(ErrorCode::InvalidInput)?;
+    publish_download_queue(&app, &queue);
+    drop(queue);
+    pump_download_queue(&app);
+
+    Ok(())
+}
+
+/// Picks the managed file name for a model imported from `source`.
+fn import_file_name(source: &std::path::Path) -> Result<String, String> {
+    let name = source
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| "Model path has no usable file name".to_string())?;
+    validate_model_file_name(name)?;
+    Ok(name.to_string())
+}
+
+/// Brings an existing `.bin`/`.gguf` file into the managed models dir, either as a
+/// copy or as a symlink, and returns the refreshed model list. The checksum and
+/// copy of multi-GB files run off the main thread.
+#[tauri::command]
+pub(crate) async fn import_model(
+    app: AppHandle,
+    path: String,
+    link: Option<bool>,
+    verify_checksum: Option<bool>,
+) -> CommandResult<Vec<ModelInfo>> {
+    tauri::async_runtime::spawn_blocking(move || {
+        import_model_file(&app, &path, link, verify_checksum)?;
+        Ok(list_models(app.clone(), app.state::<AppState>()))
+    })
+    .await
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("import_model task failed: {}", e),
+        )
+    })?
+}
+
+fn import_model_file(
+    app: &AppHandle,
+    path: &str,
+    link: Option<bool>,
+    verify_checksum: Option<bool>,
+) -> CommandResult<()> {
+    let trimmed = path.trim();
+    if trimmed.is_empty() {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "Missing model path",
+        ));
+    }
+    let source = PathBuf::from(trimmed);
+    let metadata = fs::metadata(&source).map_err(|e| {
+        CommandError::new(ErrorCode::NotFound, format!("Cannot read model file: {e}"))
+    })?;
+    if !metadata.is_file() {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "Model path is not a file",
+        ));
+    }
+    if metadata.len() == 0 {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "Model file is empty",
+        ));
+    }
+    if metadata.len() > MAX_MODEL_SIZE_BYTES {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            format!(
+                "Model too large: {} MB (max {} MB)",
+                metadata.len() / 1024 / 1024,
+                MAX_MODEL_SIZE_BYTES / 1024 / 1024
+            ),
+        ));
     }
+    let file_name = import_file_name(&source).with_code(ErrorCode::InvalidInput)?;
 
-    let app_handle = app.clone();
-    crate::util::spawn_guarded("model_download", move || {
-        let result = download_model_file(&app_handle, &model_id, &url, &name);
-        match result {
-            Ok(path) => {
-                let _ = app_handle.emit(
-                    "model:download-complete",
-                    DownloadComplete {
-                        id: model_id.clone(),
-                        path: path.to_string_lossy().to_string(),
-                    },
-                );
-            }
-            Err(error) => {
-                let _ = app_handle.emit(
-                    "model:download-error",
-                    DownloadError {
-                        id: model_id.clone(),
-                        error,
-                    },
-                );
-            }
-        }
+    let models_dir = resolve_models_dir(app);
+    fs::create_dir_all(&models_dir).map_err(|e| {
+        CommandError::new(
+            ErrorCode::Storage,
+            format!("Failed to create models dir: {e}"),
+        )
+    })?;
+    let dest_path = models_dir.join(&file_name);
+    if fs::symlink_metadata(&dest_path).is_ok() {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "A model with this file name already exists",
+        ));
+    }
 
-        let state = app_handle.state::<AppState>();
-        let mut downloads = state
-            .downloads
-            .lock()
-            .unwrap_or_else(|poisoned| poisoned.into_inner());
-        downloads.remove(&model_id);
-    });
+    if verify_checksum.unwrap_or(true) {
+        if let Some(expected_hash) = lookup_model_checksum(&file_name) {
+            verify_model_checksum(&source, expected_hash).with_code(ErrorCode::InvalidInput)?;
+        }
+    }
 
+    let linked = link.unwrap_or(false) && link_model_file(&source, &dest_path);
+    if !linked {
+        check_disk_space(metadata.len(), available_disk_space(&models_dir))?;
+        let tmp_path = dest_path.with_extension("part");
+        let copied = fs::copy(&source, &tmp_path)
+            .and_then(|_| fs::rename(&tmp_path, &dest_path))
+            .map_err(|e| {
+                CommandError::new(
+                    ErrorCode::Storage,
+                    format!("Failed to copy model file: {e}"),
+                )
+            });
+        if copied.is_err() {
+            let _ = fs::remove_file(&tmp_path);
+        }
+        copied?;
+    }
+    info!(
+        "Imported model {} from {} ({})",
+        file_name,
+        source.display(),
+        if linked { "linked" } else { "copied" }
+    );
     Ok(())
 }
 
+/// Symlinks `source` into the models dir; returns false so callers can fall back to copying.
+fn link_model_file(source: &std::path::Path, dest: &std::path::Path) -> bool {
+    let source = match source.canonicalize() {
+        Ok(path) => path,
+        Err(_) => return false,
+    };
+    #[cfg(unix)]
+    let result = std::os::unix::fs::symlink(&source, dest);
+    #[cfg(windows)]
+    let result = std::os::windows::fs::symlink_file(&source, dest);
+    #[cfg(not(any(unix, windows)))]
+    let result: std::io::Result<()> = Err(std::io::ErrorKind::Unsupported.into());
+    match result {
+        Ok(()) => true,
+        Err(err) => {
+            warn!("Could not symlink model, copying instead: {}", err);
+            false
+        }
+    }
+}
+
 #[tauri::command]
-pub(crate) fn remove_model(app: AppHandle, file_name: String) -> Result<(), String> {
+pub(crate) fn remove_model(app: AppHandle, file_name: String) -> CommandResult<()> {
     if file_name.trim().is_empty() {
-        return Err("Missing model file name".to_string());
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "Missing model file name",
+        ));
     }
-    validate_model_file_name(&file_name)?;
+    validate_model_file_name(&file_name).with_code(ErrorCode::InvalidInput)?;
     let models_dir = resolve_models_dir(&app);
     let target = models_dir.join(&file_name);
     if !target.exists() {
-        return Err("Model file not found in app cache".to_string());
+        return Err(CommandError::new(
+            ErrorCode::ModelNotFound,
+            "Model file not found in app cache",
+        ));
+    }
+    fs::remove_file(&target).with_code(ErrorCode::Storage)?;
+    let _ = app.emit("model:removed", &file_name);
+    Ok(())
+}
+
+/// Tray menu item IDs are `model:<id>`.
+pub(crate) const TRAY_MODEL_PREFIX: &str = "model:";
+
+/// Replaces the tray "Model" submenu items with one check item per installed
+/// model.
+pub(crate) fn populate_tray_model_submenu(
+    app: &AppHandle,
+    submenu: &Submenu<Wry>,
+    active_model: &str,
+) -> tauri::Result<()> {
+    for item in submenu.items()? {
+        submenu.remove(&item)?;
+    }
+    let installed: Vec<ModelInfo> = list_models(app.clone(), app.state::<AppState>())
+        .into_iter()
+        .filter(|model| model.installed && model.available)
+        .collect();
+    if installed.is_empty() {
+        let placeholder = MenuItem::new(app, "No models installed", false, None::<&str>)?;
+        return submenu.append(&placeholder);
+    }
+    for model in installed {
+        let item = CheckMenuItem::with_id(
+            app,
+            format!("{}{}", TRAY_MODEL_PREFIX, model.id),
+            &model.label,
+            true,
+            model.id == active_model,
+            None::<&str>,
+        )?;
+        submenu.append(&item)?;
     }
-    fs::remove_file(&target).map_err(|e| e.to_string())?;
     Ok(())
 }
 
+/// Moves the tray check mark to `active_model` without listing models again.
+pub(crate) fn check_tray_model(submenu: &Submenu<Wry>, active_model: &str) {
+    let Ok(items) = submenu.items() else {
+        return;
+    };
+    for item in items {
+        if let MenuItemKind::Check(check) = item {
+            let is_active =
+                check.id().as_ref().strip_prefix(TRAY_MODEL_PREFIX) == Some(active_model);
+            let _ = check.set_checked(is_active);
+        }
+    }
+}
+
 #[tauri::command]
 pub(crate) fn quantize_model(
     app: AppHandle,
     file_name: String,
     quant: Option<String>,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     if file_name.trim().is_empty() {
-        return Err("Missing model file name".to_string());
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "Missing model file name",
+        ));
     }
-    validate_model_file_name(&file_name)?;
+    validate_model_file_name(&file_name).with_code(ErrorCode::InvalidInput)?;
 
     if !file_name.ends_with(".bin") {
-        return Err("Only .bin models can be quantized".to_string());
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "Only .bin models can be quantized",
+        ));
     }
     let lower_file_name = file_name.to_ascii_lowercase();
     if lower_file_name.contains("-q5_0") || lower_file_name.contains("-q8_0") {
-        return Err("Model already looks quantized".to_string());
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "Model already looks quantized",
+        ));
     }
 
     let quant_type = quant
@@ -1167,24 +1790,36 @@ pub(crate) fn quantize_model(
         .trim()
         .to_ascii_lowercase();
     if quant_type != "q5_0" && quant_type != "q8_0" {
-        return Err("Unsupported quantization type. Supported: q5_0, q8_0".to_string());
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "Unsupported quantization type. Supported: q5_0, q8_0",
+        ));
     }
 
     let models_dir = resolve_models_dir(&app);
     let input_path = models_dir.join(&file_name);
     if !input_path.exists() {
-        return Err("Model file not found in app cache".to_string());
+        return Err(CommandError::new(
+            ErrorCode::ModelNotFound,
+            "Model file not found in app cache",
+        ));
     }
 
     let output_name = format!("{}-{}.bin", file_name.trim_end_matches(".bin"), quant_type);
-    validate_model_file_name(&output_name)?;
+    validate_model_file_name(&output_name).with_code(ErrorCode::InvalidInput)?;
     let output_path = models_dir.join(&output_name);
     if output_path.exists() {
-        return Err("Quantized model already exists".to_string());
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "Quantized model already exists",
+        ));
     }
 
     let quantize_path = resolve_quantize_path(&app).ok_or_else(|| {
-        "quantize.exe not found. Install/bundle it or set TRISPR_WHISPER_QUANTIZE.".to_string()
+        CommandError::new(
+            ErrorCode::RuntimeMissing,
+            "quantize.exe not found. Install/bundle it or set TRISPR_WHISPER_QUANTIZE.",
+        )
     })?;
 
     emit_quantize_progress(
@@ -1214,9 +1849,12 @@ pub(crate) fn quantize_model(
         quantize_cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
     }
 
-    let mut child = quantize_cmd
-        .spawn()
-        .map_err(|e| format!("Failed to launch quantize: {e}"))?;
+    let mut child = quantize_cmd.spawn().map_err(|e| {
+        CommandError::new(
+            ErrorCode::RuntimeMissing,
+            format!("Failed to launch quantize: {e}"),
+        )
+    })?;
     let progress_gate = Arc::new(AtomicU8::new(0));
     let mut reader_handles = Vec::new();
 
@@ -1271,7 +1909,12 @@ pub(crate) fn quantize_model(
                 }
                 thread::sleep(Duration::from_millis(150));
             }
-            Err(e) => return Err(format!("Failed while quantize was running: {e}")),
+            Err(e) => {
+                return Err(CommandError::new(
+                    ErrorCode::Internal,
+                    format!("Failed while quantize was running: {e}"),
+                ))
+            }
         }
     };
 
@@ -1284,17 +1927,23 @@ pub(crate) fn quantize_model(
         if let Some(code) = status.code() {
             let win_code = code as u32;
             if win_code == 0xC0000135 {
-                return Err(format!(
+                return Err(CommandError::new(
+                    ErrorCode::RuntimeMissing,
+                    format!(
           "Quantize failed for {} (exit code 0x{win_code:08x}). Missing DLL dependency for quantize.exe. \
 Please reinstall/update Trispr Flow so runtime files in bin/cuda or bin/vulkan are present.",
           quant_type
-        ));
+        ),
+                ));
             }
         }
-        return Err(format!(
-            "Quantize failed for {} ({})",
-            quant_type,
-            format_exit_status(status)
+        return Err(CommandError::new(
+            ErrorCode::Internal,
+            format!(
+                "Quantize failed for {} ({})",
+                quant_type,
+                format_exit_status(status)
+            ),
         ));
     }
 
@@ -1425,9 +2074,12 @@ pub(crate) fn hide_external_model(
     app: AppHandle,
     state: State<'_, AppState>,
     path: String,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     if path.trim().is_empty() {
-        return Err("Missing model path".to_string());
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "Missing model path",
+        ));
     }
     let mut settings = state
         .settings
@@ -1436,7 +2088,7 @@ pub(crate) fn hide_external_model(
     settings.hidden_external_models.insert(path);
     let persisted = settings.clone();
     drop(settings);
-    save_settings_file(&app, &persisted)?;
+    save_settings_file(&app, &persisted).with_code(ErrorCode::Storage)?;
     Ok(())
 }
 
@@ -1444,7 +2096,7 @@ pub(crate) fn hide_external_model(
 pub(crate) fn clear_hidden_external_models(
     app: AppHandle,
     state: State<'_, AppState>,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     let mut settings = state
         .settings
         .write()
@@ -1452,7 +2104,7 @@ pub(crate) fn clear_hidden_external_models(
     settings.hidden_external_models.clear();
     let persisted = settings.clone();
     drop(settings);
-    save_settings_file(&app, &persisted)?;
+    save_settings_file(&app, &persisted).with_code(ErrorCode::Storage)?;
     Ok(())
 }
 
@@ -1468,12 +2120,30 @@ pub(crate) fn get_models_dir(app: AppHandle) -> String {
     resolve_models_dir(&app).to_string_lossy().to_string()
 }
 
+/// Fails early when the models volume cannot hold `remaining` more bytes plus headroom.
+/// An unknown free-space figure is not treated as an error.
+fn check_disk_space(remaining: u64, available: Option<u64>) -> Result<(), AppError> {
+    let Some(available) = available else {
+        return Ok(());
+    };
+    let required = remaining.saturating_add(DOWNLOAD_DISK_HEADROOM_BYTES);
+    if available < required {
+        return Err(AppError::Storage(format!(
+            "Not enough disk space for this model: needs {} MB, {} MB available",
+            required / 1024 / 1024,
+            available / 1024 / 1024
+        )));
+    }
+    Ok(())
+}
+
 fn download_model_file(
     app: &AppHandle,
     model_id: &str,
     download_url: &str,
     file_name: &str,
-) -> Result<PathBuf, String> {
+    control: &AtomicU8,
+) -> Result<PathBuf, DownloadFailure> {
     validate_model_file_name(file_name)?;
     let models_dir = resolve_models_dir(app);
     let dest_path = models_dir.join(file_name);
@@ -1482,11 +2152,16 @@ fn download_model_file(
     }
 
     let tmp_path = dest_path.with_extension("part");
-    let result = (|| -> Result<PathBuf, String> {
-        let response = http_get_with_redirects(download_url).map_err(|e| e.to_string())?;
+    let result = (|| -> Result<PathBuf, DownloadFailure> {
+        // Resume from a partial file left behind by a pause or a previous run.
+        let resume_from = fs::metadata(&tmp_path).map(|m| m.len()).unwrap_or(0);
+        let response = http_get_with_redirects_from(download_url, Some(resume_from))?;
+        let resumed = resume_from > 0 && response.status() == 206;
+        let offset = if resumed { resume_from } else { 0 };
         let total = response
             .header("Content-Length")
-            .and_then(|value| value.parse::<u64>().ok());
+            .and_then(|value| value.parse::<u64>().ok())
+            .map(|len| len + offset);
 
         // Security: Enforce maximum model size to prevent disk exhaustion
         if let Some(size) = total {
@@ -1495,25 +2170,49 @@ fn download_model_file(
                     "Model too large: {} MB (max {} MB)",
                     size / 1024 / 1024,
                     MAX_MODEL_SIZE_BYTES / 1024 / 1024
-                ));
+                )
+                .into());
             }
+            check_disk_space(
+                size.saturating_sub(offset),
+                available_disk_space(&models_dir),
+            )
+            .map_err(DownloadFailure::App)?;
         }
 
         let mut reader = response.into_reader();
-        let mut file = fs::File::create(&tmp_path).map_err(|e| e.to_string())?;
+        let mut file = if resumed {
+            info!("Resuming download of {} at {} bytes", file_name, offset);
+            fs::OpenOptions::new()
+                .append(true)
+                .open(&tmp_path)
+                .map_err(|e| e.to_string())?
+        } else {
+            fs::File::create(&tmp_path).map_err(|e| e.to_string())?
+        };
 
-        let mut downloaded = 0u64;
+        let mut downloaded = offset;
         let mut last_emit = Instant::now();
         let mut last_read = Instant::now(); // Track for timeout detection
         let mut buffer = [0u8; 64 * 1024];
 
         loop {
+            match control.load(Ordering::Relaxed) {
+                DOWNLOAD_CONTROL_PAUSE => {
+                    file.flush().map_err(|e| e.to_string())?;
+                    return Err(DownloadFailure::Paused(downloaded));
+                }
+                DOWNLOAD_CONTROL_CANCEL => return Err(DownloadFailure::Cancelled),
+                _ => {}
+            }
+
             // Timeout detection: fail if no data for DOWNLOAD_TIMEOUT_SECS
             if last_read.elapsed().as_secs() > DOWNLOAD_TIMEOUT_SECS {
                 return Err(format!(
                     "Download stalled: no data received for {} seconds",
                     DOWNLOAD_TIMEOUT_SECS
-                ));
+                )
+                .into());
             }
 
             let read_bytes = reader.read(&mut buffer).map_err(|e| e.to_string())?;
@@ -1530,10 +2229,12 @@ fn download_model_file(
                 return Err(format!(
                     "Model too large: exceeded {} MB limit",
                     MAX_MODEL_SIZE_BYTES / 1024 / 1024
-                ));
+                )
+                .into());
             }
 
             if last_emit.elapsed() >= Duration::from_millis(250) {
+                update_queued_progress(model_id, downloaded, total);
                 let _ = app.emit(
                     "model:download-progress",
                     DownloadProgress {
@@ -1575,13 +2276,48 @@ fn download_model_file(
         Ok(dest_path)
     })();
 
-    if result.is_err() {
+    // A paused download keeps its partial file so it can resume later.
+    if matches!(
+        result,
+        Err(DownloadFailure::Cancelled | DownloadFailure::App(_) | DownloadFailure::Error(_))
+    ) {
         let _ = fs::remove_file(&tmp_path);
     }
 
     result
 }
 
+/// Downloads a small auxiliary model file (e.g. wake-word ONNX graphs) to
+/// `dest` with the same URL checks as whisper models, but without queueing,
+/// resume or progress events.
+pub(crate) fn download_auxiliary_file(
+    url: &str,
+    dest: &std::path::Path,
+    max_bytes: u64,
+) -> Result<(), String> {
+    let response = http_get_with_redirects(url)?;
+    let tmp_path = dest.with_extension("part");
+    let result = (|| -> Result<(), String> {
+        let mut reader = response.into_reader().take(max_bytes + 1);
+        let mut file = fs::File::create(&tmp_path).map_err(|e| e.to_string())?;
+        let written = std::io::copy(&mut reader, &mut file).map_err(|e| e.to_string())?;
+        if written > max_bytes {
+            return Err(format!(
+                "Download exceeded {} KB limit: {}",
+                max_bytes / 1024,
+                url
+            ));
+        }
+        file.flush().map_err(|e| e.to_string())?;
+        drop(file);
+        fs::rename(&tmp_path, dest).map_err(|e| e.to_string())
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result
+}
+
 #[tauri::command]
 pub(crate) fn check_model_available(app: AppHandle, model_id: String) -> bool {
     resolve_model_path(&app, &model_id).is_some()
@@ -1,3 +1,4 @@
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
 use crate::modules::{
     canonicalize_module_id, lifecycle as module_lifecycle, normalize_confluence_settings,
     normalize_gdd_module_settings, normalize_module_settings, normalize_vision_input_settings,
@@ -23,14 +24,17 @@ pub(crate) fn enable_module_actions(
     state: &AppState,
     module_id: String,
     grant_permissions: Option<Vec<String>>,
-) -> Result<serde_json::Value, String> {
+) -> CommandResult<serde_json::Value> {
     let module_id = canonicalize_module_id(module_id.trim()).to_string();
     if module_id.is_empty() {
-        return Err("Module id cannot be empty.".to_string());
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "Module id cannot be empty.",
+        ));
     }
 
     let grants = grant_permissions.unwrap_or_default();
-    let installed_package_ids = scan_installed_module_ids(app)?;
+    let installed_package_ids = scan_installed_module_ids(app).with_code(ErrorCode::Storage)?;
 
     // ==========================================
     // PHASE A: Settle (In-Memory State Settle)
@@ -104,7 +108,7 @@ pub(crate) fn enable_module_actions(
     // ==========================================
     // PHASE B: Persist (Durable Persistence)
     // ==========================================
-    save_settings_file(app, &snapshot)?;
+    save_settings_file(app, &snapshot).with_code(ErrorCode::Storage)?;
 
     // ==========================================
     // PHASE C: Reconcile (Reconcile Side-Effects)
@@ -146,7 +150,7 @@ pub(crate) fn enable_module_actions(
                 "module:error",
                 serde_json::json!({ "module_id": module_id, "error": error }),
             );
-            Err(error)
+            Err(CommandError::new(ErrorCode::InvalidInput, error))
         }
     }
 }
@@ -155,10 +159,13 @@ pub(crate) fn disable_module_actions(
     app: &AppHandle,
     state: &AppState,
     module_id: String,
-) -> Result<serde_json::Value, String> {
+) -> CommandResult<serde_json::Value> {
     let module_id = canonicalize_module_id(module_id.trim()).to_string();
     if module_id.is_empty() {
-        return Err("Module id cannot be empty.".to_string());
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "Module id cannot be empty.",
+        ));
     }
     let installed_package_ids = scan_installed_module_ids(app).unwrap_or_default();
 
@@ -224,7 +231,7 @@ pub(crate) fn disable_module_actions(
     // ==========================================
     // PHASE B: Persist (Durable Persistence)
     // ==========================================
-    save_settings_file(app, &snapshot)?;
+    save_settings_file(app, &snapshot).with_code(ErrorCode::Storage)?;
 
     // ==========================================
     // PHASE C: Reconcile (Reconcile Side-Effects)
@@ -280,7 +287,7 @@ pub(crate) fn disable_module_actions(
                 "module:error",
                 serde_json::json!({ "module_id": module_id, "error": error }),
             );
-            Err(error)
+            Err(CommandError::new(ErrorCode::InvalidInput, error))
         }
     }
 }
@@ -1,3 +1,4 @@
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
 use crate::modules::TaskCaptureSettings;
 use crate::state::AppState;
 use tauri::{AppHandle, Manager};
@@ -210,21 +211,24 @@ pub(crate) async fn get_task_capture_settings(app: AppHandle) -> TaskCaptureSett
 pub(crate) async fn save_task_capture_settings(
     app: AppHandle,
     task_capture_settings: TaskCaptureSettings,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     let state = app.state::<AppState>();
     let mut settings = {
         let current = state.settings.read().unwrap_or_else(|p| p.into_inner());
         current.clone()
     };
     settings.task_capture_settings = task_capture_settings;
-    crate::save_settings_inner(&app, &mut settings)
+    crate::save_settings_inner(&app, &mut settings).with_code(ErrorCode::Storage)
 }
 
 #[tauri::command]
-pub(crate) fn test_task_capture_endpoint(endpoint: String) -> Result<String, String> {
+pub(crate) fn test_task_capture_endpoint(endpoint: String) -> CommandResult<String> {
     let endpoint = endpoint.trim().to_string();
     if endpoint.is_empty() {
-        return Err("Endpoint URL is empty".to_string());
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "Endpoint URL is empty",
+        ));
     }
     match ureq::post(&endpoint)
         .set("Content-Type", "application/json")
@@ -232,12 +236,14 @@ pub(crate) fn test_task_capture_endpoint(endpoint: String) -> Result<String, Str
         .send_json(serde_json::json!({ "text": "[Test] Verbindungstest von Trispr Flow" }))
     {
         Ok(response) => Ok(format!("OK (status {})", response.status())),
-        Err(ureq::Error::Status(code, response)) => Err(crate::format_ureq_status_error(
-            "Test request",
-            code,
-            response,
+        Err(ureq::Error::Status(code, response)) => Err(CommandError::new(
+            ErrorCode::Network,
+            crate::format_ureq_status_error("Test request", code, response),
+        )),
+        Err(ureq::Error::Transport(transport)) => Err(CommandError::new(
+            ErrorCode::Network,
+            format!("Connection failed: {}", transport),
         )),
-        Err(ureq::Error::Transport(transport)) => Err(format!("Connection failed: {}", transport)),
     }
 }
 
@@ -0,0 +1,401 @@
+//! Mouse-button and modifier+wheel bindings for PTT and toggle.
+//!
+//! The global-shortcut plugin only understands keyboard accelerators, so
+//! hotkey strings such as "Mouse4", "Mouse5" or "Ctrl+WheelUp" are routed
+//! here instead and served by a low-level mouse hook (`WH_MOUSE_LL`) on
+//! Windows. Matching events are swallowed so a thumb button bound to PTT does
+//! not also navigate back in the browser. Other platforms have no hook yet and
+//! report an error when a mouse binding is configured.
+
+use std::fmt;
+use std::sync::Mutex;
+
+#[cfg(target_os = "windows")]
+use std::sync::{mpsc, OnceLock};
+use tauri::AppHandle;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MouseTrigger {
+    /// XBUTTON1, usually the rear thumb button ("back").
+    Mouse4,
+    /// XBUTTON2, usually the front thumb button ("forward").
+    Mouse5,
+    WheelUp,
+    WheelDown,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct MouseModifiers {
+    pub(crate) ctrl: bool,
+    pub(crate) shift: bool,
+    pub(crate) alt: bool,
+    pub(crate) meta: bool,
+}
+
+impl MouseModifiers {
+    fn any(self) -> bool {
+        self.ctrl || self.shift || self.alt || self.meta
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct MouseBinding {
+    pub(crate) trigger: MouseTrigger,
+    pub(crate) modifiers: MouseModifiers,
+}
+
+impl MouseBinding {
+    /// `None` when `key` is a keyboard accelerator; `Some(Err)` when it names a
+    /// mouse trigger but cannot be used (unknown modifier, bare wheel).
+    pub(crate) fn parse(key: &str) -> Option<Result<Self, String>> {
+        let parts: Vec<&str> = key.split('+').map(str::trim).collect();
+        let (last, modifier_parts) = parts.split_last()?;
+        let trigger = match last.to_ascii_lowercase().as_str() {
+            "mouse4" | "xbutton1" => MouseTrigger::Mouse4,
+            "mouse5" | "xbutton2" => MouseTrigger::Mouse5,
+            "wheelup" => MouseTrigger::WheelUp,
+            "wheeldown" => MouseTrigger::WheelDown,
+            _ => return None,
+        };
+        let mut modifiers = MouseModifiers::default();
+        for part in modifier_parts {
+            match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" | "commandorcontrol" | "cmdorctrl" => modifiers.ctrl = true,
+                "shift" => modifiers.shift = true,
+                "alt" | "option" => modifiers.alt = true,
+                "super" | "meta" | "win" | "command" | "cmd" => modifiers.meta = true,
+                other => return Some(Err(format!("Unknown modifier '{}'", other))),
+            }
+        }
+        let binding = Self { trigger, modifiers };
+        if binding.is_wheel() && !modifiers.any() {
+            return Some(Err(
+                "Wheel bindings need a modifier (e.g. Ctrl+WheelUp) so normal scrolling keeps working"
+                    .to_string(),
+            ));
+        }
+        Some(Ok(binding))
+    }
+
+    /// Wheel notches have no release, so they can only toggle.
+    pub(crate) fn is_wheel(&self) -> bool {
+        matches!(
+            self.trigger,
+            MouseTrigger::WheelUp | MouseTrigger::WheelDown
+        )
+    }
+}
+
+impl fmt::Display for MouseBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let MouseModifiers {
+            ctrl,
+            shift,
+            alt,
+            meta,
+        } = self.modifiers;
+        for (held, name) in [
+            (ctrl, "Ctrl"),
+            (shift, "Shift"),
+            (alt, "Alt"),
+            (meta, "Super"),
+        ] {
+            if held {
+                write!(f, "{}+", name)?;
+            }
+        }
+        let trigger = match self.trigger {
+            MouseTrigger::Mouse4 => "Mouse4",
+            MouseTrigger::Mouse5 => "Mouse5",
+            MouseTrigger::WheelUp => "WheelUp",
+            MouseTrigger::WheelDown => "WheelDown",
+        };
+        f.write_str(trigger)
+    }
+}
+
+pub(crate) fn is_mouse_binding(key: &str) -> bool {
+    MouseBinding::parse(key).is_some()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MouseAction {
+    Ptt,
+    Toggle,
+}
+
+static BINDINGS: Mutex<Vec<(MouseBinding, MouseAction)>> = Mutex::new(Vec::new());
+
+/// The action bound to `trigger` when exactly `modifiers` are held.
+#[cfg(any(target_os = "windows", test))]
+fn matching_action(
+    bindings: &[(MouseBinding, MouseAction)],
+    trigger: MouseTrigger,
+    modifiers: MouseModifiers,
+) -> Option<MouseAction> {
+    bindings
+        .iter()
+        .find(|(binding, _)| binding.trigger == trigger && binding.modifiers == modifiers)
+        .map(|(_, action)| *action)
+}
+
+/// Buttons whose press started an action, so the release ends that same
+/// action even when the modifiers changed while the button was held.
+#[cfg(target_os = "windows")]
+static PRESSED: Mutex<Vec<(MouseTrigger, MouseAction)>> = Mutex::new(Vec::new());
+
+/// Resolves one hook event to `(action, pressed)`. A press matches trigger
+/// and exact modifiers and is remembered in `held`; a release matches by
+/// button only, against what is in `held`.
+#[cfg(any(target_os = "windows", test))]
+fn resolve_event(
+    bindings: &[(MouseBinding, MouseAction)],
+    held: &mut Vec<(MouseTrigger, MouseAction)>,
+    trigger: MouseTrigger,
+    modifiers: MouseModifiers,
+    pressed: bool,
+) -> Option<(MouseAction, bool)> {
+    if !pressed {
+        let index = held.iter().position(|(button, _)| *button == trigger)?;
+        let (_, action) = held.remove(index);
+        return Some((action, false));
+    }
+    let action = matching_action(bindings, trigger, modifiers)?;
+    let is_wheel = matches!(trigger, MouseTrigger::WheelUp | MouseTrigger::WheelDown);
+    if !is_wheel {
+        held.retain(|(button, _)| *button != trigger);
+        held.push((trigger, action));
+    }
+    Some((action, true))
+}
+
+/// Replaces the active mouse bindings, starting the hook on first use.
+pub(crate) fn set_bindings(
+    app: &AppHandle,
+    bindings: Vec<(MouseBinding, MouseAction)>,
+) -> Result<(), String> {
+    let empty = bindings.is_empty();
+    *BINDINGS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = bindings;
+    if empty {
+        return Ok(());
+    }
+    start_hook(app)
+}
+
+#[cfg(target_os = "windows")]
+static EVENT_TX: OnceLock<Mutex<mpsc::Sender<(MouseAction, bool)>>> = OnceLock::new();
+
+#[cfg(target_os = "windows")]
+fn start_hook(app: &AppHandle) -> Result<(), String> {
+    if EVENT_TX.get().is_some() {
+        return Ok(());
+    }
+    let (tx, rx) = mpsc::channel::<(MouseAction, bool)>();
+    if EVENT_TX.set(Mutex::new(tx)).is_err() {
+        return Ok(());
+    }
+
+    // The hook callback must return quickly, so handlers run on a worker.
+    let app = app.clone();
+    crate::util::spawn_guarded("mouse-hotkey-worker", move || {
+        while let Ok((action, pressed)) = rx.recv() {
+            crate::on_mouse_hotkey(&app, action, pressed);
+        }
+    });
+    crate::util::spawn_guarded("mouse-hotkey-hook", run_hook_loop);
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn start_hook(_app: &AppHandle) -> Result<(), String> {
+    Err("Mouse button bindings are only supported on Windows".to_string())
+}
+
+#[cfg(target_os = "windows")]
+fn run_hook_loop() {
+    use tracing::error;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        DispatchMessageW, GetMessageW, SetWindowsHookExW, TranslateMessage, UnhookWindowsHookEx,
+        MSG, WH_MOUSE_LL,
+    };
+
+    // SAFETY: the hook is installed and removed on this thread, which pumps
+    // messages for it until the process exits.
+    unsafe {
+        let hook = match SetWindowsHookExW(WH_MOUSE_LL, Some(ll_mouse_proc), None, 0) {
+            Ok(hook) => hook,
+            Err(e) => {
+                error!(
+                    "[mouse-hotkeys] SetWindowsHookExW failed: {e} - mouse bindings are disabled"
+                );
+                return;
+            }
+        };
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, None, 0, 0).as_bool() {
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+        let _ = UnhookWindowsHookEx(hook);
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn current_modifiers() -> MouseModifiers {
+    use windows::Win32::UI::Input::KeyboardAndMouse::{
+        GetAsyncKeyState, VIRTUAL_KEY, VK_CONTROL, VK_LWIN, VK_MENU, VK_RWIN, VK_SHIFT,
+    };
+    // SAFETY: GetAsyncKeyState only reads global key state.
+    let down = |key: VIRTUAL_KEY| unsafe { GetAsyncKeyState(key.0 as i32) } < 0;
+    MouseModifiers {
+        ctrl: down(VK_CONTROL),
+        shift: down(VK_SHIFT),
+        alt: down(VK_MENU),
+        meta: down(VK_LWIN) || down(VK_RWIN),
+    }
+}
+
+#[cfg(target_os = "windows")]
+unsafe extern "system" fn ll_mouse_proc(
+    ncode: i32,
+    wparam: windows::Win32::Foundation::WPARAM,
+    lparam: windows::Win32::Foundation::LPARAM,
+) -> windows::Win32::Foundation::LRESULT {
+    use windows::Win32::Foundation::LRESULT;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        CallNextHookEx, MSLLHOOKSTRUCT, WM_MOUSEWHEEL, WM_XBUTTONDOWN, WM_XBUTTONUP, XBUTTON1,
+        XBUTTON2,
+    };
+
+    if ncode >= 0 {
+        let info = &*(lparam.0 as *const MSLLHOOKSTRUCT);
+        let high_word = (info.mouseData >> 16) as u16;
+        let event = match wparam.0 as u32 {
+            message @ (WM_XBUTTONDOWN | WM_XBUTTONUP) => {
+                let trigger = match high_word {
+                    XBUTTON1 => Some(MouseTrigger::Mouse4),
+                    XBUTTON2 => Some(MouseTrigger::Mouse5),
+                    _ => None,
+                };
+                trigger.map(|trigger| (trigger, message == WM_XBUTTONDOWN))
+            }
+            WM_MOUSEWHEEL if high_word as i16 > 0 => Some((MouseTrigger::WheelUp, true)),
+            WM_MOUSEWHEEL => Some((MouseTrigger::WheelDown, true)),
+            _ => None,
+        };
+        if let Some((trigger, pressed)) = event {
+            let resolved = {
+                let bindings = BINDINGS
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+                let mut held = PRESSED
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+                let modifiers = if pressed {
+                    current_modifiers()
+                } else {
+                    MouseModifiers::default()
+                };
+                resolve_event(&bindings, &mut held, trigger, modifiers, pressed)
+            };
+            if let (Some(event), Some(tx)) = (resolved, EVENT_TX.get()) {
+                let _ = tx
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .send(event);
+                return LRESULT(1);
+            }
+        }
+    }
+    CallNextHookEx(None, ncode, wparam, lparam)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_mouse_bindings_and_ignores_keyboard_accelerators() {
+        let thumb = MouseBinding::parse("mouse4").unwrap().unwrap();
+        assert_eq!(thumb.trigger, MouseTrigger::Mouse4);
+        assert!(!thumb.modifiers.any());
+
+        let wheel = MouseBinding::parse("CommandOrControl + Shift + WheelUp")
+            .unwrap()
+            .unwrap();
+        assert!(wheel.is_wheel());
+        assert_eq!(wheel.to_string(), "Ctrl+Shift+WheelUp");
+
+        assert!(MouseBinding::parse("WheelDown").unwrap().is_err());
+        assert!(MouseBinding::parse("Hyper+Mouse5").unwrap().is_err());
+        assert!(MouseBinding::parse("Ctrl+Shift+Space").is_none());
+        assert!(!is_mouse_binding("F9"));
+    }
+
+    #[test]
+    fn matching_requires_exact_modifiers() {
+        let bindings = vec![
+            (
+                MouseBinding::parse("Mouse5").unwrap().unwrap(),
+                MouseAction::Ptt,
+            ),
+            (
+                MouseBinding::parse("Ctrl+WheelDown").unwrap().unwrap(),
+                MouseAction::Toggle,
+            ),
+        ];
+        let ctrl = MouseModifiers {
+            ctrl: true,
+            ..MouseModifiers::default()
+        };
+        assert_eq!(
+            matching_action(&bindings, MouseTrigger::Mouse5, MouseModifiers::default()),
+            Some(MouseAction::Ptt)
+        );
+        assert_eq!(matching_action(&bindings, MouseTrigger::Mouse5, ctrl), None);
+        assert_eq!(
+            matching_action(&bindings, MouseTrigger::WheelDown, ctrl),
+            Some(MouseAction::Toggle)
+        );
+        assert_eq!(
+            matching_action(&bindings, MouseTrigger::WheelUp, ctrl),
+            None
+        );
+    }
+
+    #[test]
+    fn release_ends_the_pressed_action_regardless_of_modifiers() {
+        let bindings = vec![(
+            MouseBinding::parse("Ctrl+Mouse4").unwrap().unwrap(),
+            MouseAction::Ptt,
+        )];
+        let ctrl = MouseModifiers {
+            ctrl: true,
+            ..MouseModifiers::default()
+        };
+        let mut held = Vec::new();
+        assert_eq!(
+            resolve_event(&bindings, &mut held, MouseTrigger::Mouse4, ctrl, true),
+            Some((MouseAction::Ptt, true))
+        );
+        // Ctrl was let go before the button.
+        assert_eq!(
+            resolve_event(
+                &bindings,
+                &mut held,
+                MouseTrigger::Mouse4,
+                MouseModifiers::default(),
+                false
+            ),
+            Some((MouseAction::Ptt, false))
+        );
+        assert!(held.is_empty());
+        // A release whose press was not ours passes through.
+        assert_eq!(
+            resolve_event(&bindings, &mut held, MouseTrigger::Mouse4, ctrl, false),
+            None
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
 use crate::state::AppState;
 use base64::Engine;
 use serde::{Deserialize, Serialize};
@@ -219,6 +220,8 @@ pub struct PiperVoiceCatalogEntry {
     pub curated: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub path: Option<String>,
+    /// Installed in the download folder, so `remove_piper_voice_key` can delete it.
+    pub removable: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -610,13 +613,16 @@ pub fn capture_vision_frame(
 }
 
 #[tauri::command]
-pub(crate) fn list_screen_sources(app: AppHandle) -> Result<Vec<VisionSourceInfo>, String> {
+pub(crate) fn list_screen_sources(app: AppHandle) -> CommandResult<Vec<VisionSourceInfo>> {
     let window = app
         .get_webview_window("main")
-        .ok_or_else(|| "Main window not found.".to_string())?;
-    let monitors = window
-        .available_monitors()
-        .map_err(|error| format!("Failed to list monitors: {}", error))?;
+        .ok_or_else(|| CommandError::new(ErrorCode::Window, "Main window not found."))?;
+    let monitors = window.available_monitors().map_err(|error| {
+        CommandError::new(
+            ErrorCode::Window,
+            format!("Failed to list monitors: {}", error),
+        )
+    })?;
 
     let mut sources = Vec::new();
     for (index, monitor) in monitors.iter().enumerate() {
@@ -633,7 +639,7 @@ pub(crate) fn list_screen_sources(app: AppHandle) -> Result<Vec<VisionSourceInfo
         });
     }
     if sources.is_empty() {
-        if let Some(current) = window.current_monitor().map_err(|e| e.to_string())? {
+        if let Some(current) = window.current_monitor().with_code(ErrorCode::Window)? {
             let size = current.size();
             sources.push(VisionSourceInfo {
                 id: "monitor_1".to_string(),
@@ -653,14 +659,15 @@ pub(crate) fn list_screen_sources(app: AppHandle) -> Result<Vec<VisionSourceInfo
 pub(crate) fn start_vision_stream(
     app: AppHandle,
     state: State<'_, AppState>,
-) -> Result<VisionStreamHealth, String> {
+) -> CommandResult<VisionStreamHealth> {
     crate::guarded_command!("start_vision_stream", {
         let (fps, source_scope, max_width, jpeg_quality, ram_buffer_seconds) = {
             let settings = state
                 .settings
                 .read()
                 .unwrap_or_else(|poisoned| poisoned.into_inner());
-            crate::require_capability_enabled(&settings, crate::RuntimeCapability::VisionInput)?;
+            crate::require_capability_enabled(&settings, crate::RuntimeCapability::VisionInput)
+                .with_code(ErrorCode::InvalidInput)?;
             (
                 settings.vision_input_settings.fps,
                 settings.vision_input_settings.source_scope.clone(),
@@ -753,7 +760,7 @@ pub(crate) fn start_vision_stream(
 pub(crate) fn stop_vision_stream(
     app: AppHandle,
     state: State<'_, AppState>,
-) -> Result<VisionStreamHealth, String> {
+) -> CommandResult<VisionStreamHealth> {
     crate::guarded_command!("stop_vision_stream", {
         Ok(stop_vision_stream_internal(&app, state.inner()))
     })
@@ -875,8 +882,8 @@ pub(crate) fn capture_vision_snapshot_internal(
 pub(crate) fn capture_vision_snapshot(
     app: AppHandle,
     state: State<'_, AppState>,
-) -> Result<VisionSnapshotResult, String> {
-    capture_vision_snapshot_internal(&app, state.inner())
+) -> CommandResult<VisionSnapshotResult> {
+    capture_vision_snapshot_internal(&app, state.inner()).with_code(ErrorCode::Window)
 }
 
 #[tauri::command]
@@ -908,13 +915,14 @@ pub(crate) fn list_tts_voices(
 #[tauri::command]
 pub(crate) fn list_piper_voice_catalog(
     state: State<'_, AppState>,
-) -> Result<Vec<PiperVoiceCatalogEntry>, String> {
+) -> CommandResult<Vec<PiperVoiceCatalogEntry>> {
     let model_dir = {
         let settings = state
             .settings
             .read()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
-        crate::require_capability_enabled(&settings, crate::RuntimeCapability::VoiceOutputTts)?;
+        crate::require_capability_enabled(&settings, crate::RuntimeCapability::VoiceOutputTts)
+            .with_code(ErrorCode::InvalidInput)?;
         settings.voice_output_settings.piper_model_dir.clone()
     };
     Ok(list_piper_voice_catalog_entries(&model_dir))
@@ -925,20 +933,54 @@ pub(crate) fn download_piper_voice_key(
     app: AppHandle,
     state: State<'_, AppState>,
     voice_key: String,
-) -> Result<String, String> {
+) -> CommandResult<String> {
     {
         let settings = state
             .settings
             .read()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
-        crate::require_capability_enabled(&settings, crate::RuntimeCapability::VoiceOutputTts)?;
+        crate::require_capability_enabled(&settings, crate::RuntimeCapability::VoiceOutputTts)
+            .with_code(ErrorCode::InvalidInput)?;
     }
     let path = download_piper_voice_with_progress(voice_key.trim(), |progress| {
         let _ = app.emit("piper:voice-download-progress", progress);
-    })?;
+    })
+    .with_code(ErrorCode::Network)?;
     Ok(path.to_string_lossy().to_string())
 }
 
+#[tauri::command]
+pub(crate) fn remove_piper_voice_key(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    voice_key: String,
+) -> CommandResult<()> {
+    let voice_key = voice_key.trim();
+    {
+        let settings = state
+            .settings
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        crate::require_capability_enabled(&settings, crate::RuntimeCapability::VoiceOutputTts)
+            .with_code(ErrorCode::InvalidInput)?;
+        let active = Path::new(settings.voice_output_settings.piper_model_path.trim())
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or("");
+        if active.eq_ignore_ascii_case(voice_key) {
+            return Err(CommandError::new(
+                ErrorCode::InvalidInput,
+                format!(
+                    "Piper voice '{voice_key}' is the active voice; choose another voice first."
+                ),
+            ));
+        }
+    }
+    remove_piper_voice(voice_key).with_code(ErrorCode::Storage)?;
+    let _ = app.emit("piper:voice-removed", voice_key);
+    Ok(())
+}
+
 fn pinned_tts_language_hint(language_mode: &str, language_pinned: bool) -> Option<String> {
     if !language_pinned {
         return None;
@@ -1375,14 +1417,14 @@ pub(crate) fn speak_tts(
     app: AppHandle,
     state: State<'_, AppState>,
     request: TtsSpeakRequest,
-) -> Result<TtsSpeakResult, String> {
+) -> CommandResult<TtsSpeakResult> {
     crate::guarded_command!("speak_tts", {
-        speak_tts_internal(&app, state.inner(), request)
+        speak_tts_internal(&app, state.inner(), request).with_code(ErrorCode::InvalidInput)
     })
 }
 
 #[tauri::command]
-pub(crate) fn stop_tts(app: AppHandle, state: State<'_, AppState>) -> Result<bool, String> {
+pub(crate) fn stop_tts(app: AppHandle, state: State<'_, AppState>) -> CommandResult<bool> {
     Ok(stop_tts_internal(&app, state.inner()))
 }
 
@@ -1418,7 +1460,7 @@ pub(crate) fn test_tts_provider(
     app: AppHandle,
     state: State<'_, AppState>,
     provider: Option<String>,
-) -> Result<TtsSpeakResult, String> {
+) -> CommandResult<TtsSpeakResult> {
     crate::guarded_command!("test_tts_provider", {
         let preferred_provider = provider
             .unwrap_or_else(|| "windows_native".to_string())
@@ -1429,7 +1471,8 @@ pub(crate) fn test_tts_provider(
                 .settings
                 .read()
                 .unwrap_or_else(|poisoned| poisoned.into_inner());
-            crate::require_capability_enabled(&settings, crate::RuntimeCapability::VoiceOutputTts)?;
+            crate::require_capability_enabled(&settings, crate::RuntimeCapability::VoiceOutputTts)
+                .with_code(ErrorCode::InvalidInput)?;
             (
                 settings.voice_output_settings.clone(),
                 settings.language_mode.clone(),
@@ -1586,7 +1629,7 @@ pub(crate) fn test_tts_provider(
                                 "timestamp_ms": crate::util::now_ms(),
                             }),
                         );
-                        return Err(merged);
+                        return Err(CommandError::new(ErrorCode::AudioDevice, merged));
                     }
                 }
             }
@@ -1609,7 +1652,7 @@ pub(crate) fn test_tts_provider(
                         "timestamp_ms": crate::util::now_ms(),
                     }),
                 );
-                return Err(error);
+                return Err(CommandError::new(ErrorCode::Internal, error));
             }
         };
 
@@ -2160,6 +2203,7 @@ pub fn list_piper_voice_catalog_entries(model_dir: &str) -> Vec<PiperVoiceCatalo
             quality,
             installed: path.is_some(),
             curated: true,
+            removable: path.as_deref().is_some_and(is_downloaded_piper_voice),
             path,
         });
     }
@@ -2181,6 +2225,7 @@ pub fn list_piper_voice_catalog_entries(model_dir: &str) -> Vec<PiperVoiceCatalo
             quality,
             installed: true,
             curated: false,
+            removable: is_downloaded_piper_voice(&path),
             path: Some(path),
         });
     }
@@ -2586,7 +2631,8 @@ fn resolve_piper_binary(configured: &str) -> Option<std::path::PathBuf> {
 ///   1. Configured path (settings.piper_model_dir)
 ///   2. Installed piper_tts module: %LOCALAPPDATA%\Trispr Flow\modules\piper_tts\bin\piper\voices
 ///   3. Tauri resource dir: <exe_dir>/resources/bin/piper/voices/  (bundled with installer)
-///   4. %LOCALAPPDATA%\trispr-flow\piper\voices\                    (manual install)
+///   4. <data dir>/piper/voices/                                   (downloaded voices)
+///   5. %LOCALAPPDATA%\trispr-flow\piper\voices\                    (manual install)
 fn resolve_piper_model_dir(configured: &str) -> Option<std::path::PathBuf> {
     if !configured.is_empty() {
         let p = std::path::PathBuf::from(configured);
@@ -2649,6 +2695,9 @@ fn resolve_piper_model_dir(configured: &str) -> Option<std::path::PathBuf> {
             }
         }
     }
+    if let Some(downloaded) = crate::paths::managed_piper_voices_dir().filter(|dir| dir.is_dir()) {
+        return Some(downloaded.to_path_buf());
+    }
     if let Some(local_app_data) = std::env::var_os("LOCALAPPDATA") {
         let p = std::path::PathBuf::from(local_app_data)
             .join("trispr-flow")
@@ -2720,6 +2769,9 @@ fn collect_piper_catalog_model_dirs(configured: &str) -> Vec<PathBuf> {
     if let Some(primary) = resolve_piper_model_dir(configured_trimmed) {
         push_unique_dir(&mut dirs, primary);
     }
+    if let Some(downloaded) = crate::paths::managed_piper_voices_dir().filter(|dir| dir.is_dir()) {
+        push_unique_dir(&mut dirs, downloaded.to_path_buf());
+    }
     if let Some(local) = piper_local_app_data_model_dir() {
         push_unique_dir(&mut dirs, local);
     }
@@ -2769,6 +2821,76 @@ fn piper_hf_path_from_voice_key(voice_key: &str) -> Option<String> {
     ))
 }
 
+/// Where `download_piper_voice_key` installs voices: the app's managed
+/// `<data dir>/piper/voices`, on every platform.
+fn piper_download_dir() -> Result<PathBuf, String> {
+    crate::paths::managed_piper_voices_dir()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| "Piper voice folder is not set up yet".to_string())
+}
+
+/// Folders holding voices the app downloaded, which are the only removable
+/// ones: the managed folder, and `%LOCALAPPDATA%\trispr-flow\piper\voices`
+/// where earlier versions put them.
+fn piper_download_dirs() -> Vec<PathBuf> {
+    piper_download_dir()
+        .ok()
+        .into_iter()
+        .chain(piper_local_app_data_model_dir())
+        .collect()
+}
+
+fn is_downloaded_piper_voice(path: &str) -> bool {
+    let normalize = |dir: &Path| {
+        dir.to_string_lossy()
+            .replace('\\', "/")
+            .to_ascii_lowercase()
+    };
+    let Some(parent) = Path::new(path).parent() else {
+        return false;
+    };
+    piper_download_dirs()
+        .iter()
+        .any(|dir| normalize(parent) == normalize(dir))
+}
+
+/// Deletes `<voice_key>.onnx` and its `.onnx.json` from `voices_dir`.
+fn remove_piper_voice_files(voices_dir: &Path, voice_key: &str) -> Result<(), String> {
+    let voice_key = voice_key.trim();
+    if piper_hf_path_from_voice_key(voice_key).is_none() {
+        return Err(format!("Unsupported Piper voice key '{voice_key}'."));
+    }
+    let onnx_path = voices_dir.join(format!("{voice_key}.onnx"));
+    let json_path = voices_dir.join(format!("{voice_key}.onnx.json"));
+    if !onnx_path.exists() && !json_path.exists() {
+        return Err(format!(
+            "Piper voice '{voice_key}' is not installed in the download folder."
+        ));
+    }
+    for path in [&onnx_path, &json_path] {
+        match std::fs::remove_file(path) {
+            Ok(()) => {}
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+            Err(error) => return Err(format!("Cannot remove {}: {error}", path.display())),
+        }
+    }
+    Ok(())
+}
+
+pub fn remove_piper_voice(voice_key: &str) -> Result<(), String> {
+    let mut result = Err(format!(
+        "Piper voice '{}' is not installed in the download folder.",
+        voice_key.trim()
+    ));
+    for dir in piper_download_dirs() {
+        result = remove_piper_voice_files(&dir, voice_key);
+        if result.is_ok() {
+            break;
+        }
+    }
+    result
+}
+
 fn emit_piper_download_progress<F>(
     emit_progress: &mut F,
     voice_key: &str,
@@ -2894,7 +3016,7 @@ where
     Ok(())
 }
 
-/// Download a Piper voice model to `%LOCALAPPDATA%\trispr-flow\piper\voices\`.
+/// Download a Piper voice model to `<data dir>/piper/voices/`.
 ///
 /// `voice_key` must follow Piper naming, for example:
 /// - `de_DE-thorsten-medium`
@@ -2923,14 +3045,7 @@ where
         )
     })?;
 
-    let voices_dir = std::env::var_os("LOCALAPPDATA")
-        .map(|d| {
-            std::path::PathBuf::from(d)
-                .join("trispr-flow")
-                .join("piper")
-                .join("voices")
-        })
-        .ok_or_else(|| "LOCALAPPDATA not set".to_string())?;
+    let voices_dir = piper_download_dir()?;
 
     std::fs::create_dir_all(&voices_dir).map_err(|e| format!("Cannot create voices dir: {e}"))?;
 
@@ -4218,6 +4333,24 @@ fn play_wav_blocking(
     let reader = hound::WavReader::open(path).map_err(|e| format!("Cannot read WAV: {e}"))?;
     let spec = reader.spec();
     let decoded_samples = decode_wav_to_f32(reader, spec)?;
+    play_samples_blocking(
+        &decoded_samples,
+        spec,
+        volume,
+        output_device_id,
+        playback_control,
+    )
+}
+
+/// Plays interleaved f32 samples laid out as `spec` describes, trying each
+/// compatible stream config of the output device in turn.
+pub(crate) fn play_samples_blocking(
+    decoded_samples: &[f32],
+    spec: hound::WavSpec,
+    volume: f32,
+    output_device_id: &str,
+    playback_control: Option<Arc<TtsPlaybackControl>>,
+) -> Result<(), String> {
     if decoded_samples.is_empty() {
         return Ok(());
     }
@@ -4236,7 +4369,7 @@ fn play_wav_blocking(
 
     for candidate in &candidates {
         let remapped = remap_channels_interleaved(
-            &decoded_samples,
+            decoded_samples,
             usize::from(spec.channels.max(1)),
             usize::from(candidate.stream_config.channels.max(1)),
         );
@@ -4319,10 +4452,11 @@ mod tests {
         convert_f32_to_i16, convert_f32_to_u16, execute_tts_with_fallback,
         format_stream_config_mismatch_error, is_removed_piper_voice_key,
         is_tts_audio_device_unavailable_tagged, is_tts_policy_allowed, normalize_piper_rate,
-        piper_hf_path_from_voice_key, remap_channels_interleaved, resample_interleaved_linear,
-        select_voice_from_candidates_for_language, windows_audio_device_error_hint,
-        windows_natural_voice_priority, windows_voice_matches_natural_profile,
-        OutputStreamCandidate, PiperDaemonConfig, TtsVoiceInfo, VisionFrame, VisionFrameBuffer,
+        piper_hf_path_from_voice_key, remap_channels_interleaved, remove_piper_voice_files,
+        resample_interleaved_linear, select_voice_from_candidates_for_language,
+        windows_audio_device_error_hint, windows_natural_voice_priority,
+        windows_voice_matches_natural_profile, OutputStreamCandidate, PiperDaemonConfig,
+        TtsVoiceInfo, VisionFrame, VisionFrameBuffer,
     };
     use std::path::PathBuf;
 
@@ -4589,6 +4723,25 @@ mod tests {
         assert!(piper_hf_path_from_voice_key("../de_DE-thorsten-medium").is_none());
     }
 
+    #[test]
+    fn remove_piper_voice_deletes_model_and_metadata_only() {
+        let dir = std::env::temp_dir().join(format!("trispr_piper_remove_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let key = "de_DE-thorsten-medium";
+        std::fs::write(dir.join(format!("{key}.onnx")), b"model").unwrap();
+        std::fs::write(dir.join(format!("{key}.onnx.json")), b"{}").unwrap();
+        std::fs::write(dir.join("en_GB-alan-low.onnx"), b"model").unwrap();
+
+        assert!(remove_piper_voice_files(&dir, "../en_GB-alan-low").is_err());
+        remove_piper_voice_files(&dir, key).unwrap();
+        assert!(!dir.join(format!("{key}.onnx")).exists());
+        assert!(!dir.join(format!("{key}.onnx.json")).exists());
+        assert!(dir.join("en_GB-alan-low.onnx").exists());
+        assert!(remove_piper_voice_files(&dir, key).is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn removed_piper_voice_key_is_detected_case_insensitive() {
         assert!(is_removed_piper_voice_key("de_DE-mls-medium"));
@@ -2,6 +2,7 @@ use crate::ai_fallback::check_strict_local_mode;
 use crate::ai_fallback::provider::{
     is_local_ollama_endpoint, list_ollama_models, ping_ollama, ping_ollama_quick,
 };
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
 use crate::managed_child_slot_status;
 use crate::now_iso;
 use crate::paths::resolve_data_path;
@@ -1330,13 +1331,13 @@ pub(crate) fn clear_ollama_pid_lockfile(app: &AppHandle) {
 #[tauri::command]
 pub fn list_ollama_runtime_versions(
     state: State<'_, AppState>,
-) -> Result<Vec<OllamaRuntimeVersionInfo>, String> {
+) -> CommandResult<Vec<OllamaRuntimeVersionInfo>> {
     let snapshot = state
         .settings
         .read()
         .unwrap_or_else(|poisoned| poisoned.into_inner())
         .clone();
-    build_version_list(&snapshot, &[])
+    build_version_list(&snapshot, &[]).with_code(ErrorCode::Internal)
 }
 
 /// Fetches available versions from GitHub and merges with pinned list.
@@ -1344,7 +1345,7 @@ pub fn list_ollama_runtime_versions(
 #[tauri::command]
 pub async fn fetch_ollama_online_versions(
     app: AppHandle,
-) -> Result<Vec<OllamaRuntimeVersionInfo>, String> {
+) -> CommandResult<Vec<OllamaRuntimeVersionInfo>> {
     tauri::async_runtime::spawn_blocking(move || {
         let state = app.state::<AppState>();
         let snapshot = state
@@ -1356,7 +1357,13 @@ pub async fn fetch_ollama_online_versions(
         build_version_list(&snapshot, &online)
     })
     .await
-    .map_err(|e| format!("Online version fetch failed: {}", e))?
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("Online version fetch failed: {}", e),
+        )
+    })?
+    .with_code(ErrorCode::Network)
 }
 
 fn build_version_list(
@@ -1591,22 +1598,34 @@ fn detect_ollama_runtime_impl(app: &AppHandle) -> Result<OllamaRuntimeDetectResu
 }
 
 #[tauri::command]
-pub async fn detect_ollama_runtime(app: AppHandle) -> Result<OllamaRuntimeDetectResult, String> {
+pub async fn detect_ollama_runtime(app: AppHandle) -> CommandResult<OllamaRuntimeDetectResult> {
     let app_handle = app.clone();
     tauri::async_runtime::spawn_blocking(move || detect_ollama_runtime_impl(&app_handle))
         .await
-        .map_err(|e| format!("Runtime detect task failed: {}", e))?
+        .map_err(|e| {
+            CommandError::new(
+                ErrorCode::Internal,
+                format!("Runtime detect task failed: {}", e),
+            )
+        })?
+        .with_code(ErrorCode::Internal)
 }
 
 #[tauri::command]
 pub async fn download_ollama_runtime(
     app: AppHandle,
     version: Option<String>,
-) -> Result<OllamaRuntimeDownloadResult, String> {
+) -> CommandResult<OllamaRuntimeDownloadResult> {
     let app_handle = app.clone();
     tauri::async_runtime::spawn_blocking(move || download_ollama_runtime_impl(&app_handle, version))
         .await
-        .map_err(|e| format!("Runtime download task failed: {}", e))?
+        .map_err(|e| {
+            CommandError::new(
+                ErrorCode::Internal,
+                format!("Runtime download task failed: {}", e),
+            )
+        })?
+        .with_code(ErrorCode::Network)
 }
 
 fn download_ollama_runtime_impl(
@@ -1745,13 +1764,19 @@ fn download_ollama_runtime_impl(
 pub async fn install_ollama_runtime(
     app: AppHandle,
     archive_path: String,
-) -> Result<OllamaRuntimeInstallResult, String> {
+) -> CommandResult<OllamaRuntimeInstallResult> {
     let app_handle = app.clone();
     tauri::async_runtime::spawn_blocking(move || {
         install_ollama_runtime_impl(&app_handle, archive_path)
     })
     .await
-    .map_err(|e| format!("Runtime install task failed: {}", e))?
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("Runtime install task failed: {}", e),
+        )
+    })?
+    .with_code(ErrorCode::Storage)
 }
 
 fn install_ollama_runtime_impl(
@@ -1883,11 +1908,17 @@ fn install_ollama_runtime_impl(
 }
 
 #[tauri::command]
-pub async fn start_ollama_runtime(app: AppHandle) -> Result<OllamaRuntimeStartResult, String> {
+pub async fn start_ollama_runtime(app: AppHandle) -> CommandResult<OllamaRuntimeStartResult> {
     let app_handle = app.clone();
     tauri::async_runtime::spawn_blocking(move || start_ollama_runtime_impl(&app_handle))
         .await
-        .map_err(|e| format!("Runtime start task failed: {}", e))?
+        .map_err(|e| {
+            CommandError::new(
+                ErrorCode::Internal,
+                format!("Runtime start task failed: {}", e),
+            )
+        })?
+        .with_code(ErrorCode::RuntimeMissing)
 }
 
 fn start_ollama_runtime_impl(app: &AppHandle) -> Result<OllamaRuntimeStartResult, String> {
@@ -2205,11 +2236,17 @@ fn start_ollama_runtime_impl(app: &AppHandle) -> Result<OllamaRuntimeStartResult
 }
 
 #[tauri::command]
-pub async fn verify_ollama_runtime(app: AppHandle) -> Result<OllamaRuntimeVerifyResult, String> {
+pub async fn verify_ollama_runtime(app: AppHandle) -> CommandResult<OllamaRuntimeVerifyResult> {
     let app_handle = app.clone();
     tauri::async_runtime::spawn_blocking(move || verify_ollama_runtime_impl(&app_handle))
         .await
-        .map_err(|e| format!("Runtime verify task failed: {}", e))?
+        .map_err(|e| {
+            CommandError::new(
+                ErrorCode::Internal,
+                format!("Runtime verify task failed: {}", e),
+            )
+        })?
+        .with_code(ErrorCode::Network)
 }
 
 fn verify_ollama_runtime_impl(app: &AppHandle) -> Result<OllamaRuntimeVerifyResult, String> {
@@ -2342,13 +2379,19 @@ pub fn import_ollama_model_from_file(
     state: State<'_, AppState>,
     path: String,
     mode: String,
-) -> Result<OllamaImportResult, String> {
+) -> CommandResult<OllamaImportResult> {
     let source_path = PathBuf::from(path.trim());
     if !source_path.exists() {
-        return Err("Import file does not exist.".to_string());
+        return Err(CommandError::new(
+            ErrorCode::NotFound,
+            "Import file does not exist.",
+        ));
     }
     if !source_path.is_file() {
-        return Err("Import path must point to a file.".to_string());
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "Import path must point to a file.",
+        ));
     }
 
     let settings_snapshot = state
@@ -2357,15 +2400,19 @@ pub fn import_ollama_model_from_file(
         .unwrap_or_else(|poisoned| poisoned.into_inner())
         .clone();
     let endpoint = settings_snapshot.providers.ollama.endpoint.clone();
-    check_strict_local_mode(&settings_snapshot)?;
+    check_strict_local_mode(&settings_snapshot).with_code(ErrorCode::InvalidInput)?;
     ping_ollama(&endpoint).map_err(|e| {
-        format!(
-            "Ollama runtime is not reachable. Start runtime first: {}",
-            e
+        CommandError::new(
+            ErrorCode::Network,
+            format!(
+                "Ollama runtime is not reachable. Start runtime first: {}",
+                e
+            ),
         )
     })?;
 
-    let (binary_path, _) = select_runtime_binary(&app, &settings_snapshot)?;
+    let (binary_path, _) =
+        select_runtime_binary(&app, &settings_snapshot).with_code(ErrorCode::RuntimeMissing)?;
 
     let mode = mode.trim().to_lowercase();
     let mut temp_modelfile_path: Option<PathBuf> = None;
@@ -2376,13 +2423,21 @@ pub fn import_ollama_model_from_file(
             &temp_path,
             format!("FROM \"{}\"\n", source_path.to_string_lossy()),
         )
-        .map_err(|e| format!("Failed to create temporary Modelfile: {}", e))?;
+        .map_err(|e| {
+            CommandError::new(
+                ErrorCode::Storage,
+                format!("Failed to create temporary Modelfile: {}", e),
+            )
+        })?;
         temp_modelfile_path = Some(temp_path.clone());
         temp_path
     } else if mode == "modelfile" {
         source_path.clone()
     } else {
-        return Err("Unsupported import mode. Use 'gguf' or 'modelfile'.".to_string());
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "Unsupported import mode. Use 'gguf' or 'modelfile'.",
+        ));
     };
 
     let default_name = source_path
@@ -2390,7 +2445,7 @@ pub fn import_ollama_model_from_file(
         .and_then(|s| s.to_str())
         .unwrap_or("imported-model");
     let model_name = sanitize_model_name(default_name);
-    let host = endpoint_host_port(&endpoint)?;
+    let host = endpoint_host_port(&endpoint).with_code(ErrorCode::InvalidInput)?;
 
     let output = Command::new(&binary_path)
         .arg("create")
@@ -2400,7 +2455,12 @@ pub fn import_ollama_model_from_file(
         .env("OLLAMA_HOST", host)
         .env("OLLAMA_NO_CLOUD", "1")
         .output()
-        .map_err(|e| format!("Failed to run ollama create: {}", e))?;
+        .map_err(|e| {
+            CommandError::new(
+                ErrorCode::RuntimeMissing,
+                format!("Failed to run ollama create: {}", e),
+            )
+        })?;
 
     if let Some(temp) = temp_modelfile_path {
         let _ = fs::remove_file(temp);
@@ -2410,14 +2470,17 @@ pub fn import_ollama_model_from_file(
         let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
         let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
         let detail = if !stderr.is_empty() { stderr } else { stdout };
-        return Err(format!(
-            "Model import failed for '{}': {}",
-            model_name,
-            if detail.is_empty() {
-                "unknown error".to_string()
-            } else {
-                detail
-            }
+        return Err(CommandError::new(
+            ErrorCode::Internal,
+            format!(
+                "Model import failed for '{}': {}",
+                model_name,
+                if detail.is_empty() {
+                    "unknown error".to_string()
+                } else {
+                    detail
+                }
+            ),
         ));
     }
 
@@ -2440,7 +2503,7 @@ pub fn import_ollama_model_from_file(
         settings.setup.local_ai_wizard_pending = false;
         settings.clone()
     };
-    save_settings_file(&app, &snapshot)?;
+    save_settings_file(&app, &snapshot).with_code(ErrorCode::Storage)?;
     let _ = app.emit("settings-changed", snapshot);
 
     Ok(OllamaImportResult { model_name })
@@ -2451,7 +2514,7 @@ pub fn set_strict_local_mode(
     app: AppHandle,
     state: State<'_, AppState>,
     enabled: bool,
-) -> Result<serde_json::Value, String> {
+) -> CommandResult<serde_json::Value> {
     let snapshot = {
         let mut settings = state
             .settings
@@ -2463,7 +2526,7 @@ pub fn set_strict_local_mode(
         }
         settings.clone()
     };
-    save_settings_file(&app, &snapshot)?;
+    save_settings_file(&app, &snapshot).with_code(ErrorCode::Storage)?;
     let _ = app.emit("settings-changed", snapshot.clone());
     Ok(serde_json::json!({
         "status": "success",
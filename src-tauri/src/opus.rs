@@ -6,6 +6,7 @@
 // sidecar binary, hands it file paths, and parses its JSON result. When the
 // module is not installed, callers treat opus export as a no-op.
 
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
@@ -174,6 +175,69 @@ pub fn concat_with_sidecar(
     Ok(())
 }
 
+/// Decode an OPUS file to a 16 kHz mono WAV via the sidecar `decode` subcommand.
+pub fn decode_with_sidecar(sidecar: &Path, input: &Path, output: &Path) -> Result<(), String> {
+    let mut cmd = Command::new(sidecar);
+    no_window(&mut cmd);
+    cmd.arg("decode")
+        .arg("--input")
+        .arg(input)
+        .arg("--output")
+        .arg(output)
+        .arg("--sample-rate")
+        .arg("16000")
+        .arg("--channels")
+        .arg("1")
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped());
+
+    let out = cmd
+        .output()
+        .map_err(|e| format!("Failed to run opus sidecar: {e}"))?;
+    if !out.status.success() {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        return Err(format!("opus sidecar decode failed: {stderr}"));
+    }
+    Ok(())
+}
+
+/// Load a saved recording (`.wav` directly, `.opus` through the sidecar) as
+/// 16 kHz mono samples ready for `transcribe_audio`.
+pub(crate) fn load_recording_samples(app: &AppHandle, path: &Path) -> Result<Vec<i16>, String> {
+    let recording = crate::history_crypto::plaintext_recording(path)?;
+    let path = recording.path();
+    let is_wav = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("wav"));
+    if is_wav {
+        return read_wav_samples(path);
+    }
+    let sidecar =
+        resolve_sidecar(app).ok_or_else(|| "The opus module is not installed.".to_string())?;
+    let wav_path = crate::scratch::scratch_path("decode", "wav");
+    let result =
+        decode_with_sidecar(&sidecar, path, &wav_path).and_then(|_| read_wav_samples(&wav_path));
+    let _ = std::fs::remove_file(&wav_path);
+    result
+}
+
+fn read_wav_samples(path: &Path) -> Result<Vec<i16>, String> {
+    let mut reader =
+        hound::WavReader::open(path).map_err(|e| format!("Failed to open recording: {e}"))?;
+    let spec = reader.spec();
+    if spec.sample_rate != 16000 || spec.channels != 1 || spec.bits_per_sample != 16 {
+        return Err(format!(
+            "Unsupported recording format: {} Hz, {} ch, {} bit",
+            spec.sample_rate, spec.channels, spec.bits_per_sample
+        ));
+    }
+    reader
+        .samples::<i16>()
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read recording: {e}"))
+}
+
 /// Probe the sidecar for FFmpeg/libopus availability + version.
 pub fn probe_with_sidecar(sidecar: &Path) -> Result<OpusProbeResult, String> {
     let mut cmd = Command::new(sidecar);
@@ -195,23 +259,29 @@ pub(crate) fn encode_to_opus(
     input_path: String,
     output_path: String,
     bitrate_kbps: Option<u32>,
-) -> Result<OpusEncodeResult, String> {
-    let sidecar =
-        resolve_sidecar(&app).ok_or_else(|| "The opus module is not installed.".to_string())?;
+) -> CommandResult<OpusEncodeResult> {
+    let sidecar = resolve_sidecar(&app).ok_or_else(|| {
+        CommandError::new(
+            ErrorCode::RuntimeMissing,
+            "The opus module is not installed.",
+        )
+    })?;
 
     let allowed_root = crate::paths::resolve_base_dir(&app);
-    let input = crate::paths::validate_path_within(&input_path, &allowed_root)?;
-    let output = crate::paths::validate_path_within(&output_path, &allowed_root)?;
+    let input = crate::paths::validate_path_within(&input_path, &allowed_root)
+        .with_code(ErrorCode::InvalidInput)?;
+    let output = crate::paths::validate_path_within(&output_path, &allowed_root)
+        .with_code(ErrorCode::InvalidInput)?;
 
     let mut config = OpusEncoderConfig::default();
     if let Some(bitrate) = bitrate_kbps {
         config.bitrate_kbps = bitrate;
     }
-    encode_with_sidecar(&sidecar, &input, &output, &config)
+    encode_with_sidecar(&sidecar, &input, &output, &config).with_code(ErrorCode::Internal)
 }
 
 #[tauri::command]
-pub(crate) fn check_ffmpeg(app: AppHandle) -> Result<bool, String> {
+pub(crate) fn check_ffmpeg(app: AppHandle) -> CommandResult<bool> {
     match resolve_sidecar(&app) {
         Some(sidecar) => Ok(probe_with_sidecar(&sidecar)
             .map(|p| p.available)
@@ -221,10 +291,14 @@ pub(crate) fn check_ffmpeg(app: AppHandle) -> Result<bool, String> {
 }
 
 #[tauri::command]
-pub(crate) fn get_ffmpeg_version_info(app: AppHandle) -> Result<String, String> {
-    let sidecar =
-        resolve_sidecar(&app).ok_or_else(|| "The opus module is not installed.".to_string())?;
-    let probe = probe_with_sidecar(&sidecar)?;
+pub(crate) fn get_ffmpeg_version_info(app: AppHandle) -> CommandResult<String> {
+    let sidecar = resolve_sidecar(&app).ok_or_else(|| {
+        CommandError::new(
+            ErrorCode::RuntimeMissing,
+            "The opus module is not installed.",
+        )
+    })?;
+    let probe = probe_with_sidecar(&sidecar).with_code(ErrorCode::RuntimeMissing)?;
     if probe.version.is_empty() {
         warn!("opus sidecar reported an empty FFmpeg version string");
     }
@@ -273,4 +347,31 @@ mod tests {
         assert_eq!(resolve_sidecar_in(&dir), Some(bin));
         let _ = fs::remove_dir_all(&dir);
     }
+
+    #[test]
+    fn read_wav_samples_requires_16k_mono() {
+        let dir = std::env::temp_dir().join("trispr_opus_read_wav");
+        fs::create_dir_all(&dir).unwrap();
+        let write = |name: &str, sample_rate: u32| {
+            let path = dir.join(name);
+            let spec = hound::WavSpec {
+                channels: 1,
+                sample_rate,
+                bits_per_sample: 16,
+                sample_format: hound::SampleFormat::Int,
+            };
+            let mut writer = hound::WavWriter::create(&path, spec).unwrap();
+            for sample in [0i16, 100, -100] {
+                writer.write_sample(sample).unwrap();
+            }
+            writer.finalize().unwrap();
+            path
+        };
+        assert_eq!(
+            read_wav_samples(&write("ok.wav", 16000)).unwrap(),
+            vec![0, 100, -100]
+        );
+        assert!(read_wav_samples(&write("wrong_rate.wav", 44100)).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
 }
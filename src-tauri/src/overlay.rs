@@ -1,7 +1,11 @@
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
 use crate::state::{AppState, Settings};
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
 use std::time::Duration;
+use tauri::ipc::{Channel, InvokeResponseBody};
 use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindow, WindowEvent};
 use tracing::{info, warn};
 
@@ -9,12 +13,30 @@ const OVERLAY_RECOVERY_MAX_ATTEMPTS: u32 = 4;
 const OVERLAY_RECOVERY_BACKOFF_MS: u64 = 140;
 const OVERLAY_CREATE_COOLDOWN_MS: u64 = 1_200;
 const OVERLAY_HEARTBEAT_STALE_MS: u64 = 6_000;
+/// Number of recent levels kept for the waveform style (one per level tick).
+const OVERLAY_LEVEL_HISTORY_LEN: usize = 64;
+/// Cursor-follow interval while the overlay is being dragged (~60 Hz).
+const OVERLAY_DRAG_TICK_MS: u64 = 16;
+/// Distance (logical px) within which a dropped overlay snaps to a monitor edge.
+const OVERLAY_SNAP_DISTANCE: f64 = 24.0;
+/// How long the error state stays up before the overlay returns to idle.
+const OVERLAY_ERROR_FLASH_MS: u64 = 2_500;
+/// Minimum gap between error flashes, so a run of failing chunks flashes once.
+const OVERLAY_ERROR_DEBOUNCE_MS: u64 = 10_000;
 
 /// Throttles repeated create attempts after hard WebView failures.
 /// Unlike the legacy lockout, this is a short cooldown and never permanent.
 static OVERLAY_CREATE_COOLDOWN_UNTIL_MS: AtomicU64 = AtomicU64::new(0);
 static OVERLAY_CREATE_IN_FLIGHT: AtomicBool = AtomicBool::new(false);
 static MONITOR_FOLLOW_ACTIVE: AtomicBool = AtomicBool::new(false);
+/// Set while the overlay follows the cursor during a drag.
+static OVERLAY_DRAG_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Connected monitors as (name, x, y, width, height) in physical pixels,
+/// sorted. Compared between checks to notice docking, undocking and
+/// resolution changes.
+type MonitorTopology = Vec<(Option<String>, i32, i32, u32, u32)>;
+static LAST_MONITOR_TOPOLOGY: Mutex<Option<MonitorTopology>> = Mutex::new(None);
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -23,6 +45,12 @@ pub enum OverlayState {
     Armed,
     Recording,
     Transcribing,
+    /// Audio is being sent to a cloud transcription provider.
+    CloudTranscribing,
+    /// Privacy mute engaged; shown in a fixed warning color.
+    Muted,
+    /// Capture or transcription just failed; shown briefly, then idle.
+    Error,
 }
 
 /// OLLAMA model readiness tri-state for overlay color indication.
@@ -47,6 +75,8 @@ pub struct OverlaySettings {
     pub opacity_active: f64,
     pub pos_x: f64,
     pub pos_y: f64,
+    /// See `Settings::overlay_monitor`.
+    pub monitor: String,
     pub style: String,
     pub refining_indicator_enabled: bool,
     pub refining_indicator_preset: String,
@@ -61,6 +91,18 @@ pub struct OverlaySettings {
     pub kitt_height: f64,
 }
 
+/// A connected display as listed in the overlay monitor picker.
+#[derive(Debug, Clone, Serialize)]
+pub struct OverlayMonitorInfo {
+    pub name: String,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub scale_factor: f64,
+    pub primary: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OverlayHealthEvent {
     pub status: String,
@@ -68,6 +110,67 @@ pub struct OverlayHealthEvent {
     pub reason: String,
 }
 
+/// Overlay styles that draw a horizontal bar and share the KITT geometry
+/// settings (width, height, rise/fall).
+pub fn overlay_style_uses_bar(style: &str) -> bool {
+    matches!(style, "kitt" | "waveform")
+}
+
+/// Fixed-size ring of recent input levels, quantised to bytes so a frame is
+/// cheap to ship to the overlay as a binary payload.
+#[derive(Debug, Clone, Default)]
+pub struct LevelHistory {
+    levels: VecDeque<u8>,
+}
+
+impl LevelHistory {
+    pub fn push(&mut self, level: f64) {
+        if self.levels.len() == OVERLAY_LEVEL_HISTORY_LEN {
+            self.levels.pop_front();
+        }
+        self.levels
+            .push_back((level.clamp(0.0, 1.0) * 255.0).round() as u8);
+    }
+
+    pub fn clear(&mut self) {
+        self.levels.clear();
+    }
+
+    /// Oldest first; the last byte is the current level.
+    pub fn ordered(&self) -> Vec<u8> {
+        self.levels.iter().copied().collect()
+    }
+}
+
+/// Binary level stream registered by the overlay page. While set, level
+/// updates are sent here instead of through per-frame `window.eval`.
+static LEVEL_CHANNEL: Mutex<Option<Channel<InvokeResponseBody>>> = Mutex::new(None);
+
+/// Called by the overlay page on load; replaces any channel from a previous
+/// page instance.
+#[tauri::command]
+pub fn overlay_subscribe_levels(on_levels: Channel<InvokeResponseBody>) {
+    *LEVEL_CHANNEL
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(on_levels);
+}
+
+/// Sends a level frame over the subscribed channel. Returns false when no
+/// channel is available (or it broke), so the caller can fall back to eval.
+fn send_level_frame(frame: Vec<u8>) -> bool {
+    let mut guard = LEVEL_CHANNEL
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let Some(channel) = guard.as_ref() else {
+        return false;
+    };
+    if channel.send(InvokeResponseBody::Raw(frame)).is_err() {
+        *guard = None;
+        return false;
+    }
+    true
+}
+
 #[derive(Debug, Clone)]
 pub struct OverlayController {
     pub desired_state: OverlayState,
@@ -75,9 +178,17 @@ pub struct OverlayController {
     pub refining_active: bool,
     pub tts_stop_visible: bool,
     pub last_level: f64,
+    pub level_history: LevelHistory,
+    /// Overlay is accepting pointer input so the user can drag it.
+    pub move_mode: bool,
     pub last_heartbeat_ms: u64,
     pub recovery_attempt: u32,
     pub ollama_model_state: OllamaModelState,
+    /// The error flash stays up until this time; states requested meanwhile
+    /// (other than Recording) wait in `state_after_error`.
+    pub error_until_ms: u64,
+    pub state_after_error: Option<OverlayState>,
+    pub last_error_flash_ms: u64,
 }
 
 impl Default for OverlayController {
@@ -88,9 +199,14 @@ impl Default for OverlayController {
             refining_active: false,
             tts_stop_visible: false,
             last_level: 0.0,
+            level_history: LevelHistory::default(),
+            move_mode: false,
             last_heartbeat_ms: 0,
             recovery_attempt: 0,
             ollama_model_state: OllamaModelState::Cold,
+            error_until_ms: 0,
+            state_after_error: None,
+            last_error_flash_ms: 0,
         }
     }
 }
@@ -141,6 +257,132 @@ fn monitor_at_cursor(window: &WebviewWindow) -> Option<tauri::Monitor> {
     }
 }
 
+/// Monitor under the cursor, else the main window's monitor, else primary.
+fn cursor_or_fallback_monitor(window: &WebviewWindow) -> Option<tauri::Monitor> {
+    monitor_at_cursor(window)
+        .or_else(|| {
+            window
+                .app_handle()
+                .get_webview_window("main")
+                .and_then(|main_win| main_win.current_monitor().ok().flatten())
+        })
+        .or_else(|| window.primary_monitor().ok().flatten())
+}
+
+/// Resolves the `overlay_monitor` preference to a connected monitor. A named
+/// monitor that is not connected falls back to primary without touching the
+/// setting, so the overlay returns once the display is plugged back in.
+fn overlay_target_monitor(window: &WebviewWindow, preference: &str) -> Option<tauri::Monitor> {
+    match preference {
+        "" => cursor_or_fallback_monitor(window),
+        "primary" => window
+            .primary_monitor()
+            .ok()
+            .flatten()
+            .or_else(|| cursor_or_fallback_monitor(window)),
+        name => window
+            .available_monitors()
+            .ok()
+            .and_then(|monitors| {
+                monitors
+                    .into_iter()
+                    .find(|monitor| monitor.name().map(String::as_str) == Some(name))
+            })
+            .or_else(|| window.primary_monitor().ok().flatten())
+            .or_else(|| cursor_or_fallback_monitor(window)),
+    }
+}
+
+fn desired_monitor_preference(app: &AppHandle) -> String {
+    with_overlay_controller(app, |controller| {
+        controller
+            .desired_settings
+            .as_ref()
+            .map(|settings| settings.monitor.clone())
+            .unwrap_or_default()
+    })
+}
+
+fn monitor_topology(window: &WebviewWindow) -> Option<MonitorTopology> {
+    let mut topology: MonitorTopology = window
+        .available_monitors()
+        .ok()?
+        .iter()
+        .map(|monitor| {
+            let pos = monitor.position();
+            let size = monitor.size();
+            (
+                monitor.name().cloned(),
+                pos.x,
+                pos.y,
+                size.width,
+                size.height,
+            )
+        })
+        .collect();
+    topology.sort();
+    Some(topology)
+}
+
+/// Stores `current`; true when a previously seen topology differed.
+fn record_monitor_topology(last: &mut Option<MonitorTopology>, current: MonitorTopology) -> bool {
+    let changed = last.as_ref().is_some_and(|previous| *previous != current);
+    *last = Some(current);
+    changed
+}
+
+/// True when displays were added, removed or rearranged since the last check.
+/// Emits `overlay:monitors-changed` so the settings picker can refresh.
+fn monitor_topology_changed(window: &WebviewWindow) -> bool {
+    let Some(current) = monitor_topology(window) else {
+        return false;
+    };
+    let changed = {
+        let mut last = LAST_MONITOR_TOPOLOGY
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        record_monitor_topology(&mut last, current)
+    };
+    if changed {
+        info!("[overlay:monitors] display topology changed, re-resolving position");
+        let _ = window.app_handle().emit("overlay:monitors-changed", ());
+    }
+    changed
+}
+
+/// Lists connected displays for the overlay monitor picker.
+#[tauri::command]
+pub fn list_overlay_monitors(app: AppHandle) -> CommandResult<Vec<OverlayMonitorInfo>> {
+    let primary_origin = app
+        .primary_monitor()
+        .ok()
+        .flatten()
+        .map(|monitor| (monitor.position().x, monitor.position().y));
+    let monitors = app.available_monitors().map_err(|e| {
+        CommandError::new(ErrorCode::Window, format!("Failed to list monitors: {}", e))
+    })?;
+    Ok(monitors
+        .iter()
+        .enumerate()
+        .map(|(index, monitor)| {
+            let pos = monitor.position();
+            let size = monitor.size();
+            OverlayMonitorInfo {
+                name: monitor
+                    .name()
+                    .cloned()
+                    .unwrap_or_else(|| format!("Display {}", index + 1)),
+                x: pos.x,
+                y: pos.y,
+                width: size.width,
+                height: size.height,
+                scale_factor: monitor.scale_factor(),
+                primary: primary_origin == Some((pos.x, pos.y)),
+            }
+        })
+        .collect())
+}
+
 fn spawn_monitor_follow_task(app: AppHandle) {
     if MONITOR_FOLLOW_ACTIVE.swap(true, Ordering::SeqCst) {
         return;
@@ -158,20 +400,34 @@ fn spawn_monitor_follow_task(app: AppHandle) {
                 let Some(window) = app.get_webview_window("overlay") else {
                     continue;
                 };
-                let Some(cursor_monitor) = monitor_at_cursor(&window) else {
+                let controller = overlay_controller_snapshot(&app);
+                if controller.move_mode {
+                    // The user is placing the overlay by hand.
                     continue;
-                };
-                let origin = cursor_monitor.position();
-                let current_origin = (origin.x, origin.y);
-                if last_monitor_origin == Some(current_origin) {
+                }
+                let topology_changed = monitor_topology_changed(&window);
+                let follows_cursor = controller
+                    .desired_settings
+                    .as_ref()
+                    .map_or(true, |settings| settings.monitor.is_empty());
+                let mut cursor_moved = false;
+                if follows_cursor {
+                    if let Some(cursor_monitor) = monitor_at_cursor(&window) {
+                        let origin = cursor_monitor.position();
+                        let current_origin = (origin.x, origin.y);
+                        if last_monitor_origin != Some(current_origin) {
+                            info!(
+                                "[overlay:monitor_follow] cursor moved to monitor at ({}, {})",
+                                origin.x, origin.y
+                            );
+                            last_monitor_origin = Some(current_origin);
+                            cursor_moved = true;
+                        }
+                    }
+                }
+                if !topology_changed && !cursor_moved {
                     continue;
                 }
-                info!(
-                    "[overlay:monitor_follow] cursor moved to monitor at ({}, {})",
-                    origin.x, origin.y
-                );
-                last_monitor_origin = Some(current_origin);
-                let controller = overlay_controller_snapshot(&app);
                 if let Some(settings) = controller.desired_settings {
                     let app_inner = app.clone();
                     let _ = app.run_on_main_thread(move || {
@@ -290,7 +546,9 @@ pub fn mark_overlay_ready(app: &AppHandle) {
 }
 
 pub fn idle_overlay_state_for_settings(settings: &Settings) -> OverlayState {
-    if settings.capture_enabled {
+    if crate::privacy_mute::is_muted() {
+        OverlayState::Muted
+    } else if settings.capture_enabled {
         OverlayState::Armed
     } else {
         OverlayState::Hidden
@@ -416,15 +674,8 @@ pub fn create_overlay_window(app: &AppHandle) -> Result<WebviewWindow, String> {
 }
 
 fn window_position_invalid(window: &WebviewWindow) -> bool {
-    let Some(monitor) = monitor_at_cursor(window)
-        .or_else(|| {
-            window
-                .app_handle()
-                .get_webview_window("main")
-                .and_then(|main_win| main_win.current_monitor().ok().flatten())
-        })
-        .or_else(|| window.primary_monitor().ok().flatten())
-    else {
+    let preference = desired_monitor_preference(window.app_handle());
+    let Some(monitor) = overlay_target_monitor(window, &preference) else {
         return false;
     };
 
@@ -480,13 +731,7 @@ fn fallback_overlay_to_safe_anchor(window: &WebviewWindow) -> Result<(), String>
     }
 
     info!("[overlay:fallback_anchor] no desired_settings, using 50/50 hard-coded anchor");
-    let Some(monitor) = monitor_at_cursor(window)
-        .or_else(|| {
-            app.get_webview_window("main")
-                .and_then(|main_win| main_win.current_monitor().ok().flatten())
-        })
-        .or_else(|| window.primary_monitor().ok().flatten())
-    else {
+    let Some(monitor) = cursor_or_fallback_monitor(window) else {
         return Ok(());
     };
 
@@ -539,15 +784,18 @@ fn apply_overlay_state_to_window(
     let _ = window.emit("overlay:state", &state_clone);
     let _ = app.emit("overlay:state", &state_clone);
 
-    let should_show = !matches!(state_clone, OverlayState::Hidden) || controller.tts_stop_visible;
+    let should_show = !matches!(state_clone, OverlayState::Hidden)
+        || controller.tts_stop_visible
+        || controller.move_mode;
     if should_show {
         // Defensive: if the window is still parked off-screen (apply_overlay_settings
-        // failed or hasn't run yet), re-apply cached settings before showing.
+        // failed or hasn't run yet) or displays changed while it was hidden,
+        // re-apply cached settings before showing.
         let still_offscreen = window
             .outer_position()
             .map(|pos| pos.x < -5000 || pos.y < -5000)
             .unwrap_or(false);
-        if still_offscreen {
+        if still_offscreen || monitor_topology_changed(window) {
             let controller = overlay_controller_snapshot(app);
             if let Some(ref settings) = controller.desired_settings {
                 let _ = apply_overlay_settings_to_window(window, settings);
@@ -575,7 +823,7 @@ fn apply_overlay_state_to_window(
     } else {
         let _ = window.hide();
     }
-    let _ = window.set_ignore_cursor_events(!controller.tts_stop_visible);
+    let _ = window.set_ignore_cursor_events(!(controller.tts_stop_visible || controller.move_mode));
 
     let js = overlay_state_eval_js(&state_clone);
     let _ = window.eval(&js);
@@ -644,7 +892,8 @@ fn apply_overlay_tts_stop_to_window(
         } else {
             let _ = window.hide();
         }
-        let _ = window.set_ignore_cursor_events(!effective_active);
+        let move_mode = overlay_controller_snapshot(app).move_mode;
+        let _ = window.set_ignore_cursor_events(!(effective_active || move_mode));
         let js = format!(
             "if(window.setOverlayTtsStopVisible){{window.setOverlayTtsStopVisible({}, {}, {});}}",
             if effective_active { "true" } else { "false" },
@@ -825,15 +1074,37 @@ fn schedule_overlay_window_creation(app: &AppHandle, reason: &str) {
 /// so callers on background threads (e.g. PTT hotkey thread) are not stalled
 /// by Win32 SendMessage waiting for the main thread to process the message.
 pub fn update_overlay_state(app: &AppHandle, state: OverlayState) -> Result<(), String> {
-    with_overlay_controller(app, |controller| {
+    let held = with_overlay_controller(app, |controller| {
+        match state {
+            OverlayState::Recording => {
+                controller.error_until_ms = 0;
+                controller.state_after_error = None;
+            }
+            OverlayState::Error => {}
+            _ if controller.error_until_ms > now_ms() => {
+                controller.state_after_error = Some(state.clone());
+                return true;
+            }
+            _ => {}
+        }
         controller.desired_state = state.clone();
         if !matches!(state, OverlayState::Recording) {
             controller.last_level = 0.0;
         }
+        false
     });
+    if held {
+        return Ok(());
+    }
     update_monitor_follow(app);
     let Some(window) = app.get_webview_window("overlay") else {
-        if matches!(state, OverlayState::Recording | OverlayState::Transcribing) {
+        if matches!(
+            state,
+            OverlayState::Recording
+                | OverlayState::Transcribing
+                | OverlayState::CloudTranscribing
+                | OverlayState::Error
+        ) {
             schedule_overlay_window_creation(app, "state_update");
         }
         return Ok(());
@@ -849,6 +1120,62 @@ pub fn update_overlay_state(app: &AppHandle, state: OverlayState) -> Result<(),
     Ok(())
 }
 
+/// Switches a running transcription between the local and cloud look.
+/// Does nothing unless the overlay is already showing a transcription, so
+/// imports and other background jobs never pop the overlay up.
+pub fn update_overlay_transcribe_engine(app: &AppHandle, cloud: bool) {
+    let transcribing = with_overlay_controller(app, |controller| {
+        matches!(
+            controller.desired_state,
+            OverlayState::Transcribing | OverlayState::CloudTranscribing
+        )
+    });
+    if !transcribing {
+        return;
+    }
+    let state = if cloud {
+        OverlayState::CloudTranscribing
+    } else {
+        OverlayState::Transcribing
+    };
+    let _ = update_overlay_state(app, state);
+}
+
+/// Shows the error state for a moment, then returns to the last state that
+/// was requested (e.g. a running loopback transcription). An active recording
+/// is left alone, and repeated failures flash only once per debounce window;
+/// the errors themselves surface via `app:error`.
+pub fn flash_overlay_error(app: &AppHandle) {
+    let now = now_ms();
+    let flash = with_overlay_controller(app, |controller| {
+        if matches!(controller.desired_state, OverlayState::Recording)
+            || now.saturating_sub(controller.last_error_flash_ms) < OVERLAY_ERROR_DEBOUNCE_MS
+        {
+            return false;
+        }
+        controller.last_error_flash_ms = now;
+        controller.error_until_ms = now + OVERLAY_ERROR_FLASH_MS;
+        controller.state_after_error = Some(controller.desired_state.clone());
+        true
+    });
+    if !flash {
+        return;
+    }
+    let _ = update_overlay_state(app, OverlayState::Error);
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        std::thread::sleep(Duration::from_millis(OVERLAY_ERROR_FLASH_MS));
+        // A recording started meanwhile already cleared the hold.
+        let resume = with_overlay_controller(&app_handle, |controller| {
+            controller.error_until_ms = 0;
+            controller.state_after_error.take()
+        });
+        if let Some(state) = resume {
+            let _ = update_overlay_state(&app_handle, state);
+        }
+    });
+}
+
 pub fn update_overlay_tts_stop_visibility(app: &AppHandle, active: bool) -> Result<(), String> {
     let controller = overlay_controller_snapshot(app);
     let effective_active = active
@@ -875,7 +1202,7 @@ pub fn update_overlay_tts_stop_visibility(app: &AppHandle, active: bool) -> Resu
     } else {
         let _ = window.hide();
     }
-    let _ = window.set_ignore_cursor_events(!effective_active);
+    let _ = window.set_ignore_cursor_events(!(effective_active || controller.move_mode));
     let js = format!(
         "if(window.setOverlayTtsStopVisible){{window.setOverlayTtsStopVisible({}, {}, {});}}",
         if effective_active { "true" } else { "false" },
@@ -968,7 +1295,7 @@ fn apply_overlay_ollama_state_to_window(app: &AppHandle, window: &WebviewWindow)
             .unwrap_or_else(|p| p.into_inner());
         if matches!(
             controller.desired_state,
-            OverlayState::Recording | OverlayState::Transcribing
+            OverlayState::Recording | OverlayState::Transcribing | OverlayState::CloudTranscribing
         ) && matches!(controller.ollama_model_state, OllamaModelState::Warm)
         {
             return;
@@ -1000,13 +1327,18 @@ fn apply_overlay_ollama_state_to_window(app: &AppHandle, window: &WebviewWindow)
 }
 
 pub fn sync_overlay_level(app: &AppHandle, level: f64) -> Result<(), String> {
-    let desired_state = with_overlay_controller(app, |controller| {
+    let (desired_state, frame) = with_overlay_controller(app, |controller| {
         if matches!(controller.desired_state, OverlayState::Recording) {
             controller.last_level = level.clamp(0.0, 1.0);
+            controller.level_history.push(level);
         } else {
             controller.last_level = 0.0;
+            controller.level_history.clear();
         }
-        controller.desired_state.clone()
+        (
+            controller.desired_state.clone(),
+            controller.level_history.ordered(),
+        )
     });
 
     let Some(window) = app.get_webview_window("overlay") else {
@@ -1020,11 +1352,17 @@ pub fn sync_overlay_level(app: &AppHandle, level: f64) -> Result<(), String> {
         if matches!(desired_state, OverlayState::Hidden) {
             return Ok(());
         }
+        if send_level_frame(frame) {
+            return Ok(());
+        }
         return apply_overlay_level_to_window(&window, 0.0);
     }
     if matches!(desired_state, OverlayState::Hidden) {
         return Ok(());
     }
+    if send_level_frame(frame) {
+        return Ok(());
+    }
     apply_overlay_level_to_window(&window, level)
 }
 
@@ -1037,15 +1375,7 @@ fn resolve_overlay_position(
     // pos_x and pos_y are stored as percentages (0-100)
     // Convert to absolute monitor coordinates, then to window position
 
-    let monitor = monitor_at_cursor(window)
-        .or_else(|| {
-            window
-                .app_handle()
-                .get_webview_window("main")
-                .and_then(|main_win| main_win.current_monitor().ok().flatten())
-        })
-        .or_else(|| window.primary_monitor().ok().flatten());
-    if let Some(monitor) = monitor {
+    if let Some(monitor) = overlay_target_monitor(window, &settings.monitor) {
         let scale = monitor.scale_factor();
         let size_px = monitor.size();
         let pos_px = monitor.position();
@@ -1076,15 +1406,8 @@ fn resolve_overlay_position(
     }
 }
 
-fn current_monitor_logical_size(window: &WebviewWindow) -> Option<(f64, f64)> {
-    let monitor = monitor_at_cursor(window)
-        .or_else(|| {
-            window
-                .app_handle()
-                .get_webview_window("main")
-                .and_then(|main_win| main_win.current_monitor().ok().flatten())
-        })
-        .or_else(|| window.primary_monitor().ok().flatten())?;
+fn current_monitor_logical_size(window: &WebviewWindow, preference: &str) -> Option<(f64, f64)> {
+    let monitor = overlay_target_monitor(window, preference)?;
     let scale = monitor.scale_factor();
     let size_px = monitor.size();
     Some((size_px.width as f64 / scale, size_px.height as f64 / scale))
@@ -1096,7 +1419,7 @@ fn resolve_effective_dimensions(
 ) -> (f64, f64, f64, f64, f64) {
     // Hard cap: overlays may consume at most 50% of the display.
     let (monitor_width, monitor_height) =
-        current_monitor_logical_size(window).unwrap_or((1920.0, 1080.0));
+        current_monitor_logical_size(window, &settings.monitor).unwrap_or((1920.0, 1080.0));
     let kitt_width_cap = (monitor_width * 0.5).max(50.0);
     let dot_radius_cap = (monitor_width.min(monitor_height) * 0.25).max(8.0); // 50% diameter
 
@@ -1143,7 +1466,7 @@ fn apply_overlay_settings_to_window(
 
     // Calculate window size based on style
     // Add extra height for transcribe indicator positioned above the main element
-    let (width, height) = if settings.style == "kitt" {
+    let (width, height) = if overlay_style_uses_bar(&settings.style) {
         let w = effective_kitt_max_width
             .max(effective_kitt_min_width)
             .max(50.0)
@@ -1260,6 +1583,9 @@ fn overlay_state_eval_js(state: &OverlayState) -> String {
         OverlayState::Armed => "armed",
         OverlayState::Recording => "recording",
         OverlayState::Transcribing => "transcribing",
+        OverlayState::CloudTranscribing => "cloud-transcribing",
+        OverlayState::Muted => "muted",
+        OverlayState::Error => "error",
     };
     if matches!(state, OverlayState::Recording) {
         format!(
@@ -1293,3 +1619,309 @@ pub fn set_overlay_position(app: &AppHandle, x: f64, y: f64) -> Result<(), Strin
         .set_position(tauri::Position::Logical(tauri::LogicalPosition { x, y }))
         .map_err(|e| format!("Failed to set overlay position: {}", e))
 }
+
+// --- Drag to move ---
+//
+// Move mode makes the overlay accept pointer input. The page reports
+// pointer down/up; while the button is held a Rust loop keeps the window
+// under the cursor. On release the window snaps to nearby edges of the
+// monitor it landed on and the position is saved as a percentage of that
+// monitor, into the dot or KITT/waveform position fields.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ScreenRect {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+/// Keeps `window` inside `monitor` and pulls edges within `threshold` flush
+/// against the monitor edge.
+fn snap_to_monitor_edges(window: ScreenRect, monitor: ScreenRect, threshold: f64) -> (f64, f64) {
+    let snap_axis = |pos: f64, size: f64, origin: f64, extent: f64| {
+        let max = origin + (extent - size).max(0.0);
+        let pos = pos.clamp(origin, max);
+        if pos - origin <= threshold {
+            origin
+        } else if max - pos <= threshold {
+            max
+        } else {
+            pos
+        }
+    };
+    (
+        snap_axis(window.x, window.width, monitor.x, monitor.width),
+        snap_axis(window.y, window.height, monitor.y, monitor.height),
+    )
+}
+
+/// Window centre as a percentage of the monitor, the format
+/// `resolve_overlay_position` reads back.
+fn overlay_position_percent(window: ScreenRect, monitor: ScreenRect) -> (f64, f64) {
+    let percent = |pos: f64, size: f64, origin: f64, extent: f64| {
+        if extent <= 0.0 {
+            return 50.0;
+        }
+        let centre = pos + size * 0.5 - origin;
+        ((centre / extent * 100.0).clamp(0.0, 100.0) * 10.0).round() / 10.0
+    };
+    (
+        percent(window.x, window.width, monitor.x, monitor.width),
+        percent(window.y, window.height, monitor.y, monitor.height),
+    )
+}
+
+/// Enters or leaves move mode. Leaving it restores the normal click-through
+/// behaviour and visibility for the current overlay state.
+#[tauri::command]
+pub fn overlay_set_move_mode(app: AppHandle, enabled: bool) -> CommandResult<()> {
+    let desired_state = with_overlay_controller(&app, |controller| {
+        controller.move_mode = enabled;
+        controller.desired_state.clone()
+    });
+    if !enabled {
+        OVERLAY_DRAG_ACTIVE.store(false, Ordering::Release);
+    }
+    let Some(window) = app.get_webview_window("overlay") else {
+        if enabled {
+            with_overlay_controller(&app, |controller| controller.move_mode = false);
+            schedule_overlay_window_creation(&app, "move_mode");
+            return Err(CommandError::new(
+                ErrorCode::Window,
+                "Overlay window is not ready yet; try again in a moment",
+            ));
+        }
+        return Ok(());
+    };
+    let _ = window.eval(format!(
+        "if(window.setOverlayMoveMode){{window.setOverlayMoveMode({});}}",
+        enabled
+    ));
+    let _ = app.emit("overlay:move-mode", enabled);
+    update_overlay_state(&app, desired_state).with_code(ErrorCode::Window)
+}
+
+/// Pointer pressed on the overlay in move mode: follow the cursor.
+#[tauri::command]
+pub fn overlay_drag_start(app: AppHandle) -> CommandResult<()> {
+    if !overlay_controller_snapshot(&app).move_mode {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "Overlay is not in move mode",
+        ));
+    }
+    let window = app
+        .get_webview_window("overlay")
+        .ok_or_else(|| CommandError::new(ErrorCode::Window, "Overlay window not found"))?;
+    let cursor = app.cursor_position().map_err(|e| {
+        CommandError::new(
+            ErrorCode::Window,
+            format!("Failed to read cursor position: {}", e),
+        )
+    })?;
+    let origin = window.outer_position().map_err(|e| {
+        CommandError::new(
+            ErrorCode::Window,
+            format!("Failed to read overlay position: {}", e),
+        )
+    })?;
+    let grab = (cursor.x - origin.x as f64, cursor.y - origin.y as f64);
+    if OVERLAY_DRAG_ACTIVE.swap(true, Ordering::AcqRel) {
+        return Ok(());
+    }
+    crate::util::spawn_guarded("overlay-drag", move || {
+        while OVERLAY_DRAG_ACTIVE.load(Ordering::Acquire) {
+            if let Ok(cursor) = app.cursor_position() {
+                let _ = window.set_position(tauri::PhysicalPosition::new(
+                    (cursor.x - grab.0).round() as i32,
+                    (cursor.y - grab.1).round() as i32,
+                ));
+            }
+            std::thread::sleep(Duration::from_millis(OVERLAY_DRAG_TICK_MS));
+        }
+    });
+    Ok(())
+}
+
+/// Pointer released: snap, persist the position and leave move mode.
+#[tauri::command]
+pub fn overlay_drag_end(app: AppHandle) -> CommandResult<()> {
+    if !OVERLAY_DRAG_ACTIVE.swap(false, Ordering::AcqRel) {
+        return Ok(());
+    }
+    let window = app
+        .get_webview_window("overlay")
+        .ok_or_else(|| CommandError::new(ErrorCode::Window, "Overlay window not found"))?;
+    let position = window.outer_position().map_err(|e| {
+        CommandError::new(
+            ErrorCode::Window,
+            format!("Failed to read overlay position: {}", e),
+        )
+    })?;
+    let size = window.outer_size().map_err(|e| {
+        CommandError::new(
+            ErrorCode::Window,
+            format!("Failed to read overlay size: {}", e),
+        )
+    })?;
+    let monitor = window
+        .current_monitor()
+        .ok()
+        .flatten()
+        .or_else(|| window.primary_monitor().ok().flatten())
+        .ok_or_else(|| CommandError::new(ErrorCode::Window, "No monitor found for overlay"))?;
+    let monitor_rect = ScreenRect {
+        x: monitor.position().x as f64,
+        y: monitor.position().y as f64,
+        width: monitor.size().width as f64,
+        height: monitor.size().height as f64,
+    };
+    let mut window_rect = ScreenRect {
+        x: position.x as f64,
+        y: position.y as f64,
+        width: size.width as f64,
+        height: size.height as f64,
+    };
+    let (x, y) = snap_to_monitor_edges(
+        window_rect,
+        monitor_rect,
+        OVERLAY_SNAP_DISTANCE * monitor.scale_factor(),
+    );
+    window_rect.x = x;
+    window_rect.y = y;
+    let _ = window.set_position(tauri::PhysicalPosition::new(
+        x.round() as i32,
+        y.round() as i32,
+    ));
+    let (pos_x, pos_y) = overlay_position_percent(window_rect, monitor_rect);
+    let dropped_on = monitor.name().cloned();
+
+    let state = app.state::<AppState>();
+    let snapshot = {
+        let mut settings = state
+            .settings
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if overlay_style_uses_bar(&settings.overlay_style) {
+            settings.overlay_kitt_pos_x = pos_x;
+            settings.overlay_kitt_pos_y = pos_y;
+        } else {
+            settings.overlay_pos_x = pos_x;
+            settings.overlay_pos_y = pos_y;
+        }
+        // A pinned overlay dragged onto another display stays on that one.
+        if !settings.overlay_monitor.is_empty() {
+            if let Some(name) = dropped_on {
+                settings.overlay_monitor = name;
+            }
+        }
+        settings.clone()
+    };
+    with_overlay_controller(&app, |controller| {
+        if let Some(settings) = controller.desired_settings.as_mut() {
+            settings.pos_x = pos_x;
+            settings.pos_y = pos_y;
+            settings.monitor = snapshot.overlay_monitor.clone();
+        }
+    });
+    crate::state::save_settings_file(&app, &snapshot).with_code(ErrorCode::Storage)?;
+    let _ = app.emit("settings-changed", snapshot);
+    info!("[overlay:drag] saved position {:.1}%, {:.1}%", pos_x, pos_y);
+    overlay_set_move_mode(app, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn level_history_keeps_latest_levels_in_order() {
+        let mut history = LevelHistory::default();
+        for i in 0..(OVERLAY_LEVEL_HISTORY_LEN + 3) {
+            history.push(if i % 2 == 0 { 0.0 } else { 1.0 });
+        }
+        history.push(2.0);
+        let frame = history.ordered();
+        assert_eq!(frame.len(), OVERLAY_LEVEL_HISTORY_LEN);
+        assert_eq!(frame.last(), Some(&255));
+        assert_eq!(frame[frame.len() - 2], 0);
+        history.clear();
+        assert!(history.ordered().is_empty());
+    }
+
+    #[test]
+    fn new_overlay_states_use_the_same_name_in_events_and_eval() {
+        for (state, name) in [
+            (OverlayState::CloudTranscribing, "cloud-transcribing"),
+            (OverlayState::Error, "error"),
+        ] {
+            assert_eq!(
+                serde_json::to_value(&state).unwrap(),
+                serde_json::json!(name)
+            );
+            assert!(overlay_state_eval_js(&state).contains(&format!("'{}'", name)));
+        }
+    }
+
+    #[test]
+    fn monitor_topology_change_is_reported_after_first_observation() {
+        let laptop = (Some("Built-in".to_string()), 0, 0, 2560, 1600);
+        let dock = (Some("DELL U2720Q".to_string()), 2560, 0, 3840, 2160);
+        let mut last = None;
+        assert!(!record_monitor_topology(
+            &mut last,
+            vec![laptop.clone(), dock.clone()]
+        ));
+        assert!(!record_monitor_topology(
+            &mut last,
+            vec![laptop.clone(), dock]
+        ));
+        // Undocking drops the external display.
+        assert!(record_monitor_topology(&mut last, vec![laptop.clone()]));
+        assert!(!record_monitor_topology(&mut last, vec![laptop]));
+    }
+
+    #[test]
+    fn dropped_overlay_snaps_to_near_edges_and_stays_on_monitor() {
+        let monitor = ScreenRect {
+            x: 1920.0,
+            y: 0.0,
+            width: 1920.0,
+            height: 1080.0,
+        };
+        let near_left = ScreenRect {
+            x: 1930.0,
+            y: 500.0,
+            width: 100.0,
+            height: 60.0,
+        };
+        assert_eq!(
+            snap_to_monitor_edges(near_left, monitor, 24.0),
+            (1920.0, 500.0)
+        );
+        let past_bottom = ScreenRect {
+            y: 1070.0,
+            ..near_left
+        };
+        assert_eq!(
+            snap_to_monitor_edges(past_bottom, monitor, 24.0),
+            (1920.0, 1020.0)
+        );
+
+        let centred = ScreenRect {
+            x: 1920.0 + 910.0,
+            y: 1020.0,
+            width: 100.0,
+            height: 60.0,
+        };
+        assert_eq!(overlay_position_percent(centred, monitor), (50.0, 97.2));
+    }
+
+    #[test]
+    fn bar_styles_share_kitt_geometry() {
+        assert!(overlay_style_uses_bar("kitt"));
+        assert!(overlay_style_uses_bar("waveform"));
+        assert!(!overlay_style_uses_bar("dot"));
+    }
+}
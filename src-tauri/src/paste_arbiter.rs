@@ -17,7 +17,7 @@ use serde::Serialize;
 use tauri::{AppHandle, Emitter, Manager};
 use tracing::{info, warn};
 
-use crate::state::AppState;
+use crate::state::{AppState, Settings};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -32,8 +32,52 @@ pub(crate) enum PasteOutcome {
     RawTimeout,
 }
 
+/// How a settled job's text reaches the focused application.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum PasteDelivery {
+    /// Clipboard + paste keystroke; the previous clipboard is restored.
+    Paste,
+    /// Left on the clipboard for the user to paste.
+    Clipboard,
+    /// Typed as keystrokes, for targets that block clipboard pastes.
+    Type,
+    /// Inserted through the accessibility API; pastes when that fails.
+    Inject,
+    /// Not inserted anywhere; the transcript only lands in history.
+    Skip,
+}
+
+impl PasteDelivery {
+    pub(crate) fn from_settings(settings: &Settings) -> Self {
+        if !settings.auto_paste_enabled {
+            return Self::Skip;
+        }
+        match settings.output_mode.as_str() {
+            "clipboard" => Self::Clipboard,
+            "type" => Self::Type,
+            "inject" => Self::Inject,
+            _ => Self::Paste,
+        }
+    }
+
+    fn deliver(self, app_handle: &AppHandle, text: &str) -> Result<(), String> {
+        match self {
+            Self::Paste => crate::paste_text(app_handle, text),
+            Self::Clipboard => crate::set_clipboard_text_with_retry(text),
+            Self::Type => {
+                crate::type_text(app_handle, text);
+                Ok(())
+            }
+            Self::Inject => crate::text_injection::insert_text(app_handle, text),
+            Self::Skip => Ok(()),
+        }
+    }
+}
+
 struct PendingJob {
     raw_text: String,
+    delivery: PasteDelivery,
 }
 
 #[derive(Default)]
@@ -46,12 +90,12 @@ pub(crate) struct PasteArbiter {
 
 impl PasteArbiter {
     /// Register a job's raw text before any settle source can fire.
-    pub(crate) fn register(&self, job_id: &str, raw_text: String) {
+    pub(crate) fn register(&self, job_id: &str, raw_text: String, delivery: PasteDelivery) {
         let mut jobs = self
             .jobs
             .lock()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
-        jobs.insert(job_id.to_string(), PendingJob { raw_text });
+        jobs.insert(job_id.to_string(), PendingJob { raw_text, delivery });
     }
 
     /// Atomically claim the job and paste. Returns `true` if this call won
@@ -76,21 +120,25 @@ impl PasteArbiter {
         };
 
         let text = text_override.unwrap_or(&job.raw_text);
-        let paste_error = if text.trim().is_empty() {
+        let paste_error = if text.trim().is_empty() || job.delivery == PasteDelivery::Skip {
             None
         } else {
             let _order = self
                 .paste_order
                 .lock()
                 .unwrap_or_else(|poisoned| poisoned.into_inner());
-            crate::paste_text(app_handle, text).err()
+            job.delivery.deliver(app_handle, text).err()
         };
 
         if let Some(err) = &paste_error {
             warn!("[paste_arbiter:{job_id}] paste failed outcome={outcome:?}: {err}");
         } else {
+            if !text.trim().is_empty() && job.delivery != PasteDelivery::Skip {
+                crate::audio_cues::play_cue(app_handle, "paste");
+            }
             info!(
-                "[paste_arbiter:{job_id}] settled outcome={outcome:?} bytes={}",
+                "[paste_arbiter:{job_id}] settled outcome={outcome:?} delivery={:?} bytes={}",
+                job.delivery,
                 text.len()
             );
         }
@@ -99,6 +147,7 @@ impl PasteArbiter {
             serde_json::json!({
                 "job_id": job_id,
                 "outcome": outcome,
+                "delivery": job.delivery,
                 "text": text,
                 "paste_error": paste_error,
             }),
@@ -142,7 +191,7 @@ mod tests {
     #[test]
     fn first_claim_wins_second_is_noop() {
         let arbiter = PasteArbiter::default();
-        arbiter.register("job-1", "raw text".to_string());
+        arbiter.register("job-1", "raw text".to_string(), PasteDelivery::Paste);
         let first = {
             let mut jobs = arbiter.jobs.lock().unwrap();
             jobs.remove("job-1")
@@ -159,12 +208,32 @@ mod tests {
     #[test]
     fn register_overwrites_previous_job_with_same_id() {
         let arbiter = PasteArbiter::default();
-        arbiter.register("job-1", "old".to_string());
-        arbiter.register("job-1", "new".to_string());
+        arbiter.register("job-1", "old".to_string(), PasteDelivery::Paste);
+        arbiter.register("job-1", "new".to_string(), PasteDelivery::Skip);
         let job = {
             let mut jobs = arbiter.jobs.lock().unwrap();
             jobs.remove("job-1")
         };
-        assert_eq!(job.unwrap().raw_text, "new");
+        let job = job.unwrap();
+        assert_eq!(job.raw_text, "new");
+        assert_eq!(job.delivery, PasteDelivery::Skip);
+    }
+
+    #[test]
+    fn delivery_follows_auto_paste_and_output_mode() {
+        let mut settings = Settings::default();
+        assert_eq!(
+            PasteDelivery::from_settings(&settings),
+            PasteDelivery::Paste
+        );
+        settings.output_mode = "type".to_string();
+        assert_eq!(PasteDelivery::from_settings(&settings), PasteDelivery::Type);
+        settings.output_mode = "inject".to_string();
+        assert_eq!(
+            PasteDelivery::from_settings(&settings),
+            PasteDelivery::Inject
+        );
+        settings.auto_paste_enabled = false;
+        assert_eq!(PasteDelivery::from_settings(&settings), PasteDelivery::Skip);
     }
 }
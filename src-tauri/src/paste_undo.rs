@@ -0,0 +1,190 @@
+//! Undo of the last dictation paste: removes the inserted text from the
+//! window it went into and puts the previous clipboard text back.
+//!
+//! `paste_text` / `type_text` record every insertion. Undo only acts while
+//! that window still has focus, so a stale record never deletes text
+//! somewhere else. Where the foreground window cannot be queried (everything
+//! but Windows for now) undo is refused. The previous clipboard text is only
+//! put back while the clipboard still holds the pasted text, so a copy made
+//! after the paste is never overwritten.
+
+use std::sync::Mutex;
+
+use enigo::{Enigo, Key, KeyboardControllable};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::errors::{CommandError, CommandResult, ErrorCode};
+use crate::state::AppState;
+
+/// Older pastes are not undone; the user has moved on.
+const UNDO_MAX_AGE_MS: u64 = 5 * 60_000;
+
+struct LastPaste {
+    text: String,
+    /// Foreground window at paste time (Windows only).
+    window: Option<isize>,
+    /// Clipboard text before the paste, when it held text.
+    clipboard_before: Option<String>,
+    timestamp_ms: u64,
+}
+
+static LAST_PASTE: Mutex<Option<LastPaste>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct UndoPasteResult {
+    /// Characters removed from the target window.
+    pub(crate) chars_removed: usize,
+    pub(crate) clipboard_restored: bool,
+}
+
+#[cfg(target_os = "windows")]
+fn foreground_window() -> Option<isize> {
+    use windows::Win32::UI::WindowsAndMessaging::GetForegroundWindow;
+
+    // SAFETY: plain Win32 query without arguments.
+    let hwnd = unsafe { GetForegroundWindow() };
+    (!hwnd.0.is_null()).then_some(hwnd.0 as isize)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn foreground_window() -> Option<isize> {
+    None
+}
+
+/// Remember an insertion of `text`; `clipboard_before` is `None` for typed
+/// text, which leaves the clipboard alone.
+pub(crate) fn record_paste(text: &str, clipboard_before: Option<String>) {
+    if text.is_empty() {
+        return;
+    }
+    *LAST_PASTE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(LastPaste {
+        text: text.to_string(),
+        window: foreground_window(),
+        clipboard_before,
+        timestamp_ms: crate::util::now_ms(),
+    });
+}
+
+/// Keystrokes needed to remove `text` right after it was inserted: one per
+/// character, with a CRLF line break counting once.
+fn deletion_keystrokes(text: &str) -> usize {
+    text.chars().count() - text.matches("\r\n").count()
+}
+
+fn send_deletion(count: usize, select_first: bool) {
+    let mut enigo = Enigo::new();
+    // The undo hotkey's modifiers may still be down; Ctrl+Backspace would
+    // delete whole words.
+    for modifier in [Key::Control, Key::Alt, Key::Shift, Key::Meta] {
+        enigo.key_up(modifier);
+    }
+    if select_first {
+        enigo.key_down(Key::Shift);
+        for _ in 0..count {
+            enigo.key_click(Key::LeftArrow);
+        }
+        enigo.key_up(Key::Shift);
+        enigo.key_click(Key::Backspace);
+    } else {
+        for _ in 0..count {
+            enigo.key_click(Key::Backspace);
+        }
+    }
+}
+
+pub(crate) fn undo_last_paste_inner(app: &AppHandle) -> CommandResult<UndoPasteResult> {
+    let mut guard = LAST_PASTE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let Some(last) = guard.as_ref() else {
+        return Err(CommandError::new(ErrorCode::NotFound, "Nothing to undo"));
+    };
+    if crate::util::now_ms().saturating_sub(last.timestamp_ms) > UNDO_MAX_AGE_MS {
+        *guard = None;
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "The last paste is too old to undo",
+        ));
+    }
+    // Without a recorded window there is no way to tell where the
+    // keystrokes would land, so undo is refused rather than guessed.
+    let Some(window) = last.window else {
+        return Err(CommandError::new(
+            ErrorCode::Window,
+            "Undo is not available: the target window cannot be verified",
+        ));
+    };
+    if foreground_window() != Some(window) {
+        return Err(CommandError::new(
+            ErrorCode::Window,
+            "The window the text was pasted into is no longer focused",
+        ));
+    }
+    let Some(last) = guard.take() else {
+        return Err(CommandError::new(ErrorCode::NotFound, "Nothing to undo"));
+    };
+    drop(guard);
+
+    let select_first = app
+        .state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .undo_paste_mode
+        == "select";
+    let chars_removed = deletion_keystrokes(&last.text);
+    send_deletion(chars_removed, select_first);
+
+    // Only while the pasted text is still on the clipboard: the delayed
+    // restore after the paste usually put the full previous content back.
+    let clipboard_holds_paste = arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_text())
+        .is_ok_and(|current| current.replace("\r\n", "\n") == last.text.replace("\r\n", "\n"));
+    let clipboard_restored = match &last.clipboard_before {
+        Some(previous) if clipboard_holds_paste => {
+            match crate::set_clipboard_text_with_retry(previous) {
+                Ok(()) => true,
+                Err(err) => {
+                    tracing::warn!("Clipboard restore after undo failed: {}", err);
+                    false
+                }
+            }
+        }
+        _ => false,
+    };
+    let result = UndoPasteResult {
+        chars_removed,
+        clipboard_restored,
+    };
+    let _ = app.emit("paste:undone", &result);
+    Ok(result)
+}
+
+/// Remove the last pasted or typed dictation from the focused window.
+#[tauri::command]
+pub(crate) async fn undo_last_paste(app: AppHandle) -> CommandResult<UndoPasteResult> {
+    tauri::async_runtime::spawn_blocking(move || undo_last_paste_inner(&app))
+        .await
+        .map_err(|e| {
+            CommandError::new(
+                ErrorCode::Internal,
+                format!("undo_last_paste task failed: {}", e),
+            )
+        })?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_one_keystroke_per_character_and_line_break() {
+        assert_eq!(deletion_keystrokes("Hello world."), 12);
+        assert_eq!(deletion_keystrokes("Grüße\r\nSam"), 9);
+        assert_eq!(deletion_keystrokes("a\nb"), 3);
+        assert_eq!(deletion_keystrokes("👍 ok"), 4);
+    }
+}
@@ -1,8 +1,17 @@
+use crate::errors::{CommandError, CommandResult, ErrorCode};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use tauri::{AppHandle, Manager};
 use tracing::warn;
 
+/// Root of runtimes installed by `whisper_runtime` (`<base>/whisper-runtime`),
+/// set once at startup so the binary resolvers below need no `AppHandle`.
+static MANAGED_RUNTIME_DIR: OnceLock<PathBuf> = OnceLock::new();
+/// Where downloaded Piper voices go (`<base>/piper/voices`), set alongside
+/// the runtime dir.
+static MANAGED_PIPER_VOICES_DIR: OnceLock<PathBuf> = OnceLock::new();
+
 /// Returns the single canonical base directory for all Trispr Flow data.
 ///
 /// Windows default: `%LOCALAPPDATA%\Trispr Flow\`
@@ -47,11 +56,23 @@ pub(crate) fn resolve_data_path(app: &AppHandle, filename: &str) -> PathBuf {
     base.join(filename)
 }
 
+pub(crate) fn init_managed_runtime_dir(app: &AppHandle) {
+    let base = resolve_base_dir(app);
+    let _ = MANAGED_PIPER_VOICES_DIR.set(base.join("piper").join("voices"));
+    let _ = MANAGED_RUNTIME_DIR.set(base.join("whisper-runtime"));
+}
+
+pub(crate) fn managed_runtime_dir() -> Option<&'static Path> {
+    MANAGED_RUNTIME_DIR.get().map(PathBuf::as_path)
+}
+
+pub(crate) fn managed_piper_voices_dir() -> Option<&'static Path> {
+    MANAGED_PIPER_VOICES_DIR.get().map(PathBuf::as_path)
+}
+
 #[tauri::command]
-pub(crate) fn open_log_directory() -> Result<(), String> {
-    let log_dir = std::env::var("LOCALAPPDATA")
-        .map(|d| PathBuf::from(d).join("Trispr Flow").join("logs"))
-        .map_err(|e| e.to_string())?;
+pub(crate) fn open_log_directory() -> CommandResult<()> {
+    let log_dir = crate::logging::resolve_log_dir();
 
     #[cfg(target_os = "windows")]
     {
@@ -59,17 +80,24 @@ pub(crate) fn open_log_directory() -> Result<(), String> {
         Command::new("explorer.exe")
             .arg(&log_dir)
             .spawn()
-            .map_err(|e| format!("Failed to open log directory: {}", e))?;
+            .map_err(|e| {
+                CommandError::new(
+                    ErrorCode::Window,
+                    format!("Failed to open log directory: {}", e),
+                )
+            })?;
         Ok(())
     }
 
     #[cfg(not(target_os = "windows"))]
     {
         use std::process::Command;
-        Command::new("open")
-            .arg(&log_dir)
-            .spawn()
-            .map_err(|e| format!("Failed to open log directory: {}", e))?;
+        Command::new("open").arg(&log_dir).spawn().map_err(|e| {
+            CommandError::new(
+                ErrorCode::Window,
+                format!("Failed to open log directory: {}", e),
+            )
+        })?;
         Ok(())
     }
 }
@@ -119,6 +147,13 @@ pub(crate) fn resolve_recordings_dir(app: &AppHandle) -> PathBuf {
     dir
 }
 
+/// Audio of segments whose transcription failed (see `failed_segments`).
+pub(crate) fn resolve_failed_segments_dir(app: &AppHandle) -> PathBuf {
+    let dir = resolve_base_dir(app).join("failed");
+    let _ = fs::create_dir_all(&dir);
+    dir
+}
+
 pub(crate) fn resolve_video_output_dir(app: &AppHandle) -> PathBuf {
     let dir = resolve_base_dir(app).join("videos");
     let _ = fs::create_dir_all(&dir);
@@ -221,6 +256,46 @@ pub(crate) fn resolve_hyperframes_cwd(app: &AppHandle) -> Option<PathBuf> {
     None
 }
 
+/// Bytes available to the current user on the volume holding `path`.
+/// Returns `None` when the platform query fails.
+#[cfg(unix)]
+pub(crate) fn available_disk_space(path: &Path) -> Option<u64> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stats: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: c_path is NUL-terminated and stats is a valid out-pointer.
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stats) } != 0 {
+        return None;
+    }
+    Some((stats.f_bavail as u64).saturating_mul(stats.f_frsize as u64))
+}
+
+#[cfg(windows)]
+pub(crate) fn available_disk_space(path: &Path) -> Option<u64> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    let mut available: u64 = 0;
+    // SAFETY: wide is NUL-terminated; unused out-pointers may be null.
+    let ok = unsafe {
+        GetDiskFreeSpaceExW(
+            wide.as_ptr(),
+            &mut available,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        )
+    };
+    (ok != 0).then_some(available)
+}
+
+#[cfg(not(any(unix, windows)))]
+pub(crate) fn available_disk_space(_path: &Path) -> Option<u64> {
+    None
+}
+
 pub(crate) fn resolve_models_dir(app: &AppHandle) -> PathBuf {
     if let Ok(dir) = std::env::var("TRISPR_WHISPER_MODEL_DIR") {
         let trimmed = dir.trim();
@@ -247,15 +322,48 @@ fn normalize_backend_preference(preference: Option<&str>) -> &'static str {
     match env_override.or(explicit).as_deref() {
         Some("cuda") => "cuda",
         Some("vulkan") => "vulkan",
+        Some("cpu") => "cpu",
         _ => "auto",
     }
 }
 
-fn preferred_backend_order(preference: Option<&str>) -> [&'static str; 2] {
+/// Runtime folders under `bin/` to search first. "cpu" only looks at
+/// `bin/cpu`; the flat layout after the backend folders is a CPU build too.
+fn preferred_backend_order(preference: Option<&str>) -> &'static [&'static str] {
     match normalize_backend_preference(preference) {
-        "vulkan" => ["vulkan", "cuda"],
-        _ => ["cuda", "vulkan"],
+        "vulkan" => &["vulkan", "cuda"],
+        "cpu" => &["cpu"],
+        _ => &["cuda", "vulkan"],
+    }
+}
+
+/// With CPU selected but no CPU build installed, GPU builds are the last
+/// resort; callers run them with `--no-gpu`.
+fn cpu_fallback_candidates(
+    binary: &str,
+    preference: Option<&str>,
+    cwd: Option<&Path>,
+    exe_dir: Option<&Path>,
+) -> Vec<PathBuf> {
+    if normalize_backend_preference(preference) != "cpu" {
+        return Vec::new();
+    }
+    let mut candidates = Vec::new();
+    for backend in ["cuda", "vulkan"] {
+        for name in [format!("{}.exe", binary), binary.to_string()] {
+            if let Some(exe_dir) = exe_dir {
+                candidates.push(exe_dir.join("bin").join(backend).join(&name));
+            }
+            if let Some(cwd) = cwd {
+                candidates.push(cwd.join("src-tauri/bin").join(backend).join(&name));
+                candidates.push(cwd.join("bin").join(backend).join(&name));
+            }
+            if let Some(managed) = managed_runtime_dir() {
+                candidates.push(managed.join(backend).join(&name));
+            }
+        }
     }
+    candidates
 }
 
 pub(crate) fn resolve_whisper_cli_path_for_backend(preference: Option<&str>) -> Option<PathBuf> {
@@ -287,6 +395,10 @@ pub(crate) fn resolve_whisper_cli_path_for_backend(preference: Option<&str>) ->
             candidates.push(exe_dir.join(format!("bin/{}/whisper-cli.exe", backend)));
             candidates.push(exe_dir.join(format!("bin/{}/whisper-cli", backend)));
         }
+        if let Some(managed) = managed_runtime_dir() {
+            candidates.push(managed.join(backend).join("whisper-cli.exe"));
+            candidates.push(managed.join(backend).join("whisper-cli"));
+        }
     }
 
     // 3. Flat layout fallback
@@ -302,6 +414,16 @@ pub(crate) fn resolve_whisper_cli_path_for_backend(preference: Option<&str>) ->
         candidates.push(cwd.join("bin/whisper-cli.exe"));
         candidates.push(cwd.join("bin/whisper-cli"));
     }
+    if let Some(managed) = managed_runtime_dir() {
+        candidates.push(managed.join("cpu/whisper-cli.exe"));
+        candidates.push(managed.join("cpu/whisper-cli"));
+    }
+    candidates.extend(cpu_fallback_candidates(
+        "whisper-cli",
+        preference,
+        cwd.as_deref(),
+        exe_dir.as_deref(),
+    ));
 
     for path in candidates {
         if path.exists() {
@@ -340,6 +462,10 @@ pub(crate) fn resolve_whisper_server_path_for_backend(preference: Option<&str>)
             candidates.push(exe_dir.join(format!("bin/{}/whisper-server.exe", backend)));
             candidates.push(exe_dir.join(format!("bin/{}/whisper-server", backend)));
         }
+        if let Some(managed) = managed_runtime_dir() {
+            candidates.push(managed.join(backend).join("whisper-server.exe"));
+            candidates.push(managed.join(backend).join("whisper-server"));
+        }
     }
 
     // 3. Flat layout fallback
@@ -355,6 +481,16 @@ pub(crate) fn resolve_whisper_server_path_for_backend(preference: Option<&str>)
         candidates.push(cwd.join("bin/whisper-server.exe"));
         candidates.push(cwd.join("bin/whisper-server"));
     }
+    if let Some(managed) = managed_runtime_dir() {
+        candidates.push(managed.join("cpu/whisper-server.exe"));
+        candidates.push(managed.join("cpu/whisper-server"));
+    }
+    candidates.extend(cpu_fallback_candidates(
+        "whisper-server",
+        preference,
+        cwd.as_deref(),
+        exe_dir.as_deref(),
+    ));
 
     for path in candidates {
         if path.exists() {
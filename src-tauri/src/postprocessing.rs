@@ -2,25 +2,101 @@
 //
 // This module provides text quality improvements through a multi-stage pipeline:
 // 1. Rule-based enhancements (punctuation, capitalization, number normalization)
-// 2. Custom vocabulary replacements
-// 3. LLM refinement is handled asynchronously in the audio/transcription pipeline.
-use crate::state::Settings;
+// 2. Custom vocabulary replacements and user-defined find/replace rules
+// 3. Optional inline LLM cleanup (bounded by a timeout, falls back to raw text);
+//    full AI refinement is handled asynchronously in the audio/transcription pipeline.
+// The profanity / PII filter (mask, drop or tag; see `content_filter`) runs
+// after the pipeline in `process_for_output`, whether or not post-processing
+// is enabled.
+//
+// Besides the final text, `process_transcript` keeps the intermediate
+// renditions so paste and history can use different ones (`Rendition`).
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
+use crate::state::{AppState, ReplacementRule, Settings};
+use chrono::{DateTime, Local};
 use std::collections::HashMap;
-use tauri::AppHandle;
+use std::sync::LazyLock;
+use tauri::{AppHandle, Manager};
+
+/// Which stage's output is used; the `paste_rendition` and
+/// `history_rendition` settings hold its `as_str` name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Rendition {
+    /// The transcript as dictated, before any stage ran.
+    Raw,
+    /// After the rule-based and vocabulary stages, without LLM cleanup.
+    Rules,
+    /// Output of the whole pipeline.
+    Processed,
+}
+
+impl Rendition {
+    /// Unknown names fall back to `Processed`.
+    pub(crate) fn from_setting(value: &str) -> Self {
+        match value.trim().to_lowercase().as_str() {
+            "raw" => Self::Raw,
+            "rules" => Self::Rules,
+            _ => Self::Processed,
+        }
+    }
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Raw => "raw",
+            Self::Rules => "rules",
+            Self::Processed => "processed",
+        }
+    }
+}
+
+/// Result of `process_transcript`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ProcessedTranscript {
+    pub(crate) text: String,
+    /// Input of the pipeline; content-filtered like `text`.
+    pub(crate) raw: String,
+    /// Text before LLM cleanup; content-filtered like `text`.
+    pub(crate) rules: String,
+    /// Content-filter findings to note as tags on the history entry.
+    pub(crate) tags: Vec<String>,
+    /// The content filter asked to drop the transcript.
+    pub(crate) dropped: bool,
+}
+
+impl ProcessedTranscript {
+    /// `text` passed through untouched, e.g. with post-processing off.
+    pub(crate) fn unprocessed(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            raw: text.to_string(),
+            rules: text.to_string(),
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn rendition(&self, rendition: Rendition) -> &str {
+        match rendition {
+            Rendition::Raw => &self.raw,
+            Rendition::Rules => &self.rules,
+            Rendition::Processed => &self.text,
+        }
+    }
+}
 
 /// Main entry point for post-processing transcripts
 ///
 /// Applies enhancements in sequence:
 /// - Rule-based fixes (punctuation, capitalization, numbers)
 /// - Custom vocabulary replacements
-/// - No synchronous LLM call (keeps transcription path non-blocking)
+/// - User-defined find/replace rules (plain or regex)
+/// - Optional LLM cleanup (opt-in, time-limited; errors keep the text as is)
 ///
-/// Returns the processed text.
+/// Returns the processed text and its earlier renditions.
 pub(crate) fn process_transcript(
     text: &str,
     settings: &Settings,
-    _app: &AppHandle,
-) -> Result<String, String> {
+    app: &AppHandle,
+) -> Result<ProcessedTranscript, String> {
     let mut result = text.to_string();
 
     // Stage 1: Rule-based enhancements (sync, <5ms)
@@ -38,11 +114,65 @@ pub(crate) fn process_transcript(
     if settings.postproc_custom_vocab_enabled && !settings.postproc_custom_vocab.is_empty() {
         result = apply_custom_vocabulary(&result, &settings.postproc_custom_vocab);
     }
+    if settings.postproc_replacement_rules_enabled
+        && !settings.postproc_replacement_rules.is_empty()
+    {
+        result = apply_replacement_rules(&result, &settings.postproc_replacement_rules);
+    }
+
+    let rules = result.clone();
 
-    // Stage 3 is intentionally skipped here to avoid blocking transcription.
-    // AI refinement runs async via dedicated pipeline events.
+    // Stage 3: LLM cleanup (opt-in, bounded by postproc_llm_timeout_ms).
+    // AI refinement still runs async via dedicated pipeline events.
+    if settings.postproc_llm_cleanup_enabled {
+        match crate::llm_cleanup::cleanup_transcript(app, settings, &result) {
+            Ok(cleaned) => result = cleaned,
+            Err(e) => tracing::warn!("LLM cleanup skipped, keeping raw text: {}", e),
+        }
+    }
+
+    Ok(ProcessedTranscript {
+        text: result,
+        raw: text.to_string(),
+        rules,
+        ..ProcessedTranscript::default()
+    })
+}
+
+/// Post-processing (when enabled) followed by the content filter (when
+/// enabled). Post-processing errors keep the text as is.
+pub(crate) fn process_for_output(
+    text: &str,
+    settings: &Settings,
+    app: &AppHandle,
+) -> ProcessedTranscript {
+    let processed = if settings.postproc_enabled {
+        process_transcript(text, settings, app).unwrap_or_else(|err| {
+            tracing::error!("Post-processing failed: {}", err);
+            ProcessedTranscript::unprocessed(text)
+        })
+    } else {
+        ProcessedTranscript::unprocessed(text)
+    };
+    filter_content(processed, settings)
+}
 
-    Ok(result)
+/// Profanity / PII filter, last so nothing re-introduces a hit. Every
+/// rendition is filtered; findings and the drop verdict come from the final
+/// text.
+fn filter_content(processed: ProcessedTranscript, settings: &Settings) -> ProcessedTranscript {
+    if !settings.content_filter_enabled {
+        return processed;
+    }
+    let filter = |text: &str| crate::content_filter::apply_content_filter(text, settings).text;
+    let filtered = crate::content_filter::apply_content_filter(&processed.text, settings);
+    ProcessedTranscript {
+        text: filtered.text,
+        raw: filter(&processed.raw),
+        rules: filter(&processed.rules),
+        tags: filtered.tags,
+        dropped: filtered.dropped,
+    }
 }
 
 /// Apply punctuation rules based on language
@@ -389,6 +519,207 @@ fn apply_custom_vocabulary(text: &str, vocab: &HashMap<String, String>) -> Strin
     result
 }
 
+/// Build the regex for a replacement rule.
+///
+/// Plain rules are escaped (optionally wrapped in word boundaries); regex rules
+/// are compiled as written. Case sensitivity applies to both.
+fn compile_replacement_rule(rule: &ReplacementRule) -> Result<regex::Regex, regex::Error> {
+    let pattern = if rule.regex {
+        rule.find.clone()
+    } else if rule.whole_word {
+        format!(r"\b{}\b", regex::escape(&rule.find))
+    } else {
+        regex::escape(&rule.find)
+    };
+    regex::RegexBuilder::new(&pattern)
+        .case_insensitive(!rule.case_sensitive)
+        .build()
+}
+
+/// Apply user-defined find/replace rules in order.
+///
+/// Regex rules may reference capture groups (`$1`, `${name}`) in the
+/// replacement; plain rules insert the replacement literally. Rules that fail
+/// to compile are skipped (they are rejected on save, so this only affects
+/// hand-edited settings files).
+fn apply_replacement_rules(text: &str, rules: &[ReplacementRule]) -> String {
+    use std::sync::{Mutex, OnceLock};
+
+    // (find, regex, case_sensitive, whole_word)
+    type RuleKey = (String, bool, bool, bool);
+    static RULE_CACHE: OnceLock<Mutex<HashMap<RuleKey, regex::Regex>>> = OnceLock::new();
+
+    if text.is_empty() || rules.is_empty() {
+        return text.to_string();
+    }
+
+    let cache = RULE_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    let mut result = text.to_string();
+
+    for rule in rules {
+        if !rule.enabled || rule.find.is_empty() {
+            continue;
+        }
+        let key = (
+            rule.find.clone(),
+            rule.regex,
+            rule.case_sensitive,
+            rule.whole_word,
+        );
+        let cached = cache.lock().ok().and_then(|guard| guard.get(&key).cloned());
+        let re = match cached {
+            Some(re) => re,
+            None => match compile_replacement_rule(rule) {
+                Ok(re) => {
+                    if let Ok(mut guard) = cache.lock() {
+                        guard.insert(key, re.clone());
+                    }
+                    re
+                }
+                Err(e) => {
+                    tracing::warn!("Skipping replacement rule '{}': {}", rule.find, e);
+                    continue;
+                }
+            },
+        };
+
+        result = if rule.regex {
+            re.replace_all(&result, rule.replace.as_str()).to_string()
+        } else {
+            re.replace_all(&result, regex::NoExpand(&rule.replace))
+                .to_string()
+        };
+    }
+
+    result
+}
+
+/// Drop rules with an empty pattern and reject rules whose regex does not compile.
+fn validate_replacement_rules(rules: Vec<ReplacementRule>) -> Result<Vec<ReplacementRule>, String> {
+    let mut validated = Vec::with_capacity(rules.len());
+    for (index, rule) in rules.into_iter().enumerate() {
+        if rule.find.is_empty() {
+            continue;
+        }
+        if let Err(e) = compile_replacement_rule(&rule) {
+            return Err(format!(
+                "Replacement rule {} ('{}') is invalid: {}",
+                index + 1,
+                rule.find,
+                e
+            ));
+        }
+        validated.push(rule);
+    }
+    Ok(validated)
+}
+
+static TEMPLATE_PLACEHOLDER: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(r"\{(text|quoted|time|date|source|speaker)\}").expect("valid regex")
+});
+
+/// Output template for a history source: system audio or mic.
+fn output_template<'a>(settings: &'a Settings, source: &str) -> &'a str {
+    match source {
+        "output" | "system" => &settings.output_template_system,
+        _ => &settings.output_template_mic,
+    }
+}
+
+/// Fill `template` in one pass, so placeholders inside the transcript are
+/// left alone. Without `{text}` or `{quoted}` the template is a prefix.
+fn render_output_template(
+    template: &str,
+    text: &str,
+    source: &str,
+    speaker: &str,
+    time: &DateTime<Local>,
+) -> String {
+    let template = if template.contains("{text}") || template.contains("{quoted}") {
+        template.to_string()
+    } else {
+        format!("{}{{text}}", template)
+    };
+    TEMPLATE_PLACEHOLDER
+        .replace_all(&template, |caps: &regex::Captures| match &caps[1] {
+            "text" => text.to_string(),
+            "quoted" => text
+                .lines()
+                .map(|line| format!("> {}", line))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            "time" => time.format("%H:%M:%S").to_string(),
+            "date" => time.format("%Y-%m-%d").to_string(),
+            "source" => source.to_string(),
+            _ => speaker.to_string(),
+        })
+        .into_owned()
+}
+
+/// `text` rendered through the output template for `source`, or `None` when
+/// that source has no template. Placeholders: `{text}`, `{quoted}` (each
+/// line as a Markdown quote), `{time}`, `{date}`, `{source}`, `{speaker}`.
+pub(crate) fn apply_output_template(
+    settings: &Settings,
+    source: &str,
+    speaker: &str,
+    text: &str,
+    timestamp_ms: u64,
+) -> Option<String> {
+    let template = output_template(settings, source);
+    if template.trim().is_empty() || text.trim().is_empty() {
+        return None;
+    }
+    let time = DateTime::from_timestamp_millis(timestamp_ms as i64)?.with_timezone(&Local);
+    let source_label = match source {
+        "output" | "system" => "system",
+        _ => "mic",
+    };
+    Some(render_output_template(
+        template,
+        text,
+        source_label,
+        speaker,
+        &time,
+    ))
+}
+
+#[tauri::command]
+pub(crate) fn get_replacement_rules(app: AppHandle) -> Vec<ReplacementRule> {
+    let state = app.state::<AppState>();
+    let settings = state.settings.read().unwrap_or_else(|p| p.into_inner());
+    settings.postproc_replacement_rules.clone()
+}
+
+#[tauri::command]
+pub(crate) async fn save_replacement_rules(
+    app: AppHandle,
+    rules: Vec<ReplacementRule>,
+    enabled: Option<bool>,
+) -> CommandResult<Vec<ReplacementRule>> {
+    let rules = validate_replacement_rules(rules).with_code(ErrorCode::InvalidInput)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app.state::<AppState>();
+        let mut settings = {
+            let current = state.settings.read().unwrap_or_else(|p| p.into_inner());
+            current.clone()
+        };
+        settings.postproc_replacement_rules = rules.clone();
+        if let Some(enabled) = enabled {
+            settings.postproc_replacement_rules_enabled = enabled;
+        }
+        crate::save_settings_inner(&app, &mut settings).with_code(ErrorCode::Storage)?;
+        Ok(rules)
+    })
+    .await
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("save_replacement_rules task failed: {}", e),
+        )
+    })?
+}
+
 /// Refine transcript using Claude API
 ///
 /// Sends text to Claude with configurable prompt template.
@@ -615,6 +946,62 @@ mod tests {
         normalize_numbers(text, lang)
     }
 
+    // ========== Replacement Rule Tests ==========
+
+    fn rule(find: &str, replace: &str, regex: bool) -> ReplacementRule {
+        ReplacementRule {
+            find: find.to_string(),
+            replace: replace.to_string(),
+            regex,
+            ..ReplacementRule::default()
+        }
+    }
+
+    #[test]
+    fn test_replacement_plain_case_insensitive() {
+        let rules = vec![rule("tri spur", "Trispr", false)];
+        let output = apply_replacement_rules("Open Tri Spur flow", &rules);
+        assert_eq!(output, "Open Trispr flow");
+    }
+
+    #[test]
+    fn test_replacement_plain_respects_word_boundaries() {
+        let rules = vec![rule("api", "API", false)];
+        let output = apply_replacement_rules("the api and apikey", &rules);
+        assert_eq!(output, "the API and apikey");
+    }
+
+    #[test]
+    fn test_replacement_plain_inserts_literal_dollar() {
+        let rules = vec![rule("five dollars", "$5", false)];
+        let output = apply_replacement_rules("it costs five dollars", &rules);
+        assert_eq!(output, "it costs $5");
+    }
+
+    #[test]
+    fn test_replacement_regex_with_captures() {
+        let rules = vec![rule(r"ticket (\d+)", "#$1", true)];
+        let output = apply_replacement_rules("see ticket 42 today", &rules);
+        assert_eq!(output, "see #42 today");
+    }
+
+    #[test]
+    fn test_replacement_skips_disabled_rules() {
+        let mut disabled = rule("foo", "bar", false);
+        disabled.enabled = false;
+        let output = apply_replacement_rules("foo", &[disabled]);
+        assert_eq!(output, "foo");
+    }
+
+    #[test]
+    fn test_validate_replacement_rules_rejects_bad_regex() {
+        let result = validate_replacement_rules(vec![rule("(unclosed", "x", true)]);
+        assert!(result.is_err());
+        let kept = validate_replacement_rules(vec![rule("", "x", false), rule("a", "b", false)])
+            .expect("valid rules");
+        assert_eq!(kept.len(), 1);
+    }
+
     // ========== Multilingual Mode Tests ==========
 
     #[test]
@@ -690,4 +1077,60 @@ mod tests {
         assert!(result.contains('5'));
         assert!(result.ends_with('.'));
     }
+
+    #[test]
+    fn output_templates_fill_placeholders_once() {
+        let time = DateTime::from_timestamp_millis(0)
+            .unwrap()
+            .with_timezone(&Local);
+        let clock = time.format("%H:%M:%S").to_string();
+        assert_eq!(
+            render_output_template("- {time}: {text}", "hello {date}", "mic", "Me", &time),
+            format!("- {}: hello {{date}}", clock)
+        );
+        assert_eq!(
+            render_output_template("{speaker}:\n{quoted}", "a\nb", "system", "Alex", &time),
+            "Alex:\n> a\n> b"
+        );
+        assert_eq!(
+            render_output_template("[{source}] ", "hi", "mic", "Me", &time),
+            "[mic] hi"
+        );
+
+        let mut settings = Settings::default();
+        assert!(apply_output_template(&settings, "mic", "Me", "hi", 0).is_none());
+        settings.output_template_system = "\"{text}\"".to_string();
+        assert!(apply_output_template(&settings, "mic", "Me", "hi", 0).is_none());
+        assert_eq!(
+            apply_output_template(&settings, "output", "Them", "hi", 0).as_deref(),
+            Some("\"hi\"")
+        );
+    }
+
+    #[test]
+    fn renditions_fall_back_to_the_processed_text() {
+        let processed = ProcessedTranscript {
+            text: "Hello, world.".to_string(),
+            raw: "hello world".to_string(),
+            rules: "Hello world.".to_string(),
+            ..ProcessedTranscript::default()
+        };
+        assert_eq!(
+            processed.rendition(Rendition::from_setting(" RAW ")),
+            "hello world"
+        );
+        assert_eq!(
+            processed.rendition(Rendition::from_setting("rules")),
+            "Hello world."
+        );
+        assert_eq!(
+            processed.rendition(Rendition::from_setting("cleaned")),
+            "Hello, world."
+        );
+        assert_eq!(Rendition::from_setting("").as_str(), "processed");
+
+        let untouched = ProcessedTranscript::unprocessed("as dictated");
+        assert_eq!(untouched.rendition(Rendition::Raw), "as dictated");
+        assert_eq!(untouched.rendition(Rendition::Processed), "as dictated");
+    }
 }
@@ -0,0 +1,100 @@
+//! Privacy mute: an instant, session-only kill switch for all audio capture.
+//!
+//! Unlike `capture_enabled`, which is a persisted setting applied through a
+//! settings save, the mute flag flips atomically and every capture entry point
+//! (PTT press, toggle, VAD monitor, PTT warm standby, system-audio monitor,
+//! echo reference) checks it. Engaging it tears down running capture and
+//! discards a PTT recording in progress; releasing it restarts what the
+//! settings ask for.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{info, warn};
+
+use crate::state::AppState;
+
+static PRIVACY_MUTED: AtomicBool = AtomicBool::new(false);
+
+pub(crate) fn is_muted() -> bool {
+    PRIVACY_MUTED.load(Ordering::Acquire)
+}
+
+/// Engages or releases the mute. Returns the new state.
+pub(crate) fn set_muted(app: &AppHandle, muted: bool) -> bool {
+    if PRIVACY_MUTED.swap(muted, Ordering::AcqRel) == muted {
+        return muted;
+    }
+    let state = app.state::<AppState>();
+    let settings = state
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+
+    if muted {
+        info!("Privacy mute engaged: stopping all capture");
+        let recording = state
+            .recorder
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .active;
+        if recording {
+            // The stop path sees the flag and drops the audio untranscribed.
+            crate::audio::stop_recording_async(app.clone(), &state);
+        }
+        crate::audio::stop_vad_monitor(app, &state);
+        crate::audio::sync_ptt_hot_standby(app, &state, &settings);
+        crate::transcription::stop_transcribe_monitor_and_release_whisper(app, &state);
+        crate::level_probe::stop_probe();
+        crate::echo_reference::sync_echo_reference(&settings);
+    } else {
+        info!("Privacy mute released: restoring capture from settings");
+        if crate::state::mode_uses_vad_monitor(&settings.mode) && settings.capture_enabled {
+            if let Err(err) = crate::audio::start_vad_monitor(app, &state, &settings) {
+                warn!("Failed to restart VAD monitor after privacy mute: {}", err);
+            }
+        }
+        crate::audio::sync_ptt_hot_standby(app, &state, &settings);
+        crate::echo_reference::sync_echo_reference(&settings);
+        if settings.transcribe_enabled {
+            if let Err(err) = crate::transcription::start_transcribe_monitor(app, &state, &settings)
+            {
+                warn!(
+                    "Failed to restart system audio capture after privacy mute: {}",
+                    err
+                );
+            }
+        }
+    }
+
+    let _ = crate::overlay::update_overlay_state(
+        app,
+        crate::overlay::idle_overlay_state_for_settings(&settings),
+    );
+    if let Some(tray) = app.tray_by_id(crate::TRAY_ICON_ID) {
+        let tooltip = if muted {
+            "Trispr Flow (muted)"
+        } else {
+            "Trispr Flow"
+        };
+        let _ = tray.set_tooltip(Some(tooltip));
+    }
+    let _ = app.emit("privacy:muted", muted);
+    let _ = app.emit("menu:update-privacy-mute", muted);
+    crate::audio_cues::play_cue(app, if muted { "stop" } else { "start" });
+    muted
+}
+
+pub(crate) fn toggle(app: &AppHandle) -> bool {
+    set_muted(app, !is_muted())
+}
+
+#[tauri::command]
+pub(crate) fn get_privacy_mute() -> bool {
+    is_muted()
+}
+
+#[tauri::command]
+pub(crate) fn set_privacy_mute(app: AppHandle, muted: bool) -> bool {
+    set_muted(&app, muted)
+}
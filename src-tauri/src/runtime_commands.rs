@@ -1,4 +1,5 @@
 use crate::ai_fallback::provider::ping_ollama_quick;
+use crate::errors::{CommandError, CommandResult, ErrorCode};
 use crate::modules::canonicalize_module_id;
 use crate::modules::health as module_health;
 use crate::modules::registry as module_registry;
@@ -77,14 +78,17 @@ pub(crate) fn get_runtime_metrics_snapshot(state: State<'_, AppState>) -> Runtim
 pub(crate) fn record_runtime_metric(
     state: State<'_, AppState>,
     metric: String,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     match metric.trim() {
         "refinement_timeout" | "refinement_fallback_timed_out" => {
             state::record_refinement_timeout(state.inner());
             state::record_refinement_fallback_timed_out(state.inner());
             Ok(())
         }
-        other => Err(format!("Unknown runtime metric '{}'", other)),
+        other => Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            format!("Unknown runtime metric '{}'", other),
+        )),
     }
 }
 
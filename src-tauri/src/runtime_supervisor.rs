@@ -0,0 +1,210 @@
+//! Keeps whisper-server loaded while `whisper_server_keep_warm` is on.
+//!
+//! The regular keepalive only revives the server inside the recent-use
+//! window and retires it after idle time, so the first dictation after a
+//! pause pays the full model load. With keep-warm the supervisor owns the
+//! server instead: it preloads the model, pings the server on a fixed
+//! interval and restarts it when it stops answering, backing off after
+//! repeated failures so a broken runtime does not respawn in a tight loop.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{info, warn};
+
+use crate::state::AppState;
+use crate::whisper_server::{
+    active_request_count, inspect_recent_server_crash, ping_whisper_server, start_whisper_server,
+    touch_whisper_server_recent_use,
+};
+
+pub(crate) const SUPERVISOR_STATUS_EVENT: &str = "whisper:supervisor-status";
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(15);
+const RESTART_BACKOFF_BASE_MS: u64 = 5_000;
+const RESTART_BACKOFF_MAX_MS: u64 = 300_000;
+
+static SUPERVISOR_STARTED: AtomicBool = AtomicBool::new(false);
+static STATUS: Mutex<SupervisorStatus> = Mutex::new(SupervisorStatus {
+    active: false,
+    healthy: false,
+    restarts: 0,
+    consecutive_failures: 0,
+    last_error: None,
+    next_restart_ms: None,
+});
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub(crate) struct SupervisorStatus {
+    /// Keep-warm is on and the server is supervised.
+    pub(crate) active: bool,
+    pub(crate) healthy: bool,
+    /// Restarts since launch, excluding the initial preload.
+    pub(crate) restarts: u32,
+    pub(crate) consecutive_failures: u32,
+    pub(crate) last_error: Option<String>,
+    /// Earliest time (unix ms) of the next restart attempt while backing off.
+    pub(crate) next_restart_ms: Option<u64>,
+}
+
+/// Delay before the next restart after `failures` failed attempts in a row.
+fn restart_backoff_ms(failures: u32) -> u64 {
+    if failures == 0 {
+        return 0;
+    }
+    RESTART_BACKOFF_BASE_MS
+        .saturating_mul(1u64 << (failures - 1).min(16))
+        .min(RESTART_BACKOFF_MAX_MS)
+}
+
+fn update_status(app: &AppHandle, f: impl FnOnce(&mut SupervisorStatus)) {
+    let mut status = STATUS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let before = status.clone();
+    f(&mut status);
+    if *status != before {
+        let _ = app.emit(SUPERVISOR_STATUS_EVENT, &*status);
+    }
+}
+
+/// Starts the supervisor thread once; it idles while keep-warm is off.
+pub(crate) fn start_runtime_supervisor(app: AppHandle) {
+    if SUPERVISOR_STARTED
+        .compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed)
+        .is_err()
+    {
+        return;
+    }
+
+    crate::util::spawn_guarded("whisper_runtime_supervisor", move || {
+        let mut preloaded = false;
+        loop {
+            supervise_once(&app, &mut preloaded);
+            std::thread::sleep(HEALTH_CHECK_INTERVAL);
+        }
+    });
+}
+
+fn supervise_once(app: &AppHandle, preloaded: &mut bool) {
+    let state = app.state::<AppState>();
+    let settings = state
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    if !settings.whisper_server_keep_warm
+        || !crate::transcription::whisper_runtime_required(&settings)
+    {
+        *preloaded = false;
+        update_status(app, |status| {
+            status.active = false;
+            status.consecutive_failures = 0;
+            status.next_restart_ms = None;
+        });
+        return;
+    }
+
+    let port = state.whisper_server_port.load(Ordering::Relaxed);
+    if ping_whisper_server(port) {
+        *preloaded = true;
+        touch_whisper_server_recent_use(app, state.inner());
+        update_status(app, |status| {
+            status.active = true;
+            status.healthy = true;
+            status.consecutive_failures = 0;
+            status.next_restart_ms = None;
+        });
+        return;
+    }
+    // A busy server can miss a ping; never restart under a running job.
+    if active_request_count() > 0 {
+        return;
+    }
+
+    let now = crate::util::now_ms();
+    let waiting = {
+        let status = STATUS
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        status.next_restart_ms.is_some_and(|at| at > now)
+    };
+    if waiting {
+        update_status(app, |status| {
+            status.active = true;
+            status.healthy = false;
+        });
+        return;
+    }
+
+    let result = match crate::models::resolve_model_path(app, &settings.model) {
+        Some(model_path) => {
+            if *preloaded {
+                warn!("whisper-server stopped answering; supervisor restarting it");
+            } else {
+                info!(
+                    "whisper-server keep-warm: preloading model '{}'",
+                    settings.model
+                );
+            }
+            start_whisper_server(app, state.inner(), &model_path).and_then(|()| {
+                if ping_whisper_server(port) {
+                    Ok(())
+                } else {
+                    Err(inspect_recent_server_crash(app)
+                        .unwrap_or_else(|| "whisper-server started but is not healthy".into()))
+                }
+            })
+        }
+        None => Err(format!("Model '{}' is not installed", settings.model)),
+    };
+
+    let was_preloaded = std::mem::replace(preloaded, true);
+    update_status(app, |status| {
+        status.active = true;
+        match result {
+            Ok(()) => {
+                status.healthy = true;
+                if was_preloaded {
+                    status.restarts += 1;
+                }
+                status.consecutive_failures = 0;
+                status.last_error = None;
+                status.next_restart_ms = None;
+            }
+            Err(err) => {
+                warn!("whisper-server supervisor restart failed: {}", err);
+                status.healthy = false;
+                status.consecutive_failures += 1;
+                status.last_error = Some(err);
+                status.next_restart_ms =
+                    Some(now + restart_backoff_ms(status.consecutive_failures));
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub(crate) fn get_whisper_supervisor_status() -> SupervisorStatus {
+    STATUS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restart_backoff_doubles_up_to_the_cap() {
+        assert_eq!(restart_backoff_ms(0), 0);
+        assert_eq!(restart_backoff_ms(1), 5_000);
+        assert_eq!(restart_backoff_ms(2), 10_000);
+        assert_eq!(restart_backoff_ms(4), 40_000);
+        assert_eq!(restart_backoff_ms(7), RESTART_BACKOFF_MAX_MS);
+        assert_eq!(restart_backoff_ms(u32::MAX), RESTART_BACKOFF_MAX_MS);
+    }
+}
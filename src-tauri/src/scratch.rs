@@ -0,0 +1,149 @@
+//! Scratch directory for short-lived working files (whisper-cli input WAVs
+//! and transcripts, sidecar decodes).
+//!
+//! Lives at `<base>/scratch` and is emptied at startup, folders such as the
+//! decrypted recording copies included; the single-instance plugin
+//! guarantees no other process is using it. Startup also removes stale
+//! `trispr_*` files and the `trispr-decrypted` folder older builds left in
+//! the system temp dir.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime};
+
+use tauri::AppHandle;
+use tracing::{info, warn};
+
+static SCRATCH_DIR: OnceLock<PathBuf> = OnceLock::new();
+static NEXT_SCRATCH_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Extensions of the working files older builds wrote to the system temp dir.
+const LEGACY_TEMP_EXTENSIONS: &[&str] = &["wav", "txt", "srt", "vtt", "json", "lrc", "tsv"];
+/// Legacy temp files younger than this may belong to a running build.
+const LEGACY_TEMP_MIN_AGE: Duration = Duration::from_secs(10 * 60);
+/// Folder of decrypted recording copies older builds kept in the temp dir.
+const LEGACY_DECRYPTED_DIR: &str = "trispr-decrypted";
+
+/// Sets up the scratch directory and removes leftovers; call once at startup.
+pub(crate) fn init_scratch_dir(app: &AppHandle) {
+    let dir = crate::paths::resolve_base_dir(app).join("scratch");
+    let temp_dir = std::env::temp_dir();
+    let removed = clear_dir(&dir)
+        + clear_dir(&temp_dir.join(LEGACY_DECRYPTED_DIR))
+        + remove_stale_legacy_files(&temp_dir);
+    let _ = fs::remove_dir(temp_dir.join(LEGACY_DECRYPTED_DIR));
+    if let Err(e) = fs::create_dir_all(&dir) {
+        warn!("Failed to create scratch dir '{}': {}", dir.display(), e);
+    }
+    if removed > 0 {
+        info!("Removed {} stale scratch file(s)", removed);
+    }
+    let _ = SCRATCH_DIR.set(dir);
+}
+
+/// The scratch directory; a `trispr-flow` folder in the system temp dir
+/// before `init_scratch_dir` ran.
+pub(crate) fn scratch_dir() -> PathBuf {
+    let dir = SCRATCH_DIR
+        .get()
+        .cloned()
+        .unwrap_or_else(|| std::env::temp_dir().join("trispr-flow"));
+    let _ = fs::create_dir_all(&dir);
+    dir
+}
+
+/// Unique path in the scratch dir: `trispr_<label>_<pid>_<n>` with
+/// `extension` (none when empty).
+pub(crate) fn scratch_path(label: &str, extension: &str) -> PathBuf {
+    let id = NEXT_SCRATCH_ID.fetch_add(1, Ordering::Relaxed);
+    let path = scratch_dir().join(format!("trispr_{}_{}_{}", label, std::process::id(), id));
+    if extension.is_empty() {
+        path
+    } else {
+        path.with_extension(extension)
+    }
+}
+
+/// Removes everything inside `dir`, subfolders included; returns how many
+/// entries went.
+fn clear_dir(dir: &Path) -> usize {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            if path.is_dir() {
+                fs::remove_dir_all(path).is_ok()
+            } else {
+                fs::remove_file(path).is_ok()
+            }
+        })
+        .count()
+}
+
+fn is_legacy_temp_file(path: &Path, now: SystemTime) -> bool {
+    let named = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with("trispr_"));
+    let known_extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| LEGACY_TEMP_EXTENSIONS.contains(&ext));
+    let old_enough = fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| now.duration_since(modified).ok())
+        .is_some_and(|age| age >= LEGACY_TEMP_MIN_AGE);
+    named && known_extension && path.is_file() && old_enough
+}
+
+fn remove_stale_legacy_files(temp_dir: &Path) -> usize {
+    let Ok(entries) = fs::read_dir(temp_dir) else {
+        return 0;
+    };
+    let now = SystemTime::now();
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| is_legacy_temp_file(path, now) && fs::remove_file(path).is_ok())
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_old_trispr_working_files_count_as_stale() {
+        let dir = std::env::temp_dir().join(format!("trispr-scratch-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for name in [
+            "trispr_123_456.wav",
+            "trispr_123_456.txt",
+            "other.wav",
+            "trispr_notes.md",
+        ] {
+            fs::write(dir.join(name), b"x").unwrap();
+        }
+
+        let now = SystemTime::now();
+        assert!(!is_legacy_temp_file(&dir.join("trispr_123_456.wav"), now));
+        let later = now + LEGACY_TEMP_MIN_AGE + Duration::from_secs(1);
+        assert!(is_legacy_temp_file(&dir.join("trispr_123_456.wav"), later));
+        assert!(is_legacy_temp_file(&dir.join("trispr_123_456.txt"), later));
+        assert!(!is_legacy_temp_file(&dir.join("other.wav"), later));
+        assert!(!is_legacy_temp_file(&dir.join("trispr_notes.md"), later));
+
+        fs::create_dir_all(dir.join("decrypted")).unwrap();
+        fs::write(dir.join("decrypted").join("0_rec.opus"), b"x").unwrap();
+        assert_eq!(clear_dir(&dir), 5);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -1,20 +1,40 @@
 // Session Manager — Audio chunk consolidation and session lifecycle
 //
-// Problem: System audio transcription flushes a new OPUS file every 60 seconds,
-// producing hundreds of files per day. This module introduces the concept of a
-// "session" (transcription mode ON → OFF) that accumulates chunks in a temp
-// directory and merges them into a single `session.opus` at session end.
+// Problem: System audio transcription flushes a new audio chunk every 60
+// seconds, producing hundreds of files per day. This module introduces the
+// concept of a "session" (transcription mode ON → OFF) that accumulates chunks
+// in a temp directory and merges them into a single `session.<codec>` at
+// session end.
+//
+// Codecs (`recording_codec`): "opus" encodes every chunk through the opus
+// module sidecar and concatenates them (stream copy); "flac" and "wav" keep
+// WAV chunks on disk (crash-safe, no sidecar needed) and encode/join them at
+// finalize.
 //
 // File layout during recording:
 //   recordings/tmp_20260217_143022_output/
-//       chunk_001_0000s.opus
+//       chunk_001_0000s.opus   (or .wav for flac/wav sessions)
 //       chunk_002_0060s.opus
 //       manifest.json          ← status: "recording"
 //
 // File layout after merge:
 //   recordings/2026-02-17_143022_output/
-//       session.opus
+//       session.opus           (or session.flac / session.wav)
 //       manifest.json          ← status: "merged"
+//
+// Finished recordings are pruned by age and total size
+// (`recordings_max_age_days`, `recordings_max_total_mb`) after every merge
+// and on startup.
+//
+// Transcript sessions are the text-side counterpart: a named meeting started
+// and stopped by the user that collects every history entry (mic and system
+// audio) pushed while it is active, so an hour-long call exports as one
+// document instead of hundreds of fragments. Stored one JSON per session:
+//   <data>/transcript_sessions/ts_1739800222000.json
+//
+// Chapters mark topic changes inside the running session (`chapter:detected`).
+// A chapter without a title takes one from the first transcript line after
+// it; both exports start a new section at every chapter.
 
 use chrono::Local;
 use hound::{SampleFormat, WavSpec, WavWriter};
@@ -22,14 +42,86 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::sync::{Mutex, OnceLock};
-use tauri::AppHandle;
+use std::sync::{mpsc, Mutex, OnceLock};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
 use tracing::{error, info, warn};
 
+use crate::audio::CaptureBuffer;
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
+use crate::state::{AppState, HistoryEntry, Settings};
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Data structures
 // ─────────────────────────────────────────────────────────────────────────────
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordingCodec {
+    Opus,
+    Flac,
+    Wav,
+}
+
+impl RecordingCodec {
+    pub fn from_setting(value: &str) -> Self {
+        match value {
+            "flac" => RecordingCodec::Flac,
+            "wav" => RecordingCodec::Wav,
+            _ => RecordingCodec::Opus,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            RecordingCodec::Opus => "opus",
+            RecordingCodec::Flac => "flac",
+            RecordingCodec::Wav => "wav",
+        }
+    }
+
+    /// Chunks are only pre-encoded for opus; flac/wav sessions buffer WAV.
+    fn chunk_extension(self) -> &'static str {
+        match self {
+            RecordingCodec::Opus => "opus",
+            RecordingCodec::Flac | RecordingCodec::Wav => "wav",
+        }
+    }
+}
+
+/// Recording options captured when a session starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecordingConfig {
+    pub codec: RecordingCodec,
+    pub bitrate_kbps: u32,
+    /// Delete finished recordings older than this (0 = keep).
+    pub max_age_days: u32,
+    /// Keep finished recordings under this total size (0 = unlimited).
+    pub max_total_mb: u64,
+}
+
+impl Default for RecordingConfig {
+    fn default() -> Self {
+        Self {
+            codec: RecordingCodec::Opus,
+            bitrate_kbps: 64,
+            max_age_days: 0,
+            max_total_mb: 0,
+        }
+    }
+}
+
+impl RecordingConfig {
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self {
+            codec: RecordingCodec::from_setting(&settings.recording_codec),
+            bitrate_kbps: settings.opus_bitrate_kbps,
+            max_age_days: settings.recordings_max_age_days,
+            max_total_mb: settings.recordings_max_total_mb,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChunkMeta {
     pub index: usize,
@@ -38,6 +130,10 @@ pub struct ChunkMeta {
     pub duration_s: u64,
 }
 
+fn default_manifest_codec() -> String {
+    "opus".to_string()
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SessionManifest {
     pub version: u8,
@@ -49,7 +145,12 @@ pub struct SessionManifest {
     pub duration_s: u64,
     pub status: String, // "recording" | "merging" | "merged" | "merge_failed"
     pub final_file: Option<String>,
+    #[serde(default = "default_manifest_codec")]
+    pub codec: String,
     pub chunks: Vec<ChunkMeta>,
+    /// Per-source files of a multitrack call recording.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tracks: Vec<String>,
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -63,6 +164,8 @@ pub struct ActiveSession {
     pub session_name: Option<String>,
     pub chunks: Vec<ChunkMeta>,
     pub started_at_str: String,
+    pub codec: RecordingCodec,
+    pub bitrate_kbps: u32,
 }
 
 impl ActiveSession {
@@ -70,8 +173,13 @@ impl ActiveSession {
         self.chunks.iter().map(|c| c.duration_s).sum()
     }
 
-    fn write_manifest(&self, status: &str, final_file: Option<&str>, ended_at: Option<&str>) {
-        let manifest = SessionManifest {
+    fn manifest(
+        &self,
+        status: &str,
+        final_file: Option<&str>,
+        ended_at: Option<&str>,
+    ) -> SessionManifest {
+        SessionManifest {
             version: 1,
             session_id: self.session_id.clone(),
             session_name: self.session_name.clone(),
@@ -81,8 +189,14 @@ impl ActiveSession {
             duration_s: self.total_duration_s(),
             status: status.to_string(),
             final_file: final_file.map(String::from),
+            codec: self.codec.extension().to_string(),
             chunks: self.chunks.clone(),
-        };
+            tracks: Vec::new(),
+        }
+    }
+
+    fn write_manifest(&self, status: &str, final_file: Option<&str>, ended_at: Option<&str>) {
+        let manifest = self.manifest(status, final_file, ended_at);
         let path = self.session_dir.join("manifest.json");
         match serde_json::to_string_pretty(&manifest) {
             Ok(json) => {
@@ -94,35 +208,44 @@ impl ActiveSession {
         }
     }
 
-    /// Flush a batch of i16 samples as a new OPUS chunk.
-    /// Writes temp WAV → sidecar encode → deletes WAV, appends ChunkMeta.
-    pub fn flush_chunk(&mut self, samples: &[i16], sidecar: &Path) -> Result<ChunkMeta, String> {
+    /// Flush a batch of i16 samples as a new chunk. Opus sessions write a
+    /// temp WAV, encode it via the sidecar and delete the WAV; flac/wav
+    /// sessions keep the WAV as the chunk.
+    pub fn flush_chunk(
+        &mut self,
+        samples: &[i16],
+        sidecar: Option<&Path>,
+    ) -> Result<ChunkMeta, String> {
         let duration_s = samples.len() as u64 / 16_000;
         let offset_s = self.total_duration_s();
         let index = self.chunks.len() + 1;
         let chunk_base = format!("chunk_{:03}_{:04}s", index, offset_s);
+        let chunk_file = format!("{}.{}", chunk_base, self.codec.chunk_extension());
 
         let wav_path = self.session_dir.join(format!("{}.wav", chunk_base));
-        let opus_path = self.session_dir.join(format!("{}.opus", chunk_base));
 
         // Write WAV
         write_wav_i16(&wav_path, samples)?;
 
-        // Encode WAV → OPUS via the opus module sidecar (64 kbps, 16 kHz, mono).
-        let encode_result = crate::opus::encode_with_sidecar(
-            sidecar,
-            &wav_path,
-            &opus_path,
-            &crate::opus::OpusEncoderConfig::default(),
-        );
+        if self.codec == RecordingCodec::Opus {
+            let sidecar = sidecar.ok_or_else(|| "The opus module is not installed.".to_string())?;
+            let opus_path = self.session_dir.join(&chunk_file);
+            let config = crate::opus::OpusEncoderConfig {
+                bitrate_kbps: self.bitrate_kbps,
+                ..crate::opus::OpusEncoderConfig::default()
+            };
+            // Encode WAV → OPUS via the opus module sidecar (16 kHz, mono).
+            let encode_result =
+                crate::opus::encode_with_sidecar(sidecar, &wav_path, &opus_path, &config);
 
-        let _ = fs::remove_file(&wav_path);
+            let _ = fs::remove_file(&wav_path);
 
-        encode_result.map_err(|e| format!("Failed encoding chunk {}: {}", index, e))?;
+            encode_result.map_err(|e| format!("Failed encoding chunk {}: {}", index, e))?;
+        }
 
         let meta = ChunkMeta {
             index,
-            file: format!("{}.opus", chunk_base),
+            file: chunk_file,
             offset_s,
             duration_s,
         };
@@ -135,10 +258,21 @@ impl ActiveSession {
         Ok(meta)
     }
 
-    /// Merge all chunks into a single session.opus via FFmpeg concat.
+    fn chunk_paths(&self) -> Vec<PathBuf> {
+        self.chunks
+            .iter()
+            .map(|chunk| self.session_dir.join(&chunk.file))
+            .collect()
+    }
+
+    /// Merge all chunks into a single `session.<codec>`.
     /// On success: renames temp dir → final dir, cleans up chunks.
     /// On failure: leaves temp dir intact for crash recovery.
-    pub fn finalize(self, recordings_dir: &PathBuf, sidecar: &Path) -> Result<PathBuf, String> {
+    pub fn finalize(
+        self,
+        recordings_dir: &Path,
+        sidecar: Option<&Path>,
+    ) -> Result<PathBuf, String> {
         if self.chunks.is_empty() {
             warn!(
                 "Session {} has no chunks, discarding temp dir",
@@ -148,76 +282,66 @@ impl ActiveSession {
             return Err("No chunks to merge".to_string());
         }
 
-        // Write concat file list (paths relative to session_dir for FFmpeg -safe 0)
-        let concat_path = self.session_dir.join("concat.txt");
-        let list: String = self
-            .chunks
-            .iter()
-            .map(|c| format!("file '{}'\n", c.file))
-            .collect();
-        fs::write(&concat_path, &list)
-            .map_err(|e| format!("Failed to write concat list: {}", e))?;
-
-        // Build final directory name
-        let final_name = if let Some(ref name) = self.session_name {
-            let date = Local::now().format("%Y-%m-%d").to_string();
-            format!("{}_{}", date, sanitize_name(name))
-        } else {
-            self.session_id.clone()
-        };
-        let final_dir = recordings_dir.join(&final_name);
+        let final_dir = recordings_dir.join(final_dir_name(
+            &self.session_id,
+            self.session_name.as_deref(),
+        ));
         fs::create_dir_all(&final_dir)
             .map_err(|e| format!("Failed to create final session dir: {}", e))?;
-        let final_opus = final_dir.join("session.opus");
+        let final_file = format!("session.{}", self.codec.extension());
+        let final_path = final_dir.join(&final_file);
 
         let ended_at = Local::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
 
-        // Merge chunks via the opus module sidecar (stream copy, FFmpeg concat).
-        // `concat.txt` holds entries relative to the session dir, so we run with
-        // that as the working directory.
-        let merge_result = crate::opus::concat_with_sidecar(
-            sidecar,
-            &concat_path,
-            &final_opus,
-            Some(&self.session_dir),
-        );
+        let merge_result = match self.codec {
+            RecordingCodec::Opus => self.concat_opus(&final_path, sidecar),
+            RecordingCodec::Wav => concat_wav_chunks(&self.chunk_paths(), &final_path),
+            RecordingCodec::Flac => encode_flac_chunks(&self.chunk_paths(), &final_path),
+        };
 
         if let Err(e) = merge_result {
             // Leave temp dir intact — user can retry or recover manually
             self.write_manifest("merge_failed", None, Some(&ended_at));
             return Err(format!(
-                "Concat failed for session {}: {}",
+                "Merge failed for session {}: {}",
                 self.session_id, e
             ));
         }
 
         // Write final manifest to the permanent directory
-        let final_manifest = SessionManifest {
-            version: 1,
-            session_id: self.session_id.clone(),
-            session_name: self.session_name.clone(),
-            source: self.source.clone(),
-            started_at: self.started_at_str.clone(),
-            ended_at: Some(ended_at),
-            duration_s: self.total_duration_s(),
-            status: "merged".to_string(),
-            final_file: Some("session.opus".to_string()),
-            chunks: self.chunks.clone(),
-        };
+        let final_manifest = self.manifest("merged", Some(&final_file), Some(&ended_at));
         if let Ok(json) = serde_json::to_string_pretty(&final_manifest) {
             let _ = fs::write(final_dir.join("manifest.json"), json);
         }
 
         // Clean up temp dir after successful merge
         let _ = fs::remove_dir_all(&self.session_dir);
+        crate::history_crypto::seal_recording(&final_path);
 
         info!(
             "Session {} merged → {:?} ({} s)",
             self.session_id,
-            final_opus,
+            final_path,
             self.total_duration_s()
         );
-        Ok(final_opus)
+        Ok(final_path)
+    }
+
+    /// Merge opus chunks via the opus module sidecar (stream copy, FFmpeg concat).
+    fn concat_opus(&self, output: &Path, sidecar: Option<&Path>) -> Result<(), String> {
+        let sidecar = sidecar.ok_or_else(|| "The opus module is not installed.".to_string())?;
+        // Write concat file list (paths relative to session_dir for FFmpeg -safe 0)
+        let concat_path = self.session_dir.join("concat.txt");
+        let list: String = self
+            .chunks
+            .iter()
+            .map(|c| format!("file '{}'\n", c.file))
+            .collect();
+        fs::write(&concat_path, &list)
+            .map_err(|e| format!("Failed to write concat list: {}", e))?;
+        // `concat.txt` holds entries relative to the session dir, so we run with
+        // that as the working directory.
+        crate::opus::concat_with_sidecar(sidecar, &concat_path, output, Some(&self.session_dir))
     }
 }
 
@@ -229,6 +353,7 @@ pub struct SessionManager {
     active: HashMap<String, ActiveSession>,
     recordings_dir: Option<PathBuf>,
     modules_dir: Option<PathBuf>,
+    config: RecordingConfig,
 }
 
 impl SessionManager {
@@ -237,6 +362,7 @@ impl SessionManager {
             active: HashMap::new(),
             recordings_dir: None,
             modules_dir: None,
+            config: RecordingConfig::default(),
         }
     }
 
@@ -248,6 +374,10 @@ impl SessionManager {
         self.modules_dir = Some(dir);
     }
 
+    pub fn set_config(&mut self, config: RecordingConfig) {
+        self.config = config;
+    }
+
     /// Resolve the installed opus sidecar, if any. Recomputed per call so a
     /// module installed mid-session takes effect without an app restart.
     fn opus_sidecar(&self) -> Option<PathBuf> {
@@ -290,6 +420,8 @@ impl SessionManager {
             session_name: session_name.map(String::from),
             chunks: Vec::new(),
             started_at_str: started_at,
+            codec: self.config.codec,
+            bitrate_kbps: self.config.bitrate_kbps,
         };
         session.write_manifest("recording", None, None);
         info!("Audio session started: {}", session_id);
@@ -298,22 +430,28 @@ impl SessionManager {
     }
 
     /// Flush samples as a new chunk (auto-starts session if needed).
-    /// No-op when the opus module is not installed — continuous dump is an
-    /// opt-in capability that depends on the opus sidecar for encoding.
+    /// Opus sessions are a no-op when the opus module is not installed —
+    /// continuous dump then depends on the sidecar for encoding.
     pub fn flush_chunk(&mut self, samples: &[i16], source: &str) -> Result<(), String> {
-        let Some(sidecar) = self.opus_sidecar() else {
+        let sidecar = self.opus_sidecar();
+        let codec = self
+            .active
+            .get(source)
+            .map(|session| session.codec)
+            .unwrap_or(self.config.codec);
+        if codec == RecordingCodec::Opus && sidecar.is_none() {
             return Ok(());
-        };
+        }
         if !self.active.contains_key(source) {
             self.start_session(source, None)?;
         }
         if let Some(session) = self.active.get_mut(source) {
-            session.flush_chunk(samples, &sidecar)?;
+            session.flush_chunk(samples, sidecar.as_deref())?;
         }
         Ok(())
     }
 
-    /// Finalize one source-specific active session: merge → session.opus, cleanup temp dir.
+    /// Finalize one source-specific active session: merge → session file, cleanup temp dir.
     /// Returns the path to the merged file, or None if no session for this source was active.
     pub fn finalize_session_for(&mut self, source: &str) -> Result<Option<PathBuf>, String> {
         let sidecar = self.opus_sidecar();
@@ -321,7 +459,7 @@ impl SessionManager {
             return Ok(None);
         };
 
-        let Some(sidecar) = sidecar else {
+        if session.codec == RecordingCodec::Opus && sidecar.is_none() {
             // Opus module went away mid-session; leave the temp dir for manual
             // recovery rather than erroring on shutdown.
             warn!(
@@ -329,16 +467,15 @@ impl SessionManager {
                 session.session_id
             );
             return Ok(None);
-        };
+        }
 
         let recordings_dir = self
             .recordings_dir
             .clone()
             .ok_or_else(|| "Recordings directory not configured".to_string())?;
-        match session.finalize(&recordings_dir, &sidecar) {
-            Ok(path) => Ok(Some(path)),
-            Err(e) => Err(e),
-        }
+        let path = session.finalize(&recordings_dir, sidecar.as_deref())?;
+        cleanup_recordings(&recordings_dir, &self.config);
+        Ok(Some(path))
     }
 }
 
@@ -355,13 +492,42 @@ fn get() -> &'static Mutex<SessionManager> {
 /// Call once at app startup (or when transcription mode is activated).
 /// `modules_dir` is where installed module packages live; it is used to resolve
 /// the opus export sidecar at flush/finalize time.
-pub fn init(recordings_dir: PathBuf, modules_dir: PathBuf) {
+pub fn init(recordings_dir: PathBuf, modules_dir: PathBuf, config: RecordingConfig) {
     if let Ok(mut mgr) = get().lock() {
         mgr.set_recordings_dir(recordings_dir);
         mgr.set_modules_dir(modules_dir);
+        mgr.set_config(config);
     }
 }
 
+/// `init` with the directories and recording options from `settings`.
+pub(crate) fn init_from_settings(app: &AppHandle, settings: &Settings) {
+    init(
+        recordings_dir_for(app, settings),
+        crate::paths::resolve_modules_dir(app),
+        RecordingConfig::from_settings(settings),
+    );
+}
+
+/// Whether session auto-save can run: opus needs the opus export enabled,
+/// flac/wav are written without the sidecar.
+pub(crate) fn recording_available(settings: &Settings) -> bool {
+    settings.opus_enabled
+        || RecordingCodec::from_setting(&settings.recording_codec) != RecordingCodec::Opus
+}
+
+/// Session recordings directory: `recordings_dir` from the settings, or the
+/// default `recordings/` folder in the data directory.
+pub(crate) fn recordings_dir_for(app: &AppHandle, settings: &Settings) -> PathBuf {
+    let configured = settings.recordings_dir.trim();
+    if configured.is_empty() {
+        return crate::paths::resolve_recordings_dir(app);
+    }
+    let dir = PathBuf::from(configured);
+    let _ = fs::create_dir_all(&dir);
+    dir
+}
+
 /// Flush audio samples as a new session chunk.
 pub fn flush_chunk(samples: &[i16], source: &str) -> Result<(), String> {
     get()
@@ -397,68 +563,1674 @@ pub fn scan_incomplete(recordings_dir: &PathBuf) -> Vec<PathBuf> {
         .collect()
 }
 
-#[tauri::command]
-pub(crate) fn save_crash_recovery(app: AppHandle, content: String) -> Result<(), String> {
-    let data_dir = crate::paths::resolve_base_dir(&app);
-    let _ = std::fs::create_dir_all(&data_dir);
+// ─────────────────────────────────────────────────────────────────────────────
+// Merging (wav / flac)
+// ─────────────────────────────────────────────────────────────────────────────
 
-    let crash_file = data_dir.join(".crash_recovery.json");
-    std::fs::write(&crash_file, content)
-        .map_err(|e| format!("Failed to save crash recovery: {}", e))?;
+fn read_wav_chunk(path: &Path) -> Result<Vec<i16>, String> {
+    let mut reader =
+        hound::WavReader::open(path).map_err(|e| format!("Cannot open chunk {:?}: {}", path, e))?;
+    reader
+        .samples::<i16>()
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Cannot read chunk {:?}: {}", path, e))
+}
+
+/// Join 16 kHz mono WAV chunks into one WAV, chunk by chunk.
+fn concat_wav_chunks(chunks: &[PathBuf], output: &Path) -> Result<(), String> {
+    let spec = WavSpec {
+        channels: 1,
+        sample_rate: 16_000,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+    let mut writer = WavWriter::create(output, spec)
+        .map_err(|e| format!("Cannot create {:?}: {}", output, e))?;
+    for chunk in chunks {
+        for sample in read_wav_chunk(chunk)? {
+            writer
+                .write_sample(sample)
+                .map_err(|e| format!("WAV write error: {}", e))?;
+        }
+    }
+    writer
+        .finalize()
+        .map_err(|e| format!("WAV finalize error: {}", e))
+}
+
+/// Feeds WAV chunks to the FLAC encoder one block at a time, so a long
+/// session is never held in memory as raw samples. Read errors end the
+/// stream early and are left in `error`.
+struct WavChunkSource<'a> {
+    chunks: std::slice::Iter<'a, PathBuf>,
+    reader: Option<hound::WavReader<std::io::BufReader<fs::File>>>,
+    channels: usize,
+    block: Vec<i32>,
+    error: &'a std::cell::RefCell<Option<String>>,
+}
+
+impl flacenc::source::Source for WavChunkSource<'_> {
+    fn channels(&self) -> usize {
+        self.channels
+    }
+
+    fn bits_per_sample(&self) -> usize {
+        16
+    }
+
+    fn sample_rate(&self) -> usize {
+        16_000
+    }
+
+    fn read_samples<F: flacenc::source::Fill>(
+        &mut self,
+        block_size: usize,
+        dest: &mut F,
+    ) -> Result<usize, flacenc::error::SourceError> {
+        let wanted = block_size * self.channels;
+        self.block.clear();
+        while self.block.len() < wanted {
+            if self.reader.is_none() {
+                let Some(path) = self.chunks.next() else {
+                    break;
+                };
+                match hound::WavReader::open(path) {
+                    Ok(reader) => self.reader = Some(reader),
+                    Err(e) => {
+                        *self.error.borrow_mut() =
+                            Some(format!("Cannot open chunk {:?}: {}", path, e));
+                        break;
+                    }
+                }
+            }
+            let Some(reader) = self.reader.as_mut() else {
+                break;
+            };
+            let missing = wanted - self.block.len();
+            let before = self.block.len();
+            for sample in reader.samples::<i16>().take(missing) {
+                match sample {
+                    Ok(sample) => self.block.push(i32::from(sample)),
+                    Err(e) => {
+                        *self.error.borrow_mut() = Some(format!("Cannot read chunk: {}", e));
+                        break;
+                    }
+                }
+            }
+            if self.error.borrow().is_some() {
+                break;
+            }
+            if self.block.len() - before < missing {
+                self.reader = None;
+            }
+        }
+        dest.fill_interleaved(&self.block)?;
+        Ok(self.block.len() / self.channels)
+    }
+}
+
+/// Encode 16 kHz WAV chunks (mono, or stereo call recordings) into a single
+/// FLAC file.
+fn encode_flac_chunks(chunks: &[PathBuf], output: &Path) -> Result<(), String> {
+    use flacenc::component::BitRepr;
+    use flacenc::error::Verify;
+
+    let channels = match chunks.first() {
+        Some(first) => {
+            hound::WavReader::open(first)
+                .map_err(|e| format!("Cannot open chunk {:?}: {}", first, e))?
+                .spec()
+                .channels as usize
+        }
+        None => 1,
+    };
+    let config = flacenc::config::Encoder::default()
+        .into_verified()
+        .map_err(|(_, e)| format!("Invalid FLAC encoder config: {:?}", e))?;
+    let error = std::cell::RefCell::new(None);
+    let source = WavChunkSource {
+        chunks: chunks.iter(),
+        reader: None,
+        channels,
+        block: Vec::new(),
+        error: &error,
+    };
+    let stream = flacenc::encode_with_fixed_block_size(&config, source, config.block_size)
+        .map_err(|e| format!("FLAC encoding failed: {:?}", e))?;
+    if let Some(err) = error.into_inner() {
+        return Err(err);
+    }
+    let mut sink = flacenc::bitsink::ByteSink::new();
+    stream
+        .write(&mut sink)
+        .map_err(|_| "FLAC bitstream write failed".to_string())?;
+    fs::write(output, sink.as_slice()).map_err(|e| format!("Cannot write {:?}: {}", output, e))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Recording library (list / delete / reveal / cleanup)
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingInfo {
+    /// Directory name inside the recordings directory.
+    pub id: String,
+    pub name: Option<String>,
+    pub source: String,
+    pub started_at: String,
+    pub ended_at: Option<String>,
+    pub duration_s: u64,
+    pub path: String,
+    pub size_bytes: u64,
+    pub codec: String,
+    pub modified_ms: u64,
+    /// Track files of a multitrack recording (empty otherwise).
+    pub tracks: Vec<String>,
+}
+
+fn dir_size(dir: &Path) -> u64 {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter_map(|e| e.metadata().ok())
+                .filter(|m| m.is_file())
+                .map(|m| m.len())
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+/// Merged sessions in `recordings_dir`, newest first. Temp dirs of sessions
+/// still recording (or awaiting recovery) are not listed.
+pub(crate) fn scan_recordings(recordings_dir: &Path) -> Vec<RecordingInfo> {
+    let Ok(entries) = fs::read_dir(recordings_dir) else {
+        return vec![];
+    };
+    let mut recordings: Vec<RecordingInfo> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .filter_map(|dir| {
+            let id = dir.file_name()?.to_str()?.to_string();
+            if id.starts_with("tmp_") {
+                return None;
+            }
+            let json = fs::read_to_string(dir.join("manifest.json")).ok()?;
+            let manifest: SessionManifest = serde_json::from_str(&json).ok()?;
+            let final_file = manifest.final_file?;
+            let modified_ms = fs::metadata(&dir)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0);
+            Some(RecordingInfo {
+                path: dir.join(&final_file).to_string_lossy().to_string(),
+                size_bytes: dir_size(&dir),
+                id,
+                name: manifest.session_name,
+                source: manifest.source,
+                started_at: manifest.started_at,
+                ended_at: manifest.ended_at,
+                duration_s: manifest.duration_s,
+                codec: manifest.codec,
+                modified_ms,
+                tracks: manifest
+                    .tracks
+                    .iter()
+                    .map(|track| dir.join(track).to_string_lossy().to_string())
+                    .collect(),
+            })
+        })
+        .collect();
+    recordings.sort_by(|a, b| b.modified_ms.cmp(&a.modified_ms));
+    recordings
+}
+
+/// Ids to delete so that nothing is older than `max_age_days` and the total
+/// stays under `max_total_bytes` (oldest first). Zero disables a limit.
+fn recordings_to_prune(
+    recordings: &[RecordingInfo],
+    now_ms: u64,
+    max_age_days: u32,
+    max_total_bytes: u64,
+) -> Vec<String> {
+    let mut by_age: Vec<&RecordingInfo> = recordings.iter().collect();
+    by_age.sort_by_key(|r| r.modified_ms);
+
+    let max_age_ms = max_age_days as u64 * 86_400_000;
+    let mut prune = Vec::new();
+    let mut kept = Vec::new();
+    for recording in by_age {
+        if max_age_days > 0 && now_ms.saturating_sub(recording.modified_ms) > max_age_ms {
+            prune.push(recording.id.clone());
+        } else {
+            kept.push(recording);
+        }
+    }
+    if max_total_bytes > 0 {
+        let mut total: u64 = kept.iter().map(|r| r.size_bytes).sum();
+        for recording in kept {
+            if total <= max_total_bytes {
+                break;
+            }
+            total -= recording.size_bytes;
+            prune.push(recording.id.clone());
+        }
+    }
+    prune
+}
+
+/// Apply the age / size limits to `recordings_dir`.
+pub(crate) fn cleanup_recordings(recordings_dir: &Path, config: &RecordingConfig) {
+    if config.max_age_days == 0 && config.max_total_mb == 0 {
+        return;
+    }
+    let recordings = scan_recordings(recordings_dir);
+    let prune = recordings_to_prune(
+        &recordings,
+        crate::util::now_ms(),
+        config.max_age_days,
+        config.max_total_mb * 1024 * 1024,
+    );
+    for id in prune {
+        match fs::remove_dir_all(recordings_dir.join(&id)) {
+            Ok(()) => info!("Recording cleanup: removed {}", id),
+            Err(e) => warn!("Recording cleanup: failed to remove {}: {}", id, e),
+        }
+    }
+}
 
+fn current_settings(app: &AppHandle) -> Settings {
+    app.state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+/// Directory of recording `id`, rejecting anything outside the recordings dir.
+fn recording_dir(app: &AppHandle, id: &str) -> CommandResult<PathBuf> {
+    if id.is_empty() || id.contains(['/', '\\']) || id == "." || id == ".." {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            format!("Invalid recording id '{}'", id),
+        ));
+    }
+    let dir = recordings_dir_for(app, &current_settings(app)).join(id);
+    if !dir.join("manifest.json").exists() {
+        return Err(CommandError::new(
+            ErrorCode::NotFound,
+            format!("Recording '{}' not found", id),
+        ));
+    }
+    Ok(dir)
+}
+
+#[tauri::command]
+pub(crate) fn list_recordings(app: AppHandle) -> Vec<RecordingInfo> {
+    scan_recordings(&recordings_dir_for(&app, &current_settings(&app)))
+}
+
+#[tauri::command]
+pub(crate) fn delete_recording(app: AppHandle, id: String) -> CommandResult<()> {
+    let dir = recording_dir(&app, &id)?;
+    fs::remove_dir_all(&dir).map_err(|e| {
+        CommandError::new(
+            ErrorCode::Storage,
+            format!("Failed to delete recording: {}", e),
+        )
+    })?;
+    info!("Deleted recording {}", id);
+    let _ = app.emit("recordings:changed", ());
     Ok(())
 }
 
+/// Show the recording in the system file manager.
 #[tauri::command]
-pub(crate) fn clear_crash_recovery(app: AppHandle) -> Result<(), String> {
-    let data_dir = crate::paths::resolve_base_dir(&app);
+pub(crate) fn reveal_recording(app: AppHandle, id: String) -> CommandResult<()> {
+    let dir = recording_dir(&app, &id)?;
+    let target = scan_recordings(dir.parent().unwrap_or(&dir))
+        .into_iter()
+        .find(|recording| recording.id == id)
+        .map(|recording| PathBuf::from(recording.path))
+        .unwrap_or_else(|| dir.clone());
 
-    let crash_file = data_dir.join(".crash_recovery.json");
-    if crash_file.exists() {
-        std::fs::remove_file(&crash_file)
-            .map_err(|e| format!("Failed to clear crash recovery: {}", e))?;
+    #[cfg(target_os = "windows")]
+    {
+        std::process::Command::new("explorer")
+            .arg(format!("/select,{}", target.display()))
+            .spawn()
+            .map_err(|e| {
+                CommandError::new(
+                    ErrorCode::Internal,
+                    format!("Failed to reveal recording: {}", e),
+                )
+            })?;
     }
 
-    let legacy_temp = if cfg!(windows) {
-        std::env::var("TEMP").ok()
-    } else {
-        std::env::var("TMPDIR").ok().or(Some("/tmp".to_string()))
-    };
-    if let Some(temp_dir) = legacy_temp {
-        let legacy_file = PathBuf::from(&temp_dir).join("trispr_crash_recovery.json");
-        let _ = std::fs::remove_file(&legacy_file);
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("open")
+            .arg("-R")
+            .arg(&target)
+            .spawn()
+            .map_err(|e| {
+                CommandError::new(
+                    ErrorCode::Internal,
+                    format!("Failed to reveal recording: {}", e),
+                )
+            })?;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        // xdg-open cannot select a file; open the containing folder.
+        std::process::Command::new("xdg-open")
+            .arg(target.parent().unwrap_or(&dir))
+            .spawn()
+            .map_err(|e| {
+                CommandError::new(
+                    ErrorCode::Internal,
+                    format!("Failed to reveal recording: {}", e),
+                )
+            })?;
     }
 
     Ok(())
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
-// Helpers
+// Call recording (mic + system audio)
 // ─────────────────────────────────────────────────────────────────────────────
+//
+// One start/stop records both sides of a call: the mic and the system audio
+// device (`transcribe_output_device`) each through a stream of their own, so
+// the call keeps recording whatever the transcription toggle does. Tracks
+// are kept aligned to the wall clock — WASAPI loopback delivers nothing while
+// nothing plays, so a lagging track is padded with silence. Layouts
+// (`call_recording_layout`):
+//   multitrack → mic.<codec> + system.<codec>
+//   stereo     → call.<codec>, mic left / system right
+
+const CALL_SOURCE: &str = "call";
+const CALL_TICK: Duration = Duration::from_millis(500);
+/// A track may trail the wall clock by this much before silence is inserted,
+/// so late loopback packets still land in place.
+const CALL_MAX_LAG_SAMPLES: usize = 8_000;
+const SAMPLES_PER_MS: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallLayout {
+    Multitrack,
+    Stereo,
+}
+
+impl CallLayout {
+    pub fn from_setting(value: &str) -> Self {
+        match value {
+            "stereo" => CallLayout::Stereo,
+            _ => CallLayout::Multitrack,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CallLayout::Multitrack => "multitrack",
+            CallLayout::Stereo => "stereo",
+        }
+    }
+
+    fn track_names(self) -> &'static [&'static str] {
+        match self {
+            CallLayout::Multitrack => &["mic", "system"],
+            CallLayout::Stereo => &["call"],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CallRecordingInfo {
+    pub session_id: String,
+    pub name: Option<String>,
+    pub layout: String,
+    pub codec: String,
+    pub started_ms: u64,
+}
+
+struct CallSession {
+    info: CallRecordingInfo,
+    tmp_dir: PathBuf,
+    recordings_dir: PathBuf,
+    config: RecordingConfig,
+    layout: CallLayout,
+    started_at_str: String,
+}
+
+struct CallRecording {
+    session: CallSession,
+    stop_tx: mpsc::Sender<()>,
+    system_stop_tx: mpsc::Sender<()>,
+    join: JoinHandle<Option<Result<u64, String>>>,
+}
+
+static CALL_RECORDING: Mutex<Option<CallRecording>> = Mutex::new(None);
+static SYSTEM_TAP: Mutex<Option<CaptureBuffer>> = Mutex::new(None);
+
+/// Buffer decoded loopback audio for the running call recording.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn push_system_tap(mono: &[f32], sample_rate: u32) {
+    if let Some(buffer) = SYSTEM_TAP
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .as_mut()
+    {
+        buffer.push_samples(mono, sample_rate);
+    }
+}
+
+/// Start the call's own loopback on `settings.transcribe_output_device`.
+/// A failure mid-call is reported through `emit_error`; the system track
+/// is padded with silence from then on.
+#[cfg(target_os = "windows")]
+fn start_system_tap(app: &AppHandle, settings: &Settings) -> Result<mpsc::Sender<()>, String> {
+    let (stop_tx, stop_rx) = mpsc::channel();
+    let app = app.clone();
+    let device_id = settings.transcribe_output_device.clone();
+    let gain = 10f32.powf(settings.transcribe_input_gain_db / 20.0);
+    crate::util::spawn_guarded("call_system_tap", move || {
+        let result = crate::transcription::capture_loopback(&device_id, stop_rx, |mono, rate| {
+            // Privacy mute silences this side as well; padding fills the gap.
+            if crate::privacy_mute::is_muted() {
+                return;
+            }
+            let scaled: Vec<f32> = mono
+                .iter()
+                .map(|sample| (sample * gain).clamp(-1.0, 1.0))
+                .collect();
+            push_system_tap(&scaled, rate);
+        });
+        if let Err(err) = result {
+            crate::emit_error(
+                &app,
+                crate::errors::AppError::AudioDevice(format!(
+                    "Call recording lost system audio: {}",
+                    err
+                )),
+                Some("Call recording"),
+            );
+        }
+    });
+    Ok(stop_tx)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn start_system_tap(_app: &AppHandle, _settings: &Settings) -> Result<mpsc::Sender<()>, String> {
+    Err("System audio capture is not supported on this OS yet.".to_string())
+}
+
+fn take_system_tap() -> Vec<i16> {
+    SYSTEM_TAP
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .as_mut()
+        .map(CaptureBuffer::take_all_samples)
+        .unwrap_or_default()
+}
+
+/// Pad `pending` with silence so the track (`written` + pending) trails
+/// `expected` samples by at most `CALL_MAX_LAG_SAMPLES`.
+fn pad_track(pending: &mut Vec<i16>, written: usize, expected: usize) {
+    let target = expected.saturating_sub(CALL_MAX_LAG_SAMPLES);
+    let have = written + pending.len();
+    if have < target {
+        pending.resize(pending.len() + (target - have), 0);
+    }
+}
+
+/// Take `frames` samples from each track as mic-left / system-right frames.
+fn interleave_stereo(mic: &mut Vec<i16>, system: &mut Vec<i16>, frames: usize) -> Vec<i16> {
+    mic.drain(..frames)
+        .zip(system.drain(..frames))
+        .flat_map(|(left, right)| [left, right])
+        .collect()
+}
 
-fn write_wav_i16(path: &PathBuf, samples: &[i16]) -> Result<(), String> {
+type TrackWriter = WavWriter<std::io::BufWriter<fs::File>>;
+
+fn create_track_writer(path: &Path, channels: u16) -> Result<TrackWriter, String> {
     let spec = WavSpec {
-        channels: 1,
+        channels,
         sample_rate: 16_000,
         bits_per_sample: 16,
         sample_format: SampleFormat::Int,
     };
-    let mut writer = WavWriter::create(path, spec)
-        .map_err(|e| format!("Cannot create chunk WAV {:?}: {}", path, e))?;
-    for &s in samples {
+    WavWriter::create(path, spec).map_err(|e| format!("Cannot create {:?}: {}", path, e))
+}
+
+fn write_samples(writer: &mut TrackWriter, samples: &[i16]) -> Result<(), String> {
+    for &sample in samples {
         writer
-            .write_sample(s)
+            .write_sample(sample)
             .map_err(|e| format!("WAV write error: {}", e))?;
     }
+    // Keeps the WAV header valid so a crash leaves a playable file.
     writer
-        .finalize()
-        .map_err(|e| format!("WAV finalize error: {}", e))?;
-    Ok(())
+        .flush()
+        .map_err(|e| format!("WAV flush error: {}", e))
 }
 
-fn sanitize_name(name: &str) -> String {
-    let s: String = name
-        .chars()
+/// Capture loop of a call recording; returns the number of frames written.
+fn run_call_capture(
+    settings: Settings,
+    tmp_dir: PathBuf,
+    layout: CallLayout,
+    stop_rx: mpsc::Receiver<()>,
+    ready_tx: mpsc::SyncSender<Result<(), String>>,
+) -> Result<u64, String> {
+    let opened = crate::audio::open_mic_capture(&settings).and_then(|mic| {
+        let channels = if layout == CallLayout::Stereo { 2 } else { 1 };
+        let writers = layout
+            .track_names()
+            .iter()
+            .map(|name| create_track_writer(&tmp_dir.join(format!("{}.wav", name)), channels))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((mic, writers))
+    });
+    let (mic, mut writers) = match opened {
+        Ok(opened) => {
+            let _ = ready_tx.send(Ok(()));
+            opened
+        }
+        Err(err) => {
+            let _ = ready_tx.send(Err(err.clone()));
+            return Err(err);
+        }
+    };
+
+    let started = Instant::now();
+    let mut mic_pending: Vec<i16> = Vec::new();
+    let mut system_pending: Vec<i16> = Vec::new();
+    let mut written = 0usize;
+    let mut mic_lost_logged = false;
+    loop {
+        let stopping = !matches!(
+            stop_rx.recv_timeout(CALL_TICK),
+            Err(mpsc::RecvTimeoutError::Timeout)
+        );
+        let mic_samples = mic
+            .buffer
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take_all_samples();
+        // Privacy mute silences the mic side; padding fills the gap.
+        if !crate::privacy_mute::is_muted() {
+            mic_pending.extend(mic_samples);
+        }
+        system_pending.extend(take_system_tap());
+        if mic.stream_lost() && !mic_lost_logged {
+            warn!("Call recording: microphone disconnected, recording silence");
+            mic_lost_logged = true;
+        }
+
+        let expected = started.elapsed().as_millis() as usize * SAMPLES_PER_MS;
+        pad_track(&mut mic_pending, written, expected);
+        pad_track(&mut system_pending, written, expected);
+        let frames = if stopping {
+            let frames = mic_pending.len().max(system_pending.len());
+            mic_pending.resize(frames, 0);
+            system_pending.resize(frames, 0);
+            frames
+        } else {
+            mic_pending.len().min(system_pending.len())
+        };
+
+        match layout {
+            CallLayout::Stereo => {
+                let frames = interleave_stereo(&mut mic_pending, &mut system_pending, frames);
+                write_samples(&mut writers[0], &frames)?;
+            }
+            CallLayout::Multitrack => {
+                let mic_frames: Vec<i16> = mic_pending.drain(..frames).collect();
+                let system_frames: Vec<i16> = system_pending.drain(..frames).collect();
+                write_samples(&mut writers[0], &mic_frames)?;
+                write_samples(&mut writers[1], &system_frames)?;
+            }
+        }
+        written += frames;
+        if stopping {
+            break;
+        }
+    }
+
+    for writer in writers {
+        writer
+            .finalize()
+            .map_err(|e| format!("WAV finalize error: {}", e))?;
+    }
+    Ok(written as u64)
+}
+
+fn encode_track(
+    codec: RecordingCodec,
+    bitrate_kbps: u32,
+    channels: u32,
+    sidecar: Option<&Path>,
+    wav: &Path,
+    output: &Path,
+) -> Result<(), String> {
+    match codec {
+        RecordingCodec::Wav => fs::rename(wav, output)
+            .or_else(|_| fs::copy(wav, output).map(|_| ()))
+            .map_err(|e| format!("Cannot move {:?}: {}", wav, e)),
+        RecordingCodec::Flac => encode_flac_chunks(&[wav.to_path_buf()], output),
+        RecordingCodec::Opus => {
+            let sidecar = sidecar.ok_or_else(|| "The opus module is not installed.".to_string())?;
+            let config = crate::opus::OpusEncoderConfig {
+                bitrate_kbps,
+                channels,
+                ..crate::opus::OpusEncoderConfig::default()
+            };
+            crate::opus::encode_with_sidecar(sidecar, wav, output, &config).map(|_| ())
+        }
+    }
+}
+
+fn write_call_manifest(
+    dir: &Path,
+    session: &CallSession,
+    status: &str,
+    duration_s: u64,
+    tracks: Vec<String>,
+) {
+    let manifest = SessionManifest {
+        version: 1,
+        session_id: session.info.session_id.clone(),
+        session_name: session.info.name.clone(),
+        source: CALL_SOURCE.to_string(),
+        started_at: session.started_at_str.clone(),
+        ended_at: (status != "recording")
+            .then(|| Local::now().format("%Y-%m-%dT%H:%M:%SZ").to_string()),
+        duration_s,
+        status: status.to_string(),
+        final_file: tracks.first().cloned(),
+        codec: session.info.codec.clone(),
+        chunks: Vec::new(),
+        tracks: if tracks.len() > 1 { tracks } else { Vec::new() },
+    };
+    if let Ok(json) = serde_json::to_string_pretty(&manifest) {
+        let _ = fs::write(dir.join("manifest.json"), json);
+    }
+}
+
+/// Encode the captured tracks into the final recording directory.
+fn finish_call_recording(
+    app: &AppHandle,
+    session: &CallSession,
+    frames: u64,
+) -> Result<PathBuf, String> {
+    let final_dir = session.recordings_dir.join(final_dir_name(
+        &session.info.session_id,
+        session.info.name.as_deref(),
+    ));
+    fs::create_dir_all(&final_dir)
+        .map_err(|e| format!("Failed to create final session dir: {}", e))?;
+    let sidecar = crate::opus::resolve_sidecar(app);
+    let channels = if session.layout == CallLayout::Stereo {
+        2
+    } else {
+        1
+    };
+    let mut tracks = Vec::new();
+    for name in session.layout.track_names() {
+        let file = format!("{}.{}", name, session.config.codec.extension());
+        encode_track(
+            session.config.codec,
+            session.config.bitrate_kbps,
+            channels,
+            sidecar.as_deref(),
+            &session.tmp_dir.join(format!("{}.wav", name)),
+            &final_dir.join(&file),
+        )?;
+        crate::history_crypto::seal_recording(&final_dir.join(&file));
+        tracks.push(file);
+    }
+    write_call_manifest(&final_dir, session, "merged", frames / 16_000, tracks);
+    let _ = fs::remove_dir_all(&session.tmp_dir);
+    cleanup_recordings(&session.recordings_dir, &session.config);
+    Ok(final_dir)
+}
+
+fn call_recording_info() -> Option<CallRecordingInfo> {
+    CALL_RECORDING
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .as_ref()
+        .map(|recording| recording.session.info.clone())
+}
+
+/// Start recording mic and system audio together.
+#[tauri::command]
+pub(crate) fn start_call_recording(
+    app: AppHandle,
+    name: Option<String>,
+) -> CommandResult<CallRecordingInfo> {
+    let mut slot = CALL_RECORDING
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if slot.is_some() {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "A call recording is already running",
+        ));
+    }
+    let settings = current_settings(&app);
+    let config = RecordingConfig::from_settings(&settings);
+    if config.codec == RecordingCodec::Opus && crate::opus::resolve_sidecar(&app).is_none() {
+        return Err(CommandError::new(
+            ErrorCode::RuntimeMissing,
+            "The opus module is not installed; choose FLAC or WAV recording",
+        ));
+    }
+    let layout = CallLayout::from_setting(&settings.call_recording_layout);
+    let recordings_dir = recordings_dir_for(&app, &settings);
+    let now = Local::now();
+    let session_id = format!("{}_{}", now.format("%Y-%m-%d_%H%M%S"), CALL_SOURCE);
+    let tmp_dir = recordings_dir.join(format!(
+        "tmp_{}_{}",
+        now.format("%Y%m%d_%H%M%S"),
+        CALL_SOURCE
+    ));
+    fs::create_dir_all(&tmp_dir).map_err(|e| {
+        CommandError::new(
+            ErrorCode::Storage,
+            format!("Cannot create session temp dir {:?}: {}", tmp_dir, e),
+        )
+    })?;
+
+    *SYSTEM_TAP
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(CaptureBuffer::default());
+    let system_stop_tx = match start_system_tap(&app, &settings) {
+        Ok(stop_tx) => stop_tx,
+        Err(err) => {
+            SYSTEM_TAP
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .take();
+            let _ = fs::remove_dir_all(&tmp_dir);
+            return Err(CommandError::new(
+                ErrorCode::AudioDevice,
+                format!("Cannot capture system audio: {}", err),
+            ));
+        }
+    };
+
+    let (stop_tx, stop_rx) = mpsc::channel();
+    let (ready_tx, ready_rx) = mpsc::sync_channel(1);
+    let join = {
+        let tmp_dir = tmp_dir.clone();
+        let settings = settings.clone();
+        crate::util::spawn_guarded_with_result("call_recording", move || {
+            run_call_capture(settings, tmp_dir, layout, stop_rx, ready_tx)
+        })
+    };
+    let ready = ready_rx
+        .recv()
+        .unwrap_or_else(|_| Err("Call recording thread exited".to_string()));
+    if let Err(err) = ready {
+        let _ = system_stop_tx.send(());
+        SYSTEM_TAP
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take();
+        let _ = fs::remove_dir_all(&tmp_dir);
+        return Err(CommandError::new(ErrorCode::AudioDevice, err));
+    }
+
+    let name = name
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty());
+    let session = CallSession {
+        info: CallRecordingInfo {
+            session_id,
+            name,
+            layout: layout.as_str().to_string(),
+            codec: config.codec.extension().to_string(),
+            started_ms: crate::util::now_ms(),
+        },
+        tmp_dir,
+        recordings_dir,
+        config,
+        layout,
+        started_at_str: now.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+    };
+    write_call_manifest(&session.tmp_dir, &session, "recording", 0, Vec::new());
+    info!(
+        "Call recording started: {} ({})",
+        session.info.session_id, session.info.layout
+    );
+    let info = session.info.clone();
+    *slot = Some(CallRecording {
+        session,
+        stop_tx,
+        system_stop_tx,
+        join,
+    });
+    let _ = app.emit("call-recording:changed", Some(&info));
+    Ok(info)
+}
+
+/// Stop the call recording and return the final recording directory.
+#[tauri::command]
+pub(crate) async fn stop_call_recording(app: AppHandle) -> CommandResult<Option<String>> {
+    let Some(recording) = CALL_RECORDING
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .take()
+    else {
+        return Ok(None);
+    };
+    let _ = app.emit("call-recording:changed", None::<CallRecordingInfo>);
+    tauri::async_runtime::spawn_blocking(move || {
+        let CallRecording {
+            session,
+            stop_tx,
+            system_stop_tx,
+            join,
+        } = recording;
+        let _ = stop_tx.send(());
+        let result = join
+            .join()
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| Err("Call recording thread panicked".to_string()));
+        let _ = system_stop_tx.send(());
+        SYSTEM_TAP
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take();
+        let frames = result.with_code(ErrorCode::AudioDevice)?;
+        let dir = finish_call_recording(&app, &session, frames)
+            .inspect_err(|_| {
+                write_call_manifest(
+                    &session.tmp_dir,
+                    &session,
+                    "merge_failed",
+                    frames / 16_000,
+                    Vec::new(),
+                );
+            })
+            .with_code(ErrorCode::Storage)?;
+        info!("Call recording saved to {:?}", dir);
+        let _ = app.emit("recordings:changed", ());
+        Ok(Some(dir.to_string_lossy().to_string()))
+    })
+    .await
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("stop_call_recording task failed: {}", e),
+        )
+    })?
+}
+
+#[tauri::command]
+pub(crate) fn get_call_recording() -> Option<CallRecordingInfo> {
+    call_recording_info()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Transcript sessions (meeting grouping)
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptSegment {
+    pub entry_id: String,
+    pub text: String,
+    pub timestamp_ms: u64,
+    pub source: String,
+    #[serde(default)]
+    pub speaker_name: Option<String>,
+}
+
+/// AI summary attached to a session by `summarize_session`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSummaryNote {
+    pub style: String,
+    pub text: String,
+    pub provider: String,
+    pub model: String,
+    pub created_ms: u64,
+}
+
+/// Chapter boundary inside a transcript session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionChapter {
+    pub id: String,
+    pub timestamp_ms: u64,
+    /// Set by `rename_chapter`, else taken from the next transcript line;
+    /// empty until either happens.
+    #[serde(default)]
+    pub title: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptSession {
+    pub id: String,
+    pub name: String,
+    pub started_ms: u64,
+    pub ended_ms: Option<u64>,
+    pub segments: Vec<TranscriptSegment>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ai_summary: Option<SessionSummaryNote>,
+    #[serde(default)]
+    pub chapters: Vec<SessionChapter>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptSessionSummary {
+    pub id: String,
+    pub name: String,
+    pub started_ms: u64,
+    pub ended_ms: Option<u64>,
+    pub active: bool,
+    pub segment_count: usize,
+    pub word_count: usize,
+}
+
+impl TranscriptSession {
+    fn summary(&self, active: bool) -> TranscriptSessionSummary {
+        TranscriptSessionSummary {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            started_ms: self.started_ms,
+            ended_ms: self.ended_ms,
+            active,
+            segment_count: self.segments.len(),
+            word_count: self
+                .segments
+                .iter()
+                .map(|segment| segment.text.split_whitespace().count())
+                .sum(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct TranscriptSessionStore {
+    dir: Option<PathBuf>,
+    loaded: bool,
+    sessions: Vec<TranscriptSession>,
+    active_id: Option<String>,
+}
+
+impl TranscriptSessionStore {
+    fn ensure_loaded(&mut self) {
+        if self.loaded {
+            return;
+        }
+        self.loaded = true;
+        let Some(dir) = self.dir.as_ref() else {
+            return;
+        };
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let parsed = crate::history_crypto::read_to_string(&path).and_then(|raw| {
+                serde_json::from_str::<TranscriptSession>(&raw).map_err(|e| e.to_string())
+            });
+            match parsed {
+                // A session still open at shutdown is closed at its last segment.
+                Ok(mut session) => {
+                    if session.ended_ms.is_none() {
+                        session.ended_ms = Some(
+                            session
+                                .segments
+                                .last()
+                                .map(|segment| segment.timestamp_ms)
+                                .unwrap_or(session.started_ms),
+                        );
+                    }
+                    self.sessions.push(session);
+                }
+                Err(e) => warn!("Skipping unreadable transcript session {:?}: {}", path, e),
+            }
+        }
+        self.sessions.sort_by_key(|session| session.started_ms);
+    }
+
+    fn persist(&self, session_id: &str) {
+        let (Some(dir), Some(session)) = (
+            self.dir.as_ref(),
+            self.sessions
+                .iter()
+                .find(|session| session.id == session_id),
+        ) else {
+            return;
+        };
+        if let Err(e) = fs::create_dir_all(dir) {
+            error!("Cannot create transcript session dir {:?}: {}", dir, e);
+            return;
+        }
+        match serde_json::to_string_pretty(session) {
+            Ok(json) => {
+                let written = crate::history_crypto::seal(json.as_bytes()).and_then(|data| {
+                    fs::write(dir.join(format!("{}.json", session.id)), data)
+                        .map_err(|e| e.to_string())
+                });
+                if let Err(e) = written {
+                    error!("Failed to write transcript session {}: {}", session.id, e);
+                }
+            }
+            Err(e) => error!("Failed to serialize transcript session: {}", e),
+        }
+    }
+
+    fn active(&self) -> Option<&TranscriptSession> {
+        let id = self.active_id.as_deref()?;
+        self.sessions.iter().find(|session| session.id == id)
+    }
+
+    fn start(
+        &mut self,
+        name: Option<&str>,
+        now_ms: u64,
+    ) -> Result<TranscriptSessionSummary, String> {
+        self.ensure_loaded();
+        if let Some(active) = self.active() {
+            return Err(format!(
+                "Session '{}' is already running; stop it first",
+                active.name
+            ));
+        }
+        let name = name
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(String::from)
+            .unwrap_or_else(|| format!("Session {}", format_local_ms(now_ms, "%Y-%m-%d %H:%M")));
+        let session = TranscriptSession {
+            id: format!("ts_{}", now_ms),
+            name,
+            started_ms: now_ms,
+            ended_ms: None,
+            segments: Vec::new(),
+            ai_summary: None,
+            chapters: Vec::new(),
+        };
+        let summary = session.summary(true);
+        self.active_id = Some(session.id.clone());
+        self.sessions.push(session);
+        self.persist(&summary.id);
+        info!("Transcript session started: {}", summary.id);
+        Ok(summary)
+    }
+
+    fn stop(&mut self, now_ms: u64) -> Option<TranscriptSessionSummary> {
+        let id = self.active_id.take()?;
+        let session = self.sessions.iter_mut().find(|session| session.id == id)?;
+        session.ended_ms = Some(now_ms);
+        let summary = session.summary(false);
+        self.persist(&id);
+        info!(
+            "Transcript session stopped: {} ({} segments)",
+            id, summary.segment_count
+        );
+        Some(summary)
+    }
+
+    fn record(&mut self, entry: &HistoryEntry) -> bool {
+        let Some(id) = self.active_id.clone() else {
+            return false;
+        };
+        let Some(session) = self.sessions.iter_mut().find(|session| session.id == id) else {
+            return false;
+        };
+        session.segments.push(TranscriptSegment {
+            entry_id: entry.id.clone(),
+            text: entry.text.clone(),
+            timestamp_ms: entry.timestamp_ms,
+            source: entry.source.clone(),
+            speaker_name: entry.speaker_name.clone(),
+        });
+        for chapter in session.chapters.iter_mut() {
+            if chapter.title.is_empty() && chapter.timestamp_ms <= entry.timestamp_ms {
+                chapter.title = auto_chapter_title(&entry.text);
+            }
+        }
+        self.persist(&id);
+        true
+    }
+
+    fn add_chapter(&mut self, title: Option<&str>, now_ms: u64) -> Result<SessionChapter, String> {
+        self.ensure_loaded();
+        let id = self
+            .active_id
+            .clone()
+            .ok_or_else(|| "No transcript session is running".to_string())?;
+        let session = self
+            .sessions
+            .iter_mut()
+            .find(|session| session.id == id)
+            .ok_or_else(|| format!("Transcript session '{}' not found", id))?;
+        let mut chapter_id = format!("ch_{}", now_ms);
+        let mut suffix = 1;
+        while session
+            .chapters
+            .iter()
+            .any(|chapter| chapter.id == chapter_id)
+        {
+            suffix += 1;
+            chapter_id = format!("ch_{}_{}", now_ms, suffix);
+        }
+        let chapter = SessionChapter {
+            id: chapter_id,
+            timestamp_ms: now_ms,
+            title: title.map(str::trim).unwrap_or_default().to_string(),
+        };
+        session.chapters.push(chapter.clone());
+        session.chapters.sort_by_key(|chapter| chapter.timestamp_ms);
+        self.persist(&id);
+        Ok(chapter)
+    }
+
+    fn rename_chapter(
+        &mut self,
+        session_id: &str,
+        chapter_id: &str,
+        title: &str,
+    ) -> Result<SessionChapter, String> {
+        self.ensure_loaded();
+        let session = self
+            .sessions
+            .iter_mut()
+            .find(|session| session.id == session_id)
+            .ok_or_else(|| format!("Transcript session '{}' not found", session_id))?;
+        let chapter = session
+            .chapters
+            .iter_mut()
+            .find(|chapter| chapter.id == chapter_id)
+            .ok_or_else(|| format!("Chapter '{}' not found", chapter_id))?;
+        chapter.title = title.trim().to_string();
+        let renamed = chapter.clone();
+        self.persist(session_id);
+        Ok(renamed)
+    }
+
+    fn attach_summary(&mut self, session_id: &str, note: SessionSummaryNote) -> Result<(), String> {
+        self.ensure_loaded();
+        let session = self
+            .sessions
+            .iter_mut()
+            .find(|session| session.id == session_id)
+            .ok_or_else(|| format!("Transcript session '{}' not found", session_id))?;
+        session.ai_summary = Some(note);
+        self.persist(session_id);
+        Ok(())
+    }
+
+    fn list(&mut self) -> Vec<TranscriptSessionSummary> {
+        self.ensure_loaded();
+        self.sessions
+            .iter()
+            .rev()
+            .map(|session| session.summary(self.active_id.as_deref() == Some(session.id.as_str())))
+            .collect()
+    }
+
+    fn get(&mut self, session_id: &str) -> Result<TranscriptSession, String> {
+        self.ensure_loaded();
+        self.sessions
+            .iter()
+            .find(|session| session.id == session_id)
+            .cloned()
+            .ok_or_else(|| format!("Transcript session '{}' not found", session_id))
+    }
+}
+
+static TRANSCRIPT_SESSIONS: OnceLock<Mutex<TranscriptSessionStore>> = OnceLock::new();
+
+fn transcript_sessions() -> &'static Mutex<TranscriptSessionStore> {
+    TRANSCRIPT_SESSIONS.get_or_init(|| Mutex::new(TranscriptSessionStore::default()))
+}
+
+fn with_transcript_sessions<T>(
+    app: &AppHandle,
+    f: impl FnOnce(&mut TranscriptSessionStore) -> T,
+) -> Result<T, String> {
+    let mut store = transcript_sessions().lock().map_err(|e| e.to_string())?;
+    if store.dir.is_none() {
+        store.dir = Some(crate::paths::resolve_base_dir(app).join("transcript_sessions"));
+    }
+    Ok(f(&mut store))
+}
+
+/// Re-read sessions from disk after encrypted history is unlocked. Sessions
+/// already in memory (e.g. the running one) win over their files.
+pub(crate) fn reload_transcript_sessions(app: &AppHandle) {
+    let _ = with_transcript_sessions(app, |store| {
+        let in_memory = std::mem::take(&mut store.sessions);
+        store.loaded = false;
+        store.ensure_loaded();
+        for session in in_memory {
+            store.sessions.retain(|loaded| loaded.id != session.id);
+            store.sessions.push(session);
+        }
+        store.sessions.sort_by_key(|session| session.started_ms);
+    });
+}
+
+/// Append a freshly pushed history entry to the running transcript session.
+/// Cheap no-op when no session is active.
+pub(crate) fn record_transcript_entry(app: &AppHandle, entry: &HistoryEntry) {
+    let recorded = transcript_sessions()
+        .lock()
+        .map(|mut store| store.record(entry))
+        .unwrap_or(false);
+    if recorded {
+        let _ = app.emit("transcript-session:segment", &entry.id);
+    }
+}
+
+/// Id of the running transcript session, if any.
+pub(crate) fn active_session_id() -> Option<String> {
+    transcript_sessions()
+        .lock()
+        .ok()
+        .and_then(|store| store.active_id.clone())
+}
+
+/// Store `note` as the session's AI summary (replacing an older one).
+pub(crate) fn attach_session_summary(
+    app: &AppHandle,
+    session_id: &str,
+    note: SessionSummaryNote,
+) -> Result<(), String> {
+    with_transcript_sessions(app, |store| store.attach_summary(session_id, note))?
+}
+
+/// Markdown transcript of a stored session.
+pub(crate) fn session_markdown(app: &AppHandle, session_id: &str) -> Option<String> {
+    with_transcript_sessions(app, |store| store.get(session_id))
+        .ok()?
+        .ok()
+        .map(|session| render_session_markdown(&session))
+}
+
+/// Chapter title from a transcript line: its first sentence, at most
+/// `CHAPTER_TITLE_MAX_CHARS` long.
+fn auto_chapter_title(text: &str) -> String {
+    const CHAPTER_TITLE_MAX_CHARS: usize = 60;
+    let text = text.trim();
+    let sentence = text
+        .find(['.', '!', '?'])
+        .map(|end| &text[..end])
+        .unwrap_or(text)
+        .trim();
+    if sentence.chars().count() <= CHAPTER_TITLE_MAX_CHARS {
+        return sentence.to_string();
+    }
+    let mut title = String::new();
+    for word in sentence.split_whitespace() {
+        if title.chars().count() + word.chars().count() + 1 > CHAPTER_TITLE_MAX_CHARS {
+            break;
+        }
+        if !title.is_empty() {
+            title.push(' ');
+        }
+        title.push_str(word);
+    }
+    if title.is_empty() {
+        title = sentence.chars().take(CHAPTER_TITLE_MAX_CHARS).collect();
+    }
+    title.push('…');
+    title
+}
+
+/// Display title of the `index`-th chapter (0-based).
+fn chapter_title(chapter: &SessionChapter, index: usize) -> String {
+    if chapter.title.is_empty() {
+        format!("Chapter {}", index + 1)
+    } else {
+        chapter.title.clone()
+    }
+}
+
+/// Chapters starting at or before `timestamp_ms` that `*next` has not
+/// reached yet; advances `*next` past them.
+fn chapters_until<'a>(
+    chapters: &'a [SessionChapter],
+    next: &mut usize,
+    timestamp_ms: u64,
+) -> impl Iterator<Item = (usize, &'a SessionChapter)> {
+    let start = *next;
+    while *next < chapters.len() && chapters[*next].timestamp_ms <= timestamp_ms {
+        *next += 1;
+    }
+    chapters[start..*next]
+        .iter()
+        .enumerate()
+        .map(move |(offset, chapter)| (start + offset, chapter))
+}
+
+fn format_local_ms(ms: u64, fmt: &str) -> String {
+    chrono::DateTime::from_timestamp_millis(ms as i64)
+        .map(|utc| utc.with_timezone(&Local).format(fmt).to_string())
+        .unwrap_or_default()
+}
+
+fn format_offset(ms: u64) -> String {
+    let total_s = ms / 1000;
+    format!(
+        "{:02}:{:02}:{:02}",
+        total_s / 3600,
+        (total_s / 60) % 60,
+        total_s % 60
+    )
+}
+
+pub(crate) fn render_session_markdown(session: &TranscriptSession) -> String {
+    let mut out = format!("# {}\n\n", session.name);
+    out.push_str(&format!(
+        "- Started: {}\n",
+        format_local_ms(session.started_ms, "%Y-%m-%d %H:%M:%S")
+    ));
+    if let Some(ended_ms) = session.ended_ms {
+        out.push_str(&format!(
+            "- Ended: {}\n",
+            format_local_ms(ended_ms, "%Y-%m-%d %H:%M:%S")
+        ));
+        out.push_str(&format!(
+            "- Duration: {}\n",
+            format_offset(ended_ms.saturating_sub(session.started_ms))
+        ));
+    }
+    out.push_str(&format!("- Segments: {}\n\n", session.segments.len()));
+    if let Some(note) = session.ai_summary.as_ref() {
+        out.push_str(&format!("## AI summary\n\n{}\n\n", note.text.trim()));
+        out.push_str("## Transcript\n\n");
+    }
+
+    let mut next_chapter = 0;
+    let mut push_chapters = |out: &mut String, until_ms: u64| {
+        for (index, chapter) in chapters_until(&session.chapters, &mut next_chapter, until_ms) {
+            out.push_str(&format!(
+                "### [{}] {}\n\n",
+                format_offset(chapter.timestamp_ms.saturating_sub(session.started_ms)),
+                chapter_title(chapter, index)
+            ));
+        }
+    };
+    for segment in &session.segments {
+        push_chapters(&mut out, segment.timestamp_ms);
+        let speaker = segment
+            .speaker_name
+            .as_deref()
+            .filter(|name| !name.trim().is_empty())
+            .unwrap_or(segment.source.as_str());
+        out.push_str(&format!(
+            "**[{}] {}:** {}\n\n",
+            format_offset(segment.timestamp_ms.saturating_sub(session.started_ms)),
+            speaker,
+            segment.text.trim()
+        ));
+    }
+    push_chapters(&mut out, u64::MAX);
+    out
+}
+
+/// Subtitles for a session, one cue per segment from its offset to the next
+/// segment's. The first cue of every chapter starts with the chapter title.
+pub(crate) fn render_session_srt(session: &TranscriptSession) -> String {
+    const LAST_CUE_MS: u64 = 3_000;
+    let mut next_chapter = 0;
+    let cues: Vec<crate::file_transcription::FileTranscriptSegment> = session
+        .segments
+        .iter()
+        .enumerate()
+        .map(|(index, segment)| {
+            let start_ms = segment.timestamp_ms.saturating_sub(session.started_ms);
+            let end_ms = session
+                .segments
+                .get(index + 1)
+                .map(|next| next.timestamp_ms)
+                .or(session.ended_ms)
+                .map(|end| end.saturating_sub(session.started_ms))
+                .filter(|end| *end > start_ms)
+                .unwrap_or(start_ms + LAST_CUE_MS);
+            let mut text = String::new();
+            for (chapter_index, chapter) in
+                chapters_until(&session.chapters, &mut next_chapter, segment.timestamp_ms)
+            {
+                text.push_str(&format!("[{}]\n", chapter_title(chapter, chapter_index)));
+            }
+            text.push_str(segment.text.trim());
+            crate::file_transcription::FileTranscriptSegment {
+                start_ms,
+                end_ms,
+                text,
+            }
+        })
+        .collect();
+    crate::watch_folders::render_srt(&cues)
+}
+
+/// Mark a chapter at the current moment of the running transcript session
+/// and announce it with `chapter:detected`.
+pub(crate) fn mark_chapter(app: &AppHandle, title: Option<&str>) -> Result<SessionChapter, String> {
+    let chapter =
+        with_transcript_sessions(app, |store| store.add_chapter(title, crate::util::now_ms()))??;
+    let _ = app.emit("chapter:detected", &chapter);
+    Ok(chapter)
+}
+
+#[tauri::command]
+pub(crate) fn start_transcript_session(
+    app: AppHandle,
+    name: Option<String>,
+) -> CommandResult<TranscriptSessionSummary> {
+    let summary = with_transcript_sessions(&app, |store| {
+        store.start(name.as_deref(), crate::util::now_ms())
+    })
+    .with_code(ErrorCode::Internal)?
+    .with_code(ErrorCode::InvalidInput)?;
+    let _ = app.emit("transcript-session:changed", Some(&summary));
+    Ok(summary)
+}
+
+#[tauri::command]
+pub(crate) fn stop_transcript_session(
+    app: AppHandle,
+) -> CommandResult<Option<TranscriptSessionSummary>> {
+    let summary = with_transcript_sessions(&app, |store| store.stop(crate::util::now_ms()))
+        .with_code(ErrorCode::Internal)?;
+    let _ = app.emit(
+        "transcript-session:changed",
+        None::<TranscriptSessionSummary>,
+    );
+    if let Some(summary) = summary.as_ref() {
+        crate::webhooks::notify_session_end(&app, summary);
+    }
+    Ok(summary)
+}
+
+#[tauri::command]
+pub(crate) fn list_sessions(app: AppHandle) -> CommandResult<Vec<TranscriptSessionSummary>> {
+    with_transcript_sessions(&app, |store| store.list()).with_code(ErrorCode::Internal)
+}
+
+#[tauri::command]
+pub(crate) fn get_session_transcript(
+    app: AppHandle,
+    session_id: String,
+) -> CommandResult<TranscriptSession> {
+    with_transcript_sessions(&app, |store| store.get(&session_id))
+        .with_code(ErrorCode::Internal)?
+        .with_code(ErrorCode::NotFound)
+}
+
+/// Render a session as Markdown. When `path` is given the document is also
+/// written there; the Markdown is returned either way.
+#[tauri::command]
+pub(crate) fn export_session_markdown(
+    app: AppHandle,
+    session_id: String,
+    path: Option<String>,
+) -> CommandResult<String> {
+    let session = get_session_transcript(app, session_id)?;
+    let markdown = render_session_markdown(&session);
+    if let Some(path) = path.filter(|path| !path.trim().is_empty()) {
+        fs::write(&path, &markdown).map_err(|e| {
+            CommandError::new(
+                ErrorCode::Storage,
+                format!("Failed to write session export '{}': {}", path, e),
+            )
+        })?;
+    }
+    Ok(markdown)
+}
+
+/// Render a session as SRT subtitles; written to `path` when given.
+#[tauri::command]
+pub(crate) fn export_session_srt(
+    app: AppHandle,
+    session_id: String,
+    path: Option<String>,
+) -> CommandResult<String> {
+    let session = get_session_transcript(app, session_id)?;
+    let srt = render_session_srt(&session);
+    if let Some(path) = path.filter(|path| !path.trim().is_empty()) {
+        fs::write(&path, &srt).map_err(|e| {
+            CommandError::new(
+                ErrorCode::Storage,
+                format!("Failed to write session export '{}': {}", path, e),
+            )
+        })?;
+    }
+    Ok(srt)
+}
+
+/// Start a chapter in the running session; without a title it is named
+/// after the next transcript line.
+#[tauri::command]
+pub(crate) fn add_chapter(app: AppHandle, title: Option<String>) -> CommandResult<SessionChapter> {
+    mark_chapter(&app, title.as_deref()).with_code(ErrorCode::InvalidInput)
+}
+
+/// Chapters of a session, oldest first; the running session by default.
+#[tauri::command]
+pub(crate) fn list_chapters(
+    app: AppHandle,
+    session_id: Option<String>,
+) -> CommandResult<Vec<SessionChapter>> {
+    let session_id = session_id.or_else(active_session_id).ok_or_else(|| {
+        CommandError::new(ErrorCode::InvalidInput, "No transcript session is running")
+    })?;
+    Ok(get_session_transcript(app, session_id)?.chapters)
+}
+
+#[tauri::command]
+pub(crate) fn rename_chapter(
+    app: AppHandle,
+    session_id: String,
+    chapter_id: String,
+    title: String,
+) -> CommandResult<SessionChapter> {
+    with_transcript_sessions(&app, |store| {
+        store.rename_chapter(&session_id, &chapter_id, &title)
+    })
+    .with_code(ErrorCode::Internal)?
+    .with_code(ErrorCode::NotFound)
+}
+
+#[tauri::command]
+pub(crate) fn save_crash_recovery(app: AppHandle, content: String) -> CommandResult<()> {
+    let data_dir = crate::paths::resolve_base_dir(&app);
+    let _ = std::fs::create_dir_all(&data_dir);
+
+    let crash_file = data_dir.join(".crash_recovery.json");
+    std::fs::write(&crash_file, content).map_err(|e| {
+        CommandError::new(
+            ErrorCode::Storage,
+            format!("Failed to save crash recovery: {}", e),
+        )
+    })?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) fn clear_crash_recovery(app: AppHandle) -> CommandResult<()> {
+    let data_dir = crate::paths::resolve_base_dir(&app);
+
+    let crash_file = data_dir.join(".crash_recovery.json");
+    if crash_file.exists() {
+        std::fs::remove_file(&crash_file).map_err(|e| {
+            CommandError::new(
+                ErrorCode::Storage,
+                format!("Failed to clear crash recovery: {}", e),
+            )
+        })?;
+    }
+
+    let legacy_temp = if cfg!(windows) {
+        std::env::var("TEMP").ok()
+    } else {
+        std::env::var("TMPDIR").ok().or(Some("/tmp".to_string()))
+    };
+    if let Some(temp_dir) = legacy_temp {
+        let legacy_file = PathBuf::from(&temp_dir).join("trispr_crash_recovery.json");
+        let _ = std::fs::remove_file(&legacy_file);
+    }
+
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Helpers
+// ─────────────────────────────────────────────────────────────────────────────
+
+pub(crate) fn write_wav_i16(path: &PathBuf, samples: &[i16]) -> Result<(), String> {
+    let spec = WavSpec {
+        channels: 1,
+        sample_rate: 16_000,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+    let mut writer = WavWriter::create(path, spec)
+        .map_err(|e| format!("Cannot create chunk WAV {:?}: {}", path, e))?;
+    for &s in samples {
+        writer
+            .write_sample(s)
+            .map_err(|e| format!("WAV write error: {}", e))?;
+    }
+    writer
+        .finalize()
+        .map_err(|e| format!("WAV finalize error: {}", e))?;
+    Ok(())
+}
+
+/// Final directory for a merged session: `<date>_<name>` for named
+/// sessions, the session id otherwise.
+fn final_dir_name(session_id: &str, session_name: Option<&str>) -> String {
+    match session_name {
+        Some(name) => format!(
+            "{}_{}",
+            Local::now().format("%Y-%m-%d"),
+            sanitize_name(name)
+        ),
+        None => session_id.to_string(),
+    }
+}
+
+fn sanitize_name(name: &str) -> String {
+    let s: String = name
+        .chars()
         .map(|c| {
             if c.is_alphanumeric() || c == '-' || c == '_' {
                 c
@@ -474,3 +2246,163 @@ fn sanitize_name(name: &str) -> String {
         trimmed
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        interleave_stereo, pad_track, recordings_to_prune, render_session_markdown,
+        render_session_srt, RecordingInfo, TranscriptSessionStore, CALL_MAX_LAG_SAMPLES,
+    };
+    use crate::state::HistoryEntry;
+
+    fn entry(id: &str, text: &str, timestamp_ms: u64, source: &str) -> HistoryEntry {
+        HistoryEntry {
+            id: id.to_string(),
+            text: text.to_string(),
+            timestamp_ms,
+            source: source.to_string(),
+            speaker_name: None,
+            refinement: None,
+            audio_path: None,
+            revisions: Vec::new(),
+            pinned: false,
+            tags: Vec::new(),
+            note: None,
+            formatted_text: None,
+            language: None,
+            translation: None,
+        }
+    }
+
+    #[test]
+    fn transcript_session_groups_entries_only_while_active() {
+        let mut store = TranscriptSessionStore::default();
+        assert!(!store.record(&entry("o_1", "before", 500, "output")));
+
+        let started = store.start(Some("Weekly sync"), 1_000).unwrap();
+        assert!(store.start(None, 1_500).is_err());
+        assert!(store.record(&entry("o_2", "hello there", 2_000, "output")));
+        assert!(store.record(&entry("h_3", "hi", 3_000, "mic")));
+        let stopped = store.stop(4_000).unwrap();
+        assert!(!store.record(&entry("o_4", "after", 5_000, "output")));
+
+        assert_eq!(stopped.id, started.id);
+        assert_eq!(stopped.segment_count, 2);
+        assert_eq!(stopped.word_count, 3);
+        let listed = store.list();
+        assert_eq!(listed.len(), 1);
+        assert!(!listed[0].active);
+    }
+
+    #[test]
+    fn session_markdown_uses_offsets_and_speakers() {
+        let mut store = TranscriptSessionStore::default();
+        let summary = store.start(Some("Standup"), 10_000).unwrap();
+        let mut remote = entry("o_1", " Shipping today. ", 75_000, "output");
+        remote.speaker_name = Some("Remote".to_string());
+        store.record(&remote);
+        store.record(&entry("h_2", "Sounds good", 3_610_000, "mic"));
+        store.stop(3_620_000);
+
+        let markdown = render_session_markdown(&store.get(&summary.id).unwrap());
+        assert!(markdown.starts_with("# Standup\n"));
+        assert!(markdown.contains("- Duration: 01:00:10\n"));
+        assert!(markdown.contains("**[00:01:05] Remote:** Shipping today.\n"));
+        assert!(markdown.contains("**[01:00:00] mic:** Sounds good\n"));
+    }
+
+    #[test]
+    fn chapters_take_titles_and_split_exports() {
+        let mut store = TranscriptSessionStore::default();
+        assert!(store.add_chapter(None, 500).is_err());
+        let summary = store.start(Some("Planning"), 0).unwrap();
+        store.record(&entry("o_1", "Welcome everyone", 1_000, "output"));
+        let budget = store.add_chapter(None, 2_000).unwrap();
+        store.add_chapter(Some("Hiring"), 4_000).unwrap();
+        store.record(&entry("o_2", "Next the budget. It grew.", 3_000, "output"));
+        store.record(&entry("o_3", "Two open roles", 5_000, "output"));
+        store.stop(6_000);
+
+        let session = store.get(&summary.id).unwrap();
+        assert_eq!(session.chapters[0].title, "Next the budget");
+        assert_eq!(session.chapters[1].title, "Hiring");
+        let renamed = store
+            .rename_chapter(&summary.id, &budget.id, " Budget ")
+            .unwrap();
+        assert_eq!(renamed.title, "Budget");
+
+        let session = store.get(&summary.id).unwrap();
+        let markdown = render_session_markdown(&session);
+        let budget_at = markdown.find("### [00:00:02] Budget\n").unwrap();
+        assert!(markdown.find("Welcome everyone").unwrap() < budget_at);
+        assert!(budget_at < markdown.find("Next the budget").unwrap());
+        assert!(markdown.contains("### [00:00:04] Hiring\n\n**[00:00:05]"));
+
+        let srt = render_session_srt(&session);
+        assert!(srt.starts_with("1\n00:00:01,000 --> 00:00:03,000\nWelcome everyone\n"));
+        assert!(srt.contains("00:00:03,000 --> 00:00:05,000\n[Budget]\nNext the budget."));
+        assert!(srt.contains("00:00:05,000 --> 00:00:06,000\n[Hiring]\nTwo open roles\n"));
+    }
+
+    fn recording(id: &str, modified_ms: u64, size_bytes: u64) -> RecordingInfo {
+        RecordingInfo {
+            id: id.to_string(),
+            name: None,
+            source: "output".to_string(),
+            started_at: String::new(),
+            ended_at: None,
+            duration_s: 60,
+            path: String::new(),
+            size_bytes,
+            codec: "opus".to_string(),
+            modified_ms,
+            tracks: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn prune_removes_expired_then_oldest_over_size_cap() {
+        const DAY: u64 = 86_400_000;
+        let now = 100 * DAY;
+        let recordings = vec![
+            recording("new", now - DAY, 400),
+            recording("ancient", now - 40 * DAY, 100),
+            recording("mid", now - 5 * DAY, 300),
+            recording("old", now - 10 * DAY, 500),
+        ];
+        assert!(recordings_to_prune(&recordings, now, 0, 0).is_empty());
+        assert_eq!(
+            recordings_to_prune(&recordings, now, 30, 0),
+            vec!["ancient"]
+        );
+        assert_eq!(
+            recordings_to_prune(&recordings, now, 30, 800),
+            vec!["ancient", "old"]
+        );
+        assert_eq!(
+            recordings_to_prune(&recordings, now, 0, 400),
+            vec!["ancient", "old", "mid"]
+        );
+    }
+
+    #[test]
+    fn call_tracks_are_padded_to_the_wall_clock_and_interleaved() {
+        // Silent loopback: nothing arrived, so the track is filled up to the lag.
+        let mut system = Vec::new();
+        pad_track(&mut system, 0, CALL_MAX_LAG_SAMPLES + 100);
+        assert_eq!(system, vec![0; 100]);
+        // Within the lag window nothing is inserted.
+        let mut mic = vec![7; 50];
+        pad_track(&mut mic, 100, CALL_MAX_LAG_SAMPLES + 120);
+        assert_eq!(mic.len(), 50);
+
+        let mut mic = vec![1, 2, 3];
+        let mut system = vec![-1, -2];
+        assert_eq!(
+            interleave_stereo(&mut mic, &mut system, 2),
+            vec![1, -1, 2, -2]
+        );
+        assert_eq!(mic, vec![3]);
+        assert!(system.is_empty());
+    }
+}
@@ -0,0 +1,269 @@
+//! AI summaries of transcript sessions.
+//!
+//! The transcript is split into chunks that fit a local model's context, each
+//! chunk is summarized with the configured AI refinement backend, and the
+//! partial results are merged in further passes until one remains. The
+//! summary is attached to the session and included in its Markdown export.
+
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::ai_fallback::models::RefinementOptions;
+use crate::ai_fallback::RefinementSetup;
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
+use crate::session_manager::{SessionSummaryNote, TranscriptSession};
+use crate::state::{AppState, Settings};
+
+/// Stays below the word cap local providers apply to a single request.
+const CHUNK_MAX_WORDS: usize = 1_200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SummaryStyle {
+    Bullets,
+    ActionItems,
+    /// Bullet summary followed by action items.
+    Meeting,
+}
+
+impl SummaryStyle {
+    fn from_setting(value: &str) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "" | "bullets" | "summary" => Ok(Self::Bullets),
+            "action_items" | "actions" => Ok(Self::ActionItems),
+            "meeting" => Ok(Self::Meeting),
+            other => Err(format!("Unknown summary style '{}'", other)),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Bullets => "bullets",
+            Self::ActionItems => "action_items",
+            Self::Meeting => "meeting",
+        }
+    }
+
+    /// Refinement prompt profiles to run, with their section headings.
+    fn passes(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::Bullets => &[("summary", "Summary")],
+            Self::ActionItems => &[("action_items", "Action items")],
+            Self::Meeting => &[("summary", "Summary"), ("action_items", "Action items")],
+        }
+    }
+}
+
+fn transcript_lines(session: &TranscriptSession) -> Vec<String> {
+    session
+        .segments
+        .iter()
+        .filter(|segment| !segment.text.trim().is_empty())
+        .map(|segment| {
+            let speaker = segment
+                .speaker_name
+                .as_deref()
+                .filter(|name| !name.trim().is_empty())
+                .unwrap_or(segment.source.as_str());
+            format!("{}: {}", speaker, segment.text.trim())
+        })
+        .collect()
+}
+
+/// Greedily packs whole lines into chunks of at most `max_words`; a longer
+/// line becomes a chunk of its own.
+fn chunk_lines(lines: &[String], max_words: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    let mut words = 0;
+    for line in lines {
+        let line_words = line.split_whitespace().count();
+        if !current.is_empty() && words + line_words > max_words {
+            chunks.push(current.join("\n"));
+            current.clear();
+            words = 0;
+        }
+        current.push(line);
+        words += line_words;
+    }
+    if !current.is_empty() {
+        chunks.push(current.join("\n"));
+    }
+    chunks
+}
+
+fn word_count(texts: &[String]) -> usize {
+    texts
+        .iter()
+        .map(|text| text.split_whitespace().count())
+        .sum()
+}
+
+/// Repacks partial summaries for the next pass. They are split into lines
+/// first, so they fit the word budget even when no two fit together whole.
+fn repack_partials(partials: &[String]) -> Vec<String> {
+    let lines: Vec<String> = partials
+        .iter()
+        .flat_map(|partial| partial.lines())
+        .filter(|line| !line.trim().is_empty())
+        .map(str::to_string)
+        .collect();
+    chunk_lines(&lines, CHUNK_MAX_WORDS)
+}
+
+fn refine(
+    setup: &RefinementSetup,
+    options: &RefinementOptions,
+    text: &str,
+) -> Result<String, String> {
+    let result = setup
+        .provider
+        .refine_transcript(text, &setup.model, options, &setup.api_key)
+        .map_err(|e| e.to_string())?;
+    let text = crate::ai_fallback::provider::strip_thinking_tags(&result.text);
+    if text.trim().is_empty() {
+        return Err("The model returned an empty summary".to_string());
+    }
+    Ok(text.trim().to_string())
+}
+
+/// Map/reduce over `chunks` with one prompt profile.
+fn summarize_chunks(
+    setup: &RefinementSetup,
+    settings: &Settings,
+    profile: &str,
+    chunks: Vec<String>,
+) -> Result<String, String> {
+    let language = setup.options.language.as_deref().unwrap_or("auto");
+    let options = RefinementOptions {
+        custom_prompt: crate::ai_fallback::provider::prompt_for_profile(
+            profile,
+            language,
+            None,
+            settings.ai_fallback.preserve_source_language,
+        ),
+        enforce_language_guard: false,
+        prompt_profile: profile.to_string(),
+        ..setup.options.clone()
+    };
+    let mut parts = chunks;
+    loop {
+        let partials = parts
+            .iter()
+            .map(|chunk| refine(setup, &options, chunk))
+            .collect::<Result<Vec<_>, _>>()?;
+        if partials.len() == 1 {
+            return Ok(partials.into_iter().next().unwrap_or_default());
+        }
+        // Each pass must shrink the text, or the reduction would never end.
+        if word_count(&partials) >= word_count(&parts) {
+            return Err("The model's partial summaries are not getting shorter".to_string());
+        }
+        parts = repack_partials(&partials);
+    }
+}
+
+fn summarize(
+    app: &AppHandle,
+    settings: &Settings,
+    session: &TranscriptSession,
+    style: SummaryStyle,
+) -> CommandResult<SessionSummaryNote> {
+    let chunks = chunk_lines(&transcript_lines(session), CHUNK_MAX_WORDS);
+    if chunks.is_empty() {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            format!("Session '{}' has no transcript yet", session.name),
+        ));
+    }
+    let setup =
+        crate::ai_fallback::prepare_refinement(app, settings).with_code(ErrorCode::Network)?;
+    let passes = style.passes();
+    let mut sections = Vec::new();
+    for (profile, heading) in passes {
+        let text = summarize_chunks(&setup, settings, profile, chunks.clone())
+            .with_code(ErrorCode::Network)?;
+        sections.push(if passes.len() > 1 {
+            format!("### {}\n\n{}", heading, text)
+        } else {
+            text
+        });
+    }
+    Ok(SessionSummaryNote {
+        style: style.as_str().to_string(),
+        text: sections.join("\n\n"),
+        provider: settings.ai_fallback.provider.clone(),
+        model: setup.model.clone(),
+        created_ms: crate::util::now_ms(),
+    })
+}
+
+/// Summarize a transcript session (`style`: "bullets", "action_items" or
+/// "meeting") and attach the result to it, replacing an older summary.
+#[tauri::command]
+pub(crate) async fn summarize_session(
+    app: AppHandle,
+    session_id: String,
+    style: Option<String>,
+) -> CommandResult<SessionSummaryNote> {
+    let style = SummaryStyle::from_setting(style.as_deref().unwrap_or_default())
+        .with_code(ErrorCode::InvalidInput)?;
+    let session = crate::session_manager::get_session_transcript(app.clone(), session_id.clone())?;
+    let settings = app
+        .state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    let worker_app = app.clone();
+    let note = tauri::async_runtime::spawn_blocking(move || {
+        summarize(&worker_app, &settings, &session, style)
+    })
+    .await
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("summarize_session task failed: {}", e),
+        )
+    })??;
+    crate::session_manager::attach_session_summary(&app, &session_id, note.clone())
+        .with_code(ErrorCode::NotFound)?;
+    let _ = app.emit("transcript-session:summarized", &session_id);
+    Ok(note)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks_keep_lines_whole_and_respect_word_budget() {
+        let lines: Vec<String> = ["a b c", "d e", "f g h i", "j"]
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+        assert_eq!(chunk_lines(&lines, 5), vec!["a b c\nd e", "f g h i\nj"]);
+        assert_eq!(chunk_lines(&lines, 2), vec!["a b c", "d e", "f g h i", "j"]);
+        assert!(chunk_lines(&[], 5).is_empty());
+
+        // Two partials too long to pack together whole still fit line by line.
+        let half = vec!["w"; CHUNK_MAX_WORDS / 2].join(" ");
+        let partial = format!("{}\n{}", half, half);
+        let repacked = repack_partials(&[partial.clone(), partial]);
+        assert_eq!(repacked.len(), 2);
+        assert!(repacked
+            .iter()
+            .all(|chunk| word_count(&[chunk.clone()]) <= CHUNK_MAX_WORDS));
+
+        assert_eq!(
+            SummaryStyle::from_setting("").unwrap(),
+            SummaryStyle::Bullets
+        );
+        assert_eq!(
+            SummaryStyle::from_setting("Meeting")
+                .unwrap()
+                .passes()
+                .len(),
+            2
+        );
+        assert!(SummaryStyle::from_setting("haiku").is_err());
+    }
+}
@@ -0,0 +1,350 @@
+//! Named settings profiles ("Meetings", "Dictation DE", ...) for quick context
+//! switching.
+//!
+//! A profile stores a snapshot of the context-dependent settings listed in
+//! `PROFILE_KEYS` in `profiles.json` next to `settings.json`. Activating one
+//! merges those values into the current settings and saves them through the
+//! regular settings path, so hotkeys, storage paths and API keys are never
+//! touched.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fs;
+use tauri::menu::{CheckMenuItem, MenuItem, Submenu};
+use tauri::{AppHandle, Emitter, Manager, Wry};
+use tracing::{info, warn};
+
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
+use crate::paths::resolve_config_path;
+use crate::state::{AppState, Settings};
+
+const PROFILES_FILE: &str = "profiles.json";
+/// Tray menu item IDs are `profile:<name>`.
+pub(crate) const TRAY_PROFILE_PREFIX: &str = "profile:";
+const MAX_PROFILE_NAME_CHARS: usize = 48;
+
+/// Top-level settings keys captured by a profile.
+const PROFILE_KEYS: &[&str] = &[
+    "mode",
+    "input_device",
+    "input_channel",
+    "language_mode",
+    "language_pinned",
+    "translate_to_english",
+    "model",
+    "cloud_fallback",
+    "ai_fallback",
+    "capture_enabled",
+    "audio_cues",
+    "activation_words_enabled",
+    "transcribe_output_device",
+    "transcribe_vad_mode",
+    "postproc_enabled",
+    "postproc_llm_enabled",
+    "auto_paste_enabled",
+    "output_mode",
+    "output_template_mic",
+    "output_template_system",
+    "paste_rendition",
+    "history_rendition",
+    "continuous_dump_profile",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct SettingsProfile {
+    pub(crate) name: String,
+    pub(crate) values: Map<String, Value>,
+    pub(crate) updated_ms: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct SettingsProfileStore {
+    pub(crate) active: Option<String>,
+    pub(crate) profiles: Vec<SettingsProfile>,
+}
+
+impl SettingsProfileStore {
+    fn position(&self, name: &str) -> Option<usize> {
+        self.profiles
+            .iter()
+            .position(|profile| profile.name.eq_ignore_ascii_case(name))
+    }
+
+    fn upsert(&mut self, profile: SettingsProfile) {
+        match self.position(&profile.name) {
+            Some(index) => self.profiles[index] = profile,
+            None => self.profiles.push(profile),
+        }
+    }
+
+    /// Profile after the active one (wrapping), or the first when none is active.
+    fn next_after_active(&self) -> Option<&SettingsProfile> {
+        let start = self
+            .active
+            .as_deref()
+            .and_then(|name| self.position(name))
+            .map(|index| index + 1)
+            .unwrap_or(0);
+        self.profiles
+            .get(start % self.profiles.len().max(1))
+            .or(self.profiles.first())
+    }
+}
+
+fn normalize_profile_name(name: &str) -> Result<String, String> {
+    let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+    if name.is_empty() {
+        return Err("Profile name must not be empty".to_string());
+    }
+    if name.chars().count() > MAX_PROFILE_NAME_CHARS {
+        return Err(format!(
+            "Profile name must be at most {} characters",
+            MAX_PROFILE_NAME_CHARS
+        ));
+    }
+    Ok(name)
+}
+
+fn capture_profile_values(settings: &Settings) -> Result<Map<String, Value>, String> {
+    let Value::Object(all) = serde_json::to_value(settings).map_err(|e| e.to_string())? else {
+        return Err("Settings did not serialize to an object".to_string());
+    };
+    Ok(all
+        .into_iter()
+        .filter(|(key, _)| PROFILE_KEYS.contains(&key.as_str()))
+        .collect())
+}
+
+/// Current settings with the profile's values laid over them. Keys that are
+/// no longer profile keys are ignored.
+fn apply_profile_values(
+    settings: &Settings,
+    values: &Map<String, Value>,
+) -> Result<Settings, String> {
+    let mut merged = serde_json::to_value(settings).map_err(|e| e.to_string())?;
+    let Value::Object(target) = &mut merged else {
+        return Err("Settings did not serialize to an object".to_string());
+    };
+    for (key, value) in values {
+        if PROFILE_KEYS.contains(&key.as_str()) {
+            target.insert(key.clone(), value.clone());
+        }
+    }
+    serde_json::from_value(merged).map_err(|e| format!("Invalid profile values: {}", e))
+}
+
+pub(crate) fn load_profiles(app: &AppHandle) -> SettingsProfileStore {
+    let path = resolve_config_path(app, PROFILES_FILE);
+    match fs::read_to_string(&path) {
+        Ok(raw) => serde_json::from_str(&raw).unwrap_or_else(|err| {
+            warn!("Ignoring unreadable {}: {}", path.display(), err);
+            SettingsProfileStore::default()
+        }),
+        Err(_) => SettingsProfileStore::default(),
+    }
+}
+
+fn save_profiles(app: &AppHandle, store: &SettingsProfileStore) -> Result<(), String> {
+    let path = resolve_config_path(app, PROFILES_FILE);
+    let raw = serde_json::to_string_pretty(store).map_err(|e| e.to_string())?;
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, raw).map_err(|e| e.to_string())?;
+    fs::rename(&tmp_path, &path).map_err(|e| e.to_string())?;
+    let _ = app.emit("profiles:changed", store);
+    Ok(())
+}
+
+fn activate_profile_inner(app: &AppHandle, name: &str) -> CommandResult<Settings> {
+    let mut store = load_profiles(app);
+    let index = store.position(name).ok_or_else(|| {
+        CommandError::new(ErrorCode::NotFound, format!("Unknown profile: {}", name))
+    })?;
+    let profile = store.profiles[index].clone();
+
+    let current = app
+        .state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    let mut settings =
+        apply_profile_values(&current, &profile.values).with_code(ErrorCode::InvalidInput)?;
+    crate::save_settings_inner(app, &mut settings).with_code(ErrorCode::Storage)?;
+
+    store.active = Some(profile.name.clone());
+    save_profiles(app, &store).with_code(ErrorCode::Storage)?;
+    info!("Activated settings profile '{}'", profile.name);
+    Ok(settings)
+}
+
+#[tauri::command]
+pub(crate) fn list_profiles(app: AppHandle) -> SettingsProfileStore {
+    load_profiles(&app)
+}
+
+/// Saves the current settings as profile `name`, replacing a profile with the
+/// same name (case-insensitive).
+#[tauri::command]
+pub(crate) fn save_profile(app: AppHandle, name: String) -> CommandResult<SettingsProfile> {
+    let name = normalize_profile_name(&name).with_code(ErrorCode::InvalidInput)?;
+    let values = {
+        let state = app.state::<AppState>();
+        let settings = state
+            .settings
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        capture_profile_values(&settings).with_code(ErrorCode::Internal)?
+    };
+    let profile = SettingsProfile {
+        name,
+        values,
+        updated_ms: crate::util::now_ms(),
+    };
+    let mut store = load_profiles(&app);
+    store.upsert(profile.clone());
+    save_profiles(&app, &store).with_code(ErrorCode::Storage)?;
+    Ok(profile)
+}
+
+#[tauri::command]
+pub(crate) fn delete_profile(app: AppHandle, name: String) -> CommandResult<bool> {
+    let mut store = load_profiles(&app);
+    let Some(index) = store.position(&name) else {
+        return Ok(false);
+    };
+    let removed = store.profiles.remove(index);
+    if store
+        .active
+        .as_deref()
+        .is_some_and(|active| active.eq_ignore_ascii_case(&removed.name))
+    {
+        store.active = None;
+    }
+    save_profiles(&app, &store).with_code(ErrorCode::Storage)?;
+    Ok(true)
+}
+
+#[tauri::command]
+pub(crate) async fn activate_profile(app: AppHandle, name: String) -> CommandResult<Settings> {
+    tauri::async_runtime::spawn_blocking(move || activate_profile_inner(&app, &name))
+        .await
+        .map_err(|e| {
+            CommandError::new(
+                ErrorCode::Internal,
+                format!("activate_profile task failed: {}", e),
+            )
+        })?
+}
+
+/// Hotkey and tray entry point; errors are surfaced as app errors.
+pub(crate) fn activate_profile_async(app: AppHandle, name: Option<String>) {
+    crate::util::spawn_guarded("activate_profile", move || {
+        let name = match name {
+            Some(name) => name,
+            None => match load_profiles(&app).next_after_active() {
+                Some(profile) => profile.name.clone(),
+                None => return,
+            },
+        };
+        match activate_profile_inner(&app, &name) {
+            Ok(_) => {
+                crate::audio_cues::play_cue(&app, "start");
+            }
+            Err(err) => crate::emit_error(
+                &app,
+                crate::errors::AppError::Storage(err.message),
+                Some("Settings profiles"),
+            ),
+        }
+    });
+}
+
+/// Replaces the tray "Profiles" submenu items with one check item per profile.
+pub(crate) fn populate_tray_submenu(
+    app: &AppHandle,
+    submenu: &Submenu<Wry>,
+    store: &SettingsProfileStore,
+) -> tauri::Result<()> {
+    for item in submenu.items()? {
+        submenu.remove(&item)?;
+    }
+    if store.profiles.is_empty() {
+        let placeholder = MenuItem::new(app, "No profiles saved", false, None::<&str>)?;
+        return submenu.append(&placeholder);
+    }
+    for profile in &store.profiles {
+        let active = store
+            .active
+            .as_deref()
+            .is_some_and(|name| name.eq_ignore_ascii_case(&profile.name));
+        let item = CheckMenuItem::with_id(
+            app,
+            format!("{}{}", TRAY_PROFILE_PREFIX, profile.name),
+            &profile.name,
+            true,
+            active,
+            None::<&str>,
+        )?;
+        submenu.append(&item)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(name: &str) -> SettingsProfile {
+        SettingsProfile {
+            name: name.to_string(),
+            values: Map::new(),
+            updated_ms: 0,
+        }
+    }
+
+    #[test]
+    fn profile_round_trip_only_touches_profile_keys() {
+        let mut meeting = Settings::default();
+        meeting.language_mode = "de".to_string();
+        meeting.mode = "vad".to_string();
+        meeting.hotkey_ptt = "F9".to_string();
+        let values = capture_profile_values(&meeting).unwrap();
+        assert!(values.contains_key("language_mode"));
+        assert!(!values.contains_key("hotkey_ptt"));
+
+        let mut current = Settings::default();
+        current.hotkey_ptt = "F10".to_string();
+        let applied = apply_profile_values(&current, &values).unwrap();
+        assert_eq!(applied.language_mode, "de");
+        assert_eq!(applied.mode, "vad");
+        assert_eq!(applied.hotkey_ptt, "F10");
+    }
+
+    #[test]
+    fn store_upserts_by_name_and_cycles() {
+        let mut store = SettingsProfileStore::default();
+        assert!(store.next_after_active().is_none());
+        store.upsert(profile("Meetings"));
+        store.upsert(profile("Gaming"));
+        store.upsert(profile("meetings"));
+        assert_eq!(store.profiles.len(), 2);
+        assert_eq!(store.profiles[0].name, "meetings");
+
+        assert_eq!(store.next_after_active().unwrap().name, "meetings");
+        store.active = Some("Gaming".to_string());
+        assert_eq!(store.next_after_active().unwrap().name, "meetings");
+        store.active = Some("MEETINGS".to_string());
+        assert_eq!(store.next_after_active().unwrap().name, "Gaming");
+    }
+
+    #[test]
+    fn profile_names_are_trimmed_and_bounded() {
+        assert_eq!(
+            normalize_profile_name("  Dictation   DE ").unwrap(),
+            "Dictation DE"
+        );
+        assert!(normalize_profile_name("   ").is_err());
+        assert!(normalize_profile_name(&"x".repeat(49)).is_err());
+    }
+}
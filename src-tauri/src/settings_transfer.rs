@@ -0,0 +1,169 @@
+//! Export and import of `settings.json` for moving a configuration between
+//! machines or releases.
+//!
+//! Exports are the versioned on-disk form without the legacy plaintext API
+//! key. Imports run the same migrations and sanitizing as `load_settings` and
+//! keep fields that only make sense on this machine.
+
+use std::fs;
+use std::path::Path;
+use tauri::{AppHandle, Manager};
+use tracing::info;
+
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
+use crate::state::{
+    migrate_settings, persisted_settings, sanitize_settings, AppState, Settings,
+    SETTINGS_SCHEMA_VERSION,
+};
+
+/// Serialized settings for an export file.
+fn export_json(settings: &Settings) -> Result<String, String> {
+    let mut exported = persisted_settings(settings);
+    exported.postproc_llm_api_key.clear();
+    serde_json::to_string_pretty(&exported).map_err(|e| e.to_string())
+}
+
+/// Parses, migrates and sanitizes an exported settings file.
+fn parse_import(raw: &str) -> Result<Settings, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(raw).map_err(|e| format!("Not a settings file: {}", e))?;
+    if !value.is_object() {
+        return Err("Not a settings file: expected a JSON object".to_string());
+    }
+    let mut settings: Settings =
+        serde_json::from_value(value).map_err(|e| format!("Invalid settings file: {}", e))?;
+    if settings.schema_version > SETTINGS_SCHEMA_VERSION {
+        return Err(format!(
+            "Settings were exported by a newer version (schema {}, this build supports {})",
+            settings.schema_version, SETTINGS_SCHEMA_VERSION
+        ));
+    }
+    migrate_settings(&mut settings);
+    sanitize_settings(&mut settings);
+    Ok(settings)
+}
+
+/// Devices, paths, window placement, secrets and the history encryption mode
+/// (which needs its key and a migration, see `history_crypto`) stay as they
+/// are locally.
+fn keep_machine_local_fields(imported: &mut Settings, current: &Settings) {
+    imported.input_device = current.input_device.clone();
+    imported.input_channel = current.input_channel;
+    imported.device_input_profiles = current.device_input_profiles.clone();
+    imported.transcribe_output_device = current.transcribe_output_device.clone();
+    imported.ptt_gamepad_device = current.ptt_gamepad_device.clone();
+    imported.model_storage_dir = current.model_storage_dir.clone();
+    imported.main_window_x = current.main_window_x;
+    imported.main_window_y = current.main_window_y;
+    imported.main_window_width = current.main_window_width;
+    imported.main_window_height = current.main_window_height;
+    imported.main_window_monitor = current.main_window_monitor.clone();
+    imported.assistant_presence_window_x = current.assistant_presence_window_x;
+    imported.assistant_presence_window_y = current.assistant_presence_window_y;
+    imported.assistant_presence_window_width = current.assistant_presence_window_width;
+    imported.assistant_presence_window_height = current.assistant_presence_window_height;
+    imported.assistant_presence_window_monitor = current.assistant_presence_window_monitor.clone();
+    if imported.postproc_llm_api_key.is_empty() {
+        imported.postproc_llm_api_key = current.postproc_llm_api_key.clone();
+    }
+    imported.transcribe_enabled = current.transcribe_enabled;
+    imported.history_encryption_mode = current.history_encryption_mode.clone();
+}
+
+fn current_settings(app: &AppHandle) -> Settings {
+    app.state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+/// Writes the current settings to `path`.
+#[tauri::command]
+pub(crate) fn export_settings(app: AppHandle, path: String) -> CommandResult<()> {
+    let raw = export_json(&current_settings(&app)).with_code(ErrorCode::Internal)?;
+    fs::write(Path::new(&path), raw).map_err(|e| {
+        CommandError::new(
+            ErrorCode::Storage,
+            format!("Failed to write settings: {}", e),
+        )
+    })?;
+    info!("Exported settings to {}", path);
+    Ok(())
+}
+
+/// Replaces the current settings with the file at `path` and returns the
+/// applied settings.
+#[tauri::command]
+pub(crate) async fn import_settings(app: AppHandle, path: String) -> CommandResult<Settings> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let raw = fs::read_to_string(Path::new(&path)).map_err(|e| {
+            CommandError::new(
+                ErrorCode::Storage,
+                format!("Failed to read settings: {}", e),
+            )
+        })?;
+        let mut settings = parse_import(&raw).with_code(ErrorCode::InvalidInput)?;
+        keep_machine_local_fields(&mut settings, &current_settings(&app));
+        crate::save_settings_inner(&app, &mut settings).with_code(ErrorCode::Storage)?;
+        info!("Imported settings from {}", path);
+        Ok(settings)
+    })
+    .await
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("import_settings task failed: {}", e),
+        )
+    })?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_round_trips_without_secrets() {
+        let settings = Settings {
+            language_mode: "de".to_string(),
+            postproc_llm_api_key: "sk-secret".to_string(),
+            ..Settings::default()
+        };
+        let raw = export_json(&settings).unwrap();
+        assert!(!raw.contains("sk-secret"));
+        let imported = parse_import(&raw).unwrap();
+        assert_eq!(imported.language_mode, "de");
+        assert_eq!(imported.schema_version, SETTINGS_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn import_rejects_newer_schema_and_non_objects() {
+        let newer = format!(r#"{{"schema_version": {}}}"#, SETTINGS_SCHEMA_VERSION + 1);
+        assert!(parse_import(&newer).unwrap_err().contains("newer version"));
+        assert!(parse_import("[1, 2]").is_err());
+        assert!(parse_import("not json").is_err());
+    }
+
+    #[test]
+    fn import_keeps_machine_local_fields() {
+        let current = Settings {
+            input_device: "usb-mic".to_string(),
+            model_storage_dir: "D:/models".to_string(),
+            postproc_llm_api_key: "local-key".to_string(),
+            history_encryption_mode: "passphrase".to_string(),
+            ..Settings::default()
+        };
+        let mut imported = Settings {
+            input_device: "other-mic".to_string(),
+            history_encryption_mode: "off".to_string(),
+            language_mode: "fr".to_string(),
+            ..Settings::default()
+        };
+        keep_machine_local_fields(&mut imported, &current);
+        assert_eq!(imported.input_device, "usb-mic");
+        assert_eq!(imported.model_storage_dir, "D:/models");
+        assert_eq!(imported.postproc_llm_api_key, "local-key");
+        assert_eq!(imported.history_encryption_mode, "passphrase");
+        assert_eq!(imported.language_mode, "fr");
+    }
+}
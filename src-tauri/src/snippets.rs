@@ -0,0 +1,277 @@
+//! Text snippets expanded from spoken triggers ("insert signature", "my
+//! address").
+//!
+//! Runs on mic transcripts after post-processing, so the stored snippet is
+//! pasted verbatim. Triggers match case-insensitively and ignore the
+//! punctuation Whisper sprinkles in ("Insert, signature."); longer words may
+//! be off by one letter. System-audio transcripts are never expanded.
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
+use crate::state::{AppState, Settings};
+
+/// Words at least this long tolerate one wrong, missing or extra letter.
+const FUZZY_MIN_CHARS: usize = 5;
+
+/// Mirrors `src/types.ts::Snippet`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct Snippet {
+    pub(crate) id: String,
+    /// Spoken phrase that triggers the expansion.
+    pub(crate) trigger: String,
+    pub(crate) text: String,
+    pub(crate) enabled: bool,
+}
+
+impl Default for Snippet {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            trigger: String::new(),
+            text: String::new(),
+            enabled: true,
+        }
+    }
+}
+
+pub(crate) fn normalize_snippets(snippets: &mut Vec<Snippet>) {
+    for snippet in snippets.iter_mut() {
+        snippet.trigger = snippet.trigger.trim().to_string();
+    }
+    snippets.retain(|snippet| !trigger_words(&snippet.trigger).is_empty());
+    let now = crate::util::now_ms();
+    for (index, snippet) in snippets.iter_mut().enumerate() {
+        if snippet.id.trim().is_empty() {
+            snippet.id = format!("sn_{}_{}", now, index);
+        }
+    }
+}
+
+fn normalize_word(word: &str) -> String {
+    word.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn trigger_words(trigger: &str) -> Vec<String> {
+    trigger
+        .split_whitespace()
+        .map(normalize_word)
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+fn words_match(spoken: &str, trigger: &str) -> bool {
+    spoken == trigger
+        || (trigger.chars().count() >= FUZZY_MIN_CHARS && edit_distance(spoken, trigger) <= 1)
+}
+
+/// Normalized words of `text` with their byte ranges; punctuation-only
+/// tokens are skipped.
+fn spoken_words(text: &str) -> Vec<(String, usize, usize)> {
+    let mut words = Vec::new();
+    let mut start = None;
+    let mut push = |from: usize, to: usize| {
+        let word = normalize_word(&text[from..to]);
+        if !word.is_empty() {
+            words.push((word, from, to));
+        }
+    };
+    for (index, c) in text.char_indices() {
+        if c.is_whitespace() {
+            if let Some(from) = start.take() {
+                push(from, index);
+            }
+        } else if start.is_none() {
+            start = Some(index);
+        }
+    }
+    if let Some(from) = start {
+        push(from, text.len());
+    }
+    words
+}
+
+/// Replace every spoken trigger in `text` with its snippet. Longer triggers
+/// win; punctuation attached to the trigger words is dropped with them.
+pub(crate) fn expand_snippets(text: &str, snippets: &[Snippet]) -> String {
+    let mut triggers: Vec<(Vec<String>, &str)> = snippets
+        .iter()
+        .filter(|snippet| snippet.enabled)
+        .map(|snippet| (trigger_words(&snippet.trigger), snippet.text.as_str()))
+        .filter(|(words, _)| !words.is_empty())
+        .collect();
+    if triggers.is_empty() {
+        return text.to_string();
+    }
+    triggers.sort_by_key(|(words, _)| std::cmp::Reverse(words.len()));
+
+    let words = spoken_words(text);
+    let mut out = String::with_capacity(text.len());
+    let mut cursor = 0;
+    let mut index = 0;
+    while index < words.len() {
+        let matched = triggers.iter().find(|(trigger, _)| {
+            index + trigger.len() <= words.len()
+                && trigger
+                    .iter()
+                    .zip(&words[index..])
+                    .all(|(expected, (spoken, _, _))| words_match(spoken, expected))
+        });
+        match matched {
+            Some((trigger, snippet)) => {
+                out.push_str(&text[cursor..words[index].1]);
+                out.push_str(snippet);
+                cursor = words[index + trigger.len() - 1].2;
+                index += trigger.len();
+            }
+            None => index += 1,
+        }
+    }
+    out.push_str(&text[cursor..]);
+    out
+}
+
+pub(crate) fn expand_for_settings(text: &str, settings: &Settings) -> String {
+    if !settings.snippets_enabled || settings.snippets.is_empty() {
+        return text.to_string();
+    }
+    expand_snippets(text, &settings.snippets)
+}
+
+fn current_settings(app: &AppHandle) -> Settings {
+    app.state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+#[tauri::command]
+pub(crate) fn list_snippets(app: AppHandle) -> Vec<Snippet> {
+    current_settings(&app).snippets
+}
+
+/// Create (empty `id`) or update a snippet; returns it with its id.
+#[tauri::command]
+pub(crate) async fn save_snippet(app: AppHandle, snippet: Snippet) -> CommandResult<Snippet> {
+    if trigger_words(&snippet.trigger).is_empty() {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "A snippet needs a spoken trigger",
+        ));
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut settings = current_settings(&app);
+        let mut snippet = snippet;
+        snippet.trigger = snippet.trigger.trim().to_string();
+        if snippet.id.trim().is_empty() {
+            snippet.id = format!("sn_{}", crate::util::now_ms());
+        }
+        match settings.snippets.iter_mut().find(|s| s.id == snippet.id) {
+            Some(existing) => *existing = snippet.clone(),
+            None => settings.snippets.push(snippet.clone()),
+        }
+        crate::save_settings_inner(&app, &mut settings).with_code(ErrorCode::Storage)?;
+        Ok(snippet)
+    })
+    .await
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("save_snippet task failed: {}", e),
+        )
+    })?
+}
+
+#[tauri::command]
+pub(crate) async fn delete_snippet(app: AppHandle, id: String) -> CommandResult<()> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut settings = current_settings(&app);
+        let before = settings.snippets.len();
+        settings.snippets.retain(|snippet| snippet.id != id);
+        if settings.snippets.len() == before {
+            return Err(CommandError::new(
+                ErrorCode::NotFound,
+                format!("Snippet '{}' not found", id),
+            ));
+        }
+        crate::save_settings_inner(&app, &mut settings).with_code(ErrorCode::Storage)
+    })
+    .await
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("delete_snippet task failed: {}", e),
+        )
+    })?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snippet(trigger: &str, text: &str) -> Snippet {
+        Snippet {
+            trigger: trigger.to_string(),
+            text: text.to_string(),
+            ..Snippet::default()
+        }
+    }
+
+    #[test]
+    fn expands_triggers_despite_punctuation_and_typos() {
+        let snippets = vec![
+            snippet("insert signature", "Best regards,\nSam"),
+            snippet("my address", "1 Main St"),
+            snippet("my address at work", "2 Office Rd"),
+        ];
+        assert_eq!(
+            expand_snippets("Thanks for the update. Insert, signature.", &snippets),
+            "Thanks for the update. Best regards,\nSam"
+        );
+        assert_eq!(
+            expand_snippets("Ship it to my adress please", &snippets),
+            "Ship it to 1 Main St please"
+        );
+        assert_eq!(
+            expand_snippets("Use my address at work.", &snippets),
+            "Use 2 Office Rd"
+        );
+        // Short words must match exactly: "by" is not "my".
+        assert_eq!(
+            expand_snippets("drop by address desk", &snippets),
+            "drop by address desk"
+        );
+
+        let mut disabled = snippets.clone();
+        disabled[0].enabled = false;
+        assert_eq!(
+            expand_snippets("insert signature", &disabled),
+            "insert signature"
+        );
+    }
+}
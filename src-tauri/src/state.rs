@@ -1,12 +1,20 @@
 use crate::ai_fallback::models::{AIFallbackSettings, AIProvidersSettings};
 use crate::ai_fallback::provider::{is_local_ollama_endpoint, prompt_for_profile};
+use crate::app_overrides::{normalize_app_overrides, normalize_output_mode, AppOverride};
 use crate::audio::Recorder;
+use crate::capture_schedule::{normalize_capture_schedule, CaptureSchedule};
+use crate::cloud_transcription::{
+    normalize_cloud_transcription_settings, CloudTranscriptionSettings,
+};
 use crate::constants::{
     HALLUCINATION_MAX_CHARS, HALLUCINATION_MAX_DURATION_MS, HALLUCINATION_MAX_WORDS,
-    HALLUCINATION_RMS_THRESHOLD, VAD_SILENCE_MS_DEFAULT, VAD_THRESHOLD_START_DEFAULT,
-    VAD_THRESHOLD_SUSTAIN_DEFAULT,
+    HALLUCINATION_RMS_THRESHOLD, HOTKEY_GESTURE_DOUBLE_TAP_MS_DEFAULT,
+    HOTKEY_GESTURE_HOLD_MS_DEFAULT, MAX_RECORDING_MS_DEFAULT, MAX_RECORDING_MS_MAX,
+    MAX_RECORDING_MS_MIN, PRE_ROLL_MS_DEFAULT, PRE_ROLL_MS_MAX, PTT_LOCK_TAP_MS_DEFAULT,
+    VAD_SILENCE_MS_DEFAULT, VAD_THRESHOLD_START_DEFAULT, VAD_THRESHOLD_SUSTAIN_DEFAULT,
 };
-use crate::history_partition::PartitionedHistory;
+use crate::dictation_macros::{default_dictation_macros, DictationMacro};
+use crate::history_partition::{HistoryRetention, PartitionedHistory};
 use crate::modules::{
     canonicalize_module_id, normalize_confluence_settings, normalize_gdd_module_settings,
     normalize_module_settings, normalize_task_capture_settings,
@@ -19,7 +27,13 @@ use crate::modules::{
 use crate::multimodal_io::{PiperDaemonState, VisionFrameBuffer};
 use crate::overlay::OverlayController;
 use crate::paths::resolve_config_path;
+use crate::postprocessing::Rendition;
+use crate::snippets::Snippet;
 use crate::transcription::TranscribeRecorder;
+use crate::transcription_retry::{MAX_RETRY_ATTEMPTS, MAX_RETRY_BACKOFF_MS, MIN_RETRY_BACKOFF_MS};
+use crate::translation::{normalize_translation_settings, TranslationSettings};
+use crate::voice_commands::VoiceCommandPhrase;
+use crate::whisper_params::{normalize_whisper_advanced, WhisperAdvancedSettings};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
@@ -47,6 +61,11 @@ pub(crate) fn sync_diagnostic_logging_enabled(settings: &Settings) {
     DIAGNOSTIC_LOGGING_ENABLED.store(settings.diagnostic_logging_enabled, Ordering::Relaxed);
 }
 
+/// Capture modes driven by the always-on VAD monitor instead of hotkeys.
+pub(crate) fn mode_uses_vad_monitor(mode: &str) -> bool {
+    matches!(mode, "vad" | "wakeword")
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub(crate) struct SetupSettings {
@@ -244,7 +263,10 @@ fn default_topic_keywords() -> HashMap<String, Vec<String>> {
     topics
 }
 
-fn derive_postproc_language_from_asr(language_mode: &str, language_pinned: bool) -> String {
+pub(crate) fn derive_postproc_language_from_asr(
+    language_mode: &str,
+    language_pinned: bool,
+) -> String {
     if !language_pinned {
         return "multi".to_string();
     }
@@ -300,6 +322,9 @@ fn normalize_product_mode_value(value: &str) -> String {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub(crate) struct Settings {
+    /// Files without the field predate versioning and are migrated from 0.
+    #[serde(default)]
+    pub(crate) schema_version: u32,
     pub(crate) mode: String,
     #[serde(default = "default_product_mode")]
     pub(crate) product_mode: String,
@@ -308,11 +333,33 @@ pub(crate) struct Settings {
     #[serde(default = "default_hotkey_tts_stop")]
     pub(crate) hotkey_tts_stop: String,
     pub(crate) input_device: String,
+    /// 1-based channel of `input_device` to capture; 0 mixes all channels.
+    pub(crate) input_channel: u16,
+    /// VAD thresholds and mic gain last used with each input device id.
+    pub(crate) device_input_profiles: HashMap<String, DeviceInputProfile>,
     pub(crate) language_mode: String,
     pub(crate) language_pinned: bool,
+    /// Ask whisper to translate the transcript to English (`--translate`).
+    pub(crate) translate_to_english: bool,
+    /// Results in a language outside `expected_languages`: "off" | "flag"
+    /// (tag the entry) | "reject" (drop the result).
+    pub(crate) unexpected_language_action: String,
+    /// Whisper language codes; empty = the pinned language, if any.
+    pub(crate) expected_languages: Vec<String>,
     pub(crate) model: String,
+    /// Decoding parameter overrides for whisper-cli; unset uses the model default.
+    pub(crate) whisper_advanced: WhisperAdvancedSettings,
+    /// Retries of a transient transcription failure (0 = off, max 5).
+    pub(crate) transcribe_retry_attempts: u32,
+    /// Wait before the first retry; doubled for each later one.
+    pub(crate) transcribe_retry_backoff_ms: u64,
     // Legacy toggle kept for backward compatibility with old cloud transcription paths.
     pub(crate) cloud_fallback: bool,
+    /// Cloud speech-to-text provider selection (OpenAI, Groq, Deepgram, custom).
+    #[serde(default)]
+    pub(crate) cloud_transcription: CloudTranscriptionSettings,
+    /// Translation of final mic transcripts (DeepL, Google).
+    pub(crate) translation: TranslationSettings,
     // v0.7.0 AI Fallback settings
     pub(crate) ai_fallback: AIFallbackSettings,
     pub(crate) providers: AIProvidersSettings,
@@ -340,17 +387,99 @@ pub(crate) struct Settings {
     pub(crate) assistant_presence_window_monitor: Option<String>,
     pub(crate) audio_cues: bool,
     pub(crate) audio_cues_volume: f32,
+    /// Folder under `<data>/sound_packs`; "default" = built-in tones.
+    pub(crate) audio_cue_pack: String,
+    /// Per-cue volume, applied on top of `audio_cues_volume`.
+    pub(crate) audio_cue_volumes: AudioCueVolumes,
     #[serde(default)]
     pub(crate) diagnostic_logging_enabled: bool,
+    /// Log file verbosity: error, warn, info, debug or trace. `RUST_LOG` overrides it.
+    pub(crate) log_level: String,
     pub(crate) ptt_use_vad: bool, // Enable VAD threshold check even in PTT mode
     pub(crate) ptt_hot_keepalive_ms: u64, // Warm standby window after PTT release
+    /// Mic audio kept from before PTT/VAD starts recording, so the first
+    /// syllable is not clipped. 0 = off.
+    pub(crate) pre_roll_ms: u64,
+    /// PTT, toggle and VAD recordings longer than this are cut into a segment
+    /// and transcribed while capture continues. 0 = no limit.
+    pub(crate) max_recording_ms: u64,
     pub(crate) vad_threshold: f32, // Legacy: now maps to vad_threshold_start
     pub(crate) vad_threshold_start: f32,
     pub(crate) vad_threshold_sustain: f32,
     pub(crate) vad_silence_ms: u64,
+    /// Wake-word mode: catalogue id or absolute path to a custom `.onnx` classifier.
+    pub(crate) wake_word_model: String,
+    /// 0..1; higher fires more easily (lower score threshold).
+    pub(crate) wake_word_sensitivity: f32,
+    /// ONNX threads for the detector, 1-4.
+    pub(crate) wake_word_threads: u32,
+    /// RMS below which the detector idles to save CPU; 0 = always run.
+    pub(crate) wake_word_energy_gate: f32,
+    /// Pause the VAD monitor and system audio capture while a fullscreen app runs.
+    pub(crate) auto_pause_fullscreen: bool,
+    /// Same while one of `auto_pause_call_apps` is recording from a microphone.
+    pub(crate) auto_pause_calls: bool,
+    pub(crate) auto_pause_call_apps: Vec<String>,
+    /// Weekly windows outside which mic monitoring and system audio stay off.
+    pub(crate) capture_schedule: CaptureSchedule,
+    /// Localhost automation API (see `http_api`); off by default.
+    pub(crate) http_api_enabled: bool,
+    pub(crate) http_api_port: u16,
+    /// URLs that receive a JSON POST per final transcript or session end.
+    pub(crate) webhooks: Vec<crate::webhooks::Webhook>,
+    /// Mirror the latest captions to a text file / SSE stream for OBS.
+    pub(crate) caption_file_enabled: bool,
+    /// Empty = `obs-captions.txt` in the data directory.
+    pub(crate) caption_file_path: String,
+    pub(crate) caption_file_max_chars: u32,
+    /// Clear the caption after this many idle seconds; 0 = keep the last line.
+    pub(crate) caption_file_clear_secs: u32,
+    pub(crate) caption_file_include_mic: bool,
+    /// Folders whose new audio/video files are transcribed automatically.
+    pub(crate) watch_folders: Vec<crate::watch_folders::WatchFolder>,
+    /// File / watch-folder transcriptions run at once (1-4).
+    pub(crate) batch_max_parallel: u32,
+    /// Session recording codec: "opus", "flac" or "wav".
+    pub(crate) recording_codec: String,
+    /// Empty = `recordings/` in the data directory.
+    pub(crate) recordings_dir: String,
+    /// Delete session recordings older than this many days (0 = keep forever).
+    pub(crate) recordings_max_age_days: u32,
+    /// Delete the oldest session recordings above this total size (0 = unlimited).
+    pub(crate) recordings_max_total_mb: u64,
+    /// Call recordings: "multitrack" (mic + system files) or "stereo"
+    /// (mic left, system right).
+    pub(crate) call_recording_layout: String,
     pub(crate) transcribe_enabled: bool,
     pub(crate) transcribe_hotkey: String,
     pub(crate) hotkey_toggle_activation_words: String,
+    /// Switches to the next settings profile; empty = disabled.
+    pub(crate) hotkey_cycle_profile: String,
+    /// Toggles the session-wide privacy mute; empty = disabled.
+    pub(crate) hotkey_privacy_mute: String,
+    /// Removes the last dictation from the focused window; empty = disabled.
+    pub(crate) hotkey_undo_paste: String,
+    /// How undo removes the text: "backspace" | "select" (select, then delete).
+    pub(crate) undo_paste_mode: String,
+    /// One key for several actions: tap = toggle recording, double-tap =
+    /// system audio transcription, hold = PTT. Empty = disabled.
+    pub(crate) hotkey_gesture: String,
+    /// How long the gesture key must be held before it counts as PTT.
+    pub(crate) hotkey_gesture_hold_ms: u64,
+    /// Window after a tap in which a second press counts as a double-tap.
+    pub(crate) hotkey_gesture_double_tap_ms: u64,
+    /// Push-to-lock: a quick PTT tap keeps recording until the next press;
+    /// holding still works like plain PTT.
+    pub(crate) ptt_lock_enabled: bool,
+    /// Presses shorter than this count as a tap.
+    pub(crate) ptt_lock_tap_ms: u64,
+    /// Game controller / USB foot pedal button acts as PTT.
+    pub(crate) ptt_gamepad_enabled: bool,
+    /// Controller name to listen to; empty = any controller.
+    pub(crate) ptt_gamepad_device: String,
+    /// Button label as reported by `capture_gamepad_button` (e.g. "South",
+    /// "Code288").
+    pub(crate) ptt_gamepad_button: String,
     #[serde(default = "default_hotkey_product_mode_toggle")]
     pub(crate) hotkey_product_mode_toggle: String,
     pub(crate) transcribe_output_device: String,
@@ -359,8 +488,31 @@ pub(crate) struct Settings {
     pub(crate) transcribe_vad_silence_ms: u64,
     pub(crate) transcribe_batch_interval_ms: u64,
     pub(crate) transcribe_chunk_overlap_ms: u64,
+    /// System-audio chunks decoded at once (1 = serial); capped by CPU cores,
+    /// and always 1 unless the local backend is CPU.
+    pub(crate) transcribe_parallel_workers: usize,
+    /// Lengthen system-audio chunks, then decode them fast, while the backlog
+    /// stays high.
+    pub(crate) transcribe_adaptive_chunking: bool,
+    /// Prompt whisper with the tail of the previous system-audio chunk. Off by
+    /// default (a bad chunk can seed repeats in the next); forces one worker.
+    pub(crate) transcribe_context_priming: bool,
     pub(crate) transcribe_input_gain_db: f32,
     pub(crate) mic_input_gain_db: f32,
+    /// Mic DSP chain: cancel speaker output (cues, system audio) picked up by the mic.
+    pub(crate) mic_echo_cancellation: bool,
+    /// Mic DSP chain: high-pass filter against fan/HVAC rumble.
+    pub(crate) mic_highpass_enabled: bool,
+    pub(crate) mic_highpass_cutoff_hz: f32,
+    /// Mic DSP chain: RNNoise noise suppression (cafés, fans, keyboards).
+    pub(crate) noise_suppression: bool,
+    /// Mic DSP chain: spectral noise gate; blocks below this RMS level (dBFS)
+    /// are attenuated outright.
+    pub(crate) mic_noise_gate_enabled: bool,
+    pub(crate) mic_noise_gate_threshold_db: f32,
+    /// Automatic gain control: steer the mic gain toward a speech level (dBFS).
+    pub(crate) mic_agc_enabled: bool,
+    pub(crate) mic_agc_target_db: f32,
     #[serde(default = "default_history_alias_mic")]
     pub(crate) history_alias_mic: String,
     #[serde(default = "default_history_alias_system")]
@@ -386,7 +538,12 @@ pub(crate) struct Settings {
     pub(crate) overlay_pos_y: f64,
     pub(crate) overlay_kitt_pos_x: f64,
     pub(crate) overlay_kitt_pos_y: f64,
-    pub(crate) overlay_style: String, // "dot" | "kitt"
+    /// Monitor the overlay is placed on: "" follows the mouse cursor,
+    /// "primary" pins it to the primary display, anything else is a monitor
+    /// name. A pinned monitor that is disconnected falls back to primary and
+    /// is used again once it comes back.
+    pub(crate) overlay_monitor: String,
+    pub(crate) overlay_style: String, // "dot" | "kitt" | "waveform"
     #[serde(default = "default_accent_color")]
     pub(crate) accent_color: String,
     #[serde(default = "default_overlay_refining_indicator_enabled")]
@@ -408,13 +565,28 @@ pub(crate) struct Settings {
     pub(crate) overlay_kitt_min_width: f32,
     pub(crate) overlay_kitt_max_width: f32,
     pub(crate) overlay_kitt_height: f32,
+    // Live caption window
+    pub(crate) captions_enabled: bool,
+    pub(crate) captions_window_secs: u32,
+    pub(crate) captions_font_family: String,
+    pub(crate) captions_font_size: u32,
+    pub(crate) captions_position: String, // "top" | "bottom"
+    pub(crate) captions_background_opacity: f32,
+    /// Also caption microphone dictations, not only system audio.
+    pub(crate) captions_include_mic: bool,
     pub(crate) hallucination_filter_enabled: bool,
     pub(crate) hallucination_rms_threshold: f32,
     pub(crate) hallucination_max_duration_ms: u64,
     pub(crate) hallucination_max_words: u32,
     pub(crate) hallucination_max_chars: u32,
+    /// Phrases dropped as hallucinations, per whisper language code.
+    pub(crate) hallucination_phrases: HashMap<String, Vec<String>>,
+    /// Phrases the hallucination filter never drops.
+    pub(crate) hallucination_allowed_phrases: Vec<String>,
     pub(crate) activation_words_enabled: bool,
     pub(crate) activation_words: Vec<String>,
+    /// Cut the matched activation word or phrase from the kept transcript.
+    pub(crate) activation_words_strip: bool,
     #[serde(default = "default_topic_keywords")]
     pub(crate) topic_keywords: HashMap<String, Vec<String>>,
     // Post-processing settings
@@ -439,17 +611,85 @@ pub(crate) struct Settings {
     /// Set to true after the one-time migration that clears legacy heuristic data.
     #[serde(default)]
     pub(crate) edit_delta_migrated: bool,
+    /// User-defined find/replace rules applied after custom vocabulary.
+    #[serde(default)]
+    pub(crate) postproc_replacement_rules_enabled: bool,
+    #[serde(default)]
+    pub(crate) postproc_replacement_rules: Vec<ReplacementRule>,
+    /// Interpret spoken editing commands ("new line", "delete that") in mic transcripts.
+    #[serde(default)]
+    pub(crate) voice_commands_enabled: bool,
+    /// User-defined command phrases; these take precedence over the built-ins.
+    #[serde(default)]
+    pub(crate) voice_commands_custom: Vec<VoiceCommandPhrase>,
+    /// Also turn spoken punctuation ("comma", "period") into marks; off by
+    /// default because the words occur in ordinary speech.
+    #[serde(default)]
+    pub(crate) voice_commands_punctuation: bool,
+    /// Run app actions for spoken macro phrases ("open settings") instead of pasting them.
+    pub(crate) dictation_macros_enabled: bool,
+    pub(crate) dictation_macros: Vec<DictationMacro>,
+    /// Expand spoken snippet triggers in mic transcripts.
+    pub(crate) snippets_enabled: bool,
+    pub(crate) snippets: Vec<Snippet>,
+    /// Insert mic transcripts into the focused app; off keeps them in history only.
+    pub(crate) auto_paste_enabled: bool,
+    /// How transcripts are inserted: "paste" | "clipboard" | "type" | "inject"
+    /// ("inject" on Windows and macOS only).
+    pub(crate) output_mode: String,
+    /// Put the previous clipboard content back after pasting.
+    pub(crate) clipboard_restore_enabled: bool,
+    /// Templates applied to mic / system-audio transcripts before output,
+    /// e.g. "- {time}: {text}"; empty = plain text.
+    pub(crate) output_template_mic: String,
+    pub(crate) output_template_system: String,
+    /// Post-processing rendition that is pasted / stored in history:
+    /// "raw" | "rules" (no LLM cleanup) | "processed"
+    pub(crate) paste_rendition: String,
+    pub(crate) history_rendition: String,
+    /// Overrides applied while a matching process is in the foreground.
+    pub(crate) app_overrides: Vec<AppOverride>,
     pub(crate) postproc_llm_enabled: bool,
     pub(crate) postproc_llm_provider: String,
     #[serde(skip_serializing)]
     pub(crate) postproc_llm_api_key: String,
     pub(crate) postproc_llm_model: String,
     pub(crate) postproc_llm_prompt: String,
+    /// Inline LLM cleanup (punctuation, casing, filler words) before paste.
+    pub(crate) postproc_llm_cleanup_enabled: bool,
+    /// OpenAI-compatible server (llama.cpp, Ollama `/v1`, LM Studio, cloud).
+    pub(crate) postproc_llm_endpoint: String,
+    /// System prompt for the cleanup stage.
+    pub(crate) postproc_prompt: String,
+    /// Empty = the AI refinement model.
+    pub(crate) postproc_llm_cleanup_model: String,
+    /// Raw text is kept when the endpoint does not answer in time.
+    pub(crate) postproc_llm_timeout_ms: u64,
+    /// Profanity / PII filter at the end of post-processing.
+    pub(crate) content_filter_enabled: bool,
+    pub(crate) content_filter_profanity: bool,
+    pub(crate) content_filter_pii: bool,
+    /// On a hit: "mask" | "drop" (discard the transcript) | "tag" (keep as is)
+    pub(crate) content_filter_action: String,
+    /// Extra words or phrases treated as profanity.
+    pub(crate) content_filter_words: Vec<String>,
     // Analysis launcher settings (external tool)
     pub(crate) opus_enabled: bool,
     pub(crate) opus_bitrate_kbps: u32,
     pub(crate) auto_save_system_audio: bool, // Auto-save system audio as OPUS
     pub(crate) auto_save_mic_audio: bool,    // Auto-save mic continuous audio as OPUS
+    /// Keep each dictation's audio, keyed by history entry ID, for playback.
+    pub(crate) entry_audio_enabled: bool,
+    /// Delete kept entry audio older than this many days (0 = keep forever).
+    pub(crate) entry_audio_max_days: u32,
+    /// Cap on total kept entry audio; oldest files go first (0 = unlimited).
+    pub(crate) entry_audio_max_mb: u64,
+    /// History retention, enforced on every push and by an hourly prune (0 = unlimited).
+    pub(crate) history_max_entries: u32,
+    pub(crate) history_max_age_days: u32,
+    pub(crate) history_max_disk_mb: u64,
+    /// Encryption at rest for history and recordings: "off" | "keychain" | "passphrase".
+    pub(crate) history_encryption_mode: String,
     // Intelligent continuous dump settings
     pub(crate) continuous_dump_enabled: bool,
     pub(crate) continuous_dump_profile: String, // "balanced" | "low_latency" | "high_quality"
@@ -464,13 +704,19 @@ pub(crate) struct Settings {
     pub(crate) continuous_mic_soft_flush_ms: u64,
     pub(crate) continuous_mic_silence_flush_ms: u64,
     pub(crate) continuous_mic_hard_cut_ms: u64,
+    /// With continuous dump off, toggle recordings are still transcribed
+    /// every this many ms, cut at the next pause. 0 = only on stop.
+    pub(crate) mic_flush_interval_ms: u64,
     pub(crate) continuous_system_override_enabled: bool,
     pub(crate) continuous_system_soft_flush_ms: u64,
     pub(crate) continuous_system_silence_flush_ms: u64,
     pub(crate) continuous_system_hard_cut_ms: u64,
     pub(crate) transcribe_backend: String, // "whisper_cpp" | future backends
     #[serde(default = "default_local_backend_preference")]
-    pub(crate) local_backend_preference: String, // "auto" | "cuda" | "vulkan"
+    pub(crate) local_backend_preference: String, // "auto" | "cuda" | "vulkan" | "cpu"
+    /// Keep whisper-server loaded instead of retiring it after idle time;
+    /// the runtime supervisor restarts it when it dies.
+    pub(crate) whisper_server_keep_warm: bool,
     // Session consolidation settings (v0.7.0)
     pub(crate) session_idle_timeout_ms: u64, // Auto-finalize session after N ms of silence
     pub(crate) ptt_session_grouping_enabled: bool, // Group multiple PTT presses into one session
@@ -491,16 +737,27 @@ pub(crate) struct Settings {
 impl Default for Settings {
     fn default() -> Self {
         Self {
+      schema_version: SETTINGS_SCHEMA_VERSION,
       mode: "ptt".to_string(),
       product_mode: default_product_mode(),
       hotkey_ptt: "CommandOrControl+Shift+Space".to_string(),
       hotkey_toggle: "CommandOrControl+Shift+M".to_string(),
       hotkey_tts_stop: default_hotkey_tts_stop(),
       input_device: "default".to_string(),
+      device_input_profiles: HashMap::new(),
+      input_channel: 0,
       language_mode: "auto".to_string(),
       language_pinned: false,
+      translate_to_english: false,
+      unexpected_language_action: "off".to_string(),
+      expected_languages: Vec::new(),
       model: "whisper-large-v3-turbo".to_string(),
+      whisper_advanced: WhisperAdvancedSettings::default(),
+      transcribe_retry_attempts: 2,
+      transcribe_retry_backoff_ms: 400,
       cloud_fallback: false,
+      cloud_transcription: CloudTranscriptionSettings::default(),
+      translation: TranslationSettings::default(),
       ai_fallback: AIFallbackSettings::default(),
       providers: AIProvidersSettings::default(),
       setup: SetupSettings::default(),
@@ -521,16 +778,59 @@ impl Default for Settings {
       assistant_presence_window_monitor: None,
       audio_cues: true,
       audio_cues_volume: 0.3,
+      audio_cue_pack: crate::audio_cues::DEFAULT_SOUND_PACK.to_string(),
+      audio_cue_volumes: AudioCueVolumes::default(),
       diagnostic_logging_enabled: false,
+      log_level: crate::logging::DEFAULT_LOG_LEVEL.to_string(),
       ptt_use_vad: false,
       ptt_hot_keepalive_ms: 600_000,
+      pre_roll_ms: PRE_ROLL_MS_DEFAULT,
+      max_recording_ms: MAX_RECORDING_MS_DEFAULT,
       vad_threshold: VAD_THRESHOLD_START_DEFAULT,
       vad_threshold_start: VAD_THRESHOLD_START_DEFAULT,
       vad_threshold_sustain: VAD_THRESHOLD_SUSTAIN_DEFAULT,
       vad_silence_ms: VAD_SILENCE_MS_DEFAULT,
+      wake_word_model: crate::wake_word::DEFAULT_WAKE_WORD_MODEL.to_string(),
+      wake_word_sensitivity: 0.5,
+      wake_word_threads: 1,
+      wake_word_energy_gate: 0.01,
+      auto_pause_fullscreen: false,
+      auto_pause_calls: false,
+      auto_pause_call_apps: crate::capture_policy::DEFAULT_CALL_APPS
+        .iter()
+        .map(|app| app.to_string())
+        .collect(),
+      capture_schedule: CaptureSchedule::default(),
+      http_api_enabled: false,
+      http_api_port: crate::http_api::DEFAULT_HTTP_API_PORT,
+      webhooks: Vec::new(),
+      caption_file_enabled: false,
+      caption_file_path: String::new(),
+      caption_file_max_chars: 120,
+      caption_file_clear_secs: 10,
+      caption_file_include_mic: false,
+      watch_folders: Vec::new(),
+      batch_max_parallel: crate::batch_queue::DEFAULT_BATCH_MAX_PARALLEL,
+      recording_codec: "opus".to_string(),
+      recordings_dir: String::new(),
+      recordings_max_age_days: 0,
+      recordings_max_total_mb: 0,
+      call_recording_layout: "multitrack".to_string(),
       transcribe_enabled: true,
       transcribe_hotkey: "CommandOrControl+Shift+T".to_string(),
       hotkey_toggle_activation_words: "CommandOrControl+Shift+A".to_string(),
+      hotkey_cycle_profile: String::new(),
+      hotkey_privacy_mute: String::new(),
+      hotkey_undo_paste: String::new(),
+      undo_paste_mode: "backspace".to_string(),
+      hotkey_gesture: String::new(),
+      hotkey_gesture_hold_ms: HOTKEY_GESTURE_HOLD_MS_DEFAULT,
+      hotkey_gesture_double_tap_ms: HOTKEY_GESTURE_DOUBLE_TAP_MS_DEFAULT,
+      ptt_lock_enabled: false,
+      ptt_lock_tap_ms: PTT_LOCK_TAP_MS_DEFAULT,
+      ptt_gamepad_enabled: false,
+      ptt_gamepad_device: String::new(),
+      ptt_gamepad_button: String::new(),
       hotkey_product_mode_toggle: default_hotkey_product_mode_toggle(),
       transcribe_output_device: "default".to_string(),
       transcribe_vad_mode: false,
@@ -538,8 +838,19 @@ impl Default for Settings {
       transcribe_vad_silence_ms: 900,
       transcribe_batch_interval_ms: 8000,
       transcribe_chunk_overlap_ms: 1000,
+      transcribe_parallel_workers: 1,
+      transcribe_adaptive_chunking: true,
+      transcribe_context_priming: false,
       transcribe_input_gain_db: 0.0,
       mic_input_gain_db: 0.0,
+      mic_echo_cancellation: false,
+      mic_highpass_enabled: false,
+      mic_highpass_cutoff_hz: 80.0,
+      noise_suppression: false,
+      mic_noise_gate_enabled: false,
+      mic_noise_gate_threshold_db: -50.0,
+      mic_agc_enabled: false,
+      mic_agc_target_db: -20.0,
       history_alias_mic: default_history_alias_mic(),
       history_alias_system: default_history_alias_system(),
       capture_enabled: true,
@@ -563,6 +874,7 @@ impl Default for Settings {
       overlay_pos_y: 90.0,          // 90% = bottom area
       overlay_kitt_pos_x: 50.0,     // 50% = horizontal center
       overlay_kitt_pos_y: 90.0,     // 90% = bottom area
+      overlay_monitor: String::new(), // follow the mouse cursor
       overlay_style: "dot".to_string(),
       accent_color: "#4be0d4".to_string(),
       overlay_refining_indicator_enabled: true,
@@ -576,13 +888,23 @@ impl Default for Settings {
       overlay_kitt_min_width: 20.0,
       overlay_kitt_max_width: 700.0,
       overlay_kitt_height: 13.0,
+      captions_enabled: false,
+      captions_window_secs: 8,
+      captions_font_family: "Segoe UI".to_string(),
+      captions_font_size: 28,
+      captions_position: "bottom".to_string(),
+      captions_background_opacity: 0.6,
+      captions_include_mic: false,
       hallucination_filter_enabled: true,
       hallucination_rms_threshold: HALLUCINATION_RMS_THRESHOLD,
       hallucination_max_duration_ms: HALLUCINATION_MAX_DURATION_MS,
       hallucination_max_words: HALLUCINATION_MAX_WORDS as u32,
       hallucination_max_chars: HALLUCINATION_MAX_CHARS as u32,
+      hallucination_phrases: crate::hallucination_filter::default_hallucination_phrases(),
+      hallucination_allowed_phrases: Vec::new(),
       activation_words_enabled: false,
       activation_words: vec!["computer".to_string(), "hey assistant".to_string()],
+      activation_words_strip: false,
       topic_keywords: default_topic_keywords(),
       postproc_enabled: false,
       postproc_language: "multi".to_string(),
@@ -594,15 +916,49 @@ impl Default for Settings {
       vocab_terms: Vec::new(),
       edit_substitutions: Vec::new(),
       edit_delta_migrated: false,
+      postproc_replacement_rules_enabled: false,
+      postproc_replacement_rules: Vec::new(),
+      voice_commands_enabled: false,
+      voice_commands_custom: Vec::new(),
+      voice_commands_punctuation: false,
+      dictation_macros_enabled: false,
+      dictation_macros: default_dictation_macros(),
+      snippets_enabled: true,
+      snippets: Vec::new(),
+      auto_paste_enabled: true,
+      output_mode: "paste".to_string(),
+      clipboard_restore_enabled: true,
+      output_template_mic: String::new(),
+      output_template_system: String::new(),
+      paste_rendition: "processed".to_string(),
+      history_rendition: "processed".to_string(),
+      app_overrides: Vec::new(),
       postproc_llm_enabled: false,
       postproc_llm_provider: "ollama".to_string(),
       postproc_llm_api_key: String::new(),
       postproc_llm_model: String::new(),
       postproc_llm_prompt: "Refine this voice transcription: fix punctuation, capitalization, and obvious errors. Keep the original meaning. Output only the refined text.".to_string(),
+      postproc_llm_cleanup_enabled: false,
+      postproc_llm_endpoint: crate::llm_cleanup::DEFAULT_POSTPROC_LLM_ENDPOINT.to_string(),
+      postproc_prompt: crate::llm_cleanup::DEFAULT_POSTPROC_PROMPT.to_string(),
+      postproc_llm_cleanup_model: String::new(),
+      postproc_llm_timeout_ms: crate::llm_cleanup::DEFAULT_POSTPROC_LLM_TIMEOUT_MS,
+      content_filter_enabled: false,
+      content_filter_profanity: true,
+      content_filter_pii: true,
+      content_filter_action: "mask".to_string(),
+      content_filter_words: Vec::new(),
       opus_enabled: true,
       opus_bitrate_kbps: 64,
       auto_save_system_audio: false,
       auto_save_mic_audio: false,
+      entry_audio_enabled: false,
+      entry_audio_max_days: 30,
+      entry_audio_max_mb: 500,
+      history_max_entries: 0,
+      history_max_age_days: 0,
+      history_max_disk_mb: 0,
+      history_encryption_mode: "off".to_string(),
       continuous_dump_enabled: true,
       continuous_dump_profile: "balanced".to_string(),
       continuous_soft_flush_ms: 10_000,
@@ -616,12 +972,14 @@ impl Default for Settings {
       continuous_mic_soft_flush_ms: 10_000,
       continuous_mic_silence_flush_ms: 1_200,
       continuous_mic_hard_cut_ms: 45_000,
+      mic_flush_interval_ms: 0,
       continuous_system_override_enabled: false,
       continuous_system_soft_flush_ms: 10_000,
       continuous_system_silence_flush_ms: 1_200,
       continuous_system_hard_cut_ms: 45_000,
       transcribe_backend: "whisper_cpp".to_string(),
       local_backend_preference: default_local_backend_preference(),
+      whisper_server_keep_warm: false,
       session_idle_timeout_ms: 60_000,       // 60 seconds
       ptt_session_grouping_enabled: true,
       ptt_session_group_timeout_s: 120,      // 2 minutes
@@ -636,6 +994,90 @@ impl Default for Settings {
     }
 }
 
+/// Volume (0..1) of each cue. The paste cue is silent until raised.
+/// Mirrors `src/types.ts::AudioCueVolumes`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct AudioCueVolumes {
+    pub(crate) start: f32,
+    pub(crate) stop: f32,
+    pub(crate) error: f32,
+    pub(crate) paste: f32,
+}
+
+impl Default for AudioCueVolumes {
+    fn default() -> Self {
+        Self {
+            start: 1.0,
+            stop: 1.0,
+            error: 1.0,
+            paste: 0.0,
+        }
+    }
+}
+
+impl AudioCueVolumes {
+    pub(crate) fn get(&self, cue: &str) -> Option<f32> {
+        match cue {
+            "start" => Some(self.start),
+            "stop" => Some(self.stop),
+            "error" => Some(self.error),
+            "paste" => Some(self.paste),
+            _ => None,
+        }
+    }
+}
+
+/// Input tuning remembered for one microphone, so switching between a headset
+/// and a desk mic does not need recalibrating each time.
+/// Mirrors `src/types.ts::DeviceInputProfile`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct DeviceInputProfile {
+    pub(crate) vad_threshold_start: f32,
+    pub(crate) vad_threshold_sustain: f32,
+    pub(crate) vad_silence_ms: u64,
+    pub(crate) mic_input_gain_db: f32,
+}
+
+impl DeviceInputProfile {
+    fn from_settings(settings: &Settings) -> Self {
+        Self {
+            vad_threshold_start: settings.vad_threshold_start,
+            vad_threshold_sustain: settings.vad_threshold_sustain,
+            vad_silence_ms: settings.vad_silence_ms,
+            mic_input_gain_db: settings.mic_input_gain_db,
+        }
+    }
+}
+
+/// Stores the current VAD thresholds and gain as `device`'s profile.
+pub(crate) fn remember_device_input_profile(settings: &mut Settings, device: &str) {
+    let device = device.trim();
+    if device.is_empty() {
+        return;
+    }
+    let profile = DeviceInputProfile::from_settings(settings);
+    settings
+        .device_input_profiles
+        .insert(device.to_string(), profile);
+}
+
+/// Restores the values remembered for `input_device`; false when it has none.
+pub(crate) fn apply_device_input_profile(settings: &mut Settings) -> bool {
+    let Some(profile) = settings
+        .device_input_profiles
+        .get(settings.input_device.trim())
+        .cloned()
+    else {
+        return false;
+    };
+    settings.vad_threshold_start = profile.vad_threshold_start;
+    settings.vad_threshold_sustain = profile.vad_threshold_sustain;
+    settings.vad_silence_ms = profile.vad_silence_ms;
+    settings.mic_input_gain_db = profile.mic_input_gain_db;
+    true
+}
+
 /// A correction the user made by editing the pasted refinement output before submitting.
 /// Mirrors `src/types.ts::EditSubstitution`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -650,6 +1092,34 @@ pub(crate) struct EditSubstitution {
     pub(crate) last_seen_ms: u64,
 }
 
+/// A user-defined find/replace rule for post-processing.
+/// Mirrors `src/types.ts::ReplacementRule`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct ReplacementRule {
+    pub(crate) find: String,
+    pub(crate) replace: String,
+    /// Treat `find` as a regular expression (`replace` may use `$1` captures).
+    pub(crate) regex: bool,
+    pub(crate) case_sensitive: bool,
+    /// Plain rules only: require word boundaries around the match.
+    pub(crate) whole_word: bool,
+    pub(crate) enabled: bool,
+}
+
+impl Default for ReplacementRule {
+    fn default() -> Self {
+        Self {
+            find: String::new(),
+            replace: String::new(),
+            regex: false,
+            case_sensitive: false,
+            whole_word: true,
+            enabled: true,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub(crate) struct HistoryRefinement {
@@ -660,6 +1130,10 @@ pub(crate) struct HistoryRefinement {
     pub(crate) model: String,
     pub(crate) execution_time_ms: Option<u64>,
     pub(crate) error: String,
+    /// Post-processing rendition `raw` was taken from ("raw" | "rules" |
+    /// "processed"); it can differ from the entry's text when paste and
+    /// history use different renditions. Empty for older entries.
+    pub(crate) rendition: String,
 }
 
 impl Default for HistoryRefinement {
@@ -672,6 +1146,7 @@ impl Default for HistoryRefinement {
             model: String::new(),
             execution_time_ms: None,
             error: String::new(),
+            rendition: String::new(),
         }
     }
 }
@@ -686,6 +1161,49 @@ pub(crate) struct HistoryEntry {
     pub(crate) speaker_name: Option<String>,
     #[serde(default)]
     pub(crate) refinement: Option<HistoryRefinement>,
+    /// Saved recording for this entry, when one was kept (see `save_recording_opus`).
+    #[serde(default)]
+    pub(crate) audio_path: Option<String>,
+    /// Re-transcriptions of the saved audio with other models, oldest first.
+    #[serde(default)]
+    pub(crate) revisions: Vec<HistoryRevision>,
+    /// Pinned entries are kept regardless of the history retention limits.
+    #[serde(default)]
+    pub(crate) pinned: bool,
+    #[serde(default)]
+    pub(crate) tags: Vec<String>,
+    #[serde(default)]
+    pub(crate) note: Option<String>,
+    /// `text` rendered through the source's output template; what was pasted.
+    #[serde(default)]
+    pub(crate) formatted_text: Option<String>,
+    /// Whisper language code of the transcript, when known.
+    #[serde(default)]
+    pub(crate) language: Option<String>,
+    #[serde(default)]
+    pub(crate) translation: Option<HistoryTranslation>,
+}
+
+/// Machine translation of the pasted rendition of an entry (see `translation`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct HistoryTranslation {
+    pub(crate) text: String,
+    pub(crate) target_language: String,
+    /// "deepl" | "google"
+    pub(crate) provider: String,
+    /// Source language the provider detected, when reported.
+    #[serde(default)]
+    pub(crate) source_language: Option<String>,
+    /// Whether the translation was pasted instead of the original.
+    #[serde(default)]
+    pub(crate) pasted: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct HistoryRevision {
+    pub(crate) model: String,
+    pub(crate) text: String,
+    pub(crate) created_ms: u64,
 }
 
 #[cfg(target_os = "windows")]
@@ -926,6 +1444,8 @@ pub(crate) struct AppState {
     pub(crate) module_sidecars: crate::modules::runtime::ModuleSidecarMap,
     /// Port for Whisper-Server HTTP API (default 8178).
     pub(crate) whisper_server_port: AtomicU16,
+    /// Session-only override of `Settings::translate_to_english`; not persisted.
+    pub(crate) translate_session_override: Mutex<Option<bool>>,
     pub(crate) whisper_server_warmup_started: AtomicBool,
     /// True once the OLLAMA model is confirmed loaded in VRAM (after warmup or
     /// successful refinement). Reset to false when the model is unloaded.
@@ -1040,331 +1560,519 @@ pub(crate) fn get_runtime_metrics_snapshot(state: &AppState) -> RuntimeMetricsSn
     }
 }
 
+/// Version written to `settings.json`. Bump it together with a new entry in
+/// `SETTINGS_MIGRATIONS` whenever stored values change meaning.
+pub(crate) const SETTINGS_SCHEMA_VERSION: u32 = 2;
+
+/// `SETTINGS_MIGRATIONS[n]` upgrades a version `n` file to version `n + 1`.
+const SETTINGS_MIGRATIONS: &[fn(&mut Settings)] =
+    &[migrate_settings_v0_to_v1, migrate_settings_v1_to_v2];
+
+/// Unversioned files: legacy single VAD threshold, and the KITT overlay
+/// inheriting the dot overlay's look from before it had its own settings.
+fn migrate_settings_v0_to_v1(settings: &mut Settings) {
+    if settings.vad_threshold_start <= 0.0 {
+        settings.vad_threshold_start = if settings.vad_threshold > 0.0 {
+            settings.vad_threshold
+        } else {
+            VAD_THRESHOLD_START_DEFAULT
+        };
+    }
+    if settings.vad_threshold_sustain <= 0.0 {
+        settings.vad_threshold_sustain = VAD_THRESHOLD_SUSTAIN_DEFAULT;
+    }
+    let defaults = Settings::default();
+    let approx_eq = |a: f32, b: f32| (a - b).abs() < 0.0001;
+    if settings.overlay_kitt_color == defaults.overlay_kitt_color
+        && settings.overlay_color != defaults.overlay_color
+    {
+        settings.overlay_kitt_color = settings.overlay_color.clone();
+    }
+    if settings.overlay_kitt_rise_ms == defaults.overlay_kitt_rise_ms
+        && settings.overlay_rise_ms != defaults.overlay_rise_ms
+    {
+        settings.overlay_kitt_rise_ms = settings.overlay_rise_ms;
+    }
+    if settings.overlay_kitt_fall_ms == defaults.overlay_kitt_fall_ms
+        && settings.overlay_fall_ms != defaults.overlay_fall_ms
+    {
+        settings.overlay_kitt_fall_ms = settings.overlay_fall_ms;
+    }
+    if approx_eq(
+        settings.overlay_kitt_opacity_inactive,
+        defaults.overlay_kitt_opacity_inactive,
+    ) && !approx_eq(
+        settings.overlay_opacity_inactive,
+        defaults.overlay_opacity_inactive,
+    ) {
+        settings.overlay_kitt_opacity_inactive = settings.overlay_opacity_inactive;
+    }
+    if approx_eq(
+        settings.overlay_kitt_opacity_active,
+        defaults.overlay_kitt_opacity_active,
+    ) && !approx_eq(
+        settings.overlay_opacity_active,
+        defaults.overlay_opacity_active,
+    ) {
+        settings.overlay_kitt_opacity_active = settings.overlay_opacity_active;
+    }
+    if (settings.overlay_kitt_pos_x - 12.0).abs() < 0.001
+        && (settings.overlay_kitt_pos_y - 12.0).abs() < 0.001
+        && ((settings.overlay_pos_x - 12.0).abs() > 0.001
+            || (settings.overlay_pos_y - 12.0).abs() > 0.001)
+    {
+        settings.overlay_kitt_pos_x = settings.overlay_pos_x;
+        settings.overlay_kitt_pos_y = settings.overlay_pos_y;
+    }
+}
+
+/// The PTT standby and VAD pre-roll used to be sized by
+/// `continuous_pre_roll_ms`; a customized value carries over to `pre_roll_ms`.
+fn migrate_settings_v1_to_v2(settings: &mut Settings) {
+    if settings.continuous_pre_roll_ms != Settings::default().continuous_pre_roll_ms {
+        settings.pre_roll_ms = settings.continuous_pre_roll_ms.min(PRE_ROLL_MS_MAX);
+    }
+}
+
+/// Runs every migration between the file's `schema_version` and the current one.
+pub(crate) fn migrate_settings(settings: &mut Settings) {
+    let from = settings.schema_version as usize;
+    for migration in SETTINGS_MIGRATIONS.iter().skip(from) {
+        migration(settings);
+    }
+    settings.schema_version = settings.schema_version.max(SETTINGS_SCHEMA_VERSION);
+}
+
+/// Clamps values into their valid ranges and normalizes nested settings.
+/// Safe to run on any settings, including imported ones.
+pub(crate) fn sanitize_settings(settings: &mut Settings) {
+    if !matches!(settings.mode.as_str(), "ptt" | "vad" | "wakeword") {
+        settings.mode = "ptt".to_string();
+    }
+    // Clamp thresholds to valid range
+    if !(0.001..=1.0).contains(&settings.vad_threshold_start) {
+        settings.vad_threshold_start = VAD_THRESHOLD_START_DEFAULT;
+    }
+    if !(0.001..=1.0).contains(&settings.vad_threshold_sustain) {
+        settings.vad_threshold_sustain = VAD_THRESHOLD_SUSTAIN_DEFAULT;
+    }
+    // Ensure sustain <= start
+    if settings.vad_threshold_sustain > settings.vad_threshold_start {
+        settings.vad_threshold_sustain = settings.vad_threshold_start;
+    }
+    // Sync legacy field
+    settings.vad_threshold = settings.vad_threshold_start;
+    if settings.vad_silence_ms < 100 {
+        settings.vad_silence_ms = VAD_SILENCE_MS_DEFAULT;
+    }
+    settings
+        .device_input_profiles
+        .retain(|device, _| !device.trim().is_empty());
+    for profile in settings.device_input_profiles.values_mut() {
+        profile.vad_threshold_start = profile.vad_threshold_start.clamp(0.001, 1.0);
+        profile.vad_threshold_sustain = profile
+            .vad_threshold_sustain
+            .clamp(0.001, profile.vad_threshold_start);
+        profile.vad_silence_ms = profile.vad_silence_ms.max(100);
+        profile.mic_input_gain_db = profile.mic_input_gain_db.clamp(-30.0, 30.0);
+    }
+    settings.audio_cues_volume = settings.audio_cues_volume.clamp(0.0, 1.0);
+    let volumes = &mut settings.audio_cue_volumes;
+    for volume in [
+        &mut volumes.start,
+        &mut volumes.stop,
+        &mut volumes.error,
+        &mut volumes.paste,
+    ] {
+        *volume = if volume.is_finite() {
+            volume.clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+    }
+    if !crate::audio_cues::is_valid_pack_name(&settings.audio_cue_pack) {
+        settings.audio_cue_pack = crate::audio_cues::DEFAULT_SOUND_PACK.to_string();
+    }
+    if !(0.0..=1.0).contains(&settings.transcribe_vad_threshold) {
+        settings.transcribe_vad_threshold = 0.04;
+    }
+    if settings.transcribe_batch_interval_ms < 4000 {
+        settings.transcribe_batch_interval_ms = 4000;
+    }
+    if settings.transcribe_batch_interval_ms > 15000 {
+        settings.transcribe_batch_interval_ms = 15000;
+    }
+    if settings.transcribe_chunk_overlap_ms > settings.transcribe_batch_interval_ms {
+        settings.transcribe_chunk_overlap_ms = settings.transcribe_batch_interval_ms / 2;
+    }
+    settings.transcribe_parallel_workers = settings
+        .transcribe_parallel_workers
+        .clamp(1, crate::transcribe_pool::MAX_TRANSCRIBE_WORKERS);
+    if settings.transcribe_chunk_overlap_ms > 3000 {
+        settings.transcribe_chunk_overlap_ms = 3000;
+    }
+    if settings.transcribe_vad_silence_ms < 200 {
+        settings.transcribe_vad_silence_ms = 200;
+    }
+    if settings.transcribe_vad_silence_ms > 5000 {
+        settings.transcribe_vad_silence_ms = 5000;
+    }
+    normalize_continuous_dump_fields(settings);
+    normalize_history_alias_fields(settings);
+    if settings.transcribe_backend.trim().is_empty() {
+        settings.transcribe_backend = "whisper_cpp".to_string();
+    }
+    if settings.transcribe_backend != "whisper_cpp" {
+        settings.transcribe_backend = "whisper_cpp".to_string();
+    }
+    settings.local_backend_preference = match settings
+        .local_backend_preference
+        .trim()
+        .to_ascii_lowercase()
+        .as_str()
+    {
+        "cuda" => "cuda".to_string(),
+        "vulkan" => "vulkan".to_string(),
+        "cpu" => "cpu".to_string(),
+        _ => "auto".to_string(),
+    };
+    // Validate language_mode
+    let valid_languages = [
+        "auto", "en", "de", "fr", "es", "it", "pt", "nl", "pl", "ru", "ja", "ko", "zh", "ar", "tr",
+        "hi",
+    ];
+    if !valid_languages.contains(&settings.language_mode.as_str()) {
+        settings.language_mode = "auto".to_string();
+    }
+    settings.postproc_language =
+        derive_postproc_language_from_asr(&settings.language_mode, settings.language_pinned);
+    if settings.model_source.trim().is_empty() {
+        settings.model_source = "default".to_string();
+    }
+    settings.transcribe_input_gain_db = settings.transcribe_input_gain_db.clamp(-30.0, 30.0);
+    settings.mic_input_gain_db = settings.mic_input_gain_db.clamp(-30.0, 30.0);
+    settings.mic_highpass_cutoff_hz =
+        crate::audio_dsp::clamp_highpass_cutoff_hz(settings.mic_highpass_cutoff_hz);
+    settings.mic_noise_gate_threshold_db =
+        crate::audio_dsp::clamp_noise_gate_threshold_db(settings.mic_noise_gate_threshold_db);
+    #[cfg(target_os = "windows")]
+    if settings.transcribe_output_device != "default"
+        && !settings.transcribe_output_device.starts_with("wasapi:")
+    {
+        settings.transcribe_output_device = "default".to_string();
+    }
+    if !settings.overlay_min_radius.is_finite() {
+        settings.overlay_min_radius = 16.0;
+    }
+    if !settings.overlay_max_radius.is_finite() {
+        settings.overlay_max_radius = 64.0;
+    }
+    // Keep dot dimensions in sane bounds; monitor-relative 50% cap is
+    // applied at runtime in overlay.rs.
+    settings.overlay_min_radius = settings.overlay_min_radius.clamp(4.0, 5_000.0);
+    settings.overlay_max_radius = settings.overlay_max_radius.clamp(8.0, 10_000.0);
+    if settings.overlay_max_radius < settings.overlay_min_radius {
+        settings.overlay_max_radius = settings.overlay_min_radius;
+    }
+    if settings.overlay_rise_ms < 20 {
+        settings.overlay_rise_ms = 20;
+    }
+    if settings.overlay_rise_ms > 200 {
+        settings.overlay_rise_ms = 200;
+    }
+    if settings.overlay_fall_ms < 20 {
+        settings.overlay_fall_ms = 20;
+    }
+    if settings.overlay_fall_ms > 200 {
+        settings.overlay_fall_ms = 200;
+    }
+    if !(0.0..=1.0).contains(&settings.overlay_opacity_inactive) {
+        settings.overlay_opacity_inactive = 0.2;
+    }
+    if !(0.0..=1.0).contains(&settings.overlay_opacity_active) {
+        settings.overlay_opacity_active = 0.8;
+    }
+    if settings.overlay_opacity_inactive < 0.05 {
+        settings.overlay_opacity_inactive = 0.05;
+    }
+    if settings.overlay_opacity_active < 0.05 {
+        settings.overlay_opacity_active = 0.05;
+    }
+    if settings.overlay_opacity_active < settings.overlay_opacity_inactive {
+        settings.overlay_opacity_active = settings.overlay_opacity_inactive;
+    }
+    if settings.overlay_kitt_pos_x.is_nan() || settings.overlay_kitt_pos_y.is_nan() {
+        settings.overlay_kitt_pos_x = settings.overlay_pos_x;
+        settings.overlay_kitt_pos_y = settings.overlay_pos_y;
+    }
+    if settings.overlay_kitt_pos_x < 0.0 {
+        settings.overlay_kitt_pos_x = 0.0;
+    }
+    if settings.overlay_kitt_pos_y < 0.0 {
+        settings.overlay_kitt_pos_y = 0.0;
+    }
+    if settings.overlay_pos_x < 0.0 {
+        settings.overlay_pos_x = 0.0;
+    }
+    if settings.overlay_pos_y < 0.0 {
+        settings.overlay_pos_y = 0.0;
+    }
+    settings.overlay_monitor = settings.overlay_monitor.trim().to_string();
+    settings.log_level = crate::logging::normalize_log_level(&settings.log_level)
+        .unwrap_or(crate::logging::DEFAULT_LOG_LEVEL)
+        .to_string();
+    settings.wake_word_model = settings.wake_word_model.trim().to_string();
+    if settings.wake_word_model.is_empty() {
+        settings.wake_word_model = crate::wake_word::DEFAULT_WAKE_WORD_MODEL.to_string();
+    }
+    if !settings.wake_word_sensitivity.is_finite() {
+        settings.wake_word_sensitivity = 0.5;
+    }
+    settings.wake_word_sensitivity = settings.wake_word_sensitivity.clamp(0.0, 1.0);
+    settings.wake_word_threads = settings.wake_word_threads.clamp(1, 4);
+    if !settings.wake_word_energy_gate.is_finite() {
+        settings.wake_word_energy_gate = 0.01;
+    }
+    settings.wake_word_energy_gate = settings.wake_word_energy_gate.clamp(0.0, 0.5);
+    for app in settings.auto_pause_call_apps.iter_mut() {
+        *app = app.trim().to_lowercase();
+    }
+    settings.auto_pause_call_apps.retain(|app| !app.is_empty());
+    settings.auto_pause_call_apps.dedup();
+    if settings.overlay_kitt_color.trim().is_empty() {
+        settings.overlay_kitt_color = "#ff3d2e".to_string();
+    }
+    if !settings.overlay_kitt_min_width.is_finite() {
+        settings.overlay_kitt_min_width = 20.0;
+    }
+    if !settings.overlay_kitt_max_width.is_finite() {
+        settings.overlay_kitt_max_width = 700.0;
+    }
+    if !settings.overlay_kitt_height.is_finite() {
+        settings.overlay_kitt_height = 13.0;
+    }
+
+    // Keep KITT dimensions in sane bounds; monitor-relative 50% cap is
+    // applied at runtime in overlay.rs.
+    settings.overlay_kitt_min_width = settings.overlay_kitt_min_width.clamp(4.0, 10_000.0);
+    settings.overlay_kitt_max_width = settings.overlay_kitt_max_width.clamp(50.0, 20_000.0);
+    if settings.overlay_kitt_max_width < settings.overlay_kitt_min_width {
+        settings.overlay_kitt_max_width = settings.overlay_kitt_min_width.max(50.0);
+    }
+    settings.overlay_kitt_height = settings.overlay_kitt_height.clamp(8.0, 400.0);
+    if settings.overlay_kitt_rise_ms < 20 {
+        settings.overlay_kitt_rise_ms = 20;
+    }
+    if settings.overlay_kitt_rise_ms > 200 {
+        settings.overlay_kitt_rise_ms = 200;
+    }
+    if settings.overlay_kitt_fall_ms < 20 {
+        settings.overlay_kitt_fall_ms = 20;
+    }
+    if settings.overlay_kitt_fall_ms > 200 {
+        settings.overlay_kitt_fall_ms = 200;
+    }
+    settings.captions_window_secs = settings.captions_window_secs.clamp(2, 60);
+    settings.captions_font_size = settings.captions_font_size.clamp(12, 96);
+    if settings.captions_font_family.trim().is_empty() {
+        settings.captions_font_family = "Segoe UI".to_string();
+    }
+    if settings.captions_position != "top" {
+        settings.captions_position = "bottom".to_string();
+    }
+    if !settings.captions_background_opacity.is_finite() {
+        settings.captions_background_opacity = 0.6;
+    }
+    settings.captions_background_opacity = settings.captions_background_opacity.clamp(0.0, 1.0);
+    if !["subtle", "standard", "intense"]
+        .contains(&settings.overlay_refining_indicator_preset.as_str())
+    {
+        settings.overlay_refining_indicator_preset = "standard".to_string();
+    }
+    if !settings.overlay_refining_indicator_color.starts_with('#')
+        || settings.overlay_refining_indicator_color.len() != 7
+    {
+        settings.overlay_refining_indicator_color = "#6ec8ff".to_string();
+    }
+    if !settings.accent_color.starts_with('#') || settings.accent_color.len() != 7 {
+        settings.accent_color = "#4be0d4".to_string();
+    }
+    if settings.overlay_refining_indicator_speed_ms < 450 {
+        settings.overlay_refining_indicator_speed_ms = 450;
+    }
+    if settings.overlay_refining_indicator_speed_ms > 3_000 {
+        settings.overlay_refining_indicator_speed_ms = 3_000;
+    }
+    if settings.overlay_refining_indicator_range < 60.0 {
+        settings.overlay_refining_indicator_range = 60.0;
+    }
+    if settings.overlay_refining_indicator_range > 180.0 {
+        settings.overlay_refining_indicator_range = 180.0;
+    }
+    settings.hotkey_gesture_hold_ms = settings.hotkey_gesture_hold_ms.clamp(150, 1_500);
+    settings.hotkey_gesture_double_tap_ms = settings.hotkey_gesture_double_tap_ms.clamp(100, 1_000);
+    settings.ptt_lock_tap_ms = settings.ptt_lock_tap_ms.clamp(100, 1_000);
+    settings.pre_roll_ms = settings.pre_roll_ms.min(PRE_ROLL_MS_MAX);
+    if settings.max_recording_ms > 0 {
+        settings.max_recording_ms = settings
+            .max_recording_ms
+            .clamp(MAX_RECORDING_MS_MIN, MAX_RECORDING_MS_MAX);
+    }
+    if settings.hotkey_tts_stop.trim().is_empty() {
+        settings.hotkey_tts_stop = default_hotkey_tts_stop();
+    } else {
+        settings.hotkey_tts_stop = settings.hotkey_tts_stop.trim().to_string();
+    }
+    settings.overlay_tts_stop_shape = match settings
+        .overlay_tts_stop_shape
+        .trim()
+        .to_ascii_lowercase()
+        .as_str()
+    {
+        "round" => "round".to_string(),
+        _ => "compact".to_string(),
+    };
+    settings.overlay_tts_stop_color = settings.overlay_tts_stop_color.trim().to_string();
+    if settings.overlay_tts_stop_color.is_empty() {
+        settings.overlay_tts_stop_color = default_overlay_tts_stop_color();
+    }
+    if !(0.0..=1.0).contains(&settings.overlay_kitt_opacity_inactive) {
+        settings.overlay_kitt_opacity_inactive = 0.2;
+    }
+    if !(0.0..=1.0).contains(&settings.overlay_kitt_opacity_active) {
+        settings.overlay_kitt_opacity_active = 0.8;
+    }
+    if settings.overlay_kitt_opacity_inactive < 0.05 {
+        settings.overlay_kitt_opacity_inactive = 0.05;
+    }
+    if settings.overlay_kitt_opacity_active < 0.05 {
+        settings.overlay_kitt_opacity_active = 0.05;
+    }
+    if settings.overlay_kitt_opacity_active < settings.overlay_kitt_opacity_inactive {
+        settings.overlay_kitt_opacity_active = settings.overlay_kitt_opacity_inactive;
+    }
+    // Validate main_window_start_state
+    if !["normal", "minimized", "tray"].contains(&settings.main_window_start_state.as_str()) {
+        settings.main_window_start_state = "normal".to_string();
+    }
+    normalize_topic_keywords_fields(settings);
+    crate::hallucination_filter::normalize_hallucination_fields(settings);
+    settings.hotkey_undo_paste = settings.hotkey_undo_paste.trim().to_string();
+    if settings.undo_paste_mode != "select" {
+        settings.undo_paste_mode = "backspace".to_string();
+    }
+    // Normalize v0.7 AI fallback settings and legacy compatibility fields.
+    normalize_ai_fallback_fields(settings);
+    normalize_module_settings(&mut settings.module_settings);
+    normalize_ai_refinement_module_binding(settings);
+    normalize_gdd_module_settings(&mut settings.gdd_module_settings);
+    normalize_confluence_settings(&mut settings.confluence_settings);
+    normalize_workflow_agent_settings(&mut settings.workflow_agent);
+    normalize_assistant_core_binding(settings);
+    normalize_product_mode_field(settings);
+    normalize_assistant_presence_binding(settings);
+    normalize_vision_input_settings(&mut settings.vision_input_settings);
+    normalize_voice_output_settings(&mut settings.voice_output_settings);
+    normalize_video_generation_settings(&mut settings.video_generation_settings);
+    normalize_task_capture_settings(&mut settings.task_capture_settings);
+    normalize_cloud_transcription_settings(&mut settings.cloud_transcription);
+    normalize_translation_settings(&mut settings.translation);
+    normalize_whisper_advanced(&mut settings.whisper_advanced);
+    settings.transcribe_retry_attempts = settings.transcribe_retry_attempts.min(MAX_RETRY_ATTEMPTS);
+    settings.transcribe_retry_backoff_ms = settings
+        .transcribe_retry_backoff_ms
+        .clamp(MIN_RETRY_BACKOFF_MS, MAX_RETRY_BACKOFF_MS);
+    settings.output_mode = normalize_output_mode(&settings.output_mode);
+    settings.paste_rendition = Rendition::from_setting(&settings.paste_rendition)
+        .as_str()
+        .to_string();
+    settings.history_rendition = Rendition::from_setting(&settings.history_rendition)
+        .as_str()
+        .to_string();
+    normalize_app_overrides(&mut settings.app_overrides);
+    normalize_capture_schedule(&mut settings.capture_schedule);
+    if settings.http_api_port < 1024 {
+        settings.http_api_port = crate::http_api::DEFAULT_HTTP_API_PORT;
+    }
+    crate::webhooks::normalize_webhooks(&mut settings.webhooks);
+    settings.caption_file_path = settings.caption_file_path.trim().to_string();
+    settings.caption_file_max_chars = settings.caption_file_max_chars.clamp(20, 2000);
+    settings.caption_file_clear_secs = settings.caption_file_clear_secs.min(600);
+    crate::watch_folders::normalize_watch_folders(&mut settings.watch_folders);
+    crate::snippets::normalize_snippets(&mut settings.snippets);
+    crate::dictation_macros::normalize_macros(&mut settings.dictation_macros);
+    settings.batch_max_parallel = settings
+        .batch_max_parallel
+        .clamp(1, crate::batch_queue::MAX_BATCH_PARALLEL);
+    settings.recording_codec =
+        crate::session_manager::RecordingCodec::from_setting(settings.recording_codec.trim())
+            .extension()
+            .to_string();
+    settings.recordings_dir = settings.recordings_dir.trim().to_string();
+    settings.call_recording_layout =
+        crate::session_manager::CallLayout::from_setting(settings.call_recording_layout.trim())
+            .as_str()
+            .to_string();
+    settings.opus_bitrate_kbps = settings.opus_bitrate_kbps.clamp(6, 256);
+    settings.postproc_llm_endpoint = settings.postproc_llm_endpoint.trim().to_string();
+    settings.postproc_llm_cleanup_model = settings.postproc_llm_cleanup_model.trim().to_string();
+    settings.postproc_llm_timeout_ms = settings.postproc_llm_timeout_ms.clamp(500, 30_000);
+    settings.content_filter_action =
+        crate::content_filter::ContentFilterAction::from_setting(&settings.content_filter_action)
+            .as_str()
+            .to_string();
+    settings
+        .content_filter_words
+        .retain(|word| !word.trim().is_empty());
+    settings.unexpected_language_action =
+        crate::detected_language::UnexpectedLanguageAction::from_setting(
+            &settings.unexpected_language_action,
+        )
+        .as_str()
+        .to_string();
+    let mut expected_languages: Vec<String> = settings
+        .expected_languages
+        .iter()
+        .filter_map(|language| crate::detected_language::normalize_language_code(language))
+        .collect();
+    expected_languages.sort();
+    expected_languages.dedup();
+    settings.expected_languages = expected_languages;
+    if settings.setup.local_ai_wizard_completed {
+        settings.setup.local_ai_wizard_pending = false;
+    }
+}
+
 pub(crate) fn load_settings(app: &AppHandle) -> Settings {
     let path = resolve_config_path(app, "settings.json");
     match fs::read_to_string(path) {
         Ok(raw) => {
             let mut settings: Settings = serde_json::from_str(&raw).unwrap_or_default();
-            if settings.mode != "ptt" && settings.mode != "vad" {
-                settings.mode = "ptt".to_string();
-            }
-            // Migrate legacy vad_threshold to new dual-threshold system
-            if settings.vad_threshold_start <= 0.0 {
-                settings.vad_threshold_start = if settings.vad_threshold > 0.0 {
-                    settings.vad_threshold
-                } else {
-                    VAD_THRESHOLD_START_DEFAULT
-                };
-            }
-            if settings.vad_threshold_sustain <= 0.0 {
-                settings.vad_threshold_sustain = VAD_THRESHOLD_SUSTAIN_DEFAULT;
-            }
-            // Clamp thresholds to valid range
-            if !(0.001..=1.0).contains(&settings.vad_threshold_start) {
-                settings.vad_threshold_start = VAD_THRESHOLD_START_DEFAULT;
-            }
-            if !(0.001..=1.0).contains(&settings.vad_threshold_sustain) {
-                settings.vad_threshold_sustain = VAD_THRESHOLD_SUSTAIN_DEFAULT;
-            }
-            // Ensure sustain <= start
-            if settings.vad_threshold_sustain > settings.vad_threshold_start {
-                settings.vad_threshold_sustain = settings.vad_threshold_start;
-            }
-            // Sync legacy field
-            settings.vad_threshold = settings.vad_threshold_start;
-            if settings.vad_silence_ms < 100 {
-                settings.vad_silence_ms = VAD_SILENCE_MS_DEFAULT;
-            }
-            if !(0.0..=1.0).contains(&settings.transcribe_vad_threshold) {
-                settings.transcribe_vad_threshold = 0.04;
-            }
-            if settings.transcribe_batch_interval_ms < 4000 {
-                settings.transcribe_batch_interval_ms = 4000;
-            }
-            if settings.transcribe_batch_interval_ms > 15000 {
-                settings.transcribe_batch_interval_ms = 15000;
-            }
-            if settings.transcribe_chunk_overlap_ms > settings.transcribe_batch_interval_ms {
-                settings.transcribe_chunk_overlap_ms = settings.transcribe_batch_interval_ms / 2;
-            }
-            if settings.transcribe_chunk_overlap_ms > 3000 {
-                settings.transcribe_chunk_overlap_ms = 3000;
-            }
-            if settings.transcribe_vad_silence_ms < 200 {
-                settings.transcribe_vad_silence_ms = 200;
-            }
-            if settings.transcribe_vad_silence_ms > 5000 {
-                settings.transcribe_vad_silence_ms = 5000;
-            }
-            normalize_continuous_dump_fields(&mut settings);
-            normalize_history_alias_fields(&mut settings);
-            if settings.transcribe_backend.trim().is_empty() {
-                settings.transcribe_backend = "whisper_cpp".to_string();
-            }
-            if settings.transcribe_backend != "whisper_cpp" {
-                settings.transcribe_backend = "whisper_cpp".to_string();
-            }
-            settings.local_backend_preference = match settings
-                .local_backend_preference
-                .trim()
-                .to_ascii_lowercase()
-                .as_str()
-            {
-                "cuda" => "cuda".to_string(),
-                "vulkan" => "vulkan".to_string(),
-                _ => "auto".to_string(),
-            };
-            // Validate language_mode
-            let valid_languages = [
-                "auto", "en", "de", "fr", "es", "it", "pt", "nl", "pl", "ru", "ja", "ko", "zh",
-                "ar", "tr", "hi",
-            ];
-            if !valid_languages.contains(&settings.language_mode.as_str()) {
-                settings.language_mode = "auto".to_string();
-            }
-            settings.postproc_language = derive_postproc_language_from_asr(
-                &settings.language_mode,
-                settings.language_pinned,
-            );
-            if settings.model_source.trim().is_empty() {
-                settings.model_source = "default".to_string();
-            }
+            if settings.schema_version > SETTINGS_SCHEMA_VERSION {
+                warn!(
+                    "settings.json has schema version {} (this build knows {}); unknown fields are dropped on save",
+                    settings.schema_version, SETTINGS_SCHEMA_VERSION
+                );
+            }
+            migrate_settings(&mut settings);
+            apply_device_input_profile(&mut settings);
+            sanitize_settings(&mut settings);
             if settings.model_storage_dir.trim().is_empty() {
                 if let Ok(dir) = std::env::var("TRISPR_WHISPER_MODEL_DIR") {
                     settings.model_storage_dir = dir;
-                } else {
-                    settings.model_storage_dir = "".to_string();
                 }
             }
             sync_model_dir_env(&settings);
-            settings.transcribe_input_gain_db =
-                settings.transcribe_input_gain_db.clamp(-30.0, 30.0);
-            settings.mic_input_gain_db = settings.mic_input_gain_db.clamp(-30.0, 30.0);
-            #[cfg(target_os = "windows")]
-            if settings.transcribe_output_device != "default"
-                && !settings.transcribe_output_device.starts_with("wasapi:")
-            {
-                settings.transcribe_output_device = "default".to_string();
-            }
-            if !settings.overlay_min_radius.is_finite() {
-                settings.overlay_min_radius = 16.0;
-            }
-            if !settings.overlay_max_radius.is_finite() {
-                settings.overlay_max_radius = 64.0;
-            }
-            // Keep dot dimensions in sane bounds; monitor-relative 50% cap is
-            // applied at runtime in overlay.rs.
-            settings.overlay_min_radius = settings.overlay_min_radius.clamp(4.0, 5_000.0);
-            settings.overlay_max_radius = settings.overlay_max_radius.clamp(8.0, 10_000.0);
-            if settings.overlay_max_radius < settings.overlay_min_radius {
-                settings.overlay_max_radius = settings.overlay_min_radius;
-            }
-            if settings.overlay_rise_ms < 20 {
-                settings.overlay_rise_ms = 20;
-            }
-            if settings.overlay_rise_ms > 200 {
-                settings.overlay_rise_ms = 200;
-            }
-            if settings.overlay_fall_ms < 20 {
-                settings.overlay_fall_ms = 20;
-            }
-            if settings.overlay_fall_ms > 200 {
-                settings.overlay_fall_ms = 200;
-            }
-            if !(0.0..=1.0).contains(&settings.overlay_opacity_inactive) {
-                settings.overlay_opacity_inactive = 0.2;
-            }
-            if !(0.0..=1.0).contains(&settings.overlay_opacity_active) {
-                settings.overlay_opacity_active = 0.8;
-            }
-            if settings.overlay_opacity_inactive < 0.05 {
-                settings.overlay_opacity_inactive = 0.05;
-            }
-            if settings.overlay_opacity_active < 0.05 {
-                settings.overlay_opacity_active = 0.05;
-            }
-            if settings.overlay_opacity_active < settings.overlay_opacity_inactive {
-                settings.overlay_opacity_active = settings.overlay_opacity_inactive;
-            }
-            let defaults = Settings::default();
-            let approx_eq = |a: f32, b: f32| (a - b).abs() < 0.0001;
-            if settings.overlay_kitt_color == defaults.overlay_kitt_color
-                && settings.overlay_color != defaults.overlay_color
-            {
-                settings.overlay_kitt_color = settings.overlay_color.clone();
-            }
-            if settings.overlay_kitt_rise_ms == defaults.overlay_kitt_rise_ms
-                && settings.overlay_rise_ms != defaults.overlay_rise_ms
-            {
-                settings.overlay_kitt_rise_ms = settings.overlay_rise_ms;
-            }
-            if settings.overlay_kitt_fall_ms == defaults.overlay_kitt_fall_ms
-                && settings.overlay_fall_ms != defaults.overlay_fall_ms
-            {
-                settings.overlay_kitt_fall_ms = settings.overlay_fall_ms;
-            }
-            if approx_eq(
-                settings.overlay_kitt_opacity_inactive,
-                defaults.overlay_kitt_opacity_inactive,
-            ) && !approx_eq(
-                settings.overlay_opacity_inactive,
-                defaults.overlay_opacity_inactive,
-            ) {
-                settings.overlay_kitt_opacity_inactive = settings.overlay_opacity_inactive;
-            }
-            if approx_eq(
-                settings.overlay_kitt_opacity_active,
-                defaults.overlay_kitt_opacity_active,
-            ) && !approx_eq(
-                settings.overlay_opacity_active,
-                defaults.overlay_opacity_active,
-            ) {
-                settings.overlay_kitt_opacity_active = settings.overlay_opacity_active;
-            }
-            if settings.overlay_kitt_pos_x.is_nan() || settings.overlay_kitt_pos_y.is_nan() {
-                settings.overlay_kitt_pos_x = settings.overlay_pos_x;
-                settings.overlay_kitt_pos_y = settings.overlay_pos_y;
-            }
-            if settings.overlay_kitt_pos_x < 0.0 {
-                settings.overlay_kitt_pos_x = 0.0;
-            }
-            if settings.overlay_kitt_pos_y < 0.0 {
-                settings.overlay_kitt_pos_y = 0.0;
-            }
-            if settings.overlay_pos_x < 0.0 {
-                settings.overlay_pos_x = 0.0;
-            }
-            if settings.overlay_pos_y < 0.0 {
-                settings.overlay_pos_y = 0.0;
-            }
-            if (settings.overlay_kitt_pos_x - 12.0).abs() < 0.001
-                && (settings.overlay_kitt_pos_y - 12.0).abs() < 0.001
-                && ((settings.overlay_pos_x - 12.0).abs() > 0.001
-                    || (settings.overlay_pos_y - 12.0).abs() > 0.001)
-            {
-                settings.overlay_kitt_pos_x = settings.overlay_pos_x;
-                settings.overlay_kitt_pos_y = settings.overlay_pos_y;
-            }
-            if settings.overlay_kitt_color.trim().is_empty() {
-                settings.overlay_kitt_color = "#ff3d2e".to_string();
-            }
-            if !settings.overlay_kitt_min_width.is_finite() {
-                settings.overlay_kitt_min_width = 20.0;
-            }
-            if !settings.overlay_kitt_max_width.is_finite() {
-                settings.overlay_kitt_max_width = 700.0;
-            }
-            if !settings.overlay_kitt_height.is_finite() {
-                settings.overlay_kitt_height = 13.0;
-            }
-
-            // Keep KITT dimensions in sane bounds; monitor-relative 50% cap is
-            // applied at runtime in overlay.rs.
-            settings.overlay_kitt_min_width = settings.overlay_kitt_min_width.clamp(4.0, 10_000.0);
-            settings.overlay_kitt_max_width = settings.overlay_kitt_max_width.clamp(50.0, 20_000.0);
-            if settings.overlay_kitt_max_width < settings.overlay_kitt_min_width {
-                settings.overlay_kitt_max_width = settings.overlay_kitt_min_width.max(50.0);
-            }
-            settings.overlay_kitt_height = settings.overlay_kitt_height.clamp(8.0, 400.0);
-            if settings.overlay_kitt_rise_ms < 20 {
-                settings.overlay_kitt_rise_ms = 20;
-            }
-            if settings.overlay_kitt_rise_ms > 200 {
-                settings.overlay_kitt_rise_ms = 200;
-            }
-            if settings.overlay_kitt_fall_ms < 20 {
-                settings.overlay_kitt_fall_ms = 20;
-            }
-            if settings.overlay_kitt_fall_ms > 200 {
-                settings.overlay_kitt_fall_ms = 200;
-            }
-            if !["subtle", "standard", "intense"]
-                .contains(&settings.overlay_refining_indicator_preset.as_str())
-            {
-                settings.overlay_refining_indicator_preset = "standard".to_string();
-            }
-            if !settings.overlay_refining_indicator_color.starts_with('#')
-                || settings.overlay_refining_indicator_color.len() != 7
-            {
-                settings.overlay_refining_indicator_color = "#6ec8ff".to_string();
-            }
-            if !settings.accent_color.starts_with('#') || settings.accent_color.len() != 7 {
-                settings.accent_color = "#4be0d4".to_string();
-            }
-            if settings.overlay_refining_indicator_speed_ms < 450 {
-                settings.overlay_refining_indicator_speed_ms = 450;
-            }
-            if settings.overlay_refining_indicator_speed_ms > 3_000 {
-                settings.overlay_refining_indicator_speed_ms = 3_000;
-            }
-            if settings.overlay_refining_indicator_range < 60.0 {
-                settings.overlay_refining_indicator_range = 60.0;
-            }
-            if settings.overlay_refining_indicator_range > 180.0 {
-                settings.overlay_refining_indicator_range = 180.0;
-            }
-            if settings.hotkey_tts_stop.trim().is_empty() {
-                settings.hotkey_tts_stop = default_hotkey_tts_stop();
-            } else {
-                settings.hotkey_tts_stop = settings.hotkey_tts_stop.trim().to_string();
-            }
-            settings.overlay_tts_stop_shape = match settings
-                .overlay_tts_stop_shape
-                .trim()
-                .to_ascii_lowercase()
-                .as_str()
-            {
-                "round" => "round".to_string(),
-                _ => "compact".to_string(),
-            };
-            settings.overlay_tts_stop_color = settings.overlay_tts_stop_color.trim().to_string();
-            if settings.overlay_tts_stop_color.is_empty() {
-                settings.overlay_tts_stop_color = default_overlay_tts_stop_color();
-            }
-            if !(0.0..=1.0).contains(&settings.overlay_kitt_opacity_inactive) {
-                settings.overlay_kitt_opacity_inactive = 0.2;
-            }
-            if !(0.0..=1.0).contains(&settings.overlay_kitt_opacity_active) {
-                settings.overlay_kitt_opacity_active = 0.8;
-            }
-            if settings.overlay_kitt_opacity_inactive < 0.05 {
-                settings.overlay_kitt_opacity_inactive = 0.05;
-            }
-            if settings.overlay_kitt_opacity_active < 0.05 {
-                settings.overlay_kitt_opacity_active = 0.05;
-            }
-            if settings.overlay_kitt_opacity_active < settings.overlay_kitt_opacity_inactive {
-                settings.overlay_kitt_opacity_active = settings.overlay_kitt_opacity_inactive;
-            }
-            // Validate main_window_start_state
-            if !["normal", "minimized", "tray"].contains(&settings.main_window_start_state.as_str())
-            {
-                settings.main_window_start_state = "normal".to_string();
-            }
-            normalize_topic_keywords_fields(&mut settings);
-            // Normalize v0.7 AI fallback settings and legacy compatibility fields.
-            normalize_ai_fallback_fields(&mut settings);
-            normalize_module_settings(&mut settings.module_settings);
-            normalize_ai_refinement_module_binding(&mut settings);
-            normalize_gdd_module_settings(&mut settings.gdd_module_settings);
-            normalize_confluence_settings(&mut settings.confluence_settings);
-            normalize_workflow_agent_settings(&mut settings.workflow_agent);
-            normalize_assistant_core_binding(&mut settings);
-            normalize_product_mode_field(&mut settings);
-            normalize_assistant_presence_binding(&mut settings);
-            normalize_vision_input_settings(&mut settings.vision_input_settings);
-            normalize_voice_output_settings(&mut settings.voice_output_settings);
-            normalize_video_generation_settings(&mut settings.video_generation_settings);
-            normalize_task_capture_settings(&mut settings.task_capture_settings);
-            if settings.setup.local_ai_wizard_completed {
-                settings.setup.local_ai_wizard_pending = false;
-            }
-
             // Transcribe enablement is session-only; always start disabled.
             settings.transcribe_enabled = false;
             settings
         }
-        Err(_) => Settings::default(),
+        Err(_) => {
+            // First run: pick a model that fits this machine instead of the static default.
+            let mut settings = Settings::default();
+            crate::hardware_probe::apply_first_run_recommendation(&mut settings);
+            settings
+        }
     }
 }
 
@@ -1652,6 +2360,9 @@ pub(crate) fn normalize_continuous_dump_fields(settings: &mut Settings) {
         settings.continuous_mic_silence_flush_ms.clamp(300, 5_000);
     settings.continuous_mic_hard_cut_ms =
         settings.continuous_mic_hard_cut_ms.clamp(15_000, 120_000);
+    if settings.mic_flush_interval_ms > 0 {
+        settings.mic_flush_interval_ms = settings.mic_flush_interval_ms.clamp(4_000, 30_000);
+    }
 
     settings.continuous_system_soft_flush_ms = settings
         .continuous_system_soft_flush_ms
@@ -1821,11 +2532,12 @@ pub(crate) fn speaker_name_for_source(settings: &Settings, source: &str) -> Stri
     }
 }
 
-pub(crate) fn save_settings_file(app: &AppHandle, settings: &Settings) -> Result<(), String> {
-    let path = resolve_config_path(app, "settings.json");
+/// The form written to disk: normalized, versioned, without session-only state.
+pub(crate) fn persisted_settings(settings: &Settings) -> Settings {
     let mut persisted = settings.clone();
     // Do not persist session-only transcribe enablement.
     persisted.transcribe_enabled = false;
+    persisted.schema_version = SETTINGS_SCHEMA_VERSION;
     normalize_module_settings(&mut persisted.module_settings);
     normalize_history_alias_fields(&mut persisted);
     normalize_ai_refinement_module_binding(&mut persisted);
@@ -1839,6 +2551,16 @@ pub(crate) fn save_settings_file(app: &AppHandle, settings: &Settings) -> Result
     normalize_voice_output_settings(&mut persisted.voice_output_settings);
     normalize_video_generation_settings(&mut persisted.video_generation_settings);
     normalize_task_capture_settings(&mut persisted.task_capture_settings);
+    normalize_cloud_transcription_settings(&mut persisted.cloud_transcription);
+    normalize_translation_settings(&mut persisted.translation);
+    persisted.output_mode = normalize_output_mode(&persisted.output_mode);
+    normalize_app_overrides(&mut persisted.app_overrides);
+    persisted
+}
+
+pub(crate) fn save_settings_file(app: &AppHandle, settings: &Settings) -> Result<(), String> {
+    let path = resolve_config_path(app, "settings.json");
+    let persisted = persisted_settings(settings);
     let raw = serde_json::to_string_pretty(&persisted).map_err(|e| e.to_string())?;
     // Atomic write: write to .tmp then rename to avoid partial/corrupted JSON on crash.
     let tmp_path = path.with_extension("json.tmp");
@@ -1882,30 +2604,56 @@ pub(crate) fn push_history_entry_inner(
     text: String,
     source: String,
 ) -> Result<Vec<HistoryEntry>, String> {
-    let speaker_name = {
+    let timestamp_ms = crate::util::now_ms();
+    let (speaker_name, retention, formatted_text) = {
         let state = app.state::<AppState>();
         let settings = state
             .settings
             .read()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
-        Some(speaker_name_for_source(&settings, &source))
+        let speaker_name = speaker_name_for_source(&settings, &source);
+        let formatted_text = crate::postprocessing::apply_output_template(
+            &settings,
+            &source,
+            &speaker_name,
+            &text,
+            timestamp_ms,
+        );
+        (
+            Some(speaker_name),
+            HistoryRetention::from_settings(&settings),
+            formatted_text,
+        )
     };
     let lock_started = Instant::now();
     let mut ph = history
         .lock()
         .unwrap_or_else(|poisoned| poisoned.into_inner());
     let entry = HistoryEntry {
-        id: format!("h_{}", crate::util::now_ms()),
+        id: format!("h_{}", timestamp_ms),
         text,
-        timestamp_ms: crate::util::now_ms(),
+        timestamp_ms,
         source,
         speaker_name,
         refinement: None,
+        audio_path: None,
+        revisions: Vec::new(),
+        pinned: false,
+        tags: Vec::new(),
+        note: None,
+        formatted_text,
+        language: None,
+        translation: None,
     };
+    let session_entry = entry.clone();
     ph.push_entry(entry);
+    ph.trim_active(&retention, crate::util::now_ms());
     let updated: Vec<HistoryEntry> = ph.active.iter().cloned().collect();
     let lock_elapsed_ms = lock_started.elapsed().as_millis();
     drop(ph);
+    crate::session_manager::record_transcript_entry(app, &session_entry);
+    crate::webhooks::notify_transcript(app, &session_entry);
+    crate::caption_output::push_caption(app, &session_entry.text, &session_entry.source);
     if lock_elapsed_ms > HISTORY_LOCK_WARN_MS {
         warn!(
             "History lock hold exceeded threshold in push_history_entry_inner: {}ms",
@@ -1938,30 +2686,56 @@ pub(crate) fn push_transcribe_entry_inner(
     history: &Mutex<PartitionedHistory>,
     text: String,
 ) -> Result<Vec<HistoryEntry>, String> {
-    let speaker_name = {
+    let timestamp_ms = crate::util::now_ms();
+    let (speaker_name, retention, formatted_text) = {
         let state = app.state::<AppState>();
         let settings = state
             .settings
             .read()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
-        Some(speaker_name_for_source(&settings, "output"))
+        let speaker_name = speaker_name_for_source(&settings, "output");
+        let formatted_text = crate::postprocessing::apply_output_template(
+            &settings,
+            "output",
+            &speaker_name,
+            &text,
+            timestamp_ms,
+        );
+        (
+            Some(speaker_name),
+            HistoryRetention::from_settings(&settings),
+            formatted_text,
+        )
     };
     let lock_started = Instant::now();
     let mut ph = history
         .lock()
         .unwrap_or_else(|poisoned| poisoned.into_inner());
     let entry = HistoryEntry {
-        id: format!("o_{}", crate::util::now_ms()),
+        id: format!("o_{}", timestamp_ms),
         text,
-        timestamp_ms: crate::util::now_ms(),
+        timestamp_ms,
         source: "output".to_string(),
         speaker_name,
         refinement: None,
+        audio_path: None,
+        revisions: Vec::new(),
+        pinned: false,
+        tags: Vec::new(),
+        note: None,
+        formatted_text,
+        language: None,
+        translation: None,
     };
+    let session_entry = entry.clone();
     ph.push_entry(entry);
+    ph.trim_active(&retention, crate::util::now_ms());
     let updated: Vec<HistoryEntry> = ph.active.iter().cloned().collect();
     let lock_elapsed_ms = lock_started.elapsed().as_millis();
     drop(ph);
+    crate::session_manager::record_transcript_entry(app, &session_entry);
+    crate::webhooks::notify_transcript(app, &session_entry);
+    crate::caption_output::push_caption(app, &session_entry.text, &session_entry.source);
     if lock_elapsed_ms > HISTORY_LOCK_WARN_MS {
         warn!(
             "History lock hold exceeded threshold in push_transcribe_entry_inner: {}ms",
@@ -2063,6 +2837,17 @@ fn ensure_history_refinement(entry: &mut HistoryEntry) -> &mut HistoryRefinement
         .expect("refinement just initialized")
 }
 
+/// Records which rendition the refinement of `entry_id` runs on.
+pub(crate) fn mark_entry_refinement_rendition(
+    app: &AppHandle,
+    entry_id: &str,
+    rendition: &str,
+) -> Result<(), String> {
+    update_history_entry_refinement(app, entry_id, |entry| {
+        ensure_history_refinement(entry).rendition = rendition.to_string();
+    })
+}
+
 pub(crate) fn mark_entry_refinement_started(
     app: &AppHandle,
     entry_id: &str,
@@ -2151,6 +2936,80 @@ mod tests {
         assert_eq!(normalize_product_mode_value("unexpected"), "transcribe");
     }
 
+    #[test]
+    fn unversioned_settings_are_migrated_once() {
+        let mut legacy: Settings =
+            serde_json::from_str(r#"{"vad_threshold": 0.2, "vad_threshold_start": 0.0}"#)
+                .expect("parse legacy settings");
+        assert_eq!(legacy.schema_version, 0);
+        migrate_settings(&mut legacy);
+        assert_eq!(legacy.schema_version, SETTINGS_SCHEMA_VERSION);
+        assert!((legacy.vad_threshold_start - 0.2).abs() < f32::EPSILON);
+
+        let mut v1: Settings =
+            serde_json::from_str(r#"{"schema_version": 1, "continuous_pre_roll_ms": 800}"#)
+                .expect("parse v1 settings");
+        migrate_settings(&mut v1);
+        assert_eq!(v1.pre_roll_ms, 800);
+
+        // Current files skip the migrations, so a customized KITT overlay that
+        // happens to equal the defaults no longer inherits the dot overlay.
+        let mut current = Settings {
+            overlay_color: "#123456".to_string(),
+            ..Settings::default()
+        };
+        migrate_settings(&mut current);
+        assert_eq!(
+            current.overlay_kitt_color,
+            Settings::default().overlay_kitt_color
+        );
+        assert_eq!(
+            persisted_settings(&Settings {
+                schema_version: 0,
+                ..Settings::default()
+            })
+            .schema_version,
+            SETTINGS_SCHEMA_VERSION
+        );
+    }
+
+    #[test]
+    fn sanitize_settings_clamps_out_of_range_values() {
+        let mut settings = Settings {
+            mode: "bogus".to_string(),
+            transcribe_batch_interval_ms: 60_000,
+            overlay_opacity_active: 3.0,
+            ..Settings::default()
+        };
+        sanitize_settings(&mut settings);
+        assert_eq!(settings.mode, "ptt");
+        assert_eq!(settings.transcribe_batch_interval_ms, 15_000);
+        assert!((settings.overlay_opacity_active - 0.8).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn input_tuning_follows_the_selected_device() {
+        let mut settings = Settings {
+            input_device: "headset".to_string(),
+            vad_threshold_start: 0.05,
+            mic_input_gain_db: 6.0,
+            ..Settings::default()
+        };
+        remember_device_input_profile(&mut settings, "headset");
+
+        settings.input_device = "desk".to_string();
+        assert!(!apply_device_input_profile(&mut settings));
+        settings.vad_threshold_start = 0.01;
+        settings.mic_input_gain_db = -3.0;
+        remember_device_input_profile(&mut settings, "desk");
+
+        settings.input_device = "headset".to_string();
+        assert!(apply_device_input_profile(&mut settings));
+        assert!((settings.vad_threshold_start - 0.05).abs() < f32::EPSILON);
+        assert!((settings.mic_input_gain_db - 6.0).abs() < f32::EPSILON);
+        assert_eq!(settings.device_input_profiles.len(), 2);
+    }
+
     #[test]
     fn ai_refinement_module_migration_preserves_legacy_enabled_state() {
         let mut settings = Settings::default();
@@ -2259,7 +3118,16 @@ mod tests {
                 model: "qwen3:14b".to_string(),
                 execution_time_ms: Some(1234),
                 error: error.to_string(),
+                rendition: String::new(),
             }),
+            audio_path: None,
+            revisions: Vec::new(),
+            pinned: false,
+            tags: Vec::new(),
+            note: None,
+            formatted_text: None,
+            language: None,
+            translation: None,
         }
     }
 
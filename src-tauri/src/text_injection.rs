@@ -0,0 +1,320 @@
+//! Output mode "inject": writes the transcript straight into the focused
+//! control without touching the clipboard or sending a paste keystroke.
+//!
+//! - Windows: classic Edit/RichEdit controls get `EM_REPLACESEL`; any other
+//!   control UI Automation reports as editable receives the text as Unicode
+//!   key input, which lands at the caret and replaces the selection.
+//! - macOS: sets `AXSelectedText` on the focused element (needs the
+//!   Accessibility permission) and checks that the element took the text.
+//! - Other platforms: not offered; settings normalize "inject" to "paste".
+//!
+//! Whenever injection is not possible the text is pasted via the clipboard.
+
+use tauri::{AppHandle, Manager};
+use tracing::debug;
+
+/// Inserts `text` into the focused control, falling back to `paste_text`.
+pub(crate) fn insert_text(app_handle: &AppHandle, text: &str) -> Result<(), String> {
+    if crate::elevation::divert_if_blocked(app_handle, text)? {
+        return Ok(());
+    }
+    match platform::inject(text) {
+        Ok(()) => {
+            let state = app_handle.state::<crate::state::AppState>();
+            crate::uiautomation_capture::record_paste(&state.enter_capture, text);
+            crate::paste_undo::record_paste(text, None);
+            Ok(())
+        }
+        Err(err) => {
+            debug!("Text injection unavailable, pasting instead: {}", err);
+            crate::paste_text(app_handle, text)
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use windows::core::Interface;
+    use windows::Win32::Foundation::{HWND, LPARAM, WPARAM};
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CoUninitialize, CLSCTX_INPROC_SERVER,
+        COINIT_APARTMENTTHREADED,
+    };
+    use windows::Win32::UI::Accessibility::{
+        CUIAutomation, IUIAutomation, IUIAutomationTextPattern, IUIAutomationValuePattern,
+        UIA_DocumentControlTypeId, UIA_EditControlTypeId, UIA_TextPatternId, UIA_ValuePatternId,
+    };
+    use windows::Win32::UI::Input::KeyboardAndMouse::{
+        SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYBD_EVENT_FLAGS, KEYEVENTF_KEYUP,
+        KEYEVENTF_UNICODE, VIRTUAL_KEY, VK_RETURN,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetClassNameW, GetGUIThreadInfo, GetWindowLongW, SendMessageW, GUITHREADINFO, GWL_STYLE,
+    };
+
+    const EM_REPLACESEL: u32 = 0x00C2;
+    const ES_READONLY: i32 = 0x0800;
+
+    pub(super) fn inject(text: &str) -> Result<(), String> {
+        // SAFETY: Win32/COM calls on this thread; COM is uninitialized only
+        // if this call initialized it.
+        unsafe {
+            if let Some(hwnd) = focused_edit_control() {
+                replace_selection(hwnd, text);
+                return Ok(());
+            }
+            let initialized = CoInitializeEx(None, COINIT_APARTMENTTHREADED).is_ok();
+            let result = inject_via_uia(text);
+            if initialized {
+                CoUninitialize();
+            }
+            result
+        }
+    }
+
+    /// Focused window when it is a writable Edit/RichEdit control.
+    unsafe fn focused_edit_control() -> Option<HWND> {
+        let mut info = GUITHREADINFO {
+            cbSize: std::mem::size_of::<GUITHREADINFO>() as u32,
+            ..Default::default()
+        };
+        GetGUIThreadInfo(0, &mut info).ok()?;
+        let hwnd = info.hwndFocus;
+        if hwnd.0.is_null() {
+            return None;
+        }
+        let mut class = [0u16; 64];
+        let len = GetClassNameW(hwnd, &mut class);
+        let class = String::from_utf16_lossy(&class[..len.max(0) as usize]).to_lowercase();
+        let is_edit = class == "edit" || class.starts_with("richedit");
+        let read_only = GetWindowLongW(hwnd, GWL_STYLE) & ES_READONLY != 0;
+        (is_edit && !read_only).then_some(hwnd)
+    }
+
+    unsafe fn replace_selection(hwnd: HWND, text: &str) {
+        let wide: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
+        // wParam = TRUE keeps the insertion undoable in the target control.
+        SendMessageW(
+            hwnd,
+            EM_REPLACESEL,
+            Some(WPARAM(1)),
+            Some(LPARAM(wide.as_ptr() as isize)),
+        );
+    }
+
+    unsafe fn inject_via_uia(text: &str) -> Result<(), String> {
+        let automation: IUIAutomation =
+            CoCreateInstance(&CUIAutomation, None, CLSCTX_INPROC_SERVER)
+                .map_err(|e| format!("UI Automation init failed: {}", e))?;
+        let element = automation
+            .GetFocusedElement()
+            .map_err(|e| format!("No focused element: {}", e))?;
+        let value_pattern = element
+            .GetCurrentPattern(UIA_ValuePatternId)
+            .and_then(|raw| raw.cast::<IUIAutomationValuePattern>());
+        let editable = match value_pattern {
+            Ok(pattern) => !pattern.CurrentIsReadOnly().is_ok_and(|ro| ro.as_bool()),
+            // Rich editors often expose only the text pattern.
+            Err(_) => {
+                let control_type = element.CurrentControlType().unwrap_or_default();
+                (control_type == UIA_EditControlTypeId || control_type == UIA_DocumentControlTypeId)
+                    && element
+                        .GetCurrentPattern(UIA_TextPatternId)
+                        .and_then(|raw| raw.cast::<IUIAutomationTextPattern>())
+                        .is_ok()
+            }
+        };
+        if !editable {
+            return Err("Focused element is not editable".to_string());
+        }
+        send_unicode(text)
+    }
+
+    /// Types `text` as Unicode key events in one batch; the control inserts
+    /// them at its caret, replacing the selection.
+    unsafe fn send_unicode(text: &str) -> Result<(), String> {
+        let mut inputs = Vec::with_capacity(text.len() * 2);
+        let mut push = |vk: VIRTUAL_KEY, scan: u16, flags: KEYBD_EVENT_FLAGS| {
+            for up in [KEYBD_EVENT_FLAGS(0), KEYEVENTF_KEYUP] {
+                inputs.push(INPUT {
+                    r#type: INPUT_KEYBOARD,
+                    Anonymous: INPUT_0 {
+                        ki: KEYBDINPUT {
+                            wVk: vk,
+                            wScan: scan,
+                            dwFlags: flags | up,
+                            time: 0,
+                            dwExtraInfo: 0,
+                        },
+                    },
+                });
+            }
+        };
+        for (index, line) in text.replace("\r\n", "\n").split('\n').enumerate() {
+            if index > 0 {
+                push(VK_RETURN, 0, KEYBD_EVENT_FLAGS(0));
+            }
+            for unit in line.encode_utf16() {
+                push(VIRTUAL_KEY(0), unit, KEYEVENTF_UNICODE);
+            }
+        }
+        if inputs.is_empty() {
+            return Ok(());
+        }
+        let sent = SendInput(&inputs, std::mem::size_of::<INPUT>() as i32);
+        if sent as usize != inputs.len() {
+            return Err(format!(
+                "SendInput delivered {} of {} key events",
+                sent,
+                inputs.len()
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::ffi::c_void;
+
+    use core_foundation::base::{CFRelease, CFTypeRef, TCFType};
+    use core_foundation::number::{CFNumber, CFNumberRef};
+    use core_foundation::string::{CFString, CFStringRef};
+
+    type AXUIElementRef = *const c_void;
+    type AXValueRef = *const c_void;
+    type AXError = i32;
+
+    const AX_ERROR_SUCCESS: AXError = 0;
+    const AX_ERROR_ATTRIBUTE_UNSUPPORTED: AXError = -25205;
+    const AX_VALUE_CF_RANGE_TYPE: u32 = 4;
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct CFRange {
+        location: isize,
+        length: isize,
+    }
+
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXIsProcessTrusted() -> bool;
+        fn AXUIElementCreateSystemWide() -> AXUIElementRef;
+        fn AXUIElementCopyAttributeValue(
+            element: AXUIElementRef,
+            attribute: CFStringRef,
+            value: *mut CFTypeRef,
+        ) -> AXError;
+        fn AXUIElementIsAttributeSettable(
+            element: AXUIElementRef,
+            attribute: CFStringRef,
+            settable: *mut u8,
+        ) -> AXError;
+        fn AXUIElementSetAttributeValue(
+            element: AXUIElementRef,
+            attribute: CFStringRef,
+            value: CFTypeRef,
+        ) -> AXError;
+        fn AXValueGetValue(value: AXValueRef, value_type: u32, out: *mut c_void) -> bool;
+    }
+
+    pub(super) fn inject(text: &str) -> Result<(), String> {
+        // SAFETY: every AX object obtained here is released before returning.
+        unsafe {
+            if !AXIsProcessTrusted() {
+                return Err("Accessibility permission not granted".to_string());
+            }
+            let system = AXUIElementCreateSystemWide();
+            let mut focused: CFTypeRef = std::ptr::null();
+            let status = AXUIElementCopyAttributeValue(
+                system,
+                CFString::from_static_string("AXFocusedUIElement").as_concrete_TypeRef(),
+                &mut focused,
+            );
+            CFRelease(system as CFTypeRef);
+            if status != AX_ERROR_SUCCESS || focused.is_null() {
+                return Err(format!("No focused element (AXError {})", status));
+            }
+            let result = set_selected_text(focused, text);
+            CFRelease(focused);
+            result
+        }
+    }
+
+    /// Replaces the selection of `element` with `text`. Many elements report
+    /// success for `AXSelectedText` without changing anything, so the
+    /// character count is compared before and after.
+    unsafe fn set_selected_text(element: AXUIElementRef, text: &str) -> Result<(), String> {
+        let attribute = CFString::from_static_string("AXSelectedText");
+        let mut settable = 0u8;
+        let mut status =
+            AXUIElementIsAttributeSettable(element, attribute.as_concrete_TypeRef(), &mut settable);
+        if status == AX_ERROR_SUCCESS && settable == 0 {
+            status = AX_ERROR_ATTRIBUTE_UNSUPPORTED;
+        }
+        if status != AX_ERROR_SUCCESS {
+            return Err(format!(
+                "AXSelectedText is not settable (AXError {})",
+                status
+            ));
+        }
+
+        let count_before = character_count(element);
+        let selected = selected_length(element);
+        status = AXUIElementSetAttributeValue(
+            element,
+            attribute.as_concrete_TypeRef(),
+            CFString::new(text).as_CFTypeRef(),
+        );
+        if status != AX_ERROR_SUCCESS {
+            return Err(format!(
+                "Setting AXSelectedText failed (AXError {})",
+                status
+            ));
+        }
+
+        // Without a readable count the element cannot be checked; trust it.
+        let (Some(before), Some(selected)) = (count_before, selected) else {
+            return Ok(());
+        };
+        let expected = before - selected + text.encode_utf16().count() as i64;
+        if expected != before && character_count(element) == Some(before) {
+            return Err("Focused element ignored AXSelectedText".to_string());
+        }
+        Ok(())
+    }
+
+    unsafe fn copy_attribute(element: AXUIElementRef, name: &'static str) -> Option<CFTypeRef> {
+        let mut value: CFTypeRef = std::ptr::null();
+        let status = AXUIElementCopyAttributeValue(
+            element,
+            CFString::from_static_string(name).as_concrete_TypeRef(),
+            &mut value,
+        );
+        (status == AX_ERROR_SUCCESS && !value.is_null()).then_some(value)
+    }
+
+    unsafe fn character_count(element: AXUIElementRef) -> Option<i64> {
+        let value = copy_attribute(element, "AXNumberOfCharacters")?;
+        CFNumber::wrap_under_create_rule(value as CFNumberRef).to_i64()
+    }
+
+    unsafe fn selected_length(element: AXUIElementRef) -> Option<i64> {
+        let value = copy_attribute(element, "AXSelectedTextRange")?;
+        let mut range = CFRange::default();
+        let ok = AXValueGetValue(
+            value as AXValueRef,
+            AX_VALUE_CF_RANGE_TYPE,
+            &mut range as *mut CFRange as *mut c_void,
+        );
+        CFRelease(value);
+        ok.then_some(range.length as i64)
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+mod platform {
+    pub(super) fn inject(_text: &str) -> Result<(), String> {
+        Err("Accessibility text injection is not supported on this platform".to_string())
+    }
+}
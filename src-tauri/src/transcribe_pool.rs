@@ -0,0 +1,175 @@
+//! Parallel decoding for the system-audio (loopback) queue.
+//!
+//! Chunks are numbered as they leave the queue, decoded by up to
+//! `transcribe_parallel_workers` threads at once and handed back strictly in
+//! sequence order, so history entries keep the order they were spoken in even
+//! when a short chunk finishes before a long one.
+
+use std::collections::BTreeMap;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{mpsc, Mutex};
+
+use tracing::error;
+
+pub(crate) const MAX_TRANSCRIBE_WORKERS: usize = 4;
+
+/// Workers to run for `configured`, leaving half the cores to whisper's own
+/// threads and the rest of the app. GPU backends get a single worker: each
+/// whisper-cli loads its own copy of the model into VRAM.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub(crate) fn effective_worker_count(configured: usize, cpu_threads: usize, gpu: bool) -> usize {
+    if gpu {
+        return 1;
+    }
+    configured
+        .clamp(1, MAX_TRANSCRIBE_WORKERS)
+        .min((cpu_threads / 2).max(1))
+}
+
+/// Releases items in sequence order, holding back results that overtook an
+/// earlier chunk.
+struct ReorderBuffer<T> {
+    next_seq: u64,
+    pending: BTreeMap<u64, T>,
+}
+
+impl<T> ReorderBuffer<T> {
+    fn new() -> Self {
+        Self {
+            next_seq: 0,
+            pending: BTreeMap::new(),
+        }
+    }
+
+    fn push(&mut self, seq: u64, item: T) -> Vec<T> {
+        self.pending.insert(seq, item);
+        let mut ready = Vec::new();
+        while let Some(item) = self.pending.remove(&self.next_seq) {
+            ready.push(item);
+            self.next_seq += 1;
+        }
+        ready
+    }
+}
+
+/// Runs `decode` on up to `workers` jobs at once and calls `publish` on the
+/// calling thread in job order. `jobs` is pulled on its own thread and only
+/// when a worker is free, so a blocking source (the audio queue) keeps its
+/// backlog accounting. Returns once `jobs` is exhausted and every result is
+/// published; a job whose decode panicked is skipped. With one worker each
+/// job is decoded only after the previous one was published.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub(crate) fn run_ordered<I, O>(
+    workers: usize,
+    jobs: impl Iterator<Item = I> + Send,
+    decode: impl Fn(I) -> O + Sync,
+    mut publish: impl FnMut(O),
+) where
+    I: Send,
+    O: Send,
+{
+    if workers <= 1 {
+        for (seq, job) in (0u64..).zip(jobs) {
+            match catch_unwind(AssertUnwindSafe(|| decode(job))) {
+                Ok(output) => publish(output),
+                Err(_) => error!("Transcription worker panicked on chunk {}", seq),
+            }
+        }
+        return;
+    }
+    let (job_tx, job_rx) = mpsc::sync_channel::<(u64, I)>(0);
+    let job_rx = Mutex::new(job_rx);
+    let (result_tx, result_rx) = mpsc::channel::<(u64, Option<O>)>();
+    let decode = &decode;
+    let job_rx = &job_rx;
+
+    std::thread::scope(|scope| {
+        scope.spawn(move || {
+            for (seq, job) in (0u64..).zip(jobs) {
+                if job_tx.send((seq, job)).is_err() {
+                    return;
+                }
+            }
+        });
+        for _ in 0..workers {
+            let result_tx = result_tx.clone();
+            scope.spawn(move || loop {
+                let next = job_rx
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .recv();
+                let Ok((seq, job)) = next else {
+                    return;
+                };
+                let output = catch_unwind(AssertUnwindSafe(|| decode(job)));
+                if output.is_err() {
+                    error!("Transcription worker panicked on chunk {}", seq);
+                }
+                if result_tx.send((seq, output.ok())).is_err() {
+                    return;
+                }
+            });
+        }
+        drop(result_tx);
+
+        let mut reorder = ReorderBuffer::new();
+        for (seq, output) in result_rx {
+            for output in reorder.push(seq, output).into_iter().flatten() {
+                publish(output);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reorders_results_by_sequence() {
+        let mut buffer = ReorderBuffer::new();
+        assert!(buffer.push(1, "b").is_empty());
+        assert!(buffer.push(2, "c").is_empty());
+        assert_eq!(buffer.push(0, "a"), vec!["a", "b", "c"]);
+        assert_eq!(buffer.push(3, "d"), vec!["d"]);
+
+        assert_eq!(effective_worker_count(0, 16, false), 1);
+        assert_eq!(effective_worker_count(3, 16, false), 3);
+        assert_eq!(effective_worker_count(8, 16, false), MAX_TRANSCRIBE_WORKERS);
+        assert_eq!(effective_worker_count(4, 4, false), 2);
+        assert_eq!(effective_worker_count(4, 1, false), 1);
+        assert_eq!(effective_worker_count(4, 16, true), 1);
+    }
+
+    #[test]
+    fn publishes_in_job_order_despite_uneven_decode_times() {
+        let mut published = Vec::new();
+        run_ordered(
+            3,
+            0..12u64,
+            |n| {
+                std::thread::sleep(std::time::Duration::from_millis((12 - n) * 2));
+                n
+            },
+            |n| published.push(n),
+        );
+        assert_eq!(published, (0..12).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn single_worker_decodes_after_the_previous_publish() {
+        let published = std::sync::atomic::AtomicU64::new(0);
+        run_ordered(
+            1,
+            0..5u64,
+            |n| {
+                assert_eq!(published.load(std::sync::atomic::Ordering::SeqCst), n);
+                n
+            },
+            |_| {
+                published.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            },
+        );
+        assert_eq!(published.into_inner(), 5);
+    }
+}
@@ -14,32 +14,31 @@ use crate::constants::{
 };
 #[cfg(target_os = "windows")]
 use crate::continuous_dump::{AdaptiveSegmenter, AdaptiveSegmenterConfig};
+#[cfg(target_os = "windows")]
+use crate::detected_language::UnexpectedLanguageAction;
 use crate::errors::AppError;
+use crate::hallucination_filter::{normalize_phrase, HallucinationFilter};
 use crate::models::resolve_model_path;
 use crate::overlay::{emit_capture_idle_overlay, update_overlay_state, OverlayState};
-#[cfg(target_os = "windows")]
-use crate::paths::resolve_recordings_dir;
 use crate::paths::{resolve_whisper_cli_path_for_backend, resolve_whisper_server_path_for_backend};
 #[cfg(target_os = "windows")]
-use crate::postprocessing::process_transcript;
+use crate::postprocessing::{process_for_output, Rendition};
 #[cfg(target_os = "windows")]
 use crate::state::push_transcribe_entry_inner;
 use crate::state::{AppState, Settings};
-use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, VecDeque};
+use serde::Serialize;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
 use std::io::ErrorKind;
 #[cfg(target_os = "windows")]
 use std::os::windows::process::CommandExt;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
-use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU8, AtomicUsize, Ordering};
 use std::sync::{Arc, Condvar, Mutex, OnceLock};
 use std::thread;
-use std::time::Duration;
-#[cfg(target_os = "windows")]
-use std::time::Instant;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter, Manager};
 use tracing::info;
 use tracing::{error, warn};
@@ -48,7 +47,6 @@ const TRANSCRIPTION_ACCEL_UNKNOWN: u8 = 0;
 const TRANSCRIPTION_ACCEL_CPU: u8 = 1;
 const TRANSCRIPTION_ACCEL_GPU: u8 = 2;
 static LAST_TRANSCRIPTION_ACCELERATOR: AtomicU8 = AtomicU8::new(TRANSCRIPTION_ACCEL_UNKNOWN);
-static LAST_TRANSCRIPTION_TIMING: OnceLock<Mutex<TranscriptionTimingSummary>> = OnceLock::new();
 static CUDA_BACKEND_UNSTABLE: AtomicBool = AtomicBool::new(false);
 static VULKAN_BACKEND_UNSTABLE: AtomicBool = AtomicBool::new(false);
 const CUDA_RUNTIME_REQUIRED_FILES: &[&str] = &[
@@ -122,24 +120,37 @@ impl Default for TranscriptionTimingSummary {
     }
 }
 
+thread_local! {
+    /// Timing of the last local transcription on this thread. Kept per thread
+    /// so parallel loopback workers don't overwrite each other's numbers.
+    static LAST_TRANSCRIPTION_TIMING: RefCell<TranscriptionTimingSummary> =
+        RefCell::new(TranscriptionTimingSummary::default());
+    /// When whisper (server request or CLI attempt) last started on this thread.
+    static WHISPER_STARTED_AT: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// Timing of the last local transcription run on the calling thread.
 pub(crate) fn last_transcription_timing_summary() -> TranscriptionTimingSummary {
-    LAST_TRANSCRIPTION_TIMING
-        .get_or_init(|| Mutex::new(TranscriptionTimingSummary::default()))
-        .lock()
-        .map(|guard| guard.clone())
-        .unwrap_or_default()
+    LAST_TRANSCRIPTION_TIMING.with(|timing| timing.borrow().clone())
+}
+
+/// Start of the last whisper run on the calling thread; consumed by the read.
+pub(crate) fn take_whisper_started_at() -> Option<Instant> {
+    WHISPER_STARTED_AT.with(|started| started.take())
+}
+
+fn mark_whisper_started() -> Instant {
+    let now = Instant::now();
+    WHISPER_STARTED_AT.with(|started| started.set(Some(now)));
+    now
 }
 
 fn record_transcription_timing(summary: TranscriptionTimingSummary) {
-    if let Ok(mut guard) = LAST_TRANSCRIPTION_TIMING
-        .get_or_init(|| Mutex::new(TranscriptionTimingSummary::default()))
-        .lock()
-    {
-        *guard = summary;
-    }
+    LAST_TRANSCRIPTION_TIMING.with(|timing| *timing.borrow_mut() = summary);
 }
 
 fn reset_transcription_timing(settings: &Settings) {
+    WHISPER_STARTED_AT.with(|started| started.set(None));
     record_transcription_timing(TranscriptionTimingSummary {
         language_pinned: settings.language_pinned,
         language_mode: effective_language_mode(settings),
@@ -148,6 +159,15 @@ fn reset_transcription_timing(settings: &Settings) {
     });
 }
 
+/// Language of a result when whisper was told which one to expect.
+fn pinned_language_code(settings: &Settings) -> Option<String> {
+    if settings.language_pinned {
+        crate::detected_language::normalize_language_code(&settings.language_mode)
+    } else {
+        None
+    }
+}
+
 fn effective_language_mode(settings: &Settings) -> String {
     if settings.language_pinned {
         settings.language_mode.clone()
@@ -210,6 +230,14 @@ struct ContinuousDumpStats {
     percent_used: u8,
 }
 
+/// `system_segmenter_config` with chunks stretched by the backlog adapter.
+#[cfg(target_os = "windows")]
+fn adapted_segmenter_config(settings: &Settings, interval_scale: f32) -> AdaptiveSegmenterConfig {
+    let mut cfg = system_segmenter_config(settings);
+    cfg.soft_flush_ms = (cfg.soft_flush_ms as f32 * interval_scale) as u64;
+    cfg
+}
+
 #[cfg(target_os = "windows")]
 fn system_segmenter_config(settings: &Settings) -> AdaptiveSegmenterConfig {
     if !settings.continuous_dump_enabled {
@@ -286,8 +314,13 @@ pub(crate) struct TranscriptionResult {
     pub(crate) paste_timeout_ms: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) entry_id: Option<String>,
+    /// Whisper language code of the transcript, when known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) detected_language: Option<String>,
     pub(crate) audio_duration_ms: u64,
     pub(crate) word_count: u32,
+    /// Stage timings; `paste_ms` is only known after this event (see `get_latency_stats`).
+    pub(crate) latency: crate::latency_stats::LatencyBreakdown,
     pub(crate) refinement_gate: RefinementGateDecision,
 }
 
@@ -468,9 +501,10 @@ fn backlog_status_from_queue(queue: &AudioQueueState) -> TranscribeBacklogStatus
 #[cfg(test)]
 mod tests {
     use super::{
-        backlog_capacity_for_batch_ms, gpu_backend_attempt_order, should_drop_transcript,
-        whisper_runtime_auto_warm_required, whisper_runtime_preflight_issue,
-        whisper_runtime_required, AudioQueue, CUDA_BACKEND_UNSTABLE, CUDA_RUNTIME_REQUIRED_FILES,
+        backlog_capacity_for_batch_ms, gpu_backend_attempt_order, local_source_tag,
+        should_drop_transcript, transcription_engine_order, whisper_runtime_auto_warm_required,
+        whisper_runtime_preflight_issue, whisper_runtime_required, AudioQueue, HallucinationFilter,
+        CUDA_BACKEND_UNSTABLE, CUDA_RUNTIME_REQUIRED_FILES,
     };
     use crate::state::Settings;
     use std::fs;
@@ -517,19 +551,52 @@ mod tests {
 
     #[test]
     fn short_meaningful_transcript_is_not_dropped() {
+        let filter = HallucinationFilter::for_settings(&Settings::default(), Some("de"));
         assert!(!should_drop_transcript(
             "Bitte speichere das",
             0.001,
             450,
-            false
+            false,
+            &filter
         ));
-        assert!(!should_drop_transcript("das passt", 0.002, 300, false));
+        assert!(!should_drop_transcript(
+            "das passt",
+            0.002,
+            300,
+            false,
+            &filter
+        ));
+        assert!(!should_drop_transcript("okay", 0.002, 300, false, &filter));
     }
 
     #[test]
     fn common_short_hallucination_is_dropped() {
-        assert!(should_drop_transcript("thank you", 0.002, 500, false));
-        assert!(should_drop_transcript("uh", 0.001, 400, false));
+        let filter = HallucinationFilter::for_settings(&Settings::default(), Some("en"));
+        assert!(should_drop_transcript(
+            "thank you",
+            0.002,
+            500,
+            false,
+            &filter
+        ));
+        assert!(should_drop_transcript("uh", 0.001, 400, false, &filter));
+    }
+
+    #[test]
+    fn allowed_phrase_survives_strict_loopback_filter() {
+        let settings = Settings {
+            hallucination_allowed_phrases: vec!["Ja genau".to_string()],
+            ..Settings::default()
+        };
+        let filter = HallucinationFilter::for_settings(&settings, Some("de"));
+        assert!(!should_drop_transcript(
+            "Ja, genau.",
+            0.01,
+            800,
+            true,
+            &filter
+        ));
+        assert!(should_drop_transcript("Na gut.", 0.01, 800, true, &filter));
     }
 
     #[test]
@@ -625,6 +692,77 @@ mod tests {
 
         let _ = fs::remove_dir_all(&base);
     }
+
+    #[test]
+    fn engine_order_skips_cloud_unless_enabled() {
+        let mut settings = Settings::default();
+        assert_eq!(transcription_engine_order(&settings, false), vec!["local"]);
+
+        settings.cloud_fallback = true;
+        assert_eq!(transcription_engine_order(&settings, false), vec!["local"]);
+        assert_eq!(
+            transcription_engine_order(&settings, true),
+            vec!["cloud", "local"]
+        );
+
+        settings.cloud_transcription.enabled = true;
+        assert_eq!(
+            transcription_engine_order(&settings, false),
+            vec!["local", "cloud"]
+        );
+        settings.cloud_transcription.fallback_order = vec!["cloud".to_string()];
+        assert_eq!(transcription_engine_order(&settings, false), vec!["cloud"]);
+    }
+
+    #[test]
+    fn translated_local_results_get_their_own_source_tag() {
+        let mut settings = Settings::default();
+        assert_eq!(local_source_tag(&settings), "local");
+        settings.translate_to_english = true;
+        assert_eq!(local_source_tag(&settings), "local-translated");
+    }
+
+    #[test]
+    fn initial_prompt_puts_context_after_the_vocabulary() {
+        let terms = vec![
+            "Trispr".to_string(),
+            " trispr ".to_string(),
+            "Anja".to_string(),
+        ];
+        assert_eq!(
+            build_whisper_initial_prompt(&terms, Some("  the meeting went on ")).as_deref(),
+            Some("Trispr, Anja. the meeting went on")
+        );
+        assert_eq!(
+            build_whisper_initial_prompt(&[], Some("previous chunk")).as_deref(),
+            Some("previous chunk")
+        );
+        assert_eq!(build_whisper_initial_prompt(&[], Some("   ")), None);
+
+        // The context keeps its room; vocabulary terms give way.
+        let many: Vec<String> = (0..200).map(|i| format!("term{i:03}")).collect();
+        let context = "c".repeat(200);
+        let prompt = build_whisper_initial_prompt(&many, Some(&context)).unwrap();
+        assert!(prompt.len() <= 900);
+        assert!(prompt.ends_with(&format!(". {context}")));
+        assert!(prompt.starts_with("term000, term001"));
+    }
+
+    #[test]
+    fn stdin_rejection_is_recognised_from_whisper_stderr() {
+        assert!(whisper_stderr_rejects_stdin(
+            "error: input file not found '-'"
+        ));
+        assert!(whisper_stderr_rejects_stdin(
+            "error: failed to read WAV file '-'"
+        ));
+        assert!(!whisper_stderr_rejects_stdin(
+            "whisper_init_from_file_with_params_no_state: loading model"
+        ));
+        assert!(!whisper_stderr_rejects_stdin(
+            "error: failed to open 'model.bin'"
+        ));
+    }
 }
 
 fn emit_transcribe_idle(app: &AppHandle) {
@@ -691,6 +829,15 @@ pub(crate) fn start_transcribe_monitor(
         error!("SECURITY: Attempted to start transcribe monitor while transcribe_enabled=false. Blocking.");
         return Err("Transcription is disabled in settings".to_string());
     }
+    if crate::privacy_mute::is_muted() {
+        return Err("Privacy mute is on".to_string());
+    }
+    if crate::capture_policy::is_paused() {
+        return Err("Capture is paused while a call or fullscreen app is active".to_string());
+    }
+    if crate::capture_schedule::system_audio_blocked() {
+        return Err("System audio capture is outside its scheduled window".to_string());
+    }
 
     let mut recorder = state
         .transcribe
@@ -848,7 +995,8 @@ pub(crate) fn whisper_runtime_required(settings: &Settings) -> bool {
 }
 
 pub(crate) fn whisper_runtime_auto_warm_required(settings: &Settings) -> bool {
-    settings.transcribe_enabled || (settings.capture_enabled && settings.mode == "vad")
+    settings.transcribe_enabled
+        || (settings.capture_enabled && crate::state::mode_uses_vad_monitor(&settings.mode))
 }
 
 pub(crate) fn reconcile_whisper_runtime(app: &AppHandle, state: &AppState, settings: &Settings) {
@@ -916,16 +1064,6 @@ pub(crate) fn rms_i16(samples: &[i16]) -> f32 {
     (sum / samples.len() as f32).sqrt().clamp(0.0, 1.0)
 }
 
-fn normalize_transcript(text: &str) -> String {
-    text.chars()
-        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
-        .collect::<String>()
-        .to_lowercase()
-        .split_whitespace()
-        .collect::<Vec<_>>()
-        .join(" ")
-}
-
 /// Drop-filter for transcribed text.
 ///
 /// * `strict = false` (mic input): drops a known hallucination phrase only when the
@@ -938,18 +1076,24 @@ fn normalize_transcript(text: &str) -> String {
 ///   2. Any utterance that is ≤ 2 words **and** ≤ 15 characters is dropped — these
 ///      are almost always background-audio noise ("All right.", "Oh.", "Fine.") that
 ///      Whisper transcribes but are not useful content.
+///
+/// Phrases on the filter's always-allow list are never dropped.
 pub(crate) fn should_drop_transcript(
     text: &str,
     _rms: f32,
     duration_ms: u64,
     strict: bool,
+    filter: &HallucinationFilter,
 ) -> bool {
-    let normalized = normalize_transcript(text);
+    let normalized = normalize_phrase(text);
     if normalized.is_empty() {
         return true;
     }
+    if filter.allows(&normalized) {
+        return false;
+    }
 
-    let matches_common = HALLUCINATION_PHRASES.iter().any(|p| *p == normalized);
+    let matches_common = filter.matches(&normalized);
 
     if strict {
         if matches_common {
@@ -969,104 +1113,6 @@ pub(crate) fn should_drop_transcript(
     false
 }
 
-pub(crate) fn should_drop_by_activation_words(
-    text: &str,
-    activation_words: &[String],
-    enabled: bool,
-) -> bool {
-    if !enabled || activation_words.is_empty() {
-        return false; // Don't drop
-    }
-
-    let normalized_text = normalize_transcript(text);
-    let words: Vec<&str> = normalized_text.split_whitespace().collect();
-
-    // Check if any activation word exists as complete word
-    for activation_word in activation_words {
-        for word in &words {
-            if *word == activation_word.as_str() {
-                return false; // Found activation word, don't drop
-            }
-        }
-    }
-
-    true // No activation word found, drop
-}
-
-const HALLUCINATION_PHRASES: &[&str] = &[
-    // Filler sounds / acknowledgements
-    "uh",
-    "um",
-    "hmm",
-    "huh",
-    "ah",
-    "oh",
-    "uh huh",
-    // Single-word reactions
-    "yes",
-    "no",
-    "okay",
-    "ok",
-    "yeah",
-    "right",
-    "sure",
-    "fine",
-    "good",
-    "great",
-    "nice",
-    "wow",
-    "cool",
-    "really",
-    "exactly",
-    "absolutely",
-    "definitely",
-    "correct",
-    "true",
-    "hey",
-    "hi",
-    "hello",
-    "bye",
-    "goodbye",
-    "welcome",
-    "please",
-    "wait",
-    "sorry",
-    // Gratitude / social phrases
-    "you",
-    "thank you",
-    "thanks",
-    // Two-word phrases common in background audio
-    "all right",
-    "alright",
-    "oh no",
-    "oh yeah",
-    "oh well",
-    "oh wow",
-    "oh my",
-    "come on",
-    "go on",
-    "hold on",
-    "i see",
-    "me too",
-    "of course",
-    "no no",
-    "yes yes",
-    "good job",
-    "well done",
-    "no problem",
-    "no worries",
-    "for sure",
-    "see ya",
-    "take care",
-    "good luck",
-    "good night",
-    "good morning",
-    "thats right",
-    "youre right",
-    "youre welcome",
-    "not bad",
-];
-
 /// Flush accumulated system audio as a session chunk via SessionManager.
 /// Replaces the old per-flush file approach: chunks go to a temp session dir
 /// and are merged into a single session.opus when the session ends.
@@ -1143,7 +1189,8 @@ fn transcribe_worker(
     let diagnostics_enabled = crate::state::diagnostic_logging_enabled();
     let min_samples = (TARGET_SAMPLE_RATE as u64 * MIN_AUDIO_MS / 1000) as usize;
     // System audio auto-save buffer (accumulates chunks before flushing to session)
-    let auto_save = settings.auto_save_system_audio && settings.opus_enabled;
+    let auto_save =
+        settings.auto_save_system_audio && crate::session_manager::recording_available(&settings);
     let mut save_buffer: Vec<i16> = Vec::new();
     let mut saved_chunk_count: u64 = 0;
     let overlap_samples = 0usize;
@@ -1152,14 +1199,27 @@ fn transcribe_worker(
 
     // Initialise SessionManager with the recordings directory for this session
     if auto_save {
-        let recordings_dir = resolve_recordings_dir(&app);
-        let modules_dir = crate::paths::resolve_modules_dir(&app);
-        crate::session_manager::init(recordings_dir, modules_dir);
+        crate::session_manager::init_from_settings(&app, &settings);
     }
 
-    while let Some(chunk) = queue.pop() {
+    // Context priming reads the previous chunk's text, so chunks must be
+    // decoded one after another, each after the previous one was published.
+    let workers = if settings.transcribe_context_priming {
+        1
+    } else {
+        crate::transcribe_pool::effective_worker_count(
+            settings.transcribe_parallel_workers,
+            thread::available_parallelism().map_or(1, |n| n.get()),
+            effective_cli_backend_preference(&settings) != "cpu",
+        )
+    };
+    let in_flight = AtomicUsize::new(0);
+    // Raw text of the last stored chunk, for trimming the pre-roll repeat.
+    let mut previous_text: Option<String> = None;
+    let context = crate::context_priming::RollingContext::default();
+    let chunks = std::iter::from_fn(|| queue.pop()).filter_map(|chunk| {
         if chunk.len() < min_samples {
-            continue;
+            return None;
         }
 
         // Accumulate chunks for system audio session
@@ -1178,109 +1238,72 @@ fn transcribe_worker(
         let level = rms_i16(&chunk);
         let duration_ms = chunk.len() as u64 * 1000 / TARGET_SAMPLE_RATE as u64;
 
-        if settings.transcribe_vad_mode {
-            if level < settings.transcribe_vad_threshold {
-                continue;
-            }
+        if settings.transcribe_vad_mode && level < settings.transcribe_vad_threshold {
+            return None;
         }
+        Some(LoopbackChunk {
+            samples: chunk,
+            level,
+            duration_ms,
+        })
+    });
 
-        transcribing.store(true, Ordering::Relaxed);
-        let _ = app.emit("transcribe:state", "transcribing");
-        update_transcribe_overlay(&app, true);
-        let result = transcribe_audio(&app, &settings, &chunk);
-        transcribing.store(false, Ordering::Relaxed);
-        update_transcribe_overlay(&app, false);
-
-        match result {
-            Ok((text, _source)) => {
-                let _ = app.emit(
-                    "transcription:raw-result",
-                    crate::workflow_agent::RawTranscriptionEvent {
-                        text: text.clone(),
-                        source: "output".to_string(),
-                        timestamp_ms: crate::util::now_ms(),
-                    },
-                );
-                if text.trim().is_empty()
-                    || should_drop_transcript(&text, level, duration_ms, true)
-                    || should_drop_by_activation_words(
-                        &text,
-                        &settings.activation_words,
-                        settings.activation_words_enabled,
-                    )
-                {
-                    let _ = app.emit(
-                        "transcription:dropped",
-                        serde_json::json!({
-                            "source": "output",
-                            "text": text,
-                            "reason": "filtered",
-                        }),
-                    );
-                } else {
-                    // Apply post-processing if enabled
-                    let processed_text = if settings.postproc_enabled {
-                        match process_transcript(&text, &settings, &app) {
-                            Ok(processed) => processed,
-                            Err(e) => {
-                                error!("Post-processing failed: {}", e);
-                                text.clone() // Fallback to original
-                            }
-                        }
-                    } else {
-                        text.clone()
-                    };
-
-                    let state = app.state::<AppState>();
-                    let push_result = push_transcribe_entry_inner(
-                        &app,
-                        &state.history_transcribe,
-                        processed_text.clone(),
+    crate::transcribe_pool::run_ordered(
+        workers,
+        chunks,
+        |job| {
+            if in_flight.fetch_add(1, Ordering::Relaxed) == 0 {
+                transcribing.store(true, Ordering::Relaxed);
+                let _ = app.emit("transcribe:state", "transcribing");
+                update_transcribe_overlay(&app, true);
+            }
+            let fast_decode = crate::backlog_adaptation::fast_decode_active();
+            let prompt_context = if settings.transcribe_context_priming && !fast_decode {
+                context.get()
+            } else {
+                None
+            };
+            let result = crate::transcription_jobs::run_job(
+                &app,
+                crate::transcription_jobs::JobSource::Loopback,
+                job.duration_ms,
+                || {
+                    let result = crate::backlog_adaptation::with_fast_decode(
+                        fast_decode.then_some(job.duration_ms),
+                        || {
+                            crate::context_priming::with_context(prompt_context, || {
+                                transcribe_audio(&app, &settings, &job.samples)
+                            })
+                        },
                     );
-
-                    // System audio cluster tracking for AI refinement
-                    if let Ok(ref updated) = push_result {
-                        if let Some(new_entry) = updated.first() {
-                            let now = crate::util::now_ms();
-                            let flush_entries = {
-                                let mut cluster = state
-                                    .system_cluster_buffer
-                                    .lock()
-                                    .unwrap_or_else(|poisoned| poisoned.into_inner());
-                                const CLUSTER_GAP_MS: u64 = 8_000;
-                                let should_flush = cluster.last_chunk_ms > 0
-                                    && now.saturating_sub(cluster.last_chunk_ms) > CLUSTER_GAP_MS
-                                    && cluster.entries.len() >= 2;
-                                let flushed = if should_flush {
-                                    Some(std::mem::take(&mut cluster.entries))
-                                } else {
-                                    None
-                                };
-                                cluster.entries.push((
-                                    new_entry.id.clone(),
-                                    processed_text.clone(),
-                                    new_entry.timestamp_ms,
-                                ));
-                                cluster.last_chunk_ms = now;
-                                flushed
-                            };
-
-                            if let Some(entries) = flush_entries {
-                                let app_c = app.clone();
-                                let settings_c = settings.clone();
-                                crate::util::spawn_guarded("system_cluster_flush", move || {
-                                    flush_system_cluster(&app_c, entries, &settings_c);
-                                });
-                            }
-                        }
+                    if let Err(err) = &result {
+                        crate::failed_segments::keep(&app, "output", &job.samples, err);
                     }
-                }
+                    result
+                },
+            );
+            let detected_language = crate::detected_language::take_detected_language();
+            if in_flight.fetch_sub(1, Ordering::Relaxed) == 1 {
+                transcribing.store(false, Ordering::Relaxed);
+                update_transcribe_overlay(&app, false);
             }
-            Err(err) => {
-                let _ = app.emit("transcription:error", err);
+            (job.level, job.duration_ms, result, detected_language)
+        },
+        |(level, duration_ms, result, detected_language)| {
+            publish_loopback_result(
+                &app,
+                &settings,
+                &mut previous_text,
+                level,
+                duration_ms,
+                result,
+                detected_language,
+            );
+            if let Some(stored) = previous_text.as_deref() {
+                context.remember(stored);
             }
-        }
-    }
+        },
+    );
 
     // Flush remaining system audio cluster before worker exit
     {
@@ -1327,6 +1350,232 @@ fn transcribe_worker(
     }
 }
 
+#[cfg(target_os = "windows")]
+struct LoopbackChunk {
+    samples: Vec<i16>,
+    level: f32,
+    duration_ms: u64,
+}
+
+/// Transcribes audio kept from a failed system-audio segment and stores it
+/// like a fresh chunk.
+#[cfg(target_os = "windows")]
+pub(crate) fn transcribe_retained_loopback_audio(
+    app: &AppHandle,
+    samples: Vec<i16>,
+) -> Result<(), String> {
+    let settings = app
+        .state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    let duration_ms = samples.len() as u64 * 1000 / TARGET_SAMPLE_RATE as u64;
+    let result = crate::transcription_jobs::run_job(
+        app,
+        crate::transcription_jobs::JobSource::Loopback,
+        duration_ms,
+        || {
+            let result = transcribe_audio(app, &settings, &samples);
+            if let Err(err) = &result {
+                crate::failed_segments::keep(app, "output", &samples, err);
+            }
+            result
+        },
+    );
+    let outcome = result.as_ref().map(|_| ()).map_err(Clone::clone);
+    publish_loopback_result(
+        app,
+        &settings,
+        &mut None,
+        rms_i16(&samples),
+        duration_ms,
+        result,
+        crate::detected_language::take_detected_language(),
+    );
+    outcome
+}
+
+#[cfg(not(target_os = "windows"))]
+pub(crate) fn transcribe_retained_loopback_audio(
+    _app: &AppHandle,
+    _samples: Vec<i16>,
+) -> Result<(), String> {
+    Err("System audio transcription is only available on Windows".to_string())
+}
+
+/// Filters, post-processes and stores one decoded loopback chunk; called in
+/// chunk order by the worker pool. `previous_text` is the raw text of the
+/// last stored chunk; words this chunk repeats from it are trimmed first.
+#[cfg(target_os = "windows")]
+fn publish_loopback_result(
+    app: &AppHandle,
+    settings: &Settings,
+    previous_text: &mut Option<String>,
+    level: f32,
+    duration_ms: u64,
+    result: Result<(String, String), String>,
+    detected_language: Option<String>,
+) {
+    // Cleared unless the chunk makes it into history.
+    let previous = previous_text.take();
+    match result {
+        Ok((raw_text, _source)) => {
+            let _ = app.emit(
+                "transcription:raw-result",
+                crate::workflow_agent::RawTranscriptionEvent {
+                    text: raw_text.clone(),
+                    source: "output".to_string(),
+                    timestamp_ms: crate::util::now_ms(),
+                },
+            );
+            let text = match previous.as_deref() {
+                Some(previous) => crate::chunk_dedup::trim_repeated_prefix(
+                    previous,
+                    &raw_text,
+                    system_segmenter_config(settings).pre_roll_ms,
+                )
+                .to_string(),
+                None => raw_text.clone(),
+            };
+            if text.is_empty() && !raw_text.trim().is_empty() {
+                let _ = app.emit(
+                    "transcription:dropped",
+                    serde_json::json!({
+                        "source": "output",
+                        "text": raw_text,
+                        "reason": "duplicate",
+                    }),
+                );
+                *previous_text = previous;
+                return;
+            }
+            let language_verdict =
+                crate::detected_language::check_language(detected_language.as_deref(), settings);
+            let activated = crate::activation_words::gate(&text, settings)
+                .map(|activated| activated.into_owned())
+                .filter(|activated| !activated.trim().is_empty());
+            if text.trim().is_empty()
+                || should_drop_transcript(
+                    &text,
+                    level,
+                    duration_ms,
+                    true,
+                    &HallucinationFilter::for_settings(settings, detected_language.as_deref()),
+                )
+                || activated.is_none()
+            {
+                let _ = app.emit(
+                    "transcription:dropped",
+                    serde_json::json!({
+                        "source": "output",
+                        "text": text,
+                        "reason": "filtered",
+                    }),
+                );
+            } else if language_verdict == UnexpectedLanguageAction::Reject {
+                let _ = app.emit(
+                    "transcription:dropped",
+                    serde_json::json!({
+                        "source": "output",
+                        "text": text,
+                        "reason": "unexpected_language",
+                        "language": detected_language,
+                    }),
+                );
+            } else {
+                let text = activated.unwrap_or(text);
+                let processed = process_for_output(&text, settings, app);
+                if processed.dropped {
+                    let _ = app.emit(
+                        "transcription:dropped",
+                        serde_json::json!({
+                            "source": "output",
+                            "reason": "content_filter",
+                            "tags": processed.tags,
+                        }),
+                    );
+                    return;
+                }
+                // System audio is never pasted; only the history rendition applies.
+                let processed_text = processed
+                    .rendition(Rendition::from_setting(&settings.history_rendition))
+                    .to_string();
+                let mut entry_tags = processed.tags;
+                if language_verdict == UnexpectedLanguageAction::Flag {
+                    entry_tags.push(crate::detected_language::UNEXPECTED_LANGUAGE_TAG.to_string());
+                }
+
+                let state = app.state::<AppState>();
+                let push_result = push_transcribe_entry_inner(
+                    app,
+                    &state.history_transcribe,
+                    processed_text.clone(),
+                );
+                if push_result.is_ok() {
+                    *previous_text = Some(raw_text);
+                }
+                let annotate = detected_language.is_some() || !entry_tags.is_empty();
+                if let (Ok(updated), true) = (&push_result, annotate) {
+                    let annotated = updated.first().and_then(|entry| {
+                        crate::history_partition::annotate_entry(
+                            &state.history_transcribe,
+                            &entry.id,
+                            detected_language.as_deref(),
+                            &entry_tags,
+                        )
+                    });
+                    if let Some(annotated) = annotated {
+                        let _ = app.emit("transcribe:history-updated", annotated);
+                    }
+                }
+
+                // System audio cluster tracking for AI refinement
+                if let Ok(ref updated) = push_result {
+                    if let Some(new_entry) = updated.first() {
+                        let now = crate::util::now_ms();
+                        let flush_entries = {
+                            let mut cluster = state
+                                .system_cluster_buffer
+                                .lock()
+                                .unwrap_or_else(|poisoned| poisoned.into_inner());
+                            const CLUSTER_GAP_MS: u64 = 8_000;
+                            let should_flush = cluster.last_chunk_ms > 0
+                                && now.saturating_sub(cluster.last_chunk_ms) > CLUSTER_GAP_MS
+                                && cluster.entries.len() >= 2;
+                            let flushed = if should_flush {
+                                Some(std::mem::take(&mut cluster.entries))
+                            } else {
+                                None
+                            };
+                            cluster.entries.push((
+                                new_entry.id.clone(),
+                                processed_text.clone(),
+                                new_entry.timestamp_ms,
+                            ));
+                            cluster.last_chunk_ms = now;
+                            flushed
+                        };
+
+                        if let Some(entries) = flush_entries {
+                            let app_c = app.clone();
+                            let settings_c = settings.clone();
+                            crate::util::spawn_guarded("system_cluster_flush", move || {
+                                flush_system_cluster(&app_c, entries, &settings_c);
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        Err(err) if crate::transcription_jobs::is_cancellation(&err) => {}
+        Err(err) => {
+            crate::overlay::flash_overlay_error(app);
+            let _ = app.emit("transcription:error", err);
+        }
+    }
+}
+
 #[cfg(target_os = "windows")]
 fn flush_system_cluster(
     app: &AppHandle,
@@ -1390,6 +1639,14 @@ fn flush_system_cluster(
             source: "output".to_string(),
             speaker_name,
             refinement: None,
+            audio_path: None,
+            revisions: Vec::new(),
+            pinned: false,
+            tags: Vec::new(),
+            note: None,
+            formatted_text: None,
+            language: None,
+            translation: None,
         });
         let updated: Vec<crate::state::HistoryEntry> = ph.active.iter().cloned().collect();
         drop(ph);
@@ -1420,7 +1677,7 @@ fn flush_system_cluster(
 }
 
 #[cfg(target_os = "windows")]
-fn decode_wasapi_mono(
+pub(crate) fn decode_wasapi_mono(
     raw: &[u8],
     channels: usize,
     bytes_per_sample: usize,
@@ -1542,6 +1799,7 @@ fn run_transcribe_loopback(
     // The worker thread and the queue remain untouched across iterations.
     const MAX_RECONNECTS: u32 = 10;
     let mut reconnect_count = 0u32;
+    let mut adapter = crate::backlog_adaptation::BacklogAdapter::default();
 
     'reconnect: loop {
         // Check stop signal before each (re)connect attempt.
@@ -1599,7 +1857,10 @@ fn run_transcribe_loopback(
         audio_client.start_stream().map_err(|e| e.to_string())?;
 
         // Per-session state — reset on every reconnect so stale data is discarded.
-        let mut segmenter = AdaptiveSegmenter::new(system_segmenter_config(&settings));
+        let mut segmenter = AdaptiveSegmenter::new(adapted_segmenter_config(
+            &settings,
+            adapter.interval_scale(),
+        ));
         let mut last_backpressure_check = Instant::now();
         let mut gain = (10.0f32).powf(settings.transcribe_input_gain_db / 20.0);
         let mut vad_enabled = settings.transcribe_vad_mode;
@@ -1704,7 +1965,10 @@ fn run_transcribe_loopback(
                     vad_enabled = current.transcribe_vad_mode;
                     vad_threshold = current.transcribe_vad_threshold;
                     vad_silence_ms = current.transcribe_vad_silence_ms;
-                    segmenter.update_config(system_segmenter_config(&current));
+                    segmenter.update_config(adapted_segmenter_config(
+                        &current,
+                        adapter.interval_scale(),
+                    ));
                     monitor_threshold = if vad_enabled {
                         vad_threshold
                     } else {
@@ -1807,6 +2071,18 @@ fn run_transcribe_loopback(
             if last_backpressure_check.elapsed() >= Duration::from_millis(1_000) {
                 let status = queue.status();
                 segmenter.set_backpressure_percent(status.percent_used);
+                let current = app
+                    .state::<AppState>()
+                    .settings
+                    .read()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .clone();
+                if adapter.update(&app, &current, status.percent_used) {
+                    segmenter.update_config(adapted_segmenter_config(
+                        &current,
+                        adapter.interval_scale(),
+                    ));
+                }
                 let _ = app.emit(
                     "continuous-dump:stats",
                     ContinuousDumpStats {
@@ -1850,6 +2126,13 @@ fn run_transcribe_loopback(
     // Final teardown: drain the queue and wait for the worker to finish.
     queue.close();
     let _ = worker_handle.join();
+    let current = app
+        .state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    adapter.reset(&app, "stopped");
     Ok(())
 }
 
@@ -1878,28 +2161,156 @@ fn encode_wav_i16(samples: &[i16], sample_rate: u32) -> Vec<u8> {
     wav
 }
 
+/// Transcribes `samples`, retrying transient failures (see
+/// `transcription_retry`).
 pub(crate) fn transcribe_audio(
     app: &AppHandle,
     settings: &Settings,
     samples: &[i16],
 ) -> Result<(String, String), String> {
+    crate::transcription_retry::with_retries(app, settings, || {
+        transcribe_audio_once(app, settings, samples)
+    })
+}
+
+fn transcribe_audio_once(
+    app: &AppHandle,
+    settings: &Settings,
+    samples: &[i16],
+) -> Result<(String, String), String> {
+    crate::detected_language::record_detected_language(None);
+    let session_settings;
+    let settings = match translate_session_override(app) {
+        Some(translate) if translate != settings.translate_to_english => {
+            session_settings = Settings {
+                translate_to_english: translate,
+                ..settings.clone()
+            };
+            &session_settings
+        }
+        _ => settings,
+    };
     let wav_bytes = encode_wav_i16(samples, TARGET_SAMPLE_RATE);
+    let cloud_provider = if settings.cloud_transcription.enabled {
+        settings.cloud_transcription.provider.as_str()
+    } else {
+        "custom"
+    };
 
-    if settings.cloud_fallback && legacy_cloud_transcription_enabled() {
-        match transcribe_cloud(&wav_bytes) {
-            Ok(text) => return Ok((text, "cloud-legacy".to_string())),
-            Err(err) => {
-                warn!(
-                    "Legacy cloud transcription failed, falling back to local whisper: {}",
-                    err
+    let engines = transcription_engine_order(settings, legacy_cloud_transcription_enabled());
+    let mut failures: Vec<(&str, String)> = Vec::new();
+    for engine in &engines {
+        if crate::transcription_jobs::current_job_cancelled() {
+            return Err(crate::transcription_jobs::JOB_CANCELLED_ERROR.to_string());
+        }
+        crate::overlay::update_overlay_transcribe_engine(app, *engine == "cloud");
+        let result = match *engine {
+            "cloud" => transcribe_cloud(settings, &wav_bytes),
+            _ => transcribe_local(app, settings, &wav_bytes)
+                .map(|text| (text, local_source_tag(settings).to_string())),
+        };
+        match result {
+            Ok((text, source)) => {
+                let _ = app.emit(
+                    "transcription:engine",
+                    serde_json::json!({
+                        "engine": engine,
+                        "provider": if *engine == "cloud" { cloud_provider } else { "whisper_cpp" },
+                        "source": source,
+                        "fallback_used": !failures.is_empty(),
+                        "failed_engines": failures
+                            .iter()
+                            .map(|(engine, error)| serde_json::json!({ "engine": engine, "error": error }))
+                            .collect::<Vec<_>>(),
+                    }),
                 );
-                let _ = app.emit("transcription:legacy-cloud-failed", err.clone());
+                return Ok((text, source));
+            }
+            Err(err) => {
+                warn!("Transcription engine '{}' failed: {}", engine, err);
+                if *engine == "cloud" {
+                    let _ = app.emit(
+                        "transcription:cloud-failed",
+                        serde_json::json!({
+                            "provider": cloud_provider,
+                            "error": err,
+                        }),
+                    );
+                }
+                failures.push((engine, err));
             }
         }
     }
 
-    let text = transcribe_local(app, settings, &wav_bytes)?;
-    Ok((text, "local".to_string()))
+    // Keep the primary engine's message first: callers match on it
+    // (e.g. "model ... not found").
+    let mut failures = failures.into_iter();
+    let (_, mut message) = failures
+        .next()
+        .unwrap_or(("none", "No transcription engine available".to_string()));
+    for (engine, err) in failures {
+        message.push_str(&format!(" ({} fallback failed: {})", engine, err));
+    }
+    Err(message)
+}
+
+fn local_source_tag(settings: &Settings) -> &'static str {
+    if settings.translate_to_english {
+        "local-translated"
+    } else {
+        "local"
+    }
+}
+
+fn translate_session_override(app: &AppHandle) -> Option<bool> {
+    app.state::<crate::state::AppState>()
+        .translate_session_override
+        .lock()
+        .ok()
+        .and_then(|guard| *guard)
+}
+
+/// Override `translate_to_english` until the app restarts; `None` clears the
+/// override and returns to the saved setting.
+#[tauri::command]
+pub(crate) fn set_translate_session_override(app: AppHandle, enabled: Option<bool>) {
+    if let Ok(mut guard) = app
+        .state::<crate::state::AppState>()
+        .translate_session_override
+        .lock()
+    {
+        *guard = enabled;
+    }
+    let _ = app.emit("transcription:translate-override", enabled);
+}
+
+/// Engines to try for one chunk, in order. Cloud only participates when a
+/// provider is enabled; the legacy env-gated toggle keeps its cloud-first
+/// behaviour.
+fn transcription_engine_order(settings: &Settings, legacy_cloud_env: bool) -> Vec<&'static str> {
+    let cloud = &settings.cloud_transcription;
+    if !cloud.enabled {
+        return if settings.cloud_fallback && legacy_cloud_env {
+            vec!["cloud", "local"]
+        } else {
+            vec!["local"]
+        };
+    }
+    let mut order: Vec<&'static str> = cloud
+        .fallback_order
+        .iter()
+        .filter_map(|engine| {
+            crate::cloud_transcription::TRANSCRIPTION_ENGINE_IDS
+                .iter()
+                .copied()
+                .find(|id| *id == engine.as_str())
+        })
+        .collect();
+    order.dedup();
+    if order.is_empty() {
+        order.push("local");
+    }
+    order
 }
 
 fn legacy_cloud_transcription_enabled() -> bool {
@@ -1941,20 +2352,19 @@ fn resolve_whisper_gpu_layers(settings: &Settings) -> Option<usize> {
 }
 
 /// Build the initial prompt string for whisper-cli from the user's vocabulary
-/// terms list. Returns `None` when no usable terms exist. The prompt is
-/// comma-separated and capped in length (whisper's prompt window is 224
-/// tokens ≈ 1024 chars of typical text; we cap at 900 chars to stay safely
-/// below that limit).
-fn build_whisper_initial_prompt(terms: &[String]) -> Option<String> {
+/// terms list, followed by the previous chunk's text when context priming is
+/// active. Returns `None` when neither exists. The terms are comma-separated
+/// and capped in length (whisper's prompt window is 224 tokens ≈ 1024 chars
+/// of typical text; we cap at 900 chars to stay safely below that limit).
+fn build_whisper_initial_prompt(terms: &[String], context: Option<&str>) -> Option<String> {
     const MAX_PROMPT_CHARS: usize = 900;
+    let context = context.map(str::trim).filter(|c| !c.is_empty());
+    let terms_budget = MAX_PROMPT_CHARS.saturating_sub(context.map_or(0, |c| c.len() + 2));
     let mut cleaned: Vec<&str> = terms
         .iter()
         .map(|t| t.trim())
         .filter(|t| !t.is_empty())
         .collect();
-    if cleaned.is_empty() {
-        return None;
-    }
     // Remove duplicates while preserving order.
     let mut seen = std::collections::HashSet::new();
     cleaned.retain(|t| seen.insert(t.to_lowercase()));
@@ -1963,12 +2373,19 @@ fn build_whisper_initial_prompt(terms: &[String]) -> Option<String> {
     let mut out = String::new();
     for term in cleaned {
         let delim = if out.is_empty() { "" } else { ", " };
-        if out.len() + delim.len() + term.len() > MAX_PROMPT_CHARS {
+        if out.len() + delim.len() + term.len() > terms_budget {
             break;
         }
         out.push_str(delim);
         out.push_str(term);
     }
+    // Context last: whisper reads the prompt as the text preceding the audio.
+    if let Some(context) = context {
+        if !out.is_empty() {
+            out.push_str(". ");
+        }
+        out.push_str(context);
+    }
     if out.is_empty() {
         None
     } else {
@@ -2033,6 +2450,20 @@ fn whisper_cli_supports_no_gpu(cli_path: &Path) -> bool {
     result
 }
 
+/// Whether `whisper-cli --help` lists `flag`; the help text is probed once
+/// per binary.
+fn whisper_cli_supports_flag(cli_path: &Path, flag: &str) -> bool {
+    static CACHE: std::sync::OnceLock<Mutex<HashMap<PathBuf, String>>> = std::sync::OnceLock::new();
+    let cache = CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    let mut guard = cache
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    guard
+        .entry(cli_path.to_path_buf())
+        .or_insert_with(|| whisper_cli_help_text(cli_path).unwrap_or_default())
+        .contains(flag)
+}
+
 fn whisper_cli_probe_no_gpu(cli_path: &Path) -> bool {
     whisper_cli_help_text(cli_path)
         .map(|help_text| help_text.contains("-ng") || help_text.contains("--no-gpu"))
@@ -2045,7 +2476,7 @@ fn whisper_cli_probe_gpu_layers(cli_path: &Path) -> bool {
         .unwrap_or(false)
 }
 
-fn whisper_cli_help_text(cli_path: &Path) -> Option<String> {
+pub(crate) fn whisper_cli_help_text(cli_path: &Path) -> Option<String> {
     if let Some(issue) = whisper_runtime_preflight_issue(cli_path) {
         warn!(
             "Skipping whisper-cli arg probe for '{}' due to runtime preflight issue: {}",
@@ -2188,10 +2619,13 @@ fn exit_indicates_illegal_instruction(message: &str) -> bool {
     message.contains("exit=-1073741795")
 }
 
-fn effective_cli_backend_preference(settings: &Settings) -> String {
+pub(crate) fn effective_cli_backend_preference(settings: &Settings) -> String {
+    if cpu_backend_forced() {
+        return "cpu".to_string();
+    }
     if let Ok(value) = std::env::var("TRISPR_LOCAL_BACKEND") {
         let normalized = value.trim().to_ascii_lowercase();
-        if normalized == "cuda" || normalized == "vulkan" {
+        if normalized == "cuda" || normalized == "vulkan" || normalized == "cpu" {
             return normalized;
         }
     }
@@ -2240,6 +2674,8 @@ fn gpu_backend_attempt_order(settings: &Settings) -> Vec<&'static str> {
         "vulkan" if vulkan_unstable => vec!["cuda"],
         "vulkan" => vec!["vulkan"],
         "cuda" => vec!["cuda", "vulkan"],
+        // Explicit CPU: straight to the CPU attempt.
+        "cpu" => Vec::new(),
         // Auto/default: consider both unstable flags
         _ => match (cuda_unstable, vulkan_unstable) {
             (false, false) => vec!["cuda", "vulkan"],
@@ -2272,6 +2708,12 @@ fn resolve_gpu_cli_fallback_paths(settings: &Settings) -> Vec<PathBuf> {
 
 fn resolve_cpu_cli_fallback_path(settings: &Settings, attempted: &[PathBuf]) -> Option<PathBuf> {
     let mut candidates: Vec<PathBuf> = Vec::new();
+    // With CPU selected, a dedicated CPU build beats a GPU build run with -ng.
+    if effective_cli_backend_preference(settings) == "cpu" {
+        if let Some(path) = resolve_whisper_cli_path_for_exact_backend("cpu") {
+            push_unique_path(&mut candidates, path);
+        }
+    }
     for path in attempted {
         push_unique_path(&mut candidates, path.clone());
     }
@@ -2428,24 +2870,139 @@ impl Drop for WhisperGpuActivityGuard {
     }
 }
 
-/// RAII guard that deletes a temporary file when dropped.
-/// Ensures cleanup on every early-return path and panics, not just happy path.
-struct TempFileGuard {
-    path: std::path::PathBuf,
+/// Input WAV for whisper-cli. Only written to the scratch dir when a CLI
+/// attempt has to read it from disk; the file is deleted on drop, so every
+/// early-return path and panic cleans up too.
+struct ScratchWav<'a> {
+    bytes: &'a [u8],
+    output_base: PathBuf,
+    written: bool,
 }
 
-impl TempFileGuard {
-    fn new(path: std::path::PathBuf) -> Self {
-        Self { path }
+impl<'a> ScratchWav<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            output_base: crate::scratch::scratch_path("whisper", ""),
+            written: false,
+        }
+    }
+
+    fn wav_path(&self) -> PathBuf {
+        self.output_base.with_extension("wav")
+    }
+
+    /// Writes the WAV on first use and returns its path.
+    fn file(&mut self) -> Result<PathBuf, String> {
+        let wav_path = self.wav_path();
+        if self.written {
+            return Ok(wav_path);
+        }
+        let started = std::time::Instant::now();
+        fs::write(&wav_path, self.bytes).map_err(|e| {
+            format!(
+                "Failed to write temporary audio file '{}': {}",
+                wav_path.display(),
+                e
+            )
+        })?;
+        self.written = true;
+        if crate::state::diagnostic_logging_enabled() {
+            info!(
+                "[TIMING] wav_write: {:.3}s ({} bytes)",
+                started.elapsed().as_secs_f32(),
+                self.bytes.len()
+            );
+        }
+        Ok(wav_path)
     }
 }
 
-impl Drop for TempFileGuard {
+impl Drop for ScratchWav<'_> {
     fn drop(&mut self) {
-        let _ = fs::remove_file(&self.path);
+        if self.written {
+            let _ = fs::remove_file(self.wav_path());
+        }
     }
 }
 
+/// How whisper-cli receives its input audio.
+#[derive(Clone, Copy)]
+enum CliAudio<'a> {
+    /// WAV bytes piped to `-f -`; the transcript is read from stdout.
+    Stdin(&'a [u8]),
+    /// WAV on disk; the transcript is written to `<output_base>.txt`.
+    File {
+        wav_path: &'a Path,
+        output_base: &'a Path,
+    },
+}
+
+const WHISPER_STDIN_UNSUPPORTED: &str = "whisper-cli cannot read audio from stdin";
+
+/// whisper-cli builds without stdin input treat `-` as a file name.
+fn whisper_stderr_rejects_stdin(stderr: &str) -> bool {
+    let stderr = stderr.to_lowercase();
+    stderr.contains("'-'") && (stderr.contains("not found") || stderr.contains("failed to"))
+}
+
+/// whisper-cli binaries seen rejecting stdin input this session.
+fn stdin_rejecting_clis() -> &'static Mutex<HashSet<PathBuf>> {
+    static CLIS: OnceLock<Mutex<HashSet<PathBuf>>> = OnceLock::new();
+    CLIS.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+/// Runs one whisper-cli attempt, piping the audio through stdin unless the
+/// binary is known not to support it; then the WAV goes to the scratch dir.
+fn run_whisper_cli_attempt(
+    app: &AppHandle,
+    settings: &Settings,
+    cli_path: &Path,
+    model_path: &Path,
+    wav: &mut ScratchWav,
+    force_cpu: bool,
+) -> Result<String, String> {
+    let stdin_rejected = stdin_rejecting_clis()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .contains(cli_path);
+    if !stdin_rejected {
+        match run_whisper_cli(
+            app,
+            settings,
+            cli_path,
+            model_path,
+            CliAudio::Stdin(wav.bytes),
+            force_cpu,
+        ) {
+            Err(err) if err == WHISPER_STDIN_UNSUPPORTED => {
+                warn!(
+                    "whisper-cli '{}' cannot read audio from stdin; using a scratch file",
+                    cli_path.display()
+                );
+                stdin_rejecting_clis()
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .insert(cli_path.to_path_buf());
+            }
+            result => return result,
+        }
+    }
+    let wav_path = wav.file()?;
+    let output_base = wav.output_base.clone();
+    run_whisper_cli(
+        app,
+        settings,
+        cli_path,
+        model_path,
+        CliAudio::File {
+            wav_path: &wav_path,
+            output_base: &output_base,
+        },
+        force_cpu,
+    )
+}
+
 /// RAII guard that cleans whisper side-effect files for a given output base.
 /// Keeps retry attempts isolated and prevents stale transcript reuse.
 struct WhisperOutputGuard {
@@ -2468,6 +3025,52 @@ impl Drop for WhisperOutputGuard {
     }
 }
 
+thread_local! {
+    /// Set while a one-off job on this thread must not use the shared
+    /// whisper-server; holds the pid of the whisper-cli it is running.
+    static DEDICATED_CLI_PID: RefCell<Option<Arc<AtomicU32>>> = const { RefCell::new(None) };
+    /// Set while a retry after a GPU failure runs on this thread (see
+    /// `transcription_retry`).
+    static FORCE_CPU_BACKEND: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f` with local transcription on this thread skipping whisper-server
+/// and running whisper-cli on the CPU backend.
+pub(crate) fn with_cpu_backend<T>(f: impl FnOnce() -> T) -> T {
+    struct Reset(bool);
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            FORCE_CPU_BACKEND.with(|flag| flag.set(self.0));
+        }
+    }
+    let _reset = Reset(FORCE_CPU_BACKEND.with(|flag| flag.replace(true)));
+    f()
+}
+
+fn cpu_backend_forced() -> bool {
+    FORCE_CPU_BACKEND.with(|flag| flag.get())
+}
+
+/// Runs `f` with local transcription on this thread going straight to its own
+/// whisper-cli process, which loads `settings.model` itself. The shared
+/// whisper-server keeps the model live dictation uses. `child_pid` receives
+/// the pid of each whisper-cli started (0 until the first one).
+pub(crate) fn with_dedicated_cli<T>(child_pid: Arc<AtomicU32>, f: impl FnOnce() -> T) -> T {
+    struct Reset;
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            DEDICATED_CLI_PID.with(|slot| slot.borrow_mut().take());
+        }
+    }
+    DEDICATED_CLI_PID.with(|slot| *slot.borrow_mut() = Some(child_pid));
+    let _reset = Reset;
+    f()
+}
+
+fn dedicated_cli_pid() -> Option<Arc<AtomicU32>> {
+    DEDICATED_CLI_PID.with(|slot| slot.borrow().clone())
+}
+
 fn transcribe_local(
     app: &AppHandle,
     settings: &Settings,
@@ -2476,40 +3079,18 @@ fn transcribe_local(
     let diagnostics_enabled = crate::state::diagnostic_logging_enabled();
     reset_transcription_timing(settings);
     let t0 = std::time::Instant::now();
-    let temp_dir = std::env::temp_dir();
-    let _ = fs::create_dir_all(&temp_dir);
-    let stamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|d| d.as_nanos())
-        .unwrap_or(0);
-    let base = temp_dir.join(format!("trispr_{}_{}", std::process::id(), stamp));
-    let wav_path = base.with_extension("wav");
-    let output_base = base.clone();
-
-    fs::write(&wav_path, wav_bytes).map_err(|e| {
-        format!(
-            "Failed to write temporary audio file '{}': {}",
-            wav_path.display(),
-            e
-        )
-    })?;
-    if diagnostics_enabled {
-        info!(
-            "[TIMING] wav_write: {:.3}s ({} bytes)",
-            t0.elapsed().as_secs_f32(),
-            wav_bytes.len()
-        );
-    }
-    // Guard ensures wav_path is deleted on every exit path (early returns, panic).
-    let _wav_guard = TempFileGuard::new(wav_path.clone());
+    // whisper-server takes the bytes directly; CLI attempts pipe them through
+    // stdin and only touch disk when the binary cannot read stdin.
+    let mut scratch_wav = ScratchWav::new(wav_bytes);
 
     let model_path = resolve_model_path(app, &settings.model).ok_or_else(|| {
         "Model file not found. Set TRISPR_WHISPER_MODEL_DIR or TRISPR_WHISPER_MODEL.".to_string()
     })?;
-    let server_ping_ms: Option<u64>;
+    let mut server_ping_ms: Option<u64> = None;
 
-    // Try Whisper-Server first (persistent mode with pre-loaded model)
-    {
+    // Try Whisper-Server first (persistent mode with pre-loaded model); it runs
+    // on the GPU, so a retry forced onto the CPU goes straight to whisper-cli.
+    if dedicated_cli_pid().is_none() && !cpu_backend_forced() {
         let state = app.state::<crate::state::AppState>();
         let port = state
             .whisper_server_port
@@ -2541,11 +3122,25 @@ fn transcribe_local(
                     wav_bytes.len()
                 );
             }
-            let t_server = std::time::Instant::now();
-
-            match crate::whisper_server::transcribe_via_server(wav_bytes, port, &lang_str) {
-                Ok(text) => {
+            let t_server = mark_whisper_started();
+
+            let context = crate::context_priming::current_context();
+            match crate::whisper_server::transcribe_via_server(
+                wav_bytes,
+                port,
+                &lang_str,
+                settings.translate_to_english,
+                context.as_deref(),
+                crate::backlog_adaptation::current_audio_ctx(),
+                &crate::whisper_params::resolve_for_server(settings),
+            ) {
+                Ok(transcript) => {
                     let server_ms = t_server.elapsed().as_millis() as u64;
+                    crate::detected_language::record_detected_language(
+                        transcript
+                            .language
+                            .or_else(|| pinned_language_code(settings)),
+                    );
                     if diagnostics_enabled {
                         info!("[diagnostics] transcribe_via_server SUCCESS -> update(mode=server, backend=gpu, last_error=None)");
                     }
@@ -2577,7 +3172,7 @@ fn transcribe_local(
                         server_ms,
                     ));
                     record_transcription_timing(summary);
-                    return Ok(text);
+                    return Ok(transcript.text);
                 }
                 Err(e) => {
                     warn!("whisper-server failed ({}), falling back to CLI", e);
@@ -2681,14 +3276,13 @@ fn transcribe_local(
     for cli_path in &gpu_cli_paths {
         let backend = whisper_backend_from_cli_path(cli_path.as_path());
         attempted_chain.push(format!("{} GPU", backend));
-        let cli_started = std::time::Instant::now();
-        match run_whisper_cli(
+        let cli_started = mark_whisper_started();
+        match run_whisper_cli_attempt(
             app,
             settings,
             cli_path.as_path(),
             model_path.as_path(),
-            wav_path.as_path(),
-            output_base.as_path(),
+            &mut scratch_wav,
             false,
         ) {
             Ok(text) => {
@@ -2760,14 +3354,13 @@ fn transcribe_local(
             "{} CLI CPU",
             whisper_backend_from_cli_path(cpu_cli_path.as_path())
         ));
-        let cli_started = std::time::Instant::now();
-        match run_whisper_cli(
+        let cli_started = mark_whisper_started();
+        match run_whisper_cli_attempt(
             app,
             settings,
             cpu_cli_path.as_path(),
             model_path.as_path(),
-            wav_path.as_path(),
-            output_base.as_path(),
+            &mut scratch_wav,
             true,
         ) {
             Ok(text) => {
@@ -2843,10 +3436,13 @@ fn run_whisper_cli(
     settings: &Settings,
     cli_path: &Path,
     model_path: &Path,
-    wav_path: &Path,
-    output_base: &Path,
+    audio: CliAudio,
     force_cpu: bool,
 ) -> Result<String, String> {
+    // Skip the rest of the fallback chain once the job is cancelled.
+    if crate::transcription_jobs::current_job_cancelled() {
+        return Err(crate::transcription_jobs::JOB_CANCELLED_ERROR.to_string());
+    }
     let diagnostics_enabled = crate::state::diagnostic_logging_enabled();
     if let Some(issue) = whisper_runtime_preflight_issue(cli_path) {
         update_whisper_runtime_diagnostics(
@@ -2862,8 +3458,19 @@ fn run_whisper_cli(
     }
 
     // Ensure each run starts clean and always cleans side effects on return.
-    cleanup_whisper_output_files(output_base, wav_path);
-    let _output_guard = WhisperOutputGuard::new(output_base.to_path_buf(), wav_path.to_path_buf());
+    let _output_guard = match audio {
+        CliAudio::File {
+            wav_path,
+            output_base,
+        } => {
+            cleanup_whisper_output_files(output_base, wav_path);
+            Some(WhisperOutputGuard::new(
+                output_base.to_path_buf(),
+                wav_path.to_path_buf(),
+            ))
+        }
+        CliAudio::Stdin(_) => None,
+    };
 
     let mut command = Command::new(cli_path);
 
@@ -2895,11 +3502,12 @@ fn run_whisper_cli(
         command.env("GGML_VK_VISIBLE_DEVICES", "1");
     }
 
+    command.arg("-m").arg(model_path).arg("-f");
+    match audio {
+        CliAudio::Stdin(_) => command.arg("-").stdin(Stdio::piped()),
+        CliAudio::File { wav_path, .. } => command.arg(wav_path),
+    };
     command
-        .arg("-m")
-        .arg(model_path)
-        .arg("-f")
-        .arg(wav_path)
         .arg("-t")
         .arg(&threads)
         .arg("-l")
@@ -2909,18 +3517,40 @@ fn run_whisper_cli(
             "auto"
         })
         .arg("-nt")
-        .arg("-otxt")
-        .arg("-of")
-        .arg(output_base)
         .arg("-np");
+    if let CliAudio::File { output_base, .. } = audio {
+        command.arg("-otxt").arg("-of").arg(output_base);
+    }
 
     // Inject vocabulary terms as whisper-cli initial prompt. Whisper uses
     // this to bias recognition toward the listed words (proper nouns,
     // acronyms, project jargon), so they come out right on the first pass
     // instead of depending on post-processing.
-    if let Some(prompt) = build_whisper_initial_prompt(&settings.vocab_terms) {
+    // Context priming appends the previous loopback chunk's tail.
+    let context = crate::context_priming::current_context();
+    if let Some(prompt) = build_whisper_initial_prompt(&settings.vocab_terms, context.as_deref()) {
         command.arg("--prompt").arg(prompt);
     }
+    if settings.translate_to_english {
+        command.arg("--translate");
+    }
+    let mut decode_args =
+        crate::whisper_params::cli_args(&crate::whisper_params::resolve(settings));
+    if let Some(audio_ctx) = crate::backlog_adaptation::current_audio_ctx() {
+        decode_args.push(("--audio-ctx", audio_ctx.to_string()));
+    }
+    for (flag, value) in decode_args {
+        if whisper_cli_supports_flag(cli_path, flag) {
+            command.arg(flag).arg(value);
+        } else {
+            warn!(
+                "Ignoring whisper {} {} because whisper-cli '{}' does not support it.",
+                flag,
+                value,
+                cli_path.display()
+            );
+        }
+    }
 
     command.stdout(Stdio::piped()).stderr(Stdio::piped());
 
@@ -2985,6 +3615,18 @@ fn run_whisper_cli(
         );
         message
     })?;
+    if let Some(pid) = dedicated_cli_pid() {
+        pid.store(child.id(), Ordering::Relaxed);
+    }
+    if let (CliAudio::Stdin(bytes), Some(mut stdin)) = (audio, child.stdin.take()) {
+        let bytes = bytes.to_vec();
+        crate::util::spawn_guarded("whisper_cli_stdin", move || {
+            use std::io::Write;
+            // A broken pipe means whisper-cli exited early; its exit status
+            // and stderr report why.
+            let _ = stdin.write_all(&bytes);
+        });
+    }
     let deadline = std::time::Instant::now() + std::time::Duration::from_secs(120);
     let output = loop {
         match child.try_wait() {
@@ -2994,6 +3636,12 @@ fn run_whisper_cli(
                     .map_err(|e| format!("Failed to collect whisper-cli output: {}", e))?;
             }
             Ok(None) => {
+                if crate::transcription_jobs::current_job_cancelled() {
+                    info!("Killing whisper-cli: transcription job was cancelled");
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(crate::transcription_jobs::JOB_CANCELLED_ERROR.to_string());
+                }
                 if std::time::Instant::now() >= deadline {
                     let _ = child.kill();
                     let _ = child.wait();
@@ -3041,6 +3689,13 @@ fn run_whisper_cli(
         gpu_activity_guard.set_accelerator("gpu");
     }
     let stdout = String::from_utf8_lossy(&output.stdout);
+    if matches!(audio, CliAudio::Stdin(_)) && whisper_stderr_rejects_stdin(&stderr) {
+        return Err(WHISPER_STDIN_UNSUPPORTED.to_string());
+    }
+    crate::detected_language::record_detected_language(
+        crate::detected_language::parse_cli_detected_language(&stderr)
+            .or_else(|| pinned_language_code(settings)),
+    );
     if stderr.to_lowercase().contains("unknown argument:") {
         let message = format!(
             "whisper-cli argument mismatch ('{}'): {}",
@@ -3091,24 +3746,36 @@ fn run_whisper_cli(
     }
 
     let mut transcript_candidates: Vec<PathBuf> = Vec::new();
-    let txt_path = output_base.with_extension("txt");
-    push_unique_path(&mut transcript_candidates, txt_path.clone());
-    push_unique_path(
-        &mut transcript_candidates,
-        Path::new(&format!("{}.txt", wav_path.display())).to_path_buf(),
-    );
-    push_unique_path(&mut transcript_candidates, wav_path.with_extension("txt"));
-    if let Ok(cwd) = std::env::current_dir() {
-        if let Some(name) = output_base.file_name().and_then(|name| name.to_str()) {
-            push_unique_path(&mut transcript_candidates, cwd.join(format!("{name}.txt")));
-        }
-        if let Some(name) = wav_path.file_name().and_then(|name| name.to_str()) {
-            push_unique_path(&mut transcript_candidates, cwd.join(format!("{name}.txt")));
+    if let CliAudio::File {
+        wav_path,
+        output_base,
+    } = audio
+    {
+        let txt_path = output_base.with_extension("txt");
+        push_unique_path(&mut transcript_candidates, txt_path.clone());
+        push_unique_path(
+            &mut transcript_candidates,
+            Path::new(&format!("{}.txt", wav_path.display())).to_path_buf(),
+        );
+        push_unique_path(&mut transcript_candidates, wav_path.with_extension("txt"));
+        if let Ok(cwd) = std::env::current_dir() {
+            if let Some(name) = output_base.file_name().and_then(|name| name.to_str()) {
+                push_unique_path(&mut transcript_candidates, cwd.join(format!("{name}.txt")));
+            }
+            if let Some(name) = wav_path.file_name().and_then(|name| name.to_str()) {
+                push_unique_path(&mut transcript_candidates, cwd.join(format!("{name}.txt")));
+            }
         }
     }
 
     let mut text: Option<String> = None;
-    for _ in 0..20 {
+    // Stdin runs write no transcript file; stdout is the transcript.
+    let polls = if transcript_candidates.is_empty() {
+        0
+    } else {
+        20
+    };
+    for _ in 0..polls {
         if let Some((_, value)) = read_first_existing_text_file(&transcript_candidates) {
             text = Some(value);
             break;
@@ -3119,7 +3786,7 @@ fn run_whisper_cli(
     let stdout_text = String::from_utf8_lossy(&output.stdout).trim().to_string();
     let text = if let Some(text) = text {
         text
-    } else if !stdout_text.is_empty() {
+    } else if !stdout_text.is_empty() || matches!(audio, CliAudio::Stdin(_)) {
         stdout_text
     } else {
         let stderr_text = String::from_utf8_lossy(&output.stderr);
@@ -3272,30 +3939,55 @@ fn read_first_existing_text_file(paths: &[PathBuf]) -> Option<(PathBuf, String)>
     None
 }
 
-#[derive(Deserialize)]
-struct CloudResponse {
-    text: String,
+/// Transcribe via the configured cloud provider. The legacy env-gated toggle
+/// (`cloud_fallback` + `TRISPR_ENABLE_LEGACY_CLOUD_TRANSCRIBE`) maps to the
+/// `custom` provider and keeps its `cloud-legacy` source tag.
+fn transcribe_cloud(settings: &Settings, wav_bytes: &[u8]) -> Result<(String, String), String> {
+    let language = effective_language_mode(settings);
+    if settings.cloud_transcription.enabled {
+        let (text, provider) = crate::cloud_transcription::transcribe_with_settings(
+            &settings.cloud_transcription,
+            &language,
+            wav_bytes,
+        )?;
+        return Ok((text, format!("cloud-{}", provider)));
+    }
+
+    let legacy = crate::cloud_transcription::CloudTranscriptionSettings {
+        enabled: true,
+        provider: "custom".to_string(),
+        ..Default::default()
+    };
+    let (text, _) =
+        crate::cloud_transcription::transcribe_with_settings(&legacy, &language, wav_bytes)?;
+    Ok((text, "cloud-legacy".to_string()))
 }
 
-fn transcribe_cloud(wav_bytes: &[u8]) -> Result<String, String> {
-    let endpoint = std::env::var("TRISPR_CLOUD_ENDPOINT").unwrap_or_default();
-    if endpoint.trim().is_empty() {
-        return Err("Legacy cloud transcription fallback is not configured".to_string());
+/// Whether the configured loopback device is still enumerable (no fallback to
+/// the default render device). Always true off Windows, where system audio
+/// capture is not available.
+pub(crate) fn output_device_present(device_id: &str) -> bool {
+    #[cfg(target_os = "windows")]
+    {
+        let Ok(enumerator) = wasapi::DeviceEnumerator::new() else {
+            return false;
+        };
+        match device_id.strip_prefix("wasapi:") {
+            Some(id) => enumerator.get_device(id).is_ok(),
+            None => enumerator
+                .get_default_device(&wasapi::Direction::Render)
+                .is_ok(),
+        }
     }
-
-    let token = std::env::var("TRISPR_CLOUD_TOKEN").unwrap_or_default();
-    let mut req = ureq::post(&endpoint).set("Content-Type", "audio/wav");
-    if !token.trim().is_empty() {
-        req = req.set("Authorization", &format!("Bearer {token}"));
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = device_id;
+        true
     }
-
-    let resp = req.send_bytes(wav_bytes).map_err(|e| e.to_string())?;
-    let parsed: CloudResponse = resp.into_json().map_err(|e| e.to_string())?;
-    Ok(parsed.text)
 }
 
 #[cfg(target_os = "windows")]
-fn resolve_output_device(device_id: &str) -> Option<wasapi::Device> {
+pub(crate) fn resolve_output_device(device_id: &str) -> Option<wasapi::Device> {
     let enumerator = wasapi::DeviceEnumerator::new().ok()?;
     if device_id == "default" {
         return enumerator
@@ -3317,3 +4009,78 @@ fn resolve_output_device(device_id: &str) -> Option<wasapi::Device> {
         .get_default_device(&wasapi::Direction::Render)
         .ok()
 }
+
+/// Records what `device_id` plays through a WASAPI loopback and hands each
+/// packet (mono, -1..1) with its sample rate to `sink` until `stop_rx` fires.
+/// Used by side captures (echo reference, call recording) that must not
+/// depend on the system-audio transcription monitor.
+#[cfg(target_os = "windows")]
+pub(crate) fn capture_loopback(
+    device_id: &str,
+    stop_rx: std::sync::mpsc::Receiver<()>,
+    mut sink: impl FnMut(&[f32], u32),
+) -> Result<(), String> {
+    use std::sync::mpsc::TryRecvError;
+
+    let hr = wasapi::initialize_mta();
+    if hr.0 < 0 {
+        return Err(format!("WASAPI init error: 0x{:X}", hr.0));
+    }
+    let device =
+        resolve_output_device(device_id).ok_or_else(|| "Output device not found".to_string())?;
+    let mut audio_client = device
+        .get_iaudioclient()
+        .map_err(|e| format!("WASAPI audio client error: {e}"))?;
+    let format = audio_client
+        .get_mixformat()
+        .map_err(|e| format!("WASAPI format error: {e}"))?;
+    let channels = format.get_nchannels() as usize;
+    let sample_rate = format.get_samplespersec();
+    let bytes_per_sample = (format.get_bitspersample() as usize / 8).max(1);
+    let bytes_per_frame = format.get_blockalign() as usize;
+    let sample_format = format
+        .get_subformat()
+        .map_err(|e| format!("WASAPI sample type error: {e}"))?;
+
+    // Short buffer: the echo reference is only useful while it is fresh.
+    let stream_mode = wasapi::StreamMode::PollingShared {
+        autoconvert: true,
+        buffer_duration_hns: 100_000,
+    };
+    audio_client
+        .initialize_client(&format, &wasapi::Direction::Capture, &stream_mode)
+        .map_err(|e| format!("WASAPI init error: {e}"))?;
+    let capture_client = audio_client
+        .get_audiocaptureclient()
+        .map_err(|e| format!("WASAPI capture error: {e}"))?;
+    audio_client.start_stream().map_err(|e| e.to_string())?;
+
+    loop {
+        match stop_rx.try_recv() {
+            Ok(_) | Err(TryRecvError::Disconnected) => break,
+            Err(TryRecvError::Empty) => {}
+        }
+        let packet_frames = capture_client
+            .get_next_packet_size()
+            .map_err(|e| e.to_string())?
+            .unwrap_or(0);
+        if packet_frames == 0 {
+            thread::sleep(Duration::from_millis(3));
+            continue;
+        }
+        let mut raw = vec![0u8; packet_frames as usize * bytes_per_frame];
+        let (frames_read, _) = capture_client
+            .read_from_device(&mut raw)
+            .map_err(|e| e.to_string())?;
+        let valid_bytes = frames_read as usize * bytes_per_frame;
+        let mono = decode_wasapi_mono(
+            &raw[..valid_bytes],
+            channels,
+            bytes_per_sample,
+            sample_format,
+        );
+        sink(&mono, sample_rate);
+    }
+    let _ = audio_client.stop_stream();
+    Ok(())
+}
@@ -0,0 +1,531 @@
+//! Registry of transcription jobs (mic segments and loopback chunks) so the UI
+//! can see what is in flight and cancel a job that hangs.
+//!
+//! Mic results are delivered newest-last: a new PTT press cancels the mic jobs
+//! still running, and a result that finishes after a newer one is dropped.
+//!
+//! Queued, running and failed jobs are persisted to `transcription_jobs.json`
+//! so failures stay visible across restarts. Jobs still unfinished when the app
+//! closed are restored as failed, since their audio only lived in memory.
+
+use serde::{Deserialize, Serialize};
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+use tauri::{AppHandle, Emitter};
+use tracing::{info, warn};
+
+use crate::errors::{CommandResult, ErrorCode, WithErrorCode};
+
+/// Error returned in place of a transcript when the job was cancelled.
+pub(crate) const JOB_CANCELLED_ERROR: &str = "Transcription cancelled";
+/// Error recorded for jobs that were still unfinished when the app closed.
+const INTERRUPTED_JOB_ERROR: &str = "Interrupted when the app closed";
+/// Finished jobs kept around for the UI after they complete.
+const FINISHED_JOB_HISTORY: usize = 50;
+const JOBS_FILE: &str = "transcription_jobs.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum JobSource {
+    Mic,
+    /// System-audio chunks; the loopback capture only exists on Windows.
+    #[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+    Loopback,
+    /// Imported audio/video files (see `file_transcription`).
+    File,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum JobState {
+    Queued,
+    Running,
+    Done,
+    Failed,
+    Cancelled,
+}
+
+impl JobState {
+    fn is_finished(self) -> bool {
+        matches!(
+            self,
+            JobState::Done | JobState::Failed | JobState::Cancelled
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct TranscriptionJob {
+    pub(crate) id: String,
+    pub(crate) source: JobSource,
+    pub(crate) state: JobState,
+    pub(crate) audio_ms: u64,
+    pub(crate) created_ms: u64,
+    pub(crate) started_ms: Option<u64>,
+    pub(crate) finished_ms: Option<u64>,
+    pub(crate) error: Option<String>,
+}
+
+struct JobEntry {
+    seq: u64,
+    job: TranscriptionJob,
+    cancel: Arc<AtomicBool>,
+}
+
+#[derive(Default)]
+struct JobRegistry {
+    next_id: u64,
+    entries: VecDeque<JobEntry>,
+}
+
+impl JobRegistry {
+    /// Rebuilds the registry from a previous run's jobs, failing the ones that
+    /// never finished.
+    fn restore(jobs: Vec<TranscriptionJob>, now_ms: u64) -> Self {
+        let mut registry = JobRegistry::default();
+        for mut job in jobs {
+            let seq = job_seq(&job.id);
+            registry.next_id = registry.next_id.max(seq);
+            if !job.state.is_finished() {
+                job.state = JobState::Failed;
+                job.finished_ms = Some(now_ms);
+                job.error = Some(INTERRUPTED_JOB_ERROR.to_string());
+            }
+            registry.entries.push_back(JobEntry {
+                seq,
+                job,
+                cancel: Arc::new(AtomicBool::new(false)),
+            });
+        }
+        registry.prune();
+        registry
+    }
+
+    fn create(
+        &mut self,
+        source: JobSource,
+        audio_ms: u64,
+        now_ms: u64,
+    ) -> (String, Arc<AtomicBool>) {
+        self.next_id += 1;
+        let id = format!("job_{}", self.next_id);
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.entries.push_back(JobEntry {
+            seq: self.next_id,
+            job: TranscriptionJob {
+                id: id.clone(),
+                source,
+                state: JobState::Queued,
+                audio_ms,
+                created_ms: now_ms,
+                started_ms: None,
+                finished_ms: None,
+                error: None,
+            },
+            cancel: cancel.clone(),
+        });
+        (id, cancel)
+    }
+
+    fn get_mut(&mut self, id: &str) -> Option<&mut JobEntry> {
+        self.entries.iter_mut().find(|entry| entry.job.id == id)
+    }
+
+    fn set_state(&mut self, id: &str, state: JobState, error: Option<String>, now_ms: u64) {
+        if let Some(entry) = self.get_mut(id) {
+            if entry.job.state.is_finished() {
+                return;
+            }
+            entry.job.state = state;
+            match state {
+                JobState::Running => entry.job.started_ms = Some(now_ms),
+                _ if state.is_finished() => entry.job.finished_ms = Some(now_ms),
+                _ => {}
+            }
+            entry.job.error = error;
+        }
+        self.prune();
+    }
+
+    fn cancel(&mut self, id: &str, now_ms: u64) -> Result<(), String> {
+        let entry = self
+            .get_mut(id)
+            .ok_or_else(|| format!("Unknown transcription job '{}'", id))?;
+        if entry.job.state.is_finished() {
+            return Err("Transcription job already finished".to_string());
+        }
+        entry.cancel.store(true, Ordering::Relaxed);
+        self.set_state(id, JobState::Cancelled, None, now_ms);
+        Ok(())
+    }
+
+    /// Cancels every unfinished job from `source`; returns how many were cancelled.
+    fn cancel_source(&mut self, source: JobSource, now_ms: u64) -> usize {
+        let ids: Vec<String> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.job.source == source && !entry.job.state.is_finished())
+            .map(|entry| entry.job.id.clone())
+            .collect();
+        for id in &ids {
+            let _ = self.cancel(id, now_ms);
+        }
+        ids.len()
+    }
+
+    /// True when a newer job from the same source already delivered its result.
+    fn superseded(&self, id: &str) -> bool {
+        let Some(current) = self.entries.iter().find(|entry| entry.job.id == id) else {
+            return false;
+        };
+        // File jobs are independent of each other, and loopback results are
+        // put back in chunk order by `transcribe_pool`; only mic jobs race.
+        if current.job.source != JobSource::Mic {
+            return false;
+        }
+        self.entries.iter().any(|entry| {
+            entry.job.source == current.job.source
+                && entry.seq > current.seq
+                && entry.job.state == JobState::Done
+        })
+    }
+
+    fn has_unfinished(&self, source: JobSource) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.job.source == source && !entry.job.state.is_finished())
+    }
+
+    fn prune(&mut self) {
+        let mut finished = self
+            .entries
+            .iter()
+            .filter(|entry| entry.job.state.is_finished())
+            .count();
+        while finished > FINISHED_JOB_HISTORY {
+            let Some(index) = self
+                .entries
+                .iter()
+                .position(|entry| entry.job.state.is_finished())
+            else {
+                break;
+            };
+            self.entries.remove(index);
+            finished -= 1;
+        }
+    }
+
+    fn snapshot(&self) -> Vec<TranscriptionJob> {
+        self.entries.iter().map(|entry| entry.job.clone()).collect()
+    }
+
+    /// Jobs worth keeping across a restart: unfinished and failed ones.
+    fn persisted(&self) -> Vec<TranscriptionJob> {
+        self.entries
+            .iter()
+            .filter(|entry| !matches!(entry.job.state, JobState::Done | JobState::Cancelled))
+            .map(|entry| entry.job.clone())
+            .collect()
+    }
+}
+
+static JOBS: OnceLock<Mutex<JobRegistry>> = OnceLock::new();
+
+fn registry() -> MutexGuard<'static, JobRegistry> {
+    JOBS.get_or_init(|| Mutex::new(JobRegistry::default()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Persists the registry and notifies the UI.
+fn publish(app: &AppHandle, jobs: &JobRegistry) {
+    let path = crate::paths::resolve_data_path(app, JOBS_FILE);
+    let persisted = jobs.persisted();
+    if persisted.is_empty() {
+        let _ = fs::remove_file(&path);
+    } else {
+        match serde_json::to_string_pretty(&persisted) {
+            Ok(raw) => {
+                if let Err(err) = fs::write(&path, raw) {
+                    warn!("Failed to persist transcription jobs: {}", err);
+                }
+            }
+            Err(err) => warn!("Failed to serialize transcription jobs: {}", err),
+        }
+    }
+    let _ = app.emit("transcription:jobs", jobs.snapshot());
+}
+
+/// Sequence number of a `job_<n>` id, 0 for anything else.
+fn job_seq(id: &str) -> u64 {
+    id.strip_prefix("job_")
+        .and_then(|n| n.parse::<u64>().ok())
+        .unwrap_or(0)
+}
+
+/// Restores the jobs persisted by a previous run.
+pub(crate) fn init_jobs(app: &AppHandle) {
+    let path = crate::paths::resolve_data_path(app, JOBS_FILE);
+    let jobs: Vec<TranscriptionJob> = match fs::read_to_string(&path) {
+        Ok(raw) => serde_json::from_str(&raw).unwrap_or_else(|err| {
+            warn!("Ignoring unreadable transcription jobs: {}", err);
+            Vec::new()
+        }),
+        Err(_) => Vec::new(),
+    };
+    let mut restored = JobRegistry::restore(jobs, crate::util::now_ms());
+    // Kept failed audio outlives pruned jobs; `retry_job` finds it by job id,
+    // so never hand one of those ids out again.
+    let kept_max = crate::failed_segments::list_failed_segments(app.clone())
+        .iter()
+        .filter_map(|segment| segment.job_id.as_deref())
+        .map(job_seq)
+        .max()
+        .unwrap_or(0);
+    restored.next_id = restored.next_id.max(kept_max);
+    if !restored.entries.is_empty() {
+        info!(
+            "Restored {} transcription job(s) from a previous run",
+            restored.entries.len()
+        );
+    }
+    let mut jobs = registry();
+    *jobs = restored;
+    publish(app, &jobs);
+}
+
+/// Tracks one transcription from queueing to its result.
+pub(crate) struct JobHandle {
+    id: String,
+    cancel: Arc<AtomicBool>,
+}
+
+impl JobHandle {
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+
+    /// Marks the job running, unless it was cancelled while queued.
+    pub(crate) fn start(&self, app: &AppHandle) -> bool {
+        if self.is_cancelled() {
+            return false;
+        }
+        let mut jobs = registry();
+        jobs.set_state(&self.id, JobState::Running, None, crate::util::now_ms());
+        publish(app, &jobs);
+        true
+    }
+
+    /// Records the outcome; a cancelled job yields `JOB_CANCELLED_ERROR` instead of its result.
+    pub(crate) fn finish<T>(self, app: &AppHandle, result: Result<T, String>) -> Result<T, String> {
+        let mut jobs = registry();
+        let now = crate::util::now_ms();
+        let result = if self.is_cancelled() {
+            info!("Discarding result of cancelled transcription {}", self.id);
+            Err(JOB_CANCELLED_ERROR.to_string())
+        } else if result.is_ok() && jobs.superseded(&self.id) {
+            info!(
+                "Discarding out-of-order result of transcription {}",
+                self.id
+            );
+            self.cancel.store(true, Ordering::Relaxed);
+            Err(JOB_CANCELLED_ERROR.to_string())
+        } else {
+            result
+        };
+        match &result {
+            Ok(_) => jobs.set_state(&self.id, JobState::Done, None, now),
+            Err(err) if err == JOB_CANCELLED_ERROR => {
+                jobs.set_state(&self.id, JobState::Cancelled, None, now)
+            }
+            Err(err) => jobs.set_state(&self.id, JobState::Failed, Some(err.clone()), now),
+        }
+        publish(app, &jobs);
+        result
+    }
+}
+
+/// Registers a queued job for `audio_ms` of audio from `source`.
+pub(crate) fn enqueue(app: &AppHandle, source: JobSource, audio_ms: u64) -> JobHandle {
+    let mut jobs = registry();
+    let (id, cancel) = jobs.create(source, audio_ms, crate::util::now_ms());
+    publish(app, &jobs);
+    JobHandle { id, cancel }
+}
+
+thread_local! {
+    /// Cancel flag of the job running on this thread, for code deep in the
+    /// transcription path (e.g. the whisper-cli wait loop).
+    static CURRENT_JOB_CANCEL: RefCell<Option<Arc<AtomicBool>>> = const { RefCell::new(None) };
+    /// Id of the job running on this thread.
+    static CURRENT_JOB_ID: RefCell<Option<String>> = const { RefCell::new(None) };
+    /// How long the last `run_job` on this thread spent transcribing.
+    static LAST_JOB_ELAPSED_MS: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+/// Whether the job running on the current thread has been cancelled.
+pub(crate) fn current_job_cancelled() -> bool {
+    CURRENT_JOB_CANCEL.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .is_some_and(|flag| flag.load(Ordering::Relaxed))
+    })
+}
+
+/// Id of the job running on the current thread, if any.
+pub(crate) fn current_job_id() -> Option<String> {
+    CURRENT_JOB_ID.with(|current| current.borrow().clone())
+}
+
+/// Transcription time of the last job run on this thread; consumed by the read.
+pub(crate) fn take_last_job_elapsed_ms() -> Option<u64> {
+    LAST_JOB_ELAPSED_MS.with(|last| last.take())
+}
+
+/// Runs `transcribe` as a tracked job and returns its (possibly cancelled) result.
+pub(crate) fn run_job<T>(
+    app: &AppHandle,
+    source: JobSource,
+    audio_ms: u64,
+    transcribe: impl FnOnce() -> Result<T, String>,
+) -> Result<T, String> {
+    let job = enqueue(app, source, audio_ms);
+    if !job.start(app) {
+        return job.finish(app, Err(JOB_CANCELLED_ERROR.to_string()));
+    }
+    let previous = CURRENT_JOB_CANCEL.with(|current| current.replace(Some(job.cancel.clone())));
+    let previous_id = CURRENT_JOB_ID.with(|current| current.replace(Some(job.id.clone())));
+    let started = std::time::Instant::now();
+    let result = transcribe();
+    LAST_JOB_ELAPSED_MS.with(|last| last.set(Some(started.elapsed().as_millis() as u64)));
+    CURRENT_JOB_CANCEL.with(|current| *current.borrow_mut() = previous);
+    CURRENT_JOB_ID.with(|current| *current.borrow_mut() = previous_id);
+    job.finish(app, result)
+}
+
+/// Cancels all in-flight jobs from `source`, e.g. when a new PTT press starts.
+pub(crate) fn cancel_source(app: &AppHandle, source: JobSource) {
+    let mut jobs = registry();
+    let cancelled = jobs.cancel_source(source, crate::util::now_ms());
+    if cancelled > 0 {
+        info!(
+            "Cancelled {} in-flight {:?} transcription(s)",
+            cancelled, source
+        );
+        publish(app, &jobs);
+    }
+}
+
+/// Whether a job from `source` is queued or running, e.g. a live dictation
+/// that batch work should yield to.
+pub(crate) fn has_unfinished(source: JobSource) -> bool {
+    registry().has_unfinished(source)
+}
+
+pub(crate) fn is_cancellation(error: &str) -> bool {
+    error == JOB_CANCELLED_ERROR
+}
+
+#[tauri::command]
+pub(crate) fn get_jobs() -> Vec<TranscriptionJob> {
+    registry().snapshot()
+}
+
+#[tauri::command]
+pub(crate) fn cancel_job(app: AppHandle, job_id: String) -> CommandResult<()> {
+    let mut jobs = registry();
+    jobs.cancel(&job_id, crate::util::now_ms())
+        .with_code(ErrorCode::NotFound)?;
+    info!("Cancelled transcription job {}", job_id);
+    publish(&app, &jobs);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn job_lifecycle_and_cancel() {
+        let mut jobs = JobRegistry::default();
+        let (a, _) = jobs.create(JobSource::Mic, 1200, 1);
+        let (b, cancel_b) = jobs.create(JobSource::Loopback, 8000, 2);
+        assert_ne!(a, b);
+
+        jobs.set_state(&a, JobState::Running, None, 3);
+        jobs.set_state(&a, JobState::Done, None, 4);
+        assert!(jobs.cancel(&a, 5).is_err());
+
+        jobs.cancel(&b, 6).unwrap();
+        assert!(cancel_b.load(Ordering::Relaxed));
+        // A late result must not overwrite the cancelled state.
+        jobs.set_state(&b, JobState::Done, None, 7);
+        let snapshot = jobs.snapshot();
+        assert_eq!(snapshot[0].state, JobState::Done);
+        assert_eq!(snapshot[0].started_ms, Some(3));
+        assert_eq!(snapshot[1].state, JobState::Cancelled);
+        assert_eq!(snapshot[1].finished_ms, Some(6));
+        assert!(jobs.cancel("job_missing", 8).is_err());
+    }
+
+    #[test]
+    fn newer_press_cancels_and_supersedes_older_mic_jobs() {
+        let mut jobs = JobRegistry::default();
+        let (old, old_cancel) = jobs.create(JobSource::Mic, 0, 0);
+        let (loopback, _) = jobs.create(JobSource::Loopback, 0, 0);
+        assert_eq!(jobs.cancel_source(JobSource::Mic, 1), 1);
+        assert!(old_cancel.load(Ordering::Relaxed));
+        assert_eq!(jobs.snapshot()[1].state, JobState::Queued);
+
+        let (slow, _) = jobs.create(JobSource::Mic, 0, 2);
+        let (fast, _) = jobs.create(JobSource::Mic, 0, 3);
+        jobs.set_state(&fast, JobState::Done, None, 4);
+        assert!(jobs.superseded(&slow));
+        assert!(!jobs.superseded(&fast));
+        assert!(!jobs.superseded(&loopback));
+
+        let (slow_chunk, _) = jobs.create(JobSource::Loopback, 0, 5);
+        let (fast_chunk, _) = jobs.create(JobSource::Loopback, 0, 6);
+        jobs.set_state(&fast_chunk, JobState::Done, None, 7);
+        assert!(!jobs.superseded(&slow_chunk));
+        assert!(jobs.snapshot().iter().any(|job| job.id == old));
+    }
+
+    #[test]
+    fn finished_jobs_are_pruned_but_active_kept() {
+        let mut jobs = JobRegistry::default();
+        let (active, _) = jobs.create(JobSource::Mic, 0, 0);
+        for i in 0..(FINISHED_JOB_HISTORY + 5) {
+            let (id, _) = jobs.create(JobSource::Loopback, 0, i as u64);
+            jobs.set_state(&id, JobState::Failed, Some("boom".to_string()), i as u64);
+        }
+        let snapshot = jobs.snapshot();
+        assert_eq!(snapshot.len(), FINISHED_JOB_HISTORY + 1);
+        assert_eq!(snapshot[0].id, active);
+    }
+
+    #[test]
+    fn restore_fails_interrupted_jobs_and_keeps_ids_unique() {
+        let mut jobs = JobRegistry::default();
+        let (queued, _) = jobs.create(JobSource::Mic, 0, 0);
+        let (failed, _) = jobs.create(JobSource::File, 0, 1);
+        jobs.set_state(&failed, JobState::Failed, Some("boom".to_string()), 2);
+        let (done, _) = jobs.create(JobSource::Mic, 0, 3);
+        jobs.set_state(&done, JobState::Done, None, 4);
+        let persisted = jobs.persisted();
+        assert_eq!(persisted.len(), 2);
+
+        let mut restored = JobRegistry::restore(persisted, 10);
+        let snapshot = restored.snapshot();
+        assert_eq!(snapshot[0].id, queued);
+        assert_eq!(snapshot[0].state, JobState::Failed);
+        assert_eq!(snapshot[0].error.as_deref(), Some(INTERRUPTED_JOB_ERROR));
+        assert_eq!(snapshot[1].error.as_deref(), Some("boom"));
+        let (next, _) = restored.create(JobSource::Mic, 0, 11);
+        assert_eq!(next, "job_3");
+    }
+}
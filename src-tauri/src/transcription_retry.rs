@@ -0,0 +1,266 @@
+//! Retries for failed transcriptions.
+//!
+//! Transient whisper failures (temp-file contention, a GPU running out of
+//! memory, a hung runtime, a busy cloud provider) are retried up to
+//! `transcribe_retry_attempts` times, waiting `transcribe_retry_backoff_ms`
+//! before the first retry and doubling after each. Once a GPU error shows up
+//! the remaining attempts skip whisper-server and run whisper-cli on the CPU
+//! backend. Attempts always use the caller's settings; the CPU override is a
+//! per-thread flag rather than a modified copy of them.
+//!
+//! When a job still fails, its audio is kept on disk by `failed_segments`;
+//! `list_retained_jobs` lists those by job id and `retry_job` transcribes
+//! one again, including after a restart.
+
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tracing::warn;
+
+use crate::errors::{CommandError, CommandResult, ErrorCode};
+use crate::state::Settings;
+use crate::transcription_jobs::{current_job_cancelled, is_cancellation, JOB_CANCELLED_ERROR};
+
+pub(crate) const MAX_RETRY_ATTEMPTS: u32 = 5;
+pub(crate) const MIN_RETRY_BACKOFF_MS: u64 = 100;
+pub(crate) const MAX_RETRY_BACKOFF_MS: u64 = 10_000;
+const RETRY_EVENT: &str = "transcription:retry";
+
+/// Error text (lowercased) of failures worth retrying on the CPU backend.
+const GPU_FAILURE_MARKERS: &[&str] = &[
+    "out of memory",
+    "outofdevicememory",
+    "cudamalloc",
+    "cuda error",
+    "ggml_cuda",
+    "cublas",
+    "vk::",
+    "failed to allocate",
+    "exit=-1073741795",
+];
+
+/// Error text (lowercased) of other failures that tend to go away.
+const TRANSIENT_MARKERS: &[&str] = &[
+    "failed to write temporary audio file",
+    "being used by another process",
+    "os error 32",
+    "resource temporarily unavailable",
+    "timed out",
+    "connection failed",
+    "http 429",
+    "http 502",
+    "http 503",
+];
+
+fn is_gpu_failure(error: &str) -> bool {
+    let error = error.to_lowercase();
+    GPU_FAILURE_MARKERS
+        .iter()
+        .any(|marker| error.contains(marker))
+}
+
+fn is_transient(error: &str) -> bool {
+    let lowered = error.to_lowercase();
+    is_gpu_failure(error)
+        || TRANSIENT_MARKERS
+            .iter()
+            .any(|marker| lowered.contains(marker))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct RetryPolicy {
+    attempts: u32,
+    backoff_ms: u64,
+}
+
+impl RetryPolicy {
+    fn from_settings(settings: &Settings) -> Self {
+        Self {
+            attempts: settings.transcribe_retry_attempts.min(MAX_RETRY_ATTEMPTS),
+            backoff_ms: settings
+                .transcribe_retry_backoff_ms
+                .clamp(MIN_RETRY_BACKOFF_MS, MAX_RETRY_BACKOFF_MS),
+        }
+    }
+
+    /// Wait before retry number `retry` (1-based).
+    fn delay(&self, retry: u32) -> Duration {
+        let factor = 1u64 << retry.saturating_sub(1).min(16);
+        Duration::from_millis(
+            self.backoff_ms
+                .saturating_mul(factor)
+                .min(MAX_RETRY_BACKOFF_MS),
+        )
+    }
+}
+
+/// Sleeps for `delay`; returns false as soon as the current job is cancelled.
+fn sleep_unless_cancelled(delay: Duration) -> bool {
+    let step = Duration::from_millis(50);
+    let deadline = std::time::Instant::now() + delay;
+    while std::time::Instant::now() < deadline {
+        if current_job_cancelled() {
+            return false;
+        }
+        std::thread::sleep(step.min(deadline - std::time::Instant::now()));
+    }
+    !current_job_cancelled()
+}
+
+/// Runs `transcribe`, retrying transient failures under the settings' retry
+/// policy. Retries after a GPU failure run on the CPU backend.
+pub(crate) fn with_retries<T>(
+    app: &AppHandle,
+    settings: &Settings,
+    mut transcribe: impl FnMut() -> Result<T, String>,
+) -> Result<T, String> {
+    let policy = RetryPolicy::from_settings(settings);
+    let mut force_cpu = false;
+    let mut retry = 0;
+    loop {
+        let result = if force_cpu {
+            crate::transcription::with_cpu_backend(&mut transcribe)
+        } else {
+            transcribe()
+        };
+        let err = match result {
+            Ok(value) => return Ok(value),
+            Err(err) => err,
+        };
+        retry += 1;
+        if retry > policy.attempts || is_cancellation(&err) || !is_transient(&err) {
+            return Err(err);
+        }
+        // whisper-server may be on the GPU even when the CLI preference is
+        // "cpu", so any GPU failure moves the remaining attempts off it.
+        let cpu_fallback = !force_cpu && is_gpu_failure(&err);
+        force_cpu |= cpu_fallback;
+        let delay = policy.delay(retry);
+        warn!(
+            "Transcription failed (retry {}/{} in {}ms{}): {}",
+            retry,
+            policy.attempts,
+            delay.as_millis(),
+            if cpu_fallback { ", on CPU" } else { "" },
+            err
+        );
+        let _ = app.emit(
+            RETRY_EVENT,
+            serde_json::json!({
+                "retry": retry,
+                "max_retries": policy.attempts,
+                "delay_ms": delay.as_millis() as u64,
+                "cpu_fallback": cpu_fallback,
+                "error": err,
+            }),
+        );
+        if !sleep_unless_cancelled(delay) {
+            return Err(JOB_CANCELLED_ERROR.to_string());
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct RetainedJobInfo {
+    pub(crate) job_id: String,
+    pub(crate) audio_ms: u64,
+    pub(crate) error: String,
+    pub(crate) failed_ms: u64,
+}
+
+/// Kept segments that belong to a transcription job, oldest first.
+fn retained_jobs(segments: Vec<crate::failed_segments::FailedSegment>) -> Vec<RetainedJobInfo> {
+    let mut jobs: Vec<RetainedJobInfo> = segments
+        .into_iter()
+        .filter_map(|segment| {
+            Some(RetainedJobInfo {
+                job_id: segment.job_id?,
+                audio_ms: segment.duration_ms,
+                error: segment.error,
+                failed_ms: segment.failed_ms,
+            })
+        })
+        .collect();
+    jobs.sort_by_key(|job| job.failed_ms);
+    jobs
+}
+
+/// Failed jobs whose audio can still be retried with `retry_job`, oldest
+/// first.
+#[tauri::command]
+pub(crate) fn list_retained_jobs(app: AppHandle) -> Vec<RetainedJobInfo> {
+    retained_jobs(crate::failed_segments::list_failed_segments(app))
+}
+
+/// Transcribes the audio of failed job `job_id` again (see
+/// `failed_segments`).
+#[tauri::command]
+pub(crate) fn retry_job(app: AppHandle, job_id: String) -> CommandResult<()> {
+    let id = crate::failed_segments::find_by_job_id(&app, &job_id).ok_or_else(|| {
+        CommandError::new(
+            ErrorCode::NotFound,
+            format!("No kept audio for transcription job '{}'", job_id),
+        )
+    })?;
+    crate::failed_segments::retry_failed_segment(app, id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_errors_and_backs_off() {
+        assert!(is_transient(
+            "Failed to write temporary audio file 'x.wav': os error 32"
+        ));
+        assert!(is_transient(
+            "whisper-cli timed out after 120 seconds ('cli')"
+        ));
+        assert!(is_gpu_failure("ggml_cuda_init: CUDA error: out of memory"));
+        assert!(is_transient(
+            "GPU backend 'vulkan' failed: ErrorOutOfDeviceMemory"
+        ));
+        assert!(!is_transient(
+            "Model file not found. Set TRISPR_WHISPER_MODEL_DIR or TRISPR_WHISPER_MODEL."
+        ));
+
+        let settings = Settings {
+            transcribe_retry_attempts: 9,
+            transcribe_retry_backoff_ms: 400,
+            ..Settings::default()
+        };
+        let policy = RetryPolicy::from_settings(&settings);
+        assert_eq!(policy.attempts, MAX_RETRY_ATTEMPTS);
+        assert_eq!(policy.delay(1), Duration::from_millis(400));
+        assert_eq!(policy.delay(3), Duration::from_millis(1600));
+        assert_eq!(policy.delay(9), Duration::from_millis(MAX_RETRY_BACKOFF_MS));
+    }
+
+    #[test]
+    fn retained_jobs_are_the_kept_segments_with_a_job_id() {
+        let segment = |id: &str, job_id: Option<&str>, failed_ms: u64| {
+            crate::failed_segments::FailedSegment {
+                id: id.to_string(),
+                source: "mic".to_string(),
+                job_id: job_id.map(str::to_string),
+                error: "boom".to_string(),
+                failed_ms,
+                duration_ms: 1_500,
+            }
+        };
+        let jobs = retained_jobs(vec![
+            segment("mic_30", Some("job_3"), 30),
+            segment("mic_20", None, 20),
+            segment("mic_10", Some("job_1"), 10),
+        ]);
+        assert_eq!(
+            jobs.iter()
+                .map(|job| job.job_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["job_1", "job_3"]
+        );
+        assert_eq!(jobs[0].audio_ms, 1_500);
+    }
+}
@@ -0,0 +1,469 @@
+//! Machine translation of final mic transcripts.
+//!
+//! With `translation.enabled`, a dictation is sent to the configured
+//! `TranslationProvider` (DeepL or Google Cloud Translation) after
+//! post-processing and snippet expansion. The history entry keeps the
+//! original text and records the translation next to it; which of the two
+//! gets pasted follows `translation.paste_translation`. When the original is
+//! pasted, the translation runs in the background and lands in history
+//! afterwards. Dictations whisper already detected in the target language
+//! are not sent.
+//!
+//! API keys come from the OS keychain (`set_translation_credentials`); the
+//! per-provider environment variable is only a development fallback.
+
+pub(crate) mod keyring;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::warn;
+
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
+use crate::state::{AppState, HistoryTranslation, Settings};
+
+pub(crate) const TRANSLATION_PROVIDER_IDS: &[&str] = &["deepl", "google"];
+const DEFAULT_TARGET_LANGUAGE: &str = "en";
+const TRANSLATION_CONNECT_TIMEOUT_SECS: u64 = 5;
+const TRANSLATION_READ_TIMEOUT_SECS: u64 = 30;
+
+/// Mirrors `src/types.ts::TranslationSettings`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct TranslationSettings {
+    pub(crate) enabled: bool,
+    /// "deepl" | "google"
+    pub(crate) provider: String,
+    /// Language code to translate into, e.g. "en", "de", "pt-BR".
+    pub(crate) target_language: String,
+    /// Paste the translation instead of the original transcript.
+    pub(crate) paste_translation: bool,
+}
+
+impl Default for TranslationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: "deepl".to_string(),
+            target_language: DEFAULT_TARGET_LANGUAGE.to_string(),
+            paste_translation: true,
+        }
+    }
+}
+
+pub(crate) fn normalize_translation_provider_id(provider: &str) -> Option<&'static str> {
+    let normalized = provider.trim().to_lowercase();
+    TRANSLATION_PROVIDER_IDS
+        .iter()
+        .copied()
+        .find(|id| *id == normalized)
+}
+
+/// "pt_br" → "pt-BR". `None` for anything that is not a language[-region]
+/// code.
+fn normalize_target_language(code: &str) -> Option<String> {
+    let code = code.trim().replace('_', "-");
+    let (language, region) = match code.split_once('-') {
+        Some((language, region)) => (language, Some(region)),
+        None => (code.as_str(), None),
+    };
+    let valid = |part: &str, max: usize| {
+        (2..=max).contains(&part.len()) && part.chars().all(|c| c.is_ascii_alphabetic())
+    };
+    if !valid(language, 3) || region.is_some_and(|region| !valid(region, 4)) {
+        return None;
+    }
+    let language = language.to_lowercase();
+    Some(match region {
+        // Script subtags ("zh-Hant") are title case, regions upper case.
+        Some(region) if region.len() == 4 => {
+            let (first, rest) = region.split_at(1);
+            format!(
+                "{}-{}{}",
+                language,
+                first.to_uppercase(),
+                rest.to_lowercase()
+            )
+        }
+        Some(region) => format!("{}-{}", language, region.to_uppercase()),
+        None => language,
+    })
+}
+
+pub(crate) fn normalize_translation_settings(settings: &mut TranslationSettings) {
+    settings.provider = normalize_translation_provider_id(&settings.provider)
+        .unwrap_or("deepl")
+        .to_string();
+    settings.target_language = normalize_target_language(&settings.target_language)
+        .unwrap_or_else(|| DEFAULT_TARGET_LANGUAGE.to_string());
+}
+
+/// Whether whisper's detected language already is the target, ignoring the
+/// region ("en" vs "en-GB").
+fn is_target_language(detected: &str, target: &str) -> bool {
+    let base = |code: &str| code.split('-').next().unwrap_or_default().to_lowercase();
+    base(detected) == base(target)
+}
+
+pub(crate) struct Translated {
+    pub(crate) text: String,
+    /// Source language the provider detected, lowercased.
+    pub(crate) source_language: Option<String>,
+}
+
+pub(crate) trait TranslationProvider: Send + Sync {
+    fn id(&self) -> &'static str;
+    /// Environment variable consulted when no keychain entry exists.
+    fn api_key_env(&self) -> &'static str;
+    fn translate(
+        &self,
+        text: &str,
+        target_language: &str,
+        api_key: &str,
+    ) -> Result<Translated, String>;
+}
+
+/// DeepL API v2. Free-plan keys end in ":fx" and use their own host.
+struct DeepLProvider;
+
+/// Google Cloud Translation v2 (Basic) with an API key.
+struct GoogleProvider;
+
+pub(crate) fn create_translation_provider(
+    provider: &str,
+) -> Result<Box<dyn TranslationProvider>, String> {
+    match normalize_translation_provider_id(provider) {
+        Some("deepl") => Ok(Box::new(DeepLProvider)),
+        Some("google") => Ok(Box::new(GoogleProvider)),
+        _ => Err(format!("Unknown translation provider: {}", provider)),
+    }
+}
+
+fn translation_agent() -> ureq::Agent {
+    ureq::builder()
+        .timeout_connect(Duration::from_secs(TRANSLATION_CONNECT_TIMEOUT_SECS))
+        .timeout_read(Duration::from_secs(TRANSLATION_READ_TIMEOUT_SECS))
+        .build()
+}
+
+fn map_ureq_error(provider: &str, err: ureq::Error) -> String {
+    match err {
+        ureq::Error::Status(code, response) => {
+            crate::format_ureq_status_error(&format!("{} translation", provider), code, response)
+        }
+        ureq::Error::Transport(transport) => {
+            format!("{} translation connection failed: {}", provider, transport)
+        }
+    }
+}
+
+fn deepl_base_url(api_key: &str) -> &'static str {
+    if api_key.ends_with(":fx") {
+        "https://api-free.deepl.com"
+    } else {
+        "https://api.deepl.com"
+    }
+}
+
+/// DeepL wants upper-case codes and no longer accepts bare "EN"/"PT" as a
+/// target.
+fn deepl_target_code(target_language: &str) -> String {
+    match target_language.to_uppercase().as_str() {
+        "EN" => "EN-US".to_string(),
+        "PT" => "PT-PT".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn parse_deepl_response(json: &serde_json::Value) -> Option<Translated> {
+    let translation = json.pointer("/translations/0")?;
+    Some(Translated {
+        text: translation.get("text")?.as_str()?.trim().to_string(),
+        source_language: translation
+            .get("detected_source_language")
+            .and_then(|v| v.as_str())
+            .map(str::to_lowercase),
+    })
+}
+
+fn parse_google_response(json: &serde_json::Value) -> Option<Translated> {
+    let translation = json.pointer("/data/translations/0")?;
+    Some(Translated {
+        text: translation
+            .get("translatedText")?
+            .as_str()?
+            .trim()
+            .to_string(),
+        source_language: translation
+            .get("detectedSourceLanguage")
+            .and_then(|v| v.as_str())
+            .map(str::to_lowercase),
+    })
+}
+
+impl TranslationProvider for DeepLProvider {
+    fn id(&self) -> &'static str {
+        "deepl"
+    }
+
+    fn api_key_env(&self) -> &'static str {
+        "DEEPL_API_KEY"
+    }
+
+    fn translate(
+        &self,
+        text: &str,
+        target_language: &str,
+        api_key: &str,
+    ) -> Result<Translated, String> {
+        let json: serde_json::Value = translation_agent()
+            .post(&format!("{}/v2/translate", deepl_base_url(api_key)))
+            .set("Authorization", &format!("DeepL-Auth-Key {}", api_key))
+            .send_json(serde_json::json!({
+                "text": [text],
+                "target_lang": deepl_target_code(target_language),
+            }))
+            .map_err(|err| map_ureq_error(self.id(), err))?
+            .into_json()
+            .map_err(|e| format!("Failed to parse deepl response: {}", e))?;
+        parse_deepl_response(&json)
+            .ok_or_else(|| format!("No translation in deepl response: {}", json))
+    }
+}
+
+impl TranslationProvider for GoogleProvider {
+    fn id(&self) -> &'static str {
+        "google"
+    }
+
+    fn api_key_env(&self) -> &'static str {
+        "GOOGLE_TRANSLATE_API_KEY"
+    }
+
+    fn translate(
+        &self,
+        text: &str,
+        target_language: &str,
+        api_key: &str,
+    ) -> Result<Translated, String> {
+        // Key in a header rather than `?key=`, so it never shows up in
+        // transport errors (which include the URL).
+        let json: serde_json::Value = translation_agent()
+            .post("https://translation.googleapis.com/language/translate/v2")
+            .set("X-Goog-Api-Key", api_key)
+            .send_json(serde_json::json!({
+                "q": text,
+                "target": target_language,
+                "format": "text",
+            }))
+            .map_err(|err| map_ureq_error(self.id(), err))?
+            .into_json()
+            .map_err(|e| format!("Failed to parse google response: {}", e))?;
+        parse_google_response(&json)
+            .ok_or_else(|| format!("No translation in google response: {}", json))
+    }
+}
+
+fn resolve_api_key(provider: &dyn TranslationProvider) -> Option<String> {
+    match keyring::read_api_key(provider.id()) {
+        Ok(Some(key)) => return Some(key),
+        Ok(None) => {}
+        Err(err) => warn!(
+            "Translation key lookup failed for '{}': {}",
+            provider.id(),
+            err
+        ),
+    }
+    std::env::var(provider.api_key_env())
+        .ok()
+        .map(|key| key.trim().to_string())
+        .filter(|key| !key.is_empty())
+}
+
+/// Translate `text` with the configured provider and target language.
+pub(crate) fn translate_with_settings(
+    translation: &TranslationSettings,
+    text: &str,
+) -> Result<HistoryTranslation, String> {
+    let provider = create_translation_provider(&translation.provider)?;
+    let api_key = resolve_api_key(provider.as_ref()).ok_or_else(|| {
+        format!(
+            "No API key configured for translation provider '{}'. Store one with set_translation_credentials (or set {} for development).",
+            provider.id(),
+            provider.api_key_env()
+        )
+    })?;
+    let translated = provider.translate(text, &translation.target_language, &api_key)?;
+    if translated.text.is_empty() {
+        return Err(format!("{} returned an empty translation", provider.id()));
+    }
+    Ok(HistoryTranslation {
+        text: translated.text,
+        target_language: translation.target_language.clone(),
+        provider: provider.id().to_string(),
+        source_language: translated.source_language,
+        pasted: translation.paste_translation,
+    })
+}
+
+/// Translation of a final mic transcript, or `None` when translation is off,
+/// the dictation already is in the target language, or the provider failed
+/// (reported as an app error; the original is pasted instead).
+pub(crate) fn translate_transcript(
+    app: &AppHandle,
+    text: &str,
+    translation: &TranslationSettings,
+    detected_language: Option<&str>,
+) -> Option<HistoryTranslation> {
+    if !translation.enabled || text.trim().is_empty() {
+        return None;
+    }
+    if detected_language
+        .is_some_and(|detected| is_target_language(detected, &translation.target_language))
+    {
+        return None;
+    }
+    match translate_with_settings(translation, text) {
+        Ok(translated) => Some(translated),
+        Err(err) => {
+            crate::emit_error(
+                app,
+                crate::errors::AppError::Other(err),
+                Some("Translation"),
+            );
+            None
+        }
+    }
+}
+
+/// Translates a transcript whose original was already pasted and attaches
+/// the result to its history entry once the provider answers, so the paste
+/// never waits on the network.
+pub(crate) fn attach_translation_later(
+    app: &AppHandle,
+    entry_id: String,
+    text: String,
+    settings: &Settings,
+    detected_language: Option<String>,
+) {
+    if !settings.translation.enabled || text.trim().is_empty() {
+        return;
+    }
+    let app = app.clone();
+    let translation = settings.translation.clone();
+    crate::util::spawn_guarded("translate_transcript", move || {
+        let Some(translated) =
+            translate_transcript(&app, &text, &translation, detected_language.as_deref())
+        else {
+            return;
+        };
+        let state = app.state::<AppState>();
+        if let Some(updated) =
+            crate::history_partition::attach_translation(&state.history, &entry_id, translated)
+        {
+            let _ = app.emit("history:updated", updated);
+        }
+    });
+}
+
+#[tauri::command]
+pub(crate) fn set_translation_credentials(provider: String, api_key: String) -> CommandResult<()> {
+    keyring::store_api_key(&provider, &api_key).with_code(ErrorCode::Credentials)
+}
+
+#[tauri::command]
+pub(crate) fn clear_translation_credentials(provider: String) -> CommandResult<()> {
+    keyring::clear_api_key(&provider).with_code(ErrorCode::Credentials)
+}
+
+/// Which providers have a key in the keychain. Never returns the keys.
+#[tauri::command]
+pub(crate) fn get_translation_credentials_status() -> HashMap<String, bool> {
+    TRANSLATION_PROVIDER_IDS
+        .iter()
+        .map(|id| {
+            let stored = matches!(keyring::read_api_key(id), Ok(Some(_)));
+            (id.to_string(), stored)
+        })
+        .collect()
+}
+
+/// Translates `text` with the saved provider and target language, whether
+/// or not translation is enabled, so the settings page can try a key.
+#[tauri::command]
+pub(crate) async fn translate_text(
+    app: AppHandle,
+    text: String,
+) -> CommandResult<HistoryTranslation> {
+    let translation = app
+        .state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .translation
+        .clone();
+    let translated =
+        tauri::async_runtime::spawn_blocking(move || translate_with_settings(&translation, &text))
+            .await
+            .map_err(|e| {
+                CommandError::new(
+                    ErrorCode::Internal,
+                    format!("translation task failed: {}", e),
+                )
+            })?
+            .with_code(ErrorCode::Network)?;
+    Ok(translated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_settings_and_language_codes() {
+        let mut settings = TranslationSettings {
+            provider: " Google ".to_string(),
+            target_language: "pt_br".to_string(),
+            ..TranslationSettings::default()
+        };
+        normalize_translation_settings(&mut settings);
+        assert_eq!(settings.provider, "google");
+        assert_eq!(settings.target_language, "pt-BR");
+
+        settings.provider = "bing".to_string();
+        settings.target_language = "english".to_string();
+        normalize_translation_settings(&mut settings);
+        assert_eq!(settings.provider, "deepl");
+        assert_eq!(settings.target_language, "en");
+
+        assert_eq!(
+            normalize_target_language("ZH-hant").as_deref(),
+            Some("zh-Hant")
+        );
+        assert_eq!(deepl_target_code("en"), "EN-US");
+        assert_eq!(deepl_target_code("pt-BR"), "PT-BR");
+        assert!(is_target_language("en", "en-GB"));
+        assert!(!is_target_language("de", "en"));
+        assert_eq!(deepl_base_url("abc:fx"), "https://api-free.deepl.com");
+    }
+
+    #[test]
+    fn parses_provider_responses() {
+        let deepl = serde_json::json!({
+            "translations": [{ "detected_source_language": "DE", "text": " Hello world " }]
+        });
+        let parsed = parse_deepl_response(&deepl).unwrap();
+        assert_eq!(parsed.text, "Hello world");
+        assert_eq!(parsed.source_language.as_deref(), Some("de"));
+
+        let google = serde_json::json!({
+            "data": { "translations": [{ "translatedText": "Hallo Welt", "detectedSourceLanguage": "en" }] }
+        });
+        let parsed = parse_google_response(&google).unwrap();
+        assert_eq!(parsed.text, "Hallo Welt");
+        assert_eq!(parsed.source_language.as_deref(), Some("en"));
+        assert!(parse_google_response(&deepl).is_none());
+    }
+}
@@ -1,4 +1,5 @@
 use super::{now_iso, AppState};
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
 use crate::multimodal_io::Qwen3TtsConfig;
 use crate::state::Settings;
 use crate::transcription::{
@@ -6,8 +7,10 @@ use crate::transcription::{
 };
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
 use std::time::Instant;
-use tauri::{AppHandle, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 use tracing::{info, warn};
 
 #[derive(Debug, Clone, serde::Deserialize)]
@@ -473,9 +476,238 @@ pub(crate) fn run_latency_benchmark(
     app: AppHandle,
     state: State<'_, AppState>,
     request: Option<LatencyBenchmarkRequest>,
-) -> Result<LatencyBenchmarkResult, String> {
+) -> CommandResult<LatencyBenchmarkResult> {
     let request = request.unwrap_or_default();
-    run_latency_benchmark_inner(&app, state.inner(), &request)
+    run_latency_benchmark_inner(&app, state.inner(), &request).with_code(ErrorCode::Transcription)
+}
+
+const MODEL_BENCHMARK_SAMPLE: &str = "bench/short_en_like.wav";
+const MODEL_BENCHMARK_DEFAULT_RUNS: u32 = 3;
+const MODEL_BENCHMARK_RAM_POLL_MS: u64 = 100;
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct ModelBenchmarkProgress {
+    model_id: String,
+    stage: String, // "preparing" | "running" | "done"
+    run: u32,
+    total_runs: u32,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub(crate) struct ModelBenchmarkResult {
+    model_id: String,
+    runs: u32,
+    audio_ms: u64,
+    median_ms: u64,
+    /// Processing time divided by audio length; below 1.0 is faster than realtime.
+    realtime_factor: f64,
+    /// Peak resident memory of the app plus the benchmark whisper-cli, when measurable.
+    peak_ram_mb: Option<u64>,
+    accelerator: String,
+    backend: String,
+    whisper_path: String,
+}
+
+fn emit_model_benchmark_progress(
+    app: &AppHandle,
+    model_id: &str,
+    stage: &str,
+    run: u32,
+    total: u32,
+) {
+    let _ = app.emit(
+        "model:benchmark-progress",
+        ModelBenchmarkProgress {
+            model_id: model_id.to_string(),
+            stage: stage.to_string(),
+            run,
+            total_runs: total,
+        },
+    );
+}
+
+fn model_benchmark_sample_path(app: &AppHandle) -> Option<PathBuf> {
+    if let Ok(resource_dir) = app.path().resource_dir() {
+        let bundled = resource_dir.join(MODEL_BENCHMARK_SAMPLE);
+        if bundled.is_file() {
+            return Some(bundled);
+        }
+    }
+    // Dev builds read the fixture straight from the repo.
+    let fixtures = default_latency_fixture_paths();
+    fixtures
+        .iter()
+        .find(|path| path.ends_with("short_en_like.wav"))
+        .or_else(|| fixtures.first())
+        .cloned()
+}
+
+fn realtime_factor(processing_ms: u64, audio_ms: u64) -> f64 {
+    if audio_ms == 0 {
+        return 0.0;
+    }
+    processing_ms as f64 / audio_ms as f64
+}
+
+#[cfg(target_os = "linux")]
+fn process_rss_bytes(pid: u32) -> Option<u64> {
+    let status = std::fs::read_to_string(format!("/proc/{pid}/status")).ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb * 1024)
+}
+
+#[cfg(target_os = "windows")]
+fn process_rss_bytes(pid: u32) -> Option<u64> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::ProcessStatus::{
+        K32GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS,
+    };
+    use windows_sys::Win32::System::Threading::{OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
+
+    // SAFETY: the handle is checked before use and closed afterwards; the
+    // counters struct is plain data sized via `cb`.
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if handle.is_null() {
+            return None;
+        }
+        let mut counters: PROCESS_MEMORY_COUNTERS = std::mem::zeroed();
+        counters.cb = std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32;
+        let ok = K32GetProcessMemoryInfo(handle, &mut counters, counters.cb);
+        CloseHandle(handle);
+        (ok != 0).then_some(counters.WorkingSetSize as u64)
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+fn process_rss_bytes(_pid: u32) -> Option<u64> {
+    None
+}
+
+/// Samples app + whisper-cli memory until `stop` is set; returns the peak in bytes.
+fn spawn_ram_sampler(
+    cli_pid: Arc<AtomicU32>,
+    stop: Arc<AtomicBool>,
+) -> std::thread::JoinHandle<Option<u64>> {
+    std::thread::spawn(move || {
+        let mut peak: Option<u64> = None;
+        loop {
+            let own = process_rss_bytes(std::process::id());
+            let cli = match cli_pid.load(Ordering::Relaxed) {
+                0 => None,
+                pid => process_rss_bytes(pid),
+            };
+            if own.is_some() || cli.is_some() {
+                let total = own.unwrap_or(0) + cli.unwrap_or(0);
+                peak = Some(peak.map_or(total, |p| p.max(total)));
+            }
+            if stop.load(Ordering::Relaxed) {
+                return peak;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(
+                MODEL_BENCHMARK_RAM_POLL_MS,
+            ));
+        }
+    })
+}
+
+/// Transcribes the bundled sample with `model_id` and reports speed and memory use.
+/// Runs on a blocking thread so the window keeps rendering the progress events.
+#[tauri::command]
+pub(crate) async fn benchmark_model(
+    app: AppHandle,
+    model_id: String,
+    runs: Option<u32>,
+) -> CommandResult<ModelBenchmarkResult> {
+    tauri::async_runtime::spawn_blocking(move || benchmark_model_blocking(&app, model_id, runs))
+        .await
+        .map_err(|e| {
+            CommandError::new(
+                ErrorCode::Internal,
+                format!("benchmark_model task failed: {}", e),
+            )
+        })?
+}
+
+fn benchmark_model_blocking(
+    app: &AppHandle,
+    model_id: String,
+    runs: Option<u32>,
+) -> CommandResult<ModelBenchmarkResult> {
+    let runs = runs.unwrap_or(MODEL_BENCHMARK_DEFAULT_RUNS).clamp(1, 10);
+    crate::models::resolve_model_path(app, &model_id).ok_or_else(|| {
+        CommandError::new(
+            ErrorCode::ModelNotFound,
+            format!("Model '{}' is not installed", model_id),
+        )
+    })?;
+    let sample_path = model_benchmark_sample_path(app).ok_or_else(|| {
+        CommandError::new(
+            ErrorCode::NotFound,
+            "Benchmark sample audio is missing from this install",
+        )
+    })?;
+    let samples = read_wav_for_latency_benchmark(&sample_path).with_code(ErrorCode::Storage)?;
+    let audio_ms = samples.len() as u64 * 1000 / crate::constants::TARGET_SAMPLE_RATE as u64;
+
+    let mut settings = app
+        .state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    // Benchmark the local engine only, on its own whisper-cli so live
+    // dictation keeps using the shared server and its model.
+    settings.model = model_id.clone();
+    settings.cloud_transcription.enabled = false;
+    settings.cloud_fallback = false;
+
+    emit_model_benchmark_progress(app, &model_id, "preparing", 0, runs);
+    let cli_pid = Arc::new(AtomicU32::new(0));
+    let (outcome, peak_ram_bytes) =
+        crate::transcription::with_dedicated_cli(cli_pid.clone(), || {
+            let stop = Arc::new(AtomicBool::new(false));
+            let sampler = spawn_ram_sampler(cli_pid, stop.clone());
+            // Warm-up pass so the first read of the model file does not skew
+            // the numbers.
+            let mut outcome = transcribe_audio(app, &settings, &samples).map(|_| Vec::new());
+            for run in 1..=runs {
+                let Ok(durations) = outcome.as_mut() else {
+                    break;
+                };
+                emit_model_benchmark_progress(app, &model_id, "running", run, runs);
+                let started = Instant::now();
+                match transcribe_audio(app, &settings, &samples) {
+                    Ok(_) => durations.push(started.elapsed().as_millis() as u64),
+                    Err(err) => outcome = Err(err),
+                }
+            }
+            stop.store(true, Ordering::Relaxed);
+            (outcome, sampler.join().unwrap_or(None))
+        });
+
+    let mut durations = outcome.with_code(ErrorCode::Transcription)?;
+    durations.sort_unstable();
+    let median_ms = percentile(&durations, 0.5);
+    let timing = last_transcription_timing_summary();
+    let result = ModelBenchmarkResult {
+        model_id: model_id.clone(),
+        runs,
+        audio_ms,
+        median_ms,
+        realtime_factor: realtime_factor(median_ms, audio_ms),
+        peak_ram_mb: peak_ram_bytes.map(|bytes| bytes / (1024 * 1024)),
+        accelerator: last_transcription_accelerator().to_string(),
+        backend: timing.backend,
+        whisper_path: timing.whisper_path,
+    };
+    info!(
+        "Model benchmark {}: median {}ms for {}ms audio (RTF {:.2}, {})",
+        model_id, median_ms, audio_ms, result.realtime_factor, result.accelerator
+    );
+    emit_model_benchmark_progress(app, &model_id, "done", runs, runs);
+    Ok(result)
 }
 
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
@@ -1182,11 +1414,18 @@ fn provider_consistency_from_runtime_surface(
 mod tts_benchmark_tests {
     use super::{
         build_tts_fallback_order, classify_tts_failure, normalize_tts_benchmark_providers,
-        TtsBenchmarkProviderSummary, TTS_FAILURE_AUTH_MISSING, TTS_FAILURE_ENDPOINT_UNREACHABLE,
-        TTS_FAILURE_MISSING_BINARY, TTS_FAILURE_MISSING_MODEL, TTS_FAILURE_RUNTIME_ERROR,
-        TTS_FAILURE_STREAM_CONFIG_UNSUPPORTED,
+        realtime_factor, TtsBenchmarkProviderSummary, TTS_FAILURE_AUTH_MISSING,
+        TTS_FAILURE_ENDPOINT_UNREACHABLE, TTS_FAILURE_MISSING_BINARY, TTS_FAILURE_MISSING_MODEL,
+        TTS_FAILURE_RUNTIME_ERROR, TTS_FAILURE_STREAM_CONFIG_UNSUPPORTED,
     };
 
+    #[test]
+    fn realtime_factor_is_processing_over_audio() {
+        assert_eq!(realtime_factor(500, 1000), 0.5);
+        assert_eq!(realtime_factor(3000, 1500), 2.0);
+        assert_eq!(realtime_factor(100, 0), 0.0);
+    }
+
     #[test]
     fn fallback_order_prefers_reliability_gate_then_latency() {
         let summaries = vec![
@@ -1943,9 +2182,9 @@ pub(crate) fn write_tts_benchmark_report(result: &TtsBenchmarkResult) -> Result<
 pub(crate) fn run_tts_benchmark(
     state: State<'_, AppState>,
     request: Option<TtsBenchmarkRequest>,
-) -> Result<TtsBenchmarkResult, String> {
+) -> CommandResult<TtsBenchmarkResult> {
     let request = request.unwrap_or_default();
-    run_tts_benchmark_inner(state.inner(), &request)
+    run_tts_benchmark_inner(state.inner(), &request).with_code(ErrorCode::Storage)
 }
 
 pub(crate) fn tts_benchmark_request_from_env() -> TtsBenchmarkRequest {
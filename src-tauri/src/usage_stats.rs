@@ -0,0 +1,253 @@
+//! Local usage statistics: per-day dictation counts, audio minutes, words,
+//! models and latency, kept in `usage-stats.json` and never sent anywhere.
+
+use crate::errors::{CommandResult, ErrorCode, WithErrorCode};
+use chrono::{Duration as ChronoDuration, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use tauri::AppHandle;
+use tracing::warn;
+
+const USAGE_STATS_FILE: &str = "usage-stats.json";
+/// Days older than this are dropped on write.
+const RETENTION_DAYS: i64 = 400;
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct DayStats {
+    pub(crate) dictations: u32,
+    pub(crate) audio_ms: u64,
+    pub(crate) words: u64,
+    /// Sum and count of transcription latencies, so averages merge exactly.
+    pub(crate) latency_total_ms: u64,
+    pub(crate) latency_samples: u32,
+    /// Dictations per model (local model id or cloud source tag).
+    pub(crate) models: BTreeMap<String, u32>,
+}
+
+impl DayStats {
+    fn record(&mut self, sample: &UsageSample) {
+        self.dictations += 1;
+        self.audio_ms += sample.audio_ms;
+        self.words += sample.words;
+        if let Some(latency) = sample.latency_ms {
+            self.latency_total_ms += latency;
+            self.latency_samples += 1;
+        }
+        *self.models.entry(sample.model.clone()).or_default() += 1;
+    }
+
+    fn merge(&mut self, other: &DayStats) {
+        self.dictations += other.dictations;
+        self.audio_ms += other.audio_ms;
+        self.words += other.words;
+        self.latency_total_ms += other.latency_total_ms;
+        self.latency_samples += other.latency_samples;
+        for (model, count) in &other.models {
+            *self.models.entry(model.clone()).or_default() += count;
+        }
+    }
+
+    fn avg_latency_ms(&self) -> Option<u64> {
+        (self.latency_samples > 0).then(|| self.latency_total_ms / self.latency_samples as u64)
+    }
+
+    fn top_model(&self) -> Option<String> {
+        self.models
+            .iter()
+            .max_by_key(|(_, count)| **count)
+            .map(|(model, _)| model.clone())
+    }
+}
+
+/// One finished dictation.
+#[derive(Debug, Clone)]
+pub(crate) struct UsageSample {
+    pub(crate) model: String,
+    pub(crate) audio_ms: u64,
+    pub(crate) words: u64,
+    pub(crate) latency_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct UsageStore {
+    /// Keyed by local date (`YYYY-MM-DD`).
+    days: BTreeMap<String, DayStats>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct DayUsage {
+    pub(crate) date: String,
+    pub(crate) dictations: u32,
+    pub(crate) minutes: f64,
+    pub(crate) words: u64,
+    pub(crate) avg_latency_ms: Option<u64>,
+    pub(crate) top_model: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct UsageStats {
+    pub(crate) range: String,
+    /// Oldest first; days without dictations are included as zeros.
+    pub(crate) days: Vec<DayUsage>,
+    pub(crate) dictations: u32,
+    pub(crate) minutes: f64,
+    pub(crate) words: u64,
+    pub(crate) avg_latency_ms: Option<u64>,
+    pub(crate) models: BTreeMap<String, u32>,
+}
+
+/// `None` in the cache means the file has not been read yet.
+static STORE: OnceLock<Mutex<Option<UsageStore>>> = OnceLock::new();
+
+fn store_guard() -> MutexGuard<'static, Option<UsageStore>> {
+    STORE
+        .get_or_init(|| Mutex::new(None))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn load_store(app: &AppHandle) -> UsageStore {
+    let path = crate::paths::resolve_data_path(app, USAGE_STATS_FILE);
+    match fs::read_to_string(&path) {
+        Ok(raw) => serde_json::from_str(&raw).unwrap_or_else(|err| {
+            warn!("Ignoring unreadable usage stats: {}", err);
+            UsageStore::default()
+        }),
+        Err(_) => UsageStore::default(),
+    }
+}
+
+fn save_store(app: &AppHandle, store: &UsageStore) {
+    let path = crate::paths::resolve_data_path(app, USAGE_STATS_FILE);
+    match serde_json::to_string(store) {
+        Ok(raw) => {
+            if let Err(err) = fs::write(&path, raw) {
+                warn!("Failed to persist usage stats: {}", err);
+            }
+        }
+        Err(err) => warn!("Failed to serialize usage stats: {}", err),
+    }
+}
+
+fn prune_store(store: &mut UsageStore, today: NaiveDate) {
+    let cutoff = (today - ChronoDuration::days(RETENTION_DAYS))
+        .format(DATE_FORMAT)
+        .to_string();
+    store.days.retain(|date, _| *date >= cutoff);
+}
+
+/// Adds a dictation to today's bucket and persists the store.
+pub(crate) fn record_dictation(app: &AppHandle, sample: UsageSample) {
+    let today = Local::now().date_naive();
+    let mut guard = store_guard();
+    let store = guard.get_or_insert_with(|| load_store(app));
+    store
+        .days
+        .entry(today.format(DATE_FORMAT).to_string())
+        .or_default()
+        .record(&sample);
+    prune_store(store, today);
+    save_store(app, store);
+}
+
+/// Number of days covered by `range`, or `None` for everything recorded.
+fn range_days(range: &str) -> Result<Option<i64>, String> {
+    match range.trim() {
+        "today" => Ok(Some(1)),
+        "" | "7d" | "week" => Ok(Some(7)),
+        "30d" | "month" => Ok(Some(30)),
+        "365d" | "year" => Ok(Some(365)),
+        "all" => Ok(None),
+        other => Err(format!(
+            "Unknown usage stats range '{}' (expected today, 7d, 30d, 365d or all)",
+            other
+        )),
+    }
+}
+
+fn summarize(store: &UsageStore, range: &str, today: NaiveDate) -> Result<UsageStats, String> {
+    let first_day = match range_days(range)? {
+        Some(days) => today - ChronoDuration::days(days - 1),
+        None => store
+            .days
+            .keys()
+            .next()
+            .and_then(|date| NaiveDate::parse_from_str(date, DATE_FORMAT).ok())
+            .unwrap_or(today)
+            .min(today),
+    };
+
+    let mut totals = DayStats::default();
+    let mut days = Vec::new();
+    let mut day = first_day;
+    while day <= today {
+        let key = day.format(DATE_FORMAT).to_string();
+        let stats = store.days.get(&key).cloned().unwrap_or_default();
+        totals.merge(&stats);
+        days.push(DayUsage {
+            date: key,
+            dictations: stats.dictations,
+            minutes: stats.audio_ms as f64 / 60_000.0,
+            words: stats.words,
+            avg_latency_ms: stats.avg_latency_ms(),
+            top_model: stats.top_model(),
+        });
+        day += ChronoDuration::days(1);
+    }
+
+    Ok(UsageStats {
+        range: range.trim().to_string(),
+        days,
+        dictations: totals.dictations,
+        minutes: totals.audio_ms as f64 / 60_000.0,
+        words: totals.words,
+        avg_latency_ms: totals.avg_latency_ms(),
+        models: totals.models,
+    })
+}
+
+#[tauri::command]
+pub(crate) fn get_usage_stats(app: AppHandle, range: String) -> CommandResult<UsageStats> {
+    let mut guard = store_guard();
+    let store = guard.get_or_insert_with(|| load_store(&app));
+    summarize(store, &range, Local::now().date_naive()).with_code(ErrorCode::InvalidInput)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(model: &str, latency_ms: Option<u64>) -> UsageSample {
+        UsageSample {
+            model: model.to_string(),
+            audio_ms: 30_000,
+            words: 50,
+            latency_ms,
+        }
+    }
+
+    #[test]
+    fn summarize_fills_missing_days_and_averages_latency() {
+        let today = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+        let mut store = UsageStore::default();
+        let day = store.days.entry("2026-03-08".to_string()).or_default();
+        day.record(&sample("large-v3", Some(400)));
+        day.record(&sample("large-v3", Some(800)));
+        day.record(&sample("cloud-openai", None));
+
+        let stats = summarize(&store, "7d", today).unwrap();
+        assert_eq!(stats.days.len(), 7);
+        assert_eq!(stats.days[0].date, "2026-03-04");
+        assert_eq!(stats.dictations, 3);
+        assert_eq!(stats.words, 150);
+        assert!((stats.minutes - 1.5).abs() < f64::EPSILON);
+        assert_eq!(stats.avg_latency_ms, Some(600));
+        assert_eq!(stats.days[4].top_model.as_deref(), Some("large-v3"));
+        assert!(summarize(&store, "forever", today).is_err());
+    }
+}
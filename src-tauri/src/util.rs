@@ -4,6 +4,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use tauri::State;
 use tracing::{error, info, warn};
 
+use crate::errors::CommandResult;
 use crate::state::AppState;
 
 pub(crate) fn now_ms() -> u64 {
@@ -25,7 +26,7 @@ pub(crate) fn log_frontend_event(
     level: String,
     context: String,
     message: String,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     let normalized_context = context.trim();
     let normalized_message = message.trim();
     if normalized_message.is_empty() {
@@ -80,7 +81,6 @@ where
 }
 
 /// Like `spawn_guarded` but with a return value. Returns `None` on panic.
-#[allow(dead_code)]
 pub(crate) fn spawn_guarded_with_result<F, T>(label: &'static str, f: F) -> JoinHandle<Option<T>>
 where
     F: FnOnce() -> T + Send + 'static,
@@ -0,0 +1,220 @@
+//! VAD calibration behind the settings wizard.
+//!
+//! Few users get `vad_threshold_start`/`vad_threshold_sustain` right by hand.
+//! `calibrate_vad` records five seconds of room noise and five seconds of
+//! speech, measures both on the level scale the VAD monitor compares against
+//! and recommends thresholds between the two, plus a `vad_silence_ms` long
+//! enough to ride over the pauses in the speech sample. With `apply` the
+//! recommendation is saved right away.
+
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{info, warn};
+
+use crate::constants::{TARGET_SAMPLE_RATE, VAD_SILENCE_MS_DEFAULT};
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
+use crate::state::{AppState, Settings};
+use crate::transcription::rms_i16;
+
+/// Emitted with "silence", "speech" and "done" so the wizard can prompt.
+pub(crate) const CALIBRATION_PHASE_EVENT: &str = "vad:calibration-phase";
+const PHASE_DURATION: Duration = Duration::from_secs(5);
+/// 20 ms frames, about the size of a capture callback.
+const FRAME_SAMPLES: usize = TARGET_SAMPLE_RATE as usize / 50;
+const FRAME_MS: u64 = 20;
+/// Speech must be at least this much louder than the noise floor.
+const MIN_SPEECH_TO_NOISE: f32 = 2.0;
+/// Added on top of the longest pause inside the speech sample.
+const SILENCE_MARGIN_MS: u64 = 300;
+const SILENCE_MS_MIN: u64 = 500;
+const SILENCE_MS_MAX: u64 = 2_000;
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct VadCalibration {
+    /// 95th percentile level of the quiet recording (VAD scale, 0..1).
+    pub(crate) noise_floor: f32,
+    pub(crate) noise_peak: f32,
+    /// Typical level of the spoken recording.
+    pub(crate) speech_level: f32,
+    pub(crate) threshold_start: f32,
+    pub(crate) threshold_sustain: f32,
+    pub(crate) silence_ms: u64,
+    /// Set when speech was not clearly louder than the room; never applied.
+    pub(crate) warning: Option<String>,
+    pub(crate) applied: bool,
+}
+
+/// Per-frame level, scaled like the capture callback's meter.
+fn frame_levels(samples: &[i16]) -> Vec<f32> {
+    samples
+        .chunks(FRAME_SAMPLES)
+        .map(|frame| (rms_i16(frame) * 2.5).min(1.0))
+        .collect()
+}
+
+fn percentile(levels: &[f32], fraction: f32) -> f32 {
+    if levels.is_empty() {
+        return 0.0;
+    }
+    let mut sorted = levels.to_vec();
+    sorted.sort_by(f32::total_cmp);
+    let index = ((sorted.len() - 1) as f32 * fraction).round() as usize;
+    sorted[index.min(sorted.len() - 1)]
+}
+
+/// Longest run below `sustain` between the first and last frame at `start`.
+fn longest_pause_ms(levels: &[f32], start: f32, sustain: f32) -> Option<u64> {
+    let first = levels.iter().position(|&level| level >= start)?;
+    let last = levels.iter().rposition(|&level| level >= start)?;
+    let mut longest = 0u64;
+    let mut run = 0u64;
+    for &level in &levels[first..=last] {
+        if level < sustain {
+            run += 1;
+            longest = longest.max(run);
+        } else {
+            run = 0;
+        }
+    }
+    Some(longest * FRAME_MS)
+}
+
+fn recommend(silence: &[i16], speech: &[i16]) -> VadCalibration {
+    let noise = frame_levels(silence);
+    let voice = frame_levels(speech);
+    let noise_floor = percentile(&noise, 0.95);
+    let noise_peak = percentile(&noise, 1.0);
+    let speech_level = percentile(&voice, 0.75);
+
+    let threshold_start = (noise_floor + (speech_level - noise_floor) * 0.25).clamp(0.001, 0.5);
+    let threshold_sustain =
+        (noise_floor + (threshold_start - noise_floor) * 0.5).clamp(0.001, threshold_start);
+    let silence_ms = longest_pause_ms(&voice, threshold_start, threshold_sustain)
+        .map(|pause| (pause + SILENCE_MARGIN_MS).clamp(SILENCE_MS_MIN, SILENCE_MS_MAX))
+        .unwrap_or(VAD_SILENCE_MS_DEFAULT);
+
+    let warning = (speech_level < noise_floor.max(0.001) * MIN_SPEECH_TO_NOISE).then(|| {
+        "Speech was not clearly louder than the background noise. Move closer to the \
+         microphone or raise the input gain, then try again."
+            .to_string()
+    });
+
+    VadCalibration {
+        noise_floor,
+        noise_peak,
+        speech_level,
+        threshold_start,
+        threshold_sustain,
+        silence_ms,
+        warning,
+        applied: false,
+    }
+}
+
+fn current_settings(app: &AppHandle) -> Settings {
+    app.state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+/// Records the silence and speech phases on one mic stream.
+fn record_phases(app: &AppHandle, settings: &Settings) -> Result<(Vec<i16>, Vec<i16>), String> {
+    if crate::privacy_mute::is_muted() {
+        return Err("Privacy mute is on; release it to calibrate the microphone".to_string());
+    }
+    let capture = crate::audio::open_mic_capture(settings)?;
+    let take = || {
+        capture
+            .buffer
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .drain()
+    };
+
+    let _ = app.emit(CALIBRATION_PHASE_EVENT, "silence");
+    std::thread::sleep(PHASE_DURATION);
+    let silence = take();
+    let _ = app.emit(CALIBRATION_PHASE_EVENT, "speech");
+    std::thread::sleep(PHASE_DURATION);
+    let speech = take();
+    let _ = app.emit(CALIBRATION_PHASE_EVENT, "done");
+
+    if capture.stream_lost() {
+        return Err("Input device was disconnected during calibration".to_string());
+    }
+    if silence.is_empty() || speech.is_empty() {
+        return Err("The microphone delivered no audio".to_string());
+    }
+    Ok((silence, speech))
+}
+
+/// Measures room noise and speech and recommends VAD settings; `apply` saves
+/// them unless the measurement was inconclusive.
+#[tauri::command]
+pub(crate) async fn calibrate_vad(
+    app: AppHandle,
+    apply: Option<bool>,
+) -> CommandResult<VadCalibration> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let (silence, speech) =
+            record_phases(&app, &current_settings(&app)).with_code(ErrorCode::AudioDevice)?;
+        let mut calibration = recommend(&silence, &speech);
+        info!(
+            "VAD calibration: noise={:.4} (peak {:.4}), speech={:.4} -> start={:.4}, sustain={:.4}, silence={} ms",
+            calibration.noise_floor,
+            calibration.noise_peak,
+            calibration.speech_level,
+            calibration.threshold_start,
+            calibration.threshold_sustain,
+            calibration.silence_ms
+        );
+        if let Some(warning) = calibration.warning.as_deref() {
+            warn!("VAD calibration inconclusive: {}", warning);
+        } else if apply.unwrap_or(false) {
+            let mut settings = current_settings(&app);
+            settings.vad_threshold_start = calibration.threshold_start;
+            settings.vad_threshold_sustain = calibration.threshold_sustain;
+            settings.vad_silence_ms = calibration.silence_ms;
+            crate::save_settings_inner(&app, &mut settings).with_code(ErrorCode::Storage)?;
+            calibration.applied = true;
+        }
+        Ok(calibration)
+    })
+    .await
+    .map_err(|e| {
+        CommandError::new(ErrorCode::Internal, format!("calibrate_vad task failed: {}", e))
+    })?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(amplitude: i16, frames: usize) -> Vec<i16> {
+        (0..frames * FRAME_SAMPLES)
+            .map(|i| if i % 2 == 0 { amplitude } else { -amplitude })
+            .collect()
+    }
+
+    #[test]
+    fn thresholds_sit_between_noise_and_speech() {
+        let silence = tone(100, 250);
+        let mut speech = tone(3_000, 100);
+        speech.extend(tone(100, 20)); // 400 ms pause mid-sentence
+        speech.extend(tone(3_000, 130));
+
+        let calibration = recommend(&silence, &speech);
+        assert!(calibration.warning.is_none());
+        assert!(calibration.noise_floor < calibration.threshold_sustain);
+        assert!(calibration.threshold_sustain < calibration.threshold_start);
+        assert!(calibration.threshold_start < calibration.speech_level);
+        assert_eq!(calibration.silence_ms, 700);
+
+        let mumbled = recommend(&silence, &tone(150, 250));
+        assert!(mumbled.warning.is_some());
+    }
+}
@@ -9,6 +9,7 @@ use std::thread;
 use tauri::{AppHandle, Emitter, Manager};
 use tracing::{error, info, warn};
 
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
 use crate::modules::{registry, VideoGenerationSettings};
 use crate::paths;
 use crate::state::AppState;
@@ -59,43 +60,48 @@ static RENDER_LOCK: Mutex<()> = Mutex::new(());
 /// Render a video end-to-end. Caller is responsible for running this on a
 /// background thread (not a Tauri command-pool thread) because it blocks for
 /// minutes on the Node sidecar.
-pub fn render_video(app: &AppHandle, mut req: VideoJobRequest) -> Result<VideoJobResult, String> {
-    let _guard = RENDER_LOCK
-        .lock()
-        .map_err(|e| format!("render lock poisoned: {}", e))?;
+pub fn render_video(app: &AppHandle, mut req: VideoJobRequest) -> CommandResult<VideoJobResult> {
+    let _guard = RENDER_LOCK.lock().map_err(|e| {
+        CommandError::new(ErrorCode::Internal, format!("render lock poisoned: {}", e))
+    })?;
     let started = std::time::Instant::now();
 
-    validate_request(&req)?;
+    validate_request(&req).with_code(ErrorCode::InvalidInput)?;
 
-    let (settings, module_enabled) = snapshot_settings(app)?;
+    let (settings, module_enabled) = snapshot_settings(app).with_code(ErrorCode::Internal)?;
     if !module_enabled {
-        return Err(
-            "Video Generation module is not enabled. Open the Modules tab and enable it first."
-                .to_string(),
-        );
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "Video Generation module is not enabled. Open the Modules tab and enable it first.",
+        ));
     }
     let _ = settings.enabled; // legacy field kept for forward-compat, no longer gates rendering
 
-    let workdir = JobWorkdir::create(app)?;
+    let workdir = JobWorkdir::create(app).with_code(ErrorCode::Storage)?;
     emit_progress(app, &workdir.job_id, "starting", 0.0, None);
 
     let cleanup_on_drop = CleanupGuard::new(&workdir);
 
     emit_progress(app, &workdir.job_id, "materialising_assets", 0.05, None);
-    video_ingest::materialize_assets(&mut req.source_items, &workdir)?;
+    video_ingest::materialize_assets(&mut req.source_items, &workdir)
+        .with_code(ErrorCode::Storage)?;
 
     emit_progress(app, &workdir.job_id, "composing", 0.1, None);
-    compose_hyperframes_project(&req, &workdir)?;
+    compose_hyperframes_project(&req, &workdir).with_code(ErrorCode::Storage)?;
 
     emit_progress(app, &workdir.job_id, "rendering", 0.2, None);
     let output_path = resolve_output_path(app, &settings, &workdir);
-    run_hyperframes_render(app, &settings, &workdir, &output_path, req.fps)?;
+    run_hyperframes_render(app, &settings, &workdir, &output_path, req.fps)
+        .with_code(ErrorCode::Internal)?;
 
     emit_progress(app, &workdir.job_id, "finalizing", 0.95, None);
     if !output_path.exists() {
-        return Err(format!(
-            "Render finished but output file not found at {:?}",
-            output_path
+        return Err(CommandError::new(
+            ErrorCode::Internal,
+            format!(
+                "Render finished but output file not found at {:?}",
+                output_path
+            ),
         ));
     }
 
@@ -566,34 +572,40 @@ fn emit_progress(app: &AppHandle, job_id: &str, phase: &str, progress: f32, mess
 pub(crate) async fn video_generate(
     request: VideoJobRequest,
     app: AppHandle,
-) -> Result<VideoJobResult, String> {
+) -> CommandResult<VideoJobResult> {
     tauri::async_runtime::spawn_blocking(move || render_video(&app, request))
         .await
-        .map_err(|e| format!("video_generate join error: {}", e))?
+        .map_err(|e| {
+            CommandError::new(
+                ErrorCode::Internal,
+                format!("video_generate join error: {}", e),
+            )
+        })?
 }
 
 #[tauri::command]
-pub(crate) fn video_get_output_dir(app: AppHandle) -> Result<String, String> {
+pub(crate) fn video_get_output_dir(app: AppHandle) -> CommandResult<String> {
     let dir = crate::paths::resolve_video_output_dir(&app);
     Ok(dir.to_string_lossy().to_string())
 }
 
 #[tauri::command]
-pub(crate) fn video_open_output_dir(app: AppHandle) -> Result<(), String> {
+pub(crate) fn video_open_output_dir(app: AppHandle) -> CommandResult<()> {
     let dir = crate::paths::resolve_video_output_dir(&app);
     #[cfg(target_os = "windows")]
     {
         std::process::Command::new("explorer")
             .arg(&dir)
             .spawn()
-            .map_err(|e| format!("open explorer: {}", e))?;
+            .map_err(|e| CommandError::new(ErrorCode::Window, format!("open explorer: {}", e)))?;
     }
     #[cfg(not(target_os = "windows"))]
     {
         let _ = dir;
-        return Err(
-            "Opening the output directory is only wired for Windows in Phase 1.".to_string(),
-        );
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "Opening the output directory is only wired for Windows in Phase 1.",
+        ));
     }
     Ok(())
 }
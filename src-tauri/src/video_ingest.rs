@@ -5,6 +5,7 @@ use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
 use tracing::{debug, warn};
 
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
 use crate::history_partition::PartitionedHistory;
 use crate::paths;
 use crate::state::{AppState, HistoryEntry};
@@ -326,7 +327,7 @@ pub fn ingest_history_entry(
 pub(crate) async fn video_ingest_sources(
     paths: Vec<String>,
     app: AppHandle,
-) -> Result<Vec<SourceItem>, String> {
+) -> CommandResult<Vec<SourceItem>> {
     tauri::async_runtime::spawn_blocking(move || {
         let state = app.state::<AppState>();
         let max_mb = state
@@ -335,8 +336,9 @@ pub(crate) async fn video_ingest_sources(
             .map(|settings| settings.video_generation_settings.max_upload_mb)
             .unwrap_or(500);
 
-        let workdir =
-            JobWorkdir::create(&app).map_err(|e| format!("prepare ingest workdir: {}", e))?;
+        let workdir = JobWorkdir::create(&app).map_err(|e| {
+            CommandError::new(ErrorCode::Storage, format!("prepare ingest workdir: {}", e))
+        })?;
         let outcome = ingest_dropped_paths(
             paths.into_iter().map(PathBuf::from).collect(),
             &workdir,
@@ -346,7 +348,10 @@ pub(crate) async fn video_ingest_sources(
         workdir.cleanup();
 
         if outcome.items.is_empty() && !outcome.errors.is_empty() {
-            return Err(outcome.errors.join("; "));
+            return Err(CommandError::new(
+                ErrorCode::InvalidInput,
+                outcome.errors.join("; "),
+            ));
         }
         if !outcome.errors.is_empty() {
             warn!(
@@ -359,20 +364,25 @@ pub(crate) async fn video_ingest_sources(
         Ok(outcome.items)
     })
     .await
-    .map_err(|e| format!("ingest join error: {}", e))?
+    .map_err(|e| CommandError::new(ErrorCode::Internal, format!("ingest join error: {}", e)))?
 }
 
 #[tauri::command]
 pub(crate) async fn video_ingest_history_entry(
     entry_id: String,
     app: AppHandle,
-) -> Result<SourceItem, String> {
+) -> CommandResult<SourceItem> {
     tauri::async_runtime::spawn_blocking(move || {
         let state = app.state::<AppState>();
-        ingest_history_entry(&entry_id, state.inner(), 0)
+        ingest_history_entry(&entry_id, state.inner(), 0).with_code(ErrorCode::NotFound)
     })
     .await
-    .map_err(|e| format!("history ingest join error: {}", e))?
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("history ingest join error: {}", e),
+        )
+    })?
 }
 
 fn find_history_entry(history: &PartitionedHistory, entry_id: &str) -> Option<HistoryEntry> {
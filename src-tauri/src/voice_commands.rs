@@ -0,0 +1,320 @@
+//! Spoken editing commands for dictation.
+//!
+//! Runs after transcription and before post-processing on mic transcripts.
+//! Phrases like "new line" or "comma" become text edits in place; an
+//! utterance that consists only of "undo that" / "delete that" turns into an
+//! undo keystroke instead of a paste. System-audio transcripts never pass
+//! through here — a podcast saying "undo that" must not edit the user's text.
+//!
+//! Spoken punctuation ("comma", "period", "punkt", ...) is a separate opt-in:
+//! those words are ordinary vocabulary too ("the trial period ended").
+
+use enigo::{Enigo, Key};
+use serde::{Deserialize, Serialize};
+
+use crate::state::Settings;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum VoiceCommandAction {
+    NewLine,
+    NewParagraph,
+    Comma,
+    Period,
+    QuestionMark,
+    ExclamationMark,
+    Colon,
+    /// Upper-case every following word of the utterance.
+    AllCaps,
+    /// Remove the text spoken so far in this utterance, or undo the previous
+    /// paste when nothing precedes the command.
+    DeleteThat,
+    /// Send the platform undo shortcut.
+    UndoThat,
+    /// Insert the phrase's `text` verbatim (user-defined phrases only).
+    InsertText,
+}
+
+/// A user-defined phrase. Mirrors `src/types.ts::VoiceCommandPhrase`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct VoiceCommandPhrase {
+    pub(crate) phrase: String,
+    pub(crate) action: VoiceCommandAction,
+    /// Replacement text for `insert_text`; ignored by other actions.
+    #[serde(default)]
+    pub(crate) text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum VoiceKeystroke {
+    Undo,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct InterpretedUtterance {
+    /// Text to paste after command phrases were applied.
+    pub(crate) text: String,
+    /// Keystrokes to send instead of (or before) pasting `text`.
+    pub(crate) keystrokes: Vec<VoiceKeystroke>,
+    /// Number of command phrases that matched.
+    pub(crate) commands_applied: usize,
+}
+
+const BUILTIN_PHRASES: &[(&str, VoiceCommandAction)] = &[
+    ("new line", VoiceCommandAction::NewLine),
+    ("newline", VoiceCommandAction::NewLine),
+    ("neue zeile", VoiceCommandAction::NewLine),
+    ("new paragraph", VoiceCommandAction::NewParagraph),
+    ("neuer absatz", VoiceCommandAction::NewParagraph),
+    ("comma", VoiceCommandAction::Comma),
+    ("komma", VoiceCommandAction::Comma),
+    ("period", VoiceCommandAction::Period),
+    ("full stop", VoiceCommandAction::Period),
+    ("punkt", VoiceCommandAction::Period),
+    ("question mark", VoiceCommandAction::QuestionMark),
+    ("fragezeichen", VoiceCommandAction::QuestionMark),
+    ("exclamation mark", VoiceCommandAction::ExclamationMark),
+    ("exclamation point", VoiceCommandAction::ExclamationMark),
+    ("ausrufezeichen", VoiceCommandAction::ExclamationMark),
+    ("colon", VoiceCommandAction::Colon),
+    ("doppelpunkt", VoiceCommandAction::Colon),
+    ("all caps", VoiceCommandAction::AllCaps),
+    ("delete that", VoiceCommandAction::DeleteThat),
+    ("scratch that", VoiceCommandAction::DeleteThat),
+    ("lösch das", VoiceCommandAction::DeleteThat),
+    ("undo that", VoiceCommandAction::UndoThat),
+    ("rückgängig", VoiceCommandAction::UndoThat),
+];
+
+impl VoiceCommandAction {
+    fn is_punctuation(self) -> bool {
+        matches!(
+            self,
+            Self::Comma | Self::Period | Self::QuestionMark | Self::ExclamationMark | Self::Colon
+        )
+    }
+}
+
+struct CompiledPhrase {
+    words: Vec<String>,
+    action: VoiceCommandAction,
+    text: String,
+}
+
+/// Lowercase and strip the punctuation whisper attaches to spoken words.
+fn normalize_word(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
+
+fn compile_phrases(custom: &[VoiceCommandPhrase], spoken_punctuation: bool) -> Vec<CompiledPhrase> {
+    let mut phrases: Vec<CompiledPhrase> = custom
+        .iter()
+        .map(|p| CompiledPhrase {
+            words: p.phrase.split_whitespace().map(normalize_word).collect(),
+            action: p.action,
+            text: p.text.clone(),
+        })
+        .chain(
+            BUILTIN_PHRASES
+                .iter()
+                .filter(|(_, action)| spoken_punctuation || !action.is_punctuation())
+                .map(|(phrase, action)| CompiledPhrase {
+                    words: phrase.split_whitespace().map(normalize_word).collect(),
+                    action: *action,
+                    text: String::new(),
+                }),
+        )
+        .filter(|p| !p.words.is_empty() && p.words.iter().all(|w| !w.is_empty()))
+        .collect();
+    // Longest phrase wins; the stable sort keeps user phrases ahead of
+    // built-ins of the same length so they can override them.
+    phrases.sort_by_key(|p| std::cmp::Reverse(p.words.len()));
+    phrases
+}
+
+fn trim_trailing(out: &mut String, chars: &[char]) {
+    while out.ends_with(chars) {
+        out.pop();
+    }
+}
+
+fn push_punctuation(out: &mut String, mark: char) {
+    trim_trailing(out, &[' ', ',', '.', ';', ':', '!', '?']);
+    out.push(mark);
+}
+
+fn push_break(out: &mut String, breaks: &str) {
+    trim_trailing(out, &[' ', ',']);
+    out.push_str(breaks);
+}
+
+fn push_word(out: &mut String, word: &str) {
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push(' ');
+    }
+    out.push_str(word);
+}
+
+/// Apply spoken commands to a transcript. Built-in punctuation phrases only
+/// apply with `spoken_punctuation`; user phrases always do.
+pub(crate) fn interpret(
+    text: &str,
+    custom: &[VoiceCommandPhrase],
+    spoken_punctuation: bool,
+) -> InterpretedUtterance {
+    let phrases = compile_phrases(custom, spoken_punctuation);
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let normalized: Vec<String> = words.iter().map(|w| normalize_word(w)).collect();
+
+    let mut result = InterpretedUtterance::default();
+    let mut out = String::new();
+    let mut all_caps = false;
+    let mut i = 0;
+
+    while i < words.len() {
+        let matched = phrases.iter().find(|p| {
+            i + p.words.len() <= normalized.len() && normalized[i..i + p.words.len()] == p.words[..]
+        });
+        let Some(phrase) = matched else {
+            if all_caps {
+                push_word(&mut out, &words[i].to_uppercase());
+            } else {
+                push_word(&mut out, words[i]);
+            }
+            i += 1;
+            continue;
+        };
+
+        i += phrase.words.len();
+        result.commands_applied += 1;
+        match phrase.action {
+            VoiceCommandAction::NewLine => push_break(&mut out, "\n"),
+            VoiceCommandAction::NewParagraph => push_break(&mut out, "\n\n"),
+            VoiceCommandAction::Comma => push_punctuation(&mut out, ','),
+            VoiceCommandAction::Period => push_punctuation(&mut out, '.'),
+            VoiceCommandAction::QuestionMark => push_punctuation(&mut out, '?'),
+            VoiceCommandAction::ExclamationMark => push_punctuation(&mut out, '!'),
+            VoiceCommandAction::Colon => push_punctuation(&mut out, ':'),
+            VoiceCommandAction::AllCaps => all_caps = true,
+            VoiceCommandAction::DeleteThat => {
+                if out.trim().is_empty() {
+                    result.keystrokes.push(VoiceKeystroke::Undo);
+                }
+                out.clear();
+            }
+            VoiceCommandAction::UndoThat => result.keystrokes.push(VoiceKeystroke::Undo),
+            VoiceCommandAction::InsertText => push_word(&mut out, &phrase.text),
+        }
+    }
+
+    result.text = if result.commands_applied == 0 {
+        text.to_string()
+    } else {
+        // Whisper's own sentence punctuation on a trailing command word
+        // ("Hello new line.") would otherwise leave a stray space.
+        out.trim_end_matches(' ').to_string()
+    };
+    result
+}
+
+/// Interpret a mic transcript when voice commands are enabled.
+pub(crate) fn interpret_for_settings(
+    text: &str,
+    settings: &Settings,
+) -> Option<InterpretedUtterance> {
+    if !settings.voice_commands_enabled {
+        return None;
+    }
+    let interpreted = interpret(
+        text,
+        &settings.voice_commands_custom,
+        settings.voice_commands_punctuation,
+    );
+    (interpreted.commands_applied > 0).then_some(interpreted)
+}
+
+pub(crate) fn send_keystrokes(keystrokes: &[VoiceKeystroke]) {
+    if keystrokes.is_empty() {
+        return;
+    }
+    let modifier = if cfg!(target_os = "macos") {
+        Key::Meta
+    } else {
+        Key::Control
+    };
+    let mut enigo = Enigo::new();
+    for keystroke in keystrokes {
+        match keystroke {
+            VoiceKeystroke::Undo => {
+                crate::keyboard_layout::send_shortcut(&mut enigo, modifier, 'z');
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_text_passes_through_unchanged() {
+        let result = interpret("Hello world.", &[], true);
+        assert_eq!(result.text, "Hello world.");
+        assert_eq!(result.commands_applied, 0);
+        assert!(result.keystrokes.is_empty());
+    }
+
+    #[test]
+    fn punctuation_and_line_breaks_replace_phrases() {
+        let result = interpret("Hello, comma, world. New line. Second line", &[], true);
+        assert_eq!(result.text, "Hello, world.\nSecond line");
+        let result = interpret("first new paragraph second question mark", &[], true);
+        assert_eq!(result.text, "first\n\nsecond?");
+    }
+
+    #[test]
+    fn spoken_punctuation_is_opt_in() {
+        let result = interpret("The trial period ended, new line thanks", &[], false);
+        assert_eq!(result.text, "The trial period ended\nthanks");
+        let result = interpret("Der Punkt ist wichtig", &[], false);
+        assert_eq!(result.commands_applied, 0);
+    }
+
+    #[test]
+    fn delete_that_clears_utterance_or_undoes() {
+        let result = interpret("wrong words delete that right words", &[], false);
+        assert_eq!(result.text, "right words");
+        assert!(result.keystrokes.is_empty());
+
+        let result = interpret("Delete that.", &[], false);
+        assert_eq!(result.text, "");
+        assert_eq!(result.keystrokes, vec![VoiceKeystroke::Undo]);
+    }
+
+    #[test]
+    fn all_caps_uppercases_following_words() {
+        let result = interpret("this is all caps very important", &[], false);
+        assert_eq!(result.text, "this is VERY IMPORTANT");
+    }
+
+    #[test]
+    fn custom_phrases_insert_text_and_override_builtins() {
+        let custom = vec![
+            VoiceCommandPhrase {
+                phrase: "smiley face".to_string(),
+                action: VoiceCommandAction::InsertText,
+                text: ":)".to_string(),
+            },
+            VoiceCommandPhrase {
+                phrase: "period".to_string(),
+                action: VoiceCommandAction::InsertText,
+                text: "period".to_string(),
+            },
+        ];
+        let result = interpret("nice smiley face", &custom, true);
+        assert_eq!(result.text, "nice :)");
+        let result = interpret("grace period", &custom, true);
+        assert_eq!(result.text, "grace period");
+    }
+}
@@ -0,0 +1,606 @@
+//! Wake-word activation, the third capture mode next to "ptt" and "vad".
+//!
+//! In `wakeword` mode the VAD monitor runs as usual, but its start logic only
+//! sees audio after an openWakeWord model fired: detection arms the monitor
+//! for `WAKE_ARM_WINDOW_MS`, and the next utterance is recorded and finalized
+//! on silence exactly like VAD mode.
+//!
+//! openWakeWord runs three ONNX graphs: a shared melspectrogram front end, a
+//! shared speech-embedding model and a small per-phrase classifier. Inference
+//! runs on a detector thread; the audio callback only resamples and enqueues.
+//!
+//! CPU budget: the classifier threads are capped by `wake_word_threads`, and
+//! while the room is quiet (`wake_word_energy_gate`) audio is only buffered.
+//! The buffered context is processed in one go when sound returns, so the
+//! gate saves CPU without cutting off the start of the phrase.
+
+use ort::session::Session;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{info, warn};
+
+use crate::audio::CaptureBuffer;
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
+use crate::state::{AppState, Settings};
+
+const RELEASE_BASE_URL: &str = "https://github.com/dscripka/openWakeWord/releases/download/v0.5.1";
+/// Subdirectory of the models directory holding all wake-word files.
+const WAKE_WORD_DIR: &str = "wakeword";
+const MELSPEC_FILE: &str = "melspectrogram.onnx";
+const EMBEDDING_FILE: &str = "embedding_model.onnx";
+const MAX_WAKE_MODEL_BYTES: u64 = 32 * 1024 * 1024;
+
+/// 80 ms at 16 kHz: openWakeWord's native step.
+const CHUNK_SAMPLES: usize = 1280;
+/// Extra samples in front of each chunk so mel frames line up across chunks.
+const MEL_CONTEXT_SAMPLES: usize = 480;
+const MEL_BINS: usize = 32;
+/// Mel frames per embedding window (~775 ms).
+const MEL_WINDOW_FRAMES: usize = 76;
+const EMBEDDING_DIM: usize = 96;
+/// Classifier input length when the model does not declare one.
+const DEFAULT_CLASSIFIER_FRAMES: usize = 16;
+
+/// How long a detection keeps the monitor open for the following utterance.
+const WAKE_ARM_WINDOW_MS: u64 = 4_000;
+/// Minimum gap between two detections.
+const WAKE_COOLDOWN_MS: u64 = 2_000;
+/// Quiet chunks (~1 s) after which the energy gate stops inference.
+const GATE_HANG_CHUNKS: usize = 12;
+/// Context kept while gated; must cover mel window + classifier frames.
+const GATE_BACKLOG_CHUNKS: usize = 30;
+/// Resampled callback buffers queued for the detector (~4 s); older audio is dropped.
+const DETECTOR_QUEUE_CAPACITY: usize = 400;
+/// Consecutive inference errors before the detector gives up.
+const MAX_INFERENCE_ERRORS: u32 = 20;
+
+struct WakeWordModelSpec {
+    id: &'static str,
+    label: &'static str,
+    file_name: &'static str,
+}
+
+/// Pretrained openWakeWord phrases. A custom phrase (e.g. "Hey Trispr")
+/// trained with openWakeWord's notebook is used by setting the model to the
+/// absolute path of its `.onnx` file.
+const WAKE_WORD_MODELS: &[WakeWordModelSpec] = &[
+    WakeWordModelSpec {
+        id: "hey_jarvis",
+        label: "Hey Jarvis",
+        file_name: "hey_jarvis_v0.1.onnx",
+    },
+    WakeWordModelSpec {
+        id: "hey_mycroft",
+        label: "Hey Mycroft",
+        file_name: "hey_mycroft_v0.1.onnx",
+    },
+    WakeWordModelSpec {
+        id: "hey_rhasspy",
+        label: "Hey Rhasspy",
+        file_name: "hey_rhasspy_v0.1.onnx",
+    },
+    WakeWordModelSpec {
+        id: "alexa",
+        label: "Alexa",
+        file_name: "alexa_v0.1.onnx",
+    },
+];
+
+pub(crate) const DEFAULT_WAKE_WORD_MODEL: &str = "hey_jarvis";
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct WakeWordModelInfo {
+    pub(crate) id: String,
+    pub(crate) label: String,
+    pub(crate) installed: bool,
+    pub(crate) custom: bool,
+}
+
+/// Settings the detector is built from; a change restarts the monitor.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct WakeWordConfig {
+    model: String,
+    sensitivity: f32,
+    threads: usize,
+    energy_gate: f32,
+}
+
+impl WakeWordConfig {
+    pub(crate) fn from_settings(settings: &Settings) -> Self {
+        Self {
+            model: settings.wake_word_model.trim().to_string(),
+            sensitivity: settings.wake_word_sensitivity,
+            threads: settings.wake_word_threads.clamp(1, 4) as usize,
+            energy_gate: settings.wake_word_energy_gate,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct WakeWordDetectedEvent {
+    model: String,
+    score: f32,
+}
+
+fn wake_word_dir(app: &AppHandle) -> PathBuf {
+    let dir = crate::paths::resolve_models_dir(app).join(WAKE_WORD_DIR);
+    let _ = std::fs::create_dir_all(&dir);
+    dir
+}
+
+fn model_spec(id: &str) -> Option<&'static WakeWordModelSpec> {
+    WAKE_WORD_MODELS.iter().find(|spec| spec.id == id)
+}
+
+fn is_custom_model(model: &str) -> bool {
+    model.to_ascii_lowercase().ends_with(".onnx") && Path::new(model).is_absolute()
+}
+
+/// Classifier file for `model`: a catalogue id or a custom `.onnx` path.
+fn classifier_path(app: &AppHandle, model: &str) -> Result<PathBuf, String> {
+    if is_custom_model(model) {
+        return Ok(PathBuf::from(model));
+    }
+    model_spec(model)
+        .map(|spec| wake_word_dir(app).join(spec.file_name))
+        .ok_or_else(|| format!("Unknown wake-word model '{}'", model))
+}
+
+fn model_installed(app: &AppHandle, model: &str) -> bool {
+    let dir = wake_word_dir(app);
+    dir.join(MELSPEC_FILE).exists()
+        && dir.join(EMBEDDING_FILE).exists()
+        && classifier_path(app, model).is_ok_and(|path| path.exists())
+}
+
+/// Wake-word sensitivity (0..1) to classifier score threshold.
+fn score_threshold(sensitivity: f32) -> f32 {
+    (1.0 - sensitivity).clamp(0.1, 0.9)
+}
+
+fn onnx_error(context: &str, err: impl std::fmt::Display) -> String {
+    format!("Wake-word {}: {}", context, err)
+}
+
+fn load_session(path: &Path, threads: usize) -> Result<Session, String> {
+    Session::builder()
+        .and_then(|builder| builder.with_intra_threads(threads))
+        .and_then(|builder| builder.with_inter_threads(1))
+        .and_then(|builder| builder.commit_from_file(path))
+        .map_err(|e| onnx_error(&format!("failed to load '{}'", path.display()), e))
+}
+
+fn run_session(session: &Session, shape: Vec<i64>, data: Vec<f32>) -> Result<Vec<f32>, String> {
+    let input = ort::value::Tensor::from_array((shape, data))
+        .map_err(|e| onnx_error("invalid input tensor", e))?;
+    let inputs = ort::inputs![input].map_err(|e| onnx_error("invalid inputs", e))?;
+    let outputs = session
+        .run(inputs)
+        .map_err(|e| onnx_error("inference failed", e))?;
+    let (_, values) = outputs[0]
+        .try_extract_raw_tensor::<f32>()
+        .map_err(|e| onnx_error("unexpected output", e))?;
+    Ok(values.to_vec())
+}
+
+/// Streaming openWakeWord pipeline over 16 kHz audio in int16 scale.
+struct WakeWordPipeline {
+    melspec: Session,
+    embedding: Session,
+    classifier: Session,
+    classifier_frames: usize,
+    context: Vec<f32>,
+    mel_frames: VecDeque<Vec<f32>>,
+    embeddings: VecDeque<Vec<f32>>,
+}
+
+impl WakeWordPipeline {
+    fn load(dir: &Path, classifier: &Path, threads: usize) -> Result<Self, String> {
+        let classifier = load_session(classifier, threads)?;
+        // Classifier input is [1, frames, 96]; older exports leave it dynamic.
+        let classifier_frames = classifier
+            .inputs
+            .first()
+            .and_then(|input| match &input.input_type {
+                ort::value::ValueType::Tensor { dimensions, .. } => dimensions.get(1).copied(),
+                _ => None,
+            })
+            .filter(|frames| *frames > 0)
+            .map(|frames| frames as usize)
+            .unwrap_or(DEFAULT_CLASSIFIER_FRAMES);
+        Ok(Self {
+            melspec: load_session(&dir.join(MELSPEC_FILE), threads)?,
+            embedding: load_session(&dir.join(EMBEDDING_FILE), threads)?,
+            classifier,
+            classifier_frames,
+            context: Vec::new(),
+            mel_frames: VecDeque::new(),
+            embeddings: VecDeque::new(),
+        })
+    }
+
+    fn reset(&mut self) {
+        self.context.clear();
+        self.mel_frames.clear();
+        self.embeddings.clear();
+    }
+
+    /// Feeds one `CHUNK_SAMPLES` chunk; returns the classifier score once
+    /// enough history has accumulated.
+    fn process_chunk(&mut self, chunk: &[f32]) -> Result<Option<f32>, String> {
+        let mut window = Vec::with_capacity(self.context.len() + chunk.len());
+        window.extend_from_slice(&self.context);
+        window.extend_from_slice(chunk);
+        self.context = window[window.len().saturating_sub(MEL_CONTEXT_SAMPLES)..].to_vec();
+
+        let window_len = window.len() as i64;
+        let mel = run_session(&self.melspec, vec![1, window_len], window)?;
+        for frame in mel.chunks_exact(MEL_BINS) {
+            // Same scaling openWakeWord applies before the embedding model.
+            self.mel_frames
+                .push_back(frame.iter().map(|value| value / 10.0 + 2.0).collect());
+        }
+        while self.mel_frames.len() > MEL_WINDOW_FRAMES {
+            self.mel_frames.pop_front();
+        }
+        if self.mel_frames.len() < MEL_WINDOW_FRAMES {
+            return Ok(None);
+        }
+
+        let mel_window: Vec<f32> = self.mel_frames.iter().flatten().copied().collect();
+        let embedding = run_session(
+            &self.embedding,
+            vec![1, MEL_WINDOW_FRAMES as i64, MEL_BINS as i64, 1],
+            mel_window,
+        )?;
+        if embedding.len() != EMBEDDING_DIM {
+            return Err(format!(
+                "Wake-word embedding has {} values, expected {}",
+                embedding.len(),
+                EMBEDDING_DIM
+            ));
+        }
+        self.embeddings.push_back(embedding);
+        while self.embeddings.len() > self.classifier_frames {
+            self.embeddings.pop_front();
+        }
+        if self.embeddings.len() < self.classifier_frames {
+            return Ok(None);
+        }
+
+        let features: Vec<f32> = self.embeddings.iter().flatten().copied().collect();
+        let scores = run_session(
+            &self.classifier,
+            vec![1, self.classifier_frames as i64, EMBEDDING_DIM as i64],
+            features,
+        )?;
+        Ok(scores.first().copied())
+    }
+}
+
+fn chunk_rms(chunk: &[f32]) -> f32 {
+    if chunk.is_empty() {
+        return 0.0;
+    }
+    let sum: f32 = chunk.iter().map(|sample| sample * sample).sum();
+    (sum / chunk.len() as f32).sqrt() / i16::MAX as f32
+}
+
+/// Energy gate bookkeeping: decides which chunks reach the pipeline.
+struct EnergyGate {
+    threshold: f32,
+    quiet_chunks: usize,
+    backlog: VecDeque<Vec<f32>>,
+}
+
+impl EnergyGate {
+    fn new(threshold: f32) -> Self {
+        Self {
+            threshold,
+            quiet_chunks: 0,
+            backlog: VecDeque::new(),
+        }
+    }
+
+    /// Queues `chunk` and returns the chunks to run now (oldest first), plus
+    /// whether the gate just closed and the pipeline should start fresh.
+    fn admit(&mut self, chunk: Vec<f32>) -> (Vec<Vec<f32>>, bool) {
+        if self.threshold <= 0.0 {
+            return (vec![chunk], false);
+        }
+        if chunk_rms(&chunk) >= self.threshold {
+            self.quiet_chunks = 0;
+        } else {
+            self.quiet_chunks = self.quiet_chunks.saturating_add(1);
+        }
+        self.backlog.push_back(chunk);
+        if self.quiet_chunks <= GATE_HANG_CHUNKS {
+            return (self.backlog.drain(..).collect(), false);
+        }
+        while self.backlog.len() > GATE_BACKLOG_CHUNKS {
+            self.backlog.pop_front();
+        }
+        (Vec::new(), self.quiet_chunks == GATE_HANG_CHUNKS + 1)
+    }
+
+    fn clear(&mut self) {
+        self.backlog.clear();
+    }
+}
+
+/// Arm state shared between the detector thread and the audio callback.
+#[derive(Default)]
+struct WakeArmState {
+    armed_until_ms: AtomicU64,
+}
+
+/// Audio-callback side of the detector; held by the VAD monitor.
+pub(crate) struct WakeWordGate {
+    tx: SyncSender<Vec<i16>>,
+    resampler: Mutex<CaptureBuffer>,
+    arm: Arc<WakeArmState>,
+    dropped: AtomicU64,
+}
+
+impl WakeWordGate {
+    /// Loads the configured model and starts the detector thread. The thread
+    /// exits when the gate (and with it the monitor stream) is dropped.
+    pub(crate) fn start(app: &AppHandle, settings: &Settings) -> Result<Arc<Self>, String> {
+        let WakeWordConfig {
+            model,
+            sensitivity,
+            threads,
+            energy_gate,
+        } = WakeWordConfig::from_settings(settings);
+        if !model_installed(app, &model) {
+            return Err(format!("Wake-word model '{}' is not downloaded yet", model));
+        }
+        let pipeline =
+            WakeWordPipeline::load(&wake_word_dir(app), &classifier_path(app, &model)?, threads)?;
+        let threshold = score_threshold(sensitivity);
+        let gate = EnergyGate::new(energy_gate);
+        let (tx, rx) = sync_channel::<Vec<i16>>(DETECTOR_QUEUE_CAPACITY);
+        let arm = Arc::new(WakeArmState::default());
+
+        info!(
+            "Wake-word detector starting: model={}, threshold={:.2}, threads={}",
+            model, threshold, threads
+        );
+        let app = app.clone();
+        let thread_arm = arm.clone();
+        crate::util::spawn_guarded("wake_word_detector", move || {
+            run_detector(app, model, pipeline, gate, threshold, rx, thread_arm);
+        });
+
+        Ok(Arc::new(Self {
+            tx,
+            resampler: Mutex::new(CaptureBuffer::default()),
+            arm,
+            dropped: AtomicU64::new(0),
+        }))
+    }
+
+    /// Hands callback audio to the detector; never blocks the callback.
+    pub(crate) fn feed(&self, mono: &[f32], sample_rate: u32) {
+        let samples = {
+            let mut resampler = self
+                .resampler
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            resampler.push_samples(mono, sample_rate);
+            resampler.take_all_samples()
+        };
+        if samples.is_empty() {
+            return;
+        }
+        if let Err(TrySendError::Full(_)) = self.tx.try_send(samples) {
+            let dropped = self.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+            if dropped % 100 == 1 {
+                warn!(
+                    "Wake-word detector is falling behind; dropped {} audio buffers",
+                    dropped
+                );
+            }
+        }
+    }
+
+    pub(crate) fn is_armed(&self, now_ms: u64) -> bool {
+        self.arm.armed_until_ms.load(Ordering::Relaxed) > now_ms
+    }
+
+    /// Ends the armed window once the recording it allowed has started.
+    pub(crate) fn disarm(&self) {
+        self.arm.armed_until_ms.store(0, Ordering::Relaxed);
+    }
+}
+
+fn run_detector(
+    app: AppHandle,
+    model: String,
+    mut pipeline: WakeWordPipeline,
+    mut gate: EnergyGate,
+    threshold: f32,
+    rx: Receiver<Vec<i16>>,
+    arm: Arc<WakeArmState>,
+) {
+    let mut pending: Vec<f32> = Vec::with_capacity(CHUNK_SAMPLES * 2);
+    let mut last_detection_ms = 0u64;
+    let mut errors = 0u32;
+
+    for samples in rx {
+        pending.extend(samples.iter().map(|&sample| sample as f32));
+        while pending.len() >= CHUNK_SAMPLES {
+            let chunk: Vec<f32> = pending.drain(..CHUNK_SAMPLES).collect();
+            let (ready, gate_closed) = gate.admit(chunk);
+            if gate_closed {
+                pipeline.reset();
+            }
+            for chunk in ready {
+                let score = match pipeline.process_chunk(&chunk) {
+                    Ok(score) => {
+                        errors = 0;
+                        score
+                    }
+                    Err(err) => {
+                        errors += 1;
+                        warn!("{}", err);
+                        if errors >= MAX_INFERENCE_ERRORS {
+                            crate::emit_error(
+                                &app,
+                                crate::errors::AppError::AudioDevice(
+                                    "Wake-word detection stopped after repeated errors".to_string(),
+                                ),
+                                Some("Wake word"),
+                            );
+                            return;
+                        }
+                        None
+                    }
+                };
+                let now = crate::util::now_ms();
+                let Some(score) = score else {
+                    continue;
+                };
+                if score < threshold || now.saturating_sub(last_detection_ms) < WAKE_COOLDOWN_MS {
+                    continue;
+                }
+                last_detection_ms = now;
+                arm.armed_until_ms
+                    .store(now + WAKE_ARM_WINDOW_MS, Ordering::Relaxed);
+                info!("Wake word '{}' detected (score {:.2})", model, score);
+                let _ = app.emit(
+                    "wakeword:detected",
+                    WakeWordDetectedEvent {
+                        model: model.clone(),
+                        score,
+                    },
+                );
+                // Start clean so the tail of the phrase cannot fire again.
+                pipeline.reset();
+                gate.clear();
+                pending.clear();
+                break;
+            }
+        }
+    }
+    info!("Wake-word detector stopped");
+}
+
+#[tauri::command]
+pub(crate) fn list_wake_word_models(app: AppHandle) -> Vec<WakeWordModelInfo> {
+    let configured = app
+        .state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .wake_word_model
+        .clone();
+    let mut models: Vec<WakeWordModelInfo> = WAKE_WORD_MODELS
+        .iter()
+        .map(|spec| WakeWordModelInfo {
+            id: spec.id.to_string(),
+            label: spec.label.to_string(),
+            installed: model_installed(&app, spec.id),
+            custom: false,
+        })
+        .collect();
+    if is_custom_model(&configured) {
+        let custom = configured;
+        let label = Path::new(&custom)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| custom.clone());
+        models.push(WakeWordModelInfo {
+            installed: model_installed(&app, &custom),
+            id: custom,
+            label,
+            custom: true,
+        });
+    }
+    models
+}
+
+/// Downloads the shared feature models and the classifier for `model_id`.
+#[tauri::command]
+pub(crate) async fn download_wake_word_model(
+    app: AppHandle,
+    model_id: String,
+) -> CommandResult<WakeWordModelInfo> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let dir = wake_word_dir(&app);
+        let mut files = vec![MELSPEC_FILE, EMBEDDING_FILE];
+        let spec = model_spec(&model_id);
+        match spec {
+            Some(spec) => files.push(spec.file_name),
+            None if is_custom_model(&model_id) => {}
+            None => {
+                return Err(CommandError::new(
+                    ErrorCode::ModelNotFound,
+                    format!("Unknown wake-word model '{}'", model_id),
+                ))
+            }
+        }
+        for file in files {
+            let dest = dir.join(file);
+            if dest.exists() {
+                continue;
+            }
+            let url = format!("{}/{}", RELEASE_BASE_URL, file);
+            info!("Downloading wake-word model file {}", file);
+            crate::models::download_auxiliary_file(&url, &dest, MAX_WAKE_MODEL_BYTES)
+                .with_code(ErrorCode::Network)?;
+        }
+        let _ = app.emit("wakeword:model-installed", &model_id);
+        Ok(WakeWordModelInfo {
+            label: spec
+                .map(|spec| spec.label.to_string())
+                .unwrap_or_else(|| model_id.clone()),
+            installed: model_installed(&app, &model_id),
+            custom: spec.is_none(),
+            id: model_id,
+        })
+    })
+    .await
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("download_wake_word_model task failed: {}", e),
+        )
+    })?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sensitivity_maps_to_clamped_threshold() {
+        assert!((score_threshold(0.5) - 0.5).abs() < f32::EPSILON);
+        assert!((score_threshold(1.0) - 0.1).abs() < f32::EPSILON);
+        assert!((score_threshold(0.0) - 0.9).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn energy_gate_buffers_quiet_audio_and_replays_it_on_sound() {
+        let quiet = vec![0.0f32; CHUNK_SAMPLES];
+        let loud = vec![8_000.0f32; CHUNK_SAMPLES];
+        let mut gate = EnergyGate::new(0.01);
+
+        for _ in 0..GATE_HANG_CHUNKS {
+            assert_eq!(gate.admit(quiet.clone()).0.len(), 1);
+        }
+        assert_eq!(gate.admit(quiet.clone()), (Vec::new(), true));
+        for _ in 0..GATE_BACKLOG_CHUNKS * 2 {
+            assert_eq!(gate.admit(quiet.clone()), (Vec::new(), false));
+        }
+        // Sound returns: the buffered context plus the loud chunk run at once.
+        let (ready, closed) = gate.admit(loud);
+        assert!(!closed);
+        assert_eq!(ready.len(), GATE_BACKLOG_CHUNKS + 1);
+    }
+}
@@ -0,0 +1,403 @@
+//! Watch folders: audio/video files dropped into a configured directory are
+//! transcribed automatically and the result is written next to the file as
+//! `<name>.txt` and/or `<name>.srt`.
+//!
+//! A file is processed once: if its `.txt` (or, with only SRT output, its
+//! `.srt`) already exists it is skipped, so restarting the app does not redo
+//! finished work. Files still being copied are waited on until their size
+//! stops changing. Jobs run through `batch_queue`.
+
+use notify::event::{EventKind, ModifyKind};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tracing::{info, warn};
+
+use crate::file_transcription::{FileTranscriptSegment, FileTranscriptionOptions};
+use crate::state::Settings;
+
+pub(crate) const WATCH_EXTENSIONS: [&str; 14] = [
+    "wav", "mp3", "m4a", "aac", "flac", "ogg", "oga", "opus", "wma", "mp4", "m4v", "mkv", "webm",
+    "mov",
+];
+const STABLE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Consecutive polls with an unchanged size before a file counts as complete.
+const STABLE_POLLS: u32 = 2;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct WatchFolder {
+    pub(crate) path: String,
+    pub(crate) enabled: bool,
+    pub(crate) recursive: bool,
+    /// Empty = the global model / language.
+    pub(crate) model: String,
+    pub(crate) language: String,
+    pub(crate) write_txt: bool,
+    pub(crate) write_srt: bool,
+}
+
+impl Default for WatchFolder {
+    fn default() -> Self {
+        Self {
+            path: String::new(),
+            enabled: true,
+            recursive: false,
+            model: String::new(),
+            language: String::new(),
+            write_txt: true,
+            write_srt: false,
+        }
+    }
+}
+
+pub(crate) fn normalize_watch_folders(folders: &mut Vec<WatchFolder>) {
+    for folder in folders.iter_mut() {
+        folder.path = folder.path.trim().to_string();
+        folder.model = folder.model.trim().to_string();
+        folder.language = folder.language.trim().to_lowercase();
+        if !folder.write_txt && !folder.write_srt {
+            folder.write_txt = true;
+        }
+    }
+    folders.retain(|folder| !folder.path.is_empty());
+    let mut seen = HashSet::new();
+    folders.retain(|folder| seen.insert(folder.path.to_lowercase()));
+}
+
+struct WatchJob {
+    path: PathBuf,
+    folder: WatchFolder,
+}
+
+struct ActiveWatchers {
+    folders: Vec<WatchFolder>,
+    _watcher: RecommendedWatcher,
+}
+
+static WATCHERS: Mutex<Option<ActiveWatchers>> = Mutex::new(None);
+/// Files queued or in progress, so repeated events do not queue them twice.
+static PENDING: Mutex<Option<HashSet<PathBuf>>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Serialize)]
+struct WatchFolderResult {
+    path: String,
+    outputs: Vec<String>,
+    error: Option<String>,
+}
+
+fn is_media_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| WATCH_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+fn output_path(path: &Path, extension: &str) -> PathBuf {
+    path.with_extension(extension)
+}
+
+fn already_processed(path: &Path, folder: &WatchFolder) -> bool {
+    let marker = if folder.write_txt { "txt" } else { "srt" };
+    output_path(path, marker).exists()
+}
+
+fn srt_timestamp(ms: u64) -> String {
+    format!(
+        "{:02}:{:02}:{:02},{:03}",
+        ms / 3_600_000,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        ms % 1000
+    )
+}
+
+pub(crate) fn render_srt(segments: &[FileTranscriptSegment]) -> String {
+    segments
+        .iter()
+        .enumerate()
+        .map(|(index, segment)| {
+            format!(
+                "{}\n{} --> {}\n{}\n",
+                index + 1,
+                srt_timestamp(segment.start_ms),
+                srt_timestamp(segment.end_ms),
+                segment.text
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Waits until the file size stops changing (the copy has finished).
+fn wait_until_stable(path: &Path) -> bool {
+    let mut last = None;
+    let mut stable = 0;
+    while stable < STABLE_POLLS {
+        std::thread::sleep(STABLE_POLL_INTERVAL);
+        let Ok(size) = std::fs::metadata(path).map(|meta| meta.len()) else {
+            return false;
+        };
+        if size > 0 && last == Some(size) {
+            stable += 1;
+        } else {
+            stable = 0;
+        }
+        last = Some(size);
+    }
+    true
+}
+
+fn process(app: &AppHandle, job: &WatchJob) -> Result<Vec<String>, String> {
+    if !wait_until_stable(&job.path) {
+        return Err("File disappeared before it could be read".to_string());
+    }
+    let options = FileTranscriptionOptions {
+        model: Some(job.folder.model.clone()),
+        language: Some(job.folder.language.clone()),
+        add_to_history: false,
+    };
+    let transcript = crate::file_transcription::transcribe_file_blocking(app, &job.path, &options)?;
+    let mut outputs = Vec::new();
+    if job.folder.write_srt {
+        let srt = output_path(&job.path, "srt");
+        std::fs::write(&srt, render_srt(&transcript.segments))
+            .map_err(|e| format!("Failed to write {}: {}", srt.display(), e))?;
+        outputs.push(srt.to_string_lossy().to_string());
+    }
+    // The .txt doubles as the "done" marker, so write it last.
+    if job.folder.write_txt {
+        let txt = output_path(&job.path, "txt");
+        std::fs::write(&txt, format!("{}\n", transcript.text))
+            .map_err(|e| format!("Failed to write {}: {}", txt.display(), e))?;
+        outputs.push(txt.to_string_lossy().to_string());
+    }
+    Ok(outputs)
+}
+
+fn run_job(app: &AppHandle, job: WatchJob) {
+    let result = process(app, &job);
+    if let Some(pending) = PENDING
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .as_mut()
+    {
+        pending.remove(&job.path);
+    }
+    let path = job.path.to_string_lossy().to_string();
+    let payload = match result {
+        Ok(outputs) => {
+            info!("Watch folder: transcribed {}", path);
+            WatchFolderResult {
+                path,
+                outputs,
+                error: None,
+            }
+        }
+        Err(err) => {
+            warn!("Watch folder: {} failed: {}", path, err);
+            WatchFolderResult {
+                path,
+                outputs: Vec::new(),
+                error: Some(err),
+            }
+        }
+    };
+    let _ = app.emit("watch-folder:processed", payload);
+}
+
+fn enqueue(app: &AppHandle, path: PathBuf, folder: &WatchFolder) {
+    if !is_media_file(&path) || already_processed(&path, folder) {
+        return;
+    }
+    let newly_pending = PENDING
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get_or_insert_with(HashSet::new)
+        .insert(path.clone());
+    if !newly_pending {
+        return;
+    }
+    info!("Watch folder: queued {}", path.display());
+    let job = WatchJob {
+        path,
+        folder: folder.clone(),
+    };
+    let label = job.path.to_string_lossy().to_string();
+    let worker_app = app.clone();
+    crate::batch_queue::submit(app, label, move || run_job(&worker_app, job));
+}
+
+/// Folder config responsible for `path` (the deepest matching folder).
+fn folder_for<'a>(folders: &'a [WatchFolder], path: &Path) -> Option<&'a WatchFolder> {
+    folders
+        .iter()
+        .filter(|folder| {
+            let root = Path::new(&folder.path);
+            if folder.recursive {
+                path.starts_with(root)
+            } else {
+                path.parent() == Some(root)
+            }
+        })
+        .max_by_key(|folder| folder.path.len())
+}
+
+fn handle_event(app: &AppHandle, event: notify::Event) {
+    let relevant = matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(_))
+    );
+    if !relevant {
+        return;
+    }
+    let folders = match WATCHERS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .as_ref()
+    {
+        Some(active) => active.folders.clone(),
+        None => return,
+    };
+    for path in event.paths {
+        if let Some(folder) = folder_for(&folders, &path) {
+            enqueue(app, path.clone(), folder);
+        }
+    }
+}
+
+/// Queues files that arrived while the app was not running.
+fn scan_existing(app: &AppHandle, folder: &WatchFolder) {
+    let mut dirs = vec![PathBuf::from(&folder.path)];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                if folder.recursive {
+                    dirs.push(path);
+                }
+            } else {
+                enqueue(app, path, folder);
+            }
+        }
+    }
+}
+
+/// (Re)starts watching the enabled folders; call on startup and settings save.
+pub(crate) fn sync_watch_folders(app: &AppHandle, settings: &Settings) {
+    let folders: Vec<WatchFolder> = settings
+        .watch_folders
+        .iter()
+        .filter(|folder| folder.enabled)
+        .cloned()
+        .collect();
+    let previous = {
+        let mut active = WATCHERS
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if active
+            .as_ref()
+            .is_some_and(|active| active.folders == folders)
+        {
+            return;
+        }
+        active.take()
+    };
+    // Dropped outside the lock: the watcher thread may be inside `handle_event`.
+    drop(previous);
+    if folders.is_empty() {
+        return;
+    }
+
+    let handler_app = app.clone();
+    let mut watcher =
+        match notify::recommended_watcher(move |result: notify::Result<notify::Event>| match result
+        {
+            Ok(event) => handle_event(&handler_app, event),
+            Err(err) => warn!("Watch folder event error: {}", err),
+        }) {
+            Ok(watcher) => watcher,
+            Err(err) => {
+                warn!("Failed to create folder watcher: {}", err);
+                return;
+            }
+        };
+    let mut watched = Vec::new();
+    for folder in folders {
+        let mode = if folder.recursive {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+        match watcher.watch(Path::new(&folder.path), mode) {
+            Ok(()) => {
+                info!("Watching folder {}", folder.path);
+                watched.push(folder);
+            }
+            Err(err) => warn!("Cannot watch {}: {}", folder.path, err),
+        }
+    }
+    *WATCHERS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(ActiveWatchers {
+        folders: watched.clone(),
+        _watcher: watcher,
+    });
+    for folder in &watched {
+        scan_existing(app, folder);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn srt_output_and_folder_matching() {
+        let segments = vec![
+            FileTranscriptSegment {
+                start_ms: 0,
+                end_ms: 29_500,
+                text: "Hello".to_string(),
+            },
+            FileTranscriptSegment {
+                start_ms: 29_500,
+                end_ms: 3_723_004,
+                text: "World".to_string(),
+            },
+        ];
+        assert_eq!(
+            render_srt(&segments),
+            "1\n00:00:00,000 --> 00:00:29,500\nHello\n\n2\n00:00:29,500 --> 01:02:03,004\nWorld\n"
+        );
+
+        let folders = vec![
+            WatchFolder {
+                path: "/in".to_string(),
+                recursive: true,
+                ..WatchFolder::default()
+            },
+            WatchFolder {
+                path: "/in/de".to_string(),
+                language: "de".to_string(),
+                ..WatchFolder::default()
+            },
+        ];
+        let nested = folder_for(&folders, Path::new("/in/de/a.mp3")).unwrap();
+        assert_eq!(nested.language, "de");
+        assert_eq!(
+            folder_for(&folders, Path::new("/in/x/y/a.mp3"))
+                .unwrap()
+                .path,
+            "/in"
+        );
+        assert!(folder_for(&folders, Path::new("/other/a.mp3")).is_none());
+        assert!(is_media_file(Path::new("talk.M4A")) && !is_media_file(Path::new("a.txt")));
+    }
+}
@@ -0,0 +1,232 @@
+//! Webhooks: JSON POSTs to user-configured URLs for every final transcript
+//! and, optionally, when a transcript session ends.
+//!
+//! Deliveries run on their own threads so a slow endpoint never holds up
+//! dictation. Transport errors, 429 and 5xx responses are retried with
+//! exponential backoff; other 4xx responses are treated as final.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+
+use crate::errors::{CommandError, CommandResult, ErrorCode};
+use crate::session_manager::TranscriptSessionSummary;
+use crate::state::{AppState, HistoryEntry};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_ATTEMPTS: u32 = 5;
+const BASE_BACKOFF: Duration = Duration::from_secs(2);
+
+pub(crate) const WEBHOOK_FILTERS: [&str; 4] = ["all", "mic", "system", "session_end"];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct Webhook {
+    pub(crate) url: String,
+    pub(crate) enabled: bool,
+    /// "all" (every transcript), "mic", "system" or "session_end".
+    pub(crate) filter: String,
+}
+
+impl Default for Webhook {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            enabled: true,
+            filter: "all".to_string(),
+        }
+    }
+}
+
+impl Webhook {
+    fn wants_transcript(&self, source: &str) -> bool {
+        let system = source == "output";
+        self.enabled
+            && match self.filter.as_str() {
+                "all" => true,
+                "mic" => !system,
+                "system" => system,
+                _ => false,
+            }
+    }
+
+    fn wants_session_end(&self) -> bool {
+        self.enabled && self.filter == "session_end"
+    }
+}
+
+pub(crate) fn normalize_webhooks(hooks: &mut Vec<Webhook>) {
+    for hook in hooks.iter_mut() {
+        hook.url = hook.url.trim().to_string();
+        hook.filter = hook.filter.trim().to_lowercase();
+        if !WEBHOOK_FILTERS.contains(&hook.filter.as_str()) {
+            hook.filter = "all".to_string();
+        }
+    }
+    hooks.retain(|hook| hook.url.starts_with("http://") || hook.url.starts_with("https://"));
+}
+
+fn configured_hooks(app: &AppHandle) -> Vec<Webhook> {
+    app.state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .webhooks
+        .clone()
+}
+
+/// Delay before retry `attempt` (1-based): 2 s, 4 s, 8 s, …
+fn backoff(attempt: u32) -> Duration {
+    BASE_BACKOFF * 2u32.saturating_pow(attempt.saturating_sub(1))
+}
+
+fn is_retryable_status(code: u16) -> bool {
+    code == 429 || code >= 500
+}
+
+fn post_with_retry(url: &str, payload: &Value) -> Result<(), String> {
+    let agent = ureq::builder().timeout(REQUEST_TIMEOUT).build();
+    let mut attempt = 1;
+    loop {
+        let error = match agent
+            .post(url)
+            .set(
+                "User-Agent",
+                concat!("TrisprFlow/", env!("CARGO_PKG_VERSION")),
+            )
+            .send_json(payload.clone())
+        {
+            Ok(_) => return Ok(()),
+            Err(ureq::Error::Status(code, _)) if !is_retryable_status(code) => {
+                return Err(format!("HTTP {}", code));
+            }
+            Err(ureq::Error::Status(code, _)) => format!("HTTP {}", code),
+            Err(ureq::Error::Transport(transport)) => transport.to_string(),
+        };
+        if attempt >= MAX_ATTEMPTS {
+            return Err(format!("{} (gave up after {} attempts)", error, attempt));
+        }
+        std::thread::sleep(backoff(attempt));
+        attempt += 1;
+    }
+}
+
+fn deliver(hooks: Vec<Webhook>, payload: Value) {
+    for hook in hooks {
+        let payload = payload.clone();
+        crate::util::spawn_guarded("webhook_delivery", move || {
+            match post_with_retry(&hook.url, &payload) {
+                Ok(()) => info!("Webhook delivered to {}", hook.url),
+                Err(err) => warn!("Webhook to {} failed: {}", hook.url, err),
+            }
+        });
+    }
+}
+
+/// Sends a final transcript to every hook whose filter matches its source.
+pub(crate) fn notify_transcript(app: &AppHandle, entry: &HistoryEntry) {
+    let hooks: Vec<Webhook> = configured_hooks(app)
+        .into_iter()
+        .filter(|hook| hook.wants_transcript(&entry.source))
+        .collect();
+    if hooks.is_empty() {
+        return;
+    }
+    let payload = json!({
+        "type": "transcript",
+        "id": entry.id,
+        "text": entry.text,
+        "source": entry.source,
+        "speaker": entry.speaker_name,
+        "timestamp_ms": entry.timestamp_ms,
+        "session_id": crate::session_manager::active_session_id(),
+    });
+    deliver(hooks, payload);
+}
+
+/// Sends the finished session, including its Markdown transcript.
+pub(crate) fn notify_session_end(app: &AppHandle, summary: &TranscriptSessionSummary) {
+    let hooks: Vec<Webhook> = configured_hooks(app)
+        .into_iter()
+        .filter(Webhook::wants_session_end)
+        .collect();
+    if hooks.is_empty() {
+        return;
+    }
+    let payload = json!({
+        "type": "session_end",
+        "session": summary,
+        "markdown": crate::session_manager::session_markdown(app, &summary.id),
+    });
+    deliver(hooks, payload);
+}
+
+/// Posts a sample payload to `url` once, without retries.
+#[tauri::command]
+pub(crate) async fn test_webhook(url: String) -> CommandResult<()> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let payload = json!({
+            "type": "test",
+            "text": "Trispr Flow webhook test",
+            "timestamp_ms": crate::util::now_ms(),
+        });
+        ureq::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .post(url.trim())
+            .send_json(payload)
+            .map(|_| ())
+            .map_err(|e| {
+                CommandError::new(ErrorCode::Network, format!("Webhook test failed: {}", e))
+            })
+    })
+    .await
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("test_webhook task failed: {}", e),
+        )
+    })?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hook(filter: &str) -> Webhook {
+        Webhook {
+            url: "https://example.com/hook".to_string(),
+            enabled: true,
+            filter: filter.to_string(),
+        }
+    }
+
+    #[test]
+    fn filters_route_by_source_and_backoff_doubles() {
+        assert!(hook("all").wants_transcript("local"));
+        assert!(hook("mic").wants_transcript("local"));
+        assert!(!hook("mic").wants_transcript("output"));
+        assert!(hook("system").wants_transcript("output"));
+        assert!(!hook("session_end").wants_transcript("local"));
+        assert!(hook("session_end").wants_session_end());
+        assert_eq!(backoff(1), Duration::from_secs(2));
+        assert_eq!(backoff(3), Duration::from_secs(8));
+        assert!(is_retryable_status(503) && !is_retryable_status(404));
+    }
+
+    #[test]
+    fn normalization_drops_non_http_urls() {
+        let mut hooks = vec![
+            hook(" MIC "),
+            Webhook {
+                url: "ftp://x".to_string(),
+                ..hook("all")
+            },
+        ];
+        normalize_webhooks(&mut hooks);
+        assert_eq!(hooks.len(), 1);
+        assert_eq!(hooks[0].filter, "mic");
+    }
+}
@@ -0,0 +1,114 @@
+//! Installed whisper.cpp runtimes (CUDA, Vulkan, CPU) and whether each one
+//! actually starts, for the backend picker behind `local_backend_preference`.
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::errors::{CommandError, CommandResult, ErrorCode};
+use crate::paths::{resolve_whisper_cli_path_for_backend, resolve_whisper_server_path_for_backend};
+use crate::state::AppState;
+use crate::transcription::{
+    whisper_backend_from_cli_path, whisper_cli_help_text, whisper_runtime_preflight_issue,
+};
+
+const BACKENDS: [&str; 3] = ["cuda", "vulkan", "cpu"];
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct WhisperBackendInfo {
+    /// "cuda" | "vulkan" | "cpu"
+    pub(crate) id: &'static str,
+    pub(crate) cli_path: Option<String>,
+    pub(crate) server_path: Option<String>,
+    /// Runtime complete and whisper-cli answered the probe.
+    pub(crate) available: bool,
+    /// CPU without a CPU build: a GPU build runs with `--no-gpu`.
+    pub(crate) uses_no_gpu_flag: bool,
+    pub(crate) issue: Option<String>,
+    /// Matches the current `local_backend_preference`.
+    pub(crate) selected: bool,
+}
+
+/// Judges `whisper-cli --help` output; `None` means it did not run.
+fn evaluate_probe(help_text: Option<&str>, needs_no_gpu: bool) -> Result<(), String> {
+    let Some(help_text) = help_text else {
+        return Err("whisper-cli did not start".to_string());
+    };
+    if !help_text.contains("usage") {
+        return Err(
+            "whisper-cli started but printed no usage (missing runtime files?)".to_string(),
+        );
+    }
+    if needs_no_gpu && !help_text.contains("--no-gpu") && !help_text.contains("-ng") {
+        return Err("This whisper-cli build cannot be forced onto the CPU".to_string());
+    }
+    Ok(())
+}
+
+fn probe_backend(id: &'static str, preference: &str) -> WhisperBackendInfo {
+    let cli_path = resolve_whisper_cli_path_for_backend(Some(id));
+    let cli_backend = cli_path.as_deref().map(whisper_backend_from_cli_path);
+    // For GPU ids the resolver may fall through to another backend's build;
+    // that build does not count as this backend.
+    let cli_path = cli_path.filter(|_| id == "cpu" || cli_backend == Some(id));
+    let uses_no_gpu_flag = id == "cpu" && cli_backend.is_some_and(|backend| backend != "cpu");
+    let server_path = resolve_whisper_server_path_for_backend(Some(id))
+        .filter(|path| id == "cpu" || whisper_backend_from_cli_path(path) == id);
+
+    let issue = match &cli_path {
+        None => Some(format!("No whisper-cli build for {} installed", id)),
+        Some(path) => whisper_runtime_preflight_issue(path).or_else(|| {
+            evaluate_probe(whisper_cli_help_text(path).as_deref(), uses_no_gpu_flag).err()
+        }),
+    };
+    WhisperBackendInfo {
+        id,
+        available: issue.is_none(),
+        cli_path: cli_path.map(|path| path.display().to_string()),
+        server_path: server_path.map(|path| path.display().to_string()),
+        uses_no_gpu_flag,
+        issue,
+        selected: preference.eq_ignore_ascii_case(id),
+    }
+}
+
+/// Probe every backend; each probe runs `whisper-cli --help` once.
+#[tauri::command]
+pub(crate) async fn list_whisper_backends(
+    app: AppHandle,
+) -> CommandResult<Vec<WhisperBackendInfo>> {
+    let preference = app
+        .state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .local_backend_preference
+        .clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        BACKENDS
+            .iter()
+            .map(|id| probe_backend(id, &preference))
+            .collect()
+    })
+    .await
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("list_whisper_backends task failed: {}", e),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn probe_needs_usage_and_no_gpu_support_when_forcing_cpu() {
+        let help = "usage: whisper-cli [options] file0 ...\n  -ng, --no-gpu [false] disable gpu";
+        assert!(evaluate_probe(Some(help), true).is_ok());
+        assert!(evaluate_probe(Some("usage: whisper-cli [options]"), false).is_ok());
+        assert!(evaluate_probe(Some("usage: whisper-cli [options]"), true).is_err());
+        assert!(evaluate_probe(Some(""), false).is_err());
+        assert!(evaluate_probe(None, false).is_err());
+    }
+}
@@ -0,0 +1,288 @@
+//! Advanced whisper decoding parameters (beam size, temperature, no-speech
+//! and entropy thresholds) for whisper-cli.
+//!
+//! `whisper_advanced` only stores overrides; anything left unset uses the
+//! defaults for the selected model. Only values that differ from
+//! whisper-cli's own defaults are passed on the command line, so an untouched
+//! setup runs with exactly the arguments it always did. The whisper-server
+//! path sends the overrides as `/inference` form fields on top of its
+//! low-latency defaults (greedy decoding, no temperature fallback).
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
+use crate::state::{AppState, Settings};
+
+const BEAM_SIZE_MAX: u32 = 8;
+const TEMPERATURE_MAX: f32 = 1.0;
+const NO_SPEECH_THRESHOLD_MAX: f32 = 1.0;
+const ENTROPY_THRESHOLD_MAX: f32 = 10.0;
+
+/// Effective decoding parameters for one run.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub(crate) struct WhisperParams {
+    pub(crate) beam_size: u32,
+    pub(crate) temperature: f32,
+    pub(crate) no_speech_threshold: f32,
+    pub(crate) entropy_threshold: f32,
+}
+
+/// whisper-cli's built-in defaults.
+const CLI_DEFAULTS: WhisperParams = WhisperParams {
+    beam_size: 5,
+    temperature: 0.0,
+    no_speech_threshold: 0.6,
+    entropy_threshold: 2.4,
+};
+
+/// Mirrors `src/types.ts::WhisperAdvancedSettings`. `None` uses the model
+/// default.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct WhisperAdvancedSettings {
+    pub(crate) beam_size: Option<u32>,
+    pub(crate) temperature: Option<f32>,
+    pub(crate) no_speech_threshold: Option<f32>,
+    pub(crate) entropy_threshold: Option<f32>,
+}
+
+/// Greedy decoding: what distilled models are trained for (beam search only
+/// costs them time) and what the whisper-server path uses for dictation.
+const GREEDY: WhisperParams = WhisperParams {
+    beam_size: 1,
+    ..CLI_DEFAULTS
+};
+
+/// Per-model whisper-cli defaults for the catalog models (`models.rs`).
+const MODEL_DEFAULTS: &[(&str, WhisperParams)] = &[
+    ("whisper-large-v3", CLI_DEFAULTS),
+    ("whisper-large-v3-turbo", CLI_DEFAULTS),
+    ("whisper-large-v3-turbo-german", CLI_DEFAULTS),
+    ("ggml-distil-large-v3", GREEDY),
+];
+
+/// Defaults for `model`: the catalog entry, else guessed from the name of an
+/// imported model.
+pub(crate) fn model_defaults(model: &str) -> WhisperParams {
+    let model = model.trim().to_lowercase();
+    if let Some((_, params)) = MODEL_DEFAULTS.iter().find(|(id, _)| *id == model) {
+        return *params;
+    }
+    if model.contains("distil") {
+        GREEDY
+    } else {
+        CLI_DEFAULTS
+    }
+}
+
+/// Effective whisper-cli parameters: overrides on top of the model defaults.
+pub(crate) fn resolve(settings: &Settings) -> WhisperParams {
+    apply_overrides(model_defaults(&settings.model), &settings.whisper_advanced)
+}
+
+/// Effective whisper-server parameters: overrides on top of the server's
+/// low-latency defaults.
+pub(crate) fn resolve_for_server(settings: &Settings) -> WhisperParams {
+    apply_overrides(GREEDY, &settings.whisper_advanced)
+}
+
+fn apply_overrides(defaults: WhisperParams, overrides: &WhisperAdvancedSettings) -> WhisperParams {
+    WhisperParams {
+        beam_size: overrides.beam_size.unwrap_or(defaults.beam_size),
+        temperature: overrides.temperature.unwrap_or(defaults.temperature),
+        no_speech_threshold: overrides
+            .no_speech_threshold
+            .unwrap_or(defaults.no_speech_threshold),
+        entropy_threshold: overrides
+            .entropy_threshold
+            .unwrap_or(defaults.entropy_threshold),
+    }
+}
+
+fn check_range(name: &str, value: Option<f32>, max: f32) -> Result<(), String> {
+    match value {
+        Some(value) if !(0.0..=max).contains(&value) => {
+            Err(format!("{} must be between 0 and {}", name, max))
+        }
+        _ => Ok(()),
+    }
+}
+
+pub(crate) fn validate(overrides: &WhisperAdvancedSettings) -> Result<(), String> {
+    if overrides
+        .beam_size
+        .is_some_and(|beam_size| !(1..=BEAM_SIZE_MAX).contains(&beam_size))
+    {
+        return Err(format!("Beam size must be between 1 and {}", BEAM_SIZE_MAX));
+    }
+    check_range("Temperature", overrides.temperature, TEMPERATURE_MAX)?;
+    check_range(
+        "No-speech threshold",
+        overrides.no_speech_threshold,
+        NO_SPEECH_THRESHOLD_MAX,
+    )?;
+    check_range(
+        "Entropy threshold",
+        overrides.entropy_threshold,
+        ENTROPY_THRESHOLD_MAX,
+    )
+}
+
+/// Drops overrides that would not pass `validate` (hand-edited or imported
+/// settings).
+pub(crate) fn normalize_whisper_advanced(overrides: &mut WhisperAdvancedSettings) {
+    let drop_invalid = |value: &mut Option<f32>, max: f32| {
+        if value.is_some_and(|value| !(0.0..=max).contains(&value)) {
+            *value = None;
+        }
+    };
+    if overrides
+        .beam_size
+        .is_some_and(|beam_size| !(1..=BEAM_SIZE_MAX).contains(&beam_size))
+    {
+        overrides.beam_size = None;
+    }
+    drop_invalid(&mut overrides.temperature, TEMPERATURE_MAX);
+    drop_invalid(&mut overrides.no_speech_threshold, NO_SPEECH_THRESHOLD_MAX);
+    drop_invalid(&mut overrides.entropy_threshold, ENTROPY_THRESHOLD_MAX);
+}
+
+/// whisper-cli flags for the values that differ from its built-in defaults.
+pub(crate) fn cli_args(params: &WhisperParams) -> Vec<(&'static str, String)> {
+    let mut args = Vec::new();
+    if params.beam_size != CLI_DEFAULTS.beam_size {
+        args.push(("--beam-size", params.beam_size.to_string()));
+    }
+    if params.temperature != CLI_DEFAULTS.temperature {
+        args.push(("--temperature", params.temperature.to_string()));
+    }
+    if params.no_speech_threshold != CLI_DEFAULTS.no_speech_threshold {
+        args.push(("--no-speech-thold", params.no_speech_threshold.to_string()));
+    }
+    if params.entropy_threshold != CLI_DEFAULTS.entropy_threshold {
+        args.push(("--entropy-thold", params.entropy_threshold.to_string()));
+    }
+    args
+}
+
+/// whisper-server `/inference` form fields for `params`.
+pub(crate) fn server_fields(params: &WhisperParams) -> Vec<(&'static str, String)> {
+    vec![
+        ("beam_size", params.beam_size.to_string()),
+        ("temperature", params.temperature.to_string()),
+        ("no_speech_thold", params.no_speech_threshold.to_string()),
+        ("entropy_thold", params.entropy_threshold.to_string()),
+    ]
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct WhisperParamsInfo {
+    pub(crate) model: String,
+    pub(crate) defaults: WhisperParams,
+    pub(crate) overrides: WhisperAdvancedSettings,
+    pub(crate) effective: WhisperParams,
+    /// What the whisper-server path decodes with.
+    pub(crate) server: WhisperParams,
+}
+
+fn params_info(settings: &Settings) -> WhisperParamsInfo {
+    WhisperParamsInfo {
+        model: settings.model.clone(),
+        defaults: model_defaults(&settings.model),
+        overrides: settings.whisper_advanced.clone(),
+        effective: resolve(settings),
+        server: resolve_for_server(settings),
+    }
+}
+
+fn current_settings(app: &AppHandle) -> Settings {
+    app.state::<AppState>()
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+#[tauri::command]
+pub(crate) fn get_whisper_params(app: AppHandle) -> WhisperParamsInfo {
+    params_info(&current_settings(&app))
+}
+
+/// Replaces the overrides; pass all fields empty to reset to the model
+/// defaults.
+#[tauri::command]
+pub(crate) async fn set_whisper_params(
+    app: AppHandle,
+    overrides: WhisperAdvancedSettings,
+) -> CommandResult<WhisperParamsInfo> {
+    validate(&overrides).map_err(|err| {
+        CommandError::new(ErrorCode::InvalidInput, err)
+            .with_hint("Leave a field empty to use the model default")
+    })?;
+    let info = tauri::async_runtime::spawn_blocking(move || -> CommandResult<_> {
+        let mut settings = current_settings(&app);
+        settings.whisper_advanced = overrides;
+        crate::save_settings_inner(&app, &mut settings).with_code(ErrorCode::Storage)?;
+        Ok(params_info(&settings))
+    })
+    .await
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("whisper params task failed: {}", e),
+        )
+    })??;
+    Ok(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_apply_on_top_of_model_defaults() {
+        let mut settings = Settings {
+            model: "whisper-distil-large-v3".to_string(),
+            ..Settings::default()
+        };
+        assert_eq!(resolve(&settings).beam_size, 1);
+        assert_eq!(
+            cli_args(&resolve(&settings)),
+            vec![("--beam-size", "1".to_string())]
+        );
+
+        settings.model = "whisper-large-v3-turbo".to_string();
+        assert!(cli_args(&resolve(&settings)).is_empty());
+        assert_eq!(resolve_for_server(&settings).beam_size, 1);
+        settings.whisper_advanced.temperature = Some(0.2);
+        settings.whisper_advanced.no_speech_threshold = Some(0.45);
+        assert_eq!(
+            cli_args(&resolve(&settings)),
+            vec![
+                ("--temperature", "0.2".to_string()),
+                ("--no-speech-thold", "0.45".to_string()),
+            ]
+        );
+        let server = resolve_for_server(&settings);
+        assert_eq!((server.beam_size, server.temperature), (1, 0.2));
+        assert_eq!(model_defaults("My-Distil-Small.bin").beam_size, 1);
+
+        let mut invalid = WhisperAdvancedSettings {
+            beam_size: Some(0),
+            temperature: Some(1.5),
+            entropy_threshold: Some(f32::NAN),
+            no_speech_threshold: Some(0.3),
+        };
+        assert!(validate(&invalid).is_err());
+        normalize_whisper_advanced(&mut invalid);
+        assert_eq!(
+            invalid,
+            WhisperAdvancedSettings {
+                no_speech_threshold: Some(0.3),
+                ..WhisperAdvancedSettings::default()
+            }
+        );
+        assert!(validate(&invalid).is_ok());
+    }
+}
@@ -0,0 +1,427 @@
+//! Managed whisper.cpp runtime installer.
+//!
+//! Downloads the official Windows build for a backend from a pinned
+//! whisper.cpp release, verifies it against the SHA-256 recorded in this file
+//! for that asset (never one fetched alongside the download) and unpacks it
+//! into
+//! `<base>/whisper-runtime/<backend>`, where `paths` picks it up after the
+//! bundled `bin/` folders. Each install records its release in
+//! `runtime.json` so `check_runtime_update` can tell outdated installs.
+
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Emitter};
+use tracing::{info, warn};
+use zip::ZipArchive;
+
+/// Release the installer downloads; bump together with testing the assets.
+const PINNED_RELEASE: &str = "v1.7.6";
+const RELEASES_API: &str = "https://api.github.com/repos/ggml-org/whisper.cpp/releases";
+const RELEASE_DOWNLOADS: &str = "https://github.com/ggml-org/whisper.cpp/releases/download";
+const USER_AGENT: &str = "TrisprFlow/WhisperRuntimeInstaller";
+const RUNTIME_INFO_FILE: &str = "runtime.json";
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct WhisperRuntimeInstallProgress {
+    pub(crate) backend: String,
+    /// "download" | "verify" | "extract" | "done"
+    pub(crate) stage: &'static str,
+    pub(crate) downloaded: Option<u64>,
+    pub(crate) total: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct InstalledWhisperRuntime {
+    pub(crate) backend: String,
+    pub(crate) release: String,
+    pub(crate) asset: String,
+    pub(crate) sha256: String,
+    /// Set when read back from disk.
+    #[serde(skip_deserializing)]
+    pub(crate) path: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct WhisperRuntimeUpdateStatus {
+    pub(crate) pinned_release: &'static str,
+    /// Newest upstream release, when GitHub was reachable.
+    pub(crate) latest_release: Option<String>,
+    pub(crate) installed: Vec<InstalledWhisperRuntime>,
+    /// An installed runtime is older than the pinned release.
+    pub(crate) update_available: bool,
+}
+
+/// A Windows x64 build in `PINNED_RELEASE`.
+struct PinnedAsset {
+    backend: &'static str,
+    name: &'static str,
+    /// Lowercase hex SHA-256 of the verified download. Empty until someone
+    /// has hashed it; such an asset is refused.
+    sha256: &'static str,
+}
+
+/// Bump together with `PINNED_RELEASE`, hashing each asset from a verified
+/// download.
+const PINNED_ASSETS: &[PinnedAsset] = &[
+    PinnedAsset {
+        backend: "cpu",
+        name: "whisper-bin-x64.zip",
+        sha256: "",
+    },
+    PinnedAsset {
+        backend: "cuda",
+        name: "whisper-cublas-12.4.0-bin-x64.zip",
+        sha256: "",
+    },
+];
+
+/// Pinned asset holding the Windows x64 build for `backend`.
+fn pinned_asset(backend: &str) -> Result<&'static PinnedAsset, String> {
+    match backend {
+        "vulkan" => {
+            Err("whisper.cpp publishes no prebuilt Vulkan runtime; use the bundled one".to_string())
+        }
+        other => PINNED_ASSETS
+            .iter()
+            .find(|asset| asset.backend == other)
+            .ok_or_else(|| format!("Unknown whisper backend '{}'", other)),
+    }
+}
+
+/// Download URL and expected SHA-256 of `asset`.
+fn asset_source(asset: &PinnedAsset) -> Result<(String, &'static str), String> {
+    let pinned = asset.sha256.len() == 64
+        && asset
+            .sha256
+            .chars()
+            .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c));
+    if !pinned {
+        return Err(format!(
+            "No pinned checksum for '{}' in {}; refusing it",
+            asset.name, PINNED_RELEASE
+        ));
+    }
+    let url = format!("{}/{}/{}", RELEASE_DOWNLOADS, PINNED_RELEASE, asset.name);
+    Ok((url, asset.sha256))
+}
+
+fn runtime_root(app: &AppHandle) -> PathBuf {
+    crate::paths::managed_runtime_dir()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| crate::paths::resolve_base_dir(app).join("whisper-runtime"))
+}
+
+fn http_agent() -> ureq::Agent {
+    ureq::builder()
+        .timeout_connect(Duration::from_secs(10))
+        .timeout_read(Duration::from_secs(60 * 30))
+        .build()
+}
+
+/// The newest upstream release, for `check_runtime_update`.
+fn fetch_latest_release() -> Result<serde_json::Value, String> {
+    http_agent()
+        .get(&format!("{}/latest", RELEASES_API))
+        .set("User-Agent", USER_AGENT)
+        .set("Accept", "application/vnd.github+json")
+        .call()
+        .map_err(|e| format!("Fetching whisper.cpp release info failed: {}", e))?
+        .into_json()
+        .map_err(|e| format!("Invalid release info: {}", e))
+}
+
+fn emit_progress(
+    app: &AppHandle,
+    backend: &str,
+    stage: &'static str,
+    downloaded: Option<u64>,
+    total: Option<u64>,
+) {
+    let _ = app.emit(
+        "whisper:runtime-install-progress",
+        WhisperRuntimeInstallProgress {
+            backend: backend.to_string(),
+            stage,
+            downloaded,
+            total,
+        },
+    );
+}
+
+/// Streams `url` into `dest`, returning the SHA-256 of what was written.
+fn download(app: &AppHandle, backend: &str, url: &str, dest: &Path) -> Result<String, String> {
+    let response = http_agent()
+        .get(url)
+        .set("User-Agent", USER_AGENT)
+        .call()
+        .map_err(|e| format!("Downloading whisper runtime failed: {}", e))?;
+    let total = response
+        .header("Content-Length")
+        .and_then(|h| h.parse::<u64>().ok());
+    let mut reader = response.into_reader();
+    let mut out =
+        File::create(dest).map_err(|e| format!("Creating download file failed: {}", e))?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 256 * 1024];
+    let mut downloaded = 0u64;
+    let mut last_emit = Instant::now();
+    loop {
+        let read = reader
+            .read(&mut buf)
+            .map_err(|e| format!("Downloading whisper runtime failed: {}", e))?;
+        if read == 0 {
+            break;
+        }
+        out.write_all(&buf[..read])
+            .map_err(|e| format!("Writing whisper runtime failed: {}", e))?;
+        hasher.update(&buf[..read]);
+        downloaded += read as u64;
+        if last_emit.elapsed() >= Duration::from_millis(250) {
+            emit_progress(app, backend, "download", Some(downloaded), total);
+            last_emit = Instant::now();
+        }
+    }
+    emit_progress(app, backend, "download", Some(downloaded), total);
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Unpacks the directory holding `whisper-cli.exe` (zips nest it under
+/// `Release/`) flat into `target`.
+fn extract_runtime(archive: &Path, target: &Path) -> Result<(), String> {
+    let file = File::open(archive).map_err(|e| format!("Opening runtime archive failed: {}", e))?;
+    let mut zip = ZipArchive::new(file).map_err(|e| format!("Invalid runtime archive: {}", e))?;
+    let cli_dir = (0..zip.len())
+        .filter_map(|index| {
+            zip.by_index(index)
+                .ok()?
+                .enclosed_name()
+                .map(Path::to_path_buf)
+        })
+        .find(|path| {
+            path.file_name()
+                .is_some_and(|name| name.eq_ignore_ascii_case("whisper-cli.exe"))
+        })
+        .map(|path| path.parent().map(Path::to_path_buf).unwrap_or_default())
+        .ok_or_else(|| "Runtime archive contains no whisper-cli.exe".to_string())?;
+
+    fs::create_dir_all(target).map_err(|e| format!("Creating runtime folder failed: {}", e))?;
+    for index in 0..zip.len() {
+        let mut entry = zip
+            .by_index(index)
+            .map_err(|e| format!("Reading runtime archive failed: {}", e))?;
+        let Some(path) = entry.enclosed_name().map(Path::to_path_buf) else {
+            continue;
+        };
+        if entry.is_dir() || path.parent() != Some(cli_dir.as_path()) {
+            continue;
+        }
+        let Some(name) = path.file_name() else {
+            continue;
+        };
+        let mut out = File::create(target.join(name))
+            .map_err(|e| format!("Extracting '{}' failed: {}", path.display(), e))?;
+        std::io::copy(&mut entry, &mut out)
+            .map_err(|e| format!("Extracting '{}' failed: {}", path.display(), e))?;
+    }
+    Ok(())
+}
+
+fn download_and_activate(
+    app: &AppHandle,
+    backend: &str,
+    asset: &str,
+    url: &str,
+    expected_sha256: &str,
+    archive: &Path,
+    staging: &Path,
+) -> Result<InstalledWhisperRuntime, String> {
+    let sha256 = download(app, backend, url, archive)?;
+    emit_progress(app, backend, "verify", None, None);
+    if sha256 != expected_sha256 {
+        return Err(format!(
+            "Checksum mismatch for {}: expected {}, got {}",
+            asset, expected_sha256, sha256
+        ));
+    }
+    emit_progress(app, backend, "extract", None, None);
+    let _ = fs::remove_dir_all(staging);
+    extract_runtime(archive, staging)?;
+    let mut installed = InstalledWhisperRuntime {
+        backend: backend.to_string(),
+        release: PINNED_RELEASE.to_string(),
+        asset: asset.to_string(),
+        sha256,
+        path: String::new(),
+    };
+    let info = serde_json::to_string_pretty(&installed).map_err(|e| e.to_string())?;
+    fs::write(staging.join(RUNTIME_INFO_FILE), info)
+        .map_err(|e| format!("Writing runtime info failed: {}", e))?;
+    // Swap in the new build only once it is complete.
+    let target = staging
+        .parent()
+        .map(|root| root.join(backend))
+        .ok_or_else(|| "Invalid runtime folder".to_string())?;
+    let _ = fs::remove_dir_all(&target);
+    fs::rename(staging, &target)
+        .map_err(|e| format!("Activating runtime failed (is it in use?): {}", e))?;
+    installed.path = target.display().to_string();
+    Ok(installed)
+}
+
+fn install_runtime_inner(
+    app: &AppHandle,
+    backend: &str,
+) -> Result<InstalledWhisperRuntime, String> {
+    if !cfg!(target_os = "windows") {
+        return Err(
+            "Prebuilt whisper runtimes exist for Windows only; install whisper.cpp with your package manager"
+                .to_string(),
+        );
+    }
+    let asset = pinned_asset(backend)?;
+    let (url, expected_sha256) = asset_source(asset)?;
+    let asset = asset.name;
+
+    let root = runtime_root(app);
+    fs::create_dir_all(&root).map_err(|e| format!("Creating runtime folder failed: {}", e))?;
+    let archive = root.join(format!("{}.part", asset));
+    let staging = root.join(format!(".staging-{}", backend));
+    let result = download_and_activate(
+        app,
+        backend,
+        asset,
+        &url,
+        expected_sha256,
+        &archive,
+        &staging,
+    );
+    let _ = fs::remove_file(&archive);
+    let _ = fs::remove_dir_all(&staging);
+    if let Ok(installed) = &result {
+        info!(
+            "Installed whisper runtime {} {} to {}",
+            installed.backend, installed.release, installed.path
+        );
+        emit_progress(app, backend, "done", None, None);
+    }
+    result
+}
+
+fn installed_runtimes(app: &AppHandle) -> Vec<InstalledWhisperRuntime> {
+    let root = runtime_root(app);
+    ["cpu", "cuda", "vulkan"]
+        .iter()
+        .filter_map(|backend| {
+            let dir = root.join(backend);
+            let raw = fs::read_to_string(dir.join(RUNTIME_INFO_FILE)).ok()?;
+            let mut installed: InstalledWhisperRuntime = serde_json::from_str(&raw)
+                .map_err(|e| warn!("Ignoring unreadable {}: {}", dir.display(), e))
+                .ok()?;
+            installed.path = dir.display().to_string();
+            Some(installed)
+        })
+        .collect()
+}
+
+/// Numeric parts of a release tag ("v1.7.6" → [1, 7, 6]).
+fn release_version(tag: &str) -> Vec<u64> {
+    tag.trim_start_matches('v')
+        .split('.')
+        .map(|part| part.parse().unwrap_or(0))
+        .collect()
+}
+
+/// Download, verify and activate the pinned whisper.cpp build for
+/// `backend` ("cpu" | "cuda").
+#[tauri::command]
+pub(crate) async fn install_runtime(
+    app: AppHandle,
+    backend: String,
+) -> CommandResult<InstalledWhisperRuntime> {
+    let backend = backend.trim().to_ascii_lowercase();
+    tauri::async_runtime::spawn_blocking(move || {
+        install_runtime_inner(&app, &backend).with_code(ErrorCode::RuntimeMissing)
+    })
+    .await
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("install_runtime task failed: {}", e),
+        )
+    })?
+}
+
+/// Installed managed runtimes compared with the pinned and latest release.
+#[tauri::command]
+pub(crate) async fn check_runtime_update(
+    app: AppHandle,
+) -> CommandResult<WhisperRuntimeUpdateStatus> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let installed = installed_runtimes(&app);
+        let pinned = release_version(PINNED_RELEASE);
+        let update_available = installed
+            .iter()
+            .any(|runtime| release_version(&runtime.release) < pinned);
+        let latest_release = fetch_latest_release()
+            .map_err(|e| warn!("{}", e))
+            .ok()
+            .and_then(|release| release.get("tag_name")?.as_str().map(str::to_string));
+        WhisperRuntimeUpdateStatus {
+            pinned_release: PINNED_RELEASE,
+            latest_release,
+            installed,
+            update_available,
+        }
+    })
+    .await
+    .map_err(|e| {
+        CommandError::new(
+            ErrorCode::Internal,
+            format!("check_runtime_update task failed: {}", e),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pinned_assets_need_a_checksum_in_source() {
+        let cpu = pinned_asset("cpu").unwrap();
+        assert_eq!(cpu.name, "whisper-bin-x64.zip");
+        assert!(pinned_asset("vulkan").is_err());
+        assert!(pinned_asset("metal").is_err());
+
+        let hashed = PinnedAsset {
+            sha256: "ab12cd34ef56ab12cd34ef56ab12cd34ef56ab12cd34ef56ab12cd34ef56ab12",
+            ..*cpu
+        };
+        assert_eq!(
+            asset_source(&hashed),
+            Ok((
+                format!(
+                    "{}/{}/whisper-bin-x64.zip",
+                    RELEASE_DOWNLOADS, PINNED_RELEASE
+                ),
+                hashed.sha256
+            ))
+        );
+        let unhashed = PinnedAsset { sha256: "", ..*cpu };
+        assert!(asset_source(&unhashed).is_err());
+        let uppercase = PinnedAsset {
+            sha256: "AB12CD34EF56AB12CD34EF56AB12CD34EF56AB12CD34EF56AB12CD34EF56AB12",
+            ..*cpu
+        };
+        assert!(asset_source(&uppercase).is_err());
+
+        assert!(release_version("v1.7.5") < release_version(PINNED_RELEASE));
+        assert!(release_version("v1.10.0") > release_version("v1.7.6"));
+    }
+}
@@ -52,7 +52,19 @@ fn whisper_server_warm_deadline_ms() -> u64 {
     crate::util::now_ms().saturating_add(WHISPER_SERVER_IDLE_RETIRE_MS)
 }
 
+/// `whisper_server_keep_warm`: the server stays loaded regardless of idle time.
+pub(crate) fn keep_warm_enabled(state: &AppState) -> bool {
+    state
+        .settings
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .whisper_server_keep_warm
+}
+
 fn whisper_server_warm_window_active(state: &AppState) -> bool {
+    if keep_warm_enabled(state) {
+        return true;
+    }
     let warm_until = state.whisper_server_warm_until_ms.load(Ordering::Relaxed);
     warm_until > crate::util::now_ms()
 }
@@ -85,7 +97,9 @@ fn schedule_whisper_server_retire(app: AppHandle, generation: u64) {
             continue;
         }
 
-        if WHISPER_SERVER_ACTIVE_REQUESTS.load(Ordering::Relaxed) > 0 {
+        if WHISPER_SERVER_ACTIVE_REQUESTS.load(Ordering::Relaxed) > 0
+            || keep_warm_enabled(state.inner())
+        {
             let next_generation = extend_whisper_server_warm_window(state.inner());
             schedule_whisper_server_retire(app.clone(), next_generation);
             return;
@@ -332,6 +346,13 @@ pub fn start_whisper_server(
         .arg("-t")
         .arg(optimal_thread_count().to_string())
         .stdin(std::process::Stdio::null());
+    // CPU selected (or forced via TRISPR_LOCAL_BACKEND): keep a GPU build
+    // off the GPU when no CPU build exists.
+    if crate::transcription::effective_cli_backend_preference(settings).eq_ignore_ascii_case("cpu")
+        && crate::transcription::whisper_backend_from_cli_path(&server_path) != "cpu"
+    {
+        cmd.arg("--no-gpu");
+    }
 
     // Capture stdout/stderr to a log file so we can see crashes / backend
     // errors. Append mode keeps history across restarts within a session.
@@ -475,14 +496,26 @@ pub fn inspect_recent_server_crash(app: &AppHandle) -> Option<String> {
     None
 }
 
+/// Result of a Whisper-Server inference request.
+pub struct ServerTranscript {
+    pub text: String,
+    /// Whisper language code the server decoded with (detected in auto mode).
+    pub language: Option<String>,
+}
+
 /// Transcribe WAV bytes via HTTP to the Whisper-Server.
 ///
 /// Builds multipart/form-data manually since ureq v2 has no multipart feature.
+/// `prompt` is the preceding text for context priming, if any.
 pub fn transcribe_via_server(
     wav_bytes: &[u8],
     port: u16,
     language: &str,
-) -> Result<String, String> {
+    translate: bool,
+    prompt: Option<&str>,
+    audio_ctx: Option<u32>,
+    params: &crate::whisper_params::WhisperParams,
+) -> Result<ServerTranscript, String> {
     let _request_guard = WhisperServerRequestGuard::new();
     let boundary = "trispr_boundary_8f3a2b";
     let mut body: Vec<u8> = Vec::new();
@@ -498,20 +531,41 @@ pub fn transcribe_via_server(
     )
     .map_err(|e| format!("Failed to encode multipart: {}", e))?;
 
-    // Add response-format part
-    write_multipart_field_text(&mut body, boundary, "response_format", "json")
+    // Add response-format part; verbose_json also reports the language.
+    write_multipart_field_text(&mut body, boundary, "response_format", "verbose_json")
         .map_err(|e| format!("Failed to encode multipart: {}", e))?;
 
     // Add language part
     write_multipart_field_text(&mut body, boundary, "language", language)
         .map_err(|e| format!("Failed to encode multipart: {}", e))?;
 
-    // Dictation only needs final text, not token timestamps. Keep decoding
-    // deterministic and avoid fallback candidate loops for lower latency on
-    // short push-to-talk clips.
+    if translate {
+        write_multipart_field_text(&mut body, boundary, "translate", "true")
+            .map_err(|e| format!("Failed to encode multipart: {}", e))?;
+    }
+
+    if let Some(prompt) = prompt {
+        write_multipart_field_text(&mut body, boundary, "prompt", prompt)
+            .map_err(|e| format!("Failed to encode multipart: {}", e))?;
+    }
+
+    // A reduced encoder window for backlog fast decoding (see
+    // `backlog_adaptation`).
+    if let Some(audio_ctx) = audio_ctx {
+        write_multipart_field_text(&mut body, boundary, "audio_ctx", &audio_ctx.to_string())
+            .map_err(|e| format!("Failed to encode multipart: {}", e))?;
+    }
+
+    // Decoding parameters from the advanced settings (see `whisper_params`).
+    for (name, value) in crate::whisper_params::server_fields(params) {
+        write_multipart_field_text(&mut body, boundary, name, &value)
+            .map_err(|e| format!("Failed to encode multipart: {}", e))?;
+    }
+
+    // Dictation only needs final text, not token timestamps. Avoid fallback
+    // candidate loops for lower latency on short push-to-talk clips.
     for (name, value) in [
         ("no_timestamps", "true"),
-        ("temperature", "0.0"),
         ("temperature_inc", "0.0"),
         ("best_of", "1"),
         ("suppress_nst", "true"),
@@ -583,7 +637,8 @@ pub fn transcribe_via_server(
         .into_json()
         .map_err(|e| format!("Failed to parse JSON response: {}", e))?;
 
-    json.get("result")
+    let text = json
+        .get("result")
         .and_then(|v| v.as_str())
         .or_else(|| json.get("text").and_then(|v| v.as_str()))
         .or_else(|| json.get("transcript").and_then(|v| v.as_str()))
@@ -593,7 +648,12 @@ pub fn transcribe_via_server(
                 "No transcript field in server response (expected result/text/transcript): {}",
                 json
             )
-        })
+        })?;
+    let language = ["detected_language", "language"]
+        .iter()
+        .filter_map(|key| json.get(*key).and_then(|v| v.as_str()))
+        .find_map(crate::detected_language::normalize_language_code);
+    Ok(ServerTranscript { text, language })
 }
 
 /// Restart the Whisper-Server if it's running.
@@ -720,6 +780,10 @@ pub fn ensure_whisper_server_keepalive(app: AppHandle) {
             continue;
         }
 
+        if settings.whisper_server_keep_warm {
+            // Restarts are paced by the runtime supervisor.
+            continue;
+        }
         let port = state.whisper_server_port.load(Ordering::Relaxed);
         if !whisper_server_warm_window_active(state.inner()) {
             if ping_whisper_server(port)
@@ -792,7 +856,7 @@ fn update_whisper_server_diagnostics(
 // ────────────────────────────────────────────────────────────────────────────────
 
 /// Write a form field with file content to multipart body.
-fn write_multipart_field_file(
+pub(crate) fn write_multipart_field_file(
     body: &mut Vec<u8>,
     boundary: &str,
     field_name: &str,
@@ -811,7 +875,7 @@ fn write_multipart_field_file(
 }
 
 /// Write a form field with text content to multipart body.
-fn write_multipart_field_text(
+pub(crate) fn write_multipart_field_text(
     body: &mut Vec<u8>,
     boundary: &str,
     field_name: &str,
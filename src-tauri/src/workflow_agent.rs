@@ -6,6 +6,7 @@ use std::sync::Mutex;
 use tauri::{AppHandle, Emitter, Manager, State};
 use tracing::{info, warn};
 
+use crate::errors::{CommandError, CommandResult, ErrorCode, WithErrorCode};
 use crate::history_partition::PartitionedHistory;
 use crate::modules::{normalize_confluence_settings, ASSISTANT_CORE_MODULE_ID};
 use crate::overlay::update_overlay_refining_indicator;
@@ -1567,14 +1568,15 @@ pub(crate) fn assistant_execute_direct_action(
     app: AppHandle,
     state: State<'_, AppState>,
     request: AssistantExecuteDirectActionRequest,
-) -> Result<crate::workflow_agent::AgentExecutionResult, String> {
+) -> CommandResult<crate::workflow_agent::AgentExecutionResult> {
     guarded_command!("assistant_execute_direct_action", {
         let settings_snapshot = {
             let settings = state
                 .settings
                 .read()
                 .unwrap_or_else(|poisoned| poisoned.into_inner());
-            require_capability_enabled(&settings, RuntimeCapability::WorkflowAgent)?;
+            require_capability_enabled(&settings, RuntimeCapability::WorkflowAgent)
+                .with_code(ErrorCode::InvalidInput)?;
             crate::gdd::require_gdd_module_active(&app, &settings)?;
             settings.clone()
         };
@@ -1849,7 +1851,7 @@ pub(crate) fn assistant_execute_direct_action(
                     let encoded: String =
                         url::form_urlencoded::byte_serialize(query.as_bytes()).collect();
                     let target = format!("https://www.google.com/search?q={encoded}");
-                    open_external_target(&target)?;
+                    open_external_target(&target).with_code(ErrorCode::Window)?;
                     crate::workflow_agent::AgentExecutionResult {
                         status: "completed".to_string(),
                         message: format!("Opened web search for “{}”.", query.trim()),
@@ -1990,14 +1992,15 @@ pub(crate) fn agent_compose_unknown_reply(
     app: AppHandle,
     state: State<'_, AppState>,
     request: AgentComposeUnknownReplyRequest,
-) -> Result<AgentComposeReplyResult, String> {
+) -> CommandResult<AgentComposeReplyResult> {
     guarded_command!("agent_compose_unknown_reply", {
         let settings_snapshot = {
             let settings = state
                 .settings
                 .read()
                 .unwrap_or_else(|poisoned| poisoned.into_inner());
-            require_capability_enabled(&settings, RuntimeCapability::WorkflowAgent)?;
+            require_capability_enabled(&settings, RuntimeCapability::WorkflowAgent)
+                .with_code(ErrorCode::InvalidInput)?;
             settings.clone()
         };
 
@@ -2116,7 +2119,7 @@ pub(crate) fn agent_cancel_pending_confirmation(
     app: AppHandle,
     state: State<'_, AppState>,
     request: Option<AgentCancelPendingConfirmationRequest>,
-) -> Result<crate::workflow_agent::AgentExecutionResult, String> {
+) -> CommandResult<crate::workflow_agent::AgentExecutionResult> {
     guarded_command!("agent_cancel_pending_confirmation", {
         let settings_snapshot = {
             let settings = state
@@ -2192,14 +2195,15 @@ pub(crate) fn agent_parse_command(
     app: AppHandle,
     state: State<'_, AppState>,
     request: crate::workflow_agent::AgentParseCommandRequest,
-) -> Result<crate::workflow_agent::AgentCommandParseResult, String> {
+) -> CommandResult<crate::workflow_agent::AgentCommandParseResult> {
     guarded_command!("agent_parse_command", {
         let settings_snapshot = {
             let settings = state
                 .settings
                 .read()
                 .unwrap_or_else(|poisoned| poisoned.into_inner());
-            require_capability_enabled(&settings, RuntimeCapability::WorkflowAgent)?;
+            require_capability_enabled(&settings, RuntimeCapability::WorkflowAgent)
+                .with_code(ErrorCode::InvalidInput)?;
             settings.clone()
         };
         let assistant_mode = assistant_product_mode(&settings_snapshot) == "assistant";
@@ -2261,14 +2265,15 @@ pub(crate) fn agent_parse_command(
 pub(crate) fn search_transcript_sessions(
     state: State<'_, AppState>,
     mut request: crate::workflow_agent::SearchTranscriptSessionsRequest,
-) -> Result<Vec<crate::workflow_agent::TranscriptSessionCandidate>, String> {
+) -> CommandResult<Vec<crate::workflow_agent::TranscriptSessionCandidate>> {
     guarded_command!("search_transcript_sessions", {
         let defaults = {
             let settings = state
                 .settings
                 .read()
                 .unwrap_or_else(|poisoned| poisoned.into_inner());
-            require_capability_enabled(&settings, RuntimeCapability::WorkflowAgent)?;
+            require_capability_enabled(&settings, RuntimeCapability::WorkflowAgent)
+                .with_code(ErrorCode::InvalidInput)?;
             (
                 settings.workflow_agent.session_gap_minutes,
                 settings.workflow_agent.max_candidates,
@@ -2295,14 +2300,15 @@ pub(crate) fn agent_build_execution_plan(
     app: AppHandle,
     state: State<'_, AppState>,
     request: crate::workflow_agent::AgentBuildExecutionPlanRequest,
-) -> Result<crate::workflow_agent::AgentExecutionPlan, String> {
+) -> CommandResult<crate::workflow_agent::AgentExecutionPlan> {
     guarded_command!("agent_build_execution_plan", {
         let settings_snapshot = {
             let settings = state
                 .settings
                 .read()
                 .unwrap_or_else(|poisoned| poisoned.into_inner());
-            require_capability_enabled(&settings, RuntimeCapability::WorkflowAgent)?;
+            require_capability_enabled(&settings, RuntimeCapability::WorkflowAgent)
+                .with_code(ErrorCode::InvalidInput)?;
             settings.clone()
         };
         let assistant_mode = assistant_product_mode(&settings_snapshot) == "assistant";
@@ -2315,10 +2321,16 @@ pub(crate) fn agent_build_execution_plan(
             );
         }
         if request.intent.trim().is_empty() {
-            return Err("Intent is required.".to_string());
+            return Err(CommandError::new(
+                ErrorCode::InvalidInput,
+                "Intent is required.",
+            ));
         }
         if request.session_id.trim().is_empty() {
-            return Err("Session id is required.".to_string());
+            return Err(CommandError::new(
+                ErrorCode::InvalidInput,
+                "Session id is required.",
+            ));
         }
         const ALLOWED_LANGUAGES: &[&str] = &[
             "source", "en", "de", "fr", "es", "it", "pt", "nl", "pl", "ru", "ja", "ko", "zh", "ar",
@@ -2326,10 +2338,13 @@ pub(crate) fn agent_build_execution_plan(
         ];
         let lang = request.target_language.trim();
         if !ALLOWED_LANGUAGES.contains(&lang) {
-            return Err(format!(
-                "Invalid target language '{}'. Allowed: {}",
-                lang,
-                ALLOWED_LANGUAGES.join(", ")
+            return Err(CommandError::new(
+                ErrorCode::InvalidInput,
+                format!(
+                    "Invalid target language '{}'. Allowed: {}",
+                    lang,
+                    ALLOWED_LANGUAGES.join(", ")
+                ),
             ));
         }
         if assistant_mode {
@@ -2373,7 +2388,7 @@ pub(crate) fn agent_execute_gdd_plan(
     app: AppHandle,
     state: State<'_, AppState>,
     request: crate::workflow_agent::AgentExecuteGddPlanRequest,
-) -> Result<crate::workflow_agent::AgentExecutionResult, String> {
+) -> CommandResult<crate::workflow_agent::AgentExecutionResult> {
     guarded_command!("agent_execute_gdd_plan", {
         let plan = request.plan.clone();
         let settings_snapshot = {
@@ -2381,7 +2396,8 @@ pub(crate) fn agent_execute_gdd_plan(
                 .settings
                 .read()
                 .unwrap_or_else(|poisoned| poisoned.into_inner());
-            require_capability_enabled(&settings, RuntimeCapability::WorkflowAgent)?;
+            require_capability_enabled(&settings, RuntimeCapability::WorkflowAgent)
+                .with_code(ErrorCode::InvalidInput)?;
             settings.clone()
         };
         let assistant_mode = assistant_product_mode(&settings_snapshot) == "assistant";
@@ -2529,7 +2545,7 @@ pub(crate) fn agent_execute_gdd_plan(
         }
 
         let execution_result =
-            (|| -> Result<crate::workflow_agent::AgentExecutionResult, String> {
+            (|| -> CommandResult<crate::workflow_agent::AgentExecutionResult> {
                 if plan.intent != "gdd_generate_publish" {
                     let result = crate::workflow_agent::AgentExecutionResult {
                         status: "failed".to_string(),
@@ -2752,7 +2768,12 @@ pub(crate) fn agent_execute_gdd_plan(
                             Some(fallback.to_string())
                         }
                     })
-                    .ok_or_else(|| "No Confluence space key provided for publish.".to_string())?;
+                    .ok_or_else(|| {
+                        CommandError::new(
+                            ErrorCode::InvalidInput,
+                            "No Confluence space key provided for publish.",
+                        )
+                    })?;
                 let parent_page_id = request
                     .parent_page_id
                     .as_deref()
@@ -2841,7 +2862,8 @@ pub(crate) fn agent_execute_gdd_plan(
                                 &app,
                                 &queue_request,
                                 &error,
-                            )?;
+                            )
+                            .with_code(ErrorCode::Storage)?;
                             let result = crate::workflow_agent::AgentExecutionResult {
                                 status: "queued".to_string(),
                                 message: "Confluence unavailable. Publish request queued locally."
@@ -2923,7 +2945,7 @@ pub(crate) fn agent_execute_gdd_plan(
                         draft: None,
                         publish_result: None,
                         queued_job: None,
-                        error: Some(error.clone()),
+                        error: Some(error.message.clone()),
                     };
                     emit_assistant_action_result(
                         &app,
@@ -2969,6 +2991,14 @@ mod tests {
             source: "mic".to_string(),
             speaker_name: None,
             refinement: None,
+            audio_path: None,
+            revisions: Vec::new(),
+            pinned: false,
+            tags: Vec::new(),
+            note: None,
+            formatted_text: None,
+            language: None,
+            translation: None,
         }
     }
 
@@ -2985,6 +3015,14 @@ mod tests {
             source: source.to_string(),
             speaker_name: None,
             refinement: None,
+            audio_path: None,
+            revisions: Vec::new(),
+            pinned: false,
+            tags: Vec::new(),
+            note: None,
+            formatted_text: None,
+            language: None,
+            translation: None,
         }
     }
 
@@ -270,6 +270,25 @@ describe("Toast System", () => {
       expect(message?.textContent).toBe("File upload: Something went wrong");
     });
 
+    it("should show the code and remediation hint as separate fields", () => {
+      const error: AppErrorType = {
+        type: "Network",
+        message: "Download failed",
+      };
+
+      showErrorToast(error, undefined, {
+        code: "network",
+        hint: "Check your internet connection",
+      });
+
+      const toast = container.querySelector(".toast") as HTMLElement | null;
+      expect(toast?.dataset.code).toBe("network");
+      expect(container.querySelector(".toast-message")?.textContent).toBe("Download failed");
+      expect(container.querySelector(".toast-hint")?.textContent).toBe(
+        "Check your internet connection",
+      );
+    });
+
     it("should use only error message when context is not provided", () => {
       const error: AppErrorType = {
         type: "Other",
@@ -4,7 +4,7 @@ import { buildExportText, type ExportFormat } from "./history";
 import { resolveSourceLabel } from "./history-preferences";
 import { focusFirstElement, trapFocusInModal } from "./modal-focus";
 import { showToast } from "./toast";
-import { escapeHtml } from "./utils";
+import { escapeHtml, errorMessage } from "./utils";
 import type { HistoryEntry, PartitionInfo } from "./types";
 
 type HistoryKind = "mic" | "system";
@@ -174,7 +174,7 @@ async function loadPartition(kind: HistoryKind, key: string): Promise<void> {
     showToast({
       type: "error",
       title: "Archive load failed",
-      message: String(error),
+      message: errorMessage(error),
       duration: 4500,
     });
   }
@@ -216,7 +216,7 @@ async function exportCurrentPartition(): Promise<void> {
     showToast({
       type: "error",
       title: "Archive export failed",
-      message: String(error),
+      message: errorMessage(error),
       duration: 5000,
     });
   }
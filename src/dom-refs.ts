@@ -274,6 +274,8 @@ export const overlayOpacityActive = $("overlay-opacity-active") as HTMLInputElem
 export const overlayOpacityActiveValue = $("overlay-opacity-active-value");
 export const overlayPosX = $("overlay-pos-x") as HTMLInputElement | null;
 export const overlayPosY = $("overlay-pos-y") as HTMLInputElement | null;
+export const overlayMonitor = $("overlay-monitor") as HTMLSelectElement | null;
+export const overlayMoveBtn = $("overlay-move-btn") as HTMLButtonElement | null;
 export const overlayStyle = $("overlay-style") as HTMLSelectElement | null;
 export const accentColor = $("accent-color") as HTMLInputElement | null;
 export const accentColorReset = $("accent-color-reset") as HTMLButtonElement | null;
@@ -5,6 +5,7 @@ import { appRuntimeStartedMs, history, transcribeHistory } from "./state";
 import { focusFirstElement, trapFocusInModal } from "./modal-focus";
 import { showToast } from "./toast";
 import type { HistoryEntry, PartitionInfo } from "./types";
+import { errorMessage } from "./utils";
 
 type HistoryKind = "mic" | "system";
 type ExportRange = "session" | "today" | "yesterday" | "week" | "month" | "custom";
@@ -329,7 +330,7 @@ async function executeExport(): Promise<void> {
     showToast({
       type: "error",
       title: "Export failed",
-      message: String(error),
+      message: errorMessage(error),
       duration: 5000,
     });
   }
@@ -22,6 +22,7 @@ import type {
   GenerateGddDraftRequest,
   HistoryEntry,
 } from "./types";
+import { errorMessage } from "./utils";
 
 let initialized = false;
 let loadedTemplate: GddTemplateSourceResult | null = null;
@@ -228,7 +229,7 @@ async function detectPreset(): Promise<void> {
     showToast({
       type: "error",
       title: "Preset detection failed",
-      message: String(error),
+      message: errorMessage(error),
       duration: 5000,
     });
   }
@@ -289,7 +290,7 @@ async function generateDraft(): Promise<void> {
     showToast({
       type: "error",
       title: "GDD generation failed",
-      message: String(error),
+      message: errorMessage(error),
       duration: 5200,
     });
   } finally {
@@ -350,7 +351,7 @@ async function suggestConfluenceTarget(): Promise<void> {
     showToast({
       type: "error",
       title: "Suggest target failed",
-      message: String(error),
+      message: errorMessage(error),
       duration: 5200,
     });
   }
@@ -483,7 +484,7 @@ async function publishToConfluenceInternal(
     showToast({
       type: "error",
       title: "Publish failed",
-      message: String(error),
+      message: errorMessage(error),
       duration: 6000,
     });
   } finally {
@@ -526,7 +527,7 @@ async function oneClickPublishToConfluence(): Promise<void> {
     showToast({
       type: "error",
       title: "One-click publish failed",
-      message: String(error),
+      message: errorMessage(error),
       duration: 5200,
     });
   } finally {
@@ -571,7 +572,7 @@ async function validateDraft(): Promise<void> {
     showToast({
       type: "error",
       title: "Validation failed",
-      message: String(error),
+      message: errorMessage(error),
       duration: 5200,
     });
   }
@@ -620,7 +621,7 @@ async function loadTemplateFromFile(): Promise<void> {
     showToast({
       type: "error",
       title: "Template load failed",
-      message: String(error),
+      message: errorMessage(error),
       duration: 5000,
     });
   }
@@ -656,7 +657,7 @@ async function loadTemplateFromConfluence(): Promise<void> {
     showToast({
       type: "error",
       title: "Confluence load failed",
-      message: String(error),
+      message: errorMessage(error),
       duration: 5200,
     });
   }
@@ -698,7 +699,7 @@ async function refreshPendingQueue(): Promise<void> {
   } catch (error) {
     if (dom.gddFlowQueueList) {
       dom.gddFlowQueueList.innerHTML = `<div class="archive-empty">Failed to load queue: ${htmlEscape(
-        String(error)
+        errorMessage(error)
       )}</div>`;
     }
   }
@@ -731,7 +732,7 @@ async function retryPendingJob(jobId: string): Promise<void> {
     showToast({
       type: "error",
       title: "Retry failed",
-      message: String(error),
+      message: errorMessage(error),
       duration: 5200,
     });
   } finally {
@@ -753,7 +754,7 @@ async function deletePendingJob(jobId: string): Promise<void> {
     showToast({
       type: "error",
       title: "Delete failed",
-      message: String(error),
+      message: errorMessage(error),
       duration: 4200,
     });
   } finally {
@@ -1,7 +1,7 @@
 // History management and panel state functions
 
 import { invoke } from "@tauri-apps/api/core";
-import { escapeHtml } from "./utils";
+import { escapeHtml, errorMessage } from "./utils";
 import type { HistoryEntry, HistoryTab, TopicScore } from "./types";
 import { history, transcribeHistory, currentHistoryTab, setCurrentHistoryTab as setCurrentTab, isRefinementEnabled } from "./state";
 import * as dom from "./dom-refs";
@@ -1046,7 +1046,7 @@ export function initHistoryDelegation(): void {
           showToast({
             type: "error",
             title: "Delete failed",
-            message: String(error),
+            message: errorMessage(error),
           });
         }
       }
@@ -6,6 +6,7 @@ import type { ValidationResult } from "./types";
 import { settings } from "./state";
 import { persistSettings } from "./settings-persist";
 import { formatHotkeyForDisplay } from "./ui-helpers";
+import { errorMessage } from "./utils";
 
 // Map event.code → tauri-compatible key name for layout-independent recognition
 const CODE_TO_KEY: Record<string, string> = {
@@ -119,6 +120,9 @@ export function setupHotkeyRecorder(
   let isRecording = false;
   let recordedKeys: Set<string> = new Set();
   let finalizeTimeout: number | null = null;
+  // Mouse buttons and modifier+wheel are only routed for PTT and Toggle.
+  const acceptsMouse = type === "ptt" || type === "toggle";
+  let recordedMouseTrigger = false;
 
   const updateStatus = (message: string, type: "success" | "error" | "info") => {
     statusEl.textContent = message;
@@ -143,7 +147,7 @@ export function setupHotkeyRecorder(
     } catch (error) {
       input.classList.remove("valid");
       input.classList.add("invalid");
-      updateStatus(`Error: ${error}`, "error");
+      updateStatus(`Error: ${errorMessage(error)}`, "error");
       return false;
     }
   };
@@ -155,6 +159,8 @@ export function setupHotkeyRecorder(
     input.classList.remove("recording");
     document.removeEventListener("keydown", handleKeyDown);
     document.removeEventListener("keyup", handleKeyUp);
+    document.removeEventListener("mousedown", handleMouseDown, true);
+    document.removeEventListener("wheel", handleWheel, true);
 
     // Clear any pending finalization
     if (finalizeTimeout !== null) {
@@ -206,8 +212,34 @@ export function setupHotkeyRecorder(
     input.value = formatHotkeyForDisplay(hotkeyString);
   };
 
+  const recordMouseTrigger = (e: MouseEvent, trigger: string) => {
+    e.preventDefault();
+    e.stopPropagation();
+    recordedKeys.clear();
+    if (e.ctrlKey) recordedKeys.add("Ctrl");
+    if (e.shiftKey) recordedKeys.add("Shift");
+    if (e.altKey) recordedKeys.add("Alt");
+    if (e.metaKey) recordedKeys.add("Super");
+    recordedKeys.add(trigger);
+    recordedMouseTrigger = true;
+    input.value = formatHotkeyForDisplay(Array.from(recordedKeys).join("+"));
+    void finalizeHotkey();
+  };
+
+  const handleMouseDown = (e: MouseEvent) => {
+    // DOM buttons 3/4 are the thumb buttons (XBUTTON1/XBUTTON2).
+    if (e.button === 3) recordMouseTrigger(e, "Mouse4");
+    else if (e.button === 4) recordMouseTrigger(e, "Mouse5");
+  };
+
+  const handleWheel = (e: WheelEvent) => {
+    // Bare scrolling must keep working, so only modifier+wheel is recorded.
+    if (!(e.ctrlKey || e.shiftKey || e.altKey || e.metaKey) || e.deltaY === 0) return;
+    recordMouseTrigger(e, e.deltaY < 0 ? "WheelUp" : "WheelDown");
+  };
+
   const finalizeHotkey = async () => {
-    if (recordedKeys.size < 2) {
+    if (recordedKeys.size < 2 && !recordedMouseTrigger) {
       updateStatus("Need at least modifier + key", "error");
       return;
     }
@@ -265,14 +297,24 @@ export function setupHotkeyRecorder(
     } else {
       isRecording = true;
       recordedKeys.clear();
+      recordedMouseTrigger = false;
       recordBtn.textContent = "⏺ Recording...";
       recordBtn.classList.add("recording");
       input.classList.add("recording");
       input.value = "";
-      updateStatus("Press your key combination...", "info");
+      updateStatus(
+        acceptsMouse
+          ? "Press your key combination, a thumb mouse button or modifier+wheel..."
+          : "Press your key combination...",
+        "info"
+      );
 
       document.addEventListener("keydown", handleKeyDown);
       document.addEventListener("keyup", handleKeyUp);
+      if (acceptsMouse) {
+        document.addEventListener("mousedown", handleMouseDown, true);
+        document.addEventListener("wheel", handleWheel, { capture: true, passive: false });
+      }
     }
   });
 
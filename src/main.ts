@@ -11,9 +11,9 @@ import {
 } from "./frontend-trace";
 import { initWindowStatePersistence } from "./window-state";
 import { renderDownloadProgressPopup, scheduleDownloadProgressRender } from "./download-progress-popup";
+import { errorMessage } from "./utils";
 
 type TranscriptionStatus = "idle" | "recording" | "transcribing";
-type AudioCueType = "start" | "stop";
 
 import type {
   Settings,
@@ -42,6 +42,7 @@ import type {
   TranscriptionGpuActivityEvent,
   TranscriptionResultEvent,
   TranscriptionRawResultEvent,
+  PasteBlockedEvent,
   PasteSettledEvent,
   DependencyPreflightReport,
   StabilityDegradedEvent,
@@ -107,7 +108,6 @@ import {
 import { scheduleSettingsRender } from "./wiring/wire-helpers";
 import { initUnifiedTooltips, cleanupUnifiedTooltips } from "./custom-tooltips";
 import { dismissToast, showToast, showErrorToast } from "./toast";
-import { playAudioCue } from "./audio-cues";
 import { levelToDb, thresholdToPercent } from "./ui-helpers";
 import { dumpHistoryToFile, initLiveDump } from "./live-dump";
 import { initExportDialog } from "./export-dialog";
@@ -393,7 +393,7 @@ async function sendFrontendHeartbeat(source: "startup" | "interval"): Promise<vo
       traceFrontendWarn("frontend.watchdog", "frontend heartbeat failed", {
         source,
         failures: frontendHeartbeatFailureCount,
-        error: error instanceof Error ? error.message : String(error),
+        error: errorMessage(error),
       });
     }
     if (
@@ -652,7 +652,7 @@ async function bootstrap() {
   } catch (renderError) {
     console.error("Non-fatal render error during bootstrap:", renderError);
     traceFrontendError("bootstrap.render", "non-fatal render error", {
-      error: renderError instanceof Error ? renderError.message : String(renderError),
+      error: errorMessage(renderError),
     });
   }
 
@@ -757,7 +757,7 @@ async function bootstrap() {
     } catch (bgError) {
       console.error("Non-fatal background init error:", bgError);
       traceFrontendError("bootstrap.background", "background init failed", {
-        error: bgError instanceof Error ? bgError.message : String(bgError),
+        error: errorMessage(bgError),
       });
     }
   })();
@@ -931,6 +931,15 @@ async function bootstrap() {
         });
       }
     }),
+    listen<PasteBlockedEvent>("transcription:paste-blocked", (event) => {
+      if (!event.payload) return;
+      showToast({
+        type: "warning",
+        title: "Copied instead of pasted",
+        message: event.payload.message,
+        duration: 9000,
+      });
+    }),
     listen<TranscriptionRawResultEvent>("transcription:raw-result", (event) => {
       void handleWorkflowAgentRawResult(event.payload);
     }),
@@ -1274,7 +1283,7 @@ async function bootstrap() {
           try {
             await invoke<TranscribeBacklogStatus>("expand_transcribe_backlog");
           } catch (error) {
-            const message = error instanceof Error ? error.message : String(error);
+            const message = errorMessage(error);
             showToast({
               type: "error",
               title: "Backlog Expansion Failed",
@@ -1287,14 +1296,10 @@ async function bootstrap() {
     }),
     // Listen for app-wide errors from backend
     listen<ErrorEvent>("app:error", (event) => {
-      showErrorToast(event.payload.error, event.payload.context);
-    }),
-    // Listen for audio cues (beep on recording start/stop)
-    listen<string>("audio:cue", (event) => {
-      const type = event.payload as AudioCueType;
-      if (settings?.audio_cues) {
-        playAudioCue(type);
-      }
+      showErrorToast(event.payload.error, event.payload.context, {
+        code: event.payload.code,
+        hint: event.payload.hint,
+      });
     }),
     listen<number>("audio:level", (event) => {
       _pendingAudioLevel = Math.max(0, Math.min(1, event.payload ?? 0));
@@ -1453,7 +1458,7 @@ window.addEventListener("DOMContentLoaded", () => {
     .catch((error) => {
       console.error("bootstrap failed", error);
       traceFrontendError("bootstrap", "bootstrap failed", {
-        error: error instanceof Error ? error.message : String(error),
+        error: errorMessage(error),
       });
       if (dom.bootstrapLabel) {
         dom.bootstrapLabel.textContent = "Startup failed. Open DevTools/logs and retry.";
@@ -1462,7 +1467,7 @@ window.addEventListener("DOMContentLoaded", () => {
       showToast({
         type: "error",
         title: "Startup failed",
-        message: error instanceof Error ? error.message : String(error),
+        message: errorMessage(error),
         duration: 9000,
       });
     });
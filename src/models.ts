@@ -8,6 +8,7 @@ import { getModelDescription, formatSize, formatProgress } from "./ui-helpers";
 import { persistSettings } from "./settings-persist";
 import { renderHero } from "./ui-state";
 import { showToast } from "./toast";
+import { errorMessage } from "./utils";
 
 const optimizingModels = new Set<string>();
 const selectedQuantByModel = new Map<string, "q5_0" | "q8_0">();
@@ -152,7 +153,7 @@ export function renderModels() {
 
               showToast({
                 title: "Model Switch Failed",
-                message: String(error),
+                message: errorMessage(error),
                 type: "error",
               });
 
@@ -231,7 +232,7 @@ export function renderModels() {
             } catch (error) {
               showToast({
                 title: "Optimize failed",
-                message: String(error),
+                message: errorMessage(error),
                 type: "error",
               });
             } finally {
@@ -305,7 +306,7 @@ export function renderModels() {
             console.error("remove_model failed", error);
             showToast({
               title: "Remove failed",
-              message: String(error),
+              message: errorMessage(error),
               type: "error",
             });
           }
@@ -330,7 +331,7 @@ export function renderModels() {
             });
           } catch (error) {
             console.error("download_model failed", error);
-            alert(`Download failed: ${error}`);
+            alert(`Download failed: ${errorMessage(error)}`);
           }
         });
         actions.appendChild(button);
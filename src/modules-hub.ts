@@ -11,6 +11,7 @@ import { focusWorkflowAgentConsole, syncWorkflowAgentConsoleState } from "./work
 import { focusVoiceOutputConsole, syncVoiceOutputConsoleState } from "./voice-output-console";
 import { focusFirstElement } from "./modal-focus";
 import type { ModuleDescriptor, ModuleHealthStatus, ModuleUpdateInfo } from "./types";
+import { errorMessage } from "./utils";
 
 let initialized = false;
 let moduleSnapshot: ModuleDescriptor[] = [];
@@ -333,7 +334,7 @@ function openModuleConfig(moduleId: string): void {
       showToast({
         type: "warning",
         title: "Presence unavailable",
-        message: String(error),
+        message: errorMessage(error),
         duration: 3200,
       });
     });
@@ -648,7 +649,7 @@ async function handleEnable(moduleId: string): Promise<void> {
     showToast({
       type: "error",
       title: "Enable failed",
-      message: String(error),
+      message: errorMessage(error),
       duration: 5000,
     });
     await refreshModuleState();
@@ -673,7 +674,7 @@ async function handleInstall(moduleId: string): Promise<void> {
     showToast({
       type: "error",
       title: "Install failed",
-      message: String(error),
+      message: errorMessage(error),
       duration: 5200,
     });
     await refreshModuleState();
@@ -705,7 +706,7 @@ async function handleDisable(moduleId: string): Promise<void> {
     showToast({
       type: "error",
       title: "Disable failed",
-      message: String(error),
+      message: errorMessage(error),
       duration: 5000,
     });
   }
@@ -731,7 +732,7 @@ async function handleHealth(moduleId: string): Promise<void> {
     showToast({
       type: "error",
       title: "Health check failed",
-      message: String(error),
+      message: errorMessage(error),
       duration: 5000,
     });
   }
@@ -752,7 +753,7 @@ async function handleUpdates(moduleId: string): Promise<void> {
     showToast({
       type: "error",
       title: "Update check failed",
-      message: String(error),
+      message: errorMessage(error),
       duration: 5000,
     });
   }
@@ -788,7 +789,7 @@ async function handleDownload(moduleId: string): Promise<void> {
     showToast({
       type: "error",
       title: "Download failed",
-      message: String(error),
+      message: errorMessage(error),
       duration: 5200,
     });
   }
@@ -813,7 +814,7 @@ async function handleUninstall(moduleId: string): Promise<void> {
     showToast({
       type: "error",
       title: "Uninstall failed",
-      message: String(error),
+      message: errorMessage(error),
       duration: 5000,
     });
     await refreshModuleState();
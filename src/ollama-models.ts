@@ -27,6 +27,7 @@ import type {
   OllamaRuntimeStartResult,
   OllamaRuntimeVerifyResult,
 } from "./types";
+import { errorMessage } from "./utils";
 
 const DEFAULT_LOCAL_ENDPOINT = "http://127.0.0.1:11434";
 const DEFAULT_RUNTIME_VERSION = "0.20.2";
@@ -498,7 +499,7 @@ export async function autoStartLocalRuntimeIfNeeded(
       }
     }
   } catch (error) {
-    const message = error instanceof Error ? error.message : String(error);
+    const message = errorMessage(error);
     console.warn(`Ollama autostart failed (${trigger}):`, message);
     traceFrontendError("ollama.autostart", "autostart failed", { trigger, message });
     showAutostartWarning(trigger, message);
@@ -779,7 +780,7 @@ export async function fetchOnlineVersionCatalog(
     runtimeVersionCatalogLastRefreshMs = Date.now();
     onStatus(null);
   } catch (error) {
-    const msg = error instanceof Error ? error.message : String(error);
+    const msg = errorMessage(error);
     console.warn("Failed to fetch online Ollama versions:", msg);
     onStatus("GitHub fetch failed. Showing pinned versions only.");
   } finally {
@@ -934,7 +935,7 @@ async function runRuntimeAction(action: string, task: () => Promise<void>): Prom
   try {
     await task();
   } catch (error) {
-    const message = error instanceof Error ? error.message : String(error);
+    const message = errorMessage(error);
     const detail = formatOllamaDiagnosticDetail() || message;
     runtimeInstallError = {
       stage: runtimeDiagnostics?.ollama?.spawn_stage || action,
@@ -1071,7 +1072,7 @@ export async function ensureLocalRuntimeReady(): Promise<void> {
         duration: 4000,
       });
   } catch (error) {
-    const message = error instanceof Error ? error.message : String(error);
+    const message = errorMessage(error);
     runtimeInstallError = {
       stage: flowStage,
       error: message,
@@ -1152,7 +1153,7 @@ async function handleUseSystemRuntime(): Promise<void> {
       settings.providers.ollama.last_health_check = previousRuntime.last_health_check;
       await persistCurrentSettings();
       await refreshOllamaRuntimeState({ force: true });
-      const message = error instanceof Error ? error.message : String(error);
+      const message = errorMessage(error);
       throw new Error(`${message} Previous runtime selection was restored.`);
     }
   });
@@ -1396,7 +1397,7 @@ async function handleOllamaDelete(modelName: string): Promise<void> {
     showToast({
       type: "error",
       title: "Uninstall failed",
-      message: String(error),
+      message: errorMessage(error),
       duration: 5200,
     });
   } finally {
@@ -1700,7 +1701,7 @@ export async function refreshOllamaRuntimeState(
       } catch (error) {
         runtimeDetect = null;
         traceFrontendWarn("ollama.refresh", "detect_ollama_runtime failed", {
-          error: error instanceof Error ? error.message : String(error),
+          error: errorMessage(error),
         });
       }
     }
@@ -1722,7 +1723,7 @@ export async function refreshOllamaRuntimeState(
       } catch (error) {
         runtimeVerify = null;
         traceFrontendWarn("ollama.refresh", "verify_ollama_runtime failed", {
-          error: error instanceof Error ? error.message : String(error),
+          error: errorMessage(error),
         });
         runtimeHealth = {
           ok: false,
@@ -1758,7 +1759,7 @@ export async function refreshOllamaRuntimeState(
       } catch (error) {
         activeModelRequiredRuntime = null;
         traceFrontendWarn("ollama.refresh", "get_ollama_model_info failed", {
-          error: error instanceof Error ? error.message : String(error),
+          error: errorMessage(error),
         });
       }
     }
@@ -1813,12 +1814,12 @@ async function handleOllamaPull(modelName: string): Promise<void> {
   } catch (error) {
     traceFrontendWarn("ollama.pull", "pull command failed", {
       model: modelName,
-      error: error instanceof Error ? error.message : String(error),
+      error: errorMessage(error),
     });
     showToast({
       type: "error",
       title: "Download failed",
-      message: String(error),
+      message: errorMessage(error),
     });
   } finally {
     activeOllamaPulls.delete(modelName);
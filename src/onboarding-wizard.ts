@@ -4,6 +4,7 @@ import { persistSettings as saveSettings } from "./settings-persist";
 import { showToast } from "./toast";
 import { setupHotkeyRecorder } from "./hotkeys";
 import { invoke } from "@tauri-apps/api/core";
+import { errorMessage } from "./utils";
 
 let currentStep = 1;
 const TOTAL_STEPS = 3;
@@ -132,7 +133,7 @@ async function downloadModelStep(): Promise<void> {
     await saveSettings();
   } catch (err) {
     console.error("Model download failed:", err);
-    if (statusText) statusText.textContent = `Download fehlgeschlagen: ${err}`;
+    if (statusText) statusText.textContent = `Download fehlgeschlagen: ${errorMessage(err)}`;
     if (downloadBtn) {
       downloadBtn.disabled = false;
       downloadBtn.textContent = "Erneut versuchen";
@@ -1,6 +1,6 @@
 // Overlay settings rendering (R3 slice 2).
 //
-// Renders the "Overlay appearance" settings panel: dot/KITT style visibility,
+// Renders the "Overlay appearance" settings panel: dot/KITT/waveform style visibility,
 // shared appearance (colour, rise/fall timing, opacity, position), radius
 // sliders, dimension bounds, refining indicator controls, and TTS-stop button.
 //
@@ -8,6 +8,8 @@
 //   Primary   — renderOverlaySettings()         called by renderSettings() in index.ts
 //   Secondary — updateOverlayStyleVisibility()  called by overlay.wire.ts
 //               applyOverlaySharedUi()          called by overlay.wire.ts
+//               overlayStyleUsesBar()           called by overlay.wire.ts
+//               setOverlayMonitorOptions()      called by overlay.wire.ts
 //
 // Per Decision 6 (settings-decomposition.md): all other functions are private.
 // posX/posY ownership lives entirely in applyOverlaySharedUi().
@@ -15,7 +17,9 @@
 import * as dom from "../dom-refs";
 import { settings } from "../state";
 import { DEFAULT_ACCENT_COLOR, normalizeColorHex } from "../utils";
-import type { OverlayRefiningIndicatorPreset } from "../types";
+import type { OverlayMonitorInfo, OverlayRefiningIndicatorPreset } from "../types";
+
+let overlayMonitors: OverlayMonitorInfo[] = [];
 
 function detectOverlayViewport(): { width: number; height: number } {
     const screenWidth = Number(
@@ -62,15 +66,20 @@ function clampToSliderBounds(input: HTMLInputElement, value: number): number {
     return out;
 }
 
+/** KITT and waveform both draw a bar and share the overlay_kitt_* settings. */
+export function overlayStyleUsesBar(style: string | undefined): boolean {
+    return style === "kitt" || style === "waveform";
+}
+
 export function updateOverlayStyleVisibility(style: string) {
-    const isKitt = style === "kitt";
+    const isKitt = overlayStyleUsesBar(style);
     if (dom.overlayDotSettings) dom.overlayDotSettings.style.display = isKitt ? "none" : "block";
     if (dom.overlayKittSettings) dom.overlayKittSettings.style.display = isKitt ? "block" : "none";
 }
 
 function getOverlaySharedSettings(style: string, current: typeof settings) {
     if (!current) return null;
-    if (style === "kitt") {
+    if (overlayStyleUsesBar(style)) {
         return {
             color: current.overlay_kitt_color,
             rise_ms: current.overlay_kitt_rise_ms,
@@ -128,12 +137,12 @@ export function applyOverlaySharedUi(style: string) {
     }
     if (dom.overlayPosX) {
         dom.overlayPosX.value = Math.round(
-            style === "kitt" ? settings.overlay_kitt_pos_x : settings.overlay_pos_x
+            overlayStyleUsesBar(style) ? settings.overlay_kitt_pos_x : settings.overlay_pos_x
         ).toString();
     }
     if (dom.overlayPosY) {
         dom.overlayPosY.value = Math.round(
-            style === "kitt" ? settings.overlay_kitt_pos_y : settings.overlay_pos_y
+            overlayStyleUsesBar(style) ? settings.overlay_kitt_pos_y : settings.overlay_pos_y
         ).toString();
     }
 }
@@ -161,6 +170,29 @@ function normalizeOverlayRefiningPreset(
     return "standard";
 }
 
+/** Replaces the connected-monitor list shown in the monitor picker. */
+export function setOverlayMonitorOptions(monitors: OverlayMonitorInfo[]) {
+    overlayMonitors = monitors;
+    renderOverlayMonitorSelect();
+}
+
+function renderOverlayMonitorSelect() {
+    const select = dom.overlayMonitor;
+    if (!select || !settings) return;
+    const selected = settings.overlay_monitor ?? "";
+    // Keep the two fixed options ("follow cursor", "primary") from the markup.
+    while (select.options.length > 2) select.remove(2);
+    for (const monitor of overlayMonitors) {
+        const label = `${monitor.name} (${monitor.width}×${monitor.height}${monitor.primary ? ", primary" : ""})`;
+        select.add(new Option(label, monitor.name));
+    }
+    // A pinned monitor that is currently unplugged stays selected.
+    if (selected && selected !== "primary" && !overlayMonitors.some((m) => m.name === selected)) {
+        select.add(new Option(`${selected} (disconnected)`, selected));
+    }
+    select.value = selected;
+}
+
 export function renderOverlaySettings(): void {
     if (!settings) return;
 
@@ -186,6 +218,7 @@ export function renderOverlaySettings(): void {
     if (dom.overlayMaxRadiusValue) dom.overlayMaxRadiusValue.textContent = `${Math.round(settings.overlay_max_radius)}`;
     const overlayStyleValue = settings.overlay_style || "dot";
     if (dom.overlayStyle) dom.overlayStyle.value = overlayStyleValue;
+    renderOverlayMonitorSelect();
     if (dom.overlayRefiningIndicatorEnabled) {
         dom.overlayRefiningIndicatorEnabled.checked = settings.overlay_refining_indicator_enabled ?? true;
     }
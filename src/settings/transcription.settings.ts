@@ -39,7 +39,7 @@ export function updateTranscribeThreshold(threshold: number): void {
 
 export function syncCaptureModeVisibility(mode: string, pttUseVad = false): void {
     const hotkeysEnabled = mode === "ptt";
-    const vadEnabled = mode === "vad" || (mode === "ptt" && pttUseVad);
+    const vadEnabled = mode === "vad" || mode === "wakeword" || (mode === "ptt" && pttUseVad);
     if (dom.hotkeysBlock) dom.hotkeysBlock.classList.toggle("hidden", !hotkeysEnabled);
     if (dom.vadBlock) dom.vadBlock.classList.toggle("hidden", !vadEnabled);
     // In PTT+VAD mode we only use threshold gating while the key is held.
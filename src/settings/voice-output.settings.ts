@@ -10,6 +10,7 @@ import type {
   TtsVoiceInfo,
   TtsProviderInfo,
 } from "../types";
+import { errorMessage } from "../utils";
 
 // Voice Output settings rendering (R3 slice 4).
 //
@@ -456,7 +457,7 @@ export async function refreshProviderVoices(target: "default" | "fallback"): Pro
       select.value = fallbackOption.value;
       select.disabled = false;
       if (hint) {
-        hint.textContent = `Piper-Stimmliste nicht verfügbar: ${String(error).replace(/^Error:\s*/i, "").trim()}`;
+        hint.textContent = `Piper-Stimmliste nicht verfügbar: ${errorMessage(error).replace(/^Error:\s*/i, "").trim()}`;
       }
       return;
     }
@@ -519,7 +520,7 @@ export async function refreshProviderVoices(target: "default" | "fallback"): Pro
     select.value = "";
     select.disabled = false;
     if (hint) {
-      hint.textContent = `Stimmliste nicht verfügbar: ${String(error).replace(/^Error:\s*/i, "").trim()}`;
+      hint.textContent = `Stimmliste nicht verfügbar: ${errorMessage(error).replace(/^Error:\s*/i, "").trim()}`;
     }
   }
 }
@@ -790,11 +791,11 @@ export async function handleProviderVoiceSelection(target: "default" | "fallback
       select.value = previous;
       setPiperDownloadProgressUi(
         0,
-        `${nextKey}: Download fehlgeschlagen (${String(error).replace(/^Error:\s*/i, "").trim()}).`,
+        `${nextKey}: Download fehlgeschlagen (${errorMessage(error).replace(/^Error:\s*/i, "").trim()}).`,
         { forceVisible: true }
       );
       if (hint) {
-        hint.textContent = `Download fehlgeschlagen (${nextKey}): ${String(error).replace(/^Error:\s*/i, "").trim()}`;
+        hint.textContent = `Download fehlgeschlagen (${nextKey}): ${errorMessage(error).replace(/^Error:\s*/i, "").trim()}`;
       }
       return;
     }
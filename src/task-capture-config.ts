@@ -1,6 +1,7 @@
 import { invoke } from "@tauri-apps/api/core";
 import { showToast } from "./toast";
 import type { TaskCaptureRoute, TaskCaptureSettings } from "./types";
+import { errorMessage } from "./utils";
 
 function escapeHtml(value: string): string {
   return value
@@ -50,7 +51,7 @@ export async function renderTaskCaptureTab(): Promise<void> {
   try {
     loadedSettings = await invoke<TaskCaptureSettings>("get_task_capture_settings");
   } catch (error) {
-    container.innerHTML = `<p class="field-hint" style="color:var(--c-error)">${escapeHtml(`Failed to load settings: ${String(error)}`)}</p>`;
+    container.innerHTML = `<p class="field-hint" style="color:var(--c-error)">${escapeHtml(`Failed to load settings: ${errorMessage(error)}`)}</p>`;
     return;
   }
 
@@ -208,7 +209,7 @@ export async function renderTaskCaptureTab(): Promise<void> {
           showToast({
             type: "error",
             title: "Connection Failed",
-            message: String(error),
+            message: errorMessage(error),
             duration: 5000,
           });
         } finally {
@@ -336,7 +337,7 @@ export async function renderTaskCaptureTab(): Promise<void> {
         showToast({
           type: "error",
           title: "Save failed",
-          message: String(error),
+          message: errorMessage(error),
           duration: 5000,
         });
       } finally {
@@ -1,5 +1,5 @@
 // Toast notification system
-import type { ToastType, ToastOptions, AppErrorType } from "./types";
+import type { ToastType, ToastOptions, AppErrorType, ErrorCode } from "./types";
 
 let toastCounter = 0;
 const shownHotkeyErrors = new Set<string>();
@@ -24,6 +24,9 @@ export function showToast(options: ToastOptions): string | null {
   const toast = document.createElement("div");
   toast.id = id;
   toast.className = `toast ${type}`;
+  if (options.code) {
+    toast.dataset.code = options.code;
+  }
 
   const iconEl = document.createElement("span");
   iconEl.className = "toast-icon";
@@ -42,6 +45,13 @@ export function showToast(options: ToastOptions): string | null {
 
   contentEl.append(titleEl, messageEl);
 
+  if (options.hint) {
+    const hintEl = document.createElement("div");
+    hintEl.className = "toast-hint";
+    hintEl.textContent = options.hint;
+    contentEl.appendChild(hintEl);
+  }
+
   if (options.actionLabel && options.onAction) {
     const actionsEl = document.createElement("div");
     actionsEl.className = "toast-actions";
@@ -101,7 +111,11 @@ function removeToast(id: string) {
   }, 200);
 }
 
-export function showErrorToast(error: AppErrorType, context?: string) {
+export function showErrorToast(
+  error: AppErrorType,
+  context?: string,
+  details?: { code?: ErrorCode; hint?: string | null },
+) {
   const typeMapping: Record<string, string> = {
     AudioDevice: "Audio Device Issue",
     Transcription: "Transcription Failed",
@@ -118,10 +132,13 @@ export function showErrorToast(error: AppErrorType, context?: string) {
     shownHotkeyErrors.add(key);
   }
 
+  const message = context ? `${context}: ${error.message}` : error.message;
   showToast({
     type: "error",
     title: typeMapping[error.type] || "Error",
-    message: context ? `${context}: ${error.message}` : error.message,
+    message,
+    code: details?.code,
+    hint: details?.hint,
     duration: 7000,
   });
 }
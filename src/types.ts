@@ -8,6 +8,15 @@ export type ProductMode = "transcribe" | "assistant";
 export type AIProviderAuthStatus = "locked" | "verified_api_key" | "verified_oauth";
 export type AIProviderAuthMethodPreference = "api_key" | "oauth";
 export type OverlayRefiningIndicatorPreset = "subtle" | "standard" | "intense";
+export interface OverlayMonitorInfo {
+  name: string;
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+  scale_factor: number;
+  primary: boolean;
+}
 export type ModuleSurface = "assistant" | "transcription" | "shared" | "ui";
 export type AssistantActionRisk = "low" | "medium" | "high";
 export type ModuleId =
@@ -84,6 +93,102 @@ export type PromptPresetOverrides = Partial<
 >;
 
 /** A correction the user made by editing the pasted refinement output before submitting. */
+export type CloudTranscriptionProvider = "openai" | "groq" | "deepgram" | "custom";
+
+export interface CloudTranscriptionSettings {
+  enabled: boolean;
+  provider: CloudTranscriptionProvider;
+  /** Provider model id; empty uses the provider default. */
+  model: string;
+  /** Base URL override (required for `custom`, optional for the others). */
+  endpoint: string;
+  /** Engines tried in order until one succeeds; "cloud" is skipped while disabled. */
+  fallback_order: TranscriptionEngine[];
+}
+
+export type TranscriptionEngine = "local" | "cloud";
+
+export type TranslationProvider = "deepl" | "google";
+
+export interface TranslationSettings {
+  enabled: boolean;
+  provider: TranslationProvider;
+  /** Language code to translate into, e.g. "en", "de", "pt-BR". */
+  target_language: string;
+  /** Paste the translation instead of the original transcript. */
+  paste_translation: boolean;
+}
+
+/** Payload of the `transcription:engine` event. */
+export interface TranscriptionEngineEvent {
+  engine: TranscriptionEngine;
+  provider: string;
+  source: string;
+  fallback_used: boolean;
+  failed_engines: { engine: TranscriptionEngine; error: string }[];
+}
+
+export interface ReplacementRule {
+  find: string;
+  replace: string;
+  /** Treat `find` as a regular expression (`replace` may use `$1` captures). */
+  regex: boolean;
+  case_sensitive: boolean;
+  /** Plain rules only: require word boundaries around the match. */
+  whole_word: boolean;
+  enabled: boolean;
+}
+
+export type VoiceCommandAction =
+  | "new_line"
+  | "new_paragraph"
+  | "comma"
+  | "period"
+  | "question_mark"
+  | "exclamation_mark"
+  | "colon"
+  | "all_caps"
+  | "delete_that"
+  | "undo_that"
+  | "insert_text";
+
+export type OutputMode = "paste" | "clipboard" | "type" | "inject";
+
+/** "rules" is the post-processed text without LLM cleanup. */
+export type Rendition = "raw" | "rules" | "processed";
+
+/** Settings applied while `process_name` (e.g. "code.exe") is in the foreground. */
+export interface AppOverride {
+  process_name: string;
+  language_mode?: string | null;
+  postproc_enabled?: boolean | null;
+  auto_paste_enabled?: boolean | null;
+  output_mode?: OutputMode | null;
+}
+
+export interface VoiceCommandPhrase {
+  phrase: string;
+  action: VoiceCommandAction;
+  /** Replacement text for `insert_text`; ignored by other actions. */
+  text?: string;
+}
+
+export type DictationMacroAction =
+  | "open_settings"
+  | "start_transcribing"
+  | "stop_transcribing"
+  | "toggle_transcribing"
+  | "toggle_privacy_mute"
+  | "switch_language"
+  | "activate_profile";
+
+export interface DictationMacro {
+  phrase: string;
+  action: DictationMacroAction;
+  /** Language code for `switch_language`, profile name for `activate_profile`. */
+  argument?: string;
+}
+
 export interface EditSubstitution {
   /** Original token as it appeared in the refinement output. */
   from: string;
@@ -544,6 +649,8 @@ export interface PiperVoiceCatalogEntry {
   installed: boolean;
   curated: boolean;
   path: string | null;
+  /** Downloaded voice that `remove_piper_voice_key` can delete. */
+  removable?: boolean;
 }
 
 export interface PiperVoiceDownloadProgress {
@@ -630,17 +737,36 @@ export interface SetupSettings {
 }
 
 export interface Settings {
-  mode: "ptt" | "vad";
+  schema_version?: number;
+  mode: "ptt" | "vad" | "wakeword";
   product_mode: ProductMode;
   hotkey_ptt: string;
   hotkey_toggle: string;
   hotkey_tts_stop: string;
   input_device: string;
+  /** 1-based channel of `input_device` to capture; 0 mixes all channels. */
+  input_channel: number;
+  /** VAD thresholds and mic gain last used with each input device id. */
+  device_input_profiles?: Record<string, DeviceInputProfile>;
   language_mode: "auto" | "en" | "de" | "fr" | "es" | "it" | "pt" | "nl" | "pl" | "ru" | "ja" | "ko" | "zh" | "ar" | "tr" | "hi";
   language_pinned: boolean;
+  /** Whisper translate-to-English; history entries are tagged `local-translated`. */
+  translate_to_english: boolean;
+  unexpected_language_action?: "off" | "flag" | "reject";
+  expected_languages?: string[];
   model: string;
+  /** Decoding parameter overrides for whisper-cli; unset uses the model default. */
+  whisper_advanced?: WhisperAdvancedSettings;
+  /** Retries of a transient transcription failure (0 = off, max 5). */
+  transcribe_retry_attempts?: number;
+  /** Wait before the first retry in ms; doubled for each later one. */
+  transcribe_retry_backoff_ms?: number;
   // Legacy compatibility toggle for optional old cloud transcription path.
   cloud_fallback: boolean;
+  /** Cloud speech-to-text provider selection (OpenAI, Groq, Deepgram, custom). */
+  cloud_transcription?: CloudTranscriptionSettings;
+  /** Translation of final mic transcripts (DeepL, Google). */
+  translation?: TranslationSettings;
   ai_fallback: AIFallbackSettings;
   providers: AIProvidersSettings;
   setup: SetupSettings;
@@ -660,9 +786,49 @@ export interface Settings {
   assistant_presence_window_monitor?: string | null;
   audio_cues: boolean;
   audio_cues_volume: number;
+  /** Folder under `<data>/sound_packs`; "default" = built-in tones. */
+  audio_cue_pack?: string;
+  /** Per-cue volume (0–1), applied on top of `audio_cues_volume`. */
+  audio_cue_volumes?: AudioCueVolumes;
   diagnostic_logging_enabled?: boolean;
+  log_level?: string;
+  /** Wake-word mode: catalogue id or absolute path to a custom `.onnx` model. */
+  wake_word_model?: string;
+  wake_word_sensitivity?: number;
+  wake_word_threads?: number;
+  wake_word_energy_gate?: number;
+  /** Pause VAD/system-audio capture while a fullscreen app or call is active. */
+  auto_pause_fullscreen?: boolean;
+  auto_pause_calls?: boolean;
+  auto_pause_call_apps?: string[];
+  /** Weekly windows outside which mic monitoring and system audio stay off. */
+  capture_schedule?: CaptureSchedule;
+  /** Token-guarded automation API on 127.0.0.1. */
+  http_api_enabled?: boolean;
+  http_api_port?: number;
+  webhooks?: Webhook[];
+  /** Latest captions as a text file (OBS "read from file") and SSE stream. */
+  caption_file_enabled?: boolean;
+  caption_file_path?: string;
+  caption_file_max_chars?: number;
+  caption_file_clear_secs?: number;
+  caption_file_include_mic?: boolean;
+  /** Folders whose new audio/video files are transcribed to .txt/.srt. */
+  watch_folders?: WatchFolder[];
+  batch_max_parallel?: number;
+  /** Session recording codec; flac/wav do not need the opus module. */
+  recording_codec?: "opus" | "flac" | "wav";
+  /** Empty = recordings/ in the data directory. */
+  recordings_dir?: string;
+  recordings_max_age_days?: number;
+  recordings_max_total_mb?: number;
+  /** Call recordings: separate mic/system files, or one stereo file (mic left). */
+  call_recording_layout?: "multitrack" | "stereo";
   ptt_use_vad: boolean;
   ptt_hot_keepalive_ms: number;
+  pre_roll_ms?: number;
+  /** Cut PTT/toggle/VAD recordings into segments after this long; 0 = no limit. */
+  max_recording_ms?: number;
   vad_threshold: number;
   vad_threshold_start: number;
   vad_threshold_sustain: number;
@@ -670,6 +836,23 @@ export interface Settings {
   transcribe_enabled: boolean;
   transcribe_hotkey: string;
   hotkey_toggle_activation_words: string;
+  hotkey_cycle_profile?: string;
+  /** Toggles the session-only privacy mute (all capture off). */
+  hotkey_privacy_mute?: string;
+  /** Removes the last dictation from the focused window. */
+  hotkey_undo_paste?: string;
+  undo_paste_mode?: "backspace" | "select";
+  /** One key: tap = toggle, double-tap = system transcription, hold = PTT. */
+  hotkey_gesture?: string;
+  hotkey_gesture_hold_ms?: number;
+  hotkey_gesture_double_tap_ms?: number;
+  ptt_lock_enabled?: boolean;
+  ptt_lock_tap_ms?: number;
+  /** Game controller / foot pedal button as PTT. */
+  ptt_gamepad_enabled?: boolean;
+  /** Controller name; empty = any controller. */
+  ptt_gamepad_device?: string;
+  ptt_gamepad_button?: string;
   hotkey_product_mode_toggle?: string;
   transcribe_output_device: string;
   transcribe_vad_mode: boolean;
@@ -677,8 +860,26 @@ export interface Settings {
   transcribe_vad_silence_ms: number;
   transcribe_batch_interval_ms: number;
   transcribe_chunk_overlap_ms: number;
+  transcribe_parallel_workers?: number;
+  transcribe_adaptive_chunking?: boolean;
+  /** Prompt whisper with the previous system-audio chunk's text for continuity. */
+  transcribe_context_priming?: boolean;
   transcribe_input_gain_db: number;
   mic_input_gain_db: number;
+  /** Mic DSP chain: echo cancellation against the system-audio output (Windows). */
+  mic_echo_cancellation?: boolean;
+  /** Mic DSP chain: high-pass filter (cutoff 20–400 Hz). */
+  mic_highpass_enabled: boolean;
+  mic_highpass_cutoff_hz: number;
+  /** Mic DSP chain: RNNoise noise suppression (adds 10 ms of latency). */
+  noise_suppression: boolean;
+  /** Mic DSP chain: noise gate threshold in dBFS (-90 to -10). */
+  mic_noise_gate_enabled: boolean;
+  mic_noise_gate_threshold_db: number;
+  /** Automatic gain control; `mic_input_gain_db` is only the starting gain. */
+  mic_agc_enabled: boolean;
+  /** AGC speech level target in dBFS (-40 to -6). */
+  mic_agc_target_db: number;
   history_alias_mic: string;
   history_alias_system: string;
   capture_enabled: boolean;
@@ -702,6 +903,8 @@ export interface Settings {
   overlay_pos_y: number;
   overlay_kitt_pos_x: number;
   overlay_kitt_pos_y: number;
+  /** "" follows the cursor, "primary", or a monitor name. */
+  overlay_monitor?: string;
   overlay_style: string;
   overlay_refining_indicator_enabled: boolean;
   overlay_refining_indicator_preset: OverlayRefiningIndicatorPreset;
@@ -714,9 +917,21 @@ export interface Settings {
   overlay_kitt_min_width: number;
   overlay_kitt_max_width: number;
   overlay_kitt_height: number;
+  captions_enabled?: boolean;
+  captions_window_secs?: number;
+  captions_font_family?: string;
+  captions_font_size?: number;
+  captions_position?: "top" | "bottom" | string;
+  captions_background_opacity?: number;
+  captions_include_mic?: boolean;
   hallucination_filter_enabled: boolean;
+  /** Hallucination phrases per whisper language code ("en", "de", ...). */
+  hallucination_phrases?: Record<string, string[]>;
+  hallucination_allowed_phrases?: string[];
   activation_words_enabled: boolean;
   activation_words: string[];
+  /** Remove the matched activation word/phrase from the transcript. */
+  activation_words_strip?: boolean;
   topic_keywords: Record<string, string[]>;
   // Post-processing settings
   postproc_enabled: boolean;
@@ -737,6 +952,29 @@ export interface Settings {
   edit_substitutions?: EditSubstitution[];
   /** Set to true after the one-time migration that clears legacy heuristic data. */
   edit_delta_migrated?: boolean;
+  /** User-defined find/replace rules applied after custom vocabulary. */
+  postproc_replacement_rules_enabled?: boolean;
+  postproc_replacement_rules?: ReplacementRule[];
+  /** Interpret spoken editing commands ("new line", "delete that") in mic transcripts. */
+  voice_commands_enabled?: boolean;
+  /** User-defined command phrases; these take precedence over the built-ins. */
+  voice_commands_custom?: VoiceCommandPhrase[];
+  /** Also turn spoken punctuation ("comma", "period") into marks; off by default. */
+  voice_commands_punctuation?: boolean;
+  /** Run app actions for spoken macro phrases ("open settings") instead of pasting them. */
+  dictation_macros_enabled?: boolean;
+  dictation_macros?: DictationMacro[];
+  snippets_enabled?: boolean;
+  snippets?: Snippet[];
+  auto_paste_enabled?: boolean;
+  output_mode?: OutputMode;
+  clipboard_restore_enabled?: boolean;
+  output_template_mic?: string;
+  output_template_system?: string;
+  /** Post-processing rendition that is pasted / stored in history. */
+  paste_rendition?: Rendition;
+  history_rendition?: Rendition;
+  app_overrides?: AppOverride[];
   /** Unix-ms timestamp of the last successful LLM vocab cleanup run. */
   last_vocab_cleanup_ms?: number;
   postproc_llm_enabled: boolean;
@@ -744,11 +982,28 @@ export interface Settings {
   postproc_llm_api_key: string;
   postproc_llm_model: string;
   postproc_llm_prompt: string;
+  postproc_llm_cleanup_enabled?: boolean;
+  postproc_llm_endpoint?: string;
+  postproc_prompt?: string;
+  postproc_llm_cleanup_model?: string;
+  postproc_llm_timeout_ms?: number;
+  content_filter_enabled?: boolean;
+  content_filter_profanity?: boolean;
+  content_filter_pii?: boolean;
+  content_filter_action?: "mask" | "drop" | "tag";
+  content_filter_words?: string[];
   // Recording export settings
   opus_enabled?: boolean;
   opus_bitrate_kbps?: number;
   auto_save_system_audio?: boolean;
   auto_save_mic_audio?: boolean;
+  entry_audio_enabled?: boolean;
+  entry_audio_max_days?: number;
+  entry_audio_max_mb?: number;
+  history_max_entries?: number;
+  history_max_age_days?: number;
+  history_max_disk_mb?: number;
+  history_encryption_mode?: "off" | "keychain" | "passphrase";
   continuous_dump_enabled?: boolean;
   continuous_dump_profile?: "balanced" | "low_latency" | "high_quality";
   continuous_soft_flush_ms?: number;
@@ -762,12 +1017,15 @@ export interface Settings {
   continuous_mic_soft_flush_ms?: number;
   continuous_mic_silence_flush_ms?: number;
   continuous_mic_hard_cut_ms?: number;
+  /** Continuous dump off: flush toggle recordings at the next pause after this long; 0 = on stop only. */
+  mic_flush_interval_ms?: number;
   continuous_system_override_enabled?: boolean;
   continuous_system_soft_flush_ms?: number;
   continuous_system_silence_flush_ms?: number;
   continuous_system_hard_cut_ms?: number;
   transcribe_backend?: "whisper_cpp";
-  local_backend_preference?: "auto" | "cuda" | "vulkan";
+  local_backend_preference?: "auto" | "cuda" | "vulkan" | "cpu";
+  whisper_server_keep_warm?: boolean;
   // Window state fields from backend
   main_window_x?: number | null;
   main_window_y?: number | null;
@@ -792,6 +1050,116 @@ export interface HistoryEntry {
   source: string;
   speaker_name?: string | null;
   refinement?: HistoryRefinement | null;
+  audio_path?: string | null;
+  revisions?: HistoryRevision[];
+  pinned?: boolean;
+  tags?: string[];
+  note?: string | null;
+  formatted_text?: string | null;
+  language?: string | null;
+  translation?: HistoryTranslation | null;
+}
+
+/** Filter for `export_history`; omitted fields select everything. */
+export interface HistoryExportFilter {
+  kind?: "mic" | "system";
+  from_ms?: number | null;
+  to_ms?: number | null;
+  tag?: string | null;
+  pinned_only?: boolean;
+}
+
+/** Result entry of `list_tags`. */
+export interface TagCount {
+  tag: string;
+  count: number;
+}
+
+/** Result of `get_history_encryption_status` / `set_history_encryption`. */
+/** A named snapshot of context-dependent settings (see `save_profile`). */
+export interface SettingsProfile {
+  name: string;
+  values: Partial<Settings>;
+  updated_ms: number;
+}
+
+/** Result of `list_profiles`. */
+export interface SettingsProfileStore {
+  active: string | null;
+  profiles: SettingsProfile[];
+}
+
+export interface HistoryEncryptionStatus {
+  mode: "off" | "keychain" | "passphrase";
+  locked: boolean;
+}
+
+/** Result of `prune_history_now`. */
+export interface HistoryPruneReport {
+  entries_removed: number;
+  partitions_removed: number;
+  bytes_freed: number;
+}
+
+/** Re-transcription of an entry's saved recording with another model. */
+export interface HistoryRevision {
+  model: string;
+  text: string;
+  created_ms: number;
+}
+
+/** Machine translation of a history entry's `text`; also returned by `translate_text`. */
+export interface HistoryTranslation {
+  text: string;
+  target_language: string;
+  provider: TranslationProvider;
+  source_language?: string | null;
+  /** Whether the translation was pasted instead of the original. */
+  pasted: boolean;
+}
+
+/** Named meeting that groups every history entry pushed while it ran. */
+export interface TranscriptSessionSummary {
+  id: string;
+  name: string;
+  started_ms: number;
+  ended_ms: number | null;
+  active: boolean;
+  segment_count: number;
+  word_count: number;
+}
+
+export interface TranscriptSegment {
+  entry_id: string;
+  text: string;
+  timestamp_ms: number;
+  source: string;
+  speaker_name?: string | null;
+}
+
+export interface TranscriptSession {
+  id: string;
+  name: string;
+  started_ms: number;
+  ended_ms: number | null;
+  segments: TranscriptSegment[];
+  ai_summary?: SessionSummaryNote;
+  chapters?: SessionChapter[];
+}
+
+/** Chapter boundary; `title` is empty until renamed or auto-titled. */
+export interface SessionChapter {
+  id: string;
+  timestamp_ms: number;
+  title: string;
+}
+
+export interface SessionSummaryNote {
+  style: "bullets" | "action_items" | "meeting";
+  text: string;
+  provider: string;
+  model: string;
+  created_ms: number;
 }
 
 export interface HistoryRefinement {
@@ -802,6 +1170,8 @@ export interface HistoryRefinement {
   model: string;
   execution_time_ms?: number | null;
   error: string;
+  /** Rendition `raw` came from; may differ from the entry text. Empty on older entries. */
+  rendition?: "raw" | "rules" | "processed" | "";
 }
 
 export interface TopicScore {
@@ -815,6 +1185,14 @@ export interface AudioDevice {
   label: string;
 }
 
+/** Payload of `audio:device-lost` / `audio:device-restored`. */
+export interface AudioDeviceChangeEvent {
+  direction: "input" | "output";
+  device_id: string;
+  /** Device the stream fell back to (`audio:device-lost` only). */
+  fallback: "default" | null;
+}
+
 export interface ModelInfo {
   id: string;
   label: string;
@@ -827,6 +1205,11 @@ export interface ModelInfo {
   available: boolean;
   download_url?: string;
   removable: boolean;
+  format?: "ggml" | "gguf" | null;
+  quantization?: string | null;
+  parameter_count?: number | null;
+  is_whisper?: boolean;
+  header_error?: string | null;
 }
 
 export interface DownloadProgress {
@@ -835,6 +1218,66 @@ export interface DownloadProgress {
   total?: number;
 }
 
+export type DownloadStatus = "queued" | "downloading" | "paused";
+
+export interface DownloadJob {
+  model_id: string;
+  url: string;
+  file_name: string;
+  status: DownloadStatus;
+  downloaded: number;
+  total?: number | null;
+}
+
+export interface TranscriptionJob {
+  id: string;
+  source: "mic" | "loopback" | "file";
+  state: "queued" | "running" | "done" | "failed" | "cancelled";
+  audio_ms: number;
+  created_ms: number;
+  started_ms?: number | null;
+  finished_ms?: number | null;
+  error?: string | null;
+}
+
+export interface HardwareProfile {
+  cpu_cores: number;
+  avx: boolean;
+  avx2: boolean;
+  avx512: boolean;
+  gpu_name?: string | null;
+  gpu_vendor?: "nvidia" | "amd" | "intel" | "other" | null;
+  vram_mb?: number | null;
+  cuda_available: boolean;
+}
+
+export interface ModelRecommendation {
+  model_id: string;
+  gpu_layers?: number | null;
+  backend: "cuda" | "vulkan" | "auto";
+  reason: string;
+  profile: HardwareProfile;
+}
+
+export interface ModelBenchmarkProgress {
+  model_id: string;
+  stage: "preparing" | "running" | "done";
+  run: number;
+  total_runs: number;
+}
+
+export interface ModelBenchmarkResult {
+  model_id: string;
+  runs: number;
+  audio_ms: number;
+  median_ms: number;
+  realtime_factor: number;
+  peak_ram_mb?: number | null;
+  accelerator: string;
+  backend: string;
+  whisper_path: string;
+}
+
 export interface DownloadComplete {
   id: string;
   path: string;
@@ -859,13 +1302,44 @@ export interface ValidationResult {
   formatted: string | null;
 }
 
+/** Result of `capture_next_hotkey`: the pressed combination and its validation. */
+export interface CapturedHotkey {
+  accelerator: string;
+  validation: ValidationResult;
+}
+
 export interface AppErrorType {
   type: "AudioDevice" | "Transcription" | "Hotkey" | "Storage" | "Network" | "Window" | "Other";
   message: string;
 }
 
+/** Stable error codes shared with `errors.rs::ErrorCode`; safe to key handling and translations on. */
+export type ErrorCode =
+  | "audio_device"
+  | "transcription"
+  | "model_not_found"
+  | "runtime_missing"
+  | "hotkey"
+  | "storage"
+  | "network"
+  | "credentials"
+  | "invalid_input"
+  | "not_found"
+  | "cancelled"
+  | "window"
+  | "internal";
+
+/** Rejection value of every Tauri command (`CommandResult` in `errors.rs`). */
+export interface CommandError {
+  code: ErrorCode;
+  message: string;
+  hint: string | null;
+}
+
 export interface ErrorEvent {
   error: AppErrorType;
+  code?: ErrorCode;
+  hint?: string | null;
   timestamp: number;
   context?: string;
 }
@@ -899,6 +1373,10 @@ export interface ToastOptions {
   actionLabel?: string;
   onAction?: () => void | Promise<void>;
   actionDismiss?: boolean;
+  /** Stable code of the failure behind an error toast. */
+  code?: ErrorCode;
+  /** Remediation shown on its own line below the message. */
+  hint?: string | null;
 }
 
 export interface TranscribeBacklogStatus {
@@ -916,6 +1394,7 @@ export interface TranscriptionResultEvent {
   paste_deferred?: boolean;
   paste_timeout_ms?: number;
   entry_id?: string;
+  detected_language?: string;
   refinement_gate?: RefinementGateDecision;
 }
 
@@ -1196,3 +1675,419 @@ export interface HardwareInfo {
   driver_version: string;
   update_url?: string | null;
 }
+
+export interface MicTestResult {
+  rms: number;
+  peak: number;
+  duration_ms: number;
+  silent: boolean;
+  playback_path: string;
+}
+
+export interface WhisperRuntimeCheck {
+  cli_path?: string | null;
+  cli_runs: boolean;
+  model: string;
+  model_path?: string | null;
+  error?: string | null;
+}
+
+export interface SetupStatus {
+  microphone_ready: boolean;
+  whisper_cli_ready: boolean;
+  model_ready: boolean;
+  model: string;
+  hotkey_ready: boolean;
+  hotkey: string;
+  complete: boolean;
+  issues: string[];
+}
+
+export interface LogPaths {
+  directory: string;
+  current?: string | null;
+  errors?: string | null;
+  level: string;
+}
+
+export interface CrashReport {
+  path: string;
+  created_ms: number;
+  contents: string;
+}
+
+export interface DayUsage {
+  date: string;
+  dictations: number;
+  minutes: number;
+  words: number;
+  avg_latency_ms?: number | null;
+  top_model?: string | null;
+}
+
+export interface UsageStats {
+  range: string;
+  days: DayUsage[];
+  dictations: number;
+  minutes: number;
+  words: number;
+  avg_latency_ms?: number | null;
+  models: Record<string, number>;
+}
+
+export interface LatencyBreakdown {
+  capture_to_whisper_ms?: number | null;
+  whisper_ms: number;
+  postprocess_ms: number;
+  paste_ms?: number | null;
+  total_ms: number;
+  whisper_path: string;
+}
+
+export interface LatencyStageStats {
+  samples: number;
+  avg_ms: number;
+  p50_ms: number;
+  p95_ms: number;
+  max_ms: number;
+}
+
+export interface LatencyStats {
+  jobs: number;
+  capture_to_whisper?: LatencyStageStats | null;
+  whisper?: LatencyStageStats | null;
+  postprocess?: LatencyStageStats | null;
+  paste?: LatencyStageStats | null;
+  total?: LatencyStageStats | null;
+  recent: LatencyBreakdown[];
+}
+
+export interface WakeWordModelInfo {
+  id: string;
+  label: string;
+  installed: boolean;
+  custom: boolean;
+}
+
+export interface CapturePolicyState {
+  paused: boolean;
+  reason?: "fullscreen" | "call" | null;
+  app?: string | null;
+}
+
+export interface ScheduleWindow {
+  /** 0 = Monday … 6 = Sunday. */
+  days: number[];
+  /** "HH:MM"; an end at or before start runs into the next day. */
+  start: string;
+  end: string;
+  mic: boolean;
+  system_audio: boolean;
+}
+
+export interface CaptureSchedule {
+  enabled: boolean;
+  windows: ScheduleWindow[];
+}
+
+export interface ScheduleStatus {
+  enabled: boolean;
+  mic_allowed: boolean;
+  system_audio_allowed: boolean;
+}
+
+export interface HttpApiInfo {
+  enabled: boolean;
+  running: boolean;
+  url: string;
+  /** WebSocket event stream; also accepts `?token=` and `?events=a,b`. */
+  events_url: string;
+  mcp_url: string;
+  token: string;
+}
+
+export interface Webhook {
+  url: string;
+  enabled: boolean;
+  filter: "all" | "mic" | "system" | "session_end";
+}
+
+export interface FileTranscriptSegment {
+  start_ms: number;
+  end_ms: number;
+  text: string;
+}
+
+export interface FileTranscription {
+  path: string;
+  text: string;
+  duration_ms: number;
+  segments: FileTranscriptSegment[];
+  entry_id?: string | null;
+}
+
+export interface FileTranscriptionProgress {
+  path: string;
+  processed_ms: number;
+  total_ms: number;
+  done: boolean;
+}
+
+export interface WatchFolder {
+  path: string;
+  enabled: boolean;
+  recursive: boolean;
+  model: string;
+  language: string;
+  write_txt: boolean;
+  write_srt: boolean;
+}
+
+export interface WatchFolderResult {
+  path: string;
+  outputs: string[];
+  error?: string | null;
+}
+
+export interface BatchJobEntry {
+  id: number;
+  label: string;
+  created_ms: number;
+  started_ms?: number | null;
+}
+
+export interface BatchQueueStatus {
+  paused: boolean;
+  max_parallel: number;
+  running: BatchJobEntry[];
+  queued: BatchJobEntry[];
+}
+
+export interface RecordingInfo {
+  id: string;
+  name?: string | null;
+  source: string;
+  started_at: string;
+  ended_at?: string | null;
+  duration_s: number;
+  path: string;
+  size_bytes: number;
+  codec: string;
+  modified_ms: number;
+  /** Track files of a multitrack call recording. */
+  tracks: string[];
+}
+
+export interface CallRecordingInfo {
+  session_id: string;
+  name?: string | null;
+  layout: "multitrack" | "stereo";
+  codec: string;
+  started_ms: number;
+}
+
+export interface ConversationTurn {
+  entry_id: string;
+  role: "me" | "them" | "file";
+  /** Diarized speaker name when known, else "Me" / "Them" / "File". */
+  speaker: string;
+  text: string;
+  timestamp_ms: number;
+  source: string;
+}
+
+export interface Conversation {
+  session_id?: string | null;
+  name?: string | null;
+  started_ms?: number | null;
+  ended_ms?: number | null;
+  turns: ConversationTurn[];
+}
+
+export interface LlmCleanupTest {
+  input: string;
+  output: string;
+  elapsed_ms: number;
+}
+
+export interface Snippet {
+  id: string;
+  /** Spoken phrase, matched ignoring case and punctuation. */
+  trigger: string;
+  text: string;
+  enabled: boolean;
+}
+
+/** Result of the hallucination phrase list commands. */
+export interface HallucinationPhraseLists {
+  phrases: Record<string, string[]>;
+  allowed: string[];
+}
+
+/** Result of `undo_last_paste` and the `paste:undone` event. */
+export interface UndoPasteResult {
+  chars_removed: number;
+  clipboard_restored: boolean;
+}
+
+/** `transcription:paste-blocked`: the focused app runs elevated; the text was copied instead. */
+export interface PasteBlockedEvent {
+  process_name?: string | null;
+  integrity: string;
+  message: string;
+}
+
+/** Entry of `list_whisper_backends`. */
+export interface WhisperBackendInfo {
+  id: "cuda" | "vulkan" | "cpu";
+  cli_path?: string | null;
+  server_path?: string | null;
+  available: boolean;
+  uses_no_gpu_flag: boolean;
+  issue?: string | null;
+  selected: boolean;
+}
+
+/** Managed whisper.cpp runtime, from `install_runtime` / `check_runtime_update`. */
+export interface InstalledWhisperRuntime {
+  backend: string;
+  release: string;
+  asset: string;
+  sha256: string;
+  path: string;
+}
+
+export interface WhisperRuntimeUpdateStatus {
+  pinned_release: string;
+  latest_release?: string | null;
+  installed: InstalledWhisperRuntime[];
+  update_available: boolean;
+}
+
+/** `whisper:runtime-install-progress` */
+export interface WhisperRuntimeInstallProgress {
+  backend: string;
+  stage: "download" | "verify" | "extract" | "done";
+  downloaded?: number | null;
+  total?: number | null;
+}
+
+export interface WhisperSupervisorStatus {
+  active: boolean;
+  healthy: boolean;
+  restarts: number;
+  consecutive_failures: number;
+  last_error: string | null;
+  next_restart_ms: number | null;
+}
+
+/** `transcribe:adaptation` */
+export interface BacklogAdaptation {
+  level: number;
+  interval_scale: number;
+  fast_decode: boolean;
+  percent_used: number;
+  reason: "overload" | "drained" | "disabled" | "stopped";
+}
+
+/** `capture:max-duration` */
+export interface MaxDurationWarning {
+  source: "ptt" | "vad";
+  segment_ms: number;
+}
+
+/** Result of `calibrate_vad`; progress arrives as `vad:calibration-phase`. */
+export interface VadCalibration {
+  noise_floor: number;
+  noise_peak: number;
+  speech_level: number;
+  threshold_start: number;
+  threshold_sustain: number;
+  silence_ms: number;
+  warning: string | null;
+  applied: boolean;
+}
+
+export interface DeviceInputProfile {
+  vad_threshold_start: number;
+  vad_threshold_sustain: number;
+  vad_silence_ms: number;
+  mic_input_gain_db: number;
+}
+
+/** `probe:level` */
+export interface ProbeLevel {
+  device_id: string;
+  level: number;
+  peak: number;
+  lost: boolean;
+}
+
+export interface AudioCueVolumes {
+  start: number;
+  stop: number;
+  error: number;
+  paste: number;
+}
+
+export interface ActivationWords {
+  enabled: boolean;
+  strip: boolean;
+  words: string[];
+}
+
+/** Result of `test_activation`; `output` is null when the text would be dropped. */
+export interface ActivationTest {
+  enabled: boolean;
+  matched: string | null;
+  output: string | null;
+}
+
+/** Unset (null) fields use the defaults of the selected model. */
+export interface WhisperAdvancedSettings {
+  /** 1-8 */
+  beam_size?: number | null;
+  /** 0-1 */
+  temperature?: number | null;
+  /** 0-1 */
+  no_speech_threshold?: number | null;
+  /** 0-10 */
+  entropy_threshold?: number | null;
+}
+
+export interface WhisperParams {
+  beam_size: number;
+  temperature: number;
+  no_speech_threshold: number;
+  entropy_threshold: number;
+}
+
+/** Result of `get_whisper_params` / `set_whisper_params`. */
+export interface WhisperParamsInfo {
+  model: string;
+  defaults: WhisperParams;
+  overrides: WhisperAdvancedSettings;
+  /** whisper-cli decoding. */
+  effective: WhisperParams;
+  /** whisper-server decoding (greedy unless overridden). */
+  server: WhisperParams;
+}
+
+/** A failed transcription job whose audio is kept (`list_retained_jobs`, `retry_job`). */
+export interface RetainedJobInfo {
+  job_id: string;
+  audio_ms: number;
+  error: string;
+  failed_ms: number;
+}
+
+/** Audio of a segment whose transcription failed, kept for `retry_failed_segment`. */
+export interface FailedSegment {
+  id: string;
+  source: "mic" | "output";
+  /** Id of the failed transcription job, within that app run. */
+  job_id?: string | null;
+  error: string;
+  failed_ms: number;
+  duration_ms: number;
+}
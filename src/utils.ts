@@ -10,6 +10,19 @@ export function escapeHtml(value: string): string {
     .replace(/'/g, "&#39;");
 }
 
+/**
+ * Readable text of a rejected `invoke` call or a thrown error. Commands reject
+ * with a `CommandError` object, which `String()` would turn into "[object Object]".
+ */
+export function errorMessage(error: unknown): string {
+  if (typeof error === "string") return error;
+  if (error instanceof Error) return error.message;
+  if (error && typeof error === "object" && typeof (error as { message?: unknown }).message === "string") {
+    return (error as { message: string }).message;
+  }
+  return String(error);
+}
+
 // ---------------------------------------------------------------------------
 // Accent color theming
 // ---------------------------------------------------------------------------
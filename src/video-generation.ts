@@ -2,6 +2,7 @@ import { invoke, convertFileSrc } from "@tauri-apps/api/core";
 import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import { open as openDialog } from "@tauri-apps/plugin-dialog";
 import { getCurrentWindow } from "@tauri-apps/api/window";
+import { errorMessage } from "./utils";
 
 // ---------------------------------------------------------------------------
 // Types (mirror Rust SourceItem / VideoJobRequest / VideoJobResult)
@@ -172,7 +173,7 @@ async function ingestPaths(paths: string[]): Promise<void> {
     renderQueue();
   } catch (err) {
     console.error("[video-gen] ingest failed:", err);
-    renderProgress("error", 0, `ingest failed: ${err}`);
+    renderProgress("error", 0, `ingest failed: ${errorMessage(err)}`);
   }
 }
 
@@ -186,7 +187,7 @@ async function ingestHistoryEntry(entryId: string): Promise<void> {
     renderQueue();
   } catch (err) {
     console.error("[video-gen] history ingest failed:", err);
-    renderProgress("error", 0, `history ingest failed: ${err}`);
+    renderProgress("error", 0, `history ingest failed: ${errorMessage(err)}`);
   }
 }
 
@@ -273,7 +274,7 @@ async function generateVideo(): Promise<void> {
     renderResult(result);
   } catch (err) {
     console.error("[video-gen] render failed:", err);
-    renderProgress("error", 0, String(err));
+    renderProgress("error", 0, errorMessage(err));
   } finally {
     renderInFlight = false;
     if (btn) btn.disabled = false;
@@ -73,6 +73,7 @@ import {
 } from "../ollama-models";
 import { normalizeModelTag } from "../ollama-tag-utils";
 import { onChangePersist, scheduleSettingsRender } from "./wire-helpers";
+import { errorMessage } from "../utils";
 
 // ── Module-level constants ──────────────────────────────────────────────────
 
@@ -857,7 +858,7 @@ async function verifyProviderCredentials(
     showToast({
       type: "error",
       title: "Verification failed",
-      message: String(error),
+      message: errorMessage(error),
       duration: 5000,
     });
   }
@@ -980,7 +981,7 @@ async function handleSaveCredentialsClick() {
     showToast({
       type: "error",
       title: "API key save failed",
-      message: String(error),
+      message: errorMessage(error),
       duration: 5000,
     });
   }
@@ -1011,7 +1012,7 @@ async function handleClearCredentialsClick() {
     showToast({
       type: "error",
       title: "API key remove failed",
-      message: String(error),
+      message: errorMessage(error),
       duration: 5000,
     });
   }
@@ -1464,7 +1465,7 @@ export function wireAiRefinement(): void {
         dom.aiFallbackCompatStatus.textContent = `${models.length} model(s) found.`;
     } catch (err) {
       if (dom.aiFallbackCompatStatus) {
-        dom.aiFallbackCompatStatus.textContent = `Connection failed: ${err instanceof Error ? err.message : String(err)}`;
+        dom.aiFallbackCompatStatus.textContent = `Connection failed: ${errorMessage(err)}`;
       }
     } finally {
       dom.aiFallbackCompatFetchModels!.disabled = false;
@@ -1494,7 +1495,7 @@ export function wireAiRefinement(): void {
       }
     } catch (err) {
       if (dom.aiFallbackCompatStatus) {
-        dom.aiFallbackCompatStatus.textContent = `✗ ${err instanceof Error ? err.message : String(err)}`;
+        dom.aiFallbackCompatStatus.textContent = `✗ ${errorMessage(err)}`;
       }
     } finally {
       dom.aiFallbackCompatVerifyAction!.disabled = false;
@@ -1518,7 +1519,7 @@ export function wireAiRefinement(): void {
       showToast({
         type: "error",
         title: "Installer failed to launch",
-        message: err instanceof Error ? err.message : String(err),
+        message: errorMessage(err),
         duration: 5000,
       });
     } finally {
@@ -27,6 +27,7 @@ import { showToast } from "../toast";
 import { updateRangeAria } from "../accessibility";
 import { currentHistoryTab, history, transcribeHistory } from "../state";
 import { persistSettings } from "../settings-persist";
+import { errorMessage } from "../utils";
 
 function commitAlias(key: "mic" | "system", input: HTMLInputElement | null): void {
   if (!input) return;
@@ -79,7 +80,7 @@ export function wireHistory(): void {
       showToast({
         type: "error",
         title: "Löschen fehlgeschlagen",
-        message: String(error),
+        message: errorMessage(error),
       });
     }
   });
@@ -2,7 +2,7 @@
 //
 // Owns DOM event listeners for the "Overlay appearance" settings cluster:
 // colour, radius range, rise/fall timing, opacity (active + inactive),
-// position, style (dot/kitt), refining indicator (enable/preset/colour/
+// position, monitor, style (dot/kitt/waveform), refining indicator (enable/preset/colour/
 // speed/range), the optional TTS-stop button, KITT-mode dimensions, and
 // the Apply button.
 //
@@ -16,20 +16,38 @@
 //   - imports dom + helpers directly; role-level peer of event-listeners.ts
 //   - shared snippets live in ./wire-helpers.ts
 
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import * as dom from "../dom-refs";
 import { settings } from "../state";
-import { updateOverlayStyleVisibility, applyOverlaySharedUi } from "../settings/overlay.settings";
+import {
+  updateOverlayStyleVisibility,
+  applyOverlaySharedUi,
+  overlayStyleUsesBar,
+  setOverlayMonitorOptions,
+} from "../settings/overlay.settings";
 import { persistSettings } from "../settings-persist";
 import { updateRangeAria } from "../accessibility";
 import { showToast } from "../toast";
 import { onChangePersist } from "./wire-helpers";
+import type { OverlayMonitorInfo } from "../types";
+import { errorMessage } from "../utils";
+
+async function refreshOverlayMonitors(): Promise<void> {
+  try {
+    const monitors = await invoke<OverlayMonitorInfo[]>("list_overlay_monitors");
+    setOverlayMonitorOptions(Array.isArray(monitors) ? monitors : []);
+  } catch (error) {
+    console.warn("Failed to list monitors for the overlay", error);
+  }
+}
 
 export function wireOverlay(): void {
   // ───────── Core appearance (colour, radius, rise/fall, opacity) ─────────
 
   dom.overlayColor?.addEventListener("input", () => {
     if (!settings || !dom.overlayColor) return;
-    if ((settings.overlay_style || "dot") === "kitt") {
+    if (overlayStyleUsesBar(settings.overlay_style)) {
       settings.overlay_kitt_color = dom.overlayColor.value;
     } else {
       settings.overlay_color = dom.overlayColor.value;
@@ -77,7 +95,7 @@ export function wireOverlay(): void {
   dom.overlayRise?.addEventListener("input", () => {
     if (!settings || !dom.overlayRise) return;
     const value = Number(dom.overlayRise.value);
-    if ((settings.overlay_style || "dot") === "kitt") {
+    if (overlayStyleUsesBar(settings.overlay_style)) {
       settings.overlay_kitt_rise_ms = value;
     } else {
       settings.overlay_rise_ms = value;
@@ -91,7 +109,7 @@ export function wireOverlay(): void {
   dom.overlayFall?.addEventListener("input", () => {
     if (!settings || !dom.overlayFall) return;
     const value = Number(dom.overlayFall.value);
-    if ((settings.overlay_style || "dot") === "kitt") {
+    if (overlayStyleUsesBar(settings.overlay_style)) {
       settings.overlay_kitt_fall_ms = value;
     } else {
       settings.overlay_fall_ms = value;
@@ -105,7 +123,7 @@ export function wireOverlay(): void {
   dom.overlayOpacityInactive?.addEventListener("input", () => {
     if (!settings || !dom.overlayOpacityInactive || !dom.overlayOpacityActive) return;
     const value = Math.min(1, Math.max(0.05, Number(dom.overlayOpacityInactive.value) / 100));
-    if ((settings.overlay_style || "dot") === "kitt") {
+    if (overlayStyleUsesBar(settings.overlay_style)) {
       settings.overlay_kitt_opacity_inactive = value;
       if (settings.overlay_kitt_opacity_active < settings.overlay_kitt_opacity_inactive) {
         settings.overlay_kitt_opacity_active = settings.overlay_kitt_opacity_inactive;
@@ -137,7 +155,7 @@ export function wireOverlay(): void {
 
   dom.overlayOpacityActive?.addEventListener("input", () => {
     if (!settings || !dom.overlayOpacityActive || !dom.overlayOpacityInactive) return;
-    if ((settings.overlay_style || "dot") === "kitt") {
+    if (overlayStyleUsesBar(settings.overlay_style)) {
       const value = Math.min(
         1,
         Math.max(settings.overlay_kitt_opacity_inactive, Number(dom.overlayOpacityActive.value) / 100)
@@ -165,7 +183,7 @@ export function wireOverlay(): void {
 
   dom.overlayPosX?.addEventListener("change", async () => {
     if (!settings || !dom.overlayPosX) return;
-    if ((settings.overlay_style || "dot") === "kitt") {
+    if (overlayStyleUsesBar(settings.overlay_style)) {
       settings.overlay_kitt_pos_x = Number(dom.overlayPosX.value);
     } else {
       settings.overlay_pos_x = Number(dom.overlayPosX.value);
@@ -175,7 +193,7 @@ export function wireOverlay(): void {
 
   dom.overlayPosY?.addEventListener("change", async () => {
     if (!settings || !dom.overlayPosY) return;
-    if ((settings.overlay_style || "dot") === "kitt") {
+    if (overlayStyleUsesBar(settings.overlay_style)) {
       settings.overlay_kitt_pos_y = Number(dom.overlayPosY.value);
     } else {
       settings.overlay_pos_y = Number(dom.overlayPosY.value);
@@ -183,6 +201,18 @@ export function wireOverlay(): void {
     await persistSettings();
   });
 
+  dom.overlayMonitor?.addEventListener("change", async () => {
+    if (!settings || !dom.overlayMonitor) return;
+    settings.overlay_monitor = dom.overlayMonitor.value;
+    await persistSettings();
+  });
+
+  // The backend emits this on docking/undocking; keep the picker current.
+  void refreshOverlayMonitors();
+  void listen("overlay:monitors-changed", () => {
+    void refreshOverlayMonitors();
+  });
+
   dom.overlayStyle?.addEventListener("change", async () => {
     if (!settings || !dom.overlayStyle) return;
     settings.overlay_style = dom.overlayStyle.value;
@@ -288,6 +318,23 @@ export function wireOverlay(): void {
 
   onChangePersist(dom.overlayKittHeight);
 
+  // ───────── Drag-to-move ─────────
+  // The backend saves the dropped position and emits settings-changed,
+  // which refreshes the position fields above.
+
+  dom.overlayMoveBtn?.addEventListener("click", async () => {
+    try {
+      await invoke("overlay_set_move_mode", { enabled: true });
+      showToast({
+        title: "Move overlay",
+        message: "Drag the overlay to its new place; it is saved when you let go.",
+        type: "info",
+      });
+    } catch (error) {
+      showToast({ title: "Move overlay", message: errorMessage(error), type: "error" });
+    }
+  });
+
   // ───────── Apply Overlay Settings button ─────────
 
   dom.applyOverlayBtn?.addEventListener("click", async () => {
@@ -23,6 +23,7 @@ import type {
   TranscriptionRawResultEvent,
   TranscriptSessionCandidate,
 } from "./types";
+import { errorMessage } from "./utils";
 
 let initialized = false;
 let lastParse: AgentCommandParseResult | null = null;
@@ -488,7 +489,7 @@ async function maybeSpeakAgentReply(text: string): Promise<void> {
     });
     armTranscribeAssistantHold("reply_spoken");
   } catch (error) {
-    appendLog(`Voice feedback failed: ${String(error)}`);
+    appendLog(`Voice feedback failed: ${errorMessage(error)}`);
   }
 }
 
@@ -834,7 +835,7 @@ async function composeUnknownReply(commandText: string): Promise<void> {
   } catch (error) {
     latestReplyLine = buildUnknownRuleReply(commandText);
     setGateReason("unknown_reply_fallback", "local_llm_unavailable");
-    appendLog(`Unknown intent fallback reply -> ${String(error)}`);
+    appendLog(`Unknown intent fallback reply -> ${errorMessage(error)}`);
   }
   renderLiveState();
   emitAssistantReplyFinal(latestReplyLine, "unknown_reply:complete", "unknown");
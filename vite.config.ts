@@ -37,6 +37,7 @@ export default defineConfig(async () => ({
         main: resolve(rootDir, "index.html"),
         overlay: resolve(rootDir, "overlay.html"),
         "assistant-presence": resolve(rootDir, "assistant-presence.html"),
+        captions: resolve(rootDir, "captions.html"),
       },
       output: {
         // Ensure all HTML files output with simple names (no paths)